@@ -19,7 +19,6 @@ use crate::error::Error;
 use crate::multiaddr_get_peer_id;
 use ant_protocol::version::ALPHANET_ID;
 use ant_protocol::version::MAINNET_ID;
-use ant_protocol::version::get_network_id;
 use libp2p::{
     Multiaddr, PeerId, Swarm,
     core::connection::ConnectedPoint,
@@ -567,10 +566,12 @@ impl Bootstrap {
         };
 
         let event_tx = self.event_tx.clone();
+        let network_id = self.cache_store.config().network_id();
 
         tokio::spawn(async move {
             let fetch_result = tokio::time::timeout(FETCH_TIMEOUT, async {
-                let fetcher = ContactsFetcher::with_endpoints(vec![endpoint.clone()])?;
+                let mut fetcher = ContactsFetcher::with_endpoints(vec![endpoint.clone()])?;
+                fetcher.set_network_id(network_id);
                 fetcher.fetch_bootstrap_addresses().await
             })
             .await;
@@ -625,7 +626,7 @@ impl Bootstrap {
             return Ok(ContactsProgress::new(endpoints));
         }
 
-        match get_network_id() {
+        match config.network_id() {
             id if id == MAINNET_ID => {
                 info!("Using built-in mainnet contacts endpoints");
                 Ok(ContactsProgress::from_static(MAINNET_CONTACTS))
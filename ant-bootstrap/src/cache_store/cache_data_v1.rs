@@ -253,17 +253,24 @@ impl CacheData {
     }
 }
 
-impl Default for CacheData {
-    fn default() -> Self {
+impl CacheData {
+    /// Create an empty cache for the given network
+    pub fn new(network_id: u8) -> Self {
         Self {
             peers: Default::default(),
             last_updated: SystemTime::now(),
-            network_version: crate::get_network_version(),
+            network_version: crate::network_version(network_id),
             cache_version: Self::CACHE_DATA_VERSION.to_string(),
         }
     }
 }
 
+impl Default for CacheData {
+    fn default() -> Self {
+        Self::new(ant_protocol::version::get_network_id())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
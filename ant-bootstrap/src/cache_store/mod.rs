@@ -10,6 +10,7 @@ pub mod cache_data_v0;
 pub mod cache_data_v1;
 
 use crate::{BootstrapConfig, Error, Result, craft_valid_multiaddr};
+use ant_protocol::version::get_network_id;
 use libp2p::{Multiaddr, PeerId, multiaddr::Protocol};
 use rand::Rng;
 use std::{collections::HashSet, fs, sync::Arc, time::Duration};
@@ -53,8 +54,8 @@ impl BootstrapCacheStore {
         }
 
         let store = Self {
+            data: Arc::new(RwLock::new(CacheDataLatest::new(config.network_id()))),
             config: Arc::new(config),
-            data: Arc::new(RwLock::new(CacheDataLatest::default())),
             to_remove: Arc::new(RwLock::new(HashSet::new())),
         };
 
@@ -105,7 +106,7 @@ impl BootstrapCacheStore {
         // try loading latest first
         match cache_data_v1::CacheData::read_from_file(
             &cfg.cache_dir,
-            &Self::cache_file_name(cfg.local),
+            &Self::cache_file_name_for_network(cfg.local, cfg.network_id()),
         ) {
             Ok(mut data) => {
                 while data.peers.len() > cfg.max_cached_peers {
@@ -121,7 +122,7 @@ impl BootstrapCacheStore {
         // Try loading older version
         match cache_data_v0::CacheData::read_from_file(
             &cfg.cache_dir,
-            &Self::cache_file_name(cfg.local),
+            &Self::cache_file_name_for_network(cfg.local, cfg.network_id()),
         ) {
             Ok(data) => {
                 warn!("Loaded cache data from older version, upgrading to latest version");
@@ -198,7 +199,8 @@ impl BootstrapCacheStore {
             return Ok(());
         }
 
-        let filename = Self::cache_file_name(self.config.local);
+        let filename =
+            Self::cache_file_name_for_network(self.config.local, self.config.network_id());
 
         self.data
             .write()
@@ -216,13 +218,21 @@ impl BootstrapCacheStore {
 
     /// Returns the name of the cache filename based on the local flag
     pub fn cache_file_name(local: bool) -> String {
+        Self::cache_file_name_for_network(local, get_network_id())
+    }
+
+    /// Returns the name of the cache filename of the given network, based on the local flag
+    pub fn cache_file_name_for_network(local: bool, network_id: u8) -> String {
         if local {
             format!(
                 "bootstrap_cache_local_{}.json",
-                crate::get_network_version()
+                crate::network_version(network_id)
             )
         } else {
-            format!("bootstrap_cache_{}.json", crate::get_network_version())
+            format!(
+                "bootstrap_cache_{}.json",
+                crate::network_version(network_id)
+            )
         }
     }
 
@@ -308,6 +318,21 @@ mod tests {
         BootstrapConfig::default().with_cache_dir(dir.path())
     }
 
+    #[test]
+    fn test_cache_file_name_is_per_network() {
+        let mainnet = BootstrapCacheStore::cache_file_name_for_network(false, 1);
+        let alphanet = BootstrapCacheStore::cache_file_name_for_network(false, 2);
+        assert_ne!(mainnet, alphanet);
+        assert!(alphanet.starts_with("bootstrap_cache_2_"));
+        assert!(
+            BootstrapCacheStore::cache_file_name_for_network(true, 2)
+                .starts_with("bootstrap_cache_local_2_")
+        );
+
+        let config = BootstrapConfig::default().with_network_id(2);
+        assert_eq!(config.network_id(), 2);
+    }
+
     #[tokio::test]
     async fn test_empty_cache() {
         let dir = TempDir::new().expect("temp dir");
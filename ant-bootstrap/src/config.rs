@@ -7,6 +7,7 @@
 // permissions and limitations relating to use of the SAFE Network Software.
 
 use crate::error::{Error, Result};
+use ant_protocol::version::get_network_id;
 use clap::Args;
 use libp2p::Multiaddr;
 use serde::{Deserialize, Serialize};
@@ -144,6 +145,9 @@ pub struct BootstrapConfig {
     /// The URL can point to a text file containing Multiaddresses separated by newline character, or
     /// a bootstrap cache JSON file.
     pub network_contacts_url: Vec<String>,
+    /// The id of the network to bootstrap into, selecting the cache file and the built-in network
+    /// contacts. Defaults to the process-wide network id when not set.
+    pub network_id: Option<u8>,
 }
 
 impl Default for BootstrapConfig {
@@ -165,6 +169,7 @@ impl Default for BootstrapConfig {
             min_cache_save_duration: MIN_BOOTSTRAP_CACHE_SAVE_INTERVAL,
             max_cache_save_duration: MAX_BOOTSTRAP_CACHE_SAVE_INTERVAL,
             network_contacts_url: vec![],
+            network_id: None,
         }
     }
 }
@@ -298,6 +303,17 @@ impl BootstrapConfig {
         self.network_contacts_url = urls;
         self
     }
+
+    /// Sets the id of the network to bootstrap into
+    pub fn with_network_id(mut self, network_id: u8) -> Self {
+        self.network_id = Some(network_id);
+        self
+    }
+
+    /// The id of the network to bootstrap into, the process-wide one if not set
+    pub fn network_id(&self) -> u8 {
+        self.network_id.unwrap_or_else(get_network_id)
+    }
 }
 
 /// Returns the default dir that should contain the bootstrap cache file
@@ -9,6 +9,7 @@
 use crate::{
     Error, Result, cache_store::CACHE_DATA_VERSION_LATEST, craft_valid_multiaddr_from_str,
 };
+use ant_protocol::version::get_network_id;
 use futures::stream::{self, StreamExt};
 use libp2p::Multiaddr;
 use reqwest::Client;
@@ -50,6 +51,8 @@ pub struct ContactsFetcher {
     request_client: Client,
     /// Ignore PeerId in the multiaddr if not present. This is only useful for fetching nat detection contacts
     ignore_peer_id: bool,
+    /// The id of the network the contacts must belong to
+    network_id: u8,
 }
 
 impl ContactsFetcher {
@@ -69,6 +72,7 @@ impl ContactsFetcher {
             endpoints,
             request_client,
             ignore_peer_id: false,
+            network_id: get_network_id(),
         })
     }
 
@@ -109,6 +113,11 @@ impl ContactsFetcher {
         self.ignore_peer_id = ignore_peer_id;
     }
 
+    /// Set the id of the network the contacts must belong to. Defaults to the process-wide one.
+    pub fn set_network_id(&mut self, network_id: u8) {
+        self.network_id = network_id;
+    }
+
     /// Fetch the list of bootstrap multiaddrs from all configured endpoints
     pub async fn fetch_bootstrap_addresses(&self) -> Result<Vec<Multiaddr>> {
         info!(
@@ -118,6 +127,7 @@ impl ContactsFetcher {
         );
         let mut bootstrap_addresses = Vec::new();
 
+        let network_version = &crate::network_version(self.network_id);
         let mut fetches = stream::iter(self.endpoints.clone())
             .map(|endpoint| async move {
                 info!(
@@ -129,6 +139,7 @@ impl ContactsFetcher {
                         self.request_client.clone(),
                         &endpoint,
                         self.ignore_peer_id,
+                        network_version,
                     )
                     .await,
                     endpoint,
@@ -179,6 +190,7 @@ impl ContactsFetcher {
         request_client: Client,
         endpoint: &Url,
         ignore_peer_id: bool,
+        network_version: &str,
     ) -> Result<Vec<Multiaddr>> {
         let mut retries = 0;
 
@@ -194,7 +206,7 @@ impl ContactsFetcher {
                     if response.status().is_success() {
                         let text = response.text().await?;
 
-                        match Self::try_parse_response(&text, ignore_peer_id) {
+                        match Self::try_parse_response(&text, ignore_peer_id, network_version) {
                             Ok(addrs) => break addrs,
                             Err(err) => {
                                 warn!("Failed to parse response with err: {err:?}");
@@ -239,7 +251,11 @@ impl ContactsFetcher {
     }
 
     /// Try to parse a response from an endpoint
-    fn try_parse_response(response: &str, ignore_peer_id: bool) -> Result<Vec<Multiaddr>> {
+    fn try_parse_response(
+        response: &str,
+        ignore_peer_id: bool,
+        our_network_version: &str,
+    ) -> Result<Vec<Multiaddr>> {
         let cache_data = if let Ok(data) =
             serde_json::from_str::<super::cache_store::cache_data_v1::CacheData>(response)
         {
@@ -258,8 +274,6 @@ impl ContactsFetcher {
                     "Successfully parsed JSON response with {} peers",
                     cache_data.peers.len()
                 );
-                let our_network_version = crate::get_network_version();
-
                 if cache_data.network_version != our_network_version {
                     warn!(
                         "Network version mismatch. Expected: {our_network_version}, got: {}. Skipping.",
@@ -384,6 +398,39 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_network_contacts_use_configured_network_id() {
+        let mock_server = MockServer::start().await;
+        let peer_id = PeerId::random();
+        let addr: Multiaddr = "/ip4/127.0.0.1/udp/8080/quic-v1/p2p/12D3KooWRBhwfeP2Y4TCx1SM6s9rUoHhR5STiGwxBhgFRcw3UERE"
+            .parse()
+            .unwrap();
+
+        let mut v1 = cache_data_v1::CacheData::new(7);
+        v1.add_peer(peer_id, [addr.clone()].iter(), 10, 10);
+        Mock::given(method("GET"))
+            .and(path("/v1"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string(serde_json::to_string(&v1).unwrap()),
+            )
+            .mount(&mock_server)
+            .await;
+        let url: Url = format!("{}/v1", mock_server.uri()).parse().unwrap();
+
+        let mut fetcher = ContactsFetcher::with_endpoints(vec![url.clone()]).unwrap();
+        fetcher.set_network_id(7);
+        let addrs = fetcher.fetch_bootstrap_addresses().await.unwrap();
+        assert_eq!(addrs, vec![addr]);
+
+        let mut fetcher = ContactsFetcher::with_endpoints(vec![url]).unwrap();
+        fetcher.set_network_id(8);
+        let addrs = fetcher.fetch_bootstrap_addresses().await.unwrap();
+        assert!(
+            addrs.is_empty(),
+            "contacts of another network must be skipped"
+        );
+    }
+
     #[tokio::test]
     async fn test_network_contacts_retries() {
         let mock_server = MockServer::start().await;
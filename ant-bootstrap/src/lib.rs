@@ -118,6 +118,11 @@ pub fn get_network_version() -> String {
     format!("{}_{}", get_network_id_str(), get_truncate_version_str())
 }
 
+/// The network version of the given network, see [`get_network_version`].
+pub fn network_version(network_id: u8) -> String {
+    format!("{network_id}_{}", get_truncate_version_str())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -10,8 +10,12 @@ use crate::exit_code::{
     ExitCodeError, bootstrap_error_exit_code, connect_error_exit_code, evm_util_error_exit_code,
 };
 use crate::opt::{ALPHA_NETWORK_ID, LOCAL_NETWORK_ID, MAIN_NETWORK_ID, NetworkId};
+use autonomi::client::ConnectError;
 use autonomi::client::config::ClientOperatingStrategy;
-use autonomi::{BootstrapConfig, Client, ClientConfig, InitialPeersConfig, get_evm_network};
+use autonomi::networking::{MismatchReason, ProtocolMismatch};
+use autonomi::{
+    BootstrapConfig, Client, ClientConfig, ClientNetwork, InitialPeersConfig, get_evm_network,
+};
 use color_eyre::eyre::eyre;
 use indicatif::ProgressBar;
 use std::time::Duration;
@@ -22,12 +26,24 @@ pub struct NetworkContext {
     pub peers: InitialPeersConfig,
     /// The network ID
     pub network_id: NetworkId,
+    /// Whether to also dial peers over TCP
+    pub tcp_fallback: bool,
 }
 
 impl NetworkContext {
     /// Creates a new NetworkContext with the specified peer configuration and network ID
     pub fn new(peers: InitialPeersConfig, network_id: NetworkId) -> Self {
-        Self { peers, network_id }
+        Self {
+            peers,
+            network_id,
+            tcp_fallback: false,
+        }
+    }
+
+    /// Also dial peers over TCP when `tcp_fallback` is set, for networks where UDP is blocked
+    pub fn with_tcp_fallback(mut self, tcp_fallback: bool) -> Self {
+        self.tcp_fallback = tcp_fallback;
+        self
     }
 }
 
@@ -47,24 +63,37 @@ pub async fn connect_to_network_with_config(
     let new_style = progress_bar.style().tick_chars("⠁⠂⠄⡀⢀⠠⠐⠈🔗");
     progress_bar.set_style(new_style);
 
+    let tcp_fallback = network_context.tcp_fallback;
+    if tcp_fallback {
+        info!("Dialing peers over TCP as well as QUIC");
+    }
+
     let res = match network_context.network_id.as_u8() {
         LOCAL_NETWORK_ID => {
-            println!("Connecting to a local Autonomi network...");
+            eprintln!("Connecting to a local Autonomi network...");
             progress_bar.set_message("Connecting to a local Autonomi network...");
-            Client::init_local().await
+            Client::builder()
+                .network(ClientNetwork::Local)
+                .tcp_fallback(tcp_fallback)
+                .build()
+                .await
         }
         MAIN_NETWORK_ID => {
-            println!("Connecting to the Autonomi network...");
+            eprintln!("Connecting to the Autonomi network...");
             progress_bar.set_message("Connecting to the Autonomi network...");
-            Client::init().await
+            Client::builder().tcp_fallback(tcp_fallback).build().await
         }
         ALPHA_NETWORK_ID => {
-            println!("Connecting to the alpha Autonomi network...");
+            eprintln!("Connecting to the alpha Autonomi network...");
             progress_bar.set_message("Connecting to the alpha Autonomi network...");
-            Client::init_alpha().await
+            Client::builder()
+                .network(ClientNetwork::Alpha)
+                .tcp_fallback(tcp_fallback)
+                .build()
+                .await
         }
         _ => {
-            println!("Connecting to a custom Autonomi network...");
+            eprintln!("Connecting to a custom Autonomi network...");
             progress_bar.set_message("Connecting to a custom Autonomi network...");
             let evm_network = get_evm_network(
                 network_context.peers.local,
@@ -86,6 +115,8 @@ pub async fn connect_to_network_with_config(
                 evm_network,
                 strategy: operating_strategy.clone(),
                 network_id: Some(network_context.network_id.as_u8()),
+                tcp_fallback,
+                connection_budget: Default::default(),
             };
 
             Client::init_with_config(config).await
@@ -94,15 +125,18 @@ pub async fn connect_to_network_with_config(
 
     match res {
         Ok(client) => {
-            println!("Connected to the network");
+            eprintln!("Connected to the network");
             info!("Connected to the network");
             progress_bar.finish_with_message("Connected to the network".to_string());
             let client = client.with_strategy(operating_strategy);
             Ok(client)
         }
         Err(e) => {
-            println!("Failed to connect to the network: {e}");
+            eprintln!("Failed to connect to the network: {e}");
             error!("Failed to connect to the network: {e}");
+            if let ConnectError::ProtocolMismatch(mismatch) = &e {
+                eprintln!("{}", protocol_mismatch_advice(mismatch));
+            }
             progress_bar.finish_with_message("Failed to connect to the network".to_string());
             let exit_code = connect_error_exit_code(&e);
             Err((
@@ -112,3 +146,24 @@ pub async fn connect_to_network_with_config(
         }
     }
 }
+
+/// What the user can do about the peers not talking our protocol.
+fn protocol_mismatch_advice(mismatch: &ProtocolMismatch) -> String {
+    match mismatch.reason() {
+        MismatchReason::DifferentNetwork { ours, theirs } => format!(
+            "The peers belong to network {theirs}, but this client connects to network {ours}. \
+             Use `--network-id {theirs}` to connect to their network, or check the peers you \
+             bootstrap from."
+        ),
+        MismatchReason::OutdatedPeer => "The peers run an older version of the network protocol. \
+             Check that you bootstrap from peers of the intended network."
+            .to_string(),
+        MismatchReason::OutdatedSelf => "The peers run a newer version of the network protocol. \
+             Upgrade this client."
+            .to_string(),
+        MismatchReason::Unknown => {
+            "The peers do not run the Autonomi network protocol. Check the peers you bootstrap from."
+                .to_string()
+        }
+    }
+}
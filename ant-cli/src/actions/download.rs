@@ -13,12 +13,20 @@ use autonomi::{
     chunk::DataMapChunk,
     client::{GetError, analyze::Analysis, files::archive_private::PrivateArchiveDataMap},
     data::DataAddress,
-    files::{PrivateArchive, PublicArchive},
+    files::{PackCache, PrivateArchive, PublicArchive},
 };
 use color_eyre::{Section, eyre::eyre};
+use std::io::Write;
 use std::path::PathBuf;
 
+/// Path standing for stdin as a source, or stdout as a destination.
+pub const STDIO_PATH: &str = "-";
+
 pub async fn download(addr: &str, dest_path: &str, client: &Client) -> Result<(), ExitCodeError> {
+    if dest_path == STDIO_PATH {
+        return download_to_stdout(addr, client).await;
+    }
+
     let try_public_address = DataAddress::from_hex(addr).ok();
     if let Some(public_address) = try_public_address {
         println!("Input supplied was a public address");
@@ -78,7 +86,8 @@ async fn download_priv_archive_to_disk(
     let progress_bar = get_progress_bar(archive.iter().count() as u64).ok();
     let mut all_errs = vec![];
     let mut last_error = None;
-    for (path, access, _meta) in archive.iter() {
+    let mut packs = PackCache::default();
+    for (path, access, meta) in archive.iter() {
         if let Some(progress_bar) = &progress_bar {
             progress_bar.println(format!("Fetching file: {path:?}..."));
         }
@@ -88,7 +97,10 @@ async fn download_priv_archive_to_disk(
         let parent = path.parent().unwrap_or_else(|| &here);
         std::fs::create_dir_all(parent).map_err(|err| (err.into(), IO_ERROR))?;
 
-        if let Err(e) = client.file_download(access, path.clone()).await {
+        if let Err(e) = client
+            .archive_file_download_cached(access, meta, path.clone(), &mut packs)
+            .await
+        {
             let err = format!("Failed to fetch file {path:?}: {e}");
             all_errs.push(err);
             last_error = Some(e);
@@ -189,7 +201,8 @@ async fn download_pub_archive_to_disk(
     let progress_bar = get_progress_bar(archive.iter().count() as u64).ok();
     let mut all_errs = vec![];
     let mut last_error = None;
-    for (path, addr, _meta) in archive.iter() {
+    let mut packs = PackCache::default();
+    for (path, addr, meta) in archive.iter() {
         if let Some(progress_bar) = &progress_bar {
             progress_bar.println(format!("Fetching file: {path:?}..."));
         }
@@ -199,7 +212,10 @@ async fn download_pub_archive_to_disk(
         let parent = path.parent().unwrap_or_else(|| &here);
         std::fs::create_dir_all(parent).map_err(|err| (err.into(), IO_ERROR))?;
 
-        if let Err(e) = client.file_download_public(addr, path.clone()).await {
+        if let Err(e) = client
+            .archive_file_download_public_cached(addr, meta, path.clone(), &mut packs)
+            .await
+        {
             let err = format!("Failed to fetch file {path:?}: {e}");
             all_errs.push(err);
             last_error = Some(e);
@@ -288,3 +304,55 @@ async fn download_from_datamap(
         }
     }
 }
+
+/// Stream the content of a single file to stdout, writing each chunk as soon as it is decrypted.
+/// Messages go to stderr, so that stdout only carries the data.
+async fn download_to_stdout(addr: &str, client: &Client) -> Result<(), ExitCodeError> {
+    let stream = if let Ok(public_address) = DataAddress::from_hex(addr) {
+        client.data_stream_public(&public_address).await
+    } else if crate::user_data::get_local_private_archive_access(addr).is_ok() {
+        return Err((
+            eyre!("Cannot write the archive at {addr} to stdout")
+                .with_suggestion(|| "Download archives to a directory instead"),
+            INVALID_INPUT_EXIT_CODE,
+        ));
+    } else if let Some(datamap) = crate::user_data::get_local_private_file_access(addr)
+        .ok()
+        .or_else(|| DataMapChunk::from_hex(addr).ok())
+    {
+        client.data_stream(&datamap).await
+    } else {
+        return Err((
+            eyre!("Failed to parse data address {addr}")
+                .with_suggestion(|| "Only single files can be written to stdout"),
+            INVALID_INPUT_EXIT_CODE,
+        ));
+    };
+    let stream = stream.map_err(|e| {
+        let exit_code = exit_code::get_error_exit_code(&e);
+        (
+            eyre!(e).wrap_err("Failed to fetch data from address"),
+            exit_code,
+        )
+    })?;
+
+    info!(
+        "Streaming {} bytes from {addr} to stdout",
+        stream.data_size()
+    );
+    let mut stdout = std::io::stdout().lock();
+    for chunk in stream {
+        let chunk = chunk.map_err(|e| {
+            let exit_code = exit_code::get_error_exit_code(&e);
+            (eyre!(e).wrap_err("Failed to fetch data chunk"), exit_code)
+        })?;
+        stdout
+            .write_all(&chunk)
+            .map_err(|err| (err.into(), IO_ERROR))?;
+    }
+    stdout.flush().map_err(|err| (err.into(), IO_ERROR))?;
+
+    info!("Successfully streamed data at {addr} to stdout");
+    eprintln!("Successfully downloaded data at: {addr}");
+    Ok(())
+}
@@ -11,5 +11,5 @@ mod download;
 mod progress_bar;
 
 pub use connect::{NetworkContext, connect_to_network, connect_to_network_with_config};
-pub use download::download;
+pub use download::{STDIO_PATH, download};
 pub use progress_bar::get_progress_bar;
@@ -7,9 +7,12 @@
 // permissions and limitations relating to use of the SAFE Network Software.
 
 mod analyze;
+mod browse;
 #[cfg(feature = "developer")]
 mod developer;
+mod evm;
 mod file;
+mod network;
 mod pointer;
 mod register;
 mod scratchpad;
@@ -21,7 +24,7 @@ use crate::args::max_fee_per_gas::MaxFeePerGasParam;
 use crate::opt::{NetworkId, Opt};
 use autonomi::networking::Quorum;
 use clap::{Args, CommandFactory as _, Subcommand, error::ErrorKind};
-use color_eyre::Result;
+use color_eyre::{Result, eyre::eyre};
 use pointer::TargetDataType;
 use pointer::parse_target_data_type;
 use std::num::NonZeroUsize;
@@ -45,6 +48,17 @@ pub struct PaymentFlags {
     /// By default, regular payments are used for < 64 chunks. Mutually exclusive with --merkle.
     #[arg(long, conflicts_with = "merkle")]
     pub regular: bool,
+    /// Pay this many (3 to 5) of the nodes storing each chunk, each at its own price, instead of a
+    /// single node. More nodes then hold the chunk with a payment backing their responsibility, at
+    /// the cost of more gas fees. Nodes only accept payments spread over at least the 3 most
+    /// expensive of them. Note: This only applies to regular payments, not merkle payments.
+    #[arg(
+        long,
+        value_name = "COUNT",
+        value_parser = clap::value_parser!(u64).range(3..=5),
+        conflicts_with_all = ["merkle", "disable_single_node_payment"]
+    )]
+    pub payees: Option<u64>,
 }
 
 #[derive(Subcommand, Debug)]
@@ -85,11 +99,24 @@ pub enum SubCmd {
         command: WalletCmd,
     },
 
+    /// Operations related to the EVM network used for payments.
+    Evm {
+        #[command(subcommand)]
+        command: EvmCmd,
+    },
+
     /// Operations related to data analysis.
-    #[command(alias = "analyse")]
+    #[command(
+        alias = "analyse",
+        args_conflicts_with_subcommands = true,
+        subcommand_negates_reqs = true
+    )]
     Analyze {
+        #[command(subcommand)]
+        command: Option<AnalyzeCmd>,
         /// The address of the data to analyse.
-        addr: String,
+        #[arg(required = true)]
+        addr: Option<String>,
         /// Show closest nodes to this address instead of analyzing it.
         #[arg(long)]
         closest_nodes: bool,
@@ -121,6 +148,12 @@ pub enum SubCmd {
         json: Option<PathBuf>,
     },
 
+    /// Operations related to the health of the Network.
+    Network {
+        #[command(subcommand)]
+        command: NetworkCmd,
+    },
+
     /// Developer and analytics tools for network diagnostics.
     /// Requires the `developer` feature to be enabled on both client and node.
     #[cfg(feature = "developer")]
@@ -130,6 +163,77 @@ pub enum SubCmd {
     },
 }
 
+#[derive(Subcommand, Debug)]
+pub enum AnalyzeCmd {
+    /// Sample store costs across random addresses and show the price distribution.
+    /// Nothing is uploaded or paid for.
+    Prices {
+        /// Number of random addresses to sample.
+        #[arg(short = 'n', long, default_value = "20")]
+        samples: usize,
+    },
+    /// Inspect a hex encoded DataMap: format, chunk count, total size and chunk addresses.
+    /// Nothing is fetched from the Network.
+    Datamap {
+        /// The hex encoded DataMap, as returned by private uploads.
+        hex: String,
+    },
+    /// Walk the graph entries linked from a root entry and export the structure for
+    /// visualization, e.g. with Graphviz: `ant analyze graph <root> | dot -Tsvg > graph.svg`.
+    /// Links closing a cycle are flagged.
+    Graph {
+        /// The address of the root graph entry.
+        root: String,
+        /// The output format: `dot` (Graphviz) or `json`.
+        #[arg(long, value_parser = analyze::parse_graph_format, default_value = "dot")]
+        format: analyze::GraphFormat,
+        /// The maximum number of links to follow from the root.
+        #[arg(long, default_value_t = analyze::DEFAULT_GRAPH_DEPTH)]
+        max_depth: usize,
+        /// Write the graph to this file instead of stdout.
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Fetch the raw record at an address, decode its header and payload, and show the peers
+    /// holding it. A public key is looked up as a pointer, scratchpad, graph entry and register.
+    Record {
+        /// The address of the record.
+        addr: String,
+        /// Verbose output. Show the progress of the lookups.
+        #[arg(short, long)]
+        verbose: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum EvmCmd {
+    /// Fund a wallet with tokens and gas from the faucet of the local EVM testnet.
+    /// The faucet is the default Anvil account, which holds the tokens of a fresh testnet.
+    Faucet {
+        /// The address of the wallet to fund.
+        #[arg(long)]
+        to: String,
+        /// The amount of tokens to send, e.g. `100` or `0.5`.
+        #[arg(long, default_value = "100")]
+        amount: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum NetworkCmd {
+    /// Probe the health of the Network as seen from this machine: connectivity, routing table,
+    /// GET latency and store quote success. Nothing is uploaded or paid for.
+    Health {
+        /// Address of a record known to be on the Network, to measure the GET latency with.
+        /// Can be given multiple times.
+        #[arg(long = "known-address", value_name = "ADDRESS")]
+        known_addresses: Vec<String>,
+        /// Number of GETs and store quotes to make.
+        #[arg(short = 'n', long, default_value_t = autonomi::client::health::DEFAULT_HEALTH_PROBES)]
+        probes: usize,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 pub enum FileCmd {
     /// Estimate cost to upload a file.
@@ -150,7 +254,7 @@ pub enum FileCmd {
 
     /// Upload a file and pay for it. Data on the Network is private by default.
     Upload {
-        /// The file to upload.
+        /// The file to upload. Use `-` to read up to 1 GiB of data from stdin.
         file: String,
         /// Upload the file as public. Everyone can see public data on the Network.
         #[arg(short, long)]
@@ -166,6 +270,11 @@ pub enum FileCmd {
         #[arg(long)]
         #[clap(default_value = "0")]
         retry_failed: u64,
+        /// Compress files with zstd at this level (1 to 22) before encrypting them, which cuts the
+        /// storage cost of text-heavy data. Downloads decompress the files automatically.
+        /// Note: Only files small enough to be encrypted in memory are compressed.
+        #[arg(long, value_name = "LEVEL", value_parser = clap::value_parser!(i32).range(1..=22))]
+        compress: Option<i32>,
         #[command(flatten)]
         payment_flags: PaymentFlags,
         #[command(flatten)]
@@ -176,7 +285,7 @@ pub enum FileCmd {
     Download {
         /// The address of the file to download.
         addr: String,
-        /// The destination file path.
+        /// The destination file path. Use `-` to write the data of a single file to stdout.
         dest_file: String,
         /// Experimental: Optionally specify the quorum for the download (makes sure that we have n copies for each chunk).
         ///
@@ -195,6 +304,46 @@ pub enum FileCmd {
         /// This option only applies when cache is enabled (default).
         #[arg(long, conflicts_with = "disable_cache")]
         cache_dir: Option<PathBuf>,
+        /// Write the verification manifest of a downloaded public archive to this file: the
+        /// path, size, BLAKE3 checksum and chunk addresses of every downloaded file. The checksums
+        /// are computed over the data fetched from the Network.
+        #[arg(long)]
+        manifest: Option<PathBuf>,
+    },
+
+    /// Browse the content of an archive interactively.
+    ///
+    /// Navigate the directories of a public archive, or of a private archive with a local
+    /// address, preview its small text files and select the entries to download.
+    Browse {
+        /// The address of the public archive, or the local address of the private archive.
+        addr: String,
+        /// The directory to download the selected entries to.
+        #[arg(long, default_value = ".")]
+        dest: PathBuf,
+    },
+
+    /// Verify the files of a download directory against the manifest written at download time.
+    VerifyDownload {
+        /// The manifest written by `file download --manifest`.
+        manifest: PathBuf,
+        /// The directory the archive was downloaded to.
+        dir: PathBuf,
+    },
+
+    /// Repair data weakly held by the Network.
+    ///
+    /// The records of a public archive, of public data or a single record, held by fewer peers of
+    /// their close group than the minimum, are uploaded again. The uploads are paid for when the
+    /// Network asks for it.
+    Repair {
+        /// The address of the public archive, public data or record to repair.
+        addr: String,
+        /// The minimum number of peers that must hold a record, below which it is uploaded again.
+        #[arg(long, default_value_t = autonomi::client::repair::DEFAULT_MIN_HOLDERS)]
+        min_holders: usize,
+        #[command(flatten)]
+        transaction_opt: TransactionOpt,
     },
 
     /// List previous uploads
@@ -282,6 +431,42 @@ pub enum RegisterCmd {
         hex: bool,
     },
 
+    /// Inspect the history of a register, to find out if it was forked by concurrent updates.
+    Inspect {
+        /// Use the name of the register instead of the address
+        /// Note that only the owner of the register can use this shorthand as the address can be generated from the name and register key.
+        #[arg(short, long)]
+        name: bool,
+        /// The address of the register
+        /// With the name option on the address will be used as a name
+        address: String,
+        /// Display the divergent branches of the history and their heads
+        #[arg(long)]
+        show_branches: bool,
+        /// Display the values as hex strings instead of raw bytes
+        #[arg(long)]
+        hex: bool,
+    },
+
+    /// Merge the branches of a forked register with a new entry.
+    /// Without a value, the open branches are listed to pick the value to keep or enter a new one.
+    Merge {
+        /// Use the name of the register instead of the address
+        /// Note that only the owner of the register can use this shorthand as the address can be generated from the name and register key.
+        #[arg(short, long)]
+        name: bool,
+        /// The address of the register
+        /// With the name option on the address will be used as a name
+        address: String,
+        /// The value of the merge entry.
+        value: Option<String>,
+        /// Treat the value as a hex string and convert it to binary before storing
+        #[arg(long)]
+        hex: bool,
+        #[command(flatten)]
+        transaction_opt: TransactionOpt,
+    },
+
     /// List previous registers
     List,
 }
@@ -578,7 +763,8 @@ pub async fn handle_subcommand(opt: Opt) -> Result<()> {
         NetworkContext::new(opt.peers, NetworkId::alpha())
     } else {
         NetworkContext::new(opt.peers, opt.network_id)
-    };
+    }
+    .with_tcp_fallback(opt.tcp_fallback);
 
     match cmd {
         Some(SubCmd::File { command }) => match command {
@@ -593,6 +779,7 @@ pub async fn handle_subcommand(opt: Opt) -> Result<()> {
                 public,
                 no_archive,
                 retry_failed,
+                compress,
                 payment_flags,
                 transaction_opt,
             } => {
@@ -603,6 +790,7 @@ pub async fn handle_subcommand(opt: Opt) -> Result<()> {
                     network_context,
                     transaction_opt.max_fee_per_gas,
                     retry_failed,
+                    compress,
                     payment_flags,
                 )
                 .await
@@ -620,6 +808,7 @@ pub async fn handle_subcommand(opt: Opt) -> Result<()> {
                 retries,
                 disable_cache,
                 cache_dir,
+                manifest,
             } => {
                 if let Err((err, exit_code)) = file::download(
                     &addr,
@@ -629,13 +818,14 @@ pub async fn handle_subcommand(opt: Opt) -> Result<()> {
                     retries,
                     !disable_cache, // Invert the flag - cache is enabled by default
                     cache_dir.as_ref(),
+                    manifest.as_ref(),
                 )
                 .await
                 {
                     eprintln!("{err:?}");
                     if !disable_cache {
-                        println!("Successfully downloaded chunks were cached.");
-                        println!(
+                        eprintln!("Successfully downloaded chunks were cached.");
+                        eprintln!(
                             "Please run the command again to obtain the chunks that were not retrieved and complete the download."
                         );
                     }
@@ -644,6 +834,21 @@ pub async fn handle_subcommand(opt: Opt) -> Result<()> {
                     Ok(())
                 }
             }
+            FileCmd::Browse { addr, dest } => browse::browse(&addr, &dest, network_context).await,
+            FileCmd::VerifyDownload { manifest, dir } => file::verify_download(&manifest, &dir),
+            FileCmd::Repair {
+                addr,
+                min_holders,
+                transaction_opt,
+            } => {
+                file::repair(
+                    &addr,
+                    min_holders,
+                    network_context,
+                    transaction_opt.max_fee_per_gas,
+                )
+                .await
+            }
             FileCmd::List { verbose } => {
                 if let Err((err, exit_code)) = file::list(network_context, verbose).await {
                     eprintln!("{err:?}");
@@ -694,6 +899,29 @@ pub async fn handle_subcommand(opt: Opt) -> Result<()> {
             RegisterCmd::History { address, name, hex } => {
                 register::history(address, name, hex, network_context).await
             }
+            RegisterCmd::Inspect {
+                address,
+                name,
+                show_branches,
+                hex,
+            } => register::inspect(address, name, show_branches, hex, network_context).await,
+            RegisterCmd::Merge {
+                address,
+                name,
+                value,
+                hex,
+                transaction_opt,
+            } => {
+                register::merge(
+                    address,
+                    name,
+                    value,
+                    hex,
+                    network_context,
+                    transaction_opt.max_fee_per_gas,
+                )
+                .await
+            }
             RegisterCmd::List => register::list(),
         },
         Some(SubCmd::Vault { command }) => match command {
@@ -773,7 +1001,33 @@ pub async fn handle_subcommand(opt: Opt) -> Result<()> {
             WalletCmd::Export => wallet::export(),
             WalletCmd::Balance => wallet::balance(network_context).await,
         },
+        Some(SubCmd::Evm {
+            command: EvmCmd::Faucet { to, amount },
+        }) => evm::faucet(to, amount, network_context).await,
+        Some(SubCmd::Analyze {
+            command: Some(AnalyzeCmd::Prices { samples }),
+            ..
+        }) => analyze::prices(samples, network_context).await,
+        Some(SubCmd::Analyze {
+            command: Some(AnalyzeCmd::Datamap { hex }),
+            ..
+        }) => analyze::datamap(&hex),
+        Some(SubCmd::Analyze {
+            command:
+                Some(AnalyzeCmd::Graph {
+                    root,
+                    format,
+                    max_depth,
+                    output,
+                }),
+            ..
+        }) => analyze::graph(&root, format, max_depth, output, network_context).await,
+        Some(SubCmd::Analyze {
+            command: Some(AnalyzeCmd::Record { addr, verbose }),
+            ..
+        }) => analyze::record(&addr, verbose, network_context).await,
         Some(SubCmd::Analyze {
+            command: None,
             addr,
             closest_nodes,
             holders,
@@ -784,6 +1038,7 @@ pub async fn handle_subcommand(opt: Opt) -> Result<()> {
             recursive,
             json,
         }) => {
+            let addr = addr.ok_or_else(|| eyre!("Please provide an address to analyse"))?;
             analyze::analyze(
                 &addr,
                 closest_nodes,
@@ -798,6 +1053,13 @@ pub async fn handle_subcommand(opt: Opt) -> Result<()> {
             )
             .await
         }
+        Some(SubCmd::Network {
+            command:
+                NetworkCmd::Health {
+                    known_addresses,
+                    probes,
+                },
+        }) => network::health(known_addresses, probes, network_context).await,
         #[cfg(feature = "developer")]
         Some(SubCmd::Developer { command }) => match command {
             DeveloperCmd::ClosestPeers {
@@ -0,0 +1,45 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use autonomi::client::datamap::{self, DataMapFormat};
+use autonomi::data::DataMapChunk;
+use color_eyre::eyre::{Result, eyre};
+use comfy_table::Table;
+
+/// Inspect a hex encoded DataMap and print its description.
+pub fn datamap(hex: &str) -> Result<()> {
+    let data_map_chunk = DataMapChunk::from_hex(hex.trim())
+        .map_err(|err| eyre!("Invalid hex encoded DataMap: {err}"))?;
+    let info = datamap::inspect(&data_map_chunk)?;
+
+    let format = match info.format {
+        DataMapFormat::Current => "current",
+        DataMapFormat::Legacy => "legacy (can be re-packed)",
+    };
+
+    let mut table = Table::new();
+    table.set_header(vec!["Property", "Value"]);
+    table.add_row(vec!["Format".to_string(), format.to_string()]);
+    table.add_row(vec!["Chunks".to_string(), info.chunk_count.to_string()]);
+    table.add_row(vec![
+        "Total size (bytes)".to_string(),
+        info.total_size.to_string(),
+    ]);
+    table.add_row(vec![
+        "Content is another DataMap".to_string(),
+        info.points_to_a_data_map().to_string(),
+    ]);
+    println!("{table}");
+
+    println!("Chunk addresses:");
+    for address in &info.chunk_addresses {
+        println!("{}", address.to_hex());
+    }
+
+    Ok(())
+}
@@ -69,6 +69,7 @@ pub enum NetworkErrorDisplay {
     PutRecordTimeout,
     PutRecordRejected,
     OutdatedRecordRejected,
+    FreeUpdateRateLimited,
     GetQuoteError,
     InvalidQuote,
     InsufficientQuotes,
@@ -77,6 +78,7 @@ pub enum NetworkErrorDisplay {
     GetRecordQuorumFailed,
     GetRecordError,
     GetVersionError,
+    WriteNotObserved,
     InvalidRetryStrategy,
     InvalidNodeMerkleCandidate,
     TopologyVerificationFailed,
@@ -101,6 +103,7 @@ impl NetworkErrorDisplay {
             NetworkError::PutRecordTimeout(_) => Self::PutRecordTimeout,
             NetworkError::PutRecordRejected(_) => Self::PutRecordRejected,
             NetworkError::OutdatedRecordRejected { .. } => Self::OutdatedRecordRejected,
+            NetworkError::FreeUpdateRateLimited { .. } => Self::FreeUpdateRateLimited,
             NetworkError::GetQuoteError(_) => Self::GetQuoteError,
             NetworkError::InvalidQuote(_) => Self::InvalidQuote,
             NetworkError::InsufficientQuotes { .. } => Self::InsufficientQuotes,
@@ -109,6 +112,7 @@ impl NetworkErrorDisplay {
             NetworkError::GetRecordQuorumFailed { .. } => Self::GetRecordQuorumFailed,
             NetworkError::GetRecordError(_) => Self::GetRecordError,
             NetworkError::GetVersionError(_) => Self::GetVersionError,
+            NetworkError::WriteNotObserved { .. } => Self::WriteNotObserved,
             NetworkError::InvalidRetryStrategy => Self::InvalidRetryStrategy,
             NetworkError::InvalidNodeMerkleCandidate(_) => Self::InvalidNodeMerkleCandidate,
             NetworkError::TopologyVerificationFailed { .. } => Self::TopologyVerificationFailed,
@@ -0,0 +1,318 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use crate::actions::NetworkContext;
+use autonomi::graph::{GraphEntry, GraphEntryAddress};
+use color_eyre::eyre::{Context, Result, eyre};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::path::PathBuf;
+
+/// Default depth of the walk of the descendants of the root graph entry.
+pub const DEFAULT_GRAPH_DEPTH: usize = 16;
+
+/// Format of the exported graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    /// Graphviz DOT
+    Dot,
+    /// JSON list of nodes and edges
+    Json,
+}
+
+pub fn parse_graph_format(s: &str) -> Result<GraphFormat> {
+    match s {
+        "dot" => Ok(GraphFormat::Dot),
+        "json" => Ok(GraphFormat::Json),
+        _ => Err(eyre!("Invalid graph format: {s}, expected `dot` or `json`")),
+    }
+}
+
+/// State of a graph entry reached by the walk.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum NodeStatus {
+    /// Fetched, and its descendants walked
+    Fetched,
+    /// Fetched, but at the depth limit so its descendants were not walked
+    Truncated,
+    /// Could not be fetched from the Network
+    Missing,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct GraphNode {
+    address: String,
+    depth: usize,
+    status: NodeStatus,
+    /// Hex content of the entry, if fetched
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content: Option<String>,
+    /// Addresses of the parents the entry refers to
+    parents: Vec<String>,
+}
+
+/// A link from a graph entry to one of its descendants.
+#[derive(Debug, Clone, Serialize)]
+struct GraphEdge {
+    from: String,
+    to: String,
+    /// Hex content attached to the link
+    content: String,
+    /// Whether the link closes a cycle back to one of its ancestors
+    cycle: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct GraphExport {
+    root: String,
+    max_depth: usize,
+    nodes: Vec<GraphNode>,
+    edges: Vec<GraphEdge>,
+}
+
+/// Walk the graph entries from `root` and export the structure, to a file or stdout.
+pub async fn graph(
+    root: &str,
+    format: GraphFormat,
+    max_depth: usize,
+    output: Option<PathBuf>,
+    network_context: NetworkContext,
+) -> Result<()> {
+    let root = GraphEntryAddress::from_hex(root)
+        .map_err(|err| eyre!("Invalid graph entry address {root}: {err}"))?;
+    let client = crate::actions::connect_to_network(network_context)
+        .await
+        .map_err(|(err, _)| err)?;
+
+    eprintln!(
+        "Walking the graph from {} (max depth {max_depth})...",
+        root.to_hex()
+    );
+    let export = walk_graph(root, max_depth, |addr| {
+        let client = client.clone();
+        async move {
+            client
+                .graph_entry_get(&addr)
+                .await
+                .map_err(|err| err.to_string())
+        }
+    })
+    .await;
+
+    let cycles = export.edges.iter().filter(|edge| edge.cycle).count();
+    let missing = export
+        .nodes
+        .iter()
+        .filter(|node| node.status == NodeStatus::Missing)
+        .count();
+    eprintln!(
+        "Found {} entries and {} links, with {cycles} cycles and {missing} missing entries",
+        export.nodes.len(),
+        export.edges.len()
+    );
+
+    let rendered = match format {
+        GraphFormat::Dot => to_dot(&export),
+        GraphFormat::Json => serde_json::to_string_pretty(&export)?,
+    };
+    match output {
+        Some(path) => {
+            std::fs::write(&path, rendered)
+                .wrap_err(format!("Failed to write the graph to {path:?}"))?;
+            eprintln!("Graph written to {path:?}");
+        }
+        None => println!("{rendered}"),
+    }
+    Ok(())
+}
+
+/// Walk the descendants of `root` breadth first, down to `max_depth` links away, then flag the
+/// links closing a cycle.
+async fn walk_graph<F, Fut>(root: GraphEntryAddress, max_depth: usize, fetch: F) -> GraphExport
+where
+    F: Fn(GraphEntryAddress) -> Fut,
+    Fut: Future<Output = Result<GraphEntry, String>>,
+{
+    let mut nodes = vec![];
+    let mut edges = vec![];
+    let mut seen = HashSet::from([root.to_hex()]);
+    let mut layer = vec![root];
+
+    for depth in 0..=max_depth {
+        if layer.is_empty() {
+            break;
+        }
+        let fetched = futures::future::join_all(layer.iter().map(|addr| fetch(*addr))).await;
+        let mut next_layer = vec![];
+        for (addr, result) in layer.into_iter().zip(fetched) {
+            let entry = match result {
+                Ok(entry) => entry,
+                Err(err) => {
+                    warn!("Failed to fetch graph entry {}: {err}", addr.to_hex());
+                    nodes.push(GraphNode {
+                        address: addr.to_hex(),
+                        depth,
+                        status: NodeStatus::Missing,
+                        content: None,
+                        parents: vec![],
+                    });
+                    continue;
+                }
+            };
+
+            let status = if depth < max_depth {
+                NodeStatus::Fetched
+            } else {
+                NodeStatus::Truncated
+            };
+            if status == NodeStatus::Fetched {
+                for (descendant, content) in &entry.descendants {
+                    let descendant = GraphEntryAddress::new(*descendant);
+                    edges.push(GraphEdge {
+                        from: addr.to_hex(),
+                        to: descendant.to_hex(),
+                        content: hex::encode(content),
+                        cycle: false,
+                    });
+                    if seen.insert(descendant.to_hex()) {
+                        next_layer.push(descendant);
+                    }
+                }
+            }
+            nodes.push(GraphNode {
+                address: addr.to_hex(),
+                depth,
+                status,
+                content: Some(hex::encode(entry.content)),
+                parents: entry.parents.iter().map(|parent| parent.to_hex()).collect(),
+            });
+        }
+        layer = next_layer;
+    }
+
+    let mut export = GraphExport {
+        root: root.to_hex(),
+        max_depth,
+        nodes,
+        edges,
+    };
+    mark_cycles(&mut export);
+    export
+}
+
+/// Flag the back edges found by a depth first search from the root, each of which closes a cycle.
+fn mark_cycles(export: &mut GraphExport) {
+    let mut adjacency: HashMap<String, Vec<usize>> = HashMap::new();
+    for (index, edge) in export.edges.iter().enumerate() {
+        adjacency.entry(edge.from.clone()).or_default().push(index);
+    }
+
+    let mut on_path = HashSet::new();
+    let mut done = HashSet::new();
+    // Each frame is a node, with the position of the next of its edges to explore
+    let mut stack = vec![(export.root.clone(), 0)];
+    let _ = on_path.insert(export.root.clone());
+    while let Some((node, position)) = stack.last_mut() {
+        let next_edge = adjacency
+            .get(node.as_str())
+            .and_then(|edges| edges.get(*position))
+            .copied();
+        *position += 1;
+        let Some(edge_index) = next_edge else {
+            let _ = on_path.remove(node.as_str());
+            let _ = done.insert(node.clone());
+            let _ = stack.pop();
+            continue;
+        };
+
+        let to = export.edges[edge_index].to.clone();
+        if on_path.contains(&to) {
+            export.edges[edge_index].cycle = true;
+        } else if !done.contains(&to) {
+            let _ = on_path.insert(to.clone());
+            stack.push((to, 0));
+        }
+    }
+}
+
+/// Render the graph as Graphviz DOT. Cycle links are drawn in red, missing entries dashed and
+/// entries at the depth limit grey.
+fn to_dot(export: &GraphExport) -> String {
+    let mut dot =
+        String::from("digraph graph_entries {\n    node [shape=box, fontname=monospace];\n");
+    for node in &export.nodes {
+        let short = &node.address[..node.address.len().min(12)];
+        let (label, style) = match node.status {
+            NodeStatus::Fetched => (format!("{short}\\ndepth {}", node.depth), ""),
+            NodeStatus::Truncated => (
+                format!("{short}\\ndepth {} (truncated)", node.depth),
+                ", style=filled, fillcolor=lightgrey",
+            ),
+            NodeStatus::Missing => (format!("{short}\\nmissing"), ", style=dashed"),
+        };
+        let root = if node.address == export.root {
+            ", penwidth=2"
+        } else {
+            ""
+        };
+        dot.push_str(&format!(
+            "    \"{}\" [label=\"{label}\"{style}{root}];\n",
+            node.address
+        ));
+    }
+    for edge in &export.edges {
+        let color = if edge.cycle { " [color=red]" } else { "" };
+        dot.push_str(&format!(
+            "    \"{}\" -> \"{}\"{color};\n",
+            edge.from, edge.to
+        ));
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use autonomi::SecretKey;
+
+    #[tokio::test]
+    async fn walk_graph_flags_cycles_and_depth_limit() {
+        let keys: Vec<SecretKey> = (0..4).map(|_| SecretKey::random()).collect();
+        let link = |i: usize| (keys[i].public_key(), [i as u8; 32]);
+        // 0 -> 1 -> 2 -> 0 is a cycle, and 2 -> 3
+        let entries: HashMap<String, GraphEntry> = [
+            GraphEntry::new(&keys[0], vec![], [0; 32], vec![link(1)]),
+            GraphEntry::new(&keys[1], vec![keys[0].public_key()], [1; 32], vec![link(2)]),
+            GraphEntry::new(&keys[2], vec![], [2; 32], vec![link(0), link(3)]),
+            GraphEntry::new(&keys[3], vec![], [3; 32], vec![]),
+        ]
+        .into_iter()
+        .map(|entry| (entry.address().to_hex(), entry))
+        .collect();
+        let fetch = |addr: GraphEntryAddress| {
+            let entry = entries.get(&addr.to_hex()).cloned();
+            async move { entry.ok_or_else(|| "not found".to_string()) }
+        };
+
+        let root = GraphEntryAddress::new(keys[0].public_key());
+        let export = walk_graph(root, 8, fetch).await;
+        assert_eq!(export.nodes.len(), 4);
+        assert_eq!(export.edges.len(), 4);
+        let cycles: Vec<_> = export.edges.iter().filter(|edge| edge.cycle).collect();
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].to, root.to_hex());
+
+        let export = walk_graph(root, 1, fetch).await;
+        assert_eq!(export.nodes.len(), 2);
+        assert_eq!(export.nodes[1].status, NodeStatus::Truncated);
+        assert!(export.edges.iter().all(|edge| !edge.cycle));
+    }
+}
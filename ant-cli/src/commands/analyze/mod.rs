@@ -6,10 +6,18 @@
 // KIND, either express or implied. Please review the Licences for the specific language governing
 // permissions and limitations relating to use of the SAFE Network Software.
 
+mod datamap;
 mod error;
+mod graph;
 mod json;
+mod prices;
+mod record;
 
+pub use datamap::datamap;
 pub use error::{AnalysisErrorDisplay, NetworkErrorDisplay};
+pub use graph::{DEFAULT_GRAPH_DEPTH, GraphFormat, graph, parse_graph_format};
+pub use prices::prices;
+pub use record::record;
 
 use crate::actions::NetworkContext;
 use crate::utils::parse_network_address;
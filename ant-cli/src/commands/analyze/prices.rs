@@ -0,0 +1,40 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use crate::actions::NetworkContext;
+use color_eyre::eyre::{Result, eyre};
+use comfy_table::Table;
+
+/// Sample store costs across `samples` random addresses and print the price distribution.
+pub async fn prices(samples: usize, network_context: NetworkContext) -> Result<()> {
+    if samples == 0 {
+        return Err(eyre!("The number of samples must be greater than 0"));
+    }
+
+    let client = crate::actions::connect_to_network(network_context)
+        .await
+        .map_err(|(err, _)| err)?;
+
+    println!("Sampling store costs across {samples} random addresses...");
+    let sample = client.network_price_sample(samples).await?;
+
+    let mut table = Table::new();
+    table.set_header(vec!["Statistic", "Price (AttoTokens)"]);
+    table.add_row(vec!["Min".to_string(), sample.min.to_string()]);
+    table.add_row(vec!["Median".to_string(), sample.median.to_string()]);
+    table.add_row(vec!["P90".to_string(), sample.p90.to_string()]);
+    table.add_row(vec!["Max".to_string(), sample.max.to_string()]);
+    println!("{table}");
+
+    println!(
+        "Computed from {} node prices across {} addresses ({} addresses could not be quoted).",
+        sample.prices_count, sample.addresses_sampled, sample.addresses_failed
+    );
+
+    Ok(())
+}
@@ -0,0 +1,457 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use super::{KAD_HOLDERS_QUERY_RANGE, get_record_with_best_effort};
+use crate::actions::NetworkContext;
+use crate::utils::parse_network_address;
+use ant_protocol::CLOSE_GROUP_SIZE;
+use ant_protocol::close_group::close_group_majority;
+use ant_protocol::storage::{
+    DataTypes, RecordHeader, RecordHeaderFormat, RecordKind, try_deserialize_record,
+};
+use autonomi::chunk::{ChunkAddress, DataMapChunk};
+use autonomi::client::datamap;
+use autonomi::graph::GraphEntryAddress;
+use autonomi::networking::{NetworkAddress, PeerId, Record};
+use autonomi::pointer::PointerTarget;
+use autonomi::register::RegisterAddress;
+use autonomi::{
+    Chunk, GraphEntry, Pointer, PointerAddress, PublicKey, Scratchpad, ScratchpadAddress,
+};
+use color_eyre::eyre::Result;
+use comfy_table::Table;
+
+/// Fetch the raw record at `addr`, decode it and show the peers holding it.
+///
+/// A public key may address a pointer, a scratchpad, a graph entry or the head pointer of a
+/// register, which are all stored at different places: each of them is looked up.
+pub async fn record(addr: &str, verbose: bool, network_context: NetworkContext) -> Result<()> {
+    let candidates = candidate_addresses(addr)?;
+    let client = crate::actions::connect_to_network(network_context)
+        .await
+        .map_err(|(err, _)| err)?;
+
+    let mut found = 0;
+    for (description, network_addr) in candidates {
+        if verbose {
+            println!("Looking for {description} at {network_addr:?}...");
+        }
+        let (record, holders) =
+            get_record_with_best_effort(&client, network_addr.clone(), verbose).await?;
+        let Some(record) = record else {
+            if verbose {
+                println!("No {description} found");
+            }
+            continue;
+        };
+        found += 1;
+        println!("Found {description} at {network_addr:?}");
+        print_record(&record);
+        print_holders(&network_addr, holders);
+        println!();
+    }
+
+    if found == 0 {
+        println!("No record found at address: {addr}");
+    }
+    Ok(())
+}
+
+/// The network addresses `addr` may refer to, along with a description of each.
+fn candidate_addresses(addr: &str) -> Result<Vec<(&'static str, NetworkAddress)>> {
+    let hex_str = addr.trim().trim_start_matches("0x");
+    if let Ok(chunk_addr) = ChunkAddress::from_hex(hex_str) {
+        return Ok(vec![("a chunk", NetworkAddress::from(chunk_addr))]);
+    }
+    if let Ok(public_key) = PublicKey::from_hex(hex_str) {
+        return Ok(vec![
+            (
+                "a pointer",
+                NetworkAddress::from(PointerAddress::new(public_key)),
+            ),
+            (
+                "a scratchpad",
+                NetworkAddress::from(ScratchpadAddress::new(public_key)),
+            ),
+            (
+                "a graph entry",
+                NetworkAddress::from(GraphEntryAddress::new(public_key)),
+            ),
+            (
+                "the head pointer of a register",
+                NetworkAddress::from(RegisterAddress::new(public_key).to_underlying_head_pointer()),
+            ),
+        ]);
+    }
+    Ok(vec![("a record", parse_network_address(addr)?)])
+}
+
+/// Print the header of the record and its decoded payload.
+fn print_record(record: &Record) {
+    let mut table = Table::new();
+    table.set_header(vec!["Property", "Value"]);
+    table.add_row(vec![
+        "Size (bytes)".to_string(),
+        record.value.len().to_string(),
+    ]);
+
+    let (header, format, header_size) = match RecordHeader::parse(&record.value) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            table.add_row(vec!["Header".to_string(), format!("invalid: {err}")]);
+            println!("{table}");
+            return;
+        }
+    };
+    let (data_type, payment) = match header.kind {
+        RecordKind::DataOnly(data_type) => (data_type, "none"),
+        RecordKind::DataWithPayment(data_type) => (data_type, "proof of payment"),
+        RecordKind::DataWithMerklePayment(data_type) => (data_type, "merkle proof of payment"),
+    };
+    let format = match format {
+        RecordHeaderFormat::Legacy => "legacy",
+        RecordHeaderFormat::Versioned => "versioned",
+    };
+    table.add_row(vec!["Kind".to_string(), format!("{data_type:?}")]);
+    table.add_row(vec!["Payment".to_string(), payment.to_string()]);
+    table.add_row(vec![
+        "Header".to_string(),
+        format!(
+            "{format}, {header_size} bytes, flags {:#010b}",
+            header.flags
+        ),
+    ]);
+    if let Some(expiry) = header.expiry {
+        let expiry = chrono::DateTime::from_timestamp(expiry as i64, 0)
+            .map(|time| time.to_rfc3339())
+            .unwrap_or_else(|| expiry.to_string());
+        table.add_row(vec!["Expiry".to_string(), expiry]);
+    }
+
+    if !matches!(header.kind, RecordKind::DataOnly(_)) {
+        table.add_row(vec![
+            "Payload".to_string(),
+            "not decoded, records with a proof of payment are only sent to the nodes".to_string(),
+        ]);
+    } else {
+        match decode_payload(data_type, record) {
+            Ok(rows) => table.add_rows(rows),
+            Err(err) => table.add_row(vec!["Payload".to_string(), err]),
+        };
+    }
+    println!("{table}");
+}
+
+/// The decoded payload of a record without a proof of payment, as rows of properties.
+fn decode_payload(data_type: DataTypes, record: &Record) -> Result<Vec<Vec<String>>, String> {
+    let invalid = |err: ant_protocol::Error| format!("invalid {data_type:?}: {err}");
+    let rows = match data_type {
+        DataTypes::Chunk => {
+            let chunk: Chunk = try_deserialize_record(record).map_err(invalid)?;
+            let mut rows = vec![vec![
+                "Content size (bytes)".to_string(),
+                chunk.size().to_string(),
+            ]];
+            // The chunks of the data are encrypted, only a data map chunk can be decoded further
+            if let Ok(info) = datamap::inspect(&DataMapChunk(chunk)) {
+                rows.push(vec![
+                    "Content".to_string(),
+                    format!(
+                        "DataMap of {} chunks, {} bytes in total",
+                        info.chunk_count, info.total_size
+                    ),
+                ]);
+            }
+            rows
+        }
+        DataTypes::Pointer => {
+            let pointer: Pointer = try_deserialize_record(record).map_err(invalid)?;
+            let target = match pointer.target() {
+                PointerTarget::ChunkAddress(_) => "chunk",
+                PointerTarget::GraphEntryAddress(_) => "graph entry",
+                PointerTarget::PointerAddress(_) => "pointer",
+                PointerTarget::ScratchpadAddress(_) => "scratchpad",
+            };
+            vec![
+                vec!["Owner".to_string(), pointer.owner().to_hex()],
+                vec!["Counter".to_string(), pointer.counter().to_string()],
+                vec![
+                    "Target".to_string(),
+                    format!("{target} {}", pointer.target().to_hex()),
+                ],
+                vec![
+                    "Valid signature".to_string(),
+                    pointer.verify_signature().to_string(),
+                ],
+            ]
+        }
+        DataTypes::Scratchpad => {
+            let scratchpad: Scratchpad = try_deserialize_record(record).map_err(invalid)?;
+            vec![
+                vec!["Owner".to_string(), scratchpad.owner().to_hex()],
+                vec!["Counter".to_string(), scratchpad.counter().to_string()],
+                vec![
+                    "Data encoding".to_string(),
+                    scratchpad.data_encoding().to_string(),
+                ],
+                vec![
+                    "Encrypted data (bytes)".to_string(),
+                    scratchpad.encrypted_data().len().to_string(),
+                ],
+                vec![
+                    "Valid signature".to_string(),
+                    scratchpad.verify_signature().to_string(),
+                ],
+            ]
+        }
+        DataTypes::GraphEntry => {
+            // Nodes keep all the conflicting versions of a graph entry
+            let entries: Vec<GraphEntry> = try_deserialize_record(record).map_err(invalid)?;
+            let mut rows = vec![vec!["Versions".to_string(), entries.len().to_string()]];
+            for entry in &entries {
+                rows.extend([
+                    vec!["Owner".to_string(), entry.owner.to_hex()],
+                    vec!["Content".to_string(), hex::encode(entry.content)],
+                    vec![
+                        "Parents".to_string(),
+                        entry
+                            .parents
+                            .iter()
+                            .map(|parent| parent.to_hex())
+                            .collect::<Vec<_>>()
+                            .join("\n"),
+                    ],
+                    vec![
+                        "Descendants".to_string(),
+                        entry
+                            .descendants
+                            .iter()
+                            .map(|(descendant, content)| {
+                                format!("{} ({})", descendant.to_hex(), hex::encode(content))
+                            })
+                            .collect::<Vec<_>>()
+                            .join("\n"),
+                    ],
+                    vec![
+                        "Valid signature".to_string(),
+                        entry.verify_signature().to_string(),
+                    ],
+                ]);
+            }
+            rows
+        }
+    };
+    Ok(rows)
+}
+
+/// Print the peers which returned the record, closest first, and whether they make a quorum.
+fn print_holders(network_addr: &NetworkAddress, mut holders: Vec<PeerId>) {
+    holders.sort_by_key(|peer_id| network_addr.distance(&NetworkAddress::from(*peer_id)));
+    let majority = close_group_majority();
+    let quorum = if holders.len() >= majority {
+        "met"
+    } else {
+        "not met"
+    };
+    println!(
+        "Holders: {} of the {KAD_HOLDERS_QUERY_RANGE} peers queried, quorum of {majority} out of a close group of {CLOSE_GROUP_SIZE} {quorum}",
+        holders.len()
+    );
+
+    let mut table = Table::new();
+    table.set_header(vec!["Peer", "Distance (ilog2)"]);
+    for peer_id in holders {
+        let distance = network_addr.distance(&NetworkAddress::from(peer_id));
+        table.add_row(vec![
+            peer_id.to_string(),
+            distance.ilog2().unwrap_or(0).to_string(),
+        ]);
+    }
+    println!("{table}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ant_protocol::storage::try_serialize_record;
+    use autonomi::{Bytes, SecretKey};
+
+    fn record_of<T: serde::Serialize>(
+        network_addr: &NetworkAddress,
+        data: &T,
+        data_type: DataTypes,
+    ) -> Record {
+        let value = try_serialize_record(data, RecordKind::DataOnly(data_type))
+            .expect("record shall serialize");
+        Record::new(network_addr.to_record_key(), value.to_vec())
+    }
+
+    fn row<'a>(rows: &'a [Vec<String>], property: &str) -> Option<&'a str> {
+        rows.iter()
+            .find(|row| row[0] == property)
+            .map(|row| row[1].as_str())
+    }
+
+    #[test]
+    fn chunk_addresses_have_a_single_candidate() {
+        let chunk = Chunk::new(Bytes::from("chunk content"));
+        let hex = chunk.address().to_hex();
+        for addr in [hex.clone(), format!("0x{hex}"), format!(" {hex}\n")] {
+            let candidates = candidate_addresses(&addr).expect("chunk address shall parse");
+            assert_eq!(
+                candidates,
+                vec![("a chunk", NetworkAddress::from(*chunk.address()))]
+            );
+        }
+    }
+
+    #[test]
+    fn public_keys_have_a_candidate_per_data_type() {
+        let public_key = SecretKey::random().public_key();
+        let candidates = candidate_addresses(&public_key.to_hex()).expect("public key shall parse");
+
+        assert_eq!(
+            candidates,
+            vec![
+                (
+                    "a pointer",
+                    NetworkAddress::from(PointerAddress::new(public_key))
+                ),
+                (
+                    "a scratchpad",
+                    NetworkAddress::from(ScratchpadAddress::new(public_key))
+                ),
+                (
+                    "a graph entry",
+                    NetworkAddress::from(GraphEntryAddress::new(public_key))
+                ),
+                (
+                    "the head pointer of a register",
+                    NetworkAddress::from(
+                        RegisterAddress::new(public_key).to_underlying_head_pointer()
+                    )
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn other_addresses_fall_back_to_a_record() {
+        let chunk = Chunk::new(Bytes::from("chunk content"));
+        let debug_format = format!(
+            "NetworkAddress::RecordKey(\"{}\") - (...)",
+            chunk.address().to_hex()
+        );
+        let candidates = candidate_addresses(&debug_format).expect("debug format shall parse");
+        assert_eq!(
+            candidates,
+            vec![("a record", NetworkAddress::from(*chunk.address()))]
+        );
+
+        assert!(candidate_addresses("not an address").is_err());
+    }
+
+    #[test]
+    fn chunks_are_decoded_with_their_data_map() {
+        let (data_map_chunk, chunks) =
+            autonomi::self_encryption::encrypt(Bytes::from(vec![7u8; 1024 * 1024]))
+                .expect("encryption shall succeed");
+
+        let network_addr = NetworkAddress::from(*data_map_chunk.address());
+        let rows = decode_payload(
+            DataTypes::Chunk,
+            &record_of(&network_addr, &data_map_chunk, DataTypes::Chunk),
+        )
+        .expect("data map chunk shall decode");
+        assert_eq!(
+            row(&rows, "Content size (bytes)"),
+            Some(data_map_chunk.size().to_string().as_str())
+        );
+        assert!(
+            row(&rows, "Content").is_some_and(|content| content.starts_with("DataMap of")),
+            "{rows:?}"
+        );
+
+        // The content of the encrypted chunks is not decoded
+        let chunk = &chunks[0];
+        let network_addr = NetworkAddress::from(*chunk.address());
+        let rows = decode_payload(
+            DataTypes::Chunk,
+            &record_of(&network_addr, chunk, DataTypes::Chunk),
+        )
+        .expect("chunk shall decode");
+        assert_eq!(rows.len(), 1);
+    }
+
+    #[test]
+    fn mutable_data_is_decoded_with_its_signature_checked() {
+        let owner = SecretKey::random();
+
+        let target = PointerTarget::ChunkAddress(ChunkAddress::new(xor_name::XorName([1; 32])));
+        let pointer = Pointer::new(&owner, 3, target.clone());
+        let network_addr = NetworkAddress::from(pointer.address());
+        let rows = decode_payload(
+            DataTypes::Pointer,
+            &record_of(&network_addr, &pointer, DataTypes::Pointer),
+        )
+        .expect("pointer shall decode");
+        assert_eq!(
+            row(&rows, "Owner"),
+            Some(owner.public_key().to_hex().as_str())
+        );
+        assert_eq!(row(&rows, "Counter"), Some("3"));
+        assert_eq!(
+            row(&rows, "Target"),
+            Some(format!("chunk {}", target.to_hex()).as_str())
+        );
+        assert_eq!(row(&rows, "Valid signature"), Some("true"));
+
+        let scratchpad = Scratchpad::new(&owner, 42, &Bytes::from("secret"), 5);
+        let network_addr = NetworkAddress::from(*scratchpad.address());
+        let rows = decode_payload(
+            DataTypes::Scratchpad,
+            &record_of(&network_addr, &scratchpad, DataTypes::Scratchpad),
+        )
+        .expect("scratchpad shall decode");
+        assert_eq!(row(&rows, "Counter"), Some("5"));
+        assert_eq!(row(&rows, "Data encoding"), Some("42"));
+        assert_eq!(row(&rows, "Valid signature"), Some("true"));
+
+        // A pointer is not a valid scratchpad
+        let network_addr = NetworkAddress::from(pointer.address());
+        let err = decode_payload(
+            DataTypes::Scratchpad,
+            &record_of(&network_addr, &pointer, DataTypes::Pointer),
+        )
+        .expect_err("a pointer shall not decode as a scratchpad");
+        assert!(err.starts_with("invalid Scratchpad"), "{err}");
+    }
+
+    #[test]
+    fn all_the_versions_of_a_graph_entry_are_decoded() {
+        let owner = SecretKey::random();
+        let entries = vec![
+            GraphEntry::new(&owner, vec![], [1; 32], vec![]),
+            GraphEntry::new(&owner, vec![], [2; 32], vec![]),
+        ];
+        let network_addr = NetworkAddress::from(entries[0].address());
+        let rows = decode_payload(
+            DataTypes::GraphEntry,
+            &record_of(&network_addr, &entries, DataTypes::GraphEntry),
+        )
+        .expect("graph entries shall decode");
+
+        assert_eq!(row(&rows, "Versions"), Some("2"));
+        let contents: Vec<_> = rows
+            .iter()
+            .filter(|row| row[0] == "Content")
+            .map(|row| row[1].clone())
+            .collect();
+        assert_eq!(contents, vec![hex::encode([1; 32]), hex::encode([2; 32])]);
+    }
+}
@@ -0,0 +1,450 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! `ant file browse`: an interactive browser of the content of an archive, to preview its small
+//! text files and pick the entries to download.
+
+use crate::actions::NetworkContext;
+use autonomi::Client;
+use autonomi::chunk::DataMapChunk;
+use autonomi::data::DataAddress;
+use autonomi::files::{Metadata, PackCache};
+use color_eyre::eyre::{Context, Result, eyre};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use std::collections::BTreeSet;
+use std::path::{Component, Path, PathBuf};
+
+/// Files up to this size can be previewed.
+const PREVIEW_MAX_SIZE: u64 = 64 * 1024;
+
+const HELP: &str =
+    " ↑/↓ move  enter open/preview  ← back  space select  d download selected  q quit";
+
+/// How to fetch a file of the archive.
+enum FileAccess {
+    Public(DataAddress),
+    Private(DataMapChunk),
+}
+
+struct ArchiveFile {
+    path: PathBuf,
+    access: FileAccess,
+    metadata: Metadata,
+}
+
+/// An entry of the directory being browsed.
+#[derive(Debug, PartialEq, Eq)]
+enum Entry {
+    Dir(String),
+    /// Index of the file in the archive files
+    File(usize),
+}
+
+enum Preview {
+    None,
+    Loading,
+    Text(String),
+    Unavailable(String),
+}
+
+struct Browser {
+    title: String,
+    files: Vec<ArchiveFile>,
+    cwd: PathBuf,
+    entries: Vec<Entry>,
+    state: ListState,
+    selected: BTreeSet<usize>,
+    preview: Preview,
+}
+
+/// Browse the archive at `addr`, then download the selected files to `dest`.
+pub async fn browse(addr: &str, dest: &Path, network_context: NetworkContext) -> Result<()> {
+    let client = crate::actions::connect_to_network(network_context)
+        .await
+        .map_err(|(err, _)| err)?;
+    let files = fetch_archive_files(addr, &client).await?;
+    if files.is_empty() {
+        println!("The archive at {addr} is empty");
+        return Ok(());
+    }
+
+    let mut browser = Browser::new(addr.to_string(), files);
+    let mut terminal = ratatui::init();
+    let result = browser.run(&mut terminal, &client).await;
+    ratatui::restore();
+    if !result? || browser.selected.is_empty() {
+        return Ok(());
+    }
+
+    let selected = std::mem::take(&mut browser.selected);
+    println!("Downloading {} files to {dest:?}...", selected.len());
+    let mut failed = 0;
+    let mut packs = PackCache::default();
+    for index in selected {
+        let file = &browser.files[index];
+        let to_dest = dest.join(&file.path);
+        let result = match &file.access {
+            FileAccess::Public(addr) => {
+                client
+                    .archive_file_download_public_cached(addr, &file.metadata, to_dest, &mut packs)
+                    .await
+            }
+            FileAccess::Private(data_map) => {
+                client
+                    .archive_file_download_cached(data_map, &file.metadata, to_dest, &mut packs)
+                    .await
+            }
+        };
+        match result {
+            Ok(()) => println!("Downloaded {:?}", file.path),
+            Err(err) => {
+                eprintln!("Failed to download {:?}: {err}", file.path);
+                failed += 1;
+            }
+        }
+    }
+    if failed > 0 {
+        return Err(eyre!("Failed to download {failed} of the selected files"));
+    }
+    Ok(())
+}
+
+/// Fetch the files of the public archive at the address, or of the private archive with that
+/// local address. The files are given their path relative to the download directory, and those
+/// which could escape it are left out.
+async fn fetch_archive_files(addr: &str, client: &Client) -> Result<Vec<ArchiveFile>> {
+    let files: Vec<_> = if let Ok(archive_addr) = DataAddress::from_hex(addr) {
+        let archive = client
+            .archive_get_public(&archive_addr)
+            .await
+            .wrap_err("Failed to fetch the public archive")?;
+        archive
+            .iter()
+            .map(|(path, addr, metadata)| {
+                (path.clone(), FileAccess::Public(*addr), metadata.clone())
+            })
+            .collect()
+    } else {
+        let private_access = crate::user_data::get_local_private_archive_access(addr)
+            .wrap_err("The address is neither a public archive nor a local private archive")?;
+        let archive = client
+            .archive_get(&private_access)
+            .await
+            .wrap_err("Failed to fetch the private archive")?;
+        archive
+            .iter()
+            .map(|(path, data_map, metadata)| {
+                (
+                    path.clone(),
+                    FileAccess::Private(data_map.clone()),
+                    metadata.clone(),
+                )
+            })
+            .collect()
+    };
+
+    Ok(files
+        .into_iter()
+        .filter_map(|(path, access, metadata)| {
+            let Some(relative) = relative_path(&path) else {
+                eprintln!("Skipping {path:?}, its path would escape the download directory");
+                return None;
+            };
+            Some(ArchiveFile {
+                path: relative,
+                access,
+                metadata,
+            })
+        })
+        .collect())
+}
+
+/// The path of an archive file relative to the download directory: root, prefix and `.`
+/// components are dropped. `None` for a path with `..` components, or without any name.
+fn relative_path(path: &Path) -> Option<PathBuf> {
+    let mut relative = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(name) => relative.push(name),
+            Component::Prefix(_) | Component::RootDir | Component::CurDir => {}
+            Component::ParentDir => return None,
+        }
+    }
+    (!relative.as_os_str().is_empty()).then_some(relative)
+}
+
+impl Browser {
+    fn new(title: String, files: Vec<ArchiveFile>) -> Self {
+        let mut browser = Self {
+            title,
+            files,
+            cwd: PathBuf::new(),
+            entries: vec![],
+            state: ListState::default(),
+            selected: BTreeSet::new(),
+            preview: Preview::None,
+        };
+        browser.change_dir(PathBuf::new());
+        browser
+    }
+
+    /// Run the browser until the user quits. Returns whether the selected files are to be downloaded.
+    async fn run(&mut self, terminal: &mut DefaultTerminal, client: &Client) -> Result<bool> {
+        loop {
+            let _ = terminal.draw(|frame| self.draw(frame))?;
+            let Event::Key(key) = tokio::task::block_in_place(event::read)? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(false),
+                KeyCode::Char('d') => return Ok(true),
+                KeyCode::Up | KeyCode::Char('k') => self.move_cursor(false),
+                KeyCode::Down | KeyCode::Char('j') => self.move_cursor(true),
+                KeyCode::Left | KeyCode::Backspace | KeyCode::Char('h') => {
+                    if let Some(parent) = self.cwd.parent().map(Path::to_path_buf) {
+                        self.change_dir(parent);
+                    }
+                }
+                KeyCode::Char(' ') => self.toggle_selection(),
+                KeyCode::Enter | KeyCode::Right | KeyCode::Char('l') => {
+                    match self.state.selected().and_then(|i| self.entries.get(i)) {
+                        Some(Entry::Dir(name)) => {
+                            let dir = self.cwd.join(name);
+                            self.change_dir(dir);
+                        }
+                        Some(Entry::File(index)) => {
+                            let index = *index;
+                            self.preview = Preview::Loading;
+                            let _ = terminal.draw(|frame| self.draw(frame))?;
+                            self.preview = fetch_preview(client, &self.files[index]).await;
+                        }
+                        None => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn change_dir(&mut self, dir: PathBuf) {
+        self.entries = list_dir(&self.files, &dir);
+        self.cwd = dir;
+        self.state.select((!self.entries.is_empty()).then_some(0));
+        self.preview = Preview::None;
+    }
+
+    fn move_cursor(&mut self, down: bool) {
+        if down {
+            self.state.select_next();
+        } else {
+            self.state.select_previous();
+        }
+        self.preview = Preview::None;
+    }
+
+    /// The indexes of the files of the entry, all the files below it for a directory.
+    fn entry_files(&self, entry: &Entry) -> Vec<usize> {
+        match entry {
+            Entry::Dir(name) => {
+                let dir = self.cwd.join(name);
+                (0..self.files.len())
+                    .filter(|index| self.files[*index].path.starts_with(&dir))
+                    .collect()
+            }
+            Entry::File(index) => vec![*index],
+        }
+    }
+
+    fn is_selected(&self, entry: &Entry) -> bool {
+        self.entry_files(entry)
+            .iter()
+            .all(|index| self.selected.contains(index))
+    }
+
+    fn toggle_selection(&mut self) {
+        let Some(entry) = self.state.selected().and_then(|i| self.entries.get(i)) else {
+            return;
+        };
+        let select = !self.is_selected(entry);
+        for index in self.entry_files(entry) {
+            if select {
+                let _ = self.selected.insert(index);
+            } else {
+                let _ = self.selected.remove(&index);
+            }
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, help] =
+            Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+        let [list_area, preview_area] =
+            Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)])
+                .areas(main);
+
+        let items: Vec<ListItem> = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let mark = if self.is_selected(entry) {
+                    "[x]"
+                } else {
+                    "[ ]"
+                };
+                let label = match entry {
+                    Entry::Dir(name) => format!("{name}/"),
+                    Entry::File(index) => {
+                        let file = &self.files[*index];
+                        let name = file
+                            .path
+                            .file_name()
+                            .map(|name| name.to_string_lossy().to_string())
+                            .unwrap_or_default();
+                        format!("{name} ({} bytes)", file.metadata.size)
+                    }
+                };
+                ListItem::new(format!("{mark} {label}"))
+            })
+            .collect();
+        let list = List::new(items)
+            .block(Block::bordered().title(format!(" {} /{} ", self.title, self.cwd.display())))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, list_area, &mut self.state);
+
+        let preview = Paragraph::new(self.preview_text())
+            .wrap(Wrap { trim: false })
+            .block(Block::bordered().title(format!(" {} selected ", self.selected.len())));
+        frame.render_widget(preview, preview_area);
+        frame.render_widget(Line::from(HELP), help);
+    }
+
+    fn preview_text(&self) -> String {
+        let Some(Entry::File(index)) = self.state.selected().and_then(|i| self.entries.get(i))
+        else {
+            return String::new();
+        };
+        let file = &self.files[*index];
+        let mut text = format!(
+            "{}\nSize: {} bytes\nType: {}\nModified: {}\n\n",
+            file.path.display(),
+            file.metadata.size,
+            file.metadata.content_type.as_deref().unwrap_or("unknown"),
+            file.metadata.modified
+        );
+        match &self.preview {
+            Preview::None => text.push_str("Press enter to preview"),
+            Preview::Loading => text.push_str("Loading..."),
+            Preview::Text(content) => text.push_str(content),
+            Preview::Unavailable(reason) => text.push_str(reason),
+        }
+        text
+    }
+}
+
+/// The entries of `dir`: its sub directories first, then its files.
+fn list_dir(files: &[ArchiveFile], dir: &Path) -> Vec<Entry> {
+    let mut dirs = BTreeSet::new();
+    let mut entries = vec![];
+    for (index, file) in files.iter().enumerate() {
+        let Ok(rest) = file.path.strip_prefix(dir) else {
+            continue;
+        };
+        let mut components = rest.components();
+        let Some(first) = components.next() else {
+            continue;
+        };
+        if components.next().is_some() {
+            let _ = dirs.insert(first.as_os_str().to_string_lossy().to_string());
+        } else {
+            entries.push(Entry::File(index));
+        }
+    }
+    dirs.into_iter().map(Entry::Dir).chain(entries).collect()
+}
+
+async fn fetch_preview(client: &Client, file: &ArchiveFile) -> Preview {
+    if file.metadata.size > PREVIEW_MAX_SIZE {
+        return Preview::Unavailable(format!(
+            "Only files up to {PREVIEW_MAX_SIZE} bytes can be previewed"
+        ));
+    }
+    let data = match &file.access {
+        FileAccess::Public(addr) => client.data_get_public(addr).await,
+        FileAccess::Private(data_map) => client.data_get(data_map).await,
+    };
+    let data = match &file.metadata.packed {
+        Some(range) => data.and_then(|pack| range.unpack(&pack)),
+        None => data,
+    };
+    match data {
+        Ok(data) => match String::from_utf8(data.to_vec()) {
+            Ok(text) if !text.contains('\0') => Preview::Text(text),
+            _ => Preview::Unavailable("Binary file, no preview".to_string()),
+        },
+        Err(err) => Preview::Unavailable(format!("Failed to fetch the file: {err}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str) -> ArchiveFile {
+        ArchiveFile {
+            path: PathBuf::from(path),
+            access: FileAccess::Public(DataAddress::new(xor_name::XorName::default())),
+            metadata: Metadata::empty(),
+        }
+    }
+
+    #[test]
+    fn relative_path_cannot_escape_the_download_directory() {
+        assert_eq!(
+            relative_path(Path::new("docs/./a.txt")),
+            Some(PathBuf::from("docs/a.txt"))
+        );
+        assert_eq!(
+            relative_path(Path::new("/etc/passwd")),
+            Some(PathBuf::from("etc/passwd"))
+        );
+        assert_eq!(relative_path(Path::new("../a.txt")), None);
+        assert_eq!(relative_path(Path::new("docs/../../a.txt")), None);
+        assert_eq!(relative_path(Path::new("/")), None);
+    }
+
+    #[test]
+    fn list_dir_lists_sub_directories_then_files() {
+        let files = vec![
+            file("a.txt"),
+            file("docs/b.txt"),
+            file("docs/img/c.png"),
+            file("z/d.txt"),
+        ];
+
+        assert_eq!(
+            list_dir(&files, Path::new("")),
+            vec![
+                Entry::Dir("docs".to_string()),
+                Entry::Dir("z".to_string()),
+                Entry::File(0),
+            ]
+        );
+        assert_eq!(
+            list_dir(&files, Path::new("docs")),
+            vec![Entry::Dir("img".to_string()), Entry::File(1)]
+        );
+    }
+}
@@ -0,0 +1,38 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use crate::actions::NetworkContext;
+use autonomi::{AttoTokens, Network, RewardsAddress, evm_testnet, get_evm_network};
+use color_eyre::eyre::{Result, eyre};
+use std::str::FromStr;
+
+/// Fund a wallet from the faucet of the local EVM testnet.
+pub async fn faucet(to: String, amount: String, network_context: NetworkContext) -> Result<()> {
+    let to = RewardsAddress::from_str(&to)
+        .map_err(|_| eyre!("Please provide a valid wallet address in hex format"))?;
+    let amount = AttoTokens::from_str(&amount)
+        .map_err(|err| eyre!("Please provide a valid amount of tokens: {err}"))?;
+
+    let network = get_evm_network(
+        network_context.peers.local,
+        Some(network_context.network_id.as_u8()),
+    )?;
+    if !matches!(network, Network::Custom(_)) {
+        return Err(eyre!(
+            "No faucet is available for {network}, the faucet only funds wallets of a local EVM testnet"
+        ));
+    }
+
+    println!("Funding {to} with {amount} tokens on {network}...");
+    evm_testnet::fund(&network, to, amount.as_atto())
+        .await
+        .map_err(|err| eyre!("Failed to fund the wallet: {err}"))?;
+    println!("Funded {to} with {amount} tokens and some gas");
+
+    Ok(())
+}
@@ -7,26 +7,42 @@
 // permissions and limitations relating to use of the SAFE Network Software.
 
 use crate::access::{cached_merkle_payments, cached_payments};
-use crate::actions::NetworkContext;
+use crate::actions::{NetworkContext, STDIO_PATH};
 use crate::args::max_fee_per_gas::{MaxFeePerGasParam, get_max_fee_per_gas_from_opt_param};
 use crate::commands::PaymentFlags;
-use crate::exit_code::{ExitCodeError, FEES_ERROR, IO_ERROR, upload_exit_code};
+use crate::exit_code::{
+    ExitCodeError, FEES_ERROR, INVALID_INPUT_EXIT_CODE, IO_ERROR, get_download_error_exit_code,
+    put_error_exit_code, upload_exit_code,
+};
 use crate::utils::collect_upload_summary;
 use crate::wallet::load_wallet;
 use autonomi::client::PutError;
 use autonomi::client::analyze::Analysis;
 use autonomi::client::config::MERKLE_PAYMENT_THRESHOLD;
+use autonomi::client::data::DataAddress;
 use autonomi::client::merkle_payments::MerklePaymentReceipt;
 use autonomi::client::payment::{BulkPaymentOption, PaymentOption, Receipt};
-use autonomi::files::{UploadError, estimate_directory_chunks};
+use autonomi::client::repair::RepairOutcome;
+use autonomi::files::{DownloadManifest, ManifestMismatch, UploadError, estimate_directory_chunks};
 use autonomi::networking::{Quorum, RetryStrategy};
-use autonomi::{AttoTokens, Client, ClientOperatingStrategy, PaymentMode, TransactionConfig};
+use autonomi::self_encryption::Compression;
+use autonomi::{
+    AttoTokens, Bytes, Client, ClientOperatingStrategy, PaymentMode, QuoteSelectionStrategy,
+    TransactionConfig,
+};
 use color_eyre::Section;
 use color_eyre::eyre::{Context, Result, eyre};
-use std::path::PathBuf;
+use std::io::Read;
+use std::path::{Path, PathBuf};
 
 const MAX_ADDRESSES_TO_PRINT: usize = 3;
 
+/// Name under which data uploaded from stdin is saved to the local user data.
+const STDIN_FILE_NAME: &str = "stdin";
+
+/// Max size of the data read from stdin, as it is held in memory for the upload.
+const MAX_STDIN_UPLOAD_SIZE: u64 = 1024 * 1024 * 1024;
+
 /// How the payment method was selected
 #[derive(Debug, Clone)]
 pub enum PaymentSelection {
@@ -138,7 +154,7 @@ impl PaymentMethod {
             }
             PaymentMethod::Regular { is_resuming, .. } => {
                 if is_resuming && let Some(receipt) = cached_regular {
-                    return BulkPaymentOption::Receipt(receipt);
+                    return BulkPaymentOption::ContinueRegular(wallet, receipt);
                 }
                 BulkPaymentOption::ForceRegular(wallet)
             }
@@ -227,6 +243,21 @@ async fn add_archive_cost_if_needed(
     }
 }
 
+/// The operating strategy of the client, paying `payees` nodes per chunk and compressing data at
+/// the `compress` zstd level if given.
+fn operating_strategy(payees: Option<u64>, compress: Option<i32>) -> ClientOperatingStrategy {
+    let mut config = ClientOperatingStrategy::new();
+    if let Some(payees) = payees {
+        config.quote_selection = QuoteSelectionStrategy::RedundancyWeighted {
+            payees: payees as usize,
+        };
+    }
+    if let Some(level) = compress {
+        config.compression = Compression::Zstd(level);
+    }
+    config
+}
+
 pub async fn cost(
     file: &str,
     is_public: bool,
@@ -238,11 +269,15 @@ pub async fn cost(
         disable_single_node_payment: use_standard_payment,
         merkle: force_merkle,
         regular: force_regular,
+        payees,
     } = payment_flags;
 
-    let mut client = crate::actions::connect_to_network(network_context)
-        .await
-        .map_err(|(err, _)| err)?;
+    let mut client = crate::actions::connect_to_network_with_config(
+        network_context,
+        operating_strategy(payees, None),
+    )
+    .await
+    .map_err(|(err, _)| err)?;
 
     let path = PathBuf::from(file);
     let visibility = if is_public { "public" } else { "private" };
@@ -312,6 +347,7 @@ pub async fn cost(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn upload(
     file: &str,
     public: bool,
@@ -319,15 +355,36 @@ pub async fn upload(
     network_context: NetworkContext,
     max_fee_per_gas_param: Option<MaxFeePerGasParam>,
     retry_failed: u64,
+    compress: Option<i32>,
     payment_flags: PaymentFlags,
 ) -> Result<(), ExitCodeError> {
     let PaymentFlags {
         disable_single_node_payment: use_standard_payment,
         merkle: force_merkle,
         regular: force_regular,
+        payees,
     } = payment_flags;
 
-    let config = ClientOperatingStrategy::new();
+    if file == STDIO_PATH {
+        if force_merkle {
+            return Err((
+                eyre!("Merkle payments are not supported when uploading from stdin")
+                    .with_suggestion(|| "Write the data to a file and upload the file instead"),
+                INVALID_INPUT_EXIT_CODE,
+            ));
+        }
+        return upload_stdin(
+            public,
+            network_context,
+            max_fee_per_gas_param,
+            use_standard_payment,
+            payees,
+            compress,
+        )
+        .await;
+    }
+
+    let config = operating_strategy(payees, compress);
 
     let mut client =
         crate::actions::connect_to_network_with_config(network_context, config).await?;
@@ -350,7 +407,10 @@ pub async fn upload(
     let max_fee_per_gas =
         get_max_fee_per_gas_from_opt_param(max_fee_per_gas_param, client.evm_network())
             .map_err(|err| (err, FEES_ERROR))?;
-    wallet.set_transaction_config(TransactionConfig { max_fee_per_gas });
+    wallet.set_transaction_config(TransactionConfig {
+        max_fee_per_gas,
+        ..Default::default()
+    });
 
     let event_receiver = client.enable_client_events();
     let (upload_summary_thread, upload_completed_tx) =
@@ -488,6 +548,109 @@ pub async fn upload(
     Ok(())
 }
 
+/// Uploads the data read from stdin as a single file.
+///
+/// Self-encryption needs the total size of the data before it can produce the first chunk, so the
+/// input is buffered in memory until stdin is closed, then uploaded without touching the disk.
+/// Inputs larger than [`MAX_STDIN_UPLOAD_SIZE`] are refused.
+async fn upload_stdin(
+    public: bool,
+    network_context: NetworkContext,
+    max_fee_per_gas_param: Option<MaxFeePerGasParam>,
+    use_standard_payment: bool,
+    payees: Option<u64>,
+    compress: Option<i32>,
+) -> Result<(), ExitCodeError> {
+    let mut data = vec![];
+    // Read one byte over the limit to tell whether the input exceeds it
+    tokio::task::block_in_place(|| {
+        std::io::stdin()
+            .lock()
+            .take(MAX_STDIN_UPLOAD_SIZE + 1)
+            .read_to_end(&mut data)
+    })
+    .wrap_err("Failed to read data from stdin")
+    .map_err(|err| (err, IO_ERROR))?;
+    if data.len() as u64 > MAX_STDIN_UPLOAD_SIZE {
+        return Err((
+            eyre!("Cannot upload more than {MAX_STDIN_UPLOAD_SIZE} bytes from stdin")
+                .with_suggestion(|| "Write the data to a file and upload the file instead"),
+            INVALID_INPUT_EXIT_CODE,
+        ));
+    }
+    if data.len() < 3 {
+        return Err((
+            eyre!(
+                "Cannot upload {} bytes read from stdin, as it is smaller than 3 bytes",
+                data.len()
+            ),
+            INVALID_INPUT_EXIT_CODE,
+        ));
+    }
+    let data_len = data.len();
+
+    let mut client = crate::actions::connect_to_network_with_config(
+        network_context,
+        operating_strategy(payees, compress),
+    )
+    .await?;
+    if use_standard_payment {
+        client = client.with_payment_mode(PaymentMode::Standard);
+    }
+
+    let mut wallet = load_wallet(client.evm_network()).map_err(|err| (err, IO_ERROR))?;
+    let max_fee_per_gas =
+        get_max_fee_per_gas_from_opt_param(max_fee_per_gas_param, client.evm_network())
+            .map_err(|err| (err, FEES_ERROR))?;
+    wallet.set_transaction_config(TransactionConfig {
+        max_fee_per_gas,
+        ..Default::default()
+    });
+
+    info!(
+        "Uploading {} bytes read from stdin as {} data",
+        data_len,
+        if public { "public" } else { "private" }
+    );
+    let payment_option = PaymentOption::Wallet(wallet);
+    let put_result = if public {
+        client
+            .data_put_public(Bytes::from(data), payment_option)
+            .await
+            .map(|(cost, addr)| (cost, addr.to_hex(), None))
+    } else {
+        client
+            .data_put(Bytes::from(data), payment_option)
+            .await
+            .map(|(cost, data_map)| (cost, data_map.address(), Some(data_map.to_hex())))
+    };
+    let (cost, local_addr, datamap_hex) = put_result.map_err(|err| {
+        let exit_code = put_error_exit_code(&err);
+        (
+            eyre!(err).wrap_err("Failed to upload data from stdin"),
+            exit_code,
+        )
+    })?;
+
+    println!("Successfully uploaded {data_len} bytes from stdin");
+    println!("At address: {local_addr}");
+    println!("Total cost: {cost} AttoTokens");
+    info!("Successfully uploaded {data_len} bytes from stdin at address: {local_addr}");
+
+    let writer = match datamap_hex {
+        Some(datamap_hex) => {
+            crate::user_data::write_local_private_file(datamap_hex, local_addr, STDIN_FILE_NAME)
+        }
+        None => crate::user_data::write_local_public_file(local_addr, STDIN_FILE_NAME),
+    };
+    writer
+        .wrap_err("Failed to save file to local user data")
+        .with_suggestion(|| "Local user data saves the file address above to disk, without it you need to keep track of the address yourself")
+        .map_err(|err| (err, IO_ERROR))?;
+
+    Ok(())
+}
+
 /// Uploads a file or directory to the network using standard payment.
 /// Single files are uploaded without an archive, directories are uploaded with an archive.
 /// The no_archive argument can be used to skip the archive upload.
@@ -597,6 +760,7 @@ async fn upload_dir_standard(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn download(
     addr: &str,
     dest_path: &str,
@@ -605,6 +769,7 @@ pub async fn download(
     retries: Option<usize>,
     cache_chunks: bool,
     cache_dir: Option<&PathBuf>,
+    manifest: Option<&PathBuf>,
 ) -> Result<(), ExitCodeError> {
     let mut config = ClientOperatingStrategy::new();
 
@@ -622,16 +787,225 @@ pub async fn download(
         config.chunk_cache_dir = cache_dir.cloned();
         // Only print message if custom cache dir is specified
         if let Some(dir) = cache_dir {
-            println!("Using custom cache directory: {}", dir.display());
+            eprintln!("Using custom cache directory: {}", dir.display());
         }
     } else {
         config.chunk_cache_enabled = false;
-        println!("Chunk caching disabled");
+        eprintln!("Chunk caching disabled");
     }
 
     let client = crate::actions::connect_to_network_with_config(network_context, config).await?;
 
-    crate::actions::download(addr, dest_path, &client).await
+    match manifest {
+        Some(manifest_path) => {
+            download_with_manifest(addr, dest_path, manifest_path, &client).await
+        }
+        None => crate::actions::download(addr, dest_path, &client).await,
+    }
+}
+
+/// Download a public archive to `dest_path`, writing the verification manifest of the data
+/// fetched from the Network to `manifest_path`.
+async fn download_with_manifest(
+    addr: &str,
+    dest_path: &str,
+    manifest_path: &Path,
+    client: &Client,
+) -> Result<(), ExitCodeError> {
+    let not_an_archive = || {
+        (
+            eyre!("A download manifest can only be written for public archives"),
+            INVALID_INPUT_EXIT_CODE,
+        )
+    };
+    if dest_path == STDIO_PATH {
+        return Err((
+            eyre!("A download manifest cannot be written when downloading to stdout"),
+            INVALID_INPUT_EXIT_CODE,
+        ));
+    }
+    let archive_addr = DataAddress::from_hex(addr).map_err(|_| not_an_archive())?;
+    client
+        .archive_get_public(&archive_addr)
+        .await
+        .map_err(|_| not_an_archive())?;
+
+    println!("Downloading {addr} to {dest_path:?} along with its manifest...");
+    let manifest = client
+        .dir_download_public_with_manifest(&archive_addr, PathBuf::from(dest_path))
+        .await
+        .map_err(|err| {
+            let exit_code = get_download_error_exit_code(&err);
+            (
+                eyre!(err).wrap_err("Failed to download the archive"),
+                exit_code,
+            )
+        })?;
+    let json = manifest
+        .to_json()
+        .map_err(|err| (eyre!(err), INVALID_INPUT_EXIT_CODE))?;
+    std::fs::write(manifest_path, json).map_err(|err| (err.into(), IO_ERROR))?;
+    println!(
+        "Download manifest of {} files written to {manifest_path:?}",
+        manifest.files.len()
+    );
+    Ok(())
+}
+
+/// Verify the files of a download directory against the manifest written at download time.
+pub fn verify_download(manifest_path: &Path, dir: &Path) -> Result<()> {
+    let json = std::fs::read_to_string(manifest_path)
+        .wrap_err(format!("Failed to read the manifest at {manifest_path:?}"))?;
+    let manifest =
+        DownloadManifest::from_json(&json).wrap_err("Failed to parse the download manifest")?;
+
+    let mismatches = manifest
+        .verify(dir)
+        .wrap_err(format!("Failed to read the files in {dir:?}"))?;
+    if mismatches.is_empty() {
+        println!(
+            "All {} files of archive {} match the manifest",
+            manifest.files.len(),
+            manifest.archive
+        );
+        return Ok(());
+    }
+
+    for mismatch in &mismatches {
+        match mismatch {
+            ManifestMismatch::Missing(path) => println!("Missing: {path:?}"),
+            ManifestMismatch::Size {
+                path,
+                expected,
+                actual,
+            } => println!("Size mismatch: {path:?} is {actual} bytes, expected {expected}"),
+            ManifestMismatch::Checksum(path) => println!("Checksum mismatch: {path:?}"),
+        }
+    }
+    Err(eyre!(
+        "{} of {} files do not match the manifest",
+        mismatches.len(),
+        manifest.files.len()
+    ))
+}
+
+/// Repair the data at the address: the records of a public archive, of public data or a single
+/// record, held by fewer than `min_holders` peers, are uploaded again.
+pub async fn repair(
+    addr: &str,
+    min_holders: usize,
+    network_context: NetworkContext,
+    max_fee_per_gas_param: Option<MaxFeePerGasParam>,
+) -> Result<()> {
+    if min_holders == 0 {
+        return Err(eyre!(
+            "The minimum number of holders must be greater than 0"
+        ));
+    }
+
+    let client = crate::actions::connect_to_network(network_context)
+        .await
+        .map_err(|(err, _)| err)?;
+    let mut wallet = load_wallet(client.evm_network())?;
+    let max_fee_per_gas =
+        get_max_fee_per_gas_from_opt_param(max_fee_per_gas_param, client.evm_network())?;
+    wallet.set_transaction_config(TransactionConfig {
+        max_fee_per_gas,
+        ..Default::default()
+    });
+    let payment_option = PaymentOption::Wallet(wallet);
+
+    println!("Checking the holders of the records at {addr}...");
+    let analysis = client
+        .analyze_address(addr, false)
+        .await
+        .wrap_err("Failed to find the data to repair")?;
+    let report = match analysis {
+        Analysis::PublicArchive {
+            address: Some(address),
+            ..
+        } => {
+            client
+                .repair_public_archive(
+                    &DataAddress::new(*address.xorname()),
+                    min_holders,
+                    payment_option,
+                )
+                .await?
+        }
+        Analysis::DataMap { address, .. } => {
+            client
+                .repair_public_data(
+                    &DataAddress::new(*address.xorname()),
+                    min_holders,
+                    payment_option,
+                )
+                .await?
+        }
+        Analysis::Chunk(chunk) => {
+            client
+                .repair(&[chunk.network_address()], min_holders, payment_option)
+                .await
+        }
+        Analysis::GraphEntry(entry) => {
+            client
+                .repair(&[entry.address().into()], min_holders, payment_option)
+                .await
+        }
+        Analysis::Pointer(pointer) => {
+            client
+                .repair(&[pointer.address().into()], min_holders, payment_option)
+                .await
+        }
+        Analysis::Scratchpad(scratchpad) => {
+            client
+                .repair(
+                    &[(*scratchpad.address()).into()],
+                    min_holders,
+                    payment_option,
+                )
+                .await
+        }
+        other => {
+            return Err(eyre!(
+                "Cannot repair this type of data, only public archives, public data and single records can be repaired"
+            )
+            .with_note(|| format!("{other}")));
+        }
+    };
+
+    for record in &report.records {
+        match &record.outcome {
+            RepairOutcome::Healthy => {}
+            RepairOutcome::Repaired(cost) => println!(
+                "✅ {:?} was held by {} peer(s), uploaded again for {cost}",
+                record.address, record.holders
+            ),
+            RepairOutcome::AlreadyStored => println!(
+                "⚠️ {:?} is held by {} peer(s) only, but was reported as already stored and not uploaded again",
+                record.address, record.holders
+            ),
+            RepairOutcome::Lost => println!("❌ {:?} is not held by any peer", record.address),
+            RepairOutcome::Failed(err) => println!(
+                "❌ {:?} held by {} peer(s) could not be repaired: {err}",
+                record.address, record.holders
+            ),
+        }
+    }
+    println!(
+        "Checked {} record(s): {} repaired, {} already stored, {} lost, {} failed",
+        report.records.len(),
+        report.repaired_count(),
+        report.already_stored_count(),
+        report.lost_count(),
+        report.failed_count()
+    );
+    println!("Total cost: {} AttoTokens", report.total_cost());
+
+    if !report.is_healthy() {
+        return Err(eyre!("Some records could not be repaired"));
+    }
+    Ok(())
 }
 
 pub async fn list(network_context: NetworkContext, verbose: bool) -> Result<(), ExitCodeError> {
@@ -0,0 +1,74 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use crate::actions::NetworkContext;
+use crate::utils::parse_network_address;
+use color_eyre::eyre::{Result, eyre};
+use comfy_table::Table;
+
+/// Probe the health of the network and print the report.
+pub async fn health(
+    known_addresses: Vec<String>,
+    probes: usize,
+    network_context: NetworkContext,
+) -> Result<()> {
+    if probes == 0 {
+        return Err(eyre!("The number of probes must be greater than 0"));
+    }
+    let known_addresses = known_addresses
+        .iter()
+        .map(|addr| parse_network_address(addr))
+        .collect::<Result<Vec<_>>>()?;
+
+    let client = crate::actions::connect_to_network(network_context)
+        .await
+        .map_err(|(err, _)| err)?;
+
+    println!("Probing the health of the network...");
+    let report = client.network_health(&known_addresses, probes).await;
+
+    let percent =
+        |rate: Option<f64>| rate.map_or("-".to_string(), |rate| format!("{:.0}%", rate * 100.0));
+    let mut table = Table::new();
+    table.set_header(vec!["Measure", "Value"]);
+    table.add_row(vec![
+        "Time to first connected peer".to_string(),
+        report
+            .time_to_first_peer
+            .map_or("-".to_string(), |time| format!("{time:.2?}")),
+    ]);
+    table.add_row(vec![
+        "Peers in routing table".to_string(),
+        report.peers_in_routing_table.to_string(),
+    ]);
+    table.add_row(vec![
+        "Routing table fill rate".to_string(),
+        percent(Some(report.routing_table_fill_rate)),
+    ]);
+    table.add_row(vec![
+        "Median GET latency".to_string(),
+        report
+            .median_get_latency()
+            .map_or("-".to_string(), |latency| format!("{latency:.2?}")),
+    ]);
+    table.add_row(vec![
+        format!("GET success ({} probes)", report.gets_attempted),
+        percent(report.get_success_rate()),
+    ]);
+    table.add_row(vec![
+        format!("Store quote success ({} probes)", report.quotes_attempted),
+        percent(report.store_quote_success_rate()),
+    ]);
+    println!("{table}");
+
+    if known_addresses.is_empty() {
+        println!("No known addresses were given, the GET latency was not measured.");
+    }
+
+    Ok(())
+}
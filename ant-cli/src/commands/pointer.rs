@@ -117,7 +117,10 @@ pub async fn create(
 
     let max_fee_per_gas =
         get_max_fee_per_gas_from_opt_param(max_fee_per_gas_param, client.evm_network())?;
-    wallet.set_transaction_config(TransactionConfig { max_fee_per_gas });
+    wallet.set_transaction_config(TransactionConfig {
+        max_fee_per_gas,
+        ..Default::default()
+    });
 
     println!("Creating pointer with name: {name}");
     info!("Creating pointer with name: {name}");
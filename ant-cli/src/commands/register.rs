@@ -11,8 +11,10 @@
 use crate::actions::NetworkContext;
 use crate::args::max_fee_per_gas::{MaxFeePerGasParam, get_max_fee_per_gas_from_opt_param};
 use crate::wallet::load_wallet;
-use autonomi::client::register::RegisterAddress;
 use autonomi::client::register::SecretKey as RegisterSecretKey;
+use autonomi::client::register::{
+    RegisterAddress, RegisterBranches, RegisterHistoryEntry, RegisterValue,
+};
 use autonomi::{Client, TransactionConfig};
 use color_eyre::Section;
 use color_eyre::eyre::Context;
@@ -73,7 +75,10 @@ pub async fn create(
 
     let max_fee_per_gas =
         get_max_fee_per_gas_from_opt_param(max_fee_per_gas_param, client.evm_network())?;
-    wallet.set_transaction_config(TransactionConfig { max_fee_per_gas });
+    wallet.set_transaction_config(TransactionConfig {
+        max_fee_per_gas,
+        ..Default::default()
+    });
 
     let register_key = Client::register_key_from_name(&main_registers_key, name);
 
@@ -130,7 +135,10 @@ pub async fn edit(
 
     let max_fee_per_gas =
         get_max_fee_per_gas_from_opt_param(max_fee_per_gas_param, client.evm_network())?;
-    wallet.set_transaction_config(TransactionConfig { max_fee_per_gas });
+    wallet.set_transaction_config(TransactionConfig {
+        max_fee_per_gas,
+        ..Default::default()
+    });
 
     let value_bytes = if hex {
         hex::decode(value.trim_start_matches("0x"))
@@ -303,3 +311,198 @@ pub async fn history(
 
     Ok(())
 }
+
+pub async fn inspect(
+    address: String,
+    name: bool,
+    show_branches: bool,
+    hex: bool,
+    network_context: NetworkContext,
+) -> Result<()> {
+    let client = crate::actions::connect_to_network(network_context)
+        .await
+        .map_err(|(err, _)| err)?;
+
+    let addr = if name {
+        let main_registers_key = crate::keys::get_register_signing_key()
+            .wrap_err("The register key is required to perform this action")?;
+        let register_key = Client::register_key_from_name(&main_registers_key, &address);
+        RegisterAddress::new(register_key.public_key())
+    } else {
+        RegisterAddress::from_hex(&address)
+            .wrap_err(format!("Failed to parse register address: {address}"))
+            .with_suggestion(|| {
+                "if you want to use the name as the address, run the command with the --name flag"
+            })?
+    };
+
+    println!("Inspecting register at: {address}");
+    info!("Inspecting register at: {address}");
+    let branches = client
+        .register_branches(&addr)
+        .await
+        .wrap_err(format!("Error getting register branches at: {address}"))?;
+
+    let common_len = branches.common_len();
+    let open_branches = branches.open_branches().count();
+    println!("✅ Register found at: {address}");
+    match branches.head_pointer {
+        Some(head) => println!("Head pointer: {}", head.to_hex()),
+        None => println!("Head pointer: unknown"),
+    }
+    println!(
+        "History: {common_len} shared entries, {} branches, {open_branches} open",
+        branches.branches.len()
+    );
+    if branches.is_forked() {
+        println!("⚠️ The register is forked, run `ant register merge` to merge its open branches");
+    }
+    info!(
+        "Register {address} has {} branches, {open_branches} open",
+        branches.branches.len()
+    );
+
+    if !show_branches {
+        return Ok(());
+    }
+
+    println!("Shared history:");
+    if let Some(branch) = branches.branches.first() {
+        for entry in &branch.entries[..common_len] {
+            println!("  {}", format_history_entry(entry, hex, &branches));
+        }
+    }
+    for (i, branch) in branches.branches.iter().enumerate() {
+        let state = if branch.merged { "merged" } else { "open" };
+        println!("Branch {} ({state}):", i + 1);
+        for entry in &branch.entries[common_len..] {
+            println!("  {}", format_history_entry(entry, hex, &branches));
+        }
+    }
+    Ok(())
+}
+
+pub async fn merge(
+    address: String,
+    name: bool,
+    value: Option<String>,
+    hex: bool,
+    network_context: NetworkContext,
+    max_fee_per_gas_param: Option<MaxFeePerGasParam>,
+) -> Result<()> {
+    let main_registers_key = crate::keys::get_register_signing_key()
+        .wrap_err("The register key is required to perform this action")?;
+    let register_key = if name {
+        Client::register_key_from_name(&main_registers_key, &address)
+    } else {
+        let addr = RegisterAddress::from_hex(&address)
+            .wrap_err(format!("Failed to parse register address: {address}"))
+            .with_suggestion(|| {
+                "if you want to use the name as the address, run the command with the --name flag"
+            })?;
+        let name_str = crate::user_data::get_name_of_local_register_with_address(&addr)
+            .wrap_err(format!("Could not find a register with address in local user data: {address}"))
+            .with_suggestion(|| "This register is not known to this client, only its owner can merge it.")
+            .with_suggestion(|| "If you indeed have created this register before, retry using its name by using the --name flag")?;
+        Client::register_key_from_name(&main_registers_key, &name_str)
+    };
+    let addr = RegisterAddress::new(register_key.public_key());
+
+    let client = crate::actions::connect_to_network(network_context)
+        .await
+        .map_err(|(err, _)| err)?;
+
+    let branches = client
+        .register_branches(&addr)
+        .await
+        .wrap_err(format!("Error getting register branches at: {address}"))?;
+    if !branches.is_forked() {
+        println!("✅ Register at {address} is not forked, there is nothing to merge");
+        return Ok(());
+    }
+
+    let value = match value {
+        Some(value) => parse_register_value(&value, hex)?,
+        None => {
+            let heads: Vec<_> = branches
+                .open_branches()
+                .filter_map(|branch| branch.head())
+                .collect();
+            println!("The register has {} open branches:", heads.len());
+            for (i, head) in heads.iter().enumerate() {
+                println!(
+                    "  {}: {}",
+                    i + 1,
+                    format_history_entry(head, hex, &branches)
+                );
+            }
+            let input = crate::wallet::input::get_wallet_selection_input(
+                "Enter the number of the branch value to keep, or a new value for the merge entry:",
+            );
+            match input.parse::<usize>() {
+                Ok(index) if (1..=heads.len()).contains(&index) => heads[index - 1].value,
+                _ if input.is_empty() => return Err(eyre!("No merge value given, aborting")),
+                _ => parse_register_value(&input, hex)?,
+            }
+        }
+    };
+
+    let mut wallet = load_wallet(client.evm_network())?;
+    let max_fee_per_gas =
+        get_max_fee_per_gas_from_opt_param(max_fee_per_gas_param, client.evm_network())?;
+    wallet.set_transaction_config(TransactionConfig {
+        max_fee_per_gas,
+        ..Default::default()
+    });
+
+    println!("Merging the branches of register at {address}...");
+    info!("Merging the branches of register at {address}");
+    let cost = client
+        .register_merge(&register_key, value, wallet.into())
+        .await
+        .wrap_err(format!("Failed to merge register at address: {address}"))?;
+
+    println!("✅ Successfully merged register");
+    println!("With value: [{}]", format_register_value(&value, hex));
+    println!("Total cost: {cost} AttoTokens");
+    info!("Successfully merged register at address: {address}");
+    Ok(())
+}
+
+fn parse_register_value(value: &str, hex: bool) -> Result<RegisterValue> {
+    let value_bytes = if hex {
+        hex::decode(value.trim_start_matches("0x"))
+            .wrap_err("Failed to decode hex value")
+            .with_suggestion(|| "Make sure the value is a valid hex string")?
+    } else {
+        value.as_bytes().to_vec()
+    };
+    Ok(Client::register_value_from_bytes(&value_bytes)?)
+}
+
+fn format_register_value(value: &RegisterValue, hex: bool) -> String {
+    if hex {
+        hex::encode(value)
+    } else {
+        String::from_utf8_lossy(value)
+            .trim_end_matches('\0')
+            .to_string()
+    }
+}
+
+fn format_history_entry(
+    entry: &RegisterHistoryEntry,
+    hex: bool,
+    branches: &RegisterBranches,
+) -> String {
+    let head = if branches.head_pointer == Some(entry.address) {
+        " <- head pointer"
+    } else {
+        ""
+    };
+    format!(
+        "{} [{}]{head}",
+        entry.address.to_hex(),
+        format_register_value(&entry.value, hex)
+    )
+}
@@ -32,6 +32,7 @@ pub(crate) fn upload_exit_code(err: &UploadError) -> i32 {
         UploadError::PutError(err) => put_error_exit_code(err),
         UploadError::Encryption(_) => SELF_ENCRYPTION_ERROR,
         UploadError::MerkleUpload(_) => 44, // Same as PutError::MerkleBatch
+        UploadError::Source(_) => NETWORK_ERROR,
     }
 }
 
@@ -48,6 +49,7 @@ pub(crate) fn pay_error_exit_code(err: &PayError) -> i32 {
         PayError::EvmWalletError(_) => 22,
         PayError::SelfEncryption(_) => SELF_ENCRYPTION_ERROR,
         PayError::Cost(_) => 23,
+        PayError::ReadOnlyClient => 24,
     }
 }
 
@@ -115,8 +117,9 @@ pub(crate) fn connect_error_exit_code(err: &ConnectError) -> i32 {
         ConnectError::EvmNetworkError(_) => 61,
         ConnectError::Bootstrap(error) => bootstrap_error_exit_code(error),
         ConnectError::TimedOut => 59,
-        ConnectError::TimedOutWithIncompatibleProtocol(_, _) => 60,
+        ConnectError::ProtocolMismatch(_) => 60,
         ConnectError::NoKnownPeers(_) => 51, // todo: uses duplicate exit code from `BootstrapError::NoBootstrapPeersFound`
+        ConnectError::InvalidConfig(_) => INVALID_INPUT_EXIT_CODE,
     }
 }
 
@@ -27,7 +27,7 @@ pub use access::user_data;
 #[cfg(feature = "metrics")]
 use ant_logging::metrics::init_metrics;
 use ant_logging::{LogBuilder, LogFormat, ReloadHandle, WorkerGuard};
-use autonomi::version;
+use autonomi::{ClientNetwork, version};
 use clap::Parser;
 use color_eyre::Result;
 use opt::{NetworkId, Opt};
@@ -74,6 +74,10 @@ async fn main() -> Result<()> {
     }
 
     let _log_guards = init_logging_and_metrics(&opt)?;
+    if let Some(network) = opt.network {
+        opt.network_id = NetworkId::from_network(network);
+        opt.peers.local |= network == ClientNetwork::Local;
+    }
     if opt.peers.local {
         #[cfg(feature = "metrics")]
         tokio::spawn(init_metrics(std::process::id()));
@@ -107,7 +111,7 @@ fn init_logging_and_metrics(opt: &Opt) -> Result<(ReloadHandle, Option<WorkerGua
     let mute = opt
         .command
         .as_ref()
-        .map(|cmd| matches!(cmd, SubCmd::Analyze { .. }))
+        .map(|cmd| matches!(cmd, SubCmd::Analyze { .. } | SubCmd::Network { .. }))
         .unwrap_or(false);
     let mut log_builder = LogBuilder::new(logging_targets);
     log_builder.output_dest(opt.log_output_dest.clone());
@@ -8,6 +8,7 @@
 
 use crate::commands::SubCmd;
 use ant_logging::{LogFormat, LogOutputDest};
+use autonomi::ClientNetwork;
 use autonomi::InitialPeersConfig;
 use autonomi::Network as EvmNetwork;
 use autonomi::get_evm_network;
@@ -51,6 +52,16 @@ impl NetworkId {
         Self::new(ALPHA_NETWORK_ID)
     }
 
+    /// Create a new NetworkId for a named network.
+    pub fn from_network(network: ClientNetwork) -> Self {
+        match network {
+            ClientNetwork::Mainnet => Self::new(MAIN_NETWORK_ID),
+            ClientNetwork::Alpha => Self::alpha(),
+            ClientNetwork::Local => Self::local(),
+            ClientNetwork::Custom(id) => Self::new(id),
+        }
+    }
+
     /// Get the raw ID value.
     pub fn as_u8(&self) -> u8 {
         self.id
@@ -92,7 +103,7 @@ impl std::str::FromStr for NetworkId {
 #[command(author, version, about, long_about = None)]
 pub(crate) struct Opt {
     /// Set to connect to the alpha network.
-    #[clap(long)]
+    #[clap(long, conflicts_with = "network")]
     pub alpha: bool,
 
     // Available subcommands. This is optional to allow `--version` to work without a subcommand.
@@ -142,6 +153,15 @@ pub(crate) struct Opt {
     #[clap(long, verbatim_doc_comment, default_value = "1")]
     pub network_id: NetworkId,
 
+    /// Specify the network to connect to by name, with its network ID, contacts and EVM network.
+    ///
+    /// Valid values are:
+    ///  - mainnet (default)
+    ///  - alpha
+    ///  - local
+    #[clap(long, verbatim_doc_comment, conflicts_with = "network_id")]
+    pub network: Option<ClientNetwork>,
+
     /// Prevent verification of data storage on the network.
     ///
     /// This may increase operation speed, but offers no guarantees that operations were successful.
@@ -151,6 +171,12 @@ pub(crate) struct Opt {
     #[command(flatten)]
     pub(crate) peers: InitialPeersConfig,
 
+    /// Also dial peers over TCP, for networks where UDP, and so QUIC, is blocked.
+    ///
+    /// Only the nodes running with their TCP fallback enabled can be reached this way.
+    #[clap(global = true, long)]
+    pub tcp_fallback: bool,
+
     /// Print the package version.
     #[cfg(not(feature = "nightly"))]
     #[clap(long)]
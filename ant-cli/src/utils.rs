@@ -11,7 +11,7 @@ use ant_protocol::NetworkAddress;
 use autonomi::PublicKey;
 use autonomi::client::data_types::chunk::ChunkAddress;
 use autonomi::client::data_types::graph::GraphEntryAddress;
-use autonomi::client::payment::Receipt;
+use autonomi::client::upload_plan::UploadBatchPlan;
 use autonomi::client::{Amount, ClientEvent, UploadSummary};
 use autonomi::networking::PeerId;
 use color_eyre::Result;
@@ -84,7 +84,11 @@ pub fn collect_upload_summary(
         let mut tokens_spent: Amount = Amount::from(0);
         let mut record_count = 0;
         let mut records_already_paid = 0;
-        let mut accumulated_regular_receipt = Receipt::new();
+        // Start from the payments of a resumed upload, so that saving the new ones keeps them
+        let mut accumulated_regular_receipt = file_name
+            .as_deref()
+            .and_then(|file| cached_payments::load_payment_for_file(file).ok().flatten())
+            .unwrap_or_default();
 
         loop {
             tokio::select! {
@@ -112,6 +116,11 @@ pub fn collect_upload_summary(
                                 }
                             }
                         }
+                        Some(ClientEvent::UploadBatchPlanned(plan)) => log_batch_plan(&plan),
+                        Some(ClientEvent::QuotesRefreshed(addresses)) => {
+                            info!("Re-quoted {} addresses whose quotes expired", addresses.len());
+                        }
+                        Some(_) => {}
                         None => break,
                     }
                 }
@@ -147,6 +156,14 @@ pub fn collect_upload_summary(
                         }
                     }
                 }
+                ClientEvent::UploadBatchPlanned(plan) => log_batch_plan(&plan),
+                ClientEvent::QuotesRefreshed(addresses) => {
+                    info!(
+                        "Re-quoted {} addresses whose quotes expired",
+                        addresses.len()
+                    );
+                }
+                _ => {}
             }
         }
 
@@ -159,3 +176,17 @@ pub fn collect_upload_summary(
 
     (stats_thread, upload_completed_tx)
 }
+
+fn log_batch_plan(plan: &UploadBatchPlan) {
+    let composition: Vec<String> = plan
+        .neighbourhoods
+        .iter()
+        .map(|n| format!("{:03x}:{}", n.prefix, n.chunks.len()))
+        .collect();
+    info!(
+        "Upload batch of {} chunks in {} neighbourhoods: {}",
+        plan.chunk_count(),
+        plan.neighbourhoods.len(),
+        composition.join(" ")
+    );
+}
@@ -22,13 +22,16 @@ pub use evmlib::common::Address as RewardsAddress;
 pub use evmlib::common::Address as EvmAddress;
 pub use evmlib::common::QuotePayment;
 pub use evmlib::common::U256;
-pub use evmlib::common::{QuoteHash, TxHash};
+pub use evmlib::common::{Hash, QuoteHash, TxHash};
 pub use evmlib::contract::merkle_payment_vault;
 pub use evmlib::contract::payment_vault;
+pub use evmlib::contract_registry;
 pub use evmlib::cryptography;
 #[cfg(feature = "external-signer")]
 pub use evmlib::external_signer;
 pub use evmlib::merkle_batch_payment;
+pub use evmlib::payment_events;
+pub use evmlib::testnet;
 pub use evmlib::transaction_config::{MaxFeePerGas, TransactionConfig};
 pub use evmlib::utils;
 pub use evmlib::utils::get_evm_network;
@@ -37,6 +40,7 @@ pub use evmlib::utils::{
 };
 pub use evmlib::wallet::Error as EvmWalletError;
 pub use evmlib::wallet::Wallet as EvmWallet;
+pub use evmlib::wallet::{balance_of_gas_tokens, balance_of_tokens};
 
 mod amount;
 mod data_payments;
@@ -11,6 +11,8 @@ use crate::{
     error::{Error, Result},
 };
 use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Mutex;
 use tracing_appender::non_blocking::WorkerGuard;
 use tracing_core::{Event, Level, Subscriber};
 use tracing_subscriber::{
@@ -35,9 +37,27 @@ const ALL_ANT_LOGS: &str = "all";
 const VERBOSE_ANT_LOGS: &str = "v";
 
 /// Handle that implements functions to change the log level on the fly.
-pub struct ReloadHandle(pub(crate) Handle<Box<dyn Filter<Registry> + Send + Sync>, Registry>);
+pub struct ReloadHandle {
+    handle: Handle<Box<dyn Filter<Registry> + Send + Sync>, Registry>,
+    /// The targets the filter was created with
+    initial_targets: BTreeMap<String, Level>,
+    /// The targets of the current filter
+    targets: Mutex<BTreeMap<String, Level>>,
+}
 
 impl ReloadHandle {
+    pub(crate) fn new(
+        handle: Handle<Box<dyn Filter<Registry> + Send + Sync>, Registry>,
+        targets: Vec<(String, Level)>,
+    ) -> Self {
+        let targets: BTreeMap<String, Level> = targets.into_iter().collect();
+        Self {
+            handle,
+            initial_targets: targets.clone(),
+            targets: Mutex::new(targets),
+        }
+    }
+
     /// Modify the log level to the provided CSV value
     /// Example input: `libp2p=DEBUG,tokio=INFO,all,sn_client=ERROR`
     ///
@@ -45,11 +65,45 @@ impl ReloadHandle {
     /// `sn_client=ERROR` in the above example will be used instead of the TRACE level set by "all" keyword.
     pub fn modify_log_level(&self, logging_value: &str) -> Result<()> {
         let targets: Vec<(String, Level)> = get_logging_targets(logging_value)?;
-        self.0.modify(|old_filter| {
+        self.update_targets(|current| *current = targets.into_iter().collect())
+    }
+
+    /// Set the log level of a single target, e.g. `ant_node::replication` to `debug`, keeping the
+    /// levels of the other targets.
+    pub fn set_log_level(&self, target: &str, log_level: &str) -> Result<()> {
+        let level = get_log_level_from_str(log_level)?;
+        self.update_targets(|current| {
+            let _ = current.insert(target.to_string(), level);
+        })
+    }
+
+    /// Reload the log levels from a file holding a value in the format of `ANT_LOG`, e.g. when
+    /// the process receives a SIGHUP. The initial log levels are restored if the file is missing.
+    pub fn reload_from_file(&self, path: &Path) -> Result<()> {
+        match std::fs::read_to_string(path) {
+            Ok(logging_value) => self.modify_log_level(logging_value.trim()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                let initial_targets = self.initial_targets.clone();
+                self.update_targets(|current| *current = initial_targets)
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn update_targets(&self, update: impl FnOnce(&mut BTreeMap<String, Level>)) -> Result<()> {
+        let mut current = self.targets.lock().map_err(|_| {
+            Error::LoggingConfiguration("The log targets lock is poisoned".to_string())
+        })?;
+        let mut targets = current.clone();
+        update(&mut targets);
+
+        let filter_targets = targets.clone();
+        self.handle.modify(|old_filter| {
             let new_filter: Box<dyn Filter<Registry> + Send + Sync> =
-                Box::new(Targets::new().with_targets(targets));
+                Box::new(Targets::new().with_targets(filter_targets));
             *old_filter = new_filter;
         })?;
+        *current = targets;
 
         Ok(())
     }
@@ -202,14 +256,44 @@ impl TracingLayers {
         };
 
         let target_filters: Box<dyn Filter<Registry> + Send + Sync> =
-            Box::new(Targets::new().with_targets(targets));
+            Box::new(Targets::new().with_targets(targets.clone()));
 
         let (filter, reload_handle) = reload::Layer::new(target_filters);
 
         let layer = layer.with_filter(filter);
         self.layers.push(Box::new(layer));
 
-        Ok(ReloadHandle(reload_handle))
+        Ok(ReloadHandle::new(reload_handle, targets))
+    }
+
+    /// Ship the logs as JSON to a remote collector, in addition to the output destination.
+    #[cfg(feature = "log-shipping")]
+    pub(crate) fn shipping_layer(
+        &mut self,
+        target: crate::LogShippingTarget,
+        default_logging_targets: Vec<(String, Level)>,
+        print_updates_to_stdout: bool,
+    ) -> Result<()> {
+        if print_updates_to_stdout {
+            println!("Shipping logs to {target}");
+        }
+        let writer = crate::shipping::ShippingWriter::spawn(target)?;
+
+        let targets = match std::env::var("ANT_LOG") {
+            Ok(sn_log_val) => get_logging_targets(&sn_log_val)?,
+            Err(_) => default_logging_targets,
+        };
+
+        let target_filters: Box<dyn Filter<Registry> + Send + Sync> =
+            Box::new(Targets::new().with_targets(targets));
+        let shipping_layer = tracing_fmt::layer()
+            .json()
+            .flatten_event(true)
+            .with_writer(writer)
+            .with_filter(target_filters)
+            .boxed();
+        self.layers.push(shipping_layer);
+        Ok(())
     }
 
     #[cfg(feature = "otlp")]
@@ -16,6 +16,10 @@ mod error;
 mod layers;
 #[cfg(feature = "process-metrics")]
 pub mod metrics;
+#[cfg(feature = "log-shipping")]
+mod shipping;
+#[cfg(feature = "otlp")]
+pub mod trace_context;
 
 use crate::error::Result;
 use layers::TracingLayers;
@@ -27,6 +31,8 @@ use tracing_subscriber::{prelude::__tracing_subscriber_SubscriberExt, util::Subs
 
 pub use error::Error;
 pub use layers::ReloadHandle;
+#[cfg(feature = "log-shipping")]
+pub use shipping::LogShippingTarget;
 pub use tracing_appender::non_blocking::WorkerGuard;
 
 // re-exporting the tracing crate's Level as it is used in our public API
@@ -119,6 +125,8 @@ pub struct LogBuilder {
     max_archived_log_files: Option<usize>,
     /// Setting this would print the ant_logging related updates to stdout.
     print_updates_to_stdout: bool,
+    #[cfg(feature = "log-shipping")]
+    log_shipping: Option<LogShippingTarget>,
 }
 
 impl LogBuilder {
@@ -134,6 +142,8 @@ impl LogBuilder {
             max_log_files: None,
             max_archived_log_files: None,
             print_updates_to_stdout: true,
+            #[cfg(feature = "log-shipping")]
+            log_shipping: None,
         }
     }
 
@@ -162,6 +172,14 @@ impl LogBuilder {
         self.print_updates_to_stdout = print;
     }
 
+    /// Ship the logs as JSON to a remote collector, in addition to the output destination.
+    ///
+    /// If not set, the target is read from the `ANT_LOG_SHIPPING` env variable.
+    #[cfg(feature = "log-shipping")]
+    pub fn log_shipping(&mut self, target: LogShippingTarget) {
+        self.log_shipping = Some(target);
+    }
+
     /// Inits node logging, returning the NonBlocking guard if present.
     /// This guard should be held for the life of the program.
     ///
@@ -178,6 +196,24 @@ impl LogBuilder {
             self.print_updates_to_stdout,
         )?;
 
+        #[cfg(feature = "log-shipping")]
+        {
+            let log_shipping = match self.log_shipping {
+                Some(target) => Some(target),
+                None => match std::env::var("ANT_LOG_SHIPPING") {
+                    Ok(value) => Some(LogShippingTarget::parse_from_str(&value)?),
+                    Err(_) => None,
+                },
+            };
+            if let Some(target) = log_shipping {
+                layers.shipping_layer(
+                    target,
+                    self.default_logging_targets.clone(),
+                    self.print_updates_to_stdout,
+                )?;
+            }
+        }
+
         #[cfg(feature = "otlp")]
         {
             match std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT") {
@@ -542,12 +578,13 @@ mod tests {
 
         let test_target = "ant_logging::tests".to_string();
         // to enable logs just for the test.
+        let initial_targets = vec![(test_target.clone(), Level::TRACE)];
         let target_filters: Box<dyn Filter<Registry> + Send + Sync> =
-            Box::new(Targets::new().with_targets(vec![(test_target.clone(), Level::TRACE)]));
+            Box::new(Targets::new().with_targets(initial_targets.clone()));
 
         // add the reload layer
         let (filter, handle) = reload::Layer::new(target_filters);
-        let reload_handle = ReloadHandle(handle);
+        let reload_handle = ReloadHandle::new(handle, initial_targets);
         let layer = layer.with_filter(filter);
         tracing_subscriber::registry().with(layer).try_init()?;
 
@@ -586,6 +623,27 @@ mod tests {
             assert!(events[1].contains("First warn event"));
         }
 
+        reload_handle.set_log_level(&test_target, "trace")?;
+        trace!("Third trace event");
+
+        // a missing file restores the initial levels
+        reload_handle.modify_log_level("ant_logging::tests=WARN")?;
+        reload_handle.reload_from_file(std::path::Path::new("/nonexistent/ant_log"))?;
+        trace!("Fourth trace event");
+
+        {
+            let buf = global_buf().lock().unwrap();
+
+            let events: Vec<&str> = std::str::from_utf8(&buf)
+                .expect("Logs contain invalid UTF8")
+                .lines()
+                .collect();
+
+            assert_eq!(events.len(), 4);
+            assert!(events[2].contains("Third trace event"));
+            assert!(events[3].contains("Fourth trace event"));
+        }
+
         Ok(())
     }
 }
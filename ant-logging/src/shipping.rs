@@ -0,0 +1,374 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Shipping of the logs, as JSON lines, to a remote collector.
+//!
+//! The log lines are handed over to a background thread through a bounded channel, so logging
+//! never blocks on the network. The thread sends them in batches, and buffers them while the
+//! collector is unreachable, retrying with an exponential backoff. When the buffer is full, the
+//! oldest lines are dropped.
+
+use crate::error::{Error, Result};
+use std::{
+    collections::VecDeque,
+    env,
+    io::{self, Write},
+    net::{TcpStream, UdpSocket},
+    sync::mpsc::{self, Receiver, RecvTimeoutError, SyncSender, TrySendError},
+    time::{Duration, Instant},
+};
+use tracing_subscriber::fmt::MakeWriter;
+
+/// Number of log lines that can be waiting to be picked up by the shipping thread.
+const CHANNEL_CAPACITY: usize = 10_000;
+/// Number of log lines kept while the collector is unreachable.
+const MAX_BUFFERED_LINES: usize = 50_000;
+/// Maximum number of log lines sent in a single batch.
+const MAX_BATCH_LINES: usize = 1_000;
+/// Interval at which the buffered log lines are sent, if the batch is not full before.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(2);
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+/// Syslog facility of the messages: user-level messages.
+const SYSLOG_FACILITY: u8 = 1;
+
+/// Remote collector to ship the logs to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogShippingTarget {
+    /// POST batches of newline delimited JSON logs to an HTTP(S) bulk endpoint, e.g.
+    /// `https://collector.example.com/logs`
+    Http(String),
+    /// Send each JSON log line as an RFC 5424 syslog message over UDP, e.g.
+    /// `syslog://collector.example.com:514`
+    SyslogUdp(String),
+    /// Send each JSON log line as an RFC 5424 syslog message over TCP, with octet counting
+    /// framing, e.g. `syslog+tcp://collector.example.com:601`
+    SyslogTcp(String),
+}
+
+impl LogShippingTarget {
+    pub fn parse_from_str(val: &str) -> Result<Self> {
+        if val.starts_with("http://") || val.starts_with("https://") {
+            Ok(LogShippingTarget::Http(val.to_string()))
+        } else if let Some(addr) = val
+            .strip_prefix("syslog://")
+            .or_else(|| val.strip_prefix("syslog+udp://"))
+        {
+            Ok(LogShippingTarget::SyslogUdp(addr.to_string()))
+        } else if let Some(addr) = val.strip_prefix("syslog+tcp://") {
+            Ok(LogShippingTarget::SyslogTcp(addr.to_string()))
+        } else {
+            Err(Error::LoggingConfiguration(format!(
+                "Invalid log shipping target {val:?}, expected an http(s)://, syslog:// or syslog+tcp:// URL"
+            )))
+        }
+    }
+}
+
+impl std::fmt::Display for LogShippingTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            LogShippingTarget::Http(url) => write!(f, "{url}"),
+            LogShippingTarget::SyslogUdp(addr) => write!(f, "syslog://{addr}"),
+            LogShippingTarget::SyslogTcp(addr) => write!(f, "syslog+tcp://{addr}"),
+        }
+    }
+}
+
+/// Writer handing the formatted log lines over to the shipping thread.
+#[derive(Clone)]
+pub(crate) struct ShippingWriter {
+    tx: SyncSender<Vec<u8>>,
+}
+
+impl ShippingWriter {
+    /// Spawn the thread shipping the logs to the target, returning the writer feeding it.
+    pub(crate) fn spawn(target: LogShippingTarget) -> Result<Self> {
+        let shipper = Shipper::new(target)?;
+        let (tx, rx) = mpsc::sync_channel(CHANNEL_CAPACITY);
+        let _handle = std::thread::Builder::new()
+            .name("log-shipping".to_string())
+            .spawn(move || run_shipping(shipper, rx))?;
+        Ok(Self { tx })
+    }
+}
+
+impl Write for ShippingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self.tx.try_send(buf.to_vec()) {
+            // A full channel means the shipping thread can't keep up, drop the line rather than
+            // slowing down the program.
+            Ok(()) | Err(TrySendError::Full(_)) => Ok(buf.len()),
+            Err(TrySendError::Disconnected(_)) => Err(io::Error::new(
+                io::ErrorKind::BrokenPipe,
+                "log shipping thread has stopped",
+            )),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for ShippingWriter {
+    type Writer = ShippingWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+fn run_shipping(mut shipper: Shipper, rx: Receiver<Vec<u8>>) {
+    let mut buffered: VecDeque<Vec<u8>> = VecDeque::new();
+    let mut last_flush = Instant::now();
+    let mut backoff = INITIAL_BACKOFF;
+    let mut retry_at: Option<Instant> = None;
+
+    loop {
+        let disconnected = match rx.recv_timeout(FLUSH_INTERVAL) {
+            Ok(line) => {
+                buffered.push_back(line);
+                buffered.extend(rx.try_iter());
+                false
+            }
+            Err(RecvTimeoutError::Timeout) => false,
+            Err(RecvTimeoutError::Disconnected) => true,
+        };
+        if buffered.len() > MAX_BUFFERED_LINES {
+            let excess = buffered.len() - MAX_BUFFERED_LINES;
+            let _ = buffered.drain(..excess);
+        }
+
+        let due = buffered.len() >= MAX_BATCH_LINES || last_flush.elapsed() >= FLUSH_INTERVAL;
+        let backing_off = retry_at.is_some_and(|retry_at| Instant::now() < retry_at);
+        if (due || disconnected) && !backing_off {
+            last_flush = Instant::now();
+            while !buffered.is_empty() {
+                let batch_len = buffered.len().min(MAX_BATCH_LINES);
+                match shipper.send(buffered.range(..batch_len)) {
+                    Ok(()) => {
+                        let _ = buffered.drain(..batch_len);
+                        backoff = INITIAL_BACKOFF;
+                        retry_at = None;
+                    }
+                    Err(_) => {
+                        // The errors can't be logged, they would be shipped as well.
+                        retry_at = Some(Instant::now() + backoff);
+                        backoff = (backoff * 2).min(MAX_BACKOFF);
+                        break;
+                    }
+                }
+            }
+        }
+
+        if disconnected {
+            return;
+        }
+    }
+}
+
+enum Shipper {
+    Http {
+        runtime: tokio::runtime::Runtime,
+        client: reqwest::Client,
+        url: String,
+    },
+    SyslogUdp {
+        socket: UdpSocket,
+        addr: String,
+        header: SyslogHeader,
+    },
+    SyslogTcp {
+        stream: Option<TcpStream>,
+        addr: String,
+        header: SyslogHeader,
+    },
+}
+
+impl Shipper {
+    fn new(target: LogShippingTarget) -> Result<Self> {
+        let shipper = match target {
+            LogShippingTarget::Http(url) => Shipper::Http {
+                runtime: tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()?,
+                client: reqwest::Client::builder()
+                    .timeout(HTTP_TIMEOUT)
+                    .build()
+                    .map_err(|err| Error::LoggingConfiguration(err.to_string()))?,
+                url,
+            },
+            LogShippingTarget::SyslogUdp(addr) => Shipper::SyslogUdp {
+                socket: UdpSocket::bind("0.0.0.0:0")?,
+                addr,
+                header: SyslogHeader::new(),
+            },
+            LogShippingTarget::SyslogTcp(addr) => Shipper::SyslogTcp {
+                stream: None,
+                addr,
+                header: SyslogHeader::new(),
+            },
+        };
+        Ok(shipper)
+    }
+
+    fn send<'a>(&mut self, lines: impl Iterator<Item = &'a Vec<u8>>) -> io::Result<()> {
+        match self {
+            Shipper::Http {
+                runtime,
+                client,
+                url,
+            } => {
+                let body: Vec<u8> = lines.flatten().copied().collect();
+                runtime.block_on(async {
+                    let _response = client
+                        .post(url.as_str())
+                        .header("Content-Type", "application/x-ndjson")
+                        .body(body)
+                        .send()
+                        .await
+                        .and_then(|response| response.error_for_status())
+                        .map_err(io::Error::other)?;
+                    Ok(())
+                })
+            }
+            Shipper::SyslogUdp {
+                socket,
+                addr,
+                header,
+            } => {
+                for line in lines {
+                    let _ = socket.send_to(&header.message(line), addr.as_str())?;
+                }
+                Ok(())
+            }
+            Shipper::SyslogTcp {
+                stream,
+                addr,
+                header,
+            } => {
+                let mut frames = Vec::new();
+                for line in lines {
+                    let message = header.message(line);
+                    frames.extend_from_slice(format!("{} ", message.len()).as_bytes());
+                    frames.extend_from_slice(&message);
+                }
+                if stream.is_none() {
+                    *stream = Some(TcpStream::connect(addr.as_str())?);
+                }
+                if let Some(connection) = stream.as_mut()
+                    && let Err(err) = connection.write_all(&frames)
+                {
+                    // Reconnect on the next attempt
+                    *stream = None;
+                    return Err(err);
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// The fields of the RFC 5424 header that don't change between the messages.
+struct SyslogHeader {
+    hostname: String,
+    app_name: String,
+    proc_id: u32,
+}
+
+impl SyslogHeader {
+    fn new() -> Self {
+        let hostname = env::var("HOSTNAME")
+            .or_else(|_| env::var("COMPUTERNAME"))
+            .unwrap_or_else(|_| "-".to_string());
+        let app_name = env::current_exe()
+            .ok()
+            .and_then(|path| {
+                path.file_stem()
+                    .map(|stem| stem.to_string_lossy().into_owned())
+            })
+            .unwrap_or_else(|| "autonomi".to_string());
+        Self {
+            hostname,
+            app_name,
+            proc_id: std::process::id(),
+        }
+    }
+
+    /// Wrap a JSON log line in an RFC 5424 message, with the severity taken from its level.
+    fn message(&self, line: &[u8]) -> Vec<u8> {
+        let line = line.strip_suffix(b"\n").unwrap_or(line);
+        let level = serde_json::from_slice::<serde_json::Value>(line)
+            .ok()
+            .and_then(|value| value.get("level")?.as_str().map(str::to_string))
+            .unwrap_or_default();
+        let priority = SYSLOG_FACILITY * 8 + syslog_severity(&level);
+        let timestamp = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+
+        let mut message = format!(
+            "<{priority}>1 {timestamp} {} {} {} - - ",
+            self.hostname, self.app_name, self.proc_id
+        )
+        .into_bytes();
+        message.extend_from_slice(line);
+        message
+    }
+}
+
+fn syslog_severity(level: &str) -> u8 {
+    match level {
+        "ERROR" => 3,
+        "WARN" => 4,
+        "INFO" => 6,
+        _ => 7,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_log_shipping_target() {
+        assert_eq!(
+            LogShippingTarget::parse_from_str("https://logs.example.com/bulk").ok(),
+            Some(LogShippingTarget::Http(
+                "https://logs.example.com/bulk".to_string()
+            ))
+        );
+        assert_eq!(
+            LogShippingTarget::parse_from_str("syslog://10.0.0.1:514").ok(),
+            Some(LogShippingTarget::SyslogUdp("10.0.0.1:514".to_string()))
+        );
+        assert_eq!(
+            LogShippingTarget::parse_from_str("syslog+tcp://10.0.0.1:601").ok(),
+            Some(LogShippingTarget::SyslogTcp("10.0.0.1:601".to_string()))
+        );
+        assert!(LogShippingTarget::parse_from_str("ftp://10.0.0.1").is_err());
+    }
+
+    #[test]
+    fn test_syslog_message() {
+        let header = SyslogHeader {
+            hostname: "host".to_string(),
+            app_name: "antnode".to_string(),
+            proc_id: 42,
+        };
+        let line = br#"{"level":"WARN","fields":{"message":"hello"}}"#;
+        let message =
+            String::from_utf8(header.message(&[&line[..], b"\n"].concat())).expect("utf8 message");
+
+        assert!(message.starts_with("<12>1 "));
+        assert!(
+            message
+                .ends_with(r#" host antnode 42 - - {"level":"WARN","fields":{"message":"hello"}}"#)
+        );
+    }
+}
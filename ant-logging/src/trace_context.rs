@@ -0,0 +1,37 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! W3C trace context propagation, to continue a trace across processes.
+//!
+//! The sender attaches the `traceparent` of its current span to the outgoing message,
+//! the receiver sets it as the parent of the span handling that message. Both export their spans
+//! via OTLP, so the whole request shows up as a single trace.
+
+use opentelemetry::propagation::TextMapPropagator;
+use opentelemetry::sdk::propagation::TraceContextPropagator;
+use std::collections::HashMap;
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+const TRACEPARENT_HEADER: &str = "traceparent";
+
+/// Returns the W3C `traceparent` of the current span, if it is part of a trace.
+pub fn current_traceparent() -> Option<String> {
+    let context = Span::current().context();
+    let mut carrier = HashMap::new();
+    TraceContextPropagator::new().inject_context(&context, &mut carrier);
+    carrier.remove(TRACEPARENT_HEADER)
+}
+
+/// Continue the trace described by a W3C `traceparent` in the provided span.
+/// An invalid `traceparent` is ignored, the span then starts a new trace.
+pub fn set_span_parent(span: &Span, traceparent: &str) {
+    let carrier = HashMap::from([(TRACEPARENT_HEADER.to_string(), traceparent.to_string())]);
+    let context = TraceContextPropagator::new().extract(&carrier);
+    span.set_parent(context);
+}
@@ -9,7 +9,9 @@
 use ant_bootstrap::InitialPeersConfig;
 use ant_evm::{EvmNetwork, RewardsAddress};
 use ant_logging::LogFormat;
-use ant_service_management::node::push_arguments_from_initial_peers_config;
+use ant_service_management::{
+    NodeContainer, node::push_arguments_from_initial_peers_config, systemd::node_unit_contents,
+};
 use color_eyre::{Result, eyre::eyre};
 use service_manager::{RestartPolicy, ServiceInstallCtx, ServiceLabel};
 use std::{
@@ -93,6 +95,8 @@ pub struct InstallNodeServiceCtxBuilder {
     pub rpc_socket_addr: SocketAddr,
     pub service_user: Option<String>,
     pub stop_on_upgrade: bool,
+    /// Install the service as a systemd unit with a watchdog of this many seconds.
+    pub watchdog_secs: Option<u64>,
     pub write_older_cache_files: bool,
 }
 
@@ -156,12 +160,21 @@ impl InstallNodeServiceCtxBuilder {
         if self.stop_on_upgrade {
             args.push(OsString::from("--stop-on-upgrade"));
         }
+        // The status of the service is reported from the node info
+        args.push(OsString::from("--write-node-info"));
 
         // The EVM details must always be the last arguments.
         args.push(OsString::from(self.evm_network.to_string()));
         if let EvmNetwork::Custom(custom_network) = &self.evm_network {
             args.push(OsString::from("--rpc-url"));
-            args.push(OsString::from(custom_network.rpc_url_http.to_string()));
+            // The fallback endpoints follow the primary one
+            let rpc_urls: Vec<String> = self
+                .evm_network
+                .rpc_urls()
+                .iter()
+                .map(ToString::to_string)
+                .collect();
+            args.push(OsString::from(rpc_urls.join(",")));
             args.push(OsString::from("--payment-token-address"));
             args.push(OsString::from(
                 custom_network.payment_token_address.to_string(),
@@ -176,7 +189,7 @@ impl InstallNodeServiceCtxBuilder {
             }
         }
 
-        Ok(ServiceInstallCtx {
+        let mut install_ctx = ServiceInstallCtx {
             args,
             autostart: self.autostart,
             contents: None,
@@ -186,7 +199,13 @@ impl InstallNodeServiceCtxBuilder {
             restart_policy: self.restart_policy,
             username: self.service_user.clone(),
             working_directory: None,
-        })
+        };
+        if let Some(watchdog_secs) = self.watchdog_secs {
+            // Only user mode services run without a service user
+            let user_mode = self.service_user.is_none();
+            install_ctx.contents = Some(node_unit_contents(&install_ctx, user_mode, watchdog_secs));
+        }
+        Ok(install_ctx)
     }
 }
 
@@ -196,6 +215,8 @@ pub struct AddNodeServiceOptions {
     pub antnode_src_path: PathBuf,
     pub auto_restart: bool,
     pub auto_set_nat_flags: bool,
+    /// The container the nodes run in, `None` to install them as native services.
+    pub container: Option<NodeContainer>,
     pub count: Option<u16>,
     pub delete_antnode_src: bool,
     pub enable_metrics_server: bool,
@@ -220,6 +241,8 @@ pub struct AddNodeServiceOptions {
     pub user: Option<String>,
     pub user_mode: bool,
     pub version: String,
+    /// Install the services as systemd units with a watchdog of this many seconds.
+    pub watchdog_secs: Option<u64>,
     pub write_older_cache_files: bool,
 }
 
@@ -229,6 +252,11 @@ pub struct AddDaemonServiceOptions {
     pub daemon_install_bin_path: PathBuf,
     pub daemon_src_bin_path: PathBuf,
     pub port: u16,
+    /// The certificate and key the daemon serves TLS with, in PEM format
+    pub tls_cert_path: Option<PathBuf>,
+    pub tls_key_path: Option<PathBuf>,
+    /// The CA client certificates must be signed by, for mutual TLS
+    pub tls_client_ca_path: Option<PathBuf>,
     pub user: String,
     pub version: String,
 }
@@ -269,6 +297,7 @@ mod tests {
             rpc_socket_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
             service_user: None,
             stop_on_upgrade: true,
+            watchdog_secs: None,
             write_older_cache_files: false,
         }
     }
@@ -291,6 +320,7 @@ mod tests {
                 )
                 .unwrap(),
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             init_peers_config: InitialPeersConfig::default(),
             log_dir_path: PathBuf::from("/logs"),
@@ -310,6 +340,7 @@ mod tests {
             rpc_socket_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
             service_user: None,
             stop_on_upgrade: false,
+            watchdog_secs: None,
             write_older_cache_files: false,
         }
     }
@@ -334,6 +365,7 @@ mod tests {
                 merkle_payments_address: Some(
                     RewardsAddress::from_str("0x742D35CC6634C0532925A3B844BC9E7595F0BE3A").unwrap(),
                 ),
+                fallback_rpc_urls: Default::default(),
             }),
             log_dir_path: PathBuf::from("/logs"),
             log_format: None,
@@ -353,6 +385,7 @@ mod tests {
             rpc_socket_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
             service_user: None,
             stop_on_upgrade: true,
+            watchdog_secs: None,
             write_older_cache_files: false,
         }
     }
@@ -378,6 +411,7 @@ mod tests {
             "--rewards-address",
             "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             "--stop-on-upgrade",
+            "--write-node-info",
             "evm-arbitrum-one",
         ];
         assert_eq!(
@@ -410,6 +444,7 @@ mod tests {
             "/logs",
             "--rewards-address",
             "0x03B770D9cD32077cC0bF330c13C114a87643B124",
+            "--write-node-info",
             "evm-custom",
             "--rpc-url",
             "http://localhost:8545/",
@@ -487,6 +522,7 @@ mod tests {
             "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             "--write-older-cache-files",
             "--stop-on-upgrade",
+            "--write-node-info",
             "evm-custom",
             "--rpc-url",
             "http://localhost:8545/",
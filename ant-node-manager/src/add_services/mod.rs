@@ -161,11 +161,14 @@ pub async fn add_node(
             std::fs::create_dir_all(service_log_dir_path.clone())?;
         }
 
-        debug!("Copying antnode binary to {service_antnode_path:?}");
-        std::fs::copy(
-            options.antnode_src_path.clone(),
-            service_antnode_path.clone(),
-        )?;
+        // A containerized node runs the binary of its image
+        if options.container.is_none() {
+            debug!("Copying antnode binary to {service_antnode_path:?}");
+            std::fs::copy(
+                options.antnode_src_path.clone(),
+                service_antnode_path.clone(),
+            )?;
+        }
 
         if options.auto_set_nat_flags {
             let nat_status = node_registry.nat_status.read().await;
@@ -221,6 +224,7 @@ pub async fn add_node(
             rpc_socket_addr,
             service_user: options.user.clone(),
             stop_on_upgrade: true,
+            watchdog_secs: options.watchdog_secs,
             write_older_cache_files: options.write_older_cache_files,
         }
         .build()?;
@@ -242,6 +246,7 @@ pub async fn add_node(
                         antnode_path: service_antnode_path,
                         auto_restart: options.auto_restart,
                         connected_peers: None,
+                        container: options.container.clone(),
                         data_dir_path: service_data_dir_path.clone(),
                         evm_network: options.evm_network.clone(),
                         relay: options.relay,
@@ -268,6 +273,7 @@ pub async fn add_node(
                         user: options.user.clone(),
                         user_mode: options.user_mode,
                         version: options.version.clone(),
+                        watchdog_secs: options.watchdog_secs,
                         write_older_cache_files: options.write_older_cache_files,
                     })
                     .await;
@@ -351,13 +357,25 @@ pub async fn add_daemon(
         options.daemon_install_bin_path.clone(),
     )?;
 
+    let mut args = vec![
+        OsString::from("--port"),
+        OsString::from(options.port.to_string()),
+        OsString::from("--address"),
+        OsString::from(options.address.to_string()),
+    ];
+    for (arg, path) in [
+        ("--tls-cert", &options.tls_cert_path),
+        ("--tls-key", &options.tls_key_path),
+        ("--tls-client-ca", &options.tls_client_ca_path),
+    ] {
+        if let Some(path) = path {
+            args.push(OsString::from(arg));
+            args.push(path.clone().into_os_string());
+        }
+    }
+
     let install_ctx = ServiceInstallCtx {
-        args: vec![
-            OsString::from("--port"),
-            OsString::from(options.port.to_string()),
-            OsString::from("--address"),
-            OsString::from(options.address.to_string()),
-        ],
+        args,
         autostart: true,
         contents: None,
         environment: options.env_variables,
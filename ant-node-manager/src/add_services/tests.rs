@@ -123,6 +123,7 @@ async fn add_genesis_node_should_use_latest_version_and_add_one_service() -> Res
                 "0x8464135c8F25Da09e49BC8782676a84730C318bC",
             )?,
             merkle_payments_address: None,
+            fallback_rpc_urls: Default::default(),
         }),
         relay: false,
         log_dir_path: node_logs_dir.to_path_buf().join("antnode1"),
@@ -143,6 +144,7 @@ async fn add_genesis_node_should_use_latest_version_and_add_one_service() -> Res
             .join(ANTNODE_FILE_NAME),
         service_user: Some(get_username()),
         no_upnp: false,
+        watchdog_secs: None,
         write_older_cache_files: false,
         restart_policy,
         stop_on_upgrade: true,
@@ -160,6 +162,7 @@ async fn add_genesis_node_should_use_latest_version_and_add_one_service() -> Res
             alpha: false,
             auto_restart: false,
             auto_set_nat_flags: false,
+            container: None,
             count: None,
             delete_antnode_src: true,
             enable_metrics_server: false,
@@ -192,10 +195,12 @@ async fn add_genesis_node_should_use_latest_version_and_add_one_service() -> Res
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -242,6 +247,7 @@ async fn add_genesis_node_should_use_latest_version_and_add_one_service() -> Res
                 "0x8464135c8F25Da09e49BC8782676a84730C318bC"
             )?,
             merkle_payments_address: None,
+            fallback_rpc_urls: Default::default(),
         })
     );
     assert_eq!(
@@ -277,6 +283,7 @@ async fn add_genesis_node_should_return_an_error_if_there_is_already_a_genesis_n
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::Custom(CustomNetwork {
                 rpc_url_http: "http://localhost:8545".parse()?,
@@ -287,6 +294,7 @@ async fn add_genesis_node_should_return_an_error_if_there_is_already_a_genesis_n
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             relay: false,
             initial_peers_config: init_peers_config.clone(),
@@ -315,6 +323,7 @@ async fn add_genesis_node_should_return_an_error_if_there_is_already_a_genesis_n
             user: Some("ant".to_string()),
             user_mode: false,
             version: latest_version.to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         })
         .await;
@@ -334,6 +343,7 @@ async fn add_genesis_node_should_return_an_error_if_there_is_already_a_genesis_n
             alpha: false,
             auto_restart: false,
             auto_set_nat_flags: false,
+            container: None,
             count: None,
             delete_antnode_src: true,
             enable_metrics_server: false,
@@ -366,10 +376,12 @@ async fn add_genesis_node_should_return_an_error_if_there_is_already_a_genesis_n
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -419,6 +431,7 @@ async fn add_genesis_node_should_return_an_error_if_count_is_greater_than_1() ->
             alpha: false,
             auto_restart: false,
             auto_set_nat_flags: false,
+            container: None,
             count: Some(3),
             delete_antnode_src: true,
             enable_metrics_server: false,
@@ -451,10 +464,12 @@ async fn add_genesis_node_should_return_an_error_if_count_is_greater_than_1() ->
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -518,6 +533,7 @@ async fn add_node_should_use_latest_version_and_add_three_services() -> Result<(
                 "0x8464135c8F25Da09e49BC8782676a84730C318bC",
             )?,
             merkle_payments_address: None,
+            fallback_rpc_urls: Default::default(),
         }),
         relay: false,
         log_dir_path: node_logs_dir.to_path_buf().join("antnode1"),
@@ -538,6 +554,7 @@ async fn add_node_should_use_latest_version_and_add_three_services() -> Result<(
         rewards_address: RewardsAddress::from_str("0x03B770D9cD32077cC0bF330c13C114a87643B124")?,
         service_user: Some(get_username()),
         no_upnp: false,
+        watchdog_secs: None,
         write_older_cache_files: false,
         restart_policy,
         stop_on_upgrade: true,
@@ -576,6 +593,7 @@ async fn add_node_should_use_latest_version_and_add_three_services() -> Result<(
                 "0x8464135c8F25Da09e49BC8782676a84730C318bC",
             )?,
             merkle_payments_address: None,
+            fallback_rpc_urls: Default::default(),
         }),
         relay: false,
         log_dir_path: node_logs_dir.to_path_buf().join("antnode2"),
@@ -596,6 +614,7 @@ async fn add_node_should_use_latest_version_and_add_three_services() -> Result<(
             .join(ANTNODE_FILE_NAME),
         service_user: Some(get_username()),
         no_upnp: false,
+        watchdog_secs: None,
         write_older_cache_files: false,
         restart_policy,
         stop_on_upgrade: true,
@@ -634,6 +653,7 @@ async fn add_node_should_use_latest_version_and_add_three_services() -> Result<(
                 "0x8464135c8F25Da09e49BC8782676a84730C318bC",
             )?,
             merkle_payments_address: None,
+            fallback_rpc_urls: Default::default(),
         }),
         relay: false,
         log_format: None,
@@ -654,6 +674,7 @@ async fn add_node_should_use_latest_version_and_add_three_services() -> Result<(
             .join(ANTNODE_FILE_NAME),
         service_user: Some(get_username()),
         no_upnp: false,
+        watchdog_secs: None,
         write_older_cache_files: false,
         restart_policy,
         stop_on_upgrade: true,
@@ -672,6 +693,7 @@ async fn add_node_should_use_latest_version_and_add_three_services() -> Result<(
             alpha: false,
             auto_restart: false,
             auto_set_nat_flags: false,
+            container: None,
             count: Some(3),
             delete_antnode_src: true,
             enable_metrics_server: false,
@@ -704,10 +726,12 @@ async fn add_node_should_use_latest_version_and_add_three_services() -> Result<(
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -828,6 +852,7 @@ async fn add_node_should_update_the_environment_variables_inside_node_registry()
                 "0x8464135c8F25Da09e49BC8782676a84730C318bC",
             )?,
             merkle_payments_address: None,
+            fallback_rpc_urls: Default::default(),
         }),
         relay: false,
         log_dir_path: node_logs_dir.to_path_buf().join("antnode1"),
@@ -848,6 +873,7 @@ async fn add_node_should_update_the_environment_variables_inside_node_registry()
             .join(ANTNODE_FILE_NAME),
         service_user: Some(get_username()),
         no_upnp: false,
+        watchdog_secs: None,
         write_older_cache_files: false,
         restart_policy,
         stop_on_upgrade: true,
@@ -865,6 +891,7 @@ async fn add_node_should_update_the_environment_variables_inside_node_registry()
             alpha: false,
             auto_restart: false,
             auto_set_nat_flags: false,
+            container: None,
             count: None,
             delete_antnode_src: true,
             enable_metrics_server: false,
@@ -897,10 +924,12 @@ async fn add_node_should_update_the_environment_variables_inside_node_registry()
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -957,6 +986,7 @@ async fn add_new_node_should_add_another_service() -> Result<()> {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::Custom(CustomNetwork {
                 rpc_url_http: "http://localhost:8545".parse()?,
@@ -967,6 +997,7 @@ async fn add_new_node_should_add_another_service() -> Result<()> {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             relay: false,
             initial_peers_config: Default::default(),
@@ -995,6 +1026,7 @@ async fn add_new_node_should_add_another_service() -> Result<()> {
             user: Some("ant".to_string()),
             user_mode: false,
             version: latest_version.to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         })
         .await;
@@ -1031,6 +1063,7 @@ async fn add_new_node_should_add_another_service() -> Result<()> {
                 "0x8464135c8F25Da09e49BC8782676a84730C318bC",
             )?,
             merkle_payments_address: None,
+            fallback_rpc_urls: Default::default(),
         }),
         relay: false,
         log_dir_path: node_logs_dir.to_path_buf().join("antnode2"),
@@ -1051,6 +1084,7 @@ async fn add_new_node_should_add_another_service() -> Result<()> {
             .join(ANTNODE_FILE_NAME),
         service_user: Some(get_username()),
         no_upnp: false,
+        watchdog_secs: None,
         write_older_cache_files: false,
         restart_policy,
         stop_on_upgrade: true,
@@ -1069,6 +1103,7 @@ async fn add_new_node_should_add_another_service() -> Result<()> {
             alpha: false,
             auto_restart: false,
             auto_set_nat_flags: false,
+            container: None,
             count: None,
             delete_antnode_src: true,
             enable_metrics_server: false,
@@ -1101,10 +1136,12 @@ async fn add_new_node_should_add_another_service() -> Result<()> {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -1209,6 +1246,7 @@ async fn add_node_should_create_service_file_with_first_arg() -> Result<()> {
                     OsString::from("--rewards-address"),
                     OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                     OsString::from("--stop-on-upgrade"),
+                    OsString::from("--write-node-info"),
                     OsString::from("evm-custom"),
                     OsString::from("--rpc-url"),
                     OsString::from("http://localhost:8545/"),
@@ -1239,6 +1277,7 @@ async fn add_node_should_create_service_file_with_first_arg() -> Result<()> {
             alpha: false,
             auto_restart: false,
             auto_set_nat_flags: false,
+            container: None,
             count: None,
             delete_antnode_src: true,
             enable_metrics_server: false,
@@ -1271,10 +1310,12 @@ async fn add_node_should_create_service_file_with_first_arg() -> Result<()> {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -1371,6 +1412,7 @@ async fn add_node_should_create_service_file_with_peers_args() -> Result<()> {
                     OsString::from("--rewards-address"),
                     OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                     OsString::from("--stop-on-upgrade"),
+                    OsString::from("--write-node-info"),
                     OsString::from("evm-custom"),
                     OsString::from("--rpc-url"),
                     OsString::from("http://localhost:8545/"),
@@ -1401,6 +1443,7 @@ async fn add_node_should_create_service_file_with_peers_args() -> Result<()> {
             alpha: false,
             auto_restart: false,
             auto_set_nat_flags: false,
+            container: None,
             count: None,
             delete_antnode_src: true,
             enable_metrics_server: false,
@@ -1433,10 +1476,12 @@ async fn add_node_should_create_service_file_with_peers_args() -> Result<()> {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -1528,6 +1573,7 @@ async fn add_node_should_create_service_file_with_local_arg() -> Result<()> {
                     OsString::from("--rewards-address"),
                     OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                     OsString::from("--stop-on-upgrade"),
+                    OsString::from("--write-node-info"),
                     OsString::from("evm-custom"),
                     OsString::from("--rpc-url"),
                     OsString::from("http://localhost:8545/"),
@@ -1558,6 +1604,7 @@ async fn add_node_should_create_service_file_with_local_arg() -> Result<()> {
             alpha: false,
             auto_restart: false,
             auto_set_nat_flags: false,
+            container: None,
             count: None,
             delete_antnode_src: true,
             enable_metrics_server: false,
@@ -1590,10 +1637,12 @@ async fn add_node_should_create_service_file_with_local_arg() -> Result<()> {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -1689,6 +1738,7 @@ async fn add_node_should_create_service_file_with_network_contacts_url_arg() ->
                     OsString::from("--rewards-address"),
                     OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                     OsString::from("--stop-on-upgrade"),
+                    OsString::from("--write-node-info"),
                     OsString::from("evm-custom"),
                     OsString::from("--rpc-url"),
                     OsString::from("http://localhost:8545/"),
@@ -1719,6 +1769,7 @@ async fn add_node_should_create_service_file_with_network_contacts_url_arg() ->
             alpha: false,
             auto_restart: false,
             auto_set_nat_flags: false,
+            container: None,
             count: None,
             delete_antnode_src: true,
             enable_metrics_server: false,
@@ -1751,10 +1802,12 @@ async fn add_node_should_create_service_file_with_network_contacts_url_arg() ->
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -1846,6 +1899,7 @@ async fn add_node_should_create_service_file_with_ignore_cache_arg() -> Result<(
                     OsString::from("--rewards-address"),
                     OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                     OsString::from("--stop-on-upgrade"),
+                    OsString::from("--write-node-info"),
                     OsString::from("evm-custom"),
                     OsString::from("--rpc-url"),
                     OsString::from("http://localhost:8545/"),
@@ -1876,6 +1930,7 @@ async fn add_node_should_create_service_file_with_ignore_cache_arg() -> Result<(
             alpha: false,
             auto_restart: false,
             auto_set_nat_flags: false,
+            container: None,
             count: None,
             delete_antnode_src: true,
             enable_metrics_server: false,
@@ -1908,10 +1963,12 @@ async fn add_node_should_create_service_file_with_ignore_cache_arg() -> Result<(
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -2004,6 +2061,7 @@ async fn add_node_should_create_service_file_with_custom_bootstrap_cache_path()
                     OsString::from("--rewards-address"),
                     OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                     OsString::from("--stop-on-upgrade"),
+                    OsString::from("--write-node-info"),
                     OsString::from("evm-custom"),
                     OsString::from("--rpc-url"),
                     OsString::from("http://localhost:8545/"),
@@ -2034,6 +2092,7 @@ async fn add_node_should_create_service_file_with_custom_bootstrap_cache_path()
             alpha: false,
             auto_restart: false,
             auto_set_nat_flags: false,
+            container: None,
             count: None,
             delete_antnode_src: true,
             enable_metrics_server: false,
@@ -2066,10 +2125,12 @@ async fn add_node_should_create_service_file_with_custom_bootstrap_cache_path()
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -2156,6 +2217,7 @@ async fn add_node_should_create_service_file_with_network_id() -> Result<()> {
                     OsString::from("--rewards-address"),
                     OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                     OsString::from("--stop-on-upgrade"),
+                    OsString::from("--write-node-info"),
                     OsString::from("evm-custom"),
                     OsString::from("--rpc-url"),
                     OsString::from("http://localhost:8545/"),
@@ -2186,6 +2248,7 @@ async fn add_node_should_create_service_file_with_network_id() -> Result<()> {
             alpha: false,
             auto_restart: false,
             auto_set_nat_flags: false,
+            container: None,
             count: None,
             delete_antnode_src: true,
             enable_metrics_server: false,
@@ -2218,10 +2281,12 @@ async fn add_node_should_create_service_file_with_network_id() -> Result<()> {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -2306,6 +2371,7 @@ async fn add_node_should_use_custom_ip() -> Result<()> {
                     OsString::from("--rewards-address"),
                     OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                     OsString::from("--stop-on-upgrade"),
+                    OsString::from("--write-node-info"),
                     OsString::from("evm-custom"),
                     OsString::from("--rpc-url"),
                     OsString::from("http://localhost:8545/"),
@@ -2336,6 +2402,7 @@ async fn add_node_should_use_custom_ip() -> Result<()> {
             alpha: false,
             auto_restart: false,
             auto_set_nat_flags: false,
+            container: None,
             count: None,
             delete_antnode_src: true,
             enable_metrics_server: false,
@@ -2368,10 +2435,12 @@ async fn add_node_should_use_custom_ip() -> Result<()> {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -2433,6 +2502,7 @@ async fn add_node_should_use_custom_ports_for_one_service() -> Result<()> {
                 "0x8464135c8F25Da09e49BC8782676a84730C318bC",
             )?,
             merkle_payments_address: None,
+            fallback_rpc_urls: Default::default(),
         }),
         relay: false,
         log_dir_path: node_logs_dir.to_path_buf().join("antnode1"),
@@ -2453,6 +2523,7 @@ async fn add_node_should_use_custom_ports_for_one_service() -> Result<()> {
             .join(ANTNODE_FILE_NAME),
         service_user: Some(get_username()),
         no_upnp: false,
+        watchdog_secs: None,
         write_older_cache_files: false,
         restart_policy,
         stop_on_upgrade: true,
@@ -2471,6 +2542,7 @@ async fn add_node_should_use_custom_ports_for_one_service() -> Result<()> {
             alpha: false,
             auto_restart: false,
             auto_set_nat_flags: false,
+            container: None,
             count: None,
             delete_antnode_src: true,
             enable_metrics_server: false,
@@ -2503,10 +2575,12 @@ async fn add_node_should_use_custom_ports_for_one_service() -> Result<()> {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -2582,6 +2656,7 @@ async fn add_node_should_use_a_custom_port_range() -> Result<()> {
                     OsString::from("--rewards-address"),
                     OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                     OsString::from("--stop-on-upgrade"),
+                    OsString::from("--write-node-info"),
                     OsString::from("evm-custom"),
                     OsString::from("--rpc-url"),
                     OsString::from("http://localhost:8545/"),
@@ -2642,6 +2717,7 @@ async fn add_node_should_use_a_custom_port_range() -> Result<()> {
                     OsString::from("--rewards-address"),
                     OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                     OsString::from("--stop-on-upgrade"),
+                    OsString::from("--write-node-info"),
                     OsString::from("evm-custom"),
                     OsString::from("--rpc-url"),
                     OsString::from("http://localhost:8545/"),
@@ -2702,6 +2778,7 @@ async fn add_node_should_use_a_custom_port_range() -> Result<()> {
                     OsString::from("--rewards-address"),
                     OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                     OsString::from("--stop-on-upgrade"),
+                    OsString::from("--write-node-info"),
                     OsString::from("evm-custom"),
                     OsString::from("--rpc-url"),
                     OsString::from("http://localhost:8545/"),
@@ -2732,6 +2809,7 @@ async fn add_node_should_use_a_custom_port_range() -> Result<()> {
             alpha: false,
             auto_restart: false,
             auto_set_nat_flags: false,
+            container: None,
             count: Some(3),
             delete_antnode_src: true,
             enable_metrics_server: false,
@@ -2764,10 +2842,12 @@ async fn add_node_should_use_a_custom_port_range() -> Result<()> {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -2803,6 +2883,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_port_is_used() -> R
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::Custom(CustomNetwork {
                 rpc_url_http: "http://localhost:8545".parse()?,
@@ -2813,6 +2894,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_port_is_used() -> R
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             relay: false,
             initial_peers_config: Default::default(),
@@ -2841,6 +2923,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_port_is_used() -> R
             user: Some("ant".to_string()),
             user_mode: false,
             version: "0.98.1".to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         })
         .await;
@@ -2858,6 +2941,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_port_is_used() -> R
             alpha: false,
             auto_restart: false,
             auto_set_nat_flags: false,
+            container: None,
             count: None,
             delete_antnode_src: true,
             enable_metrics_server: false,
@@ -2890,10 +2974,12 @@ async fn add_node_should_return_an_error_if_duplicate_custom_port_is_used() -> R
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -2924,6 +3010,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_port_in_range_is_us
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::Custom(CustomNetwork {
                 rpc_url_http: "http://localhost:8545".parse()?,
@@ -2934,6 +3021,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_port_in_range_is_us
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             relay: false,
             initial_peers_config: Default::default(),
@@ -2962,6 +3050,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_port_in_range_is_us
             user: Some("ant".to_string()),
             user_mode: false,
             version: "0.98.1".to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         })
         .await;
@@ -2979,6 +3068,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_port_in_range_is_us
             alpha: false,
             auto_restart: false,
             auto_set_nat_flags: false,
+            container: None,
             count: Some(3),
             delete_antnode_src: true,
             enable_metrics_server: false,
@@ -3011,10 +3101,12 @@ async fn add_node_should_return_an_error_if_duplicate_custom_port_in_range_is_us
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -3054,6 +3146,7 @@ async fn add_node_should_return_an_error_if_port_and_node_count_do_not_match() -
             alpha: false,
             auto_restart: false,
             auto_set_nat_flags: false,
+            container: None,
             count: Some(2),
             delete_antnode_src: true,
             enable_metrics_server: false,
@@ -3086,10 +3179,12 @@ async fn add_node_should_return_an_error_if_port_and_node_count_do_not_match() -
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -3134,6 +3229,7 @@ async fn add_node_should_return_an_error_if_multiple_services_are_specified_with
             alpha: false,
             auto_restart: false,
             auto_set_nat_flags: false,
+            container: None,
             count: Some(2),
             delete_antnode_src: true,
             enable_metrics_server: false,
@@ -3166,10 +3262,12 @@ async fn add_node_should_return_an_error_if_multiple_services_are_specified_with
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -3250,6 +3348,7 @@ async fn add_node_should_set_random_ports_if_enable_metrics_server_is_true() ->
                     OsString::from("--rewards-address"),
                     OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                     OsString::from("--stop-on-upgrade"),
+                    OsString::from("--write-node-info"),
                     OsString::from("evm-custom"),
                     OsString::from("--rpc-url"),
                     OsString::from("http://localhost:8545/"),
@@ -3280,6 +3379,7 @@ async fn add_node_should_set_random_ports_if_enable_metrics_server_is_true() ->
             alpha: false,
             auto_restart: false,
             auto_set_nat_flags: false,
+            container: None,
             count: None,
             delete_antnode_src: true,
             enable_metrics_server: true,
@@ -3312,10 +3412,12 @@ async fn add_node_should_set_random_ports_if_enable_metrics_server_is_true() ->
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -3393,6 +3495,7 @@ async fn add_node_should_set_max_archived_log_files() -> Result<()> {
                     OsString::from("--rewards-address"),
                     OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                     OsString::from("--stop-on-upgrade"),
+                    OsString::from("--write-node-info"),
                     OsString::from("evm-custom"),
                     OsString::from("--rpc-url"),
                     OsString::from("http://localhost:8545/"),
@@ -3423,6 +3526,7 @@ async fn add_node_should_set_max_archived_log_files() -> Result<()> {
             alpha: false,
             auto_restart: false,
             auto_set_nat_flags: false,
+            container: None,
             count: Some(1),
             delete_antnode_src: false,
             enable_metrics_server: false,
@@ -3455,10 +3559,12 @@ async fn add_node_should_set_max_archived_log_files() -> Result<()> {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -3537,6 +3643,7 @@ async fn add_node_should_set_max_log_files() -> Result<()> {
                     OsString::from("--rewards-address"),
                     OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                     OsString::from("--stop-on-upgrade"),
+                    OsString::from("--write-node-info"),
                     OsString::from("evm-custom"),
                     OsString::from("--rpc-url"),
                     OsString::from("http://localhost:8545/"),
@@ -3567,6 +3674,7 @@ async fn add_node_should_set_max_log_files() -> Result<()> {
             alpha: false,
             auto_restart: false,
             auto_set_nat_flags: false,
+            container: None,
             count: Some(1),
             delete_antnode_src: false,
             enable_metrics_server: false,
@@ -3599,10 +3707,12 @@ async fn add_node_should_set_max_log_files() -> Result<()> {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -3680,6 +3790,7 @@ async fn add_node_should_use_a_custom_port_range_for_metrics_server() -> Result<
                     OsString::from("--rewards-address"),
                     OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                     OsString::from("--stop-on-upgrade"),
+                    OsString::from("--write-node-info"),
                     OsString::from("evm-custom"),
                     OsString::from("--rpc-url"),
                     OsString::from("http://localhost:8545/"),
@@ -3747,6 +3858,7 @@ async fn add_node_should_use_a_custom_port_range_for_metrics_server() -> Result<
                     OsString::from("--rewards-address"),
                     OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                     OsString::from("--stop-on-upgrade"),
+                    OsString::from("--write-node-info"),
                     OsString::from("evm-custom"),
                     OsString::from("--rpc-url"),
                     OsString::from("http://localhost:8545/"),
@@ -3814,6 +3926,7 @@ async fn add_node_should_use_a_custom_port_range_for_metrics_server() -> Result<
                     OsString::from("--rewards-address"),
                     OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                     OsString::from("--stop-on-upgrade"),
+                    OsString::from("--write-node-info"),
                     OsString::from("evm-custom"),
                     OsString::from("--rpc-url"),
                     OsString::from("http://localhost:8545/"),
@@ -3844,6 +3957,7 @@ async fn add_node_should_use_a_custom_port_range_for_metrics_server() -> Result<
             alpha: false,
             auto_restart: false,
             auto_set_nat_flags: false,
+            container: None,
             count: Some(3),
             delete_antnode_src: true,
             enable_metrics_server: false,
@@ -3876,10 +3990,12 @@ async fn add_node_should_use_a_custom_port_range_for_metrics_server() -> Result<
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -3913,6 +4029,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_metrics_port_is_use
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::Custom(CustomNetwork {
                 rpc_url_http: "http://localhost:8545".parse()?,
@@ -3923,6 +4040,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_metrics_port_is_use
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             relay: false,
             initial_peers_config: Default::default(),
@@ -3951,6 +4069,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_metrics_port_is_use
             user: Some("ant".to_string()),
             user_mode: false,
             version: "0.98.1".to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         })
         .await;
@@ -3968,6 +4087,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_metrics_port_is_use
             alpha: false,
             auto_restart: false,
             auto_set_nat_flags: false,
+            container: None,
             count: None,
             delete_antnode_src: true,
             enable_metrics_server: false,
@@ -4000,10 +4120,12 @@ async fn add_node_should_return_an_error_if_duplicate_custom_metrics_port_is_use
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -4035,6 +4157,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_metrics_port_in_ran
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::Custom(CustomNetwork {
                 rpc_url_http: "http://localhost:8545".parse()?,
@@ -4045,6 +4168,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_metrics_port_in_ran
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             relay: false,
             initial_peers_config: Default::default(),
@@ -4073,6 +4197,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_metrics_port_in_ran
             user: Some("ant".to_string()),
             user_mode: false,
             version: "0.98.1".to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         })
         .await;
@@ -4090,6 +4215,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_metrics_port_in_ran
             alpha: false,
             auto_restart: false,
             auto_set_nat_flags: false,
+            container: None,
             count: Some(3),
             delete_antnode_src: true,
             enable_metrics_server: false,
@@ -4122,10 +4248,12 @@ async fn add_node_should_return_an_error_if_duplicate_custom_metrics_port_in_ran
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -4195,6 +4323,7 @@ async fn add_node_should_use_a_custom_port_range_for_the_rpc_server() -> Result<
                     OsString::from("--rewards-address"),
                     OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                     OsString::from("--stop-on-upgrade"),
+                    OsString::from("--write-node-info"),
                     OsString::from("evm-custom"),
                     OsString::from("--rpc-url"),
                     OsString::from("http://localhost:8545/"),
@@ -4250,6 +4379,7 @@ async fn add_node_should_use_a_custom_port_range_for_the_rpc_server() -> Result<
                     OsString::from("--rewards-address"),
                     OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                     OsString::from("--stop-on-upgrade"),
+                    OsString::from("--write-node-info"),
                     OsString::from("evm-custom"),
                     OsString::from("--rpc-url"),
                     OsString::from("http://localhost:8545/"),
@@ -4305,6 +4435,7 @@ async fn add_node_should_use_a_custom_port_range_for_the_rpc_server() -> Result<
                     OsString::from("--rewards-address"),
                     OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                     OsString::from("--stop-on-upgrade"),
+                    OsString::from("--write-node-info"),
                     OsString::from("evm-custom"),
                     OsString::from("--rpc-url"),
                     OsString::from("http://localhost:8545/"),
@@ -4335,6 +4466,7 @@ async fn add_node_should_use_a_custom_port_range_for_the_rpc_server() -> Result<
             alpha: false,
             auto_restart: false,
             auto_set_nat_flags: false,
+            container: None,
             count: Some(3),
             delete_antnode_src: true,
             enable_metrics_server: false,
@@ -4367,10 +4499,12 @@ async fn add_node_should_use_a_custom_port_range_for_the_rpc_server() -> Result<
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -4414,6 +4548,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_rpc_port_is_used()
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::Custom(CustomNetwork {
                 rpc_url_http: "http://localhost:8545".parse()?,
@@ -4424,6 +4559,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_rpc_port_is_used()
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             relay: false,
             initial_peers_config: Default::default(),
@@ -4452,6 +4588,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_rpc_port_is_used()
             user: Some("ant".to_string()),
             user_mode: false,
             version: "0.98.1".to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         })
         .await;
@@ -4469,6 +4606,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_rpc_port_is_used()
             alpha: false,
             auto_restart: false,
             auto_set_nat_flags: false,
+            container: None,
             count: None,
             delete_antnode_src: true,
             enable_metrics_server: false,
@@ -4501,10 +4639,12 @@ async fn add_node_should_return_an_error_if_duplicate_custom_rpc_port_is_used()
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -4536,6 +4676,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_rpc_port_in_range_i
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::Custom(CustomNetwork {
                 rpc_url_http: "http://localhost:8545".parse()?,
@@ -4546,6 +4687,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_rpc_port_in_range_i
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             relay: false,
             initial_peers_config: Default::default(),
@@ -4574,6 +4716,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_rpc_port_in_range_i
             user: Some("ant".to_string()),
             user_mode: false,
             version: "0.98.1".to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         })
         .await;
@@ -4591,6 +4734,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_rpc_port_in_range_i
             alpha: false,
             auto_restart: false,
             auto_set_nat_flags: false,
+            container: None,
             count: Some(2),
             delete_antnode_src: true,
             enable_metrics_server: false,
@@ -4623,10 +4767,12 @@ async fn add_node_should_return_an_error_if_duplicate_custom_rpc_port_in_range_i
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -4691,6 +4837,7 @@ async fn add_node_should_disable_upnp_and_relay_if_nat_status_is_public() -> Res
                 "0x8464135c8F25Da09e49BC8782676a84730C318bC",
             )?,
             merkle_payments_address: None,
+            fallback_rpc_urls: Default::default(),
         }),
         relay: false,
         log_dir_path: node_logs_dir.to_path_buf().join("antnode1"),
@@ -4711,6 +4858,7 @@ async fn add_node_should_disable_upnp_and_relay_if_nat_status_is_public() -> Res
             .join(ANTNODE_FILE_NAME),
         service_user: Some(get_username()),
         no_upnp: true,
+        watchdog_secs: None,
         write_older_cache_files: false,
         restart_policy,
         stop_on_upgrade: true,
@@ -4728,6 +4876,7 @@ async fn add_node_should_disable_upnp_and_relay_if_nat_status_is_public() -> Res
             alpha: false,
             auto_restart: false,
             auto_set_nat_flags: true,
+            container: None,
             count: None,
             delete_antnode_src: true,
             enable_metrics_server: false,
@@ -4760,10 +4909,12 @@ async fn add_node_should_disable_upnp_and_relay_if_nat_status_is_public() -> Res
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -4826,6 +4977,7 @@ async fn add_node_should_not_set_no_upnp_if_nat_status_is_upnp() -> Result<()> {
                 "0x8464135c8F25Da09e49BC8782676a84730C318bC",
             )?,
             merkle_payments_address: None,
+            fallback_rpc_urls: Default::default(),
         }),
         relay: false,
         log_dir_path: node_logs_dir.to_path_buf().join("antnode1"),
@@ -4846,6 +4998,7 @@ async fn add_node_should_not_set_no_upnp_if_nat_status_is_upnp() -> Result<()> {
             .join(ANTNODE_FILE_NAME),
         service_user: Some(get_username()),
         no_upnp: false,
+        watchdog_secs: None,
         write_older_cache_files: false,
         restart_policy,
         stop_on_upgrade: true,
@@ -4863,6 +5016,7 @@ async fn add_node_should_not_set_no_upnp_if_nat_status_is_upnp() -> Result<()> {
             alpha: false,
             auto_restart: false,
             auto_set_nat_flags: true,
+            container: None,
             count: None,
             delete_antnode_src: true,
             enable_metrics_server: false,
@@ -4895,10 +5049,12 @@ async fn add_node_should_not_set_no_upnp_if_nat_status_is_upnp() -> Result<()> {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -4960,6 +5116,7 @@ async fn add_node_should_enable_relay_if_nat_status_is_private() -> Result<()> {
                 "0x8464135c8F25Da09e49BC8782676a84730C318bC",
             )?,
             merkle_payments_address: None,
+            fallback_rpc_urls: Default::default(),
         }),
         relay: true,
         log_dir_path: node_logs_dir.to_path_buf().join("antnode1"),
@@ -4980,6 +5137,7 @@ async fn add_node_should_enable_relay_if_nat_status_is_private() -> Result<()> {
             .join(ANTNODE_FILE_NAME),
         service_user: Some(get_username()),
         no_upnp: true,
+        watchdog_secs: None,
         write_older_cache_files: false,
         restart_policy,
         stop_on_upgrade: true,
@@ -4997,6 +5155,7 @@ async fn add_node_should_enable_relay_if_nat_status_is_private() -> Result<()> {
             alpha: false,
             auto_restart: false,
             auto_set_nat_flags: true,
+            container: None,
             count: None,
             delete_antnode_src: true,
             enable_metrics_server: false,
@@ -5029,10 +5188,12 @@ async fn add_node_should_enable_relay_if_nat_status_is_private() -> Result<()> {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -5095,6 +5256,7 @@ async fn add_node_should_set_relay_and_no_upnp_if_nat_status_is_none_but_auto_se
                 "0x8464135c8F25Da09e49BC8782676a84730C318bC",
             )?,
             merkle_payments_address: None,
+            fallback_rpc_urls: Default::default(),
         }),
         relay: true,
         log_dir_path: node_logs_dir.to_path_buf().join("antnode1"),
@@ -5115,6 +5277,7 @@ async fn add_node_should_set_relay_and_no_upnp_if_nat_status_is_none_but_auto_se
             .join(ANTNODE_FILE_NAME),
         service_user: Some(get_username()),
         no_upnp: true,
+        watchdog_secs: None,
         write_older_cache_files: false,
         restart_policy,
         stop_on_upgrade: true,
@@ -5132,6 +5295,7 @@ async fn add_node_should_set_relay_and_no_upnp_if_nat_status_is_none_but_auto_se
             alpha: false,
             auto_restart: false,
             auto_set_nat_flags: true,
+            container: None,
             count: None,
             delete_antnode_src: true,
             enable_metrics_server: false,
@@ -5164,10 +5328,12 @@ async fn add_node_should_set_relay_and_no_upnp_if_nat_status_is_none_but_auto_se
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -5233,6 +5399,9 @@ async fn add_daemon_should_add_a_daemon_service() -> Result<()> {
             daemon_src_bin_path: daemon_download_path.to_path_buf(),
             env_variables: Some(vec![("ANT_LOG".to_string(), "ALL".to_string())]),
             port: 8080,
+            tls_cert_path: None,
+            tls_key_path: None,
+            tls_client_ca_path: None,
             user: get_username(),
             version: latest_version.to_string(),
         },
@@ -5264,6 +5433,79 @@ async fn add_daemon_should_add_a_daemon_service() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn add_daemon_should_pass_the_tls_arguments_to_the_service() -> Result<()> {
+    let tmp_data_dir = assert_fs::TempDir::new()?;
+    let node_reg_path = tmp_data_dir.child("node_reg.json");
+
+    let restart_policy = RestartPolicy::OnSuccess { delay_secs: None };
+    let temp_dir = assert_fs::TempDir::new()?;
+    let daemon_install_dir = temp_dir.child("install");
+    daemon_install_dir.create_dir_all()?;
+    let daemon_install_path = daemon_install_dir.child(DAEMON_FILE_NAME);
+    let daemon_download_path = temp_dir.child(DAEMON_FILE_NAME);
+    daemon_download_path.write_binary(b"fake daemon bin")?;
+
+    let node_registry = NodeRegistryManager::empty(node_reg_path.to_path_buf());
+
+    let mut mock_service_control = MockServiceControl::new();
+    mock_service_control
+        .expect_install()
+        .times(1)
+        .with(
+            eq(ServiceInstallCtx {
+                args: vec![
+                    OsString::from("--port"),
+                    OsString::from("8080"),
+                    OsString::from("--address"),
+                    OsString::from("10.0.0.1"),
+                    OsString::from("--tls-cert"),
+                    OsString::from("/etc/antctld/cert.pem"),
+                    OsString::from("--tls-key"),
+                    OsString::from("/etc/antctld/key.pem"),
+                    OsString::from("--tls-client-ca"),
+                    OsString::from("/etc/antctld/ca.pem"),
+                ],
+                autostart: true,
+                contents: None,
+                environment: Some(vec![(
+                    "ANTCTLD_AUTH_TOKEN".to_string(),
+                    "secret".to_string(),
+                )]),
+                label: "antctld".parse()?,
+                program: daemon_install_path.to_path_buf(),
+                restart_policy,
+                username: Some(get_username()),
+                working_directory: None,
+            }),
+            eq(false),
+        )
+        .returning(|_, _| Ok(()));
+
+    add_daemon(
+        AddDaemonServiceOptions {
+            address: Ipv4Addr::new(10, 0, 0, 1),
+            daemon_install_bin_path: daemon_install_path.to_path_buf(),
+            daemon_src_bin_path: daemon_download_path.to_path_buf(),
+            env_variables: Some(vec![(
+                "ANTCTLD_AUTH_TOKEN".to_string(),
+                "secret".to_string(),
+            )]),
+            port: 8080,
+            tls_cert_path: Some(PathBuf::from("/etc/antctld/cert.pem")),
+            tls_key_path: Some(PathBuf::from("/etc/antctld/key.pem")),
+            tls_client_ca_path: Some(PathBuf::from("/etc/antctld/ca.pem")),
+            user: get_username(),
+            version: "0.96.4".to_string(),
+        },
+        node_registry.clone(),
+        &mock_service_control,
+    )
+    .await?;
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn add_daemon_should_return_an_error_if_a_daemon_service_was_already_created() -> Result<()> {
     let tmp_data_dir = assert_fs::TempDir::new()?;
@@ -5299,6 +5541,9 @@ async fn add_daemon_should_return_an_error_if_a_daemon_service_was_already_creat
             daemon_src_bin_path: daemon_download_path.to_path_buf(),
             env_variables: Some(Vec::new()),
             port: 8080,
+            tls_cert_path: None,
+            tls_key_path: None,
+            tls_client_ca_path: None,
             user: get_username(),
             version: latest_version.to_string(),
         },
@@ -5367,6 +5612,7 @@ async fn add_node_should_not_delete_the_source_binary_if_path_arg_is_used() -> R
                 "0x8464135c8F25Da09e49BC8782676a84730C318bC",
             )?,
             merkle_payments_address: None,
+            fallback_rpc_urls: Default::default(),
         }),
         relay: false,
         log_dir_path: node_logs_dir.to_path_buf().join("antnode1"),
@@ -5387,6 +5633,7 @@ async fn add_node_should_not_delete_the_source_binary_if_path_arg_is_used() -> R
             .join(ANTNODE_FILE_NAME),
         service_user: Some(get_username()),
         no_upnp: false,
+        watchdog_secs: None,
         write_older_cache_files: false,
         restart_policy,
         stop_on_upgrade: true,
@@ -5405,6 +5652,7 @@ async fn add_node_should_not_delete_the_source_binary_if_path_arg_is_used() -> R
             alpha: false,
             auto_restart: false,
             auto_set_nat_flags: false,
+            container: None,
             count: Some(1),
             delete_antnode_src: false,
             enable_metrics_server: false,
@@ -5437,10 +5685,12 @@ async fn add_node_should_not_delete_the_source_binary_if_path_arg_is_used() -> R
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -5503,6 +5753,7 @@ async fn add_node_should_apply_the_relay_flag_if_it_is_used() -> Result<()> {
                 "0x8464135c8F25Da09e49BC8782676a84730C318bC",
             )?,
             merkle_payments_address: None,
+            fallback_rpc_urls: Default::default(),
         }),
         log_dir_path: node_logs_dir.to_path_buf().join("antnode1"),
         log_format: None,
@@ -5522,6 +5773,7 @@ async fn add_node_should_apply_the_relay_flag_if_it_is_used() -> Result<()> {
             .join(ANTNODE_FILE_NAME),
         service_user: Some(get_username()),
         no_upnp: false,
+        watchdog_secs: None,
         write_older_cache_files: false,
         restart_policy,
         stop_on_upgrade: true,
@@ -5540,6 +5792,7 @@ async fn add_node_should_apply_the_relay_flag_if_it_is_used() -> Result<()> {
             alpha: false,
             auto_restart: false,
             auto_set_nat_flags: false,
+            container: None,
             count: Some(1),
             delete_antnode_src: false,
             enable_metrics_server: false,
@@ -5572,10 +5825,12 @@ async fn add_node_should_apply_the_relay_flag_if_it_is_used() -> Result<()> {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -5638,6 +5893,7 @@ async fn add_node_should_add_the_node_in_user_mode() -> Result<()> {
                 "0x8464135c8F25Da09e49BC8782676a84730C318bC",
             )?,
             merkle_payments_address: None,
+            fallback_rpc_urls: Default::default(),
         }),
         relay: true,
         log_dir_path: node_logs_dir.to_path_buf().join("antnode1"),
@@ -5658,6 +5914,7 @@ async fn add_node_should_add_the_node_in_user_mode() -> Result<()> {
             .join(ANTNODE_FILE_NAME),
         service_user: Some(get_username()),
         no_upnp: false,
+        watchdog_secs: None,
         write_older_cache_files: false,
         restart_policy,
         stop_on_upgrade: true,
@@ -5676,6 +5933,7 @@ async fn add_node_should_add_the_node_in_user_mode() -> Result<()> {
             alpha: false,
             auto_restart: false,
             auto_set_nat_flags: false,
+            container: None,
             count: Some(1),
             delete_antnode_src: false,
             enable_metrics_server: false,
@@ -5708,10 +5966,12 @@ async fn add_node_should_add_the_node_in_user_mode() -> Result<()> {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -5770,6 +6030,7 @@ async fn add_node_should_add_the_node_with_no_upnp_flag() -> Result<()> {
                 "0x8464135c8F25Da09e49BC8782676a84730C318bC",
             )?,
             merkle_payments_address: None,
+            fallback_rpc_urls: Default::default(),
         }),
         relay: true,
         log_dir_path: node_logs_dir.to_path_buf().join("antnode1"),
@@ -5790,6 +6051,7 @@ async fn add_node_should_add_the_node_with_no_upnp_flag() -> Result<()> {
             .join(ANTNODE_FILE_NAME),
         service_user: Some(get_username()),
         no_upnp: true,
+        watchdog_secs: None,
         write_older_cache_files: false,
         restart_policy,
         stop_on_upgrade: true,
@@ -5808,6 +6070,7 @@ async fn add_node_should_add_the_node_with_no_upnp_flag() -> Result<()> {
             alpha: false,
             auto_restart: false,
             auto_set_nat_flags: false,
+            container: None,
             count: Some(1),
             delete_antnode_src: false,
             enable_metrics_server: false,
@@ -5840,10 +6103,12 @@ async fn add_node_should_add_the_node_with_no_upnp_flag() -> Result<()> {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -5918,6 +6183,7 @@ async fn add_node_should_auto_restart() -> Result<()> {
                     OsString::from("--rewards-address"),
                     OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                     OsString::from("--stop-on-upgrade"),
+                    OsString::from("--write-node-info"),
                     OsString::from("evm-custom"),
                     OsString::from("--rpc-url"),
                     OsString::from("http://localhost:8545/"),
@@ -5949,6 +6215,7 @@ async fn add_node_should_auto_restart() -> Result<()> {
             alpha: false,
             auto_restart: true,
             auto_set_nat_flags: false,
+            container: None,
             count: None,
             delete_antnode_src: true,
             enable_metrics_server: false,
@@ -5981,10 +6248,12 @@ async fn add_node_should_auto_restart() -> Result<()> {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -6046,6 +6315,7 @@ async fn add_node_should_add_the_node_with_write_older_cache_files() -> Result<(
                 "0x8464135c8F25Da09e49BC8782676a84730C318bC",
             )?,
             merkle_payments_address: None,
+            fallback_rpc_urls: Default::default(),
         }),
         relay: false,
         log_dir_path: node_logs_dir.to_path_buf().join("antnode1"),
@@ -6066,6 +6336,7 @@ async fn add_node_should_add_the_node_with_write_older_cache_files() -> Result<(
             .join(ANTNODE_FILE_NAME),
         service_user: Some(get_username()),
         no_upnp: true,
+        watchdog_secs: None,
         write_older_cache_files: true,
         restart_policy,
         stop_on_upgrade: true,
@@ -6084,6 +6355,7 @@ async fn add_node_should_add_the_node_with_write_older_cache_files() -> Result<(
             alpha: false,
             auto_restart: false,
             auto_set_nat_flags: false,
+            container: None,
             count: Some(1),
             delete_antnode_src: false,
             enable_metrics_server: false,
@@ -6116,10 +6388,12 @@ async fn add_node_should_add_the_node_with_write_older_cache_files() -> Result<(
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: true,
             restart_policy,
         },
@@ -6206,6 +6480,7 @@ async fn add_node_should_create_service_file_with_alpha_arg() -> Result<()> {
                     OsString::from("--rewards-address"),
                     OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                     OsString::from("--stop-on-upgrade"),
+                    OsString::from("--write-node-info"),
                     OsString::from("evm-custom"),
                     OsString::from("--rpc-url"),
                     OsString::from("http://localhost:8545/"),
@@ -6236,6 +6511,7 @@ async fn add_node_should_create_service_file_with_alpha_arg() -> Result<()> {
             alpha: true,
             auto_restart: false,
             auto_set_nat_flags: false,
+            container: None,
             count: None,
             delete_antnode_src: true,
             enable_metrics_server: false,
@@ -6268,10 +6544,12 @@ async fn add_node_should_create_service_file_with_alpha_arg() -> Result<()> {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
             restart_policy,
         },
@@ -0,0 +1,185 @@
+// Copyright (C) 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Authentication and authorization of the requests received by the `antctld` daemon.
+//!
+//! Requests carry a token in their `authorization` metadata, as `Bearer <token>`. The daemon can
+//! be given two tokens: a control token, granting every command, and a read-only token, only
+//! granting the commands that do not change the services. A daemon without tokens grants every
+//! command to anyone able to connect to it, which is only safe on localhost or when mutual TLS
+//! restricts the clients.
+
+use color_eyre::{Result, eyre::eyre};
+use std::path::Path;
+use tonic::{
+    Request, Status,
+    transport::{Certificate, ClientTlsConfig, Identity, ServerTlsConfig},
+};
+
+/// The metadata key carrying the token of the daemon's RPC requests.
+pub const AUTH_METADATA_KEY: &str = "authorization";
+
+/// What a client is allowed to do with the daemon.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AccessLevel {
+    /// Get the status of the services
+    ReadOnly,
+    /// Any command, including restarting and upgrading the services
+    Control,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct AuthPolicy {
+    pub control_token: Option<String>,
+    pub read_only_token: Option<String>,
+}
+
+impl AuthPolicy {
+    /// Returns true if requests must carry a token.
+    pub fn requires_token(&self) -> bool {
+        self.control_token.is_some() || self.read_only_token.is_some()
+    }
+
+    /// Authenticate a request and record its access level in its extensions, for the handlers to
+    /// check with [`require_access`].
+    pub fn authorize(&self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        let access_level = if !self.requires_token() {
+            AccessLevel::Control
+        } else {
+            let provided = request
+                .metadata()
+                .get(AUTH_METADATA_KEY)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.strip_prefix("Bearer "))
+                .map(str::as_bytes);
+            let matches = |token: &Option<String>| match (provided, token) {
+                (Some(provided), Some(token)) => constant_time_eq(provided, token.as_bytes()),
+                _ => false,
+            };
+            if matches(&self.control_token) {
+                AccessLevel::Control
+            } else if matches(&self.read_only_token) {
+                AccessLevel::ReadOnly
+            } else {
+                warn!("Rejected a request with a missing or invalid auth token");
+                return Err(Status::unauthenticated("Missing or invalid auth token"));
+            }
+        };
+        let _ = request.extensions_mut().insert(access_level);
+        Ok(request)
+    }
+}
+
+/// Check that the client of an authorized request has the given access level.
+pub fn require_access<T>(request: &Request<T>, required: AccessLevel) -> Result<(), Status> {
+    match request.extensions().get::<AccessLevel>() {
+        Some(access_level) if *access_level >= required => Ok(()),
+        _ => {
+            warn!("Rejected a request requiring {required:?} access");
+            Err(Status::permission_denied(format!(
+                "This command requires {required:?} access"
+            )))
+        }
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn read_pem(path: &Path) -> Result<Vec<u8>> {
+    std::fs::read(path).map_err(|err| eyre!("Could not read {}: {err}", path.display()))
+}
+
+/// The TLS configuration of the daemon, from its PEM certificate and key. Clients must present
+/// a certificate signed by the client CA, if given.
+pub fn server_tls_config(
+    cert_path: &Path,
+    key_path: &Path,
+    client_ca_path: Option<&Path>,
+) -> Result<ServerTlsConfig> {
+    let identity = Identity::from_pem(read_pem(cert_path)?, read_pem(key_path)?);
+    let mut config = ServerTlsConfig::new().identity(identity);
+    if let Some(client_ca_path) = client_ca_path {
+        config = config.client_ca_root(Certificate::from_pem(read_pem(client_ca_path)?));
+    }
+    Ok(config)
+}
+
+/// The TLS configuration of a client of the daemon. The daemon's certificate is verified with the
+/// given CA, and the client certificate and key are presented to daemons requiring mutual TLS.
+pub fn client_tls_config(
+    ca_path: &Path,
+    client_identity: Option<(&Path, &Path)>,
+    domain_name: Option<&str>,
+) -> Result<ClientTlsConfig> {
+    let mut config =
+        ClientTlsConfig::new().ca_certificate(Certificate::from_pem(read_pem(ca_path)?));
+    if let Some((cert_path, key_path)) = client_identity {
+        config = config.identity(Identity::from_pem(
+            read_pem(cert_path)?,
+            read_pem(key_path)?,
+        ));
+    }
+    if let Some(domain_name) = domain_name {
+        config = config.domain_name(domain_name);
+    }
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request_with_token(token: Option<&str>) -> Request<()> {
+        let mut request = Request::new(());
+        if let Some(token) = token {
+            let _ = request.metadata_mut().insert(
+                AUTH_METADATA_KEY,
+                format!("Bearer {token}").parse().expect("valid metadata"),
+            );
+        }
+        request
+    }
+
+    fn access_level(policy: &AuthPolicy, token: Option<&str>) -> Option<AccessLevel> {
+        policy
+            .authorize(request_with_token(token))
+            .ok()
+            .and_then(|request| request.extensions().get::<AccessLevel>().copied())
+    }
+
+    #[test]
+    fn test_authorization_policy() {
+        let open = AuthPolicy::default();
+        assert_eq!(access_level(&open, None), Some(AccessLevel::Control));
+        assert_eq!(access_level(&open, Some("any")), Some(AccessLevel::Control));
+
+        let policy = AuthPolicy {
+            control_token: Some("control".to_string()),
+            read_only_token: Some("read".to_string()),
+        };
+        assert_eq!(
+            access_level(&policy, Some("control")),
+            Some(AccessLevel::Control)
+        );
+        assert_eq!(
+            access_level(&policy, Some("read")),
+            Some(AccessLevel::ReadOnly)
+        );
+        assert_eq!(access_level(&policy, Some("other")), None);
+        assert_eq!(access_level(&policy, None), None);
+
+        let read_only = policy
+            .authorize(request_with_token(Some("read")))
+            .expect("authorized");
+        assert!(require_access(&read_only, AccessLevel::ReadOnly).is_ok());
+        assert!(require_access(&read_only, AccessLevel::Control).is_err());
+        assert!(require_access(&Request::new(()), AccessLevel::ReadOnly).is_err());
+    }
+}
@@ -11,19 +11,22 @@ mod subcommands;
 use crate::subcommands::evm_network::EvmNetworkCommand;
 use ant_bootstrap::InitialPeersConfig;
 use ant_evm::RewardsAddress;
-use ant_logging::{LogBuilder, LogFormat};
+use ant_logging::{LogBuilder, LogFormat, LogShippingTarget};
 use ant_node_manager::{
     DEFAULT_NODE_STARTUP_CONNECTION_TIMEOUT_S, VerbosityLevel,
     add_services::config::PortRange,
+    chaos::ChaosOptions,
     cmd::{self},
     config,
+    inventory::{Inventory, RemoteHost, get_inventory_path},
+    logs::parse_duration,
 };
-use ant_service_management::NodeRegistryManager;
+use ant_service_management::{ContainerRuntime, NodeRegistryManager};
 use clap::{Parser, Subcommand};
 use color_eyre::{Result, eyre::eyre};
 use libp2p::Multiaddr;
 use service_manager::RestartPolicy;
-use std::{net::Ipv4Addr, path::PathBuf};
+use std::{net::Ipv4Addr, path::PathBuf, str::FromStr, time::Duration};
 use tracing::Level;
 
 const DEFAULT_NODE_COUNT: u16 = 25;
@@ -43,6 +46,13 @@ pub(crate) struct Cmd {
     #[clap(long, conflicts_with = "trace")]
     debug: bool,
 
+    /// Also ship the debug or trace-level logs as JSON to a remote collector.
+    ///
+    /// Valid values are an HTTP(S) bulk endpoint URL, "syslog://host:port" (UDP) or
+    /// "syslog+tcp://host:port".
+    #[clap(long, value_parser = LogShippingTarget::parse_from_str)]
+    log_shipping: Option<LogShippingTarget>,
+
     /// Print the package version.
     #[cfg(not(feature = "nightly"))]
     #[clap(long)]
@@ -132,6 +142,14 @@ pub enum SubCmd {
         /// Specify what EVM network to use for payments.
         #[command(subcommand)]
         evm_network: EvmNetworkCommand,
+        /// The image of the containers, when the services run in containers.
+        ///
+        /// The image is tagged with the version of the node, so no tag should be given. Its
+        /// entrypoint must be the antnode binary.
+        ///
+        /// Defaults to maidsafe/antnode.
+        #[clap(long, requires = "runtime")]
+        image: Option<String>,
         /// Set this flag if UPnP doesn't work, and you are not able to manually port forward.
         #[clap(long)]
         relay: bool,
@@ -221,6 +239,15 @@ pub enum SubCmd {
         /// services, which in this case would be 5. The range must also go from lower to higher.
         #[clap(long, value_parser = PortRange::parse)]
         rpc_port: Option<PortRange>,
+        /// Run the services in containers of the given runtime rather than as native services.
+        ///
+        /// Valid values are "docker" or "podman".
+        ///
+        /// The containers are created from the image tagged with the node version, which is the
+        /// latest release unless --version is used. They use the network of the host, and the data
+        /// and log directories of the nodes are mounted in them.
+        #[clap(long, value_parser = ContainerRuntime::from_str, conflicts_with_all = ["path", "url"], verbatim_doc_comment)]
+        runtime: Option<ContainerRuntime>,
         /// Disables UPnP.
         ///
         /// By default, antnode will try to use UPnP if available. Use this flag to disable UPnP.
@@ -253,6 +280,37 @@ pub enum SubCmd {
         #[clap(long, default_value_t = false)]
         write_older_cache_files: bool,
     },
+    /// Converge the antnode services to a fleet declared in a YAML file.
+    ///
+    /// The file declares the number of services, and optionally their version, port ranges and EVM
+    /// network, along with the rewards address, e.g.:
+    ///
+    /// count: 20
+    /// version: 0.4.17
+    /// node_port: 12000-12019
+    /// rpc_port: 13000-13019
+    /// evm_network: arbitrum-one
+    /// rewards_address: "0x03B770D9cD32077cC0bF330c13C114a87643B124"
+    ///
+    /// Services are compared with the node registry: excess services, and services whose rewards
+    /// address or EVM network differ, are removed; services running another version are upgraded;
+    /// missing services are added. All the services of the fleet are then started.
+    ///
+    /// Services are removed with their data directories.
+    #[clap(name = "apply", verbatim_doc_comment)]
+    Apply {
+        /// The path of the fleet file.
+        #[clap(name = "path")]
+        path: PathBuf,
+        /// The time in seconds to wait for each started or upgraded node to connect to the network.
+        ///
+        /// Defaults to 300s.
+        #[clap(long, default_value_t = DEFAULT_NODE_STARTUP_CONNECTION_TIMEOUT_S)]
+        connection_timeout: u64,
+        /// Print the changes without applying them.
+        #[clap(long)]
+        dry_run: bool,
+    },
     /// Get node reward balances.
     #[clap(name = "balance")]
     Balance {
@@ -267,6 +325,46 @@ pub enum SubCmd {
         #[clap(long, conflicts_with = "peer_id")]
         service_name: Vec<String>,
     },
+    /// Put antnode service(s) into hibernation.
+    ///
+    /// A hibernating node keeps its peer ID and its place in the routing tables of its peers, but
+    /// refuses new data, pauses replication and only keeps the connections to its routing table
+    /// peers. Use the 'resume' command to bring it back into full operation.
+    ///
+    /// If no peer ID(s) or service name(s) are supplied, all running services will hibernate.
+    #[clap(name = "hibernate")]
+    Hibernate {
+        /// The peer ID of the service to put into hibernation.
+        ///
+        /// The argument can be used multiple times to put many services into hibernation.
+        #[clap(long)]
+        peer_id: Vec<String>,
+        /// The name of the service to put into hibernation.
+        ///
+        /// The argument can be used multiple times to put many services into hibernation.
+        #[clap(long, conflicts_with = "peer_id")]
+        service_name: Vec<String>,
+    },
+    /// Print the logs of an antnode service.
+    ///
+    /// The log directory of the service is found in the node registry. By default the current log
+    /// file is printed; with 'since', the rotated files which have not been compressed are also
+    /// read.
+    #[clap(name = "logs")]
+    Logs {
+        /// Keep printing new lines as the service writes them, until interrupted.
+        #[clap(long, short)]
+        follow: bool,
+        /// Only print the lines matching this regular expression.
+        #[clap(long)]
+        grep: Option<String>,
+        /// The name of the service.
+        #[clap(long)]
+        service_name: String,
+        /// Only print the lines logged within this duration, e.g. 30s, 15m, 1h or 2d.
+        #[clap(long, value_parser = parse_duration)]
+        since: Option<Duration>,
+    },
     #[clap(subcommand)]
     Daemon(DaemonSubCmd),
     #[clap(subcommand)]
@@ -297,6 +395,29 @@ pub enum SubCmd {
         #[clap(long)]
         keep_directories: bool,
     },
+    /// Push a record held by antnode service(s) to the peers of its current close group that are
+    /// missing it.
+    ///
+    /// The nodes re-check which peers of the close group of the record hold it, without waiting
+    /// for the periodic replication. This can be used to heal content reported as missing.
+    ///
+    /// If no peer ID(s) or service name(s) are supplied, all running services holding the record
+    /// will push it.
+    #[clap(name = "replicate")]
+    Replicate {
+        /// The hex encoded address of the record.
+        address: String,
+        /// The peer ID of the service that should push the record.
+        ///
+        /// The argument can be used multiple times.
+        #[clap(long)]
+        peer_id: Vec<String>,
+        /// The name of the service that should push the record.
+        ///
+        /// The argument can be used multiple times.
+        #[clap(long, conflicts_with = "peer_id")]
+        service_name: Vec<String>,
+    },
     /// Reset back to a clean base state.
     ///
     /// Stop and remove all services and delete the node registry, which will set the service
@@ -309,6 +430,22 @@ pub enum SubCmd {
         #[clap(long, short)]
         force: bool,
     },
+    /// Resume antnode service(s) from hibernation.
+    ///
+    /// If no peer ID(s) or service name(s) are supplied, all running services will be resumed.
+    #[clap(name = "resume")]
+    Resume {
+        /// The peer ID of the service to resume.
+        ///
+        /// The argument can be used multiple times to resume many services.
+        #[clap(long)]
+        peer_id: Vec<String>,
+        /// The name of the service to resume.
+        ///
+        /// The argument can be used multiple times to resume many services.
+        #[clap(long, conflicts_with = "peer_id")]
+        service_name: Vec<String>,
+    },
     /// Start antnode service(s).
     ///
     /// By default, each node service is started after the previous node has successfully connected to the network or
@@ -353,13 +490,28 @@ pub enum SubCmd {
     #[clap(name = "status")]
     Status {
         /// Set this flag to display more details
-        #[clap(long)]
+        #[clap(long, conflicts_with_all = ["host", "all_hosts"])]
         details: bool,
         /// Set this flag to return an error if any nodes are not running
         #[clap(long)]
         fail: bool,
+        /// Run the command on the given remote host, through its antctld daemon.
+        ///
+        /// The host is either the name of a host in the inventory, or the address of a daemon.
+        ///
+        /// The argument can be used multiple times.
+        #[clap(long)]
+        host: Vec<String>,
+        /// Run the command on all the hosts of the inventory, through their antctld daemons.
+        #[clap(long, conflicts_with = "host")]
+        all_hosts: bool,
+        /// The path of the inventory of remote hosts.
+        ///
+        /// Defaults to inventory.yaml, next to the node registry.
+        #[clap(long)]
+        inventory: Option<PathBuf>,
         /// Set this flag to output the status as a JSON document
-        #[clap(long, conflicts_with = "details")]
+        #[clap(long, conflicts_with_all = ["details", "host", "all_hosts"])]
         json: bool,
     },
     /// Stop antnode service(s).
@@ -395,6 +547,9 @@ pub enum SubCmd {
     ///
     /// If no peer ID(s) or service name(s) are supplied, all services will be upgraded.
     ///
+    /// With '--host' or '--all-hosts', the services of remote hosts are upgraded by their antctld
+    /// daemons, one host after another. The upgrade stops at the first host that fails.
+    ///
     /// On Windows, this command must run as the administrative user. On Linux/macOS, run using
     /// sudo if you defined system-wide services; otherwise, do not run the command elevated.
     #[clap(name = "upgrade")]
@@ -420,7 +575,7 @@ pub enum SubCmd {
         /// spaces.
         ///
         /// Example: --env ANT_LOG=all,RUST_LOG=libp2p=debug
-        #[clap(name = "env", long, use_value_delimiter = false, value_parser = parse_environment_variables)]
+        #[clap(name = "env", long, use_value_delimiter = false, value_parser = parse_environment_variables, conflicts_with_all = ["host", "all_hosts"])]
         env_variables: Option<Vec<(String, String)>>,
         /// Set this flag to force the upgrade command to replace binaries without comparing any
         /// version numbers.
@@ -428,6 +583,21 @@ pub enum SubCmd {
         /// Required if we want to downgrade, or for testing purposes.
         #[clap(long)]
         force: bool,
+        /// Run the command on the given remote host, through its antctld daemon.
+        ///
+        /// The host is either the name of a host in the inventory, or the address of a daemon.
+        ///
+        /// The argument can be used multiple times.
+        #[clap(long)]
+        host: Vec<String>,
+        /// Run the command on all the hosts of the inventory, through their antctld daemons.
+        #[clap(long, conflicts_with = "host")]
+        all_hosts: bool,
+        /// The path of the inventory of remote hosts.
+        ///
+        /// Defaults to inventory.yaml, next to the node registry.
+        #[clap(long)]
+        inventory: Option<PathBuf>,
         /// An interval applied between upgrading each service.
         ///
         /// Use connection-timeout to scale the interval automatically. This argument is mutually exclusive with the
@@ -439,10 +609,10 @@ pub enum SubCmd {
         /// Provide a path for the antnode binary to be used by the service.
         ///
         /// Useful for upgrading the service using a custom built binary.
-        #[clap(long)]
+        #[clap(long, conflicts_with_all = ["host", "all_hosts"])]
         path: Option<PathBuf>,
         /// The peer ID of the service to upgrade
-        #[clap(long)]
+        #[clap(long, conflicts_with_all = ["host", "all_hosts"])]
         peer_id: Vec<String>,
         /// The name of the service to upgrade
         #[clap(long, conflicts_with = "peer_id")]
@@ -452,7 +622,7 @@ pub enum SubCmd {
         /// The binary must be inside a zip or gzipped tar archive.
         ///
         /// This can be useful for testing scenarios.
-        #[clap(long, conflicts_with = "version")]
+        #[clap(long, conflicts_with_all = ["version", "host", "all_hosts"])]
         url: Option<String>,
         /// Upgrade to a specific version rather than the latest version.
         ///
@@ -460,6 +630,35 @@ pub enum SubCmd {
         #[clap(long)]
         version: Option<String>,
     },
+    /// Verify the integrity of the records held by antnode service(s).
+    ///
+    /// Each node randomly samples the given number of its held records, re-reads and re-hashes
+    /// them from disk, and cross-checks a few of the intact ones against the other holders of
+    /// their close group over the network. Records corrupted on disk, or held with a different
+    /// content by other holders, are reported.
+    ///
+    /// If no peer ID(s) or service name(s) are supplied, all running services will be verified.
+    #[clap(name = "verify")]
+    Verify {
+        /// The number of held records to sample at random on each node.
+        #[clap(long, default_value_t = 100)]
+        sample: u32,
+        /// The number of intact sampled records to cross-check with their close group.
+        ///
+        /// Set to 0 to only check the records on disk.
+        #[clap(long, default_value_t = 3)]
+        cross_check: u32,
+        /// The peer ID of the service to verify.
+        ///
+        /// The argument can be used multiple times.
+        #[clap(long)]
+        peer_id: Vec<String>,
+        /// The name of the service to verify.
+        ///
+        /// The argument can be used multiple times.
+        #[clap(long, conflicts_with = "peer_id")]
+        service_name: Vec<String>,
+    },
 }
 
 /// Manage the RPC service.
@@ -480,6 +679,17 @@ pub enum DaemonSubCmd {
         /// If not set, the daemon listens locally.
         #[clap(long, default_value_t = Ipv4Addr::new(127, 0, 0, 1))]
         address: Ipv4Addr,
+        /// A secret token that clients must present to issue any command to the daemon.
+        ///
+        /// A token, or mutual TLS, should be set whenever the daemon listens on an address
+        /// reachable from other machines. Remote clients provide it through the inventory or the
+        /// ANTCTL_AUTH_TOKEN environment variable.
+        #[clap(long)]
+        auth_token: Option<String>,
+        /// A secret token that clients must present to get the status of the services, without
+        /// being able to change them.
+        #[clap(long)]
+        read_only_token: Option<String>,
         /// Provide environment variables for the daemon service.
         ///
         /// Useful to set log levels. Variables should be comma separated without spaces.
@@ -490,6 +700,17 @@ pub enum DaemonSubCmd {
         /// Specify a port for the daemon to listen on.
         #[clap(long, default_value_t = 12500)]
         port: u16,
+        /// The certificate the daemon serves TLS with, in PEM format.
+        #[clap(long, requires = "tls_key")]
+        tls_cert: Option<PathBuf>,
+        /// The private key of the daemon's certificate, in PEM format.
+        #[clap(long, requires = "tls_cert")]
+        tls_key: Option<PathBuf>,
+        /// The CA certificate, in PEM format, that client certificates must be signed by.
+        ///
+        /// Setting it enables mutual TLS: clients without such a certificate cannot connect.
+        #[clap(long, requires = "tls_cert")]
+        tls_client_ca: Option<PathBuf>,
         /// Provide a path for the daemon binary to be used by the service.
         ///
         /// Useful for creating the daemon service using a custom built binary.
@@ -686,6 +907,40 @@ pub enum LocalSubCmd {
         /// Set to remove the client data directory and kill any existing local network.
         #[clap(long)]
         clean: bool,
+        /// Set to run the network in chaos mode once it is up, until Ctrl-C is received.
+        ///
+        /// On each round, a percentage of the nodes are randomly either killed and relaunched after
+        /// a downtime, or restarted. The genesis node is never churned. The schedule of the actions
+        /// is written to the local_chaos_schedule.log file in the autonomi data directory.
+        ///
+        /// The value is the interval between two rounds. Units are seconds.
+        #[clap(long, verbatim_doc_comment)]
+        chaos_interval: Option<u64>,
+        /// Percentage of the nodes churned on each chaos round.
+        #[clap(long, default_value_t = 10, requires = "chaos_interval", value_parser = clap::value_parser!(u8).range(1..=100))]
+        chaos_churn_percent: u8,
+        /// How long a node killed by the chaos mode stays down before being relaunched.
+        ///
+        /// Units are seconds.
+        #[clap(long, default_value_t = 10, requires = "chaos_interval")]
+        chaos_downtime: u64,
+        /// Latency added to the local traffic during chaos mode.
+        ///
+        /// Only supported on Linux, with the tc utility and the privileges to use it.
+        ///
+        /// Units are milliseconds.
+        #[clap(long, requires = "chaos_interval")]
+        chaos_latency: Option<u32>,
+        /// Percentage of the local traffic packets dropped during chaos mode.
+        ///
+        /// Only supported on Linux, with the tc utility and the privileges to use it.
+        #[clap(long, requires = "chaos_interval", value_parser = clap::value_parser!(u8).range(0..=100))]
+        chaos_packet_loss: Option<u8>,
+        /// Seed of the chaos schedule, to reproduce a previous run.
+        ///
+        /// The seed of each run is written to the chaos schedule.
+        #[clap(long, requires = "chaos_interval")]
+        chaos_seed: Option<u64>,
         /// The number of nodes to run.
         #[clap(long, default_value_t = DEFAULT_NODE_COUNT)]
         count: u16,
@@ -817,7 +1072,11 @@ async fn main() -> Result<()> {
         } else {
             Level::TRACE
         };
-        get_log_builder(level)?.initialize()?.1
+        let mut log_builder = get_log_builder(level)?;
+        if let Some(target) = args.log_shipping.clone() {
+            log_builder.log_shipping(target);
+        }
+        log_builder.initialize()?.1
     } else {
         None
     };
@@ -837,6 +1096,7 @@ async fn main() -> Result<()> {
             enable_metrics_server,
             env_variables,
             evm_network,
+            image,
             relay,
             log_dir_path,
             log_format,
@@ -851,6 +1111,7 @@ async fn main() -> Result<()> {
             rewards_address,
             rpc_address,
             rpc_port,
+            runtime,
             url,
             no_upnp,
             user,
@@ -866,6 +1127,7 @@ async fn main() -> Result<()> {
                 enable_metrics_server,
                 env_variables,
                 Some(evm_network.try_into()?),
+                image,
                 log_dir_path,
                 log_format,
                 max_archived_log_files,
@@ -881,6 +1143,7 @@ async fn main() -> Result<()> {
                 rewards_address,
                 rpc_address,
                 rpc_port,
+                runtime,
                 path,
                 no_upnp,
                 url,
@@ -892,18 +1155,54 @@ async fn main() -> Result<()> {
             .await?;
             Ok(())
         }
+        Some(SubCmd::Apply {
+            path,
+            connection_timeout,
+            dry_run,
+        }) => {
+            cmd::node::apply(
+                connection_timeout,
+                dry_run,
+                path,
+                node_registry,
+                get_restart_policy(),
+                verbosity,
+            )
+            .await
+        }
         Some(SubCmd::Balance {
             peer_id: peer_ids,
             service_name: service_names,
         }) => cmd::node::balance(peer_ids, node_registry, service_names, verbosity).await,
         Some(SubCmd::Daemon(DaemonSubCmd::Add {
             address,
+            auth_token,
+            read_only_token,
             env_variables,
             port,
+            tls_cert,
+            tls_key,
+            tls_client_ca,
             path,
             url,
             version,
-        })) => cmd::daemon::add(address, env_variables, port, path, url, version, verbosity).await,
+        })) => {
+            cmd::daemon::add(
+                address,
+                auth_token,
+                read_only_token,
+                env_variables,
+                port,
+                tls_cert,
+                tls_key,
+                tls_client_ca,
+                path,
+                url,
+                version,
+                verbosity,
+            )
+            .await
+        }
         Some(SubCmd::Daemon(DaemonSubCmd::Start {})) => cmd::daemon::start(verbosity).await,
         Some(SubCmd::Daemon(DaemonSubCmd::Stop {})) => cmd::daemon::stop(verbosity).await,
         Some(SubCmd::Local(local_command)) => match local_command {
@@ -951,6 +1250,12 @@ async fn main() -> Result<()> {
             }
             LocalSubCmd::Run {
                 build,
+                chaos_churn_percent,
+                chaos_downtime,
+                chaos_interval,
+                chaos_latency,
+                chaos_packet_loss,
+                chaos_seed,
                 clean,
                 count,
                 enable_metrics_server,
@@ -968,6 +1273,14 @@ async fn main() -> Result<()> {
                 let evm_network = evm_network
                     .unwrap_or(EvmNetworkCommand::EvmLocal)
                     .try_into()?;
+                let chaos = chaos_interval.map(|interval| ChaosOptions {
+                    interval: Duration::from_secs(interval),
+                    churn_percent: chaos_churn_percent,
+                    downtime: Duration::from_secs(chaos_downtime),
+                    latency_ms: chaos_latency,
+                    packet_loss_percent: chaos_packet_loss,
+                    seed: chaos_seed,
+                });
 
                 cmd::local::run(
                     build,
@@ -984,6 +1297,7 @@ async fn main() -> Result<()> {
                     rewards_address,
                     evm_network,
                     true,
+                    chaos,
                     verbosity,
                 )
                 .await
@@ -1003,6 +1317,12 @@ async fn main() -> Result<()> {
             cmd::nat_detection::run_nat_detection(servers, true, path, url, version, verbosity)
                 .await
         }
+        Some(SubCmd::Logs {
+            follow,
+            grep,
+            service_name,
+            since,
+        }) => cmd::node::logs(follow, grep, node_registry, service_name, since, verbosity).await,
         Some(SubCmd::Remove {
             keep_directories,
             peer_id: peer_ids,
@@ -1017,6 +1337,13 @@ async fn main() -> Result<()> {
             )
             .await
         }
+        Some(SubCmd::Replicate {
+            address,
+            peer_id: peer_ids,
+            service_name: service_names,
+        }) => {
+            cmd::node::replicate(address, node_registry, peer_ids, service_names, verbosity).await
+        }
         Some(SubCmd::Reset { force }) => cmd::node::reset(force, node_registry, verbosity).await,
         Some(SubCmd::Start {
             connection_timeout,
@@ -1037,8 +1364,26 @@ async fn main() -> Result<()> {
         Some(SubCmd::Status {
             details,
             fail,
+            host,
+            all_hosts,
+            inventory,
             json,
-        }) => cmd::node::status(details, fail, json, node_registry).await,
+        }) => {
+            if all_hosts || !host.is_empty() {
+                let hosts = select_remote_hosts(inventory, &host, all_hosts)?;
+                cmd::remote::status(hosts, fail).await
+            } else {
+                cmd::node::status(details, fail, json, node_registry).await
+            }
+        }
+        Some(SubCmd::Hibernate {
+            peer_id: peer_ids,
+            service_name: service_names,
+        }) => cmd::node::hibernate(node_registry, peer_ids, service_names, verbosity).await,
+        Some(SubCmd::Resume {
+            peer_id: peer_ids,
+            service_name: service_names,
+        }) => cmd::node::resume(node_registry, peer_ids, service_names, verbosity).await,
         Some(SubCmd::Stop {
             interval,
             peer_id: peer_ids,
@@ -1048,6 +1393,9 @@ async fn main() -> Result<()> {
             connection_timeout,
             do_not_start,
             force,
+            host,
+            all_hosts,
+            inventory,
             interval,
             path,
             peer_id: peer_ids,
@@ -1056,18 +1404,49 @@ async fn main() -> Result<()> {
             url,
             version,
         }) => {
-            cmd::node::upgrade(
-                connection_timeout,
-                do_not_start,
-                path,
-                force,
-                interval,
+            if all_hosts || !host.is_empty() {
+                let hosts = select_remote_hosts(inventory, &host, all_hosts)?;
+                cmd::remote::upgrade(
+                    hosts,
+                    connection_timeout,
+                    do_not_start,
+                    force,
+                    interval,
+                    service_names,
+                    version,
+                    verbosity,
+                )
+                .await
+            } else {
+                cmd::node::upgrade(
+                    connection_timeout,
+                    do_not_start,
+                    path,
+                    force,
+                    interval,
+                    node_registry,
+                    peer_ids,
+                    provided_env_variable,
+                    service_names,
+                    url,
+                    version,
+                    verbosity,
+                )
+                .await
+            }
+        }
+        Some(SubCmd::Verify {
+            sample,
+            cross_check,
+            peer_id: peer_ids,
+            service_name: service_names,
+        }) => {
+            cmd::node::verify(
+                sample,
+                cross_check,
                 node_registry,
                 peer_ids,
-                provided_env_variable,
                 service_names,
-                url,
-                version,
                 verbosity,
             )
             .await
@@ -1076,6 +1455,18 @@ async fn main() -> Result<()> {
     }
 }
 
+fn select_remote_hosts(
+    inventory_path: Option<PathBuf>,
+    hosts: &[String],
+    all_hosts: bool,
+) -> Result<Vec<RemoteHost>> {
+    let inventory_path = match inventory_path {
+        Some(path) => path,
+        None => get_inventory_path()?,
+    };
+    Inventory::load(&inventory_path)?.select(hosts, all_hosts)
+}
+
 /// Get the appropriate restart policy for the current platform
 #[cfg(unix)]
 fn get_restart_policy() -> RestartPolicy {
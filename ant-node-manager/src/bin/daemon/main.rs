@@ -10,11 +10,18 @@
 extern crate tracing;
 
 use ant_logging::LogBuilder;
-use ant_node_manager::{DAEMON_DEFAULT_PORT, config::get_node_registry_path, rpc};
+use ant_node_manager::{
+    DAEMON_DEFAULT_PORT, VerbosityLevel,
+    auth::{AccessLevel, AuthPolicy, require_access, server_tls_config},
+    cmd,
+    config::get_node_registry_path,
+    rpc,
+};
 use ant_service_management::{
     NodeRegistryManager,
     antctl_proto::{
         GetStatusRequest, GetStatusResponse, NodeServiceRestartRequest, NodeServiceRestartResponse,
+        NodeServiceUpgradeRequest, NodeServiceUpgradeResponse,
         ant_ctl_server::{AntCtl, AntCtlServer},
         get_status_response::Node,
     },
@@ -22,7 +29,10 @@ use ant_service_management::{
 use clap::Parser;
 use color_eyre::eyre::{Result, eyre};
 use libp2p_identity::PeerId;
-use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    path::PathBuf,
+};
 use tonic::{Code, Request, Response, Status, transport::Server};
 use tracing::Level;
 
@@ -34,6 +44,27 @@ struct Args {
     /// If not set, the daemon listens locally for commands.
     #[clap(long, default_value_t = Ipv4Addr::new(127, 0, 0, 1))]
     address: Ipv4Addr,
+    /// A secret token that clients must present to issue any command.
+    ///
+    /// A token, or mutual TLS, should be set whenever the daemon listens on an address reachable
+    /// from other machines.
+    #[clap(long, env = "ANTCTLD_AUTH_TOKEN", hide_env_values = true)]
+    auth_token: Option<String>,
+    /// A secret token that clients must present to get the status of the services, without
+    /// being able to change them.
+    #[clap(long, env = "ANTCTLD_READ_ONLY_TOKEN", hide_env_values = true)]
+    read_only_token: Option<String>,
+    /// The certificate of the daemon, in PEM format. The daemon serves TLS when it is set.
+    #[clap(long, requires = "tls_key")]
+    tls_cert: Option<PathBuf>,
+    /// The private key of the daemon's certificate, in PEM format.
+    #[clap(long, requires = "tls_cert")]
+    tls_key: Option<PathBuf>,
+    /// The CA certificate, in PEM format, that client certificates must be signed by.
+    ///
+    /// Setting it enables mutual TLS: clients without such a certificate cannot connect.
+    #[clap(long, requires = "tls_cert")]
+    tls_client_ca: Option<PathBuf>,
     /// Print the crate version.
     #[clap(long)]
     pub crate_version: bool,
@@ -58,6 +89,7 @@ impl AntCtl for AntCtlDaemon {
         &self,
         request: Request<NodeServiceRestartRequest>,
     ) -> Result<Response<NodeServiceRestartResponse>, Status> {
+        require_access(&request, AccessLevel::Control)?;
         println!("RPC request received {:?}", request.get_ref());
         info!("RPC request received {:?}", request.get_ref());
         let node_registry = Self::load_node_registry().await.map_err(|err| {
@@ -86,6 +118,7 @@ impl AntCtl for AntCtlDaemon {
         &self,
         request: Request<GetStatusRequest>,
     ) -> Result<Response<GetStatusResponse>, Status> {
+        require_access(&request, AccessLevel::ReadOnly)?;
         println!("RPC request received {:?}", request.get_ref());
         info!("RPC request received {:?}", request.get_ref());
         let node_registry = Self::load_node_registry().await.map_err(|err| {
@@ -103,12 +136,41 @@ impl AntCtl for AntCtlDaemon {
                 peer_id: node.peer_id.map(|id| id.to_bytes()),
                 status: node.status.clone() as i32,
                 number: node.number as u32,
+                service_name: node.service_name.clone(),
+                version: node.version.clone(),
             });
         }
 
         info!("Node status retrieved, nod len: {:?}", nodes_info.len());
         Ok(Response::new(GetStatusResponse { nodes: nodes_info }))
     }
+
+    async fn upgrade_node_services(
+        &self,
+        request: Request<NodeServiceUpgradeRequest>,
+    ) -> Result<Response<NodeServiceUpgradeResponse>, Status> {
+        require_access(&request, AccessLevel::Control)?;
+        println!("RPC request received {:?}", request.get_ref());
+        info!("RPC request received {:?}", request.get_ref());
+        let node_registry = Self::load_node_registry().await.map_err(|err| {
+            Status::new(
+                Code::Internal,
+                format!("Failed to load node registry: {err}"),
+            )
+        })?;
+
+        Self::upgrade_handler(node_registry, request.into_inner())
+            .await
+            .map_err(|err| {
+                Status::new(
+                    Code::Internal,
+                    format!("Failed to upgrade the node services: {err}"),
+                )
+            })?;
+
+        info!("Node services upgraded");
+        Ok(Response::new(NodeServiceUpgradeResponse {}))
+    }
 }
 
 impl AntCtlDaemon {
@@ -133,6 +195,32 @@ impl AntCtlDaemon {
 
         res
     }
+
+    async fn upgrade_handler(
+        node_registry: NodeRegistryManager,
+        request: NodeServiceUpgradeRequest,
+    ) -> Result<()> {
+        let res = cmd::node::upgrade(
+            request.connection_timeout_s,
+            request.do_not_start,
+            None,
+            request.force,
+            request.interval_millis,
+            node_registry.clone(),
+            vec![],
+            None,
+            request.service_names,
+            None,
+            request.version,
+            VerbosityLevel::Minimal,
+        )
+        .await;
+
+        // make sure to save the state even if the above fn fails.
+        node_registry.save().await?;
+
+        res
+    }
 }
 
 // The SafeNodeManager trait returns `Status` as its error. So the actual logic is here and we can easily map the errors
@@ -170,8 +258,43 @@ async fn main() -> Result<()> {
     println!("Starting antctld");
     let service = AntCtlDaemon {};
 
-    if let Err(err) = Server::builder()
-        .add_service(AntCtlServer::new(service))
+    let policy = AuthPolicy {
+        control_token: args.auth_token,
+        read_only_token: args.read_only_token,
+    };
+    if !args.address.is_loopback() {
+        if !policy.requires_token() && args.tls_client_ca.is_none() {
+            warn!(
+                "antctld listens on {} without auth tokens or mutual TLS",
+                args.address
+            );
+            println!(
+                "WARNING: antctld listens on {} without auth tokens or mutual TLS, anyone who can reach it can manage the nodes",
+                args.address
+            );
+        } else if policy.requires_token() && args.tls_cert.is_none() {
+            warn!("antctld listens on {} without TLS", args.address);
+            println!(
+                "WARNING: antctld listens on {} without TLS, the auth tokens are sent in clear text",
+                args.address
+            );
+        }
+    }
+
+    let mut server = Server::builder();
+    if let (Some(cert_path), Some(key_path)) = (&args.tls_cert, &args.tls_key) {
+        info!("Serving TLS with the certificate at {cert_path:?}");
+        server = server.tls_config(server_tls_config(
+            cert_path,
+            key_path,
+            args.tls_client_ca.as_deref(),
+        )?)?;
+    }
+
+    if let Err(err) = server
+        .add_service(AntCtlServer::with_interceptor(service, move |request| {
+            policy.authorize(request)
+        }))
         .serve(SocketAddr::new(IpAddr::V4(args.address), args.port))
         .await
     {
@@ -0,0 +1,421 @@
+// Copyright (C) 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Chaos mode for a local network.
+//!
+//! Nodes are randomly killed or restarted on an interval, to validate the client retry logic and
+//! the replication against churn. Every action is written to a schedule file, and the seed of the
+//! schedule is logged so a run can be reproduced.
+
+use ant_service_management::{
+    NodeRegistryManager, NodeServiceData,
+    rpc::{RpcActions, RpcClient},
+};
+use color_eyre::{Result, eyre::eyre};
+use colored::Colorize;
+use libp2p::multiaddr::Protocol;
+use rand::{Rng, SeedableRng, rngs::StdRng, seq::index::sample};
+use std::{
+    fs::{File, OpenOptions},
+    io::Write,
+    path::PathBuf,
+    process::{Command, Stdio},
+    sync::Arc,
+};
+use sysinfo::{Pid, System};
+use tokio::time::{Duration, sleep};
+
+/// Time given to a relaunched or restarted node before querying it through RPC.
+const NODE_STARTUP_WAIT: Duration = Duration::from_secs(5);
+
+/// Options of the chaos mode of a local network.
+#[derive(Clone, Debug)]
+pub struct ChaosOptions {
+    /// Interval between two rounds of churn.
+    pub interval: Duration,
+    /// Percentage of the nodes churned on each round.
+    pub churn_percent: u8,
+    /// How long a killed node stays down before being relaunched.
+    pub downtime: Duration,
+    /// Latency added to the loopback traffic, in milliseconds.
+    pub latency_ms: Option<u32>,
+    /// Percentage of the loopback packets dropped.
+    pub packet_loss_percent: Option<u8>,
+    /// Seed of the schedule. A random seed is used if not set.
+    pub seed: Option<u64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ChaosAction {
+    /// Kill the process abruptly, then relaunch it with the same identity after the downtime.
+    Kill,
+    /// Gracefully restart the node through its RPC service.
+    Restart,
+}
+
+/// Run the chaos mode against the nodes of the local network, until Ctrl-C is received.
+///
+/// The genesis node is never churned, so that the network stays joinable.
+pub async fn run_chaos(options: ChaosOptions, node_registry: NodeRegistryManager) -> Result<()> {
+    let seed = options.seed.unwrap_or_else(rand::random);
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut schedule = ChaosSchedule::create()?;
+
+    println!(
+        "Running chaos mode: churning {}% of the nodes every {:?}. Press Ctrl-C to stop.",
+        options.churn_percent, options.interval
+    );
+    println!("Chaos schedule: {}", schedule.path.to_string_lossy());
+    schedule.record(&format!("Started with seed {seed} and options {options:?}"));
+
+    let impairment = if options.latency_ms.is_some() || options.packet_loss_percent.is_some() {
+        NetworkImpairment::apply(
+            options.latency_ms,
+            options.packet_loss_percent,
+            &mut schedule,
+        )
+    } else {
+        None
+    };
+
+    // A single listener for the whole run, so a Ctrl-C received during a round is not missed.
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+    loop {
+        tokio::select! {
+            _ = &mut ctrl_c => {
+                println!("Stopping chaos mode");
+                break;
+            }
+            _ = sleep(options.interval) => {
+                if let Err(err) = churn_round(&options, &node_registry, &mut rng, &mut schedule).await {
+                    error!("Chaos round failed: {err:?}");
+                    schedule.record(&format!("Round failed: {err}"));
+                }
+            }
+        }
+    }
+
+    if let Some(impairment) = impairment {
+        impairment.remove(&mut schedule);
+    }
+    schedule.record("Stopped");
+    node_registry.save().await?;
+
+    Ok(())
+}
+
+async fn churn_round(
+    options: &ChaosOptions,
+    node_registry: &NodeRegistryManager,
+    rng: &mut StdRng,
+    schedule: &mut ChaosSchedule,
+) -> Result<()> {
+    let mut candidates = Vec::new();
+    for node in node_registry.nodes.read().await.iter() {
+        if !node.read().await.initial_peers_config.first {
+            candidates.push(Arc::clone(node));
+        }
+    }
+
+    let count = churn_count(candidates.len(), options.churn_percent);
+    let selected: Vec<_> = sample(rng, candidates.len(), count)
+        .into_iter()
+        .map(|index| (Arc::clone(&candidates[index]), random_action(rng)))
+        .collect();
+
+    let mut system = System::new_all();
+    system.refresh_all();
+
+    let mut killed = Vec::new();
+    for (node, action) in &selected {
+        let node_data = node.read().await;
+        schedule.record(&format!("{action:?} {}", node_data.service_name));
+        println!("{} {action:?} {}", "⚡".yellow(), node_data.service_name);
+
+        match action {
+            ChaosAction::Kill => match kill_node(&node_data, &system) {
+                Ok(relaunch) => killed.push((Arc::clone(node), relaunch)),
+                Err(err) => {
+                    schedule.record(&format!("Failed to kill {}: {err}", node_data.service_name))
+                }
+            },
+            ChaosAction::Restart => {
+                let rpc_client = RpcClient::from_socket_addr(node_data.rpc_socket_addr);
+                if let Err(err) = rpc_client.node_restart(0, true).await {
+                    schedule.record(&format!(
+                        "Failed to restart {}: {err}",
+                        node_data.service_name
+                    ));
+                }
+            }
+        }
+    }
+
+    if !killed.is_empty() {
+        sleep(options.downtime).await;
+        for (node, relaunch) in &killed {
+            let service_name = node.read().await.service_name.clone();
+            schedule.record(&format!("Relaunch {service_name}"));
+            if let Err(err) = relaunch.spawn() {
+                schedule.record(&format!("Failed to relaunch {service_name}: {err}"));
+            }
+        }
+    }
+
+    // The processes of the churned nodes have changed, keep the registry up to date.
+    sleep(NODE_STARTUP_WAIT).await;
+    for (node, _) in &selected {
+        let rpc_socket_addr = node.read().await.rpc_socket_addr;
+        match RpcClient::from_socket_addr(rpc_socket_addr)
+            .node_info()
+            .await
+        {
+            Ok(node_info) => node.write().await.pid = Some(node_info.pid),
+            Err(err) => {
+                let service_name = node.read().await.service_name.clone();
+                schedule.record(&format!("{service_name} is not back up: {err}"));
+            }
+        }
+    }
+    node_registry.save().await?;
+
+    Ok(())
+}
+
+/// Number of nodes churned out of `total`, rounded up so at least one node is churned.
+fn churn_count(total: usize, churn_percent: u8) -> usize {
+    (total * churn_percent as usize)
+        .div_ceil(100)
+        .clamp(total.min(1), total)
+}
+
+fn random_action(rng: &mut StdRng) -> ChaosAction {
+    if rng.gen_bool(0.5) {
+        ChaosAction::Kill
+    } else {
+        ChaosAction::Restart
+    }
+}
+
+/// Command to relaunch a killed node with the same identity.
+struct Relaunch {
+    program: PathBuf,
+    args: Vec<String>,
+}
+
+impl Relaunch {
+    fn spawn(&self) -> Result<()> {
+        let _ = Command::new(&self.program)
+            .args(&self.args)
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .spawn()?;
+        Ok(())
+    }
+}
+
+/// Kill the process of the node, returning how to relaunch it.
+fn kill_node(node: &NodeServiceData, system: &System) -> Result<Relaunch> {
+    let pid = node
+        .pid
+        .ok_or_else(|| eyre!("The PID was not set for {}", node.service_name))?;
+    let process = system
+        .process(Pid::from(pid as usize))
+        .ok_or_else(|| eyre!("No process found for {} ({pid})", node.service_name))?;
+
+    let mut original_args: Vec<String> = process.cmd().iter().skip(1).cloned().collect();
+    original_args.retain(|arg| arg != "--first");
+
+    // As for a restart, the root dir and port are set so the node retains its peer id. They must
+    // come first, as the EVM network arguments must be at the end.
+    let mut args = Vec::new();
+    if !original_args.iter().any(|arg| arg == "--root-dir") {
+        args.push("--root-dir".to_string());
+        args.push(node.data_dir_path.to_string_lossy().to_string());
+    }
+    let port = node.node_port.or_else(|| {
+        node.listen_addr.iter().flatten().find_map(|addr| {
+            addr.iter().find_map(|protocol| match protocol {
+                Protocol::Udp(port) => Some(port),
+                _ => None,
+            })
+        })
+    });
+    if let Some(port) = port
+        && !original_args.iter().any(|arg| arg == "--port")
+    {
+        args.push("--port".to_string());
+        args.push(port.to_string());
+    }
+    args.extend(original_args);
+
+    let program = process
+        .exe()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| node.antnode_path.clone());
+
+    if !process.kill() {
+        return Err(eyre!("Failed to kill {} ({pid})", node.service_name));
+    }
+    debug!("Chaos killed node: {} ({pid})", node.service_name);
+
+    Ok(Relaunch { program, args })
+}
+
+/// Append only log of the chaos actions.
+struct ChaosSchedule {
+    path: PathBuf,
+    file: File,
+}
+
+impl ChaosSchedule {
+    fn create() -> Result<Self> {
+        let path = dirs_next::data_dir()
+            .ok_or_else(|| eyre!("Could not obtain user's data directory"))?
+            .join("autonomi")
+            .join("local_chaos_schedule.log");
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        Ok(Self { path, file })
+    }
+
+    fn record(&mut self, entry: &str) {
+        info!("Chaos: {entry}");
+        let timestamp = chrono::Utc::now().to_rfc3339();
+        if let Err(err) = writeln!(self.file, "{timestamp} {entry}") {
+            warn!("Failed to write to the chaos schedule: {err}");
+        }
+    }
+}
+
+/// Latency and packet loss injected on the loopback interface, with `tc netem`.
+///
+/// Only supported on Linux, and requires the privileges to change the qdisc of the interface.
+/// The impairment is removed when dropped, if not removed before, so that the loopback interface
+/// is not left impaired whichever way the chaos mode ends.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+struct NetworkImpairment {
+    active: bool,
+}
+
+impl NetworkImpairment {
+    #[cfg(target_os = "linux")]
+    fn apply(
+        latency_ms: Option<u32>,
+        packet_loss_percent: Option<u8>,
+        schedule: &mut ChaosSchedule,
+    ) -> Option<Self> {
+        let mut args: Vec<String> = ["qdisc", "add", "dev", "lo", "root", "netem"]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+        if let Some(latency_ms) = latency_ms {
+            args.extend(["delay".to_string(), format!("{latency_ms}ms")]);
+        }
+        if let Some(packet_loss_percent) = packet_loss_percent {
+            args.extend(["loss".to_string(), format!("{packet_loss_percent}%")]);
+        }
+
+        match Command::new("tc").args(&args).output() {
+            Ok(output) if output.status.success() => {
+                schedule.record(&format!("Applied tc {}", args.join(" ")));
+                Some(Self { active: true })
+            }
+            Ok(output) => {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                println!(
+                    "{} Could not inject latency/packet loss: {stderr}",
+                    "✕".red()
+                );
+                schedule.record(&format!("Failed to apply tc {}: {stderr}", args.join(" ")));
+                None
+            }
+            Err(err) => {
+                println!("{} Could not inject latency/packet loss: {err}", "✕".red());
+                schedule.record(&format!("Failed to run tc: {err}"));
+                None
+            }
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn apply(
+        _latency_ms: Option<u32>,
+        _packet_loss_percent: Option<u8>,
+        schedule: &mut ChaosSchedule,
+    ) -> Option<Self> {
+        println!(
+            "{} Latency and packet loss injection is only supported on Linux",
+            "✕".red()
+        );
+        schedule.record("Latency and packet loss injection is not supported on this platform");
+        None
+    }
+
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    fn remove(mut self, schedule: &mut ChaosSchedule) {
+        self.active = false;
+        match Self::delete_qdisc() {
+            Ok(()) => schedule.record("Removed latency/packet loss from the loopback interface"),
+            Err(err) => schedule.record(&format!("Failed to remove latency/packet loss: {err}")),
+        }
+    }
+
+    fn delete_qdisc() -> Result<()> {
+        let output = Command::new("tc")
+            .args(["qdisc", "del", "dev", "lo", "root", "netem"])
+            .output()?;
+        if !output.status.success() {
+            return Err(eyre!("{}", String::from_utf8_lossy(&output.stderr)));
+        }
+        Ok(())
+    }
+}
+
+impl Drop for NetworkImpairment {
+    fn drop(&mut self) {
+        if !self.active {
+            return;
+        }
+        match Self::delete_qdisc() {
+            Ok(()) => info!("Chaos: Removed latency/packet loss from the loopback interface"),
+            Err(err) => {
+                println!(
+                    "{} Could not remove latency/packet loss from the loopback interface: {err}",
+                    "✕".red()
+                );
+                warn!("Chaos: Failed to remove latency/packet loss: {err}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn churn_count_should_round_up_to_at_least_one_node() {
+        assert_eq!(churn_count(0, 10), 0);
+        assert_eq!(churn_count(5, 10), 1);
+        assert_eq!(churn_count(24, 10), 3);
+        assert_eq!(churn_count(24, 100), 24);
+    }
+
+    #[test]
+    fn random_action_should_be_reproducible_from_the_seed() {
+        let mut first = StdRng::seed_from_u64(42);
+        let mut second = StdRng::seed_from_u64(42);
+        let first_actions: Vec<_> = (0..10).map(|_| random_action(&mut first)).collect();
+        let second_actions: Vec<_> = (0..10).map(|_| random_action(&mut second)).collect();
+        assert_eq!(first_actions, second_actions);
+    }
+}
@@ -23,8 +23,13 @@ use std::{net::Ipv4Addr, path::PathBuf, sync::Arc};
 
 pub async fn add(
     address: Ipv4Addr,
-    env_variables: Option<Vec<(String, String)>>,
+    auth_token: Option<String>,
+    read_only_token: Option<String>,
+    mut env_variables: Option<Vec<(String, String)>>,
     port: u16,
+    tls_cert_path: Option<PathBuf>,
+    tls_key_path: Option<PathBuf>,
+    tls_client_ca_path: Option<PathBuf>,
     src_path: Option<PathBuf>,
     url: Option<String>,
     version: Option<String>,
@@ -64,6 +69,19 @@ pub async fn add(
 
     info!("Adding daemon service");
 
+    // The tokens are passed through the environment of the service rather than its arguments,
+    // so that they do not show in the process list.
+    for (var, token) in [
+        ("ANTCTLD_AUTH_TOKEN", auth_token),
+        ("ANTCTLD_READ_ONLY_TOKEN", read_only_token),
+    ] {
+        if let Some(token) = token {
+            env_variables
+                .get_or_insert_with(Vec::new)
+                .push((var.to_string(), token));
+        }
+    }
+
     // At the moment we don't have the option to provide a user for running the service. Since
     // `antctld` requires manipulation of services, the user running it must either be root or
     // have root access. For now we will just use the `root` user. The user option gets ignored on
@@ -75,6 +93,9 @@ pub async fn add(
             daemon_install_bin_path: config::get_daemon_install_path(),
             daemon_src_bin_path,
             port,
+            tls_cert_path,
+            tls_key_path,
+            tls_client_ca_path,
             user: "root".to_string(),
             version,
         },
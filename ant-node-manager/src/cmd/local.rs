@@ -12,6 +12,7 @@ use super::get_bin_path;
 use crate::{
     VerbosityLevel,
     add_services::config::PortRange,
+    chaos::{ChaosOptions, run_chaos},
     local::{LocalNetworkOptions, kill_network, run_network},
     print_banner, status_report,
 };
@@ -130,6 +131,7 @@ pub async fn run(
     rewards_address: RewardsAddress,
     evm_network: EvmNetwork,
     skip_validation: bool,
+    chaos: Option<ChaosOptions>,
     verbosity: VerbosityLevel,
 ) -> Result<(), Report> {
     if (enable_metrics_server || metrics_port.is_some()) && !cfg!(feature = "open-metrics") && build
@@ -206,6 +208,13 @@ pub async fn run(
     run_network(options, local_node_registry.clone(), &ServiceController {}).await?;
 
     local_node_registry.save().await?;
+
+    if let Some(chaos) = chaos {
+        if verbosity != VerbosityLevel::Minimal {
+            print_banner("Chaos Mode");
+        }
+        run_chaos(chaos, local_node_registry).await?;
+    }
     Ok(())
 }
 
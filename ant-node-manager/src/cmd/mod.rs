@@ -10,6 +10,7 @@ pub mod daemon;
 pub mod local;
 pub mod nat_detection;
 pub mod node;
+pub mod remote;
 
 use crate::{
     VerbosityLevel,
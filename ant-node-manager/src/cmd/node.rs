@@ -16,8 +16,9 @@ use crate::{
         config::{AddNodeServiceOptions, PortRange},
     },
     config::{self, is_running_as_root},
+    fleet::{FleetPlan, FleetSpec},
     helpers::{download_and_extract_release, get_bin_version},
-    print_banner, refresh_node_registry, status_report,
+    logs, print_banner, refresh_node_registry, status_report,
 };
 use ant_bootstrap::{Bootstrap, InitialPeersConfig};
 use ant_evm::{EvmNetwork, RewardsAddress};
@@ -26,11 +27,14 @@ use ant_releases::{AntReleaseRepoActions, ReleaseType};
 use ant_service_management::{
     NodeRegistryManager, NodeService, NodeServiceData, ServiceStateActions, ServiceStatus,
     UpgradeOptions, UpgradeResult,
+    container::{ContainerRuntime, DEFAULT_ANTNODE_IMAGE, NodeContainer},
     control::{ServiceControl, ServiceController},
-    rpc::RpcClient,
+    rpc::{RecordAddress, RpcActions, RpcClient},
+    systemd::{NODE_WATCHDOG_SECS, is_systemd_available},
 };
 use color_eyre::{Help, Result, eyre::eyre};
 use colored::Colorize;
+use libp2p::kad::RecordKey;
 use libp2p_identity::PeerId;
 use semver::Version;
 use service_manager::RestartPolicy;
@@ -50,6 +54,7 @@ pub async fn add(
     enable_metrics_server: bool,
     env_variables: Option<Vec<(String, String)>>,
     evm_network: Option<EvmNetwork>,
+    image: Option<String>,
     log_dir_path: Option<PathBuf>,
     log_format: Option<LogFormat>,
     max_archived_log_files: Option<usize>,
@@ -65,6 +70,7 @@ pub async fn add(
     rewards_address: RewardsAddress,
     rpc_address: Option<Ipv4Addr>,
     rpc_port: Option<PortRange>,
+    runtime: Option<ContainerRuntime>,
     src_path: Option<PathBuf>,
     no_upnp: bool,
     url: Option<String>,
@@ -101,7 +107,21 @@ pub async fn add(
 
     let release_repo = <dyn AntReleaseRepoActions>::default_config();
 
-    let (antnode_src_path, version) = if let Some(path) = src_path.clone() {
+    let container = runtime.map(|runtime| NodeContainer {
+        runtime,
+        image: image.unwrap_or_else(|| DEFAULT_ANTNODE_IMAGE.to_string()),
+    });
+    let (antnode_src_path, version) = if container.is_some() {
+        // Containerized nodes run from the image tagged with their version, no binary is needed
+        let version = match version {
+            Some(version) => version,
+            None => release_repo
+                .get_latest_version(&ReleaseType::AntNode)
+                .await?
+                .to_string(),
+        };
+        (PathBuf::from("antnode"), version)
+    } else if let Some(path) = src_path.clone() {
         let version = get_bin_version(&path)?;
         (path, version)
     } else {
@@ -127,8 +147,9 @@ pub async fn add(
         antnode_src_path,
         auto_restart,
         auto_set_nat_flags,
+        container: container.clone(),
         count,
-        delete_antnode_src: src_path.is_none(),
+        delete_antnode_src: src_path.is_none() && container.is_none(),
         enable_metrics_server,
         evm_network: evm_network.unwrap_or(EvmNetwork::ArbitrumOne),
         env_variables,
@@ -150,12 +171,19 @@ pub async fn add(
         service_log_dir_path,
         user: service_user,
         user_mode,
-        version,
+        version: version.clone(),
+        watchdog_secs: (container.is_none() && is_systemd_available())
+            .then_some(NODE_WATCHDOG_SECS),
         write_older_cache_files,
     };
     info!("Adding node service(s)");
-    let added_services_names =
-        add_node(options, node_registry.clone(), &service_manager, verbosity).await?;
+    let added_services_names = match &container {
+        Some(container) => {
+            let service_control = container.service_control(&version);
+            add_node(options, node_registry.clone(), &service_control, verbosity).await?
+        }
+        None => add_node(options, node_registry.clone(), &service_manager, verbosity).await?,
+    };
 
     node_registry.save().await?;
     debug!("Node registry saved");
@@ -199,6 +227,29 @@ pub async fn balance(
     Ok(())
 }
 
+pub async fn logs(
+    follow: bool,
+    grep: Option<String>,
+    node_registry: NodeRegistryManager,
+    service_name: String,
+    since: Option<Duration>,
+    verbosity: VerbosityLevel,
+) -> Result<()> {
+    let node = node_registry
+        .node_by_service_name(&service_name)
+        .await
+        .ok_or_else(|| eyre!("No service named '{service_name}' in the node registry"))?;
+    let filter = logs::LogFilter::new(grep.as_deref(), since)?;
+
+    if verbosity != VerbosityLevel::Minimal {
+        println!(
+            "Logs of {service_name} in {}",
+            node.log_dir_path.to_string_lossy()
+        );
+    }
+    logs::print_logs(&node.log_dir_path, filter, follow).await
+}
+
 pub async fn remove(
     keep_directories: bool,
     peer_ids: Vec<String>,
@@ -237,8 +288,8 @@ pub async fn remove(
         let service_name = node.read().await.service_name.clone();
         let rpc_client = RpcClient::from_socket_addr(node.read().await.rpc_socket_addr);
         let service = NodeService::new(Arc::clone(node), Box::new(rpc_client));
-        let mut service_manager =
-            ServiceManager::new(service, Box::new(ServiceController {}), verbosity);
+        let service_control = node.read().await.service_control();
+        let mut service_manager = ServiceManager::new(service, service_control, verbosity);
         match service_manager.remove(keep_directories).await {
             Ok(()) => {
                 debug!("Removed service {service_name}");
@@ -339,8 +390,8 @@ pub async fn start(
             service
         };
 
-        let mut service_manager =
-            ServiceManager::new(service, Box::new(ServiceController {}), verbosity);
+        let service_control = node.read().await.service_control();
+        let mut service_manager = ServiceManager::new(service, service_control, verbosity);
         if service_manager.service.status().await != ServiceStatus::Running {
             // It would be possible here to check if the service *is* running and then just
             // continue without applying the delay. The reason for not doing so is because when
@@ -427,8 +478,8 @@ pub async fn stop(
         let service_name = node.read().await.service_name.clone();
         let rpc_client = RpcClient::from_socket_addr(node.read().await.rpc_socket_addr);
         let service = NodeService::new(Arc::clone(node), Box::new(rpc_client));
-        let mut service_manager =
-            ServiceManager::new(service, Box::new(ServiceController {}), verbosity);
+        let service_control = node.read().await.service_control();
+        let mut service_manager = ServiceManager::new(service, service_control, verbosity);
 
         if service_manager.service.status().await == ServiceStatus::Running
             && let Some(interval) = interval
@@ -451,6 +502,247 @@ pub async fn stop(
     summarise_any_failed_ops(failed_services, "stop", verbosity)
 }
 
+pub async fn hibernate(
+    node_registry: NodeRegistryManager,
+    peer_ids: Vec<String>,
+    service_names: Vec<String>,
+    verbosity: VerbosityLevel,
+) -> Result<()> {
+    if verbosity != VerbosityLevel::Minimal {
+        print_banner("Hibernate Antnode Services");
+    }
+    info!("Hibernating antnode services for: {peer_ids:?}, {service_names:?}");
+    set_hibernation(true, node_registry, peer_ids, service_names, verbosity).await
+}
+
+pub async fn resume(
+    node_registry: NodeRegistryManager,
+    peer_ids: Vec<String>,
+    service_names: Vec<String>,
+    verbosity: VerbosityLevel,
+) -> Result<()> {
+    if verbosity != VerbosityLevel::Minimal {
+        print_banner("Resume Antnode Services");
+    }
+    info!("Resuming antnode services for: {peer_ids:?}, {service_names:?}");
+    set_hibernation(false, node_registry, peer_ids, service_names, verbosity).await
+}
+
+async fn set_hibernation(
+    hibernate: bool,
+    node_registry: NodeRegistryManager,
+    peer_ids: Vec<String>,
+    service_names: Vec<String>,
+    verbosity: VerbosityLevel,
+) -> Result<()> {
+    let verb = if hibernate { "hibernate" } else { "resume" };
+
+    refresh_node_registry(
+        node_registry.clone(),
+        &ServiceController {},
+        verbosity != VerbosityLevel::Minimal,
+        false,
+        verbosity,
+    )
+    .await?;
+
+    let services_for_ops = get_services_for_ops(&node_registry, peer_ids, service_names).await?;
+    let mut failed_services = Vec::new();
+    for node in services_for_ops.iter() {
+        let node = node.read().await;
+        if node.status != ServiceStatus::Running {
+            debug!("Service {} is not running, skipping it", node.service_name);
+            if verbosity != VerbosityLevel::Minimal {
+                println!("Service {} is not running", node.service_name);
+            }
+            continue;
+        }
+
+        let rpc_client = RpcClient::from_socket_addr(node.rpc_socket_addr);
+        let result = if hibernate {
+            rpc_client.node_hibernate().await
+        } else {
+            rpc_client.node_resume().await
+        };
+        match result {
+            Ok(()) => {
+                debug!("Requested service {} to {verb}", node.service_name);
+                if verbosity != VerbosityLevel::Minimal {
+                    println!("{} Requested {} to {verb}", "✓".green(), node.service_name);
+                }
+            }
+            Err(err) => {
+                error!("Failed to {verb} service {}: {err}", node.service_name);
+                failed_services.push((node.service_name.clone(), err.to_string()))
+            }
+        }
+    }
+
+    summarise_any_failed_ops(failed_services, verb, verbosity)
+}
+
+pub async fn replicate(
+    address: String,
+    node_registry: NodeRegistryManager,
+    peer_ids: Vec<String>,
+    service_names: Vec<String>,
+    verbosity: VerbosityLevel,
+) -> Result<()> {
+    if verbosity != VerbosityLevel::Minimal {
+        print_banner("Replicate Record");
+    }
+    info!(
+        "Replicating record {address} from antnode services for: {peer_ids:?}, {service_names:?}"
+    );
+
+    let key = RecordKey::new(
+        &hex::decode(address.trim_start_matches("0x"))
+            .map_err(|err| eyre!("The record address is not valid hex: {err}"))?,
+    );
+
+    refresh_node_registry(
+        node_registry.clone(),
+        &ServiceController {},
+        verbosity != VerbosityLevel::Minimal,
+        false,
+        verbosity,
+    )
+    .await?;
+
+    let services_for_ops = get_services_for_ops(&node_registry, peer_ids, service_names).await?;
+    let mut holders = 0;
+    let mut failed_services = Vec::new();
+    for node in services_for_ops.iter() {
+        let node = node.read().await;
+        if node.status != ServiceStatus::Running {
+            debug!("Service {} is not running, skipping it", node.service_name);
+            continue;
+        }
+
+        let rpc_client = RpcClient::from_socket_addr(node.rpc_socket_addr);
+        match rpc_client
+            .replicate_record(RecordAddress { key: key.clone() })
+            .await
+        {
+            Ok(None) => {
+                debug!("Service {} does not hold {address}", node.service_name);
+            }
+            Ok(Some(replicated_to)) => {
+                holders += 1;
+                debug!(
+                    "Service {} pushed {address} to {replicated_to:?}",
+                    node.service_name
+                );
+                if verbosity != VerbosityLevel::Minimal {
+                    println!(
+                        "{} {} pushed the record to {} peer(s)",
+                        "✓".green(),
+                        node.service_name,
+                        replicated_to.len()
+                    );
+                    for peer_id in replicated_to {
+                        println!("    {peer_id}");
+                    }
+                }
+            }
+            Err(err) => {
+                error!(
+                    "Failed to replicate {address} from {}: {err}",
+                    node.service_name
+                );
+                failed_services.push((node.service_name.clone(), err.to_string()))
+            }
+        }
+    }
+
+    if holders == 0 && failed_services.is_empty() {
+        println!("None of the running services hold the record {address}");
+    }
+
+    summarise_any_failed_ops(failed_services, "replicate", verbosity)
+}
+
+pub async fn verify(
+    sample_size: u32,
+    cross_check_count: u32,
+    node_registry: NodeRegistryManager,
+    peer_ids: Vec<String>,
+    service_names: Vec<String>,
+    verbosity: VerbosityLevel,
+) -> Result<()> {
+    if verbosity != VerbosityLevel::Minimal {
+        print_banner("Verify Records");
+    }
+    info!(
+        "Verifying {sample_size} records of antnode services for: {peer_ids:?}, {service_names:?}"
+    );
+
+    refresh_node_registry(
+        node_registry.clone(),
+        &ServiceController {},
+        verbosity != VerbosityLevel::Minimal,
+        false,
+        verbosity,
+    )
+    .await?;
+
+    let services_for_ops = get_services_for_ops(&node_registry, peer_ids, service_names).await?;
+    let mut failed_services = Vec::new();
+    for node in services_for_ops.iter() {
+        let node = node.read().await;
+        if node.status != ServiceStatus::Running {
+            debug!("Service {} is not running, skipping it", node.service_name);
+            continue;
+        }
+
+        let rpc_client = RpcClient::from_socket_addr(node.rpc_socket_addr);
+        let verification = match rpc_client
+            .verify_records(sample_size, cross_check_count)
+            .await
+        {
+            Ok(verification) => verification,
+            Err(err) => {
+                error!("Failed to verify records of {}: {err}", node.service_name);
+                failed_services.push((node.service_name.clone(), err.to_string()));
+                continue;
+            }
+        };
+        debug!(
+            "Records verification of {}: {verification:?}",
+            node.service_name
+        );
+
+        let is_healthy = verification.corrupted.is_empty() && verification.diverging.is_empty();
+        let mark = if is_healthy {
+            "✓".green()
+        } else {
+            "✕".red()
+        };
+        println!(
+            "{mark} {}: {}/{} records intact on disk, {} cross-checked, {} diverging, {} unconfirmed",
+            node.service_name,
+            verification.intact(),
+            verification.sampled,
+            verification.cross_checked,
+            verification.diverging.len(),
+            verification.unconfirmed.len()
+        );
+        if verbosity != VerbosityLevel::Minimal {
+            for key in verification.corrupted.iter() {
+                println!("    corrupted: {}", hex::encode(key.to_vec()));
+            }
+            for key in verification.diverging.iter() {
+                println!("    diverging: {}", hex::encode(key.to_vec()));
+            }
+            for key in verification.unconfirmed.iter() {
+                println!("    unconfirmed: {}", hex::encode(key.to_vec()));
+            }
+        }
+    }
+
+    summarise_any_failed_ops(failed_services, "verify", verbosity)
+}
+
 pub async fn upgrade(
     connection_timeout_s: u64,
     do_not_start: bool,
@@ -553,8 +845,11 @@ pub async fn upgrade(
             service
         };
 
-        let mut service_manager =
-            ServiceManager::new(service, Box::new(ServiceController {}), verbosity);
+        let service_control = node
+            .read()
+            .await
+            .service_control_for_version(&target_version.to_string());
+        let mut service_manager = ServiceManager::new(service, service_control, verbosity);
 
         match service_manager.upgrade(options).await {
             Ok(upgrade_result) => {
@@ -728,6 +1023,7 @@ pub async fn maintain_n_running_nodes(
                         enable_metrics_server,
                         env_variables.clone(),
                         evm_network.clone(),
+                        None,
                         log_dir_path.clone(),
                         log_format,
                         max_archived_log_files,
@@ -743,6 +1039,7 @@ pub async fn maintain_n_running_nodes(
                         rewards_address,
                         rpc_address,
                         rpc_port.clone(),
+                        None,
                         src_path.clone(),
                         no_upnp,
                         url.clone(),
@@ -805,6 +1102,173 @@ pub async fn maintain_n_running_nodes(
     Ok(())
 }
 
+/// Converge the services to the fleet declared in the fleet file, by removing, upgrading and
+/// adding services, then starting any service of the fleet that is not running.
+pub async fn apply(
+    connection_timeout_s: u64,
+    dry_run: bool,
+    fleet_path: PathBuf,
+    node_registry: NodeRegistryManager,
+    restart_policy: RestartPolicy,
+    verbosity: VerbosityLevel,
+) -> Result<()> {
+    if verbosity != VerbosityLevel::Minimal {
+        print_banner("Apply Antnode Fleet");
+    }
+    let spec = FleetSpec::from_file(&fleet_path)?;
+    info!("Applying fleet file {fleet_path:?}: {spec:?}");
+
+    refresh_node_registry(
+        node_registry.clone(),
+        &ServiceController {},
+        verbosity != VerbosityLevel::Minimal,
+        false,
+        verbosity,
+    )
+    .await?;
+
+    let mut services = Vec::new();
+    for node in node_registry.nodes.read().await.iter() {
+        let node = node.read().await;
+        if node.status != ServiceStatus::Removed {
+            services.push(node.clone());
+        }
+    }
+    let plan = FleetPlan::new(&spec, &services.iter().collect::<Vec<_>>())?;
+    info!("Fleet plan: {plan:?}");
+
+    if verbosity != VerbosityLevel::Minimal {
+        if plan.is_empty() {
+            println!("The services are in the declared state");
+        } else {
+            for service_name in &plan.remove {
+                println!("{} {service_name}", "- remove".red());
+            }
+            for service_name in &plan.upgrade {
+                println!(
+                    "{} {service_name} to {}",
+                    "~ upgrade".yellow(),
+                    spec.version.as_deref().unwrap_or("latest")
+                );
+            }
+            for ports in &plan.add {
+                println!(
+                    "{} service with node port {}, rpc port {}, metrics port {}",
+                    "+ add".green(),
+                    port_or_auto(ports.node_port),
+                    port_or_auto(ports.rpc_port),
+                    port_or_auto(ports.metrics_port)
+                );
+            }
+        }
+    }
+    if dry_run {
+        return Ok(());
+    }
+
+    if !plan.remove.is_empty() {
+        stop(
+            None,
+            node_registry.clone(),
+            vec![],
+            plan.remove.clone(),
+            verbosity,
+        )
+        .await?;
+        remove(
+            false,
+            vec![],
+            node_registry.clone(),
+            plan.remove.clone(),
+            verbosity,
+        )
+        .await?;
+    }
+
+    if !plan.upgrade.is_empty() {
+        // The declared version is the target, even when it is older than the running one.
+        upgrade(
+            connection_timeout_s,
+            true,
+            None,
+            true,
+            None,
+            node_registry.clone(),
+            vec![],
+            None,
+            plan.upgrade.clone(),
+            None,
+            spec.version.clone(),
+            verbosity,
+        )
+        .await?;
+    }
+
+    let evm_network = EvmNetwork::from(spec.evm_network.clone());
+    for ports in &plan.add {
+        let _ = add(
+            false,
+            false,
+            false,
+            Some(1),
+            None,
+            ports.metrics_port.is_some(),
+            None,
+            Some(evm_network.clone()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            ports.metrics_port.map(PortRange::Single),
+            None,
+            None,
+            ports.node_port.map(PortRange::Single),
+            node_registry.clone(),
+            InitialPeersConfig::default(),
+            false,
+            restart_policy,
+            spec.rewards_address,
+            None,
+            ports.rpc_port.map(PortRange::Single),
+            None,
+            None,
+            false,
+            None,
+            None,
+            spec.version.clone(),
+            verbosity,
+            false,
+        )
+        .await?;
+    }
+
+    let mut inactive_services = Vec::new();
+    for node in node_registry.nodes.read().await.iter() {
+        let node = node.read().await;
+        if node.status != ServiceStatus::Running && node.status != ServiceStatus::Removed {
+            inactive_services.push(node.service_name.clone());
+        }
+    }
+    if !inactive_services.is_empty() {
+        start(
+            connection_timeout_s,
+            None,
+            node_registry,
+            vec![],
+            inactive_services,
+            verbosity,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+fn port_or_auto(port: Option<u16>) -> String {
+    port.map_or_else(|| "auto".to_string(), |port| port.to_string())
+}
+
 async fn get_services_for_ops(
     node_registry: &NodeRegistryManager,
     peer_ids: Vec<String>,
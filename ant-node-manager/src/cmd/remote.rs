@@ -0,0 +1,136 @@
+// Copyright (C) 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Commands run on remote hosts, through their `antctld` daemons.
+
+use crate::{
+    VerbosityLevel,
+    inventory::RemoteHost,
+    print_banner,
+    rpc_client::{get_remote_status, upgrade_remote_node_services},
+};
+use ant_service_management::antctl_proto::{
+    NodeServiceUpgradeRequest, get_status_response::ServiceStatus,
+};
+use color_eyre::{Result, eyre::eyre};
+use colored::Colorize;
+use libp2p_identity::PeerId;
+
+pub async fn status(hosts: Vec<RemoteHost>, fail: bool) -> Result<()> {
+    let mut failed_hosts = Vec::new();
+    let mut not_running_count = 0;
+    for host in &hosts {
+        print_banner(&format!("{} ({})", host.name, host.address));
+        let nodes = match get_remote_status(host).await {
+            Ok(nodes) => nodes,
+            Err(err) => {
+                println!("{} {err}", "✕".red());
+                failed_hosts.push(host.name.clone());
+                continue;
+            }
+        };
+
+        println!(
+            "{:<18} {:<52} {:<10} {:<7}",
+            "Service Name", "Peer ID", "Version", "Status"
+        );
+        for node in nodes {
+            let status = ServiceStatus::from_i32(node.status);
+            if status == Some(ServiceStatus::Removed) {
+                continue;
+            }
+            if status != Some(ServiceStatus::Running) {
+                not_running_count += 1;
+            }
+            let peer_id = node
+                .peer_id
+                .and_then(|bytes| PeerId::from_bytes(&bytes).ok())
+                .map_or("-".to_string(), |peer_id| peer_id.to_string());
+            let status = status.map_or("UNKNOWN".to_string(), |status| {
+                format!("{status:?}").to_uppercase()
+            });
+            println!(
+                "{:<18} {:<52} {:<10} {:<7}",
+                node.service_name, peer_id, node.version, status
+            );
+        }
+        println!();
+    }
+
+    if !failed_hosts.is_empty() {
+        error!("Failed to get the status of hosts: {failed_hosts:?}");
+        return Err(eyre!(
+            "Failed to get the status of {} host(s): {}",
+            failed_hosts.len(),
+            failed_hosts.join(", ")
+        ));
+    }
+    if fail && not_running_count > 0 {
+        return Err(eyre!("{not_running_count} service(s) are not running"));
+    }
+    Ok(())
+}
+
+/// Upgrade the services of the hosts one host at a time, stopping at the first host that fails to
+/// upgrade so that a faulty release does not spread through the fleet.
+pub async fn upgrade(
+    hosts: Vec<RemoteHost>,
+    connection_timeout_s: u64,
+    do_not_start: bool,
+    force: bool,
+    fixed_interval: Option<u64>,
+    service_names: Vec<String>,
+    version: Option<String>,
+    verbosity: VerbosityLevel,
+) -> Result<()> {
+    if verbosity != VerbosityLevel::Minimal {
+        print_banner("Rolling Upgrade of Remote Hosts");
+    }
+    info!(
+        "Rolling upgrade of {} host(s) to version {version:?} for: {service_names:?}",
+        hosts.len()
+    );
+
+    for (i, host) in hosts.iter().enumerate() {
+        if verbosity != VerbosityLevel::Minimal {
+            println!(
+                "Upgrading the services of {} ({}/{})...",
+                host.name,
+                i + 1,
+                hosts.len()
+            );
+        }
+        let request = NodeServiceUpgradeRequest {
+            version: version.clone(),
+            service_names: service_names.clone(),
+            force,
+            do_not_start,
+            connection_timeout_s,
+            interval_millis: fixed_interval,
+        };
+        if let Err(err) = upgrade_remote_node_services(host, request).await {
+            let remaining: Vec<_> = hosts[i + 1..]
+                .iter()
+                .map(|host| host.name.clone())
+                .collect();
+            error!("Rolling upgrade stopped at host {}: {err}", host.name);
+            if verbosity != VerbosityLevel::Minimal {
+                println!("{} {}: {err}", "✕".red(), host.name);
+                if !remaining.is_empty() {
+                    println!("Hosts not upgraded: {}", remaining.join(", "));
+                }
+            }
+            return Err(eyre!("Rolling upgrade stopped at host {}", host.name));
+        }
+        if verbosity != VerbosityLevel::Minimal {
+            println!("{} Upgraded the services of {}", "✓".green(), host.name);
+        }
+    }
+
+    Ok(())
+}
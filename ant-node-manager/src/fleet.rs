@@ -0,0 +1,438 @@
+// Copyright (C) 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Declarative node fleets.
+//!
+//! A fleet file declares the node services a machine should run, e.g.
+//!
+//! ```yaml
+//! count: 20
+//! version: 0.4.17
+//! node_port: 12000-12019
+//! rpc_port: 13000-13019
+//! evm_network: arbitrum-one
+//! rewards_address: "0x03B770D9cD32077cC0bF330c13C114a87643B124"
+//! ```
+//!
+//! A [`FleetPlan`] is the difference between the declared fleet and the services in the node
+//! registry, i.e. the services to remove, upgrade and add to converge to the declared state.
+
+use crate::add_services::config::PortRange;
+use ant_evm::{EvmAddress, EvmNetwork, RewardsAddress};
+use ant_service_management::NodeServiceData;
+use color_eyre::{Result, eyre::eyre};
+use semver::Version;
+use serde::Deserialize;
+use std::{collections::HashSet, path::Path, str::FromStr};
+
+/// The node services of the machine, as declared in a fleet file.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct FleetSpec {
+    /// The number of node services
+    pub count: u16,
+    /// The version of `antnode` run by the services.
+    /// If not set, new services run the latest release and existing ones are not upgraded.
+    #[serde(default)]
+    pub version: Option<String>,
+    /// The port of each service, as a range covering `count` ports, e.g. `12000-12019`
+    #[serde(default)]
+    pub node_port: Option<String>,
+    /// The RPC port of each service, as a range covering `count` ports
+    #[serde(default)]
+    pub rpc_port: Option<String>,
+    /// The metrics server port of each service, as a range covering `count` ports.
+    /// The metrics server is enabled when set.
+    #[serde(default)]
+    pub metrics_port: Option<String>,
+    /// The EVM network the services run on, Arbitrum One if not set
+    #[serde(default)]
+    pub evm_network: FleetEvmNetwork,
+    /// The address receiving the rewards of the services
+    pub rewards_address: RewardsAddress,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum FleetEvmNetwork {
+    #[default]
+    ArbitrumOne,
+    ArbitrumSepoliaTest,
+    Custom {
+        rpc_url: String,
+        payment_token_address: String,
+        data_payments_address: String,
+        #[serde(default)]
+        merkle_payments_address: Option<String>,
+    },
+}
+
+impl FleetEvmNetwork {
+    /// Check the endpoints and addresses of a custom network, which can't be converted into an
+    /// [`EvmNetwork`] otherwise.
+    fn validate(&self) -> Result<()> {
+        let FleetEvmNetwork::Custom {
+            rpc_url,
+            payment_token_address,
+            data_payments_address,
+            merkle_payments_address,
+        } = self
+        else {
+            return Ok(());
+        };
+
+        // A comma separated list of endpoints, the first one being the primary one
+        let mut rpc_urls = rpc_url
+            .split(',')
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .peekable();
+        if rpc_urls.peek().is_none() {
+            return Err(eyre!("The rpc_url of the custom EVM network is empty"));
+        }
+        for url in rpc_urls {
+            let _ = reqwest::Url::parse(url)
+                .map_err(|err| eyre!("Invalid rpc_url '{url}' in the fleet file: {err}"))?;
+        }
+
+        for (name, address) in [
+            ("payment_token_address", Some(payment_token_address)),
+            ("data_payments_address", Some(data_payments_address)),
+            ("merkle_payments_address", merkle_payments_address.as_ref()),
+        ] {
+            if let Some(address) = address {
+                let _ = EvmAddress::from_str(address)
+                    .map_err(|err| eyre!("Invalid {name} '{address}' in the fleet file: {err}"))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<FleetEvmNetwork> for EvmNetwork {
+    fn from(network: FleetEvmNetwork) -> Self {
+        match network {
+            FleetEvmNetwork::ArbitrumOne => EvmNetwork::ArbitrumOne,
+            FleetEvmNetwork::ArbitrumSepoliaTest => EvmNetwork::ArbitrumSepoliaTest,
+            FleetEvmNetwork::Custom {
+                rpc_url,
+                payment_token_address,
+                data_payments_address,
+                merkle_payments_address,
+            } => EvmNetwork::new_custom(
+                &rpc_url,
+                &payment_token_address,
+                &data_payments_address,
+                merkle_payments_address.as_deref(),
+            ),
+        }
+    }
+}
+
+impl FleetSpec {
+    /// Read a fleet file.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|err| eyre!("Could not read the fleet file {}: {err}", path.display()))?;
+        Self::from_yaml(&content)
+    }
+
+    /// Parse and validate the content of a fleet file.
+    pub fn from_yaml(content: &str) -> Result<Self> {
+        let spec: Self = serde_yaml::from_str(content)
+            .map_err(|err| eyre!("Could not parse the fleet file: {err}"))?;
+        spec.evm_network.validate()?;
+        if let Some(version) = &spec.version {
+            let _ = Version::parse(version)
+                .map_err(|err| eyre!("Invalid version '{version}' in the fleet file: {err}"))?;
+        }
+        for range in [&spec.node_port, &spec.rpc_port, &spec.metrics_port]
+            .into_iter()
+            .flatten()
+        {
+            PortRange::parse(range)?.validate(spec.count)?;
+        }
+        Ok(spec)
+    }
+}
+
+/// The ports of a service to add. Unset ports are selected by the node manager.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NewServicePorts {
+    pub node_port: Option<u16>,
+    pub rpc_port: Option<u16>,
+    pub metrics_port: Option<u16>,
+}
+
+/// The changes converging the services of the node registry to a declared fleet.
+#[derive(Debug, Default, PartialEq)]
+pub struct FleetPlan {
+    /// The services in excess, or whose rewards address or EVM network differ from the declared
+    /// ones, which can only be changed by replacing the service
+    pub remove: Vec<String>,
+    /// The services running another version than the declared one
+    pub upgrade: Vec<String>,
+    /// The ports of each service to add
+    pub add: Vec<NewServicePorts>,
+}
+
+impl FleetPlan {
+    /// Compute the plan from the services of the registry which have not been removed.
+    ///
+    /// The services with the lowest numbers are kept, so the excess ones are the last added.
+    pub fn new(spec: &FleetSpec, services: &[&NodeServiceData]) -> Result<Self> {
+        let evm_network = EvmNetwork::from(spec.evm_network.clone());
+        let target_version = spec.version.as_deref().map(Version::parse).transpose()?;
+
+        let mut services = services.to_vec();
+        services.sort_by_key(|service| service.number);
+
+        let mut plan = Self::default();
+        let mut kept = vec![];
+        for service in services {
+            if service.rewards_address != spec.rewards_address
+                || service.evm_network != evm_network
+                || kept.len() >= spec.count as usize
+            {
+                plan.remove.push(service.service_name.clone());
+            } else {
+                kept.push(service);
+            }
+        }
+
+        if let Some(target_version) = &target_version {
+            plan.upgrade = kept
+                .iter()
+                .filter(|service| {
+                    Version::parse(&service.version).ok().as_ref() != Some(target_version)
+                })
+                .map(|service| service.service_name.clone())
+                .collect();
+        }
+
+        let to_add = spec.count as usize - kept.len();
+        let node_ports = free_ports(
+            spec.node_port.as_deref(),
+            kept.iter().filter_map(|service| service.node_port),
+            to_add,
+        )?;
+        let rpc_ports = free_ports(
+            spec.rpc_port.as_deref(),
+            kept.iter().map(|service| service.rpc_socket_addr.port()),
+            to_add,
+        )?;
+        let metrics_ports = free_ports(
+            spec.metrics_port.as_deref(),
+            kept.iter().filter_map(|service| service.metrics_port),
+            to_add,
+        )?;
+        plan.add = (0..to_add)
+            .map(|i| NewServicePorts {
+                node_port: node_ports.get(i).copied(),
+                rpc_port: rpc_ports.get(i).copied(),
+                metrics_port: metrics_ports.get(i).copied(),
+            })
+            .collect();
+
+        Ok(plan)
+    }
+
+    /// Returns true if the services are already in the declared state.
+    pub fn is_empty(&self) -> bool {
+        self.remove.is_empty() && self.upgrade.is_empty() && self.add.is_empty()
+    }
+}
+
+/// The first `count` ports of the range that are not used by the kept services.
+/// Empty if no range was declared.
+fn free_ports(
+    range: Option<&str>,
+    used: impl Iterator<Item = u16>,
+    count: usize,
+) -> Result<Vec<u16>> {
+    let Some(range) = range else {
+        return Ok(vec![]);
+    };
+    let (start, end) = match PortRange::parse(range)? {
+        PortRange::Single(port) => (port, port),
+        PortRange::Range(start, end) => (start, end),
+    };
+    let used: HashSet<u16> = used.collect();
+    let ports: Vec<u16> = (start..=end)
+        .filter(|port| !used.contains(port))
+        .take(count)
+        .collect();
+    if ports.len() < count {
+        return Err(eyre!(
+            "The port range {range} has {} free port(s), {count} are needed",
+            ports.len()
+        ));
+    }
+    Ok(ports)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ant_bootstrap::InitialPeersConfig;
+    use ant_service_management::{ServiceStatus, node::NODE_SERVICE_DATA_SCHEMA_LATEST};
+    use std::{
+        net::{IpAddr, Ipv4Addr, SocketAddr},
+        path::PathBuf,
+    };
+
+    #[test]
+    fn test_fleet_spec_parsing() {
+        let spec = FleetSpec::from_yaml(
+            r#"
+count: 3
+version: 0.4.17
+node_port: 12000-12002
+evm_network:
+  custom:
+    rpc_url: http://localhost:8545
+    payment_token_address: "0x5FbDB2315678afecb367f032d93F642f64180aa3"
+    data_payments_address: "0x8464135c8F25Da09e49BC8782676a84730C318bC"
+rewards_address: "0x03B770D9cD32077cC0bF330c13C114a87643B124"
+"#,
+        )
+        .expect("fleet file shall parse");
+        assert_eq!(spec.count, 3);
+        assert_eq!(spec.version.as_deref(), Some("0.4.17"));
+        assert!(matches!(spec.evm_network, FleetEvmNetwork::Custom { .. }));
+
+        assert!(FleetSpec::from_yaml("count: 3\nrewards_address: \"0x03B770D9cD32077cC0bF330c13C114a87643B124\"\nnode_port: 12000-12001\n").is_err());
+        assert!(FleetSpec::from_yaml("count: 3\nrewards_address: \"0x03B770D9cD32077cC0bF330c13C114a87643B124\"\nunknown: 1\n").is_err());
+    }
+
+    #[test]
+    fn test_fleet_spec_rejects_invalid_custom_network() {
+        let spec = |rpc_url: &str, data_payments_address: &str| {
+            FleetSpec::from_yaml(&format!(
+                r#"
+count: 1
+evm_network:
+  custom:
+    rpc_url: "{rpc_url}"
+    payment_token_address: "0x5FbDB2315678afecb367f032d93F642f64180aa3"
+    data_payments_address: "{data_payments_address}"
+rewards_address: "0x03B770D9cD32077cC0bF330c13C114a87643B124"
+"#
+            ))
+        };
+
+        assert!(
+            spec(
+                "http://localhost:8545",
+                "0x8464135c8F25Da09e49BC8782676a84730C318bC"
+            )
+            .is_ok()
+        );
+        assert!(
+            spec(
+                "http://localhost:8545, http://localhost:8546",
+                "0x8464135c8F25Da09e49BC8782676a84730C318bC"
+            )
+            .is_ok()
+        );
+        assert!(spec("not a url", "0x8464135c8F25Da09e49BC8782676a84730C318bC").is_err());
+        assert!(spec("", "0x8464135c8F25Da09e49BC8782676a84730C318bC").is_err());
+        assert!(spec("http://localhost:8545", "0x8464").is_err());
+    }
+
+    fn service(number: u16, version: &str, node_port: u16) -> NodeServiceData {
+        NodeServiceData {
+            alpha: false,
+            antnode_path: PathBuf::from(format!("/var/antctl/services/antnode{number}/antnode")),
+            auto_restart: false,
+            connected_peers: None,
+            container: None,
+            data_dir_path: PathBuf::from(format!("/var/antctl/services/antnode{number}")),
+            evm_network: EvmNetwork::ArbitrumOne,
+            initial_peers_config: InitialPeersConfig::default(),
+            listen_addr: None,
+            log_dir_path: PathBuf::from(format!("/var/log/antnode/antnode{number}")),
+            log_format: None,
+            max_archived_log_files: None,
+            max_log_files: None,
+            metrics_port: None,
+            network_id: None,
+            no_upnp: false,
+            node_ip: None,
+            node_port: Some(node_port),
+            number,
+            peer_id: None,
+            pid: None,
+            relay: false,
+            rewards_address: RewardsAddress::from_str(REWARDS_ADDRESS)
+                .expect("valid rewards address"),
+            reward_balance: None,
+            rpc_socket_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 13000 + number - 1),
+            schema_version: NODE_SERVICE_DATA_SCHEMA_LATEST,
+            service_name: format!("antnode{number}"),
+            status: ServiceStatus::Running,
+            user: None,
+            user_mode: false,
+            version: version.to_string(),
+            watchdog_secs: None,
+            write_older_cache_files: false,
+        }
+    }
+
+    const REWARDS_ADDRESS: &str = "0x03B770D9cD32077cC0bF330c13C114a87643B124";
+
+    fn fleet_spec(count: u16) -> FleetSpec {
+        FleetSpec::from_yaml(&format!(
+            "count: {count}\nversion: 0.4.17\nnode_port: 12000-12009\nrewards_address: \"{REWARDS_ADDRESS}\"\n"
+        ))
+        .expect("fleet file shall parse")
+    }
+
+    #[test]
+    fn test_fleet_plan_adds_upgrades_and_removes_services() {
+        let first = service(1, "0.4.17", 12000);
+        let second = service(2, "0.4.16", 12001);
+        let mut third = service(3, "0.4.17", 12002);
+        third.rewards_address =
+            RewardsAddress::from_str("0x8464135c8F25Da09e49BC8782676a84730C318bC")
+                .expect("valid rewards address");
+
+        // The service with another rewards address is replaced, the outdated one upgraded
+        let plan = FleetPlan::new(&fleet_spec(4), &[&third, &second, &first])
+            .expect("plan shall be computed");
+        assert_eq!(plan.remove, vec!["antnode3".to_string()]);
+        assert_eq!(plan.upgrade, vec!["antnode2".to_string()]);
+        let added_ports: Vec<_> = plan.add.iter().map(|ports| ports.node_port).collect();
+        assert_eq!(added_ports, vec![Some(12002), Some(12003)]);
+        assert!(plan.add.iter().all(|ports| ports.rpc_port.is_none()));
+
+        // The last added services are removed when there are too many
+        let plan =
+            FleetPlan::new(&fleet_spec(1), &[&second, &first]).expect("plan shall be computed");
+        assert_eq!(plan.remove, vec!["antnode2".to_string()]);
+        assert!(plan.upgrade.is_empty());
+        assert!(plan.add.is_empty());
+
+        // Nothing to do once converged
+        let plan = FleetPlan::new(&fleet_spec(1), &[&first]).expect("plan shall be computed");
+        assert!(plan.is_empty());
+    }
+
+    #[test]
+    fn test_free_ports_skip_used_ones() {
+        let ports = free_ports(Some("12000-12004"), [12000, 12002].into_iter(), 3)
+            .expect("range has enough free ports");
+        assert_eq!(ports, vec![12001, 12003, 12004]);
+        assert!(free_ports(Some("12000-12004"), [12000, 12002].into_iter(), 4).is_err());
+        assert!(
+            free_ports(None, [12000].into_iter(), 2)
+                .expect("no range")
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,223 @@
+// Copyright (C) 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! The inventory of the remote hosts managed through their `antctld` daemons, e.g.
+//!
+//! ```yaml
+//! hosts:
+//!   - name: host-1
+//!     address: 10.0.0.1:12500
+//!     auth_token: a-long-secret
+//!   - name: host-2
+//!     address: 10.0.0.2
+//!     tls_ca_cert: /etc/antctl/ca.pem
+//!     tls_client_cert: /etc/antctl/client.pem
+//!     tls_client_key: /etc/antctl/client.key
+//! ```
+//!
+//! The port defaults to the daemon's default port. Hosts without an auth token use the token in
+//! the `ANTCTL_AUTH_TOKEN` environment variable, if set. Hosts with a CA certificate are reached
+//! over TLS, presenting the client certificate, if any, to daemons requiring mutual TLS.
+
+use crate::{DAEMON_DEFAULT_PORT, auth::client_tls_config, config::get_node_manager_path};
+use color_eyre::{Result, eyre::eyre};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use tonic::transport::ClientTlsConfig;
+
+/// The environment variable holding the auth token of the hosts that do not declare one.
+pub const AUTH_TOKEN_ENV_VAR: &str = "ANTCTL_AUTH_TOKEN";
+
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Inventory {
+    pub hosts: Vec<RemoteHost>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RemoteHost {
+    pub name: String,
+    /// The address of the daemon, as `host` or `host:port`
+    pub address: String,
+    #[serde(default)]
+    pub auth_token: Option<String>,
+    /// The CA certificate the daemon's certificate is verified with, in PEM format
+    #[serde(default)]
+    pub tls_ca_cert: Option<PathBuf>,
+    /// The client certificate, in PEM format, for daemons requiring mutual TLS
+    #[serde(default)]
+    pub tls_client_cert: Option<PathBuf>,
+    #[serde(default)]
+    pub tls_client_key: Option<PathBuf>,
+    /// The name the daemon's certificate is issued for, if it differs from the address
+    #[serde(default)]
+    pub tls_domain_name: Option<String>,
+}
+
+impl RemoteHost {
+    /// A host that is not in the inventory, addressed directly.
+    fn from_address(address: &str) -> Self {
+        Self {
+            name: address.to_string(),
+            address: address.to_string(),
+            auth_token: None,
+            tls_ca_cert: None,
+            tls_client_cert: None,
+            tls_client_key: None,
+            tls_domain_name: None,
+        }
+    }
+
+    /// The endpoint of the daemon's RPC server.
+    pub fn endpoint(&self) -> String {
+        let scheme = if self.tls_ca_cert.is_some() {
+            "https"
+        } else {
+            "http"
+        };
+        if self.address.contains(':') {
+            format!("{scheme}://{}", self.address)
+        } else {
+            format!("{scheme}://{}:{DAEMON_DEFAULT_PORT}", self.address)
+        }
+    }
+
+    /// The TLS configuration to reach the daemon with, if it uses TLS.
+    pub fn tls_config(&self) -> Result<Option<ClientTlsConfig>> {
+        let Some(ca_path) = &self.tls_ca_cert else {
+            return Ok(None);
+        };
+        let client_identity = match (&self.tls_client_cert, &self.tls_client_key) {
+            (Some(cert_path), Some(key_path)) => Some((cert_path.as_path(), key_path.as_path())),
+            (None, None) => None,
+            _ => {
+                return Err(eyre!(
+                    "Host '{}' must have both a client certificate and key, or neither",
+                    self.name
+                ));
+            }
+        };
+        client_tls_config(ca_path, client_identity, self.tls_domain_name.as_deref()).map(Some)
+    }
+
+    /// The auth token of the host, falling back to the one in the environment.
+    pub fn auth_token(&self) -> Option<String> {
+        self.auth_token
+            .clone()
+            .or_else(|| std::env::var(AUTH_TOKEN_ENV_VAR).ok())
+    }
+}
+
+/// The default location of the inventory, next to the node registry.
+pub fn get_inventory_path() -> Result<PathBuf> {
+    Ok(get_node_manager_path()?.join("inventory.yaml"))
+}
+
+impl Inventory {
+    /// Load the inventory, empty if the file does not exist.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            debug!("No inventory found at {path:?}");
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)
+            .map_err(|err| eyre!("Could not read the inventory {}: {err}", path.display()))?;
+        Self::from_yaml(&content)
+    }
+
+    pub fn from_yaml(content: &str) -> Result<Self> {
+        let inventory: Self = serde_yaml::from_str(content)
+            .map_err(|err| eyre!("Could not parse the inventory: {err}"))?;
+        for (i, host) in inventory.hosts.iter().enumerate() {
+            if inventory.hosts[..i]
+                .iter()
+                .any(|other| other.name == host.name)
+            {
+                return Err(eyre!(
+                    "Host '{}' is declared twice in the inventory",
+                    host.name
+                ));
+            }
+        }
+        Ok(inventory)
+    }
+
+    /// Select the hosts to run a command on: all of them, or the given ones. A given host which
+    /// is not named in the inventory is taken as the address of a daemon.
+    pub fn select(&self, hosts: &[String], all_hosts: bool) -> Result<Vec<RemoteHost>> {
+        if all_hosts {
+            if self.hosts.is_empty() {
+                return Err(eyre!("The inventory has no hosts"));
+            }
+            return Ok(self.hosts.clone());
+        }
+        Ok(hosts
+            .iter()
+            .map(|host| {
+                self.hosts
+                    .iter()
+                    .find(|known| &known.name == host)
+                    .cloned()
+                    .unwrap_or_else(|| RemoteHost::from_address(host))
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inventory_host_selection() {
+        let inventory = Inventory::from_yaml(
+            r#"
+hosts:
+  - name: host-1
+    address: 10.0.0.1:13000
+    auth_token: secret
+  - name: host-2
+    address: 10.0.0.2
+"#,
+        )
+        .expect("inventory shall parse");
+
+        let all = inventory.select(&[], true).expect("all hosts");
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].endpoint(), "http://10.0.0.1:13000");
+        assert_eq!(
+            all[1].endpoint(),
+            format!("http://10.0.0.2:{DAEMON_DEFAULT_PORT}")
+        );
+
+        let selected = inventory
+            .select(&["host-2".to_string(), "10.0.0.3:12500".to_string()], false)
+            .expect("given hosts");
+        assert_eq!(selected[0].name, "host-2");
+        assert_eq!(selected[1].endpoint(), "http://10.0.0.3:12500");
+        assert_eq!(selected[1].auth_token, None);
+
+        let tls_host = RemoteHost {
+            tls_ca_cert: Some(PathBuf::from("/etc/antctl/ca.pem")),
+            ..RemoteHost::from_address("10.0.0.4")
+        };
+        assert_eq!(
+            tls_host.endpoint(),
+            format!("https://10.0.0.4:{DAEMON_DEFAULT_PORT}")
+        );
+
+        assert!(Inventory::default().select(&[], true).is_err());
+        assert!(
+            Inventory::from_yaml(
+                "hosts:\n  - name: a\n    address: 10.0.0.1\n  - name: a\n    address: 10.0.0.2\n"
+            )
+            .is_err()
+        );
+    }
+}
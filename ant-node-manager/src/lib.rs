@@ -10,11 +10,16 @@
 extern crate tracing;
 
 pub mod add_services;
+pub mod auth;
+pub mod chaos;
 pub mod cmd;
 pub mod config;
 pub mod error;
+pub mod fleet;
 pub mod helpers;
+pub mod inventory;
 pub mod local;
+pub mod logs;
 pub mod rpc;
 pub mod rpc_client;
 
@@ -43,7 +48,7 @@ use ant_service_management::NodeRegistryManager;
 use ant_service_management::rpc::RpcActions;
 use ant_service_management::{
     NodeService, ServiceStateActions, ServiceStatus, UpgradeOptions, UpgradeResult,
-    control::ServiceControl, error::Error as ServiceError, rpc::RpcClient,
+    control::ServiceControl, error::Error as ServiceError, node::NodeInfoFile, rpc::RpcClient,
 };
 use colored::Colorize;
 use indicatif::ProgressBar;
@@ -415,6 +420,46 @@ pub async fn status_report(
                     failure_reason.0, failure_reason.1
                 );
             }
+            if let Some(crash_report) = node.get_latest_crash_report() {
+                println!(
+                    "Last crash: [{}] {}: {}",
+                    crash_report
+                        .datetime()
+                        .map_or("-".to_string(), |datetime| datetime.to_string()),
+                    crash_report.kind,
+                    crash_report.reason
+                );
+                if let Some(location) = &crash_report.location {
+                    println!("  Location: {location}");
+                }
+                println!(
+                    "  Version: {}, config hash: {}",
+                    crash_report.version, crash_report.config_hash
+                );
+                println!(
+                    "  Report: {}",
+                    crash_report.path(&node.data_dir_path).to_string_lossy()
+                );
+            }
+            if let Some(node_info) = node.get_node_info() {
+                println!(
+                    "Node info: {} (updated {})",
+                    NodeInfoFile::path(&node.data_dir_path).to_string_lossy(),
+                    node_info
+                        .updated_datetime()
+                        .map_or("-".to_string(), |datetime| datetime.to_string())
+                );
+                println!(
+                    "  Metrics port: {}, RPC port: {}, network ID: {}",
+                    node_info
+                        .metrics_port
+                        .map_or("-".to_string(), |port| port.to_string()),
+                    node_info
+                        .rpc_port
+                        .map_or("-".to_string(), |port| port.to_string()),
+                    node_info.network_id
+                );
+            }
             println!("Data path: {}", node.data_dir_path.to_string_lossy());
             println!("Log path: {}", node.log_dir_path.to_string_lossy());
             println!("Bin path: {}", node.antnode_path.to_string_lossy());
@@ -678,6 +723,18 @@ pub async fn refresh_node_registry(
                 }
             }
         } else {
+            // Nodes running in containers are found through their container runtime
+            let container_control = {
+                let node = node.read().await;
+                node.container
+                    .as_ref()
+                    .map(|container| container.service_control(&node.version))
+            };
+            let service_control: &dyn ServiceControl = match &container_control {
+                Some(container_control) => container_control,
+                None => service_control,
+            };
+
             debug!("Using two-tier approach to attempt to find PID for {service_name}");
             if let Some(stored_pid) = service.pid().await {
                 debug!("Tier 1: attempting to verify stored PID {stored_pid} for {service_name}");
@@ -771,7 +828,10 @@ mod tests {
         UpgradeOptions, UpgradeResult,
         error::{Error as ServiceControlError, Result as ServiceControlResult},
         node::{NODE_SERVICE_DATA_SCHEMA_LATEST, NodeService, NodeServiceData},
-        rpc::{NetworkInfo, NodeInfo, RecordAddress, RpcActions},
+        rpc::{
+            BehaviourHealth, NetworkInfo, NodeInfo, Reachability, RecordAddress,
+            RecordStoreStats, RecordsVerification, RpcActions,
+        },
     };
     use assert_fs::prelude::*;
     use assert_matches::assert_matches;
@@ -798,11 +858,19 @@ mod tests {
             async fn node_info(&self) -> ServiceControlResult<NodeInfo>;
             async fn network_info(&self) -> ServiceControlResult<NetworkInfo>;
             async fn record_addresses(&self) -> ServiceControlResult<Vec<RecordAddress>>;
+            async fn behaviour_health(&self) -> ServiceControlResult<BehaviourHealth>;
+            async fn reachability(&self) -> ServiceControlResult<Reachability>;
+            async fn record_store_stats(&self, sample_size: u32) -> ServiceControlResult<RecordStoreStats>;
             async fn node_restart(&self, delay_millis: u64, retain_peer_id: bool) -> ServiceControlResult<()>;
             async fn node_stop(&self, delay_millis: u64) -> ServiceControlResult<()>;
             async fn node_update(&self, delay_millis: u64) -> ServiceControlResult<()>;
             async fn is_node_connected_to_network(&self, timeout: std::time::Duration) -> ServiceControlResult<()>;
             async fn update_log_level(&self, log_levels: String) -> ServiceControlResult<()>;
+            async fn set_log_level(&self, target: String, level: String) -> ServiceControlResult<()>;
+            async fn node_hibernate(&self) -> ServiceControlResult<()>;
+            async fn node_resume(&self) -> ServiceControlResult<()>;
+            async fn replicate_record(&self, address: RecordAddress) -> ServiceControlResult<Option<Vec<PeerId>>>;
+            async fn verify_records(&self, sample_size: u32, cross_check_count: u32) -> ServiceControlResult<RecordsVerification>;
         }
     }
 
@@ -887,6 +955,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::Custom(CustomNetwork {
                 rpc_url_http: "http://localhost:8545".parse()?,
@@ -897,6 +966,7 @@ mod tests {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             relay: false,
             initial_peers_config: InitialPeersConfig::default(),
@@ -925,6 +995,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             version: "0.98.1".to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -1008,6 +1079,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::Custom(CustomNetwork {
                 rpc_url_http: "http://localhost:8545".parse()?,
@@ -1018,6 +1090,7 @@ mod tests {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             relay: false,
             initial_peers_config: InitialPeersConfig::default(),
@@ -1048,6 +1121,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             version: "0.98.1".to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -1089,6 +1163,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::Custom(CustomNetwork {
                 rpc_url_http: "http://localhost:8545".parse()?,
@@ -1099,6 +1174,7 @@ mod tests {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             relay: false,
             initial_peers_config: InitialPeersConfig::default(),
@@ -1129,6 +1205,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             version: "0.98.1".to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -1215,6 +1292,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::Custom(CustomNetwork {
                 rpc_url_http: "http://localhost:8545".parse()?,
@@ -1225,6 +1303,7 @@ mod tests {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             relay: false,
             initial_peers_config: InitialPeersConfig::default(),
@@ -1255,6 +1334,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             version: "0.98.1".to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -1309,6 +1389,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::Custom(CustomNetwork {
                 rpc_url_http: "http://localhost:8545".parse()?,
@@ -1319,6 +1400,7 @@ mod tests {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             relay: false,
             initial_peers_config: InitialPeersConfig::default(),
@@ -1347,6 +1429,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             version: "0.98.1".to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -1420,6 +1503,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::Custom(CustomNetwork {
                 rpc_url_http: "http://localhost:8545".parse()?,
@@ -1430,6 +1514,7 @@ mod tests {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             relay: false,
             initial_peers_config: InitialPeersConfig::default(),
@@ -1458,6 +1543,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: true,
             version: "0.98.1".to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -1530,6 +1616,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::Custom(CustomNetwork {
                 rpc_url_http: "http://localhost:8545".parse()?,
@@ -1540,6 +1627,7 @@ mod tests {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             relay: false,
             initial_peers_config: InitialPeersConfig::default(),
@@ -1568,6 +1656,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             version: "0.98.1".to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -1605,6 +1694,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::Custom(CustomNetwork {
                 rpc_url_http: "http://localhost:8545".parse()?,
@@ -1615,6 +1705,7 @@ mod tests {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             relay: false,
             initial_peers_config: InitialPeersConfig::default(),
@@ -1645,6 +1736,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             version: "0.98.1".to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -1670,6 +1762,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::Custom(CustomNetwork {
                 rpc_url_http: "http://localhost:8545".parse()?,
@@ -1680,6 +1773,7 @@ mod tests {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             relay: false,
             initial_peers_config: InitialPeersConfig::default(),
@@ -1708,6 +1802,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             version: "0.98.1".to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -1735,6 +1830,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::Custom(CustomNetwork {
                 rpc_url_http: "http://localhost:8545".parse()?,
@@ -1745,6 +1841,7 @@ mod tests {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             relay: false,
             initial_peers_config: InitialPeersConfig::default(),
@@ -1775,6 +1872,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             version: "0.98.1".to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -1803,6 +1901,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::Custom(CustomNetwork {
                 rpc_url_http: "http://localhost:8545".parse()?,
@@ -1813,6 +1912,7 @@ mod tests {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             relay: false,
             initial_peers_config: InitialPeersConfig::default(),
@@ -1841,6 +1941,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             version: "0.98.1".to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -1882,6 +1983,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::Custom(CustomNetwork {
                 rpc_url_http: "http://localhost:8545".parse()?,
@@ -1892,6 +1994,7 @@ mod tests {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             relay: false,
             listen_addr: None,
@@ -1922,6 +2025,7 @@ mod tests {
             user: None,
             user_mode: true,
             version: "0.98.1".to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -2029,6 +2133,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::Custom(CustomNetwork {
                 rpc_url_http: "http://localhost:8545".parse()?,
@@ -2039,6 +2144,7 @@ mod tests {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             relay: false,
             initial_peers_config: InitialPeersConfig::default(),
@@ -2069,6 +2175,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             version: current_version.to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -2132,6 +2239,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::Custom(CustomNetwork {
                 rpc_url_http: "http://localhost:8545".parse()?,
@@ -2142,6 +2250,7 @@ mod tests {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             relay: false,
             initial_peers_config: InitialPeersConfig::default(),
@@ -2172,6 +2281,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             version: current_version.to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -2287,6 +2397,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::Custom(CustomNetwork {
                 rpc_url_http: "http://localhost:8545".parse()?,
@@ -2297,6 +2408,7 @@ mod tests {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             relay: false,
             initial_peers_config: InitialPeersConfig::default(),
@@ -2327,6 +2439,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             version: current_version.to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -2447,6 +2560,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::Custom(CustomNetwork {
                 rpc_url_http: "http://localhost:8545".parse()?,
@@ -2457,6 +2571,7 @@ mod tests {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             relay: false,
             initial_peers_config: InitialPeersConfig::default(),
@@ -2487,6 +2602,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             version: current_version.to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -2599,6 +2715,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::Custom(CustomNetwork {
                 rpc_url_http: "http://localhost:8545".parse()?,
@@ -2609,6 +2726,7 @@ mod tests {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             relay: false,
             initial_peers_config: InitialPeersConfig::default(),
@@ -2639,6 +2757,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             version: current_version.to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -2761,6 +2880,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::Custom(CustomNetwork {
                 rpc_url_http: "http://localhost:8545".parse()?,
@@ -2771,6 +2891,7 @@ mod tests {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             relay: false,
             initial_peers_config: InitialPeersConfig::default(),
@@ -2801,6 +2922,7 @@ mod tests {
             user: None,
             user_mode: true,
             version: current_version.to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -2892,6 +3014,7 @@ mod tests {
                         OsString::from("/var/log/antnode/antnode1"),
                         OsString::from("--first"),
                         OsString::from("--stop-on-upgrade"),
+                        OsString::from("--write-node-info"),
                         OsString::from("--rewards-address"),
                         OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                         OsString::from("evm-arbitrum-one"),
@@ -2957,6 +3080,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::ArbitrumOne,
             relay: false,
@@ -2995,6 +3119,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             version: current_version.to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -3074,6 +3199,7 @@ mod tests {
                             "/ip4/127.0.0.1/tcp/8080/p2p/12D3KooWRBhwfeP2Y4TCx1SM6s9rUoHhR5STiGwxBhgFRcw3UERE"
                         ),
                         OsString::from("--stop-on-upgrade"),
+                        OsString::from("--write-node-info"),
                         OsString::from("--rewards-address"),
                         OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                         OsString::from("evm-arbitrum-one"),
@@ -3139,6 +3265,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::ArbitrumOne,
             relay: false,
@@ -3179,6 +3306,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             version: current_version.to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -3256,6 +3384,7 @@ mod tests {
                         OsString::from("--network-id"),
                         OsString::from("5"),
                         OsString::from("--stop-on-upgrade"),
+                        OsString::from("--write-node-info"),
                         OsString::from("--rewards-address"),
                         OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                         OsString::from("evm-arbitrum-one"),
@@ -3321,6 +3450,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::ArbitrumOne,
             relay: false,
@@ -3352,6 +3482,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             version: current_version.to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -3428,6 +3559,7 @@ mod tests {
                         OsString::from("/var/log/antnode/antnode1"),
                         OsString::from("--local"),
                         OsString::from("--stop-on-upgrade"),
+                        OsString::from("--write-node-info"),
                         OsString::from("--rewards-address"),
                         OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                         OsString::from("evm-arbitrum-one"),
@@ -3493,6 +3625,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::ArbitrumOne,
             relay: false,
@@ -3531,6 +3664,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             version: current_version.to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -3608,6 +3742,7 @@ mod tests {
                         OsString::from("--network-contacts-url"),
                         OsString::from("http://localhost:8080/contacts.json,http://localhost:8081/contacts.json"),
                         OsString::from("--stop-on-upgrade"),
+                        OsString::from("--write-node-info"),
                         OsString::from("--rewards-address"),
                         OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                         OsString::from("evm-arbitrum-one"),
@@ -3673,6 +3808,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::ArbitrumOne,
             relay: false,
@@ -3714,6 +3850,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             version: current_version.to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -3793,6 +3930,7 @@ mod tests {
                         OsString::from("/var/log/antnode/antnode1"),
                         OsString::from("--ignore-cache"),
                         OsString::from("--stop-on-upgrade"),
+                        OsString::from("--write-node-info"),
                         OsString::from("--rewards-address"),
                         OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                         OsString::from("evm-arbitrum-one"),
@@ -3858,6 +3996,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::ArbitrumOne,
             relay: false,
@@ -3896,6 +4035,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             version: current_version.to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -3973,6 +4113,7 @@ mod tests {
                         OsString::from("--bootstrap-cache-dir"),
                         OsString::from("/var/antctl/services/antnode1/bootstrap_cache"),
                         OsString::from("--stop-on-upgrade"),
+                        OsString::from("--write-node-info"),
                         OsString::from("--rewards-address"),
                         OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                         OsString::from("evm-arbitrum-one"),
@@ -4038,6 +4179,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::ArbitrumOne,
             relay: false,
@@ -4078,6 +4220,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             version: current_version.to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -4159,6 +4302,7 @@ mod tests {
                         OsString::from("/var/log/antnode/antnode1"),
                         OsString::from("--no-upnp"),
                         OsString::from("--stop-on-upgrade"),
+                        OsString::from("--write-node-info"),
                         OsString::from("--rewards-address"),
                         OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                         OsString::from("evm-arbitrum-one"),
@@ -4224,6 +4368,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::ArbitrumOne,
             relay: false,
@@ -4255,6 +4400,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             version: current_version.to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -4332,6 +4478,7 @@ mod tests {
                         OsString::from("--log-format"),
                         OsString::from("json"),
                         OsString::from("--stop-on-upgrade"),
+                        OsString::from("--write-node-info"),
                         OsString::from("--rewards-address"),
                         OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                         OsString::from("evm-arbitrum-one"),
@@ -4397,6 +4544,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::ArbitrumOne,
             relay: false,
@@ -4428,6 +4576,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             version: current_version.to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -4505,6 +4654,7 @@ mod tests {
                         OsString::from("/var/log/antnode/antnode1"),
                         OsString::from("--relay"),
                         OsString::from("--stop-on-upgrade"),
+                        OsString::from("--write-node-info"),
                         OsString::from("--rewards-address"),
                         OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                         OsString::from("evm-arbitrum-one"),
@@ -4570,6 +4720,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::ArbitrumOne,
             relay: true,
@@ -4601,6 +4752,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             version: current_version.to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -4678,6 +4830,7 @@ mod tests {
                         OsString::from("--ip"),
                         OsString::from("192.168.1.1"),
                         OsString::from("--stop-on-upgrade"),
+                        OsString::from("--write-node-info"),
                         OsString::from("--rewards-address"),
                         OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                         OsString::from("evm-arbitrum-one"),
@@ -4743,6 +4896,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::ArbitrumOne,
             relay: false,
@@ -4774,6 +4928,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             version: current_version.to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -4851,6 +5006,7 @@ mod tests {
                         OsString::from("--port"),
                         OsString::from("12000"),
                         OsString::from("--stop-on-upgrade"),
+                        OsString::from("--write-node-info"),
                         OsString::from("--rewards-address"),
                         OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                         OsString::from("evm-arbitrum-one"),
@@ -4916,6 +5072,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::ArbitrumOne,
             relay: false,
@@ -4947,6 +5104,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             version: current_version.to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -5024,6 +5182,7 @@ mod tests {
                         OsString::from("--max-archived-log-files"),
                         OsString::from("20"),
                         OsString::from("--stop-on-upgrade"),
+                        OsString::from("--write-node-info"),
                         OsString::from("--rewards-address"),
                         OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                         OsString::from("evm-arbitrum-one"),
@@ -5089,6 +5248,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             relay: false,
             initial_peers_config: Default::default(),
@@ -5120,6 +5280,7 @@ mod tests {
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -5197,6 +5358,7 @@ mod tests {
                         OsString::from("--max-log-files"),
                         OsString::from("20"),
                         OsString::from("--stop-on-upgrade"),
+                        OsString::from("--write-node-info"),
                         OsString::from("--rewards-address"),
                         OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                         OsString::from("evm-arbitrum-one"),
@@ -5262,6 +5424,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             relay: false,
             initial_peers_config: Default::default(),
@@ -5293,6 +5456,7 @@ mod tests {
             rewards_address: RewardsAddress::from_str(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -5370,6 +5534,7 @@ mod tests {
                         OsString::from("--metrics-server-port"),
                         OsString::from("12000"),
                         OsString::from("--stop-on-upgrade"),
+                        OsString::from("--write-node-info"),
                         OsString::from("--rewards-address"),
                         OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                         OsString::from("evm-arbitrum-one"),
@@ -5435,6 +5600,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::ArbitrumOne,
             relay: false,
@@ -5466,6 +5632,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             version: current_version.to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -5543,6 +5710,7 @@ mod tests {
                         OsString::from("--metrics-server-port"),
                         OsString::from("12000"),
                         OsString::from("--stop-on-upgrade"),
+                        OsString::from("--write-node-info"),
                         OsString::from("--rewards-address"),
                         OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                         OsString::from("evm-arbitrum-one"),
@@ -5608,6 +5776,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::ArbitrumOne,
             relay: false,
@@ -5639,6 +5808,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             version: current_version.to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -5717,6 +5887,7 @@ mod tests {
                         OsString::from("--log-output-dest"),
                         OsString::from("/var/log/antnode/antnode1"),
                         OsString::from("--stop-on-upgrade"),
+                        OsString::from("--write-node-info"),
                         OsString::from("--rewards-address"),
                         OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                         OsString::from("evm-arbitrum-one"),
@@ -5782,6 +5953,7 @@ mod tests {
             alpha: false,
             auto_restart: true,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::ArbitrumOne,
             relay: false,
@@ -5813,6 +5985,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             version: current_version.to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -5888,6 +6061,7 @@ mod tests {
                         OsString::from("--log-output-dest"),
                         OsString::from("/var/log/antnode/antnode1"),
                         OsString::from("--stop-on-upgrade"),
+                        OsString::from("--write-node-info"),
                         OsString::from("--rewards-address"),
                         OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                         OsString::from("evm-custom"),
@@ -5961,6 +6135,7 @@ mod tests {
             alpha: false,
             auto_restart: true,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::Custom(CustomNetwork {
                 rpc_url_http: "http://localhost:8545".parse()?,
@@ -5973,6 +6148,7 @@ mod tests {
                 merkle_payments_address: Some(RewardsAddress::from_str(
                     "0x1234567890abcdef1234567890abcdef12345678",
                 )?),
+                fallback_rpc_urls: Default::default(),
             }),
             relay: false,
             initial_peers_config: Default::default(),
@@ -6004,6 +6180,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             version: current_version.to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -6079,6 +6256,7 @@ mod tests {
                         OsString::from("--log-output-dest"),
                         OsString::from("/var/log/antnode/antnode1"),
                         OsString::from("--stop-on-upgrade"),
+                        OsString::from("--write-node-info"),
                         OsString::from("--rewards-address"),
                         OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                         OsString::from("evm-custom"),
@@ -6150,6 +6328,7 @@ mod tests {
             alpha: false,
             auto_restart: true,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::Custom(CustomNetwork {
                 rpc_url_http: "http://localhost:8545".parse()?,
@@ -6160,6 +6339,7 @@ mod tests {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             relay: false,
             initial_peers_config: Default::default(),
@@ -6191,6 +6371,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             version: current_version.to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -6266,6 +6447,7 @@ mod tests {
                         OsString::from("--log-output-dest"),
                         OsString::from("/var/log/antnode/antnode1"),
                         OsString::from("--stop-on-upgrade"),
+                        OsString::from("--write-node-info"),
                         OsString::from("--rewards-address"),
                         OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                         OsString::from("evm-arbitrum-one"),
@@ -6334,6 +6516,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::ArbitrumOne,
             relay: false,
@@ -6365,6 +6548,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             version: current_version.to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -6442,6 +6626,7 @@ mod tests {
                         OsString::from("--metrics-server-port"),
                         OsString::from("12000"),
                         OsString::from("--stop-on-upgrade"),
+                        OsString::from("--write-node-info"),
                         OsString::from("--rewards-address"),
                         OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                         OsString::from("--write-older-cache-files"),
@@ -6508,6 +6693,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::ArbitrumOne,
             relay: false,
@@ -6539,6 +6725,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             version: current_version.to_string(),
+            watchdog_secs: None,
             write_older_cache_files: true,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -6588,6 +6775,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: data_dir.to_path_buf(),
             evm_network: EvmNetwork::Custom(CustomNetwork {
                 rpc_url_http: "http://localhost:8545".parse()?,
@@ -6598,6 +6786,7 @@ mod tests {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             relay: false,
             initial_peers_config: Default::default(),
@@ -6626,6 +6815,7 @@ mod tests {
             no_upnp: false,
             user: Some("ant".to_string()),
             user_mode: false,
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -6659,6 +6849,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::Custom(CustomNetwork {
                 rpc_url_http: "http://localhost:8545".parse()?,
@@ -6669,6 +6860,7 @@ mod tests {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             relay: false,
             initial_peers_config: Default::default(),
@@ -6699,6 +6891,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             version: "0.98.1".to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -6747,6 +6940,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::Custom(CustomNetwork {
                 rpc_url_http: "http://localhost:8545".parse()?,
@@ -6757,6 +6951,7 @@ mod tests {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             relay: false,
             initial_peers_config: Default::default(),
@@ -6787,6 +6982,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             version: "0.98.1".to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -6830,6 +7026,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: data_dir.to_path_buf(),
             evm_network: EvmNetwork::Custom(CustomNetwork {
                 rpc_url_http: "http://localhost:8545".parse()?,
@@ -6840,6 +7037,7 @@ mod tests {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             relay: false,
             initial_peers_config: Default::default(),
@@ -6868,6 +7066,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             version: "0.98.1".to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -6909,6 +7108,7 @@ mod tests {
             alpha: false,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: data_dir.to_path_buf(),
             evm_network: EvmNetwork::Custom(CustomNetwork {
                 rpc_url_http: "http://localhost:8545".parse()?,
@@ -6919,6 +7119,7 @@ mod tests {
                     "0x8464135c8F25Da09e49BC8782676a84730C318bC",
                 )?,
                 merkle_payments_address: None,
+                fallback_rpc_urls: Default::default(),
             }),
             relay: false,
             initial_peers_config: Default::default(),
@@ -6947,6 +7148,7 @@ mod tests {
             user: None,
             user_mode: true,
             version: "0.98.1".to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -7015,6 +7217,7 @@ mod tests {
                         OsString::from("/var/log/antnode/antnode1"),
                         OsString::from("--alpha"),
                         OsString::from("--stop-on-upgrade"),
+                        OsString::from("--write-node-info"),
                         OsString::from("--rewards-address"),
                         OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
                         OsString::from("evm-arbitrum-one"),
@@ -7080,6 +7283,7 @@ mod tests {
             alpha: true,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::ArbitrumOne,
             relay: false,
@@ -7118,6 +7322,7 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             version: current_version.to_string(),
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
         let service_data = Arc::new(RwLock::new(service_data));
@@ -424,6 +424,7 @@ pub async fn run_node(
         antnode_path: launcher.get_antnode_path(),
         auto_restart: false,
         connected_peers,
+        container: None,
         data_dir_path: node_info.data_path,
         evm_network: run_options.evm_network,
         relay: false,
@@ -457,6 +458,7 @@ pub async fn run_node(
         user: None,
         user_mode: false,
         version: run_options.version.to_string(),
+        watchdog_secs: None,
         write_older_cache_files: false,
     })
 }
@@ -529,7 +531,10 @@ mod tests {
     use ant_evm::utils::dummy_address;
     use ant_service_management::{
         error::Result as RpcResult,
-        rpc::{NetworkInfo, NodeInfo, RecordAddress, RpcActions},
+        rpc::{
+            BehaviourHealth, NetworkInfo, NodeInfo, Reachability, RecordAddress, RecordStoreStats,
+            RecordsVerification, RpcActions,
+        },
     };
     use async_trait::async_trait;
     use evmlib::CustomNetwork;
@@ -545,11 +550,19 @@ mod tests {
             async fn node_info(&self) -> RpcResult<NodeInfo>;
             async fn network_info(&self) -> RpcResult<NetworkInfo>;
             async fn record_addresses(&self) -> RpcResult<Vec<RecordAddress>>;
+            async fn behaviour_health(&self) -> RpcResult<BehaviourHealth>;
+            async fn reachability(&self) -> RpcResult<Reachability>;
+            async fn record_store_stats(&self, sample_size: u32) -> RpcResult<RecordStoreStats>;
             async fn node_restart(&self, delay_millis: u64, retain_peer_id: bool) -> RpcResult<()>;
             async fn node_stop(&self, delay_millis: u64) -> RpcResult<()>;
             async fn node_update(&self, delay_millis: u64) -> RpcResult<()>;
             async fn is_node_connected_to_network(&self, timeout: std::time::Duration) -> RpcResult<()>;
             async fn update_log_level(&self, log_levels: String) -> RpcResult<()>;
+            async fn set_log_level(&self, target: String, level: String) -> RpcResult<()>;
+            async fn node_hibernate(&self) -> RpcResult<()>;
+            async fn node_resume(&self) -> RpcResult<()>;
+            async fn replicate_record(&self, address: RecordAddress) -> RpcResult<Option<Vec<PeerId>>>;
+            async fn verify_records(&self, sample_size: u32, cross_check_count: u32) -> RpcResult<RecordsVerification>;
         }
     }
 
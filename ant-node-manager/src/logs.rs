@@ -0,0 +1,266 @@
+// Copyright (C) 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Reading the logs of node services.
+//!
+//! A node writes to `antnode.log` in its log directory. When the file reaches its maximum size it
+//! is renamed with a timestamp suffix, e.g. `antnode.log.20250101T120000`, and older files are
+//! eventually compressed. Only the uncompressed files are read here.
+
+use chrono::{DateTime, Utc};
+use color_eyre::{Result, eyre::eyre};
+use regex::Regex;
+use std::{
+    io::SeekFrom,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tokio::{
+    fs::File,
+    io::{AsyncBufReadExt, AsyncSeekExt, BufReader},
+};
+
+/// The name of the log file a node is currently writing to.
+const CURRENT_LOG_FILE_NAME: &str = "antnode.log";
+/// Interval between two checks for new lines when following a log file.
+const FOLLOW_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Parse a duration such as `90s`, `15m`, `1h` or `2d`.
+pub fn parse_duration(value: &str) -> Result<Duration> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (amount, unit) = value.split_at(split);
+    let amount: u64 = amount
+        .parse()
+        .map_err(|_| eyre!("The duration '{value}' must start with a number, e.g. 1h"))?;
+    let unit_secs = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => {
+            return Err(eyre!(
+                "The duration '{value}' must end with one of the units s, m, h or d"
+            ));
+        }
+    };
+    Ok(Duration::from_secs(amount.saturating_mul(unit_secs)))
+}
+
+/// Selects the lines of a log to print.
+#[derive(Debug)]
+pub struct LogFilter {
+    pattern: Option<Regex>,
+    since: Option<DateTime<Utc>>,
+    /// Whether the last timestamped line was logged after `since`. Lines without a timestamp,
+    /// e.g. the continuation of a multi-line message, follow the line before them.
+    in_window: bool,
+}
+
+impl LogFilter {
+    /// Lines must match `pattern`, if set, and be logged within `since` of now, if set.
+    pub fn new(pattern: Option<&str>, since: Option<Duration>) -> Result<Self> {
+        let pattern = pattern
+            .map(Regex::new)
+            .transpose()
+            .map_err(|err| eyre!("Invalid pattern: {err}"))?;
+        let since = since
+            .map(|since| {
+                chrono::Duration::from_std(since)
+                    .map(|since| Utc::now() - since)
+                    .map_err(|_| eyre!("The duration {since:?} is too long"))
+            })
+            .transpose()?;
+        Ok(Self {
+            pattern,
+            in_window: since.is_none(),
+            since,
+        })
+    }
+
+    /// Whether the line should be printed.
+    pub fn matches(&mut self, line: &str) -> bool {
+        if let Some(since) = self.since
+            && let Some(timestamp) = line_timestamp(line)
+        {
+            self.in_window = timestamp >= since;
+        }
+        self.in_window
+            && self
+                .pattern
+                .as_ref()
+                .is_none_or(|pattern| pattern.is_match(line))
+    }
+}
+
+/// The time a line was logged, in either the default or the JSON log format.
+fn line_timestamp(line: &str) -> Option<DateTime<Utc>> {
+    let timestamp = if let Some(rest) = line.strip_prefix('[') {
+        rest.split_whitespace().next()?.to_string()
+    } else if line.starts_with('{') {
+        let value: serde_json::Value = serde_json::from_str(line).ok()?;
+        value.get("timestamp")?.as_str()?.to_string()
+    } else {
+        return None;
+    };
+    DateTime::parse_from_rfc3339(&timestamp)
+        .ok()
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+}
+
+/// The uncompressed log files in `log_dir`, oldest first.
+///
+/// The timestamp suffixes of rotated files sort in chronological order, and the current file comes
+/// last.
+pub fn log_files(log_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut rotated = Vec::new();
+    for entry in std::fs::read_dir(log_dir)? {
+        let path = entry?.path();
+        let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if file_name.starts_with(&format!("{CURRENT_LOG_FILE_NAME}."))
+            && !file_name.ends_with(".gz")
+        {
+            rotated.push(path);
+        }
+    }
+    rotated.sort();
+
+    let current = log_dir.join(CURRENT_LOG_FILE_NAME);
+    if current.is_file() {
+        rotated.push(current);
+    }
+    Ok(rotated)
+}
+
+/// Print the lines of the logs in `log_dir` selected by `filter`.
+///
+/// Rotated files are only read when `filter` has a start time, otherwise only the current file is.
+/// With `follow`, keep printing the lines appended to the current file until interrupted.
+pub async fn print_logs(log_dir: &Path, mut filter: LogFilter, follow: bool) -> Result<()> {
+    if !log_dir.exists() {
+        return Err(eyre!("The log directory {log_dir:?} does not exist"));
+    }
+
+    let mut files = log_files(log_dir)?;
+    if filter.since.is_none() {
+        files.retain(|file| file.ends_with(CURRENT_LOG_FILE_NAME));
+    }
+    let mut position = 0;
+    for file in &files {
+        debug!("Reading log file {file:?}");
+        position = print_lines(file, 0, &mut filter).await?;
+    }
+    if !follow {
+        return Ok(());
+    }
+
+    let current = log_dir.join(CURRENT_LOG_FILE_NAME);
+    if !files.last().is_some_and(|file| *file == current) {
+        position = 0;
+    }
+    loop {
+        tokio::time::sleep(FOLLOW_POLL_INTERVAL).await;
+        let len = match tokio::fs::metadata(&current).await {
+            Ok(metadata) => metadata.len(),
+            // The file does not exist between its rotation and the next line logged
+            Err(_) => continue,
+        };
+        if len < position {
+            debug!("The log file {current:?} was rotated");
+            position = 0;
+        }
+        if len > position {
+            position = print_lines(&current, position, &mut filter).await?;
+        }
+    }
+}
+
+/// Print the complete lines of `file` after `position`, returning the position of the first line
+/// not read.
+async fn print_lines(file: &Path, position: u64, filter: &mut LogFilter) -> Result<u64> {
+    let mut file = File::open(file).await?;
+    let _ = file.seek(SeekFrom::Start(position)).await?;
+    let mut reader = BufReader::new(file);
+    let mut position = position;
+    let mut line = String::new();
+    loop {
+        line.clear();
+        let read = reader.read_line(&mut line).await?;
+        // A line without its newline is still being written, it is read on the next poll
+        if read == 0 || !line.ends_with('\n') {
+            return Ok(position);
+        }
+        position += read as u64;
+        let line = line.trim_end();
+        if filter.matches(line) {
+            println!("{line}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_duration_should_accept_units() {
+        assert_eq!(parse_duration("90s").unwrap(), Duration::from_secs(90));
+        assert_eq!(parse_duration("15m").unwrap(), Duration::from_secs(900));
+        assert_eq!(parse_duration("1h").unwrap(), Duration::from_secs(3600));
+        assert_eq!(parse_duration("2d").unwrap(), Duration::from_secs(172800));
+        assert!(parse_duration("1").is_err());
+        assert!(parse_duration("h").is_err());
+        assert!(parse_duration("1w").is_err());
+    }
+
+    #[test]
+    fn filter_should_select_lines_since_a_time_and_matching_a_pattern() {
+        let now = Utc::now();
+        let old = (now - chrono::Duration::hours(2)).to_rfc3339();
+        let recent = (now - chrono::Duration::minutes(5)).to_rfc3339();
+        let mut filter = LogFilter::new(Some("Peer"), Some(Duration::from_secs(3600))).unwrap();
+
+        assert!(!filter.matches(&format!("[{old} INFO ant_node 10] Peer added")));
+        assert!(!filter.matches("Peer continuation of an old line"));
+        assert!(filter.matches(&format!("[{recent} INFO ant_node 10] Peer added")));
+        assert!(filter.matches("Peer continuation of a recent line"));
+        assert!(!filter.matches(&format!("[{recent} INFO ant_node 10] Record stored")));
+        assert!(filter.matches(&format!(
+            "{{\"timestamp\":\"{recent}\",\"level\":\"INFO\",\"message\":\"Peer added\"}}"
+        )));
+    }
+
+    #[test]
+    fn log_files_should_list_rotated_files_before_the_current_one() {
+        let tmp_dir = assert_fs::TempDir::new().unwrap();
+        let dir = tmp_dir.path();
+        for name in [
+            "antnode.log",
+            "antnode.log.20250102T000000",
+            "antnode.log.20250101T000000",
+            "antnode.log.20241231T000000.gz",
+            "other.txt",
+        ] {
+            std::fs::write(dir.join(name), "").unwrap();
+        }
+
+        let files = log_files(dir).unwrap();
+        assert_eq!(
+            files,
+            vec![
+                dir.join("antnode.log.20250101T000000"),
+                dir.join("antnode.log.20250102T000000"),
+                dir.join("antnode.log"),
+            ]
+        );
+    }
+}
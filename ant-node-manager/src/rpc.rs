@@ -12,9 +12,7 @@ use crate::{
 };
 use ant_service_management::{
     NodeRegistryManager, NodeService, NodeServiceData, ServiceStatus,
-    control::{ServiceControl, ServiceController},
-    node::NODE_SERVICE_DATA_SCHEMA_LATEST,
-    rpc::RpcClient,
+    node::NODE_SERVICE_DATA_SCHEMA_LATEST, rpc::RpcClient,
 };
 use color_eyre::{
     Result,
@@ -46,15 +44,16 @@ pub async fn restart_node_service(
 
     let rpc_client = RpcClient::from_socket_addr(current_node.read().await.rpc_socket_addr);
     let service = NodeService::new(Arc::clone(&current_node), Box::new(rpc_client));
+    // A containerized node is managed through its container runtime
+    let service_control = current_node.read().await.service_control();
     let mut service_manager = ServiceManager::new(
         service,
-        Box::new(ServiceController {}),
+        current_node.read().await.service_control(),
         VerbosityLevel::Normal,
     );
     service_manager.stop().await?;
     let service_name = current_node.read().await.service_name.clone();
 
-    let service_control = ServiceController {};
     if retain_peer_id {
         debug!("Retaining the peer id: {peer_id:?} for the node: {service_name:?}");
         // reuse the same port and root dir to retain peer id.
@@ -90,6 +89,7 @@ pub async fn restart_node_service(
             service_user: current_node_clone.user.clone(),
             // This setting doesn't really matter because RPC will soon be taken out of use.
             stop_on_upgrade: true,
+            watchdog_secs: current_node_clone.watchdog_secs,
             write_older_cache_files: current_node_clone.write_older_cache_files,
         }
         .build()?;
@@ -149,7 +149,17 @@ pub async fn restart_node_service(
             )
         })?;
         // example path "antnode_path":"/var/antctl/services/antnode18/antnode"
-        let antnode_path = {
+        let antnode_path = if current_node_clone.container.is_some() {
+            // A containerized node runs the binary of its image
+            let mut antnode_path = data_dir_path.clone();
+            antnode_path.push(
+                current_node_clone
+                    .antnode_path
+                    .file_name()
+                    .ok_or_eyre("Could not get filename from the current node's antnode path")?,
+            );
+            antnode_path
+        } else {
             debug!("Copying antnode binary");
             let mut antnode_path = current_node_clone.antnode_path.clone();
             let antnode_file_name = antnode_path
@@ -211,6 +221,7 @@ pub async fn restart_node_service(
             service_user: current_node_clone.user.clone(),
             // This setting doesn't really matter because RPC will soon be taken out of use.
             stop_on_upgrade: true,
+            watchdog_secs: current_node_clone.watchdog_secs,
             write_older_cache_files: current_node_clone.write_older_cache_files,
         }
         .build()?;
@@ -223,6 +234,7 @@ pub async fn restart_node_service(
             antnode_path,
             auto_restart: current_node_clone.auto_restart,
             connected_peers: None,
+            container: current_node_clone.container.clone(),
             data_dir_path,
             evm_network: current_node_clone.evm_network,
             relay: current_node_clone.relay,
@@ -249,16 +261,14 @@ pub async fn restart_node_service(
             user: current_node_clone.user.clone(),
             user_mode: false,
             version: current_node_clone.version.clone(),
+            watchdog_secs: current_node_clone.watchdog_secs,
             write_older_cache_files: current_node_clone.write_older_cache_files,
         };
 
         let rpc_client = RpcClient::from_socket_addr(node.rpc_socket_addr);
         let service = NodeService::new(Arc::clone(&current_node), Box::new(rpc_client));
-        let mut service_manager = ServiceManager::new(
-            service,
-            Box::new(ServiceController {}),
-            VerbosityLevel::Normal,
-        );
+        let mut service_manager =
+            ServiceManager::new(service, node.service_control(), VerbosityLevel::Normal);
         service_manager.start().await?;
         node_registry
             .push_node(service_manager.service.service_data.read().await.clone())
@@ -1,17 +1,52 @@
-use ant_service_management::antctl_proto::NodeServiceRestartRequest;
+use crate::auth::AUTH_METADATA_KEY;
+use crate::inventory::RemoteHost;
 use ant_service_management::antctl_proto::ant_ctl_client::AntCtlClient;
+use ant_service_management::antctl_proto::get_status_response::Node;
+use ant_service_management::antctl_proto::{
+    GetStatusRequest, NodeServiceRestartRequest, NodeServiceUpgradeRequest,
+};
 use color_eyre::eyre::bail;
 use color_eyre::{Result, eyre::eyre};
 use libp2p_identity::PeerId;
 use std::net::SocketAddr;
 use std::str::FromStr;
 use std::time::Duration;
-use tonic::Request;
-use tonic::transport::Channel;
+use tonic::codegen::InterceptedService;
+use tonic::metadata::{Ascii, MetadataValue};
+use tonic::service::Interceptor;
+use tonic::transport::{Channel, ClientTlsConfig};
+use tonic::{Request, Status};
 
 struct DaemonRpcClient {
-    addr: SocketAddr,
-    rpc: AntCtlClient<Channel>,
+    addr: String,
+    rpc: AntCtlClient<InterceptedService<Channel, AuthInterceptor>>,
+}
+
+/// Adds the auth token, if any, to the requests sent to the daemon.
+#[derive(Clone)]
+struct AuthInterceptor {
+    authorization: Option<MetadataValue<Ascii>>,
+}
+
+impl AuthInterceptor {
+    fn new(auth_token: Option<String>) -> Result<Self> {
+        let authorization = auth_token
+            .map(|token| MetadataValue::from_str(&format!("Bearer {token}")))
+            .transpose()
+            .map_err(|_| eyre!("The auth token must only contain printable ASCII characters"))?;
+        Ok(Self { authorization })
+    }
+}
+
+impl Interceptor for AuthInterceptor {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        if let Some(authorization) = &self.authorization {
+            let _ = request
+                .metadata_mut()
+                .insert(AUTH_METADATA_KEY, authorization.clone());
+        }
+        Ok(request)
+    }
 }
 
 pub async fn restart_node(
@@ -23,7 +58,8 @@ pub async fn restart_node(
         debug!("Sending NodeServiceRestartRequest to {peer_id:?} at {rpc_server_address:?}");
         let str_bytes = PeerId::from_str(&peer_id)?.to_bytes();
 
-        let mut daemon_client = get_rpc_client(rpc_server_address).await?;
+        let mut daemon_client =
+            get_rpc_client(format!("https://{rpc_server_address}"), None, None).await?;
 
         let _response = daemon_client
             .rpc
@@ -44,14 +80,72 @@ pub async fn restart_node(
     Ok(())
 }
 
-async fn get_rpc_client(socket_addr: SocketAddr) -> Result<DaemonRpcClient> {
-    let endpoint = format!("https://{socket_addr}");
+/// Get the status of the node services managed by the daemon of a remote host.
+pub async fn get_remote_status(host: &RemoteHost) -> Result<Vec<Node>> {
+    debug!("Sending GetStatusRequest to {}", host.endpoint());
+    let mut daemon_client =
+        get_rpc_client(host.endpoint(), host.auth_token(), host.tls_config()?).await?;
+    let response = daemon_client
+        .rpc
+        .get_status(Request::new(GetStatusRequest {}))
+        .await
+        .map_err(|err| {
+            error!(
+                "Failed to get the status of {}: {err:?}",
+                daemon_client.addr
+            );
+            eyre!(
+                "Failed to get the status of {}: {}",
+                host.name,
+                err.message()
+            )
+        })?;
+    Ok(response.into_inner().nodes)
+}
+
+/// Upgrade the node services managed by the daemon of a remote host. Returns once the daemon
+/// has upgraded all the services.
+pub async fn upgrade_remote_node_services(
+    host: &RemoteHost,
+    request: NodeServiceUpgradeRequest,
+) -> Result<()> {
+    debug!("Sending {request:?} to {}", host.endpoint());
+    let mut daemon_client =
+        get_rpc_client(host.endpoint(), host.auth_token(), host.tls_config()?).await?;
+    let _response = daemon_client
+        .rpc
+        .upgrade_node_services(Request::new(request))
+        .await
+        .map_err(|err| {
+            error!(
+                "Failed to upgrade the services of {}: {err:?}",
+                daemon_client.addr
+            );
+            eyre!(
+                "Failed to upgrade the services of {}: {}",
+                host.name,
+                err.message()
+            )
+        })?;
+    Ok(())
+}
+
+async fn get_rpc_client(
+    endpoint: String,
+    auth_token: Option<String>,
+    tls_config: Option<ClientTlsConfig>,
+) -> Result<DaemonRpcClient> {
+    let interceptor = AuthInterceptor::new(auth_token)?;
+    let mut channel_endpoint = Channel::from_shared(endpoint.clone())?;
+    if let Some(tls_config) = tls_config {
+        channel_endpoint = channel_endpoint.tls_config(tls_config)?;
+    }
     let mut attempts = 0;
     loop {
-        if let Ok(rpc_client) = AntCtlClient::connect(endpoint.clone()).await {
+        if let Ok(channel) = channel_endpoint.connect().await {
             let rpc_client = DaemonRpcClient {
-                addr: socket_addr,
-                rpc: rpc_client,
+                addr: endpoint,
+                rpc: AntCtlClient::with_interceptor(channel, interceptor),
             };
             return Ok(rpc_client);
         }
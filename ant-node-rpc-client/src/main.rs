@@ -10,9 +10,10 @@
 use ant_logging::{Level, LogBuilder};
 use ant_node::NodeEvent;
 use ant_protocol::antnode_proto::{NodeEventsRequest, ant_node_client::AntNodeClient};
-use ant_service_management::rpc::{RpcActions, RpcClient};
+use ant_service_management::rpc::{RecordAddress, RpcActions, RpcClient};
 use clap::Parser;
 use color_eyre::eyre::Result;
+use libp2p::kad::RecordKey;
 use std::{net::SocketAddr, time::Duration};
 use tokio_stream::StreamExt;
 use tonic::Request;
@@ -85,6 +86,48 @@ enum Cmd {
         #[clap(name = "level", long)]
         log_level: String,
     },
+    /// Set the log level of a single target of the node, keeping the levels of the other targets.
+    #[clap(name = "loglevel")]
+    LogLevel {
+        /// The target, e.g. a crate or module like `ant_node::replication`
+        #[clap(long)]
+        target: String,
+        /// The log level: error, warn, info, debug or trace
+        #[clap(long)]
+        level: String,
+    },
+    /// Show statistics of the records held by the node
+    #[clap(name = "storestats")]
+    StoreStats {
+        /// The number of record addresses to sample at random
+        #[clap(long, default_value = "10")]
+        sample_size: u32,
+    },
+    /// Put the node into hibernation: it keeps its peer ID and its place in the network, but
+    /// refuses new data and pauses replication until resumed.
+    #[clap(name = "hibernate")]
+    Hibernate,
+    /// Resume the node from hibernation
+    #[clap(name = "resume")]
+    Resume,
+    /// Push a record held by the node to the peers of its current close group missing it,
+    /// without waiting for the periodic replication
+    #[clap(name = "replicate")]
+    Replicate {
+        /// The hex encoded address of the record
+        address: String,
+    },
+    /// Randomly sample held records, re-read and re-hash them from disk, and cross-check a few of
+    /// them against the other holders of their close group
+    #[clap(name = "verify")]
+    Verify {
+        /// The number of held records to sample at random
+        #[clap(long, default_value = "100")]
+        sample_size: u32,
+        /// The number of intact sampled records to cross-check with their close group
+        #[clap(long, default_value = "3")]
+        cross_check: u32,
+    },
 }
 
 #[tokio::main]
@@ -133,6 +176,15 @@ async fn main() -> Result<()> {
         Cmd::Stop { delay_millis } => node_stop(addr, delay_millis).await,
         Cmd::Update { delay_millis } => node_update(addr, delay_millis).await,
         Cmd::Log { log_level } => update_log_level(addr, log_level).await,
+        Cmd::LogLevel { target, level } => set_log_level(addr, target, level).await,
+        Cmd::StoreStats { sample_size } => record_store_stats(addr, sample_size).await,
+        Cmd::Hibernate => node_hibernate(addr).await,
+        Cmd::Resume => node_resume(addr).await,
+        Cmd::Replicate { address } => replicate_record(addr, address).await,
+        Cmd::Verify {
+            sample_size,
+            cross_check,
+        } => verify_records(addr, sample_size, cross_check).await,
     }
 }
 
@@ -209,6 +261,30 @@ pub async fn record_addresses(addr: SocketAddr) -> Result<()> {
     Ok(())
 }
 
+pub async fn record_store_stats(addr: SocketAddr, sample_size: u32) -> Result<()> {
+    let endpoint = format!("https://{addr}");
+    let client = RpcClient::new(&endpoint);
+    let stats = client.record_store_stats(sample_size).await?;
+
+    println!("Record store stats:");
+    println!("===================");
+    println!("Total bytes stored: {}", stats.total_bytes);
+    println!("Records per type:");
+    for (data_type, count) in stats.records_per_type.iter() {
+        println!("\t{data_type}: {count}");
+    }
+    println!("Records per distance (ilog2):");
+    for (ilog2_distance, count) in stats.records_per_distance.iter() {
+        println!("\t{ilog2_distance}: {count}");
+    }
+    println!("Sample of held records:");
+    for key in stats.sample.iter() {
+        println!("Key: {key:?}");
+    }
+
+    Ok(())
+}
+
 pub async fn node_restart(addr: SocketAddr, delay_millis: u64, retain_peer_id: bool) -> Result<()> {
     let endpoint = format!("https://{addr}");
     let client = RpcClient::new(&endpoint);
@@ -250,3 +326,76 @@ pub async fn update_log_level(addr: SocketAddr, log_levels: String) -> Result<()
     println!("Node successfully received the request to update the log level to {log_levels:?}",);
     Ok(())
 }
+
+pub async fn set_log_level(addr: SocketAddr, target: String, level: String) -> Result<()> {
+    let endpoint = format!("https://{addr}");
+    let client = RpcClient::new(&endpoint);
+
+    client.set_log_level(target.clone(), level.clone()).await?;
+    println!("Node successfully received the request to set the log level of {target} to {level}");
+    Ok(())
+}
+
+pub async fn node_hibernate(addr: SocketAddr) -> Result<()> {
+    let endpoint = format!("https://{addr}");
+    let client = RpcClient::new(&endpoint);
+    client.node_hibernate().await?;
+    println!("Node successfully received the request to hibernate");
+    Ok(())
+}
+
+pub async fn node_resume(addr: SocketAddr) -> Result<()> {
+    let endpoint = format!("https://{addr}");
+    let client = RpcClient::new(&endpoint);
+    client.node_resume().await?;
+    println!("Node successfully received the request to resume from hibernation");
+    Ok(())
+}
+
+pub async fn replicate_record(addr: SocketAddr, address: String) -> Result<()> {
+    let endpoint = format!("https://{addr}");
+    let client = RpcClient::new(&endpoint);
+    let key = RecordKey::new(&hex::decode(address.trim_start_matches("0x"))?);
+    match client.replicate_record(RecordAddress { key }).await? {
+        None => println!("The node does not hold the record {address}"),
+        Some(replicated_to) if replicated_to.is_empty() => {
+            println!("All the peers of the close group of {address} already hold the record")
+        }
+        Some(replicated_to) => {
+            println!("Record {address} pushed to:");
+            for peer_id in replicated_to {
+                println!("\t{peer_id}");
+            }
+        }
+    }
+    Ok(())
+}
+
+pub async fn verify_records(addr: SocketAddr, sample_size: u32, cross_check: u32) -> Result<()> {
+    let endpoint = format!("https://{addr}");
+    let client = RpcClient::new(&endpoint);
+    let verification = client.verify_records(sample_size, cross_check).await?;
+
+    println!("Records verification:");
+    println!("=====================");
+    println!(
+        "Intact on disk: {}/{}",
+        verification.intact(),
+        verification.sampled
+    );
+    for key in verification.corrupted.iter() {
+        println!("	Corrupted: {}", hex::encode(key.to_vec()));
+    }
+    println!(
+        "Cross-checked with the close group: {}",
+        verification.cross_checked
+    );
+    for key in verification.diverging.iter() {
+        println!("	Diverging: {}", hex::encode(key.to_vec()));
+    }
+    for key in verification.unconfirmed.iter() {
+        println!("	Unconfirmed: {}", hex::encode(key.to_vec()));
+    }
+
+    Ok(())
+}
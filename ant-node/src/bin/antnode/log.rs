@@ -1,6 +1,17 @@
-use std::path::PathBuf;
+use ant_service_management::node::{CrashKind, CrashReport};
+use std::backtrace::Backtrace;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 const CRITICAL_FAILURE_LOG_FILE: &str = "critical_failure.log";
+/// Number of log lines included in the crash reports.
+const CRASH_REPORT_LOG_LINES: usize = 100;
+/// How far back from the end of the log file the last lines are looked for.
+const CRASH_REPORT_LOG_TAIL_BYTES: u64 = 64 * 1024;
+
+static CRASH_REPORTER: OnceLock<CrashReporter> = OnceLock::new();
 
 pub fn set_critical_failure(log_output_dest: &str, reason: &str) {
     let log_path = PathBuf::from(log_output_dest).join(CRITICAL_FAILURE_LOG_FILE);
@@ -16,3 +27,100 @@ pub fn reset_critical_failure(log_output_dest: &str) {
         let _ = std::fs::remove_file(log_path);
     }
 }
+
+/// What is needed to write a crash report, set once the node's root dir and logging are known.
+struct CrashReporter {
+    root_dir: PathBuf,
+    log_output_dest: String,
+    config_hash: String,
+}
+
+/// Write a crash report in the node's root dir on panics, and enable `report_crash`.
+pub fn init_crash_reporting(root_dir: PathBuf, log_output_dest: String, config_hash: String) {
+    let reporter = CrashReporter {
+        root_dir,
+        log_output_dest,
+        config_hash,
+    };
+    if CRASH_REPORTER.set(reporter).is_err() {
+        warn!("Crash reporting has already been initialised");
+        return;
+    }
+
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |panic_info| {
+        let reason = if let Some(message) = panic_info.payload().downcast_ref::<&str>() {
+            message.to_string()
+        } else if let Some(message) = panic_info.payload().downcast_ref::<String>() {
+            message.clone()
+        } else {
+            "unknown panic payload".to_string()
+        };
+        error!("Node panicked: {reason}");
+        report_crash(
+            CrashKind::Panic,
+            &reason,
+            panic_info.location().map(|location| location.to_string()),
+            Some(Backtrace::force_capture().to_string()),
+        );
+        default_hook(panic_info);
+    }));
+}
+
+/// Write a crash report, if crash reporting has been initialised.
+pub fn report_crash(
+    kind: CrashKind,
+    reason: &str,
+    location: Option<String>,
+    backtrace: Option<String>,
+) {
+    let Some(reporter) = CRASH_REPORTER.get() else {
+        return;
+    };
+    let report = CrashReport {
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+        kind,
+        reason: reason.to_string(),
+        location,
+        backtrace,
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        config_hash: reporter.config_hash.clone(),
+        last_log_lines: last_log_lines(&reporter.log_output_dest),
+    };
+    match report.write(&reporter.root_dir) {
+        Ok(path) => error!("Crash report written to {path:?}"),
+        Err(err) => error!("Failed to write the crash report: {err}"),
+    }
+}
+
+/// The last lines of the node's current log file, if the node logs to a file.
+fn last_log_lines(log_output_dest: &str) -> Vec<String> {
+    let dest = Path::new(log_output_dest);
+    let log_file = if dest.extension().is_some_and(|ext| ext == "log") {
+        dest.to_path_buf()
+    } else {
+        dest.join(format!("{}.log", env!("CARGO_BIN_NAME")))
+    };
+
+    let Ok(mut file) = std::fs::File::open(log_file) else {
+        return vec![];
+    };
+    let len = file.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+    let start = len.saturating_sub(CRASH_REPORT_LOG_TAIL_BYTES);
+    let mut tail = Vec::new();
+    if file.seek(SeekFrom::Start(start)).is_err() || file.read_to_end(&mut tail).is_err() {
+        return vec![];
+    }
+
+    let tail = String::from_utf8_lossy(&tail);
+    let mut lines: Vec<&str> = tail.lines().collect();
+    if start > 0 && !lines.is_empty() {
+        // the first line is likely cut
+        let _ = lines.remove(0);
+    }
+    let skip = lines.len().saturating_sub(CRASH_REPORT_LOG_LINES);
+    lines[skip..].iter().map(|line| line.to_string()).collect()
+}
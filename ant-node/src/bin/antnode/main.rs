@@ -14,25 +14,34 @@
 extern crate tracing;
 
 mod log;
+mod node_info;
 mod rpc_service;
 mod subcommands;
+#[cfg(unix)]
+mod systemd;
 mod upgrade;
 
-use crate::log::{reset_critical_failure, set_critical_failure};
+use crate::log::{
+    init_crash_reporting, report_crash, reset_critical_failure, set_critical_failure,
+};
+use crate::node_info::NodeInfoPorts;
 use crate::subcommands::EvmNetworkCommand;
 use ant_bootstrap::BootstrapConfig;
 use ant_bootstrap::InitialPeersConfig;
 use ant_bootstrap::bootstrap::Bootstrap;
 use ant_evm::{EvmNetwork, RewardsAddress, get_evm_network};
 use ant_logging::metrics::init_metrics;
-use ant_logging::{Level, LogFormat, LogOutputDest, ReloadHandle};
+use ant_logging::{Level, LogFormat, LogOutputDest, LogShippingTarget, ReloadHandle};
+use ant_node::rewards_ledger::{MonthlyStatement, monthly_statements, read_rewards_ledger};
 use ant_node::utils::{get_antnode_root_dir, get_root_dir_and_keypair};
-use ant_node::{Marker, NodeBuilder, NodeEvent, NodeEventsReceiver};
+use ant_node::{FreeUpdateLimits, Marker, NodeBuilder, NodeEvent, NodeEventsReceiver};
 use ant_protocol::{
     node_rpc::{NodeCtrl, StopResult},
+    storage::DataTypes,
     version,
 };
-use clap::Parser;
+use ant_service_management::node::CrashKind;
+use clap::{Parser, builder::FalseyValueParser};
 use color_eyre::{Result, eyre::eyre};
 use const_hex::traits::FromHex;
 use libp2p::PeerId;
@@ -42,6 +51,7 @@ use std::{
     net::{IpAddr, Ipv4Addr, SocketAddr},
     path::{Path, PathBuf},
     process::{Command, ExitCode},
+    sync::Arc,
     time::Duration,
 };
 use tokio::{
@@ -78,14 +88,58 @@ pub fn parse_log_output(val: &str) -> Result<LogOutputDestArg> {
     }
 }
 
+/// Parse a storage quota given as `<TYPE>=<PERCENT>`, e.g. `scratchpad=5`, into the data type and
+/// the fraction of the store it may take.
+fn parse_storage_quota(val: &str) -> Result<(DataTypes, f64)> {
+    let (data_type, percent) = val
+        .split_once('=')
+        .ok_or_else(|| eyre!("The storage quota '{val}' must be given as <TYPE>=<PERCENT>"))?;
+    let data_type = match data_type.trim() {
+        "chunk" => DataTypes::Chunk,
+        "graph-entry" => DataTypes::GraphEntry,
+        "pointer" => DataTypes::Pointer,
+        "scratchpad" => DataTypes::Scratchpad,
+        other => {
+            return Err(eyre!(
+                "Unknown data type '{other}', expected one of chunk, graph-entry, pointer or scratchpad"
+            ));
+        }
+    };
+    let percent: f64 = percent
+        .trim()
+        .trim_end_matches('%')
+        .parse()
+        .map_err(|_| eyre!("The storage quota '{val}' must end with a percentage"))?;
+    if !(0.0..=100.0).contains(&percent) {
+        return Err(eyre!(
+            "The storage quota '{val}' must be between 0 and 100%"
+        ));
+    }
+    Ok((data_type, percent / 100.0))
+}
+
+/// File in the node's root dir holding the log levels to apply on SIGHUP, in the `ANT_LOG` format,
+/// e.g. `ant_networking=debug,ant_node=trace`. Without it, the levels of the startup are restored.
+const LOG_LEVELS_FILENAME: &str = "log_levels";
+
+/// How the options can be set through environment variables, e.g. in container deployments.
+const ENV_VARS_HELP: &str = "Most options can also be set through the environment variable shown in \
+their help, named after the option with the ANTNODE_ prefix, e.g. ANTNODE_PORT or \
+ANTNODE_REWARDS_ADDRESS. The others, such as the version and rewards statement options, can only \
+be given on the command line. A value given on the command line takes precedence over the environment variable, which \
+takes precedence over the default value. Boolean flags are disabled by 'false', 'f', 'no', 'n', \
+'off', '0' or an empty value, and enabled by any other value. When not given on the command line, \
+the initial peers are read from ANT_PEERS and the EVM network from EVM_NETWORK.";
+
 // Please do not remove the blank lines in these doc comments.
 // They are used for inserting line breaks when the help menu is rendered in the UI.
 #[derive(Parser, Debug)]
 #[command(disable_version_flag = true)]
+#[command(after_help = ENV_VARS_HELP)]
 #[clap(name = "antnode cli", version = env!("CARGO_PKG_VERSION"))]
 struct Opt {
     /// Set to connect to the alpha network.
-    #[clap(long)]
+    #[clap(long, env = "ANTNODE_ALPHA", value_parser = FalseyValueParser::new())]
     alpha: bool,
 
     /// Print the crate version.
@@ -98,6 +152,8 @@ struct Opt {
     /// This is automatically enabled if `metrics_server_port` is specified.
     #[clap(
         long,
+        env = "ANTNODE_ENABLE_METRICS_SERVER",
+        value_parser = FalseyValueParser::new(),
         default_value_t = false,
         required_if_eq("metrics_server_port", "0")
     )]
@@ -113,7 +169,7 @@ struct Opt {
     /// Specify the IP to listen on.
     ///
     /// The special value `0.0.0.0` binds to all network interfaces available.
-    #[clap(long, default_value_t = IpAddr::V4(Ipv4Addr::UNSPECIFIED))]
+    #[clap(long, env = "ANTNODE_IP", default_value_t = IpAddr::V4(Ipv4Addr::UNSPECIFIED))]
     ip: IpAddr,
 
     /// Specify the logging output destination.
@@ -127,7 +183,7 @@ struct Opt {
     ///  - macOS: $HOME/Library/Application Support/autonomi/node/<peer-id>/logs
     ///  - Windows: C:\Users\<username>\AppData\Roaming\autonomi\node\<peer-id>\logs
     #[expect(rustdoc::invalid_html_tags)]
-    #[clap(long, default_value_t = LogOutputDestArg::DataDir, value_parser = parse_log_output, verbatim_doc_comment)]
+    #[clap(long, env = "ANTNODE_LOG_OUTPUT_DEST", default_value_t = LogOutputDestArg::DataDir, value_parser = parse_log_output, verbatim_doc_comment)]
     log_output_dest: LogOutputDestArg,
 
     /// Specify the logging format.
@@ -135,7 +191,7 @@ struct Opt {
     /// Valid values are "default" or "json".
     ///
     /// If the argument is not used, the default format will be applied.
-    #[clap(long, value_parser = LogFormat::parse_from_str, verbatim_doc_comment)]
+    #[clap(long, env = "ANTNODE_LOG_FORMAT", value_parser = LogFormat::parse_from_str, verbatim_doc_comment)]
     log_format: Option<LogFormat>,
 
     /// Specify the maximum number of uncompressed log files to store.
@@ -144,7 +200,7 @@ struct Opt {
     ///
     /// After reaching this limit, the older files are archived to save space.
     /// You can also specify the maximum number of archived log files to keep.
-    #[clap(long, verbatim_doc_comment)]
+    #[clap(long, env = "ANTNODE_MAX_LOG_FILES", verbatim_doc_comment)]
     max_log_files: Option<usize>,
 
     /// Specify the maximum number of archived log files to store.
@@ -152,27 +208,100 @@ struct Opt {
     /// This argument is ignored if `log_output_dest` is set to "stdout"
     ///
     /// After reaching this limit, the older archived files are deleted.
-    #[clap(long, verbatim_doc_comment)]
+    #[clap(long, env = "ANTNODE_MAX_ARCHIVED_LOG_FILES", verbatim_doc_comment)]
     max_archived_log_files: Option<usize>,
 
+    /// Ship the logs as JSON to a remote collector, in addition to `log_output_dest`.
+    ///
+    /// Valid values are:
+    ///  - an HTTP(S) URL, e.g. "https://collector.example.com/logs": batches of newline delimited JSON are POSTed to it
+    ///  - "syslog://<host>:<port>": each log line is sent as an RFC 5424 syslog message over UDP
+    ///  - "syslog+tcp://<host>:<port>": each log line is sent as an RFC 5424 syslog message over TCP
+    ///
+    /// The logs are buffered and the sending is retried with a backoff while the collector is unreachable.
+    #[expect(rustdoc::invalid_html_tags)]
+    #[clap(long, env = "ANTNODE_LOG_SHIPPING", value_parser = LogShippingTarget::parse_from_str, verbatim_doc_comment)]
+    log_shipping: Option<LogShippingTarget>,
+
+    /// Specify the maximum number of records fetched in parallel during replication.
+    ///
+    /// Mutable records are fetched before chunks, and records closest to the node first.
+    /// Raising this can speed up replication at the cost of more bandwidth.
+    #[clap(
+        long,
+        env = "ANTNODE_MAX_PARALLEL_REPLICATION_FETCHES",
+        verbatim_doc_comment
+    )]
+    max_parallel_replication_fetches: Option<usize>,
+
+    /// Specify the bounds, in seconds, of the interval between two periodic replications.
+    ///
+    /// The node replicates more often as more peers join and leave its routing table, down to the
+    /// min interval during a mass churn, and relaxes up to the max interval while it is stable.
+    /// Defaults to 90 and 180 seconds, the min interval can't be lower than 90 seconds.
+    #[clap(
+        long,
+        env = "ANTNODE_MIN_REPLICATION_INTERVAL",
+        value_parser = clap::value_parser!(u64).range(90..),
+        verbatim_doc_comment
+    )]
+    min_replication_interval: Option<u64>,
+    /// See `--min-replication-interval`.
+    #[clap(
+        long,
+        env = "ANTNODE_MAX_REPLICATION_INTERVAL",
+        value_parser = clap::value_parser!(u64).range(90..)
+    )]
+    max_replication_interval: Option<u64>,
+
+    #[cfg(feature = "health-check")]
+    /// Enable the HTTP health service by providing an IP and port for it to listen on.
+    ///
+    /// It serves `/healthz` for liveness probes and `/readyz` for readiness probes.
+    /// Use 0.0.0.0 as the IP for probes from outside the host, e.g. in containers.
+    #[clap(long, env = "ANTNODE_HEALTH", verbatim_doc_comment)]
+    health: Option<SocketAddr>,
+
+    #[cfg(feature = "health-check")]
+    /// Specify the number of peers the routing table must hold for `/readyz` to report the node as ready.
+    ///
+    /// Defaults to the close group size.
+    #[clap(long, env = "ANTNODE_HEALTH_MIN_PEERS", verbatim_doc_comment)]
+    health_min_peers: Option<usize>,
+
+    /// Specify the maximum number of frequently requested records kept in memory.
+    ///
+    /// Popular records are then served without reading them from disk.
+    /// Chunks being up to 4MB, each cached record can use up to 4MB of memory.
+    /// The cache is disabled by default, and when set to 0.
+    #[clap(long, env = "ANTNODE_HOT_RECORDS_CACHE_SIZE", verbatim_doc_comment)]
+    hot_records_cache_size: Option<usize>,
+
     #[cfg(feature = "open-metrics")]
     /// Specify the port for the OpenMetrics server.
     ///
     /// If set, `--enable-metrics-server` will automatically be set to true.
     /// If not set, you must manually specify `--enable-metrics-server` and a port will be selected at random.
-    #[clap(long, default_value_t = 0)]
+    #[clap(long, env = "ANTNODE_METRICS_SERVER_PORT", default_value_t = 0)]
     metrics_server_port: u16,
 
+    /// Specify the free disk space, in megabytes, under which the node stops accepting new data.
+    ///
+    /// The node keeps serving GETs and replicating mutable records while under this threshold.
+    /// Defaults to 1024 MB.
+    #[clap(long, env = "ANTNODE_MIN_FREE_DISK_SPACE_MB", verbatim_doc_comment)]
+    min_free_disk_space_mb: Option<u64>,
+
     /// Specify the network ID to use. This will allow you to run the node on a different network.
     ///
     /// By default, the network ID is set to 1, which represents the mainnet.
-    #[clap(long, verbatim_doc_comment)]
+    #[clap(long, env = "ANTNODE_NETWORK_ID", verbatim_doc_comment)]
     network_id: Option<u8>,
 
     /// By default, a node would use UPnP to open a port in the home router and allow incoming connections.
     ///
     /// Set to 'true' to disable UPnP.
-    #[clap(long, default_value_t = false)]
+    #[clap(long, env = "ANTNODE_NO_UPNP", value_parser = FalseyValueParser::new(), default_value_t = false)]
     no_upnp: bool,
 
     /// Print the package version.
@@ -180,6 +309,19 @@ struct Opt {
     #[clap(long)]
     package_version: bool,
 
+    /// Specify the number of blocks a payment must be confirmed by before it is trusted.
+    ///
+    /// Until then, the node checks whether the payment was dropped by a chain reorganisation, in which case
+    /// the payment is verified again and the record removed if no longer paid for.
+    /// Defaults to 0, trusting payments as soon as they are verified.
+    #[clap(
+        long,
+        env = "ANTNODE_PAYMENT_CONFIRMATION_DEPTH",
+        default_value_t = 0,
+        verbatim_doc_comment
+    )]
+    payment_confirmation_depth: u64,
+
     #[command(flatten)]
     peers: InitialPeersConfig,
 
@@ -190,17 +332,31 @@ struct Opt {
     /// Specify the port to listen on.
     ///
     /// The special value `0` will cause the OS to assign a random port.
-    #[clap(long, default_value_t = 0)]
+    #[clap(long, env = "ANTNODE_PORT", default_value_t = 0)]
     port: u16,
 
     /// Specify the rewards address.
     /// The rewards address is the address that will receive the rewards for the node.
     /// It should be a valid EVM address.
-    #[clap(long)]
+    #[clap(long, env = "ANTNODE_REWARDS_ADDRESS")]
     rewards_address: Option<String>,
 
+    /// Print the rewards statements of the node, then exit.
+    ///
+    /// The statements list the payments accepted by the node, grouped per calendar month (UTC).
+    /// Use `all` for every month or `YYYY-MM` for a single month. Requires `--root-dir` to point
+    /// to the data directory of the node.
+    #[clap(long, requires = "root_dir", verbatim_doc_comment)]
+    rewards_statement: Option<String>,
+
+    /// Specify the format of the rewards statements.
+    ///
+    /// Valid values are "csv" or "json".
+    #[clap(long, default_value = "csv", value_parser = ["csv", "json"], verbatim_doc_comment)]
+    rewards_statement_format: String,
+
     /// Enable the mode to run as a relay client if it is behind a NAT and is not externally reachable.
-    #[clap(long, default_value_t = false)]
+    #[clap(long, env = "ANTNODE_RELAY", value_parser = FalseyValueParser::new(), default_value_t = false)]
     relay: bool,
 
     /// Specify the node's data directory.
@@ -210,28 +366,122 @@ struct Opt {
     ///  - macOS: $HOME/Library/Application Support/autonomi/node/<peer-id>
     ///  - Windows: C:\Users\<username>\AppData\Roaming\autonomi\node\<peer-id>
     #[expect(rustdoc::invalid_html_tags)]
-    #[clap(long, verbatim_doc_comment)]
+    #[clap(long, env = "ANTNODE_ROOT_DIR", verbatim_doc_comment)]
     root_dir: Option<PathBuf>,
 
+    /// Specify directories, e.g. on different disks, to store the records across.
+    ///
+    /// Records are sharded across the directories by the hash of their address, and moved to their
+    /// directory on restart when the list changes. Records stored under the root directory before
+    /// are moved to the data directories on the first start with them. Records in a directory
+    /// removed from the list are not moved, and are fetched again through replication.
+    /// If not provided, records are stored under the root directory.
+    #[clap(
+        long,
+        env = "ANTNODE_DATA_DIRS",
+        value_delimiter = ',',
+        verbatim_doc_comment
+    )]
+    data_dirs: Vec<PathBuf>,
+
+    /// Cap the share of the record store taken by the records of a data type, as <TYPE>=<PERCENT>.
+    ///
+    /// This stops cheap mutable records from taking the space of paid chunks, e.g.
+    /// `--storage-quota scratchpad=5` lets scratchpads take at most 5% of the store.
+    /// The data types are chunk, graph-entry, pointer and scratchpad. Can be repeated, or given
+    /// as a comma separated list. Data types without a quota may take the whole store.
+    #[clap(
+        long = "storage-quota",
+        env = "ANTNODE_STORAGE_QUOTAS",
+        value_delimiter = ',',
+        value_parser = parse_storage_quota,
+        verbatim_doc_comment
+    )]
+    storage_quotas: Vec<(DataTypes, f64)>,
+
+    /// Max number of free updates of the same pointer or scratchpad accepted per minute, 0 for no
+    /// limit.
+    ///
+    /// Updates of existing pointers and scratchpads don't need a payment, until they go over this
+    /// limit or the `--max-free-updates-per-source` one. Defaults to 20.
+    #[clap(
+        long,
+        env = "ANTNODE_MAX_FREE_UPDATES_PER_ADDRESS",
+        verbatim_doc_comment
+    )]
+    max_free_updates_per_address: Option<usize>,
+    /// Max number of free updates of pointers and scratchpads accepted per minute from the same
+    /// peer, 0 for no limit. Defaults to 120.
+    #[clap(
+        long,
+        env = "ANTNODE_MAX_FREE_UPDATES_PER_SOURCE",
+        verbatim_doc_comment
+    )]
+    max_free_updates_per_source: Option<usize>,
+    /// The difficulty, in bits, of the proof of work accepted instead of a payment for the updates
+    /// of pointers and scratchpads over the free update limits, 0 to only accept payments.
+    /// Defaults to 20.
+    #[clap(
+        long,
+        env = "ANTNODE_FREE_UPDATE_WORK_DIFFICULTY",
+        verbatim_doc_comment
+    )]
+    free_update_work_difficulty: Option<u8>,
+
     /// Enable the admin/control RPC service by providing an IP and port for it to listen on.
     ///
     /// The RPC service can be used for querying information about the running node.
-    #[clap(long)]
+    #[clap(long, env = "ANTNODE_RPC")]
     rpc: Option<SocketAddr>,
 
+    /// Also listen on, and dial peers over, TCP on the same port as QUIC.
+    ///
+    /// Useful when UDP is blocked on the network the node or its peers run on.
+    #[clap(long, env = "ANTNODE_TCP_FALLBACK", value_parser = FalseyValueParser::new(), default_value_t = false)]
+    tcp_fallback: bool,
+
+    /// Switch to relay mode when the node is found unreachable.
+    ///
+    /// When the peers fail to dial the node back, it stops advertising its external addresses and
+    /// is reached through relays instead, until the peers manage to dial it back again.
+    #[clap(long, env = "ANTNODE_RELAY_FALLBACK", value_parser = FalseyValueParser::new(), default_value_t = false)]
+    relay_fallback: bool,
+
     /// Print version information.
     #[clap(long)]
     version: bool,
 
     /// Set this to true if you want the node to write the cache files in the older formats.
-    #[clap(long, default_value_t = false)]
+    #[clap(long, env = "ANTNODE_WRITE_OLDER_CACHE_FILES", value_parser = FalseyValueParser::new(), default_value_t = false)]
     write_older_cache_files: bool,
 
+    /// Write the peer id, listen addresses, ports, network id and version of the node to a
+    /// `node-info.json` file in its root dir, kept up to date while the node runs.
+    ///
+    /// Meant for deployment automation, instead of parsing the logs.
+    #[clap(
+        long,
+        env = "ANTNODE_WRITE_NODE_INFO",
+        value_parser = FalseyValueParser::new(),
+        default_value_t = false,
+        verbatim_doc_comment
+    )]
+    write_node_info: bool,
+
     /// Stop the node instead of restarting after a successful upgrade.
     ///
     /// Useful when running under a service manager that handles restarts.
-    #[clap(long, default_value_t = false)]
+    #[clap(long, env = "ANTNODE_STOP_ON_UPGRADE", value_parser = FalseyValueParser::new(), default_value_t = false)]
     stop_on_upgrade: bool,
+
+    /// Specify the maximum time, in seconds, spent on shutdown completing the in-flight record
+    /// writes and flushing the bootstrap cache and metrics to disk.
+    ///
+    /// The node drains whenever it stops or restarts. When stopped by SIGINT (Ctrl-C) or SIGTERM,
+    /// it then exits with the code 128 + the signal number, i.e. 130 or 143.
+    /// Defaults to 10 seconds.
+    #[clap(long, env = "ANTNODE_MAX_DRAIN_TIME", verbatim_doc_comment)]
+    max_drain_time: Option<u64>,
 }
 
 fn main() -> ExitCode {
@@ -279,6 +529,11 @@ fn run() -> Result<ExitCode> {
         return Ok(ExitCode::SUCCESS);
     }
 
+    if let (Some(month), Some(root_dir)) = (&opt.rewards_statement, &opt.root_dir) {
+        print_rewards_statements(root_dir, month, &opt.rewards_statement_format)?;
+        return Ok(ExitCode::SUCCESS);
+    }
+
     let rewards_address = RewardsAddress::from_hex(opt.rewards_address.as_ref().expect(
         "the following required arguments were not provided: --rewards-address <REWARDS_ADDRESS>",
     ))?;
@@ -316,6 +571,7 @@ fn run() -> Result<ExitCode> {
 
     let (log_output_dest, log_reload_handle, _log_appender_guard) =
         init_logging(&opt, keypair.public().to_peer_id())?;
+    init_crash_reporting(root_dir.clone(), log_output_dest.clone(), config_hash(&opt));
 
     // Create a tokio runtime per `run_node` attempt, this ensures
     // any spawned tasks are closed before we would attempt to run
@@ -343,6 +599,11 @@ fn run() -> Result<ExitCode> {
         rt.spawn(init_metrics(std::process::id()));
     }
     let outcome = rt.block_on(async move {
+        // Verify payments against the same contracts as the clients
+        if let Err(err) = ant_evm::contract_registry::discover_contracts(&evm_network).await {
+            warn!("Failed to discover the EVM contract addresses, using the default ones: {err}");
+        }
+
         let mut node_builder = NodeBuilder::new(
             keypair,
             bootstrap,
@@ -354,6 +615,47 @@ fn run() -> Result<ExitCode> {
         node_builder.local(opt.peers.local);
         node_builder.no_upnp(opt.no_upnp);
         node_builder.relay_client(opt.relay);
+        node_builder.relay_fallback(opt.relay_fallback);
+        node_builder.tcp_fallback(opt.tcp_fallback);
+        node_builder.replication_max_parallel_fetches(opt.max_parallel_replication_fetches);
+        node_builder.replication_interval_bounds(
+            opt.min_replication_interval.map(Duration::from_secs),
+            opt.max_replication_interval.map(Duration::from_secs),
+        );
+        node_builder.hot_records_cache_size(opt.hot_records_cache_size);
+        node_builder.payment_confirmation_depth(opt.payment_confirmation_depth);
+        #[cfg(feature = "health-check")]
+        {
+            node_builder.health_server_addr(opt.health);
+            node_builder.health_min_peers(opt.health_min_peers);
+        }
+        node_builder.data_dirs(opt.data_dirs.clone());
+        for (data_type, fraction) in &opt.storage_quotas {
+            node_builder.storage_quota(*data_type, *fraction);
+        }
+        if opt.max_free_updates_per_address.is_some()
+            || opt.max_free_updates_per_source.is_some()
+            || opt.free_update_work_difficulty.is_some()
+        {
+            let defaults = FreeUpdateLimits::default();
+            node_builder.free_update_limits(Some(FreeUpdateLimits {
+                max_per_address: opt
+                    .max_free_updates_per_address
+                    .unwrap_or(defaults.max_per_address),
+                max_per_source: opt
+                    .max_free_updates_per_source
+                    .unwrap_or(defaults.max_per_source),
+                work_difficulty: opt
+                    .free_update_work_difficulty
+                    .unwrap_or(defaults.work_difficulty),
+                ..defaults
+            }));
+        }
+        node_builder.max_drain_time(opt.max_drain_time.map(Duration::from_secs));
+        node_builder.min_free_disk_space(
+            opt.min_free_disk_space_mb
+                .map(|mb| mb.saturating_mul(1024 * 1024)),
+        );
         #[cfg(feature = "open-metrics")]
         let mut node_builder = node_builder;
         // if enable flag is provided or only if the port is specified then enable the server by setting Some()
@@ -365,9 +667,19 @@ fn run() -> Result<ExitCode> {
         };
         #[cfg(feature = "open-metrics")]
         node_builder.metrics_server_port(metrics_server_port);
+        // A port of 0 is picked at random when binding, it is then not known here
+        #[cfg(feature = "open-metrics")]
+        let metrics_port = metrics_server_port.filter(|port| *port != 0);
+        #[cfg(not(feature = "open-metrics"))]
+        let metrics_port = None;
+        let node_info_ports = opt.write_node_info.then(|| NodeInfoPorts {
+            metrics_port,
+            rpc_port: opt.rpc.map(|addr| addr.port()),
+        });
         let outcome = run_node(
             node_builder,
             opt.rpc,
+            node_info_ports,
             &log_output_dest,
             log_reload_handle,
             opt.stop_on_upgrade,
@@ -382,13 +694,21 @@ fn run() -> Result<ExitCode> {
 
     match outcome {
         RunNodeOutcome::Restart(root_dir, port) => {
+            // A child spawned under systemd would be killed along with the unit, or outlive it
+            // unsupervised, so have systemd restart the node instead
+            #[cfg(unix)]
+            if systemd::is_supervised() {
+                println!("Node restart requested. Exiting with code 100 for service restart.");
+                return Ok(ExitCode::from(100));
+            }
             start_new_node_process(root_dir, port);
             println!("A new node process has been started successfully.");
         }
         RunNodeOutcome::UpgradeStop => {
             // On Windows, return exit code 100 to signal WinSW (configured with
             // RestartPolicy::OnFailure) to restart the service with the new binary.
-            // On Unix/macOS, exit 0 so that systemd's Restart=on-success triggers the restart.
+            // On Unix/macOS, exit 0 so that systemd's Restart=on-success triggers the restart,
+            // unless the node runs in a watchdog unit, which only restarts on failure.
             #[cfg(windows)]
             {
                 println!("Node stopped after upgrade. Exiting with code 100 for service restart.");
@@ -396,12 +716,22 @@ fn run() -> Result<ExitCode> {
             }
             #[cfg(not(windows))]
             {
+                if systemd::is_supervised() {
+                    println!(
+                        "Node stopped after upgrade. Exiting with code 100 for service restart."
+                    );
+                    return Ok(ExitCode::from(100));
+                }
                 println!("Node stopped after upgrade. Service manager will handle restart.");
             }
         }
         RunNodeOutcome::Stop => {
             println!("The node process has been stopped.");
         }
+        RunNodeOutcome::Signal(signal) => {
+            println!("The node process has been stopped by signal {signal}.");
+            return Ok(ExitCode::from(128u8.saturating_add(signal as u8)));
+        }
     }
 
     Ok(ExitCode::SUCCESS)
@@ -416,12 +746,16 @@ enum RunNodeOutcome {
     /// The node completed an upgrade and should exit so the service manager
     /// can restart it with the new binary.
     UpgradeStop,
+    /// The node was stopped by the given termination signal, and should exit with the
+    /// conventional 128 + signal code.
+    Signal(i32),
 }
 
 /// Start a node with the given configuration.
 async fn run_node(
     node_builder: NodeBuilder,
     rpc: Option<SocketAddr>,
+    node_info_ports: Option<NodeInfoPorts>,
     log_output_dest: &str,
     log_reload_handle: ReloadHandle,
     stop_on_upgrade: bool,
@@ -450,6 +784,23 @@ You can check your reward balance by running:
     let pid_file = running_node.root_dir_path().join("antnode.pid");
     std::fs::write(pid_file, pid.to_string().as_bytes())?;
 
+    if let Some(ports) = node_info_ports {
+        node_info::spawn_node_info_writer(running_node.clone(), ports);
+    }
+
+    #[cfg(unix)]
+    {
+        systemd::notify_ready();
+        systemd::spawn_watchdog(running_node.clone());
+    }
+
+    let log_reload_handle = Arc::new(log_reload_handle);
+    #[cfg(unix)]
+    reload_log_levels_on_sighup(
+        Arc::clone(&log_reload_handle),
+        running_node.root_dir_path().join(LOG_LEVELS_FILENAME),
+    );
+
     // Channel to receive node ctrl cmds from RPC service (if enabled), and events monitoring task
     let (ctrl_tx, mut ctrl_rx) = mpsc::channel::<NodeCtrl>(5);
 
@@ -457,17 +808,15 @@ You can check your reward balance by running:
     let node_events_rx = running_node.node_events_channel().subscribe();
     monitor_node_events(node_events_rx, ctrl_tx.clone());
 
-    // Monitor ctrl-c
+    // Monitor ctrl-c and SIGTERM
     let ctrl_tx_clone = ctrl_tx.clone();
     tokio::spawn(async move {
-        if let Err(err) = tokio::signal::ctrl_c().await {
-            // I/O error, ignore/print the error, but continue to handle as if ctrl-c was received
-            warn!("Listening to ctrl-c error: {err}");
-        }
+        let signal = termination_signal().await;
+        info!("Termination signal {signal} received, stopping the node gracefully");
         if let Err(err) = ctrl_tx_clone
             .send(NodeCtrl::Stop {
-                delay: Duration::from_secs(1),
-                result: StopResult::Error(eyre!("Ctrl-C received!")),
+                delay: Duration::ZERO,
+                result: StopResult::Signal(signal),
             })
             .await
         {
@@ -558,6 +907,9 @@ You can check your reward balance by running:
                 info!("{msg}");
                 println!("{msg} Node path: {log_output_dest}");
                 sleep(delay).await;
+                #[cfg(unix)]
+                systemd::notify_stopping();
+                running_node.graceful_shutdown().await;
 
                 return Ok(RunNodeOutcome::Restart(root_dir, node_port));
             }
@@ -566,6 +918,9 @@ You can check your reward balance by running:
                 info!("{msg}");
                 println!("{msg} Node log path: {log_output_dest}");
                 sleep(delay).await;
+                #[cfg(unix)]
+                systemd::notify_stopping();
+                running_node.graceful_shutdown().await;
                 match result {
                     StopResult::Success(message) => {
                         info!("Node stopped successfully: {}", message);
@@ -575,9 +930,14 @@ You can check your reward balance by running:
                         info!("Node stopping for upgrade: {}", message);
                         return Ok(RunNodeOutcome::UpgradeStop);
                     }
+                    StopResult::Signal(signal) => {
+                        info!("Node stopped by signal {signal}");
+                        return Ok(RunNodeOutcome::Signal(signal));
+                    }
                     StopResult::Error(cause) => {
                         error!("Node stopped with error: {}", cause);
                         set_critical_failure(log_output_dest, &cause.to_string());
+                        report_crash(CrashKind::FatalError, &format!("{cause:?}"), None, None);
                         return Err(cause);
                     }
                 }
@@ -594,11 +954,73 @@ You can check your reward balance by running:
     }
 }
 
+/// Wait for a termination signal, returning its number: SIGINT (Ctrl-C), or SIGTERM on unix.
+async fn termination_signal() -> i32 {
+    const SIGINT: i32 = 2;
+    #[cfg(unix)]
+    const SIGTERM: i32 = 15;
+
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                tokio::select! {
+                    result = tokio::signal::ctrl_c() => {
+                        if let Err(err) = result {
+                            // I/O error, ignore/print the error, but continue to handle as if ctrl-c was received
+                            warn!("Listening to ctrl-c error: {err}");
+                        }
+                        return SIGINT;
+                    }
+                    _ = sigterm.recv() => return SIGTERM,
+                }
+            }
+            Err(err) => warn!("Listening to SIGTERM error: {err}"),
+        }
+    }
+
+    if let Err(err) = tokio::signal::ctrl_c().await {
+        // I/O error, ignore/print the error, but continue to handle as if ctrl-c was received
+        warn!("Listening to ctrl-c error: {err}");
+    }
+    SIGINT
+}
+
+/// Reload the log levels from `path` whenever the process receives a SIGHUP.
+#[cfg(unix)]
+fn reload_log_levels_on_sighup(log_reload_handle: Arc<ReloadHandle>, path: PathBuf) {
+    use tokio::signal::unix::{SignalKind, signal};
+
+    tokio::spawn(async move {
+        let mut sighup = match signal(SignalKind::hangup()) {
+            Ok(sighup) => sighup,
+            Err(err) => {
+                warn!("Failed to listen to SIGHUP, log levels can't be reloaded: {err}");
+                return;
+            }
+        };
+        while sighup.recv().await.is_some() {
+            match log_reload_handle.reload_from_file(&path) {
+                Ok(()) => info!("SIGHUP received, log levels reloaded from {path:?}"),
+                Err(err) => {
+                    error!("SIGHUP received, failed to reload log levels from {path:?}: {err}")
+                }
+            }
+        }
+    });
+}
+
 fn monitor_node_events(mut node_events_rx: NodeEventsReceiver, ctrl_tx: mpsc::Sender<NodeCtrl>) {
     let _handle = tokio::spawn(async move {
         loop {
             match node_events_rx.recv().await {
-                Ok(NodeEvent::ConnectedToNetwork) => Marker::NodeConnectedToNetwork.log(),
+                Ok(NodeEvent::ConnectedToNetwork) => {
+                    Marker::NodeConnectedToNetwork.log();
+                    #[cfg(unix)]
+                    systemd::notify_status("Connected to the network");
+                }
                 Ok(NodeEvent::ChannelClosed) | Err(RecvError::Closed) => {
                     if let Err(err) = ctrl_tx
                         .send(NodeCtrl::Stop {
@@ -636,6 +1058,33 @@ fn monitor_node_events(mut node_events_rx: NodeEventsReceiver, ctrl_tx: mpsc::Se
     });
 }
 
+/// Hash of the options the node was started with, recorded in the crash reports.
+fn config_hash(opt: &Opt) -> String {
+    use sha2::{Digest, Sha256};
+
+    hex::encode(Sha256::digest(format!("{opt:?}").as_bytes()))
+}
+
+/// Print the rewards statements of the node in `root_dir`, for every month or a single `YYYY-MM` one.
+fn print_rewards_statements(root_dir: &Path, month: &str, format: &str) -> Result<()> {
+    let rewards = read_rewards_ledger(root_dir)
+        .map_err(|err| eyre!("Failed to read the rewards ledger in {root_dir:?}: {err}"))?;
+    let statements: Vec<MonthlyStatement> = monthly_statements(rewards)
+        .into_iter()
+        .filter(|statement| month == "all" || statement.month == month)
+        .collect();
+
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&statements)?);
+    } else {
+        println!("{}", MonthlyStatement::CSV_HEADER);
+        for statement in &statements {
+            print!("{}", statement.to_csv_rows());
+        }
+    }
+    Ok(())
+}
+
 fn init_logging(opt: &Opt, peer_id: PeerId) -> Result<(String, ReloadHandle, Option<WorkerGuard>)> {
     let logging_targets = vec![
         ("ant_bootstrap".to_string(), Level::INFO),
@@ -668,6 +1117,9 @@ fn init_logging(opt: &Opt, peer_id: PeerId) -> Result<(String, ReloadHandle, Opt
         if let Some(files) = opt.max_archived_log_files {
             log_builder.max_archived_log_files(files);
         }
+        if let Some(target) = &opt.log_shipping {
+            log_builder.log_shipping(target.clone());
+        }
 
         log_builder.initialize()?
     };
@@ -686,6 +1138,9 @@ fn init_logging(opt: &Opt, peer_id: PeerId) -> Result<(String, ReloadHandle, Opt
             if let Some(files) = opt.max_archived_log_files {
                 log_builder.max_archived_log_files(files);
             }
+            if let Some(target) = &opt.log_shipping {
+                log_builder.log_shipping(target.clone());
+            }
             log_builder.initialize()
         })?;
         (rt, reload_handle, log_appender_guard)
@@ -761,3 +1216,60 @@ fn start_new_node_process(root_dir: PathBuf, port: u16) {
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct EnvVars(&'static [(&'static str, &'static str)]);
+
+    impl EnvVars {
+        #[allow(unsafe_code)]
+        fn set(vars: &'static [(&'static str, &'static str)]) -> Self {
+            for (key, value) in vars {
+                unsafe {
+                    std::env::set_var(key, value);
+                }
+            }
+            Self(vars)
+        }
+    }
+
+    impl Drop for EnvVars {
+        #[allow(unsafe_code)]
+        fn drop(&mut self) {
+            for (key, _) in self.0 {
+                unsafe {
+                    std::env::remove_var(key);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn options_are_read_from_env_vars() -> Result<()> {
+        let _env = EnvVars::set(&[
+            ("ANTNODE_ALPHA", "1"),
+            ("ANTNODE_NO_UPNP", "yes"),
+            ("ANTNODE_RELAY", "off"),
+            ("ANTNODE_TCP_FALLBACK", "false"),
+            ("ANTNODE_STOP_ON_UPGRADE", "true"),
+            ("ANTNODE_PORT", "12000"),
+        ]);
+
+        let opt = Opt::try_parse_from(["antnode"])?;
+        assert!(opt.alpha);
+        assert!(opt.no_upnp);
+        assert!(!opt.relay);
+        assert!(!opt.tcp_fallback);
+        assert!(opt.stop_on_upgrade);
+        assert!(!opt.relay_fallback);
+        assert_eq!(opt.port, 12000);
+
+        // The command line takes precedence over the environment variables.
+        let opt = Opt::try_parse_from(["antnode", "--relay", "--port", "13000"])?;
+        assert!(opt.relay);
+        assert_eq!(opt.port, 13000);
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use ant_node::RunningNode;
+use ant_protocol::version;
+use ant_service_management::node::NodeInfoFile;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::time::sleep;
+
+/// Interval between two checks for changes of the listen addresses of the node.
+const NODE_INFO_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The ports of the services of the node, which the running node does not know of.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct NodeInfoPorts {
+    pub(crate) metrics_port: Option<u16>,
+    pub(crate) rpc_port: Option<u16>,
+}
+
+/// Write the node info to the root dir of the node, and rewrite it whenever its listen addresses
+/// change.
+pub(crate) fn spawn_node_info_writer(running_node: RunningNode, ports: NodeInfoPorts) {
+    tokio::spawn(async move {
+        let root_dir = running_node.root_dir_path();
+        let mut written_addrs = None;
+        loop {
+            match running_node.get_listen_addrs_with_peer_id().await {
+                Ok(addrs) => {
+                    let listen_addrs: Vec<String> =
+                        addrs.iter().map(|addr| addr.to_string()).collect();
+                    if written_addrs.as_ref() != Some(&listen_addrs) {
+                        let info = NodeInfoFile {
+                            peer_id: running_node.peer_id().to_string(),
+                            listen_addrs: listen_addrs.clone(),
+                            metrics_port: ports.metrics_port,
+                            rpc_port: ports.rpc_port,
+                            network_id: version::get_network_id(),
+                            version: env!("CARGO_PKG_VERSION").to_string(),
+                            pid: std::process::id(),
+                            updated_at: SystemTime::now()
+                                .duration_since(UNIX_EPOCH)
+                                .map(|since_epoch| since_epoch.as_secs())
+                                .unwrap_or_default(),
+                        };
+                        match info.write(&root_dir) {
+                            Ok(path) => {
+                                debug!("Node info written to {path:?}");
+                                written_addrs = Some(listen_addrs);
+                            }
+                            Err(err) => warn!("Failed to write the node info: {err}"),
+                        }
+                    }
+                }
+                Err(err) => warn!("Failed to get the listen addresses for the node info: {err}"),
+            }
+            sleep(NODE_INFO_REFRESH_INTERVAL).await;
+        }
+    });
+}
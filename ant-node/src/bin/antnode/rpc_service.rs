@@ -7,14 +7,19 @@
 // permissions and limitations relating to use of the SAFE Network Software.
 
 use ant_logging::ReloadHandle;
-use ant_node::RunningNode;
+use ant_node::{AddressFamily, ReachabilityStatus, RunningNode};
+use ant_protocol::NetworkAddress;
 use ant_protocol::antnode_proto::{
+    BehaviourHealthRequest, BehaviourHealthResponse, HibernateRequest, HibernateResponse,
     KBucketsRequest, KBucketsResponse, NetworkInfoRequest, NetworkInfoResponse, NodeEvent,
-    NodeEventsRequest, NodeInfoRequest, NodeInfoResponse, RecordAddressesRequest,
-    RecordAddressesResponse, RestartRequest, RestartResponse, StopRequest, StopResponse,
-    UpdateLogLevelRequest, UpdateLogLevelResponse, UpdateRequest, UpdateResponse,
+    NodeEventsRequest, NodeInfoRequest, NodeInfoResponse, ReachabilityRequest,
+    ReachabilityResponse, RecordAddressesRequest, RecordAddressesResponse, RecordStoreStatsRequest,
+    RecordStoreStatsResponse, ReplicateRequest, ReplicateResponse, RestartRequest, RestartResponse,
+    ResumeRequest, ResumeResponse, SetLogLevelRequest, SetLogLevelResponse, StopRequest,
+    StopResponse, UpdateLogLevelRequest, UpdateLogLevelResponse, UpdateRequest, UpdateResponse,
+    VerifyRecordsRequest, VerifyRecordsResponse,
     ant_node_server::{AntNode, AntNodeServer},
-    k_buckets_response,
+    behaviour_health_response, k_buckets_response, reachability_response,
 };
 use ant_protocol::node_rpc::{NodeCtrl, StopResult};
 use eyre::{ErrReport, Result};
@@ -23,6 +28,7 @@ use std::{
     env,
     net::SocketAddr,
     process,
+    sync::Arc,
     time::{Duration, Instant},
 };
 use tokio::sync::mpsc::{self, Sender};
@@ -30,6 +36,11 @@ use tokio_stream::wrappers::ReceiverStream;
 use tonic::{Code, Request, Response, Status, transport::Server};
 use tracing::{debug, info};
 
+/// The maximum number of record addresses sampled by a `RecordStoreStats` request.
+const MAX_RECORD_SAMPLE_SIZE: usize = 1000;
+/// The maximum number of records cross-checked with their close group by a `VerifyRecords` request.
+const MAX_RECORD_CROSS_CHECK_COUNT: usize = 20;
+
 // Defining a struct to hold information used by our gRPC service backend
 struct SafeNodeRpcService {
     addr: SocketAddr,
@@ -37,7 +48,7 @@ struct SafeNodeRpcService {
     running_node: RunningNode,
     ctrl_tx: Sender<NodeCtrl>,
     started_instant: Instant,
-    log_reload_handle: ReloadHandle,
+    log_reload_handle: Arc<ReloadHandle>,
 }
 
 // Implementing RPC interface for service defined in .proto
@@ -194,6 +205,127 @@ impl AntNode for SafeNodeRpcService {
         Ok(Response::new(KBucketsResponse { kbuckets }))
     }
 
+    async fn behaviour_health(
+        &self,
+        request: Request<BehaviourHealthRequest>,
+    ) -> Result<Response<BehaviourHealthResponse>, Status> {
+        debug!(
+            "RPC request received at {}: {:?}",
+            self.addr,
+            request.get_ref()
+        );
+
+        let health = match self.running_node.get_behaviour_health().await {
+            Ok(health) => health,
+            Err(err) => {
+                return Err(Status::invalid_argument(format!(
+                    "Failed to get behaviour health: {err:?}"
+                )));
+            }
+        };
+
+        let resp = Response::new(BehaviourHealthResponse {
+            kademlia: Some(behaviour_health_response::Kademlia {
+                peers_in_routing_table: health.kademlia.peers_in_routing_table as u64,
+                non_empty_kbuckets: health.kademlia.non_empty_kbuckets as u64,
+                ongoing_queries: health.kademlia.ongoing_queries as u64,
+            }),
+            request_response: Some(behaviour_health_response::RequestResponse {
+                inflight_outbound_requests: health.request_response.inflight_outbound_requests
+                    as u64,
+                outbound_failures: health.request_response.outbound_failures,
+                inbound_failures: health.request_response.inbound_failures,
+            }),
+            identify: Some(behaviour_health_response::Identify {
+                received: health.identify.received,
+                errors: health.identify.errors,
+            }),
+        });
+
+        Ok(resp)
+    }
+
+    async fn reachability(
+        &self,
+        request: Request<ReachabilityRequest>,
+    ) -> Result<Response<ReachabilityResponse>, Status> {
+        debug!(
+            "RPC request received at {}: {:?}",
+            self.addr,
+            request.get_ref()
+        );
+
+        let reachability = match self.running_node.get_reachability().await {
+            Ok(reachability) => reachability,
+            Err(err) => {
+                return Err(Status::internal(format!(
+                    "Failed to get reachability: {err:?}"
+                )));
+            }
+        };
+
+        let status = match reachability.status {
+            ReachabilityStatus::NotChecked => reachability_response::Status::NotChecked,
+            ReachabilityStatus::Reachable => reachability_response::Status::Reachable,
+            ReachabilityStatus::Unreachable => reachability_response::Status::Unreachable,
+        };
+        let address_family = match reachability.address_family {
+            None => reachability_response::AddressFamily::Unknown,
+            Some(AddressFamily::Ipv4) => reachability_response::AddressFamily::Ipv4,
+            Some(AddressFamily::Ipv6) => reachability_response::AddressFamily::Ipv6,
+        };
+        let resp = Response::new(ReachabilityResponse {
+            status: status as i32,
+            incoming_peers: reachability.incoming_peers as u64,
+            address_family: address_family as i32,
+            relayed: reachability.relayed,
+            progress: reachability.progress as u32,
+            rounds: reachability.rounds,
+        });
+
+        Ok(resp)
+    }
+
+    async fn record_store_stats(
+        &self,
+        request: Request<RecordStoreStatsRequest>,
+    ) -> Result<Response<RecordStoreStatsResponse>, Status> {
+        debug!(
+            "RPC request received at {}: {:?}",
+            self.addr,
+            request.get_ref()
+        );
+
+        let sample_size = (request.get_ref().sample_size as usize).min(MAX_RECORD_SAMPLE_SIZE);
+        let stats = match self.running_node.get_record_store_stats(sample_size).await {
+            Ok(stats) => stats,
+            Err(err) => {
+                return Err(Status::invalid_argument(format!(
+                    "Failed to get record store stats: {err:?}"
+                )));
+            }
+        };
+
+        Ok(Response::new(RecordStoreStatsResponse {
+            records_per_type: stats
+                .records_per_type
+                .into_iter()
+                .map(|(data_type, count)| (format!("{data_type:?}"), count as u64))
+                .collect(),
+            total_bytes: stats.total_bytes,
+            records_per_distance: stats
+                .records_per_distance
+                .into_iter()
+                .map(|(ilog2_distance, count)| (ilog2_distance, count as u64))
+                .collect(),
+            sample: stats
+                .sample
+                .into_iter()
+                .map(|addr| addr.as_bytes())
+                .collect(),
+        }))
+    }
+
     async fn stop(&self, request: Request<StopRequest>) -> Result<Response<StopResponse>, Status> {
         debug!(
             "RPC request received at {}: {:?}",
@@ -294,6 +426,139 @@ impl AntNode for SafeNodeRpcService {
             )),
         }
     }
+
+    async fn set_log_level(
+        &self,
+        request: Request<SetLogLevelRequest>,
+    ) -> Result<Response<SetLogLevelResponse>, Status> {
+        debug!(
+            "RPC request received at {}: {:?}",
+            self.addr,
+            request.get_ref()
+        );
+
+        let SetLogLevelRequest { target, level } = request.get_ref();
+        match self.log_reload_handle.set_log_level(target, level) {
+            Ok(()) => Ok(Response::new(SetLogLevelResponse {})),
+            Err(err) => Err(Status::new(
+                Code::InvalidArgument,
+                format!("Failed to set the log level of {target}: {err:?}"),
+            )),
+        }
+    }
+
+    async fn hibernate(
+        &self,
+        request: Request<HibernateRequest>,
+    ) -> Result<Response<HibernateResponse>, Status> {
+        debug!(
+            "RPC request received at {}: {:?}",
+            self.addr,
+            request.get_ref()
+        );
+
+        self.running_node.hibernate();
+        Ok(Response::new(HibernateResponse {}))
+    }
+
+    async fn resume(
+        &self,
+        request: Request<ResumeRequest>,
+    ) -> Result<Response<ResumeResponse>, Status> {
+        debug!(
+            "RPC request received at {}: {:?}",
+            self.addr,
+            request.get_ref()
+        );
+
+        self.running_node.resume();
+        Ok(Response::new(ResumeResponse {}))
+    }
+
+    async fn replicate(
+        &self,
+        request: Request<ReplicateRequest>,
+    ) -> Result<Response<ReplicateResponse>, Status> {
+        debug!(
+            "RPC request received at {}: {:?}",
+            self.addr,
+            request.get_ref()
+        );
+
+        if request.get_ref().address.is_empty() {
+            return Err(Status::invalid_argument("No record address provided"));
+        }
+        if self.running_node.is_hibernating() {
+            return Err(Status::failed_precondition(
+                "The node is hibernating, replication is paused",
+            ));
+        }
+
+        let address = NetworkAddress::RecordKey(request.into_inner().address.into());
+        match self.running_node.replicate_record(address).await {
+            Ok(peers) => Ok(Response::new(ReplicateResponse {
+                replicated_to: peers.iter().map(|peer| peer.to_bytes()).collect(),
+            })),
+            Err(err @ ant_node::Error::RecordNotHeld(_)) => {
+                Err(Status::not_found(format!("{err}")))
+            }
+            Err(err) => Err(Status::internal(format!(
+                "Failed to replicate the record: {err:?}"
+            ))),
+        }
+    }
+
+    async fn verify_records(
+        &self,
+        request: Request<VerifyRecordsRequest>,
+    ) -> Result<Response<VerifyRecordsResponse>, Status> {
+        debug!(
+            "RPC request received at {}: {:?}",
+            self.addr,
+            request.get_ref()
+        );
+
+        let sample_size = (request.get_ref().sample_size as usize).min(MAX_RECORD_SAMPLE_SIZE);
+        // The records are still checked on disk while hibernating, but not over the network.
+        let cross_check_count = if self.running_node.is_hibernating() {
+            0
+        } else {
+            (request.get_ref().cross_check_count as usize).min(MAX_RECORD_CROSS_CHECK_COUNT)
+        };
+
+        let verification = match self
+            .running_node
+            .verify_records(sample_size, cross_check_count)
+            .await
+        {
+            Ok(verification) => verification,
+            Err(err) => {
+                return Err(Status::internal(format!(
+                    "Failed to verify the records: {err:?}"
+                )));
+            }
+        };
+
+        Ok(Response::new(VerifyRecordsResponse {
+            sampled: verification.sampled as u32,
+            corrupted: verification
+                .corrupted
+                .into_iter()
+                .map(|addr| addr.as_bytes())
+                .collect(),
+            cross_checked: verification.cross_checked as u32,
+            diverging: verification
+                .diverging
+                .into_iter()
+                .map(|addr| addr.as_bytes())
+                .collect(),
+            unconfirmed: verification
+                .unconfirmed
+                .into_iter()
+                .map(|addr| addr.as_bytes())
+                .collect(),
+        }))
+    }
 }
 
 pub(crate) fn start_rpc_service(
@@ -302,7 +567,7 @@ pub(crate) fn start_rpc_service(
     running_node: RunningNode,
     ctrl_tx: Sender<NodeCtrl>,
     started_instant: Instant,
-    log_reload_handle: ReloadHandle,
+    log_reload_handle: Arc<ReloadHandle>,
 ) {
     // creating a service
     let service = SafeNodeRpcService {
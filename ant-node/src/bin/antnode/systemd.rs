@@ -0,0 +1,75 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Supervision of the node by systemd, when it runs as a `Type=notify` unit.
+//!
+//! The node notifies systemd once it has started, and pings the watchdog of its unit as long as its
+//! networking loop is responsive, so that systemd restarts a node which hangs. Outside of such a
+//! unit `NOTIFY_SOCKET` is not set, and nothing is sent.
+
+use ant_node::RunningNode;
+use sd_notify::NotifyState;
+use std::time::Duration;
+
+/// Time the networking loop has to report its state for the watchdog to be pinged.
+const WATCHDOG_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Whether the node runs in a unit expecting notifications from it.
+pub(crate) fn is_supervised() -> bool {
+    std::env::var_os("NOTIFY_SOCKET").is_some()
+}
+
+fn notify(state: NotifyState) {
+    if let Err(err) = sd_notify::notify(false, &[state]) {
+        warn!("Failed to notify systemd: {err}");
+    }
+}
+
+/// Tell systemd the node has started.
+pub(crate) fn notify_ready() {
+    notify(NotifyState::Ready);
+}
+
+/// Update the status of the unit, as shown by `systemctl status`.
+pub(crate) fn notify_status(status: &str) {
+    notify(NotifyState::Status(status));
+}
+
+/// Tell systemd the node is shutting down.
+pub(crate) fn notify_stopping() {
+    notify(NotifyState::Stopping);
+}
+
+/// Ping the watchdog of the unit, if it has one, at half its timeout for as long as the networking
+/// loop of the node is responsive.
+pub(crate) fn spawn_watchdog(running_node: RunningNode) {
+    let mut watchdog_usec = 0;
+    if !sd_notify::watchdog_enabled(false, &mut watchdog_usec) {
+        return;
+    }
+    let interval = Duration::from_micros(watchdog_usec) / 2;
+    info!("The systemd watchdog is enabled, pinging it every {interval:?}");
+
+    let _handle = tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            let _ = ticker.tick().await;
+            match tokio::time::timeout(WATCHDOG_CHECK_TIMEOUT, running_node.get_swarm_local_state())
+                .await
+            {
+                Ok(Ok(_)) => notify(NotifyState::Watchdog),
+                Ok(Err(err)) => {
+                    warn!("Failed to get the swarm state, not pinging the watchdog: {err}")
+                }
+                Err(_) => warn!(
+                    "The networking loop did not respond in {WATCHDOG_CHECK_TIMEOUT:?}, not pinging the watchdog"
+                ),
+            }
+        }
+    });
+}
@@ -0,0 +1,114 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Default free space under which the node stops accepting new paid records: 1 GiB.
+pub(crate) const DEFAULT_MIN_FREE_DISK_SPACE: u64 = 1024 * 1024 * 1024;
+
+/// Interval to refresh the free disk space of the node.
+pub(crate) const DISK_SPACE_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Tracks the free space of the disks holding the node's data and decides whether new data
+/// can be admitted. With records sharded across several disks, the least free one is tracked.
+///
+/// Once the free space falls below the threshold, the node is considered full: it stops quoting
+/// and accepting new paid records, while still serving GETs and replicating mutable records.
+/// Chunks only come back once the free space is above the threshold again.
+#[derive(Debug)]
+pub(crate) struct DiskSpaceMonitor {
+    dirs: Vec<PathBuf>,
+    min_free_space: u64,
+    available_space: AtomicU64,
+    is_full: AtomicBool,
+}
+
+impl DiskSpaceMonitor {
+    pub(crate) fn new(dirs: Vec<PathBuf>, min_free_space: u64) -> Self {
+        let monitor = Self {
+            dirs,
+            min_free_space,
+            available_space: AtomicU64::new(u64::MAX),
+            is_full: AtomicBool::new(false),
+        };
+        let _ = monitor.refresh();
+        monitor
+    }
+
+    /// Query the free space of the disks and update the full state.
+    /// Returns the free space in bytes, or `None` if it could not be queried.
+    pub(crate) fn refresh(&self) -> Option<u64> {
+        let available_space = self
+            .dirs
+            .iter()
+            .filter_map(|dir| match fs2::available_space(dir) {
+                Ok(available_space) => Some(available_space),
+                Err(err) => {
+                    warn!("Failed to get the free disk space of {dir:?}: {err:?}");
+                    None
+                }
+            })
+            .min()?;
+        self.update(available_space);
+        Some(available_space)
+    }
+
+    fn update(&self, available_space: u64) {
+        self.available_space
+            .store(available_space, Ordering::Relaxed);
+
+        let is_full = available_space < self.min_free_space;
+        let was_full = self.is_full.swap(is_full, Ordering::Relaxed);
+        if is_full && !was_full {
+            warn!(
+                "Free disk space {available_space} bytes is below {} bytes, no longer accepting new data",
+                self.min_free_space
+            );
+        } else if !is_full && was_full {
+            info!(
+                "Free disk space {available_space} bytes is back above the threshold, accepting new data"
+            );
+        }
+    }
+
+    /// Returns true if the node shall not accept new paid records.
+    pub(crate) fn is_full(&self) -> bool {
+        self.is_full.load(Ordering::Relaxed)
+    }
+
+    /// Free space in bytes, as of the last refresh.
+    pub(crate) fn available_space(&self) -> u64 {
+        self.available_space.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full_state_follows_threshold() {
+        let monitor = DiskSpaceMonitor::new(vec![std::env::temp_dir()], 100);
+
+        monitor.update(99);
+        assert!(monitor.is_full());
+        assert_eq!(monitor.available_space(), 99);
+
+        monitor.update(100);
+        assert!(!monitor.is_full());
+    }
+
+    #[test]
+    fn test_refresh_reads_free_space() {
+        let monitor = DiskSpaceMonitor::new(vec![std::env::temp_dir()], 0);
+        assert!(monitor.refresh().is_some());
+        assert!(!monitor.is_full());
+    }
+}
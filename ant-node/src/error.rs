@@ -6,7 +6,7 @@
 // KIND, either express or implied. Please review the Licences for the specific language governing
 // permissions and limitations relating to use of the SAFE Network Software.
 
-use ant_protocol::PrettyPrintRecordKey;
+use ant_protocol::{NetworkAddress, PrettyPrintRecordKey};
 use libp2p::PeerId;
 use thiserror::Error;
 
@@ -61,6 +61,13 @@ pub enum PutValidationError {
         error: ant_evm::payment_vault::error::Error,
     },
 
+    #[error("The record was removed as its payment was dropped by a chain reorganisation: {0:?}")]
+    UnpaidRecordRemoved(PrettyPrintRecordKey<'static>),
+
+    // ---------- Storage errors
+    #[error("The node is running out of disk space and does not accept new data")]
+    StorageFull,
+
     // ---------- Chunk errors
     #[error("Chunk is too large: {0} bytes, when max size is {1} bytes")]
     OversizedChunk(usize, usize),
@@ -75,6 +82,19 @@ pub enum PutValidationError {
     #[error("Scratchpad too big: {0}, max size is {SCRATCHPAD_MAX_SIZE}")]
     ScratchpadTooBig(usize),
 
+    #[error("Too many free updates of the record, further updates must be paid for: {0:?}")]
+    FreeUpdateRateLimited(PrettyPrintRecordKey<'static>),
+
+    // ---------- Temporary data errors
+    #[error("The lease of the temporary record has expired: {0:?}")]
+    RecordExpired(PrettyPrintRecordKey<'static>),
+
+    #[error("The expiry {expiry} of the temporary record {record_key:?} is beyond a lease period")]
+    InvalidRecordExpiry {
+        record_key: PrettyPrintRecordKey<'static>,
+        expiry: u64,
+    },
+
     // ---------- GraphEntry errors
     #[error("There are no GraphEntries in the record: {0:?}")]
     EmptyGraphEntry(PrettyPrintRecordKey<'static>),
@@ -109,6 +129,25 @@ pub enum PutValidationError {
     },
 }
 
+impl PutValidationError {
+    /// Whether the record itself is invalid, as opposed to the node failing to store it.
+    pub(crate) fn is_invalid_record(&self) -> bool {
+        matches!(
+            self,
+            Self::InvalidRecordHeader
+                | Self::InvalidRecord(_)
+                | Self::RecordKeyMismatch
+                | Self::UnexpectedRecordWithPayment(_)
+                | Self::OversizedChunk(..)
+                | Self::InvalidScratchpadSignature
+                | Self::ScratchpadTooBig(_)
+                | Self::InvalidRecordExpiry { .. }
+                | Self::EmptyGraphEntry(_)
+                | Self::InvalidPointerSignature
+        )
+    }
+}
+
 /// Internal node error.
 #[derive(Debug, Error)]
 #[allow(missing_docs)]
@@ -122,6 +161,9 @@ pub enum Error {
     #[error("Failed to obtain node's current port")]
     FailedToGetNodePort,
 
+    #[error("The record is not held by the node: {0:?}")]
+    RecordNotHeld(NetworkAddress),
+
     // ---------- Quote Errors
     #[error("The content of the payment quote is invalid")]
     InvalidQuoteContent,
@@ -0,0 +1,283 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Rate limiting of the free updates of existing pointers and scratchpads.
+//!
+//! Once a pointer or scratchpad has been paid for, its owner may update it without paying again.
+//! To stop a flood of such updates from hogging the node, the free updates are counted per record
+//! address and, when the peer sending them is known, per source peer. Over the limit, updates must
+//! be paid for until the window has passed, the node quoting for them again, or carry a proof of
+//! work of the configured difficulty, see [`ant_protocol::storage::update_work`].
+//!
+//! An update is only counted once fully validated, right before being stored, so that invalid or
+//! outdated updates, or copies of an update already stored, can't use up the free updates of others.
+
+use libp2p::{PeerId, kad::RecordKey};
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Max number of addresses and source peers tracked, to bound the memory used by the limiter.
+const MAX_TRACKED_ENTRIES: usize = 10_000;
+
+/// Limits applied to the free updates of existing pointers and scratchpads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FreeUpdateLimits {
+    /// The window over which the free updates are counted.
+    pub window: Duration,
+    /// Max number of free updates of the same address within the window, 0 for no limit.
+    pub max_per_address: usize,
+    /// Max number of free updates sent by the same peer within the window, 0 for no limit.
+    pub max_per_source: usize,
+    /// The difficulty of the proof of work accepted instead of a payment over the limits, 0 to
+    /// only accept payments.
+    pub work_difficulty: u8,
+}
+
+impl Default for FreeUpdateLimits {
+    fn default() -> Self {
+        Self {
+            window: Duration::from_secs(60),
+            max_per_address: 20,
+            max_per_source: 120,
+            work_difficulty: 20,
+        }
+    }
+}
+
+/// A free update of an existing pointer or scratchpad, sent by `source` if known.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct FreeUpdate {
+    pub(crate) source: Option<PeerId>,
+}
+
+/// Counts the free updates accepted within the window.
+#[derive(Debug)]
+pub(crate) struct FreeUpdateLimiter {
+    limits: FreeUpdateLimits,
+    by_address: Mutex<HashMap<RecordKey, VecDeque<Instant>>>,
+    by_source: Mutex<HashMap<PeerId, VecDeque<Instant>>>,
+}
+
+impl FreeUpdateLimiter {
+    pub(crate) fn new(limits: FreeUpdateLimits) -> Self {
+        info!("Free update limits: {limits:?}");
+        Self {
+            limits,
+            by_address: Mutex::new(HashMap::new()),
+            by_source: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The difficulty of the proof of work accepted instead of a payment, 0 if none is.
+    pub(crate) fn work_difficulty(&self) -> u8 {
+        self.limits.work_difficulty
+    }
+
+    /// Whether a free update of `key` sent by `source`, if known, is over the limits. The update
+    /// is not counted, see [`Self::try_acquire`].
+    pub(crate) fn is_limited(&self, key: &RecordKey, source: Option<PeerId>) -> bool {
+        self.is_limited_at(key, source, Instant::now())
+    }
+
+    /// Whether a free update of `key` sent by `source`, if known, is within the limits. The update
+    /// is counted when it is.
+    pub(crate) fn try_acquire(&self, key: &RecordKey, source: Option<PeerId>) -> bool {
+        self.try_acquire_at(key, source, Instant::now())
+    }
+
+    fn is_limited_at(&self, key: &RecordKey, source: Option<PeerId>, now: Instant) -> bool {
+        let (Ok(mut by_address), Ok(mut by_source)) =
+            (self.by_address.lock(), self.by_source.lock())
+        else {
+            return false;
+        };
+        !self.has_room(&mut by_address, &mut by_source, key, source, now)
+    }
+
+    fn try_acquire_at(&self, key: &RecordKey, source: Option<PeerId>, now: Instant) -> bool {
+        let (Ok(mut by_address), Ok(mut by_source)) =
+            (self.by_address.lock(), self.by_source.lock())
+        else {
+            return true;
+        };
+        if !self.has_room(&mut by_address, &mut by_source, key, source, now) {
+            return false;
+        }
+
+        record(
+            &mut by_address,
+            key.clone(),
+            self.limits.max_per_address,
+            self.limits.window,
+            now,
+        );
+        if let Some(source) = source {
+            record(
+                &mut by_source,
+                source,
+                self.limits.max_per_source,
+                self.limits.window,
+                now,
+            );
+        }
+        true
+    }
+
+    fn has_room(
+        &self,
+        by_address: &mut HashMap<RecordKey, VecDeque<Instant>>,
+        by_source: &mut HashMap<PeerId, VecDeque<Instant>>,
+        key: &RecordKey,
+        source: Option<PeerId>,
+        now: Instant,
+    ) -> bool {
+        let address_ok = has_room(
+            by_address,
+            key,
+            self.limits.max_per_address,
+            self.limits.window,
+            now,
+        );
+        let source_ok = source.is_none_or(|source| {
+            has_room(
+                by_source,
+                &source,
+                self.limits.max_per_source,
+                self.limits.window,
+                now,
+            )
+        });
+        address_ok && source_ok
+    }
+}
+
+/// Whether `entry` made less than `max` updates within `window`, dropping the older ones.
+fn has_room<K: Hash + Eq>(
+    updates: &mut HashMap<K, VecDeque<Instant>>,
+    entry: &K,
+    max: usize,
+    window: Duration,
+    now: Instant,
+) -> bool {
+    if max == 0 {
+        return true;
+    }
+    let Some(times) = updates.get_mut(entry) else {
+        return true;
+    };
+    while times
+        .front()
+        .is_some_and(|time| now.saturating_duration_since(*time) >= window)
+    {
+        let _ = times.pop_front();
+    }
+    times.len() < max
+}
+
+fn record<K: Hash + Eq + Clone>(
+    updates: &mut HashMap<K, VecDeque<Instant>>,
+    entry: K,
+    max: usize,
+    window: Duration,
+    now: Instant,
+) {
+    if max == 0 {
+        return;
+    }
+    if updates.len() >= MAX_TRACKED_ENTRIES && !updates.contains_key(&entry) {
+        updates.retain(|_, times| {
+            times
+                .back()
+                .is_some_and(|time| now.saturating_duration_since(*time) < window)
+        });
+        // Still full of entries updated within the window, forget the least recently updated one
+        if updates.len() >= MAX_TRACKED_ENTRIES
+            && let Some(oldest) = updates
+                .iter()
+                .min_by_key(|(_, times)| times.back().copied())
+                .map(|(entry, _)| entry.clone())
+        {
+            let _ = updates.remove(&oldest);
+        }
+    }
+    updates.entry(entry).or_default().push_back(now);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn free_updates_should_be_limited_per_address_and_per_source() {
+        let limiter = FreeUpdateLimiter::new(FreeUpdateLimits {
+            window: Duration::from_secs(60),
+            max_per_address: 2,
+            max_per_source: 3,
+            work_difficulty: 0,
+        });
+        let start = Instant::now();
+        let key_a = RecordKey::new(&[1]);
+        let key_b = RecordKey::new(&[2]);
+        let source = PeerId::random();
+
+        assert!(limiter.try_acquire_at(&key_a, Some(source), start));
+        assert!(limiter.try_acquire_at(&key_a, None, start));
+        // The address reached its limit, whoever sends the update
+        assert!(!limiter.try_acquire_at(&key_a, None, start));
+        assert!(!limiter.try_acquire_at(&key_a, Some(PeerId::random()), start));
+
+        assert!(limiter.try_acquire_at(&key_b, Some(source), start));
+        assert!(limiter.try_acquire_at(&key_b, Some(source), start));
+        // The source reached its limit, even on an address with room left
+        assert!(!limiter.try_acquire_at(&RecordKey::new(&[3]), Some(source), start));
+
+        // Both have room again once the window has passed
+        let later = start + Duration::from_secs(60);
+        assert!(limiter.try_acquire_at(&key_a, Some(source), later));
+    }
+
+    #[test]
+    fn checking_the_limits_should_not_count_the_update() {
+        let limiter = FreeUpdateLimiter::new(FreeUpdateLimits {
+            window: Duration::from_secs(60),
+            max_per_address: 1,
+            max_per_source: 1,
+            work_difficulty: 0,
+        });
+        let start = Instant::now();
+        let key = RecordKey::new(&[1]);
+        let source = PeerId::random();
+
+        for _ in 0..10 {
+            assert!(!limiter.is_limited_at(&key, Some(source), start));
+        }
+        assert!(limiter.try_acquire_at(&key, Some(source), start));
+        assert!(limiter.is_limited_at(&key, None, start));
+        assert!(limiter.is_limited_at(&RecordKey::new(&[2]), Some(source), start));
+        assert!(!limiter.is_limited_at(&RecordKey::new(&[2]), None, start));
+    }
+
+    #[test]
+    fn zero_should_disable_the_limit() {
+        let limiter = FreeUpdateLimiter::new(FreeUpdateLimits {
+            window: Duration::from_secs(60),
+            max_per_address: 0,
+            max_per_source: 0,
+            work_difficulty: 0,
+        });
+        let key = RecordKey::new(&[1]);
+        let source = PeerId::random();
+        for _ in 0..100 {
+            assert!(limiter.try_acquire(&key, Some(source)));
+        }
+    }
+}
@@ -0,0 +1,263 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! HTTP health endpoints, for load balancers and container orchestrators to probe the node:
+//!  - `/healthz`: liveness, the node's networking loop is responsive.
+//!  - `/readyz`: readiness, the node is listening, has enough peers in its routing table, is
+//!    reachable by its peers, directly or through relays, and is not hibernating. As the
+//!    reachability is only known once the first round of its check completes, a node is not ready
+//!    before then.
+//!
+//! Both respond with `200 OK` or `503 Service Unavailable`, along with a JSON report.
+
+use crate::{Reachability, ReachabilityStatus, RunningNode, SwarmLocalState};
+use hyper::{
+    Body, Method, Request, Response, Server, StatusCode,
+    service::{make_service_fn, service_fn},
+};
+use serde::Serialize;
+use std::{
+    convert::Infallible,
+    net::SocketAddr,
+    time::{Duration, Instant},
+};
+
+/// Time the networking loop has to report its state before the node is considered unresponsive.
+const LIVENESS_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Serialize)]
+struct HealthReport {
+    status: &'static str,
+    peer_id: String,
+    version: &'static str,
+    uptime_secs: u64,
+    hibernating: bool,
+    peers_in_routing_table: Option<usize>,
+    connected_peers: Option<usize>,
+    min_peers: usize,
+    reachability: Option<&'static str>,
+    relayed: Option<bool>,
+}
+
+pub(crate) fn run_health_server(addr: SocketAddr, running_node: RunningNode, min_peers: usize) {
+    let started_instant = Instant::now();
+
+    #[allow(clippy::let_underscore_future)]
+    let _ = tokio::spawn(async move {
+        let make_service = make_service_fn(move |_| {
+            let running_node = running_node.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |req| {
+                    let running_node = running_node.clone();
+                    async move {
+                        Ok::<_, Infallible>(
+                            respond(req, &running_node, started_instant, min_peers).await,
+                        )
+                    }
+                }))
+            }
+        });
+
+        let server = match Server::try_bind(&addr) {
+            Ok(builder) => builder.serve(make_service),
+            Err(err) => {
+                error!("Failed to bind the health server to {addr}: {err}");
+                return;
+            }
+        };
+        info!(
+            "Health server on http://{} Available endpoints: /healthz, /readyz",
+            server.local_addr()
+        );
+        if let Err(err) = server.await {
+            error!("Health server error: {err}");
+        }
+    });
+}
+
+async fn respond(
+    req: Request<Body>,
+    running_node: &RunningNode,
+    started_instant: Instant,
+    min_peers: usize,
+) -> Response<Body> {
+    if req.method() != Method::GET {
+        return plain_response(StatusCode::METHOD_NOT_ALLOWED, "Method not allowed");
+    }
+    let readiness = match req.uri().path() {
+        "/healthz" => false,
+        "/readyz" => true,
+        _ => return plain_response(StatusCode::NOT_FOUND, "Not found, try /healthz or /readyz"),
+    };
+
+    let state =
+        match tokio::time::timeout(LIVENESS_TIMEOUT, running_node.get_swarm_local_state()).await {
+            Ok(Ok(state)) => Some(state),
+            Ok(Err(err)) => {
+                warn!("Health check failed to get the swarm local state: {err}");
+                None
+            }
+            Err(_elapsed) => {
+                warn!("Health check timed out getting the swarm local state");
+                None
+            }
+        };
+    let reachability = if readiness && state.is_some() {
+        match tokio::time::timeout(LIVENESS_TIMEOUT, running_node.get_reachability()).await {
+            Ok(Ok(reachability)) => Some(reachability),
+            Ok(Err(err)) => {
+                warn!("Health check failed to get the reachability: {err}");
+                None
+            }
+            Err(_elapsed) => {
+                warn!("Health check timed out getting the reachability");
+                None
+            }
+        }
+    } else {
+        None
+    };
+    let hibernating = running_node.is_hibernating();
+
+    let healthy = match (&state, &reachability) {
+        (Some(state), Some(reachability)) if readiness => {
+            is_ready(state, reachability, hibernating, min_peers)
+        }
+        (Some(_), None) if readiness => false,
+        (Some(_), _) => true,
+        (None, _) => false,
+    };
+    let status = match (healthy, readiness) {
+        (true, true) => "ready",
+        (false, true) => "not_ready",
+        (true, false) => "alive",
+        (false, false) => "unresponsive",
+    };
+
+    let report = HealthReport {
+        status,
+        peer_id: running_node.peer_id().to_string(),
+        version: env!("CARGO_PKG_VERSION"),
+        uptime_secs: started_instant.elapsed().as_secs(),
+        hibernating,
+        peers_in_routing_table: state.as_ref().map(|state| state.peers_in_routing_table),
+        connected_peers: state.as_ref().map(|state| state.connected_peers.len()),
+        min_peers,
+        reachability: reachability
+            .as_ref()
+            .map(|reachability| match reachability.status {
+                ReachabilityStatus::NotChecked => "not_checked",
+                ReachabilityStatus::Reachable => "reachable",
+                ReachabilityStatus::Unreachable => "unreachable",
+            }),
+        relayed: reachability
+            .as_ref()
+            .map(|reachability| reachability.relayed),
+    };
+    let status_code = if healthy {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    match serde_json::to_string(&report) {
+        Ok(body) => {
+            let mut response = Response::new(Body::from(body));
+            *response.status_mut() = status_code;
+            let _ = response.headers_mut().insert(
+                hyper::header::CONTENT_TYPE,
+                hyper::header::HeaderValue::from_static("application/json"),
+            );
+            response
+        }
+        Err(err) => {
+            error!("Failed to serialize the health report: {err}");
+            plain_response(StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+        }
+    }
+}
+
+/// The node is ready to be sent traffic once it is listening, has enough peers in its routing
+/// table to take part in its close groups, can be reached by its peers, and is not hibernating.
+///
+/// A node reached through relays can't pass the check of the peers dialing it directly, so it is
+/// reachable as long as it advertises relayed addresses.
+fn is_ready(
+    state: &SwarmLocalState,
+    reachability: &Reachability,
+    hibernating: bool,
+    min_peers: usize,
+) -> bool {
+    let reachable = reachability.status == ReachabilityStatus::Reachable || reachability.relayed;
+    !hibernating
+        && reachable
+        && !state.listeners.is_empty()
+        && state.peers_in_routing_table >= min_peers
+}
+
+fn plain_response(status_code: StatusCode, body: &'static str) -> Response<Body> {
+    let mut response = Response::new(Body::from(body));
+    *response.status_mut() = status_code;
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libp2p::Multiaddr;
+
+    #[test]
+    fn test_readiness() {
+        let mut state = SwarmLocalState {
+            connected_peers: vec![],
+            peers_in_routing_table: 4,
+            listeners: vec![
+                "/ip4/127.0.0.1/udp/1200/quic-v1"
+                    .parse::<Multiaddr>()
+                    .expect("valid multiaddr"),
+            ],
+        };
+        let reachability = Reachability {
+            status: ReachabilityStatus::Reachable,
+            ..Default::default()
+        };
+        assert!(!is_ready(&state, &reachability, false, 5));
+
+        state.peers_in_routing_table = 5;
+        assert!(is_ready(&state, &reachability, false, 5));
+        assert!(!is_ready(&state, &reachability, true, 5));
+
+        state.listeners.clear();
+        assert!(!is_ready(&state, &reachability, false, 5));
+    }
+
+    #[test]
+    fn test_readiness_requires_reachability() {
+        let state = SwarmLocalState {
+            connected_peers: vec![],
+            peers_in_routing_table: 5,
+            listeners: vec![
+                "/ip4/127.0.0.1/udp/1200/quic-v1"
+                    .parse::<Multiaddr>()
+                    .expect("valid multiaddr"),
+            ],
+        };
+        let mut reachability = Reachability::default();
+        assert!(!is_ready(&state, &reachability, false, 5));
+
+        reachability.status = ReachabilityStatus::Unreachable;
+        assert!(!is_ready(&state, &reachability, false, 5));
+
+        reachability.relayed = true;
+        assert!(is_ready(&state, &reachability, false, 5));
+
+        reachability.relayed = false;
+        reachability.status = ReachabilityStatus::Reachable;
+        assert!(is_ready(&state, &reachability, false, 5));
+    }
+}
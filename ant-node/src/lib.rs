@@ -27,42 +27,61 @@
 #[macro_use]
 extern crate tracing;
 
+mod disk_space;
 mod error;
 mod event;
+mod free_updates;
+#[cfg(feature = "health-check")]
+mod health;
 mod log_markers;
 #[cfg(feature = "open-metrics")]
 mod metrics;
 mod networking;
 mod node;
+mod payment_cache;
+mod payment_confirmation;
 mod put_validation;
 #[cfg(feature = "extension-module")]
 mod python;
 mod quote;
 mod replication;
+pub mod rewards_ledger;
 #[allow(missing_docs)]
 pub mod spawn;
 #[allow(missing_docs)]
 pub mod utils;
+mod verification;
 
 pub use self::{
     error::{Error, PutValidationError},
     event::{NodeEvent, NodeEventsChannel, NodeEventsReceiver},
+    free_updates::FreeUpdateLimits,
     log_markers::Marker,
     networking::sort_peers_by_key,
     node::{NodeBuilder, PERIODIC_REPLICATION_INTERVAL_MAX_S},
+    verification::RecordsVerification,
 };
 pub use ant_bootstrap::{Bootstrap, BootstrapCacheStore, BootstrapConfig, InitialPeersConfig};
 
 use crate::error::Result;
 
 use crate::networking::Network;
-pub use crate::networking::SwarmLocalState;
+use crate::node::Node;
+pub use crate::networking::{
+    AddressFamily, BehaviourHealth, BucketChurn, ChurnStats, IdentifyHealth, KademliaHealth,
+    Reachability, ReachabilityStatus, RecordStoreStats, RequestKind, RequestLimits,
+    RequestResponseHealth, SwarmLocalState,
+};
 use ant_evm::RewardsAddress;
 use ant_protocol::{NetworkAddress, get_port_from_multiaddr};
 use libp2p::{Multiaddr, PeerId};
 use std::{
     collections::{BTreeMap, HashSet},
     path::PathBuf,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
 };
 use tokio::sync::watch;
 
@@ -71,10 +90,12 @@ use tokio::sync::watch;
 #[derive(Clone)]
 pub struct RunningNode {
     shutdown_sender: watch::Sender<bool>,
+    shutdown_complete_rx: watch::Receiver<bool>,
     network: Network,
     node_events_channel: NodeEventsChannel,
     root_dir_path: PathBuf,
     rewards_address: RewardsAddress,
+    hibernating: Arc<AtomicBool>,
 }
 
 impl RunningNode {
@@ -162,14 +183,99 @@ impl RunningNode {
         Ok(kbuckets.1)
     }
 
+    /// Returns the reputation scores of the peers reported with issues, lowest first.
+    /// Peers not listed have a neutral score of 0.
+    pub async fn get_peer_scores(&self) -> Result<Vec<(PeerId, i32)>> {
+        let scores = self.network.get_peer_scores().await?;
+        Ok(scores)
+    }
+
+    /// Returns the health of each of the networking behaviours, to diagnose a node that is
+    /// connected to the network but fails to take part in it.
+    pub async fn get_behaviour_health(&self) -> Result<BehaviourHealth> {
+        let health = self.network.get_behaviour_health().await?;
+        Ok(health)
+    }
+
+    /// Returns the outcome of the continuous reachability check of the node: whether enough peers
+    /// dialed it directly in the last round, over which IP version, and whether it is relayed.
+    pub async fn get_reachability(&self) -> Result<Reachability> {
+        let reachability = self.network.get_reachability().await?;
+        Ok(reachability)
+    }
+
+    /// Returns the churn of the routing table: the peers joining and leaving each of its buckets
+    /// over the last hour, and whether a mass churn event is ongoing.
+    pub async fn get_churn_stats(&self) -> Result<ChurnStats> {
+        let churn_stats = self.network.get_churn_stats().await?;
+        Ok(churn_stats)
+    }
+
+    /// Returns the statistics of the record store: the records per data type, their size on
+    /// disk, their distribution of distances from the node, and a random sample of
+    /// `sample_size` of their addresses.
+    pub async fn get_record_store_stats(&self, sample_size: usize) -> Result<RecordStoreStats> {
+        let stats = self.network.get_record_store_stats(sample_size).await?;
+        Ok(stats)
+    }
+
+    /// Re-check which peers of the current close group of the record at `address` hold it, and
+    /// push it to the ones missing it, without waiting for the periodic replication. Returns the
+    /// peers the record was pushed to. The record must be held by the node.
+    pub async fn replicate_record(&self, address: NetworkAddress) -> Result<Vec<PeerId>> {
+        Node::replicate_record_to_close_group(self.network.clone(), address).await
+    }
+
+    /// Randomly sample `sample_size` of the held records, re-read and re-hash them from disk,
+    /// and cross-check `cross_check_count` of the intact ones against the other holders of their
+    /// close group.
+    pub async fn verify_records(
+        &self,
+        sample_size: usize,
+        cross_check_count: usize,
+    ) -> Result<RecordsVerification> {
+        Node::verify_records(self.network.clone(), sample_size, cross_check_count).await
+    }
+
     /// Returns the node's reward address
     pub fn reward_address(&self) -> &RewardsAddress {
         &self.rewards_address
     }
 
+    /// Put the node into hibernation, keeping its identity and its presence in the routing tables
+    /// of its peers with minimal resource usage: new data is refused, replication and network
+    /// discovery are paused, and only the connections to the peers of its routing table are kept.
+    pub fn hibernate(&self) {
+        info!("Node is entering hibernation");
+        self.hibernating.store(true, Ordering::Relaxed);
+        self.network.set_hibernation(true);
+    }
+
+    /// Resume the node from hibernation.
+    pub fn resume(&self) {
+        info!("Node is resuming from hibernation");
+        self.hibernating.store(false, Ordering::Relaxed);
+        self.network.set_hibernation(false);
+        self.network.trigger_interval_replication();
+    }
+
+    /// Returns true while the node is hibernating.
+    pub fn is_hibernating(&self) -> bool {
+        self.hibernating.load(Ordering::Relaxed)
+    }
+
     /// Shutdown the network driver loop and the node (NetworkEvents) loop.
     pub fn shutdown(self) {
         // Send the shutdown signal to the swarm driver and node loop
         let _ = self.shutdown_sender.send(true);
     }
+
+    /// Shutdown the node gracefully: the in-flight record writes are completed and the state is
+    /// flushed to disk before the connections to the peers are closed. Returns once done, which
+    /// takes the max drain time of the node at most.
+    pub async fn graceful_shutdown(self) {
+        let mut shutdown_complete_rx = self.shutdown_complete_rx.clone();
+        let _ = self.shutdown_sender.send(true);
+        let _ = shutdown_complete_rx.wait_for(|complete| *complete).await;
+    }
 }
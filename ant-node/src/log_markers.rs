@@ -54,6 +54,11 @@ pub enum Marker<'a> {
     /// Valid paid to us and royalty paid pointer stored
     ValidPointerPutFromClient(&'a PrettyPrintRecordKey<'a>),
 
+    /// Payment verification was served from the local cache, no EVM RPC call made
+    PaymentVerificationCacheHit(&'a PrettyPrintRecordKey<'a>),
+    /// Payment verification was not cached and had to be checked on chain
+    PaymentVerificationCacheMiss(&'a PrettyPrintRecordKey<'a>),
+
     /// Record rejected
     RecordRejected(&'a PrettyPrintRecordKey<'a>, &'a PutValidationError),
 
@@ -30,10 +30,15 @@ pub(crate) struct NodeMetricsRecorder {
     put_record_err: Counter,
     put_record_err_v2: Family<PutRecordErr, Counter>,
 
+    /// payment verification
+    payment_verification_cache_hit: Counter,
+    payment_verification_cache_miss: Counter,
+
     /// replication
     replication_triggered: Counter,
     replication_keys_to_fetch: Histogram,
     pub(crate) network_wide_replication_holders: Histogram,
+    pub(crate) replication_interval: Gauge,
 
     // routing table
     peer_added_to_routing_table: Counter,
@@ -43,6 +48,10 @@ pub(crate) struct NodeMetricsRecorder {
     pub(crate) current_reward_wallet_balance: Gauge,
     pub(crate) _total_forwarded_rewards: Gauge,
 
+    // disk space
+    pub(crate) free_disk_space: Gauge,
+    pub(crate) storage_full: Gauge,
+
     // to track the uptime of the node.
     pub(crate) started_instant: Instant,
     pub(crate) uptime: Gauge,
@@ -90,6 +99,20 @@ impl NodeMetricsRecorder {
             put_record_err_v2.clone(),
         );
 
+        let payment_verification_cache_hit = Counter::default();
+        sub_registry.register(
+            "payment_verification_cache_hit",
+            "Number of payment verifications served from the local cache",
+            payment_verification_cache_hit.clone(),
+        );
+
+        let payment_verification_cache_miss = Counter::default();
+        sub_registry.register(
+            "payment_verification_cache_miss",
+            "Number of payment verifications that required an EVM RPC call",
+            payment_verification_cache_miss.clone(),
+        );
+
         let replication_triggered = Counter::default();
         sub_registry.register(
             "replication_triggered",
@@ -146,6 +169,27 @@ impl NodeMetricsRecorder {
             total_forwarded_rewards.clone(),
         );
 
+        let replication_interval = Gauge::default();
+        sub_registry.register(
+            "replication_interval",
+            "The interval in seconds between two periodic replications, adapted to the churn of the routing table",
+            replication_interval.clone(),
+        );
+
+        let free_disk_space = Gauge::default();
+        sub_registry.register(
+            "free_disk_space",
+            "The free space in bytes of the disk holding the node's data",
+            free_disk_space.clone(),
+        );
+
+        let storage_full = Gauge::default();
+        sub_registry.register(
+            "storage_full",
+            "Set to 1 when the node is running out of disk space and refuses new data",
+            storage_full.clone(),
+        );
+
         let uptime = Gauge::default();
         sub_registry.register(
             "uptime",
@@ -157,13 +201,18 @@ impl NodeMetricsRecorder {
             put_record_ok,
             put_record_err,
             put_record_err_v2,
+            payment_verification_cache_hit,
+            payment_verification_cache_miss,
             replication_triggered,
             replication_keys_to_fetch,
             network_wide_replication_holders,
+            replication_interval,
             peer_added_to_routing_table,
             peer_removed_from_routing_table,
             current_reward_wallet_balance,
             _total_forwarded_rewards: total_forwarded_rewards,
+            free_disk_space,
+            storage_full,
             started_instant: Instant::now(),
             uptime,
         }
@@ -200,6 +249,14 @@ impl NodeMetricsRecorder {
                 let _ = self.put_record_err.inc();
             }
 
+            Marker::PaymentVerificationCacheHit(_) => {
+                let _ = self.payment_verification_cache_hit.inc();
+            }
+
+            Marker::PaymentVerificationCacheMiss(_) => {
+                let _ = self.payment_verification_cache_miss.inc();
+            }
+
             Marker::IntervalReplicationTriggered => {
                 let _ = self.replication_triggered.inc();
             }
@@ -241,10 +298,15 @@ enum PutRecordErrorType {
     PaymentMadeToIncorrectDataType,
     PaymentQuoteOutOfRange,
     PaymentVerificationFailed,
+    UnpaidRecordRemoved,
+    StorageFull,
     OversizedChunk,
     OutdatedRecordCounter,
     InvalidScratchpadSignature,
     ScratchpadTooBig,
+    FreeUpdateRateLimited,
+    RecordExpired,
+    InvalidRecordExpiry,
     EmptyGraphEntry,
     InvalidPointerSignature,
     MerklePaymentVerificationFailed,
@@ -275,12 +337,17 @@ impl From<&crate::PutValidationError> for PutRecordErrorType {
             crate::PutValidationError::PaymentVerificationFailed { .. } => {
                 Self::PaymentVerificationFailed
             }
+            crate::PutValidationError::UnpaidRecordRemoved(_) => Self::UnpaidRecordRemoved,
+            crate::PutValidationError::StorageFull => Self::StorageFull,
             crate::PutValidationError::OversizedChunk(_, _) => Self::OversizedChunk,
             crate::PutValidationError::OutdatedRecordCounter { .. } => Self::OutdatedRecordCounter,
             crate::PutValidationError::InvalidScratchpadSignature => {
                 Self::InvalidScratchpadSignature
             }
             crate::PutValidationError::ScratchpadTooBig(_) => Self::ScratchpadTooBig,
+            crate::PutValidationError::FreeUpdateRateLimited(_) => Self::FreeUpdateRateLimited,
+            crate::PutValidationError::RecordExpired(_) => Self::RecordExpired,
+            crate::PutValidationError::InvalidRecordExpiry { .. } => Self::InvalidRecordExpiry,
             crate::PutValidationError::EmptyGraphEntry(_) => Self::EmptyGraphEntry,
             crate::PutValidationError::InvalidPointerSignature => Self::InvalidPointerSignature,
             crate::PutValidationError::MerklePaymentVerificationFailed { .. } => {
@@ -12,19 +12,17 @@ use crate::networking::{
     error::{NetworkError, Result},
     interface::{LocalSwarmCmd, NetworkSwarmCmd, TerminateNodeReason},
     log_markers::Marker,
+    peer_reputation::ReputationAction,
 };
 use ant_evm::PaymentQuote;
 use ant_protocol::{
-    NetworkAddress, PrettyPrintRecordKey,
+    NetworkAddress, PrettyPrintRecordKey, close_group,
     messages::{Cmd, Request},
     storage::{DataTypes, RecordHeader, RecordKind, ValidationType},
 };
 use libp2p::{
     Multiaddr, PeerId,
-    kad::{
-        KBucketDistance as Distance,
-        store::{Error as StoreError, RecordStore},
-    },
+    kad::store::{Error as StoreError, RecordStore},
 };
 use std::time::Instant;
 use std::{collections::BTreeMap, time::Duration};
@@ -39,6 +37,9 @@ const REPLICATION_TIMEOUT: Duration = Duration::from_secs(45);
 // Throttles replication to at most once every 30 seconds
 const MIN_REPLICATION_INTERVAL_S: Duration = Duration::from_secs(30);
 
+// How long a peer disconnected due to its low reputation score is not dialed.
+const REPUTATION_DISCONNECTION_DURATION: Duration = Duration::from_secs(5 * 60);
+
 impl SwarmDriver {
     pub(crate) fn handle_network_cmd(&mut self, cmd: NetworkSwarmCmd) -> Result<(), NetworkError> {
         let start = Instant::now();
@@ -77,10 +78,12 @@ impl SwarmDriver {
                 // `self` then handles the request and sends a response back again to itself.
                 if peer == *self.swarm.local_peer_id() {
                     trace!("Sending query request to self");
-                    if let Request::Query(query) = req {
+                    if let Some((query, trace_context)) = req.into_query() {
                         self.send_event(NetworkEvent::QueryRequestReceived {
                             query,
                             channel: MsgResponder::FromSelf(sender),
+                            trace_context,
+                            source: None,
                         });
                     } else {
                         // We should never receive a Replicate request from ourselves.
@@ -239,6 +242,17 @@ impl SwarmDriver {
                 let _ = sender.send(record);
             }
 
+            LocalSwarmCmd::ReadLocalRecordFromDisk { key, sender } => {
+                cmd_string = "ReadLocalRecordFromDisk";
+                let record = self
+                    .swarm
+                    .behaviour_mut()
+                    .kademlia
+                    .store_mut()
+                    .read_record_from_disk(&key);
+                let _ = sender.send(record);
+            }
+
             LocalSwarmCmd::PutLocalRecord {
                 record,
                 is_client_put,
@@ -291,14 +305,13 @@ impl SwarmDriver {
                     }
                     Err(StoreError::MaxRecords) => {
                         // In case the capacity reaches full, restrict replication_fetcher to
-                        // only fetch entries not farther than the current farthest record
-                        let farthest = self
-                            .swarm
-                            .behaviour_mut()
-                            .kademlia
-                            .store_mut()
-                            .get_farthest();
-                        self.replication_fetcher.set_farthest_on_full(farthest);
+                        // only fetch entries not farther than the current farthest record.
+                        // Otherwise the record exceeded the storage quota of its data type.
+                        let store = self.swarm.behaviour_mut().kademlia.store_mut();
+                        if store.is_full() {
+                            let farthest = store.get_farthest();
+                            self.replication_fetcher.set_farthest_on_full(farthest);
+                        }
                     }
                     Err(_) => {
                         // Nothing special to do for these errors,
@@ -374,6 +387,11 @@ impl SwarmDriver {
                     .store_mut()
                     .pruning_indexing_cache(&key);
             }
+            LocalSwarmCmd::RemoveUnpaidLocalRecord { key } => {
+                info!("Removing Record locally as its payment is no longer valid, for {key:?}");
+                cmd_string = "RemoveUnpaidLocalRecord";
+                self.swarm.behaviour_mut().kademlia.store_mut().remove(&key);
+            }
             LocalSwarmCmd::RecordStoreHasKey { key, sender } => {
                 cmd_string = "RecordStoreHasKey";
                 let has_key = self
@@ -516,11 +534,9 @@ impl SwarmDriver {
             }
             LocalSwarmCmd::TriggerIrrelevantRecordCleanup => {
                 cmd_string = "TriggerIrrelevantRecordCleanup";
-                self.swarm
-                    .behaviour_mut()
-                    .kademlia
-                    .store_mut()
-                    .cleanup_irrelevant_records();
+                let store = self.swarm.behaviour_mut().kademlia.store_mut();
+                store.remove_expired_records();
+                store.cleanup_irrelevant_records();
             }
             LocalSwarmCmd::NotifyPeerScores { peer_scores } => {
                 cmd_string = "NotifyPeerScores";
@@ -550,6 +566,63 @@ impl SwarmDriver {
                     self.update_on_peer_removal(*dead_peer.node.key.preimage());
                 }
             }
+            LocalSwarmCmd::GetPeerScores { sender } => {
+                cmd_string = "GetPeerScores";
+                let _ = sender.send(self.peer_reputation.scores());
+            }
+            LocalSwarmCmd::GetBehaviourHealth { sender } => {
+                cmd_string = "GetBehaviourHealth";
+                let mut health = self.behaviour_health.clone();
+
+                let kademlia = &mut self.swarm.behaviour_mut().kademlia;
+                health.kademlia.peers_in_routing_table = self.peers_in_rt;
+                health.kademlia.ongoing_queries = kademlia.iter_queries().count();
+                health.kademlia.non_empty_kbuckets = kademlia
+                    .kbuckets()
+                    .filter(|kbucket| kbucket.num_entries() > 0)
+                    .count();
+                health.request_response.inflight_outbound_requests = self.pending_requests.len();
+
+                let _ = sender.send(health);
+            }
+            LocalSwarmCmd::GetChurnStats { sender } => {
+                cmd_string = "GetChurnStats";
+                let _ = sender.send(self.refresh_peer_churn());
+            }
+            LocalSwarmCmd::GetReachability { sender } => {
+                cmd_string = "GetReachability";
+                let _ = sender.send(self.reachability_checker.reachability().clone());
+            }
+            LocalSwarmCmd::GetRecordStoreStats {
+                sample_size,
+                sender,
+            } => {
+                cmd_string = "GetRecordStoreStats";
+                let store = self.swarm.behaviour_mut().kademlia.store_mut();
+                let mut stats = store.stats(sample_size);
+                let file_paths = store.record_file_paths();
+
+                // Summing the file sizes hits the disk, keep it off the swarm loop.
+                #[allow(clippy::let_underscore_future)]
+                let _ = tokio::task::spawn_blocking(move || {
+                    stats.total_bytes = file_paths
+                        .iter()
+                        .filter_map(|path| std::fs::metadata(path).ok())
+                        .map(|metadata| metadata.len())
+                        .sum();
+                    let _ = sender.send(stats);
+                });
+            }
+            LocalSwarmCmd::SetHibernation { hibernating } => {
+                cmd_string = "SetHibernation";
+                if self.hibernating != hibernating {
+                    info!("Hibernation set to {hibernating}");
+                    self.hibernating = hibernating;
+                    if hibernating {
+                        self.prune_connections();
+                    }
+                }
+            }
         }
 
         self.log_handling(cmd_string.to_string(), start.elapsed());
@@ -563,6 +636,20 @@ impl SwarmDriver {
 
     pub(crate) fn record_node_issue(&mut self, peer_id: PeerId, issue: NodeIssue) {
         info!("Peer {peer_id:?} is reported as having issue {issue:?}");
+
+        // To avoid being too sensitive, only consider as a new issue
+        // when after certain while since the last one
+        let is_new_issue = self
+            .bad_nodes
+            .get(&peer_id)
+            .and_then(|(issue_vec, _is_bad)| issue_vec.last())
+            .is_none_or(|(_issue, timestamp)| timestamp.elapsed().as_secs() > 10);
+
+        // A burst of reports of the same misbehaviour only lowers the score once
+        if is_new_issue && let Some(action) = self.peer_reputation.penalise(peer_id, &issue) {
+            self.apply_reputation_action(peer_id, action);
+        }
+
         let (issue_vec, is_bad) = self.bad_nodes.entry(peer_id).or_default();
         let mut new_bad_behaviour = None;
         let mut eviction_issue: Option<NodeIssue> = None;
@@ -578,14 +665,6 @@ impl SwarmDriver {
                 let _ = issue_vec.remove(0);
             }
 
-            // To avoid being too sensitive, only consider as a new issue
-            // when after certain while since the last one
-            let is_new_issue = if let Some((_issue, timestamp)) = issue_vec.last() {
-                timestamp.elapsed().as_secs() > 10
-            } else {
-                true
-            };
-
             if is_new_issue {
                 issue_vec.push((issue, Instant::now()));
             } else {
@@ -708,6 +787,22 @@ impl SwarmDriver {
         }
     }
 
+    /// Demote or disconnect a peer whose reputation score dropped too low.
+    fn apply_reputation_action(&mut self, peer_id: PeerId, action: ReputationAction) {
+        warn!("Peer {peer_id:?} reputation score dropped too low, applying {action:?}");
+        if let Some(dead_peer) = self.swarm.behaviour_mut().kademlia.remove_peer(&peer_id) {
+            self.update_on_peer_removal(*dead_peer.node.key.preimage());
+        }
+
+        if action == ReputationAction::Disconnect {
+            self.swarm
+                .behaviour_mut()
+                .do_not_disturb
+                .block_peer(peer_id, REPUTATION_DISCONNECTION_DURATION);
+            let _ = self.swarm.disconnect_peer_id(peer_id);
+        }
+    }
+
     fn verify_peer_quote(&mut self, peer_id: PeerId, quote: PaymentQuote) {
         if let Some(history_quote) = self.quotes_history.get(&peer_id) {
             if !history_quote.historical_verify(&quote) {
@@ -727,6 +822,10 @@ impl SwarmDriver {
     }
 
     fn try_interval_replication(&mut self) -> Result<()> {
+        if self.hibernating {
+            debug!("Skipping replication as hibernating");
+            return Ok(());
+        }
         // Add a last_replication field to track the last time replication was performed
         if let Some(last_replication) = self.last_replication
             && last_replication.elapsed() < MIN_REPLICATION_INTERVAL_S
@@ -835,7 +934,8 @@ impl SwarmDriver {
             .store_mut()
             .get_responsible_distance_range()
         {
-            let peers_in_range = get_peers_in_range(&closest_k_peers, target, responsible_range);
+            let peers_in_range =
+                close_group::peers_in_range(&closest_k_peers, target, responsible_range);
 
             if peers_in_range.len() >= expected_candidates {
                 self.record_metrics(Marker::ReplicateCandidatesObtained {
@@ -860,21 +960,3 @@ impl SwarmDriver {
         Ok(closest_k_peers)
     }
 }
-
-/// Returns the nodes that within the defined distance.
-fn get_peers_in_range(
-    peers: &[(PeerId, Addresses)],
-    address: &NetworkAddress,
-    range: Distance,
-) -> Vec<(PeerId, Addresses)> {
-    peers
-        .iter()
-        .filter_map(|(peer_id, addresses)| {
-            if address.distance(&NetworkAddress::from(*peer_id)) <= range {
-                Some((*peer_id, addresses.clone()))
-            } else {
-                None
-            }
-        })
-        .collect()
-}
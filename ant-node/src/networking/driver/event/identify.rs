@@ -11,7 +11,7 @@ use crate::networking::{
     network::connection_action_logging,
     relay_manager::{RelayManager, is_a_relayed_peer},
 };
-use ant_protocol::version::IDENTIFY_PROTOCOL_STR;
+use ant_protocol::version::NetworkProtocol;
 use ant_protocol::version_gate::{
     PeerType, VersionCheckResult, check_peer_version, get_min_node_version,
 };
@@ -46,6 +46,7 @@ impl SwarmDriver {
                     &connection_id,
                     "Identify::Received",
                 );
+                self.behaviour_health.identify.received += 1;
 
                 self.handle_identify_received(peer_id, info, connection_id);
                 trace!("SwarmEvent handled in {:?}: identify", start.elapsed());
@@ -80,6 +81,7 @@ impl SwarmDriver {
                     &identify_event.connection_id(),
                     "Identify::Error",
                 );
+                self.behaviour_health.identify.errors += 1;
                 warn!("identify: {identify_event:?}")
             }
         }
@@ -104,6 +106,13 @@ impl SwarmDriver {
             return;
         }
 
+        if self.peer_reputation.is_demoted(&peer_id) {
+            debug!(
+                "identify: not adding {peer_id:?} to the RT as its reputation score is too low."
+            );
+            return;
+        }
+
         // If the peer dials us with a different addr, we would add it to our RT via update_pre_existing_peer
         let Some((_, addr_fom_connection, _)) = self.live_connected_peers.get(&connection_id)
         else {
@@ -113,17 +122,12 @@ impl SwarmDriver {
             return;
         };
 
-        let our_identify_protocol = IDENTIFY_PROTOCOL_STR.read().expect("IDENTIFY_PROTOCOL_STR has been locked to write. A call to set_network_id performed. This should not happen.").to_string();
-
-        if info.protocol_version != our_identify_protocol {
-            warn!(
-                "identify: {peer_id:?} does not have the same protocol. Our IDENTIFY_PROTOCOL_STR: {our_identify_protocol:?}. Their protocol version: {:?}",
-                info.protocol_version
-            );
+        if let Err(mismatch) = NetworkProtocol::current().negotiate(&info.protocol_version) {
+            warn!("identify: {peer_id:?} does not have the same protocol. {mismatch}");
 
             self.send_event(NetworkEvent::PeerWithUnsupportedProtocol {
-                our_protocol: our_identify_protocol,
-                their_protocol: info.protocol_version,
+                our_protocol: mismatch.ours,
+                their_protocol: mismatch.theirs,
             });
             // Block the peer from any further communication.
             let _ = self.swarm.behaviour_mut().blocklist.block_peer(peer_id);
@@ -532,7 +536,8 @@ fn does_the_peer_support_dnd(info: &Info) -> bool {
     false
 }
 
-/// Craft valid multiaddr like /ip4/68.183.39.80/udp/31055/quic-v1
+/// Craft valid multiaddr like /ip4/68.183.39.80/udp/31055/quic-v1, or /ip4/68.183.39.80/tcp/31055
+/// if the peer is connected over the TCP fallback.
 /// RelayManager::craft_relay_address for relayed addr. This is for non-relayed addr.
 fn craft_valid_multiaddr_without_p2p(addr: &Multiaddr) -> Option<Multiaddr> {
     let mut new_multiaddr = Multiaddr::empty();
@@ -543,8 +548,12 @@ fn craft_valid_multiaddr_without_p2p(addr: &Multiaddr) -> Option<Multiaddr> {
     let port = multiaddr_get_port(addr)?;
 
     new_multiaddr.push(Protocol::Ip4(ip));
-    new_multiaddr.push(Protocol::Udp(port));
-    new_multiaddr.push(Protocol::QuicV1);
+    if addr.iter().any(|p| matches!(p, Protocol::Tcp(_))) {
+        new_multiaddr.push(Protocol::Tcp(port));
+    } else {
+        new_multiaddr.push(Protocol::Udp(port));
+        new_multiaddr.push(Protocol::QuicV1);
+    }
 
     Some(new_multiaddr)
 }
@@ -21,6 +21,9 @@ use crate::networking::{
 /// long-running requests are not cut off by the node closing the connection (ApplicationClosed).
 /// This deadline is reset each time a new request is received on the connection.
 const CONNECTION_PRUNE_AFTER_SECS: u64 = 120;
+/// While hibernating, a connection to a peer not in our routing table is pruned once no request
+/// has been received on it for this long, so that clients mid-fetch are not cut off.
+const HIBERNATION_PRUNE_IDLE_AFTER_SECS: u64 = 30;
 use ant_protocol::messages::ConnectionInfo;
 use custom_debug::Debug as CustomDebug;
 use libp2p::kad::K_VALUE;
@@ -72,6 +75,7 @@ pub(crate) enum NodeEvent {
     Identify(Box<libp2p::identify::Event>),
     RelayClient(Box<libp2p::relay::client::Event>),
     RelayServer(Box<libp2p::relay::Event>),
+    Autonat(Box<libp2p::autonat::Event>),
     DoNotDisturb(super::behaviour::do_not_disturb::DoNotDisturbEvent),
     Void(void::Void),
 }
@@ -110,6 +114,12 @@ impl From<libp2p::relay::Event> for NodeEvent {
     }
 }
 
+impl From<libp2p::autonat::Event> for NodeEvent {
+    fn from(event: libp2p::autonat::Event) -> Self {
+        NodeEvent::Autonat(Box::new(event))
+    }
+}
+
 impl From<super::behaviour::do_not_disturb::DoNotDisturbEvent> for NodeEvent {
     fn from(event: super::behaviour::do_not_disturb::DoNotDisturbEvent) -> Self {
         NodeEvent::DoNotDisturb(event)
@@ -172,6 +182,10 @@ impl SwarmDriver {
             self.self_peer_id,
             distance.ilog2()
         );
+        if let Some(ilog2) = distance.ilog2() {
+            self.network_discovery.notify_peer_joined(ilog2);
+            let _ = self.refresh_peer_churn();
+        }
 
         #[cfg(feature = "loud")]
         println!(
@@ -214,6 +228,10 @@ impl SwarmDriver {
             self.peers_in_rt,
             distance.ilog2()
         );
+        if let Some(ilog2) = distance.ilog2() {
+            self.network_discovery.notify_peer_left(ilog2);
+            let _ = self.refresh_peer_churn();
+        }
 
         self.send_event(NetworkEvent::PeerRemoved(removed_peer, self.peers_in_rt));
 
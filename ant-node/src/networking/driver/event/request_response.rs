@@ -10,7 +10,7 @@ use super::{CONNECTION_PRUNE_AFTER_SECS, SwarmDriver};
 use crate::networking::driver::event::MsgResponder;
 use crate::networking::interface::NetworkSwarmCmd;
 use crate::networking::network::connection_action_logging;
-use crate::networking::{NetworkError, NetworkEvent, log_markers::Marker};
+use crate::networking::{NetworkError, NetworkEvent, NodeIssue, log_markers::Marker};
 use ant_protocol::messages::ConnectionInfo;
 use ant_protocol::{
     NetworkAddress,
@@ -67,7 +67,7 @@ impl SwarmDriver {
                                 "Request::Cmd::PeerConsideredAsBad"
                             }
                         },
-                        Request::Query(query) => match query {
+                        Request::Query(query) | Request::TracedQuery { query, .. } => match query {
                             ant_protocol::messages::Query::PutRecord { .. } => {
                                 "Request::Query::PutRecord"
                             }
@@ -92,6 +92,15 @@ impl SwarmDriver {
                             ant_protocol::messages::Query::GetMerkleCandidateQuote { .. } => {
                                 "Request::Query::GetMerkleCandidateQuote"
                             }
+                            ant_protocol::messages::Query::GetRecordHeader(_) => {
+                                "Request::Query::GetRecordHeader"
+                            }
+                            ant_protocol::messages::Query::GetChunkRange { .. } => {
+                                "Request::Query::GetChunkRange"
+                            }
+                            ant_protocol::messages::Query::GetStorageStatement { .. } => {
+                                "Request::Query::GetStorageStatement"
+                            }
                             #[cfg(feature = "developer")]
                             ant_protocol::messages::Query::DevGetClosestPeersFromNetwork {
                                 ..
@@ -120,7 +129,11 @@ impl SwarmDriver {
                                 channel: MsgResponder::FromPeer(channel),
                             });
 
-                            self.add_keys_to_replication_fetcher(holder, keys, false)?;
+                            if self.hibernating {
+                                debug!("Ignoring replicated keys from {holder:?} as hibernating");
+                            } else {
+                                self.add_keys_to_replication_fetcher(holder, keys, false)?;
+                            }
                         }
                         Request::Cmd(ant_protocol::messages::Cmd::FreshReplicate {
                             holder,
@@ -135,7 +148,16 @@ impl SwarmDriver {
                                 channel: MsgResponder::FromPeer(channel),
                             });
 
-                            self.send_event(NetworkEvent::FreshReplicateToFetch { holder, keys });
+                            if self.hibernating {
+                                debug!(
+                                    "Ignoring fresh replicated keys from {holder:?} as hibernating"
+                                );
+                            } else {
+                                self.send_event(NetworkEvent::FreshReplicateToFetch {
+                                    holder,
+                                    keys,
+                                });
+                            }
                         }
                         Request::Cmd(ant_protocol::messages::Cmd::PeerConsideredAsBad {
                             detected_by,
@@ -177,8 +199,19 @@ impl SwarmDriver {
                             self.send_event(NetworkEvent::QueryRequestReceived {
                                 query,
                                 channel: MsgResponder::FromPeer(channel),
+                                trace_context: None,
+                                source: Some(peer),
                             })
                         }
+                        Request::TracedQuery {
+                            query,
+                            trace_context,
+                        } => self.send_event(NetworkEvent::QueryRequestReceived {
+                            query,
+                            channel: MsgResponder::FromPeer(channel),
+                            trace_context: Some(trace_context),
+                            source: Some(peer),
+                        }),
                     }
                 }
                 Message::Response {
@@ -234,6 +267,24 @@ impl SwarmDriver {
                                     result_to_str(result)
                                 )
                             }
+                            ant_protocol::messages::QueryResponse::GetRecordHeader(result) => {
+                                format!(
+                                    "Response::Query::GetRecordHeader::{}",
+                                    result_to_str(result)
+                                )
+                            }
+                            ant_protocol::messages::QueryResponse::GetChunkRange(result) => {
+                                format!(
+                                    "Response::Query::GetChunkRange::{}",
+                                    result_to_str(result)
+                                )
+                            }
+                            ant_protocol::messages::QueryResponse::GetStorageStatement(result) => {
+                                format!(
+                                    "Response::Query::GetStorageStatement::{}",
+                                    result_to_str(result)
+                                )
+                            }
                             #[cfg(feature = "developer")]
                             ant_protocol::messages::QueryResponse::DevGetClosestPeersFromNetwork {
                                 ..
@@ -299,6 +350,16 @@ impl SwarmDriver {
                     &connection_id,
                     "RequestResponse::OutboundFailure",
                 );
+                self.behaviour_health.request_response.outbound_failures += 1;
+                match error {
+                    request_response::OutboundFailure::Timeout => {
+                        self.record_node_issue(peer, NodeIssue::RequestTimeout);
+                    }
+                    request_response::OutboundFailure::UnsupportedProtocols => {
+                        self.record_node_issue(peer, NodeIssue::ProtocolViolation);
+                    }
+                    _ => {}
+                }
                 if let Some(sender) = self.pending_requests.remove(&request_id) {
                     match sender {
                         Some(sender) => {
@@ -333,6 +394,7 @@ impl SwarmDriver {
                     &connection_id,
                     "RequestResponse::InboundFailure",
                 );
+                self.behaviour_health.request_response.inbound_failures += 1;
                 warn!(
                     "RequestResponse: InboundFailure for request_id: {request_id:?} and peer: {peer:?}, with error: {error:?}"
                 );
@@ -10,7 +10,7 @@ use super::SwarmDriver;
 use crate::networking::{
     error::{dial_error_to_str, listen_error_to_str},
     interface::TerminateNodeReason,
-    NetworkEvent, NodeIssue, Result,
+    multiaddr_get_ip, multiaddr_is_global, NetworkEvent, NodeIssue, Result,
 };
 use itertools::Itertools;
 #[cfg(feature = "open-metrics")]
@@ -27,7 +27,7 @@ use libp2p::{
 use std::time::Instant;
 use tokio::time::Duration;
 
-use super::{CONNECTION_PRUNE_AFTER_SECS, NodeEvent};
+use super::{CONNECTION_PRUNE_AFTER_SECS, HIBERNATION_PRUNE_IDLE_AFTER_SECS, NodeEvent};
 
 impl SwarmDriver {
     /// Handle `SwarmEvents`
@@ -157,6 +157,16 @@ impl SwarmDriver {
                     _ => {}
                 }
             }
+            SwarmEvent::Behaviour(NodeEvent::Autonat(event)) => {
+                event_string = "autonat_event";
+                match *event {
+                    libp2p::autonat::Event::StatusChanged { old, new } => {
+                        info!("AutoNAT status changed from {old:?} to {new:?}");
+                        self.on_nat_status_changed(&new);
+                    }
+                    event => debug!("AutoNAT event: {event:?}"),
+                }
+            }
             SwarmEvent::Behaviour(NodeEvent::Identify(event)) => {
                 // Record the Identify event for metrics if the feature is enabled.
                 #[cfg(feature = "open-metrics")]
@@ -263,6 +273,17 @@ impl SwarmDriver {
                     external_address_manager.on_established_incoming_connection(local_addr.clone());
                 }
 
+                if let ConnectedPoint::Listener { send_back_addr, .. } = &endpoint {
+                    self.reachability_checker
+                        .on_incoming_connection(peer_id, send_back_addr);
+                    #[cfg(feature = "open-metrics")]
+                    if let Some(metrics_recorder) = &self.metrics_recorder {
+                        let _ = metrics_recorder
+                            .reachability_check_progress
+                            .set(self.reachability_checker.reachability().progress as i64);
+                    }
+                }
+
                 #[cfg(feature = "open-metrics")]
                 if let Some(relay_manager) = self.relay_manager.as_mut() {
                     relay_manager.on_connection_established(&peer_id, &connection_id);
@@ -558,6 +579,18 @@ impl SwarmDriver {
                 event_string = "NewExternalAddrCandidate";
                 debug!("New external address candidate: {address:?}");
                 if let Some(external_address_manager) = self.external_address_manager.as_mut() {
+                    // A peer observing the node at another IP address hints it may have changed
+                    if let Some(current_ip_address) = external_address_manager.current_ip_address()
+                        && multiaddr_is_global(&address)
+                        && multiaddr_get_ip(&address)
+                            .is_some_and(|ip_address| ip_address != current_ip_address)
+                    {
+                        self.reachability_checker.start_probe();
+                        self.swarm
+                            .behaviour_mut()
+                            .autonat
+                            .probe_address(address.clone());
+                    }
                     external_address_manager
                         .add_external_address_candidate(address, &mut self.swarm);
                 }
@@ -608,13 +641,30 @@ impl SwarmDriver {
         if Instant::now() < self.last_connection_pruning_time + Duration::from_secs(30) {
             return;
         }
+        self.prune_connections();
+    }
+
+    // Remove the connections to the peers not in the RT, once outdated.
+    // While hibernating, they are removed as soon as they have been idle for a short while.
+    pub(crate) fn prune_connections(&mut self) {
         self.last_connection_pruning_time = Instant::now();
 
+        // The timeout of a connection is set `CONNECTION_PRUNE_AFTER_SECS` after the last request
+        // received on it, bring it forward while hibernating.
+        let prune_time = if self.hibernating {
+            Instant::now()
+                + Duration::from_secs(
+                    CONNECTION_PRUNE_AFTER_SECS - HIBERNATION_PRUNE_IDLE_AFTER_SECS,
+                )
+        } else {
+            Instant::now()
+        };
+
         let mut removed_conns = 0;
         self.live_connected_peers.retain(|connection_id, (peer_id, _addr, timeout_time)| {
 
             // skip if timeout isn't reached yet
-            if Instant::now() < *timeout_time {
+            if prune_time < *timeout_time {
                 return true; // retain peer
             }
 
@@ -21,21 +21,29 @@ use crate::networking::driver::network_wide_replication::NetworkWideReplication;
 #[cfg(feature = "open-metrics")]
 use crate::networking::metrics::NetworkMetricsRecorder;
 use crate::networking::{
-    Addresses, CLOSE_GROUP_SIZE, NodeIssue, NodeRecordStore, circular_vec::CircularVec,
-    driver::kad::U256, error::Result, external_address::ExternalAddressManager,
-    log_markers::Marker, relay_manager::RelayManager, replication_fetcher::ReplicationFetcher,
+    Addresses, BehaviourHealth, CLOSE_GROUP_SIZE, NodeIssue, NodeRecordStore,
+    circular_vec::CircularVec,
+    error::Result,
+    external_address::ExternalAddressManager,
+    log_markers::Marker,
+    network::relay_server_behaviour,
+    peer_reputation::{PEER_REPUTATION_DECAY_INTERVAL, PeerReputation},
+    reachability::{REACHABILITY_POLL_INTERVAL, ReachabilityChecker},
+    relay_manager::{RelayManager, is_a_relayed_peer},
+    replication_fetcher::ReplicationFetcher,
 };
 use ant_bootstrap::bootstrap::Bootstrap;
 use ant_evm::PaymentQuote;
 use ant_protocol::messages::ConnectionInfo;
 use ant_protocol::{
-    NetworkAddress,
+    NetworkAddress, close_group,
     messages::{Request, Response},
 };
 use futures::StreamExt;
 use libp2p::{
     Multiaddr, PeerId,
-    kad::{self, K_VALUE, KBucketDistance as Distance, QueryId},
+    autonat::NatStatus,
+    kad::{self, K_VALUE, QueryId},
     request_response::OutboundRequestId,
     swarm::{
         ConnectionId, Swarm,
@@ -52,7 +60,7 @@ use tokio::sync::{mpsc, oneshot, watch};
 use tokio::time::{Duration, interval};
 use tracing::warn;
 
-use super::interface::{LocalSwarmCmd, NetworkEvent, NetworkSwarmCmd};
+use super::interface::{BackgroundSwarmCmd, LocalSwarmCmd, NetworkEvent, NetworkSwarmCmd};
 
 /// 10 is the max number of issues per node we track to avoid mem leaks
 /// The boolean flag to indicate whether the node is considered as bad or not
@@ -77,6 +85,12 @@ pub(crate) const NETWORK_WIDE_REPLICATION_INTERVAL: Duration = Duration::from_se
 /// Maximum number of peers to keep in the blocklist before evicting oldest entries
 pub(crate) const BLOCKLIST_CACHE_SIZE: usize = 1000;
 
+/// Maximum time spent draining the node on shutdown, if not configured.
+pub(crate) const DEFAULT_MAX_DRAIN_TIME: Duration = Duration::from_secs(10);
+
+/// Interval at which the completion of the in-flight record writes is checked while draining.
+const DRAIN_CHECK_INTERVAL: Duration = Duration::from_millis(50);
+
 /// The ways in which the Get Closest queries are used.
 pub(crate) enum PendingGetClosestType {
     /// The network discovery method is present at the networking layer
@@ -106,6 +120,7 @@ pub(super) struct NodeBehaviour {
     pub(super) upnp: Toggle<libp2p::upnp::tokio::Behaviour>,
     pub(super) relay_client: libp2p::relay::client::Behaviour,
     pub(super) relay_server: Toggle<libp2p::relay::Behaviour>,
+    pub(super) autonat: libp2p::autonat::Behaviour,
     pub(super) kademlia: kad::Behaviour<NodeRecordStore>,
     pub(super) request_response: request_response::cbor::Behaviour<Request, Response>,
 }
@@ -136,6 +151,8 @@ pub(crate) struct SwarmDriver {
     pub(crate) local_cmd_sender: mpsc::Sender<LocalSwarmCmd>,
     pub(crate) local_cmd_receiver: mpsc::Receiver<LocalSwarmCmd>,
     pub(crate) network_cmd_receiver: mpsc::Receiver<NetworkSwarmCmd>,
+    pub(crate) background_cmd_sender: mpsc::Sender<BackgroundSwarmCmd>,
+    pub(crate) background_cmd_receiver: mpsc::Receiver<BackgroundSwarmCmd>,
     pub(crate) event_sender: mpsc::Sender<NetworkEvent>, // Use `self.send_event()` to send a NetworkEvent.
 
     /// Trackers for underlying behaviour related events
@@ -170,6 +187,24 @@ pub(crate) struct SwarmDriver {
     pub(crate) peers_version: HashMap<PeerId, String>,
     /// FIFO cache to track blocked peers, allowing us to unblock the oldest when limit is reached
     pub(crate) blocklist_cache: CircularVec<PeerId>,
+    /// Reputation scores of the peers reported with issues
+    pub(crate) peer_reputation: PeerReputation,
+    /// Failure counts of the behaviours, the rest of their health is read from the swarm on demand
+    pub(crate) behaviour_health: BehaviourHealth,
+    /// Counts the peers dialing the node directly, to check it is reachable
+    pub(crate) reachability_checker: ReachabilityChecker,
+    /// Switch to relay mode when the peers fail to dial the node back, and back to direct mode
+    /// once they succeed again
+    pub(crate) relay_fallback: bool,
+    /// Whether the node is in relay mode because of the relay fallback, rather than configured so
+    pub(crate) in_relay_fallback: bool,
+    /// While hibernating, replication and network discovery are paused and the idle connections
+    /// to the peers outside the routing table are pruned.
+    pub(crate) hibernating: bool,
+    /// Maximum time spent draining the node on shutdown
+    pub(crate) max_drain_time: Duration,
+    /// Set to true once the node has been drained on shutdown
+    pub(crate) shutdown_complete_tx: watch::Sender<bool>,
 }
 
 impl SwarmDriver {
@@ -191,7 +226,10 @@ impl SwarmDriver {
             10, // 10% variance
         );
         let mut network_wide_replication_interval = interval(network_wide_replication);
+        let mut peer_reputation_decay_interval = interval(PEER_REPUTATION_DECAY_INTERVAL);
+        let mut reachability_check_interval = interval(REACHABILITY_POLL_INTERVAL);
         let _ = dial_queue_check_interval.tick().await; // first tick completes immediately
+        let _ = reachability_check_interval.tick().await;
 
         let mut round_robin_index = 0;
         loop {
@@ -228,6 +266,8 @@ impl SwarmDriver {
                 result = shutdown_rx.changed() => {
                     if result.is_ok() && *shutdown_rx.borrow() || result.is_err() {
                         info!("Shutdown signal received or sender dropped. Exiting swarm driver loop.");
+                        self.drain().await;
+                        let _ = self.shutdown_complete_tx.send(true);
                         break;
                     }
                 },
@@ -237,6 +277,20 @@ impl SwarmDriver {
                         warn!("Error while handling swarm event: {err}");
                     }
                 },
+                // low priority cmds are only handled once there is nothing more urgent
+                some_cmd = self.background_cmd_receiver.recv() => match some_cmd {
+                    Some(BackgroundSwarmCmd::Local(cmd)) => {
+                        if let Err(err) = self.handle_local_cmd(cmd) {
+                            warn!("Error while handling background local cmd: {err}");
+                        }
+                    },
+                    Some(BackgroundSwarmCmd::Network(cmd)) => {
+                        if let Err(err) = self.handle_network_cmd(cmd) {
+                            warn!("Error while handling background cmd: {err}");
+                        }
+                    },
+                    None => continue,
+                },
                 // thereafter we can check our intervals
 
                 _ = dial_queue_check_interval.tick() => {
@@ -280,6 +334,9 @@ impl SwarmDriver {
                 }
                 // runs every NETWORK_WIDE_REPLICATION_INTERVAL time
                 _ = network_wide_replication_interval.tick() => {
+                    if self.hibernating {
+                        continue;
+                    }
                     if let Err(err) = self.network_wide_replication.execute(&mut self.swarm).await {
                         warn!("Error during network wide replication: {err}");
                     }
@@ -291,7 +348,7 @@ impl SwarmDriver {
                         round_robin_index = 0;
                     }
 
-                    if let Some(new_interval) = self.run_network_discover_continuously(network_discover_interval.period(), round_robin_index).await {
+                    if !self.hibernating && let Some(new_interval) = self.run_network_discover_continuously(network_discover_interval.period(), round_robin_index).await {
                         network_discover_interval = new_interval;
                     }
 
@@ -329,15 +386,8 @@ impl SwarmDriver {
                         kbucket_status.num_of_full_buckets);
                         continue;
                     }
-                    // The entire Distance space is U256
-                    // (U256::MAX is 115792089237316195423570985008687907853269984665640564039457584007913129639935)
-                    // The network density (average distance among nodes) can be estimated as:
-                    //     network_density = entire_U256_space / estimated_network_size
-                    let density = U256::MAX / U256::from(kbucket_status.estimated_network_size);
-                    let density_distance = density * U256::from(CLOSE_GROUP_SIZE);
-
                     // Use distance to close peer to avoid the situation that
-                    // the estimated density_distance is too narrow.
+                    // the estimated density distance is too narrow.
                     let closest_k_peers = self.get_closest_k_local_peers_to_self();
                     if closest_k_peers.len() <= CLOSE_GROUP_SIZE + 2 {
                         continue;
@@ -347,7 +397,7 @@ impl SwarmDriver {
                     let self_addr = NetworkAddress::from(self.self_peer_id);
                     let close_peers_distance = self_addr.distance(&NetworkAddress::from(closest_k_peers[CLOSE_GROUP_SIZE + 1].0));
 
-                    let distance = std::cmp::max(Distance(density_distance), close_peers_distance);
+                    let distance = close_group::responsible_distance_range(kbucket_status.estimated_network_size, close_peers_distance);
 
                     info!("Set responsible range to {distance:?}({:?})", distance.ilog2());
 
@@ -365,10 +415,86 @@ impl SwarmDriver {
                         relay_manager.try_connecting_to_relay(&mut self.swarm, &self.bad_nodes)
                     }
                 },
+                _ = peer_reputation_decay_interval.tick() => {
+                    self.peer_reputation.decay();
+                },
+                _ = reachability_check_interval.tick() => {
+                    if self.reachability_checker.is_round_due() {
+                        self.complete_reachability_check();
+                    }
+                },
             }
         }
     }
 
+    /// Complete the in-flight record writes and flush the quoting metrics and the bootstrap cache
+    /// to disk, then close the connections so that the peers drop us right away. Gives up on what
+    /// is left once `max_drain_time` has elapsed.
+    async fn drain(&mut self) {
+        let start = Instant::now();
+        let deadline = start + self.max_drain_time;
+        info!(
+            "Draining the node before shutting down, for {:?} at most",
+            self.max_drain_time
+        );
+
+        let mut pending_writes = self
+            .swarm
+            .behaviour_mut()
+            .kademlia
+            .store_mut()
+            .pending_writes();
+        while pending_writes > 0 && Instant::now() < deadline {
+            tokio::time::sleep(DRAIN_CHECK_INTERVAL).await;
+            pending_writes = self
+                .swarm
+                .behaviour_mut()
+                .kademlia
+                .store_mut()
+                .pending_writes();
+        }
+        if pending_writes > 0 {
+            warn!(
+                "{pending_writes} record writes were still in progress when the drain time ran out"
+            );
+        }
+
+        self.swarm
+            .behaviour_mut()
+            .kademlia
+            .store_mut()
+            .flush_historic_quoting_metrics_now();
+
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        match tokio::time::timeout(
+            remaining,
+            self.bootstrap.cache_store().sync_and_flush_to_disk(),
+        )
+        .await
+        {
+            Ok(Ok(())) => info!("Bootstrap cache flushed to disk"),
+            Ok(Err(err)) => warn!("Failed to flush the bootstrap cache to disk: {err}"),
+            Err(_) => warn!("Timed out flushing the bootstrap cache to disk"),
+        }
+
+        let connected_peers: Vec<PeerId> = self.swarm.connected_peers().copied().collect();
+        for peer_id in connected_peers {
+            let _ = self.swarm.disconnect_peer_id(peer_id);
+        }
+        // drive the swarm until the connections are closed
+        while self.swarm.connected_peers().next().is_some() {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if tokio::time::timeout(remaining, self.swarm.select_next_some())
+                .await
+                .is_err()
+            {
+                break;
+            }
+        }
+
+        info!("Node drained in {:?}", start.elapsed());
+    }
+
     // --------------------------------------------
     // ---------- Crate helpers -------------------
     // --------------------------------------------
@@ -536,6 +662,81 @@ impl SwarmDriver {
             metrics_recorder.record_from_marker(marker)
         }
     }
+
+    /// Complete the current round of the reachability check, and record its outcome.
+    fn complete_reachability_check(&mut self) {
+        let relayed = is_a_relayed_peer(self.swarm.external_addresses());
+        self.reachability_checker.complete_round(relayed);
+        #[cfg(feature = "open-metrics")]
+        if let Some(metrics_recorder) = self.metrics_recorder.as_ref() {
+            metrics_recorder.record_reachability(self.reachability_checker.reachability());
+        }
+    }
+
+    /// Switch between relay and direct mode as per the dial-back probes of the peers, if the relay
+    /// fallback is enabled. The probes dial the addresses the peers observe the node at, whatever
+    /// the addresses it advertises, so they still tell whether it became reachable again while
+    /// in relay mode.
+    pub(crate) fn on_nat_status_changed(&mut self, status: &NatStatus) {
+        if !self.relay_fallback || self.local {
+            return;
+        }
+        match status {
+            NatStatus::Private if !self.is_relay_client => self.switch_to_relay_mode(),
+            NatStatus::Public(address) if self.in_relay_fallback => {
+                self.switch_to_direct_mode(address)
+            }
+            _ => {}
+        }
+    }
+
+    /// Stop advertising the direct addresses of the node, and reach the network through relays.
+    /// The relay server is stopped, as the peers can't reach it.
+    fn switch_to_relay_mode(&mut self) {
+        warn!("The peers failed to dial the node back, switching to relay mode");
+        if let Some(external_address_manager) = self.external_address_manager.take() {
+            external_address_manager.remove_external_addresses(&mut self.swarm);
+        }
+
+        self.swarm.behaviour_mut().relay_server = None.into();
+        for relay_client in std::mem::take(&mut self.connected_relay_clients) {
+            let _ = self.swarm.disconnect_peer_id(relay_client);
+        }
+        #[cfg(feature = "open-metrics")]
+        if let Some(metrics_recorder) = &self.metrics_recorder {
+            let _ = metrics_recorder.connected_relay_clients.set(0);
+        }
+
+        let relay_manager = RelayManager::new(self.self_peer_id);
+        #[cfg(feature = "open-metrics")]
+        let mut relay_manager = relay_manager;
+        #[cfg(feature = "open-metrics")]
+        if let Some(metrics_recorder) = &self.metrics_recorder {
+            relay_manager
+                .set_reservation_health_metrics(metrics_recorder.relay_reservation_health.clone());
+        }
+        // Relay servers are picked among the peers identified from now on
+        self.relay_manager = Some(relay_manager);
+        self.is_relay_client = true;
+        self.in_relay_fallback = true;
+    }
+
+    /// Leave the relay mode entered by the relay fallback, once the peers dial the node back at
+    /// `address` again: the relayed addresses are dropped, and the direct ones advertised again.
+    fn switch_to_direct_mode(&mut self, address: &Multiaddr) {
+        info!("The peers dialed the node back at {address:?}, switching back to direct mode");
+        if let Some(relay_manager) = self.relay_manager.take() {
+            relay_manager.remove_relayed_listeners(&mut self.swarm);
+        }
+        self.is_relay_client = false;
+        self.in_relay_fallback = false;
+
+        self.swarm.behaviour_mut().relay_server =
+            Some(relay_server_behaviour(self.self_peer_id)).into();
+        // The direct addresses are confirmed again from what the peers observe
+        self.external_address_manager = Some(ExternalAddressManager::new(self.self_peer_id));
+    }
+
     #[cfg(feature = "open-metrics")]
     /// Updates metrics that rely on our current close group.
     pub(crate) fn record_change_in_close_group(&self, new_close_group: Vec<PeerId>) {
@@ -624,7 +825,7 @@ impl InitialBootstrapTrigger {
 
 #[cfg(test)]
 mod distance_multiplication_examples {
-    use super::*;
+    use libp2p::kad::{KBucketDistance as Distance, U256};
 
     /// Example function demonstrating how distance multiplication affects ilog2 values.
     /// This creates distances with ilog2 values from 0 to 256 with steps of 20, then multiplies each by various factors.
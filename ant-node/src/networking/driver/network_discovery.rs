@@ -16,7 +16,7 @@ use libp2p::{
 };
 use rand::{Rng, rngs::OsRng};
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
-use std::collections::{BTreeMap, btree_map::Entry};
+use std::collections::{BTreeMap, VecDeque, btree_map::Entry};
 use std::time::Instant;
 use tokio::time::{Duration, Interval, interval};
 
@@ -38,6 +38,19 @@ const LAST_PEER_ADDED_TIME_LIMIT: Duration = Duration::from_secs(180);
 /// The network discovery interval to use if we haven't added any new peers in a while.
 const NO_PEER_ADDED_SLOWDOWN_INTERVAL_MAX_S: u64 = 1200;
 
+/// The window over which the joins and leaves of the buckets are counted.
+const CHURN_RATE_WINDOW: Duration = Duration::from_secs(60 * 60);
+
+/// The window over which the peers leaving the RT are counted to detect a mass churn event.
+const MASS_CHURN_WINDOW: Duration = Duration::from_secs(5 * 60);
+
+/// The fraction of the RT which must leave within MASS_CHURN_WINDOW for a mass churn event.
+const MASS_CHURN_RATIO: f64 = 0.2;
+
+/// The minimum number of peers which must leave within MASS_CHURN_WINDOW for a mass churn event,
+/// so that a few peers leaving a sparse RT are not flagged.
+const MASS_CHURN_MIN_LEAVES: usize = 10;
+
 /// (get_closest_candidates, picked_non_full_bucket_peers, picked_full_bucket_peers)
 type RefreshTargets = (
     Vec<NetworkAddress>,
@@ -61,9 +74,25 @@ impl SwarmDriver {
         if should_discover {
             self.trigger_network_discovery(round_robin_index);
         }
+        // Also refresh the churn rates, which decay while the RT is stable
+        let _ = self.refresh_peer_churn();
         new_interval
     }
 
+    /// Update the churn analytics of the RT, detecting the start or end of a mass churn event, and
+    /// record them.
+    pub(crate) fn refresh_peer_churn(&mut self) -> ChurnStats {
+        let stats = self
+            .network_discovery
+            .peer_churn
+            .stats(self.peers_in_rt, Instant::now());
+        #[cfg(feature = "open-metrics")]
+        if let Some(metrics_recorder) = &self.metrics_recorder {
+            metrics_recorder.record_churn(&stats);
+        }
+        stats
+    }
+
     pub(crate) fn trigger_network_discovery(&mut self, round_robin_index: usize) {
         let now = Instant::now();
 
@@ -211,6 +240,7 @@ pub(crate) struct NetworkDiscovery {
     last_peer_added_instant: Instant,
     last_network_discover_triggered: Option<Instant>,
     candidates: NetworkDiscoveryCandidates,
+    peer_churn: PeerChurn,
 }
 
 impl NetworkDiscovery {
@@ -220,6 +250,7 @@ impl NetworkDiscovery {
             last_peer_added_instant: Instant::now(),
             last_network_discover_triggered: None,
             candidates: NetworkDiscoveryCandidates::new(self_peer_id),
+            peer_churn: PeerChurn::default(),
         }
     }
 
@@ -243,6 +274,18 @@ impl NetworkDiscovery {
         }
     }
 
+    /// Notify about a peer added to the bucket `ilog2` of the RT, for the churn analytics.
+    pub(crate) fn notify_peer_joined(&mut self, ilog2: u32) {
+        self.peer_churn
+            .record(ilog2, ChurnKind::Join, Instant::now());
+    }
+
+    /// Notify about a peer removed from the bucket `ilog2` of the RT, for the churn analytics.
+    pub(crate) fn notify_peer_left(&mut self, ilog2: u32) {
+        self.peer_churn
+            .record(ilog2, ChurnKind::Leave, Instant::now());
+    }
+
     /// Returns `true` if we should carry out the Kademlia Bootstrap process immediately.
     /// Also optionally returns the new interval for network discovery.
     pub(crate) async fn should_we_discover(
@@ -307,6 +350,103 @@ impl NetworkDiscovery {
     }
 }
 
+/// The churn of a bucket of the RT over the last hour.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BucketChurn {
+    /// Number of peers added to the bucket in the last hour
+    pub joins_per_hour: usize,
+    /// Number of peers removed from the bucket in the last hour
+    pub leaves_per_hour: usize,
+}
+
+/// The churn of the RT, from the peers joining and leaving its buckets.
+#[derive(Debug, Clone, Default)]
+pub struct ChurnStats {
+    /// The churn of each bucket which saw peers joining or leaving, by ilog2 distance to us
+    pub buckets: BTreeMap<u32, BucketChurn>,
+    /// Whether a large part of the RT is leaving right now
+    pub mass_churn: bool,
+    /// Number of mass churn events since the node started
+    pub mass_churn_events: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChurnKind {
+    Join,
+    Leave,
+}
+
+/// Keeps the times at which peers joined and left each bucket over the last CHURN_RATE_WINDOW.
+#[derive(Debug, Default)]
+struct PeerChurn {
+    joins: BTreeMap<u32, VecDeque<Instant>>,
+    leaves: BTreeMap<u32, VecDeque<Instant>>,
+    mass_churn: bool,
+    mass_churn_events: u64,
+}
+
+impl PeerChurn {
+    fn record(&mut self, ilog2: u32, kind: ChurnKind, now: Instant) {
+        let events = match kind {
+            ChurnKind::Join => &mut self.joins,
+            ChurnKind::Leave => &mut self.leaves,
+        };
+        events.entry(ilog2).or_default().push_back(now);
+    }
+
+    /// Drop the events older than CHURN_RATE_WINDOW, detect whether a mass churn event started or
+    /// ended, and return the churn of each bucket. Buckets are kept once seen, so that their rates
+    /// drop back to zero.
+    fn stats(&mut self, peers_in_rt: usize, now: Instant) -> ChurnStats {
+        for events in self.joins.values_mut().chain(self.leaves.values_mut()) {
+            while events
+                .front()
+                .is_some_and(|time| now.duration_since(*time) > CHURN_RATE_WINDOW)
+            {
+                let _ = events.pop_front();
+            }
+        }
+
+        let recent_leaves: usize = self
+            .leaves
+            .values()
+            .map(|events| {
+                events
+                    .iter()
+                    .rev()
+                    .take_while(|time| now.duration_since(**time) <= MASS_CHURN_WINDOW)
+                    .count()
+            })
+            .sum();
+        // Compare against the size of the RT before these peers left
+        let mass_churn = recent_leaves >= MASS_CHURN_MIN_LEAVES
+            && recent_leaves as f64 >= MASS_CHURN_RATIO * (peers_in_rt + recent_leaves) as f64;
+        if mass_churn && !self.mass_churn {
+            self.mass_churn_events += 1;
+            warn!(
+                "Mass churn detected: {recent_leaves} peers left the RT in the last {MASS_CHURN_WINDOW:?}, {peers_in_rt} peers remain"
+            );
+        } else if !mass_churn && self.mass_churn {
+            info!("The mass churn event is over, {peers_in_rt} peers in the RT");
+        }
+        self.mass_churn = mass_churn;
+
+        let mut buckets: BTreeMap<u32, BucketChurn> = BTreeMap::new();
+        for (ilog2, events) in &self.joins {
+            buckets.entry(*ilog2).or_default().joins_per_hour = events.len();
+        }
+        for (ilog2, events) in &self.leaves {
+            buckets.entry(*ilog2).or_default().leaves_per_hour = events.len();
+        }
+
+        ChurnStats {
+            buckets,
+            mass_churn,
+            mass_churn_events: self.mass_churn_events,
+        }
+    }
+}
+
 /// Keep track of NetworkAddresses belonging to every bucket (if we can generate them with reasonable effort)
 /// which we can then query using Kad::GetClosestPeers to effectively fill our RT.
 #[derive(Debug, Clone)]
@@ -529,4 +669,41 @@ mod tests {
             println!("Peers: {peers}, Expected: {expected_secs:.2}s, Actual: {actual_secs:.2}s",);
         }
     }
+
+    #[test]
+    fn test_peer_churn_rates_and_mass_churn() {
+        let start = Instant::now();
+        let mut churn = PeerChurn::default();
+        churn.record(200, ChurnKind::Join, start);
+        churn.record(200, ChurnKind::Join, start);
+        churn.record(201, ChurnKind::Leave, start);
+
+        let stats = churn.stats(100, start);
+        assert_eq!(
+            stats.buckets.get(&200),
+            Some(&BucketChurn {
+                joins_per_hour: 2,
+                leaves_per_hour: 0
+            })
+        );
+        assert_eq!(stats.buckets.get(&201).map(|b| b.leaves_per_hour), Some(1));
+        assert!(!stats.mass_churn);
+
+        // A fifth of the RT leaving within the window is a mass churn event
+        let later = start + CHURN_RATE_WINDOW;
+        for _ in 0..30 {
+            churn.record(201, ChurnKind::Leave, later);
+        }
+        let stats = churn.stats(100, later);
+        assert!(stats.mass_churn);
+        assert_eq!(stats.mass_churn_events, 1);
+        assert_eq!(stats.buckets.get(&201).map(|b| b.leaves_per_hour), Some(31));
+
+        // It is over once these leaves are out of the window, and old events are dropped
+        let stats = churn.stats(70, later + MASS_CHURN_WINDOW + Duration::from_secs(1));
+        assert!(!stats.mass_churn);
+        assert_eq!(stats.mass_churn_events, 1);
+        assert_eq!(stats.buckets.get(&200), Some(&BucketChurn::default()));
+        assert_eq!(stats.buckets.get(&201).map(|b| b.leaves_per_hour), Some(30));
+    }
 }
@@ -108,6 +108,9 @@ pub enum NetworkError {
     #[error("Internal messaging channel was dropped")]
     InternalMsgChannelDropped,
 
+    #[error("The queue of low priority swarm cmds is full, the cmd has been rejected")]
+    SwarmCmdQueueOverloaded,
+
     #[error("Response received for a request not found in our local tracking map: {0}")]
     ReceivedResponseDropped(OutboundRequestId),
 
@@ -75,6 +75,21 @@ impl ExternalAddressManager {
             .collect()
     }
 
+    /// The IP address of the external addresses of the node
+    pub(crate) fn current_ip_address(&self) -> Option<IpAddr> {
+        self.current_ip_address
+    }
+
+    /// Stop advertising all the external addresses, when the node switches to relay mode.
+    pub(crate) fn remove_external_addresses(self, swarm: &mut Swarm<NodeBehaviour>) {
+        for state in &self.address_states {
+            if !state.is_candidate() {
+                swarm.remove_external_address(state.multiaddr());
+            }
+        }
+        Self::print_swarm_state(swarm);
+    }
+
     /// Add an external address candidate to the manager.
     /// If the address has been reported often enough, it is confirmed and added to the swarm.
     /// If a new IP address has been reported often enough, then we switch to the new IP address and discard the old
@@ -426,6 +441,12 @@ impl ExternalAddressManager {
                 .find(|protocol| matches!(protocol, Protocol::Udp(_)))?;
             output_address.push(port);
             output_address.push(Protocol::QuicV1);
+        } else if let Some(port) = given_address
+            .iter()
+            .find(|protocol| matches!(protocol, Protocol::Tcp(_)))
+        {
+            // TCP fallback
+            output_address.push(port);
         } else {
             return None;
         }
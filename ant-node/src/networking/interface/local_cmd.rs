@@ -23,7 +23,10 @@ use libp2p::{
 };
 use tokio::sync::oneshot;
 
-use crate::networking::Addresses;
+use super::CmdPriority;
+use crate::networking::{
+    Addresses, driver::network_discovery::ChurnStats, reachability::Reachability,
+};
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub(crate) enum NodeIssue {
@@ -40,6 +43,12 @@ pub(crate) enum NodeIssue {
     BadQuoting,
     /// Peer failed to pass the chunk proof verification
     FailedChunkProofCheck,
+    /// Peer supplied a record that failed validation
+    InvalidRecord,
+    /// Peer did not respond to a request in time
+    RequestTimeout,
+    /// Peer did not follow the protocol, e.g. does not support our request-response protocol
+    ProtocolViolation,
 }
 
 impl std::fmt::Display for NodeIssue {
@@ -51,6 +60,9 @@ impl std::fmt::Display for NodeIssue {
             NodeIssue::CloseNodesShunning => write!(f, "CloseNodesShunning"),
             NodeIssue::BadQuoting => write!(f, "BadQuoting"),
             NodeIssue::FailedChunkProofCheck => write!(f, "FailedChunkProofCheck"),
+            NodeIssue::InvalidRecord => write!(f, "InvalidRecord"),
+            NodeIssue::RequestTimeout => write!(f, "RequestTimeout"),
+            NodeIssue::ProtocolViolation => write!(f, "ProtocolViolation"),
         }
     }
 }
@@ -86,6 +98,11 @@ pub(crate) enum LocalSwarmCmd {
         key: RecordKey,
         sender: oneshot::Sender<Option<Record>>,
     },
+    /// Read a held record straight from its file on disk, bypassing the in-memory caches
+    ReadLocalRecordFromDisk {
+        key: RecordKey,
+        sender: oneshot::Sender<Option<Record>>,
+    },
     /// GetLocalQuotingMetrics for this node
     /// Returns the quoting metrics and whether the record at `key` is already stored locally
     GetLocalQuotingMetrics {
@@ -111,6 +128,11 @@ pub(crate) enum LocalSwarmCmd {
     RemoveOutOfSyncEntry {
         key: RecordKey,
     },
+    /// Remove a local record from the RecordStore
+    /// Because its payment was dropped by a chain reorganisation
+    RemoveUnpaidLocalRecord {
+        key: RecordKey,
+    },
     /// Add a local record to the RecordStore's HashSet of stored records
     /// This should be done after the record has been stored to disk
     AddLocalRecordAsStored {
@@ -141,7 +163,7 @@ pub(crate) enum LocalSwarmCmd {
     /// Triggers interval repliation
     /// NOTE: This does result in outgoing messages, but is produced locally
     TriggerIntervalReplication,
-    /// Triggers unrelevant record cleanup
+    /// Triggers the cleanup of the unrelevant and expired records
     TriggerIrrelevantRecordCleanup,
     /// Send peer scores (collected from storage challenge) to replication_fetcher
     NotifyPeerScores {
@@ -167,6 +189,53 @@ pub(crate) enum LocalSwarmCmd {
     },
     /// Some records were not found at their target location
     RecordNotAtTargetLocation,
+    /// Get the reputation scores of the penalised peers, lowest first
+    GetPeerScores {
+        sender: oneshot::Sender<Vec<(PeerId, i32)>>,
+    },
+    /// Get the health of each of the swarm behaviours
+    GetBehaviourHealth {
+        sender: oneshot::Sender<BehaviourHealth>,
+    },
+    /// Get the churn rates of the buckets of the RT, and whether it is undergoing mass churn
+    GetChurnStats {
+        sender: oneshot::Sender<ChurnStats>,
+    },
+    /// Get the outcome of the reachability check, with the progress of its current round
+    GetReachability {
+        sender: oneshot::Sender<Reachability>,
+    },
+    /// Get statistics of the record store, along with a random sample of the held addresses
+    GetRecordStoreStats {
+        sample_size: usize,
+        sender: oneshot::Sender<RecordStoreStats>,
+    },
+    /// Enter or leave hibernation, where replication and discovery are paused and only the
+    /// connections to the peers of the routing table are kept.
+    SetHibernation {
+        hibernating: bool,
+    },
+}
+
+impl LocalSwarmCmd {
+    /// Replication and other background bookkeeping are of low priority.
+    pub(crate) fn priority(&self) -> CmdPriority {
+        match self {
+            LocalSwarmCmd::PutLocalRecord {
+                is_client_put: false,
+                ..
+            }
+            | LocalSwarmCmd::FetchCompleted(_)
+            | LocalSwarmCmd::TriggerIntervalReplication
+            | LocalSwarmCmd::TriggerIrrelevantRecordCleanup
+            | LocalSwarmCmd::NotifyPeerScores { .. }
+            | LocalSwarmCmd::AddFreshReplicateRecords { .. }
+            | LocalSwarmCmd::NotifyPeerVersion { .. }
+            | LocalSwarmCmd::QuoteVerification { .. }
+            | LocalSwarmCmd::RecordNotAtTargetLocation => CmdPriority::Low,
+            _ => CmdPriority::High,
+        }
+    }
 }
 
 /// Debug impl for LocalSwarmCmd to avoid printing full Record, instead only RecodKey
@@ -198,6 +267,13 @@ impl Debug for LocalSwarmCmd {
                     PrettyPrintRecordKey::from(key)
                 )
             }
+            LocalSwarmCmd::RemoveUnpaidLocalRecord { key } => {
+                write!(
+                    f,
+                    "LocalSwarmCmd::RemoveUnpaidLocalRecord {{ key: {:?} }}",
+                    PrettyPrintRecordKey::from(key)
+                )
+            }
             LocalSwarmCmd::AddLocalRecordAsStored {
                 key,
                 record_type,
@@ -228,6 +304,13 @@ impl Debug for LocalSwarmCmd {
                     PrettyPrintRecordKey::from(key)
                 )
             }
+            LocalSwarmCmd::ReadLocalRecordFromDisk { key, .. } => {
+                write!(
+                    f,
+                    "LocalSwarmCmd::ReadLocalRecordFromDisk {{ key: {:?} }}",
+                    PrettyPrintRecordKey::from(key)
+                )
+            }
             LocalSwarmCmd::GetAllLocalRecordAddresses { .. } => {
                 write!(f, "LocalSwarmCmd::GetAllLocalRecordAddresses")
             }
@@ -300,6 +383,24 @@ impl Debug for LocalSwarmCmd {
             LocalSwarmCmd::RecordNotAtTargetLocation => {
                 write!(f, "LocalSwarmCmd::RecordNotAtTargetLocation")
             }
+            LocalSwarmCmd::GetPeerScores { .. } => {
+                write!(f, "LocalSwarmCmd::GetPeerScores")
+            }
+            LocalSwarmCmd::GetBehaviourHealth { .. } => {
+                write!(f, "LocalSwarmCmd::GetBehaviourHealth")
+            }
+            LocalSwarmCmd::GetChurnStats { .. } => {
+                write!(f, "LocalSwarmCmd::GetChurnStats")
+            }
+            LocalSwarmCmd::GetReachability { .. } => {
+                write!(f, "LocalSwarmCmd::GetReachability")
+            }
+            LocalSwarmCmd::GetRecordStoreStats { sample_size, .. } => {
+                write!(f, "LocalSwarmCmd::GetRecordStoreStats({sample_size})")
+            }
+            LocalSwarmCmd::SetHibernation { hibernating } => {
+                write!(f, "LocalSwarmCmd::SetHibernation({hibernating})")
+            }
         }
     }
 }
@@ -314,3 +415,61 @@ pub struct SwarmLocalState {
     /// List of addresses the node is currently listening on
     pub listeners: Vec<Multiaddr>,
 }
+
+/// Health of each of the swarm behaviours.
+///
+/// Helps diagnosing nodes that are connected to the network, but fail to take part in it.
+/// The failure counts are since the node started.
+#[derive(Debug, Clone, Default)]
+pub struct BehaviourHealth {
+    /// Health of the kademlia behaviour
+    pub kademlia: KademliaHealth,
+    /// Health of the request-response behaviour
+    pub request_response: RequestResponseHealth,
+    /// Health of the identify behaviour
+    pub identify: IdentifyHealth,
+}
+
+/// Health of the kademlia behaviour
+#[derive(Debug, Clone, Default)]
+pub struct KademliaHealth {
+    /// The number of peers in the routing table
+    pub peers_in_routing_table: usize,
+    /// The number of kbuckets holding at least one peer
+    pub non_empty_kbuckets: usize,
+    /// The number of queries still in progress
+    pub ongoing_queries: usize,
+}
+
+/// Health of the request-response behaviour
+#[derive(Debug, Clone, Default)]
+pub struct RequestResponseHealth {
+    /// The number of requests sent to peers and still awaiting a response
+    pub inflight_outbound_requests: usize,
+    /// The number of requests sent to peers that failed, including timeouts
+    pub outbound_failures: u64,
+    /// The number of requests received from peers that could not be responded to
+    pub inbound_failures: u64,
+}
+
+/// Health of the identify behaviour
+#[derive(Debug, Clone, Default)]
+pub struct IdentifyHealth {
+    /// The number of identify infos received from peers
+    pub received: u64,
+    /// The number of failed identify exchanges
+    pub errors: u64,
+}
+
+/// Statistics of the records held by the record store.
+#[derive(Debug, Clone, Default)]
+pub struct RecordStoreStats {
+    /// The number of records of each data type
+    pub records_per_type: Vec<(DataTypes, usize)>,
+    /// The size of the record files on disk, in bytes
+    pub total_bytes: u64,
+    /// The number of records at each distance from the node, keyed by the `ilog2` of the distance
+    pub records_per_distance: BTreeMap<u32, usize>,
+    /// A random sample of the addresses of the held records
+    pub sample: Vec<NetworkAddress>,
+}
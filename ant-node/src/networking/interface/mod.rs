@@ -10,7 +10,40 @@ mod local_cmd;
 mod network_cmd;
 mod network_event;
 
-pub use local_cmd::SwarmLocalState;
+pub use local_cmd::{
+    BehaviourHealth, IdentifyHealth, KademliaHealth, RecordStoreStats, RequestResponseHealth,
+    SwarmLocalState,
+};
 pub(crate) use local_cmd::{LocalSwarmCmd, NodeIssue};
 pub(crate) use network_cmd::NetworkSwarmCmd;
 pub(crate) use network_event::{NetworkEvent, TerminateNodeReason};
+
+#[cfg(feature = "open-metrics")]
+use prometheus_client::encoding::EncodeLabelValue;
+
+/// Priority of a command sent to the `SwarmDriver`.
+///
+/// High priority commands serve the clients and the node's own operation. Low priority commands
+/// drive background work such as replication: they are queued separately and only handled once
+/// there is nothing more urgent, and are rejected rather than queued when that queue is full.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub(crate) enum CmdPriority {
+    High,
+    Low,
+}
+
+/// A low priority command, see `CmdPriority`.
+#[derive(Debug)]
+pub(crate) enum BackgroundSwarmCmd {
+    Local(LocalSwarmCmd),
+    Network(NetworkSwarmCmd),
+}
+
+/// The queues of commands sent to the `SwarmDriver`.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "open-metrics", derive(EncodeLabelValue))]
+pub(crate) enum SwarmCmdQueue {
+    Local,
+    Network,
+    Background,
+}
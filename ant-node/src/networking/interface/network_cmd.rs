@@ -9,11 +9,12 @@
 use std::fmt::Debug;
 use std::num::NonZeroUsize;
 
-use ant_protocol::messages::{ConnectionInfo, Request, Response};
+use ant_protocol::messages::{Cmd, ConnectionInfo, Query, Request, Response};
 use libp2p::PeerId;
 use tokio::sync::oneshot;
 
-use crate::networking::error::Result;
+use super::CmdPriority;
+use crate::networking::error::{NetworkError, Result};
 use crate::networking::{Addresses, NetworkAddress, driver::event::MsgResponder};
 
 /// Commands to send to the Swarm
@@ -48,6 +49,39 @@ pub(crate) enum NetworkSwarmCmd {
     },
 }
 
+impl NetworkSwarmCmd {
+    /// Replication traffic is background work, everything else serves the clients or the node itself.
+    pub(crate) fn priority(&self) -> CmdPriority {
+        match self {
+            NetworkSwarmCmd::SendRequest {
+                req: Request::Cmd(Cmd::Replicate { .. } | Cmd::FreshReplicate { .. }),
+                ..
+            }
+            | NetworkSwarmCmd::SendRequest {
+                req:
+                    Request::Query(Query::GetReplicatedRecord { .. })
+                    | Request::TracedQuery {
+                        query: Query::GetReplicatedRecord { .. },
+                        ..
+                    },
+                ..
+            } => CmdPriority::Low,
+            _ => CmdPriority::High,
+        }
+    }
+
+    /// Reject the command as the queue is overloaded, notifying the caller awaiting its result.
+    pub(crate) fn reject(self) {
+        if let NetworkSwarmCmd::SendRequest {
+            sender: Some(sender),
+            ..
+        } = self
+        {
+            let _ = sender.send(Err(NetworkError::SwarmCmdQueueOverloaded));
+        }
+    }
+}
+
 /// Debug impl for NetworkSwarmCmd to avoid printing full Record, instead only RecodKey
 /// and RecordKind are printed.
 impl Debug for NetworkSwarmCmd {
@@ -28,6 +28,10 @@ pub(crate) enum NetworkEvent {
         query: Query,
         /// The channel to send the `Response` through
         channel: MsgResponder,
+        /// W3C trace context of the sender, if the query was traced
+        trace_context: Option<String>,
+        /// The peer which sent the query, `None` if sent by ourselves
+        source: Option<PeerId>,
     },
     /// Handles the responses that are not awaited at the call site
     ResponseReceived {
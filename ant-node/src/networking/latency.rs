@@ -0,0 +1,44 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+#[cfg(feature = "open-metrics")]
+use prometheus_client::encoding::EncodeLabelValue;
+
+/// Node operations whose latency is recorded when the metrics server is enabled.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "open-metrics", derive(EncodeLabelValue))]
+pub(crate) enum LatencyOperation {
+    /// Round-trip of a record GET sent to a peer
+    Get,
+    /// Validation and storage of a record PUT received from a peer
+    Put,
+    /// Round-trip of a record fetched from a peer during replication, until it is stored
+    Replication,
+    /// Generation of a store quote
+    Quote,
+    /// On-chain verification of a payment, retries included
+    PaymentVerification,
+}
+
+/// Outcome of a node operation whose latency is recorded.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "open-metrics", derive(EncodeLabelValue))]
+pub(crate) enum LatencyOutcome {
+    Success,
+    Failure,
+}
+
+impl<T, E> From<&Result<T, E>> for LatencyOutcome {
+    fn from(result: &Result<T, E>) -> Self {
+        if result.is_ok() {
+            Self::Success
+        } else {
+            Self::Failure
+        }
+    }
+}
@@ -0,0 +1,39 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use crate::networking::ChurnStats;
+use prometheus_client::encoding::EncodeLabelSet;
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub(crate) struct BucketLabels {
+    /// The ilog2 distance of the bucket to us
+    ilog2: u32,
+}
+
+impl super::NetworkMetricsRecorder {
+    /// Record the churn of the routing table.
+    pub(crate) fn record_churn(&self, stats: &ChurnStats) {
+        for (ilog2, churn) in &stats.buckets {
+            let labels = BucketLabels { ilog2: *ilog2 };
+            let _ = self
+                .bucket_joins_per_hour
+                .get_or_create(&labels)
+                .set(churn.joins_per_hour as i64);
+            let _ = self
+                .bucket_leaves_per_hour
+                .get_or_create(&labels)
+                .set(churn.leaves_per_hour as i64);
+        }
+        let _ = self.mass_churn.set(stats.mass_churn as i64);
+        let _ = self.mass_churn_events.inc_by(
+            stats
+                .mass_churn_events
+                .saturating_sub(self.mass_churn_events.get()),
+        );
+    }
+}
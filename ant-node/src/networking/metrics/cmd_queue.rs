@@ -0,0 +1,19 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use crate::networking::interface::SwarmCmdQueue;
+use prometheus_client::encoding::EncodeLabelSet;
+use prometheus_client::metrics::{family::Family, gauge::Gauge};
+
+/// Number of cmds waiting in each queue of the `SwarmDriver`.
+pub(crate) type SwarmCmdQueueDepths = Family<SwarmCmdQueueLabels, Gauge>;
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub(crate) struct SwarmCmdQueueLabels {
+    pub(crate) queue: SwarmCmdQueue,
+}
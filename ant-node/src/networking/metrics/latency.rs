@@ -0,0 +1,32 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use crate::networking::latency::{LatencyOperation, LatencyOutcome};
+use prometheus_client::encoding::EncodeLabelSet;
+use prometheus_client::metrics::{
+    family::Family,
+    histogram::{Histogram, exponential_buckets},
+};
+
+/// Latency histograms of the node operations, in seconds.
+pub(crate) type OperationLatencies = Family<OperationLatencyLabels, Histogram, fn() -> Histogram>;
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub(crate) struct OperationLatencyLabels {
+    pub(crate) operation: LatencyOperation,
+    pub(crate) outcome: LatencyOutcome,
+}
+
+/// Buckets from 5ms up to ~40s, payment verification with retries can take that long.
+fn latency_histogram() -> Histogram {
+    Histogram::new(exponential_buckets(0.005, 2.0, 14))
+}
+
+pub(crate) fn new_operation_latencies() -> OperationLatencies {
+    Family::new_with_constructor(latency_histogram as fn() -> Histogram)
+}
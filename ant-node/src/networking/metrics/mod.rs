@@ -8,13 +8,19 @@
 
 // Implementation to record `libp2p::upnp::Event` metrics
 mod bad_node;
+mod churn;
+pub(crate) mod cmd_queue;
+pub(crate) mod latency;
+mod reachability;
 mod relay_client;
 mod replication;
+pub(crate) mod requests;
 pub(super) mod service;
 mod upnp;
 
 use crate::networking::MetricsRegistries;
 use crate::networking::log_markers::Marker;
+use crate::networking::record_store::RecordTypeLabels;
 use bad_node::{BadNodeMetrics, BadNodeMetricsMsg, TimeFrame};
 use libp2p::{
     PeerId,
@@ -69,9 +75,26 @@ pub(crate) struct NetworkMetricsRecorder {
     pub(crate) peers_in_routing_table: Gauge,
     pub(crate) relay_peers_in_routing_table: Gauge,
     pub(crate) records_stored: Gauge,
+    pub(crate) records_stored_per_type: Family<RecordTypeLabels, Gauge>,
+    pub(crate) storage_quota_rejections: Family<RecordTypeLabels, Counter>,
+    pub(crate) hot_records_cache_hits: Counter,
+    pub(crate) hot_records_cache_misses: Counter,
     pub(crate) relay_reservation_health: Gauge<f64, AtomicU64>,
     pub(crate) node_versions: Family<VersionLabels, Gauge>,
 
+    // reachability metrics
+    reachability_status: Gauge,
+    pub(crate) reachability_check_progress: Gauge,
+    reachability_incoming_peers: Gauge,
+    relayed: Gauge,
+    external_address_family: Family<reachability::AddressFamilyLabels, Gauge>,
+
+    // churn metrics
+    bucket_joins_per_hour: Family<churn::BucketLabels, Gauge>,
+    bucket_leaves_per_hour: Family<churn::BucketLabels, Gauge>,
+    mass_churn: Gauge,
+    mass_churn_events: Counter,
+
     // replication metrics
     pub(crate) replicate_candidates: Family<replication::ReplicateCandidateLabels, Gauge>,
     pub(crate) replication_sender_range: Family<replication::ReplicationSenderRangeLabels, Counter>,
@@ -86,6 +109,14 @@ pub(crate) struct NetworkMetricsRecorder {
     // Internal state for sliding window (not exposed to Prometheus)
     replication_stats_window: Arc<Mutex<replication::ReplicationStatsWindow>>,
 
+    // latency of the node operations
+    pub(crate) operation_latencies: latency::OperationLatencies,
+    // requests sent to peers, by kind and outcome
+    pub(crate) request_outcomes: requests::RequestOutcomes,
+    // queues of the cmds sent to the swarm driver
+    pub(crate) swarm_cmd_queue_depths: cmd_queue::SwarmCmdQueueDepths,
+    pub(crate) swarm_cmds_rejected: Counter,
+
     // quoting metrics
     relevant_records: Gauge,
     max_records: Gauge,
@@ -128,6 +159,30 @@ impl NetworkMetricsRecorder {
             "The number of records stored locally",
             records_stored.clone(),
         );
+        let records_stored_per_type = Family::default();
+        sub_registry.register(
+            "records_stored_per_type",
+            "The number of records stored locally, by data type",
+            records_stored_per_type.clone(),
+        );
+        let storage_quota_rejections = Family::default();
+        sub_registry.register(
+            "storage_quota_rejections",
+            "The number of records refused as their data type already takes its quota of the store",
+            storage_quota_rejections.clone(),
+        );
+        let hot_records_cache_hits = Counter::default();
+        sub_registry.register(
+            "hot_records_cache_hits",
+            "Number of GETs of a locally stored record served from the hot records cache",
+            hot_records_cache_hits.clone(),
+        );
+        let hot_records_cache_misses = Counter::default();
+        sub_registry.register(
+            "hot_records_cache_misses",
+            "Number of GETs of a locally stored record read from disk",
+            hot_records_cache_misses.clone(),
+        );
         let relay_reservation_health = Gauge::<f64, AtomicU64>::default();
         sub_registry.register(
             "relay_reservation_health",
@@ -229,6 +284,65 @@ impl NetworkMetricsRecorder {
             process_cpu_usage_percentage.clone(),
         );
 
+        // ==== Reachability metrics =====
+        let reachability_status = Gauge::default();
+        sub_registry.register(
+            "reachability_status",
+            "The outcome of the last reachability check: 1 if enough peers dialed the node directly, 0 if not, -1 if the node has not been checked yet",
+            reachability_status.clone(),
+        );
+        let _ = reachability_status.set(-1);
+        let reachability_check_progress = Gauge::default();
+        sub_registry.register(
+            "reachability_check_progress",
+            "The progress of the current reachability check towards passing. Value is from 0-100",
+            reachability_check_progress.clone(),
+        );
+        let reachability_incoming_peers = Gauge::default();
+        sub_registry.register(
+            "reachability_incoming_peers",
+            "The number of peers that dialed the node directly during the last reachability check",
+            reachability_incoming_peers.clone(),
+        );
+        let relayed = Gauge::default();
+        sub_registry.register(
+            "relayed",
+            "Set to 1 if the node advertises relayed addresses, i.e. is reached through relays",
+            relayed.clone(),
+        );
+        let external_address_family = Family::default();
+        sub_registry.register(
+            "external_address_family",
+            "Set to 1 for the IP version most peers dialed the node over during the last reachability check",
+            external_address_family.clone(),
+        );
+
+        // ==== Churn metrics =====
+        let bucket_joins_per_hour = Family::default();
+        sub_registry.register(
+            "bucket_joins_per_hour",
+            "The number of peers added to each bucket of the routing table in the last hour",
+            bucket_joins_per_hour.clone(),
+        );
+        let bucket_leaves_per_hour = Family::default();
+        sub_registry.register(
+            "bucket_leaves_per_hour",
+            "The number of peers removed from each bucket of the routing table in the last hour",
+            bucket_leaves_per_hour.clone(),
+        );
+        let mass_churn = Gauge::default();
+        sub_registry.register(
+            "mass_churn",
+            "Set to 1 while a large part of the routing table is leaving",
+            mass_churn.clone(),
+        );
+        let mass_churn_events = Counter::default();
+        sub_registry.register(
+            "mass_churn_events",
+            "The number of mass churn events detected in the routing table",
+            mass_churn_events.clone(),
+        );
+
         // ==== Replication metrics =====
         let replicate_candidates = Family::default();
         sub_registry.register(
@@ -286,6 +400,34 @@ impl NetworkMetricsRecorder {
             distance_range.clone(),
         );
 
+        // ==== Latency metrics =====
+        let operation_latencies = latency::new_operation_latencies();
+        sub_registry.register(
+            "operation_latency_seconds",
+            "Latency of GET/PUT/replication round-trips, quote generation and payment verification, by outcome",
+            operation_latencies.clone(),
+        );
+        let request_outcomes = requests::RequestOutcomes::default();
+        sub_registry.register(
+            "request_outcomes",
+            "Number of requests sent to peers, by kind and outcome (success, failure or timeout)",
+            request_outcomes.clone(),
+        );
+
+        // ==== Swarm cmd queue metrics =====
+        let swarm_cmd_queue_depths = cmd_queue::SwarmCmdQueueDepths::default();
+        sub_registry.register(
+            "swarm_cmd_queue_depth",
+            "Number of cmds waiting to be handled by the swarm driver, by queue",
+            swarm_cmd_queue_depths.clone(),
+        );
+        let swarm_cmds_rejected = Counter::default();
+        sub_registry.register(
+            "swarm_cmds_rejected",
+            "Number of low priority cmds rejected as their queue was full",
+            swarm_cmds_rejected.clone(),
+        );
+
         // quoting metrics
         let relevant_records = Gauge::default();
         sub_registry.register(
@@ -358,6 +500,10 @@ impl NetworkMetricsRecorder {
             relay_client_events,
 
             records_stored,
+            records_stored_per_type,
+            storage_quota_rejections,
+            hot_records_cache_hits,
+            hot_records_cache_misses,
             estimated_network_size,
             relay_peers_percentage,
             connected_peers,
@@ -372,6 +518,17 @@ impl NetworkMetricsRecorder {
             live_time,
             node_versions,
 
+            reachability_status,
+            reachability_check_progress,
+            reachability_incoming_peers,
+            relayed,
+            external_address_family,
+
+            bucket_joins_per_hour,
+            bucket_leaves_per_hour,
+            mass_churn,
+            mass_churn_events,
+
             replicate_candidates,
             replication_sender_range,
             replication_sender_close_group_threshold,
@@ -384,6 +541,11 @@ impl NetworkMetricsRecorder {
             )),
             distance_range,
 
+            operation_latencies,
+            request_outcomes,
+            swarm_cmd_queue_depths,
+            swarm_cmds_rejected,
+
             bad_peers_count,
             shunned_count_across_time_frames,
             shunned_count,
@@ -0,0 +1,40 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use crate::networking::reachability::{AddressFamily, Reachability, ReachabilityStatus};
+use prometheus_client::encoding::EncodeLabelSet;
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub(crate) struct AddressFamilyLabels {
+    family: AddressFamily,
+}
+
+impl super::NetworkMetricsRecorder {
+    /// Record the reachability of the node, as last checked.
+    pub(crate) fn record_reachability(&self, reachability: &Reachability) {
+        let status = match reachability.status {
+            ReachabilityStatus::NotChecked => -1,
+            ReachabilityStatus::Unreachable => 0,
+            ReachabilityStatus::Reachable => 1,
+        };
+        let _ = self.reachability_status.set(status);
+        let _ = self
+            .reachability_check_progress
+            .set(reachability.progress as i64);
+        let _ = self
+            .reachability_incoming_peers
+            .set(reachability.incoming_peers as i64);
+        let _ = self.relayed.set(reachability.relayed as i64);
+        for family in [AddressFamily::Ipv4, AddressFamily::Ipv6] {
+            let _ = self
+                .external_address_family
+                .get_or_create(&AddressFamilyLabels { family })
+                .set((reachability.address_family == Some(family)) as i64);
+        }
+    }
+}
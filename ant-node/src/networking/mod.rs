@@ -14,34 +14,43 @@ mod driver;
 mod error;
 mod external_address;
 mod interface;
+mod latency;
 mod log_markers;
 #[cfg(feature = "open-metrics")]
 mod metrics;
 mod network;
+mod peer_reputation;
+mod reachability;
 mod record_store;
 mod relay_manager;
 mod replication_fetcher;
+mod request_limits;
 mod transport;
 
 // re-export arch dependent deps for use in the crate, or above
-pub use self::interface::SwarmLocalState;
 pub(crate) use self::{
     error::NetworkError,
     interface::{NetworkEvent, NodeIssue},
+    latency::{LatencyOperation, LatencyOutcome},
     network::{Network, NetworkConfig},
     record_store::NodeRecordStore,
 };
+pub use self::{
+    driver::network_discovery::{BucketChurn, ChurnStats},
+    interface::{
+        BehaviourHealth, IdentifyHealth, KademliaHealth, RecordStoreStats, RequestResponseHealth,
+        SwarmLocalState,
+    },
+    reachability::{AddressFamily, Reachability, ReachabilityStatus},
+    request_limits::{RequestKind, RequestLimits},
+};
 
 #[cfg(feature = "open-metrics")]
 pub(crate) use metrics::service::MetricsRegistries;
 
 use self::error::Result;
-use ant_protocol::{CLOSE_GROUP_SIZE, NetworkAddress};
-use libp2p::{
-    Multiaddr, PeerId,
-    kad::{KBucketDistance, KBucketKey},
-    multiaddr::Protocol,
-};
+use ant_protocol::{CLOSE_GROUP_SIZE, close_group};
+use libp2p::{Multiaddr, PeerId, kad::KBucketKey, multiaddr::Protocol};
 use std::net::IpAddr;
 
 /// Sort the provided peers by their distance to the given `KBucketKey`.
@@ -61,28 +70,7 @@ pub fn sort_peers_by_key<T>(
         });
     }
 
-    // Create a vector of tuples where each tuple is a reference to a peer and its distance to the key.
-    // This avoids multiple computations of the same distance in the sorting process.
-    let mut peer_distances: Vec<(PeerId, Addresses, KBucketDistance)> =
-        Vec::with_capacity(peers.len());
-
-    for (peer_id, addrs) in peers.into_iter() {
-        let addr = NetworkAddress::from(peer_id);
-        let distance = key.distance(&addr.as_kbucket_key());
-        peer_distances.push((peer_id, addrs, distance));
-    }
-
-    // Sort the vector of tuples by the distance.
-    peer_distances.sort_by(|a, b| a.2.cmp(&b.2));
-
-    // Collect the sorted peers into a new vector.
-    let sorted_peers: Vec<(PeerId, Addresses)> = peer_distances
-        .into_iter()
-        .take(expected_entries)
-        .map(|(peer_id, addrs, _)| (peer_id, addrs))
-        .collect();
-
-    Ok(sorted_peers)
+    Ok(close_group::sort_peers_by_key(peers, key, expected_entries))
 }
 
 /// A list of addresses of a peer in the routing table.
@@ -116,9 +104,10 @@ pub(crate) fn multiaddr_get_ip(addr: &Multiaddr) -> Option<IpAddr> {
     })
 }
 
+/// Get the UDP port of a QUIC address, or the TCP port of a TCP fallback address.
 pub(crate) fn multiaddr_get_port(addr: &Multiaddr) -> Option<u16> {
     addr.iter().find_map(|p| match p {
-        Protocol::Udp(port) => Some(port),
+        Protocol::Udp(port) | Protocol::Tcp(port) => Some(port),
         _ => None,
     })
 }
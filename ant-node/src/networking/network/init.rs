@@ -9,14 +9,17 @@
 use ant_protocol::constants::{KAD_STREAM_PROTOCOL_ID, MAX_PACKET_SIZE, REPLICATION_FACTOR};
 
 use crate::networking::{
-    CLOSE_GROUP_SIZE, NetworkEvent,
+    CLOSE_GROUP_SIZE, NetworkEvent, RequestKind, RequestLimits,
     circular_vec::CircularVec,
     driver::{
-        BLOCKLIST_CACHE_SIZE, InitialBootstrapTrigger, NodeBehaviour, SwarmDriver,
-        network_discovery::NetworkDiscovery, network_wide_replication::NetworkWideReplication,
+        BLOCKLIST_CACHE_SIZE, DEFAULT_MAX_DRAIN_TIME, InitialBootstrapTrigger, NodeBehaviour,
+        SwarmDriver, network_discovery::NetworkDiscovery,
+        network_wide_replication::NetworkWideReplication,
     },
     error::{NetworkError, Result},
     external_address::ExternalAddressManager,
+    peer_reputation::PeerReputation,
+    reachability::ReachabilityChecker,
     record_store::{NodeRecordStore, NodeRecordStoreConfig},
     relay_manager::RelayManager,
     replication_fetcher::ReplicationFetcher,
@@ -24,12 +27,16 @@ use crate::networking::{
 };
 #[cfg(feature = "open-metrics")]
 use crate::networking::{
-    MetricsRegistries, metrics::NetworkMetricsRecorder, metrics::service::run_metrics_server,
+    MetricsRegistries,
+    metrics::NetworkMetricsRecorder,
+    metrics::service::run_metrics_server,
+    record_store::{HotRecordCacheMetrics, StorageQuotaMetrics},
 };
 use ant_bootstrap::bootstrap::Bootstrap;
 use ant_protocol::{
     NetworkAddress, PrettyPrintKBucketKey,
     messages::{Request, Response},
+    storage::DataTypes,
     version::{IDENTIFY_PROTOCOL_STR, REQ_RESPONSE_VERSION_STR, get_network_id_str},
     version_gate::get_min_node_version,
 };
@@ -50,6 +57,7 @@ use libp2p::{core::muxing::StreamMuxerBox, relay};
 use prometheus_client::metrics::info::Info;
 use std::time::Instant;
 use std::{
+    collections::HashMap,
     convert::TryInto,
     fmt::Debug,
     fs,
@@ -70,9 +78,18 @@ const RESEND_IDENTIFY_INVERVAL: Duration = Duration::from_secs(3600);
 
 const NETWORKING_CHANNEL_SIZE: usize = 10_000;
 
+/// Low priority cmds beyond this are rejected, so that background work cannot pile up unbounded.
+const BACKGROUND_CMD_CHANNEL_SIZE: usize = 5_000;
+
 /// Time before a Kad query times out if no response is received
 const KAD_QUERY_TIMEOUT_S: Duration = Duration::from_secs(120);
 
+/// Delay before the first AutoNAT probe, to have some peers to ask to dial the node back.
+const AUTONAT_BOOT_DELAY: Duration = Duration::from_secs(60);
+
+/// Interval between two AutoNAT probes once the NAT status is known.
+const AUTONAT_REFRESH_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
 #[derive(Debug)]
 pub(crate) struct NetworkConfig {
     pub keypair: Keypair,
@@ -83,7 +100,25 @@ pub(crate) struct NetworkConfig {
     pub bootstrap: Bootstrap,
     pub no_upnp: bool,
     pub relay_client: bool,
+    /// Switch to relay mode when the peers fail to dial the node back
+    pub relay_fallback: bool,
+    /// Also listen and dial over TCP, for peers unable to use QUIC (e.g. UDP being blocked)
+    pub tcp_fallback: bool,
     pub custom_request_timeout: Option<Duration>,
+    /// Max number of replication fetches undertaken at the same time
+    pub replication_max_parallel_fetches: Option<usize>,
+    /// Max number of frequently read records kept in memory, disabled if not set or 0
+    pub hot_records_cache_size: Option<usize>,
+    /// Max fraction of the record store each data type may take
+    pub storage_quotas: HashMap<DataTypes, f64>,
+    /// Directories to shard the records across, instead of the root dir, e.g. one per disk
+    pub data_dirs: Vec<PathBuf>,
+    /// Timeout and concurrency limits overriding the defaults of a kind of request
+    pub request_limits: HashMap<RequestKind, RequestLimits>,
+    /// Max time spent completing the in-flight record writes and flushing the state on shutdown
+    pub max_drain_time: Option<Duration>,
+    /// Set to true once the node has been drained on shutdown
+    pub shutdown_complete_tx: tokio::sync::watch::Sender<bool>,
     #[cfg(feature = "open-metrics")]
     pub metrics_registries: MetricsRegistries,
     #[cfg(feature = "open-metrics")]
@@ -137,26 +172,54 @@ pub(super) fn init_driver(
         .set_provider_publication_interval(None);
 
     let store_cfg = {
-        let storage_dir_path = config.root_dir.join("record_store");
+        let peer_id = PeerId::from(config.keypair.public());
+        let root_storage_dir_path = config.root_dir.join("record_store");
+        let mut storage_dir_paths: Vec<PathBuf> = if config.data_dirs.is_empty() {
+            vec![root_storage_dir_path.clone()]
+        } else {
+            // Several nodes can share the same disks, hence the peer id in the path
+            config
+                .data_dirs
+                .iter()
+                .map(|dir| dir.join(peer_id.to_string()).join("record_store"))
+                .collect()
+        };
         // In case the node instanace is restarted for a different version of network,
         // the previous storage folder shall be wiped out,
         // to avoid bring old data into new network.
-        check_and_wipe_storage_dir_if_necessary(
+        let wiped = check_and_wipe_storage_dir_if_necessary(
             config.root_dir.clone(),
-            storage_dir_path.clone(),
+            storage_dir_paths[0].clone(),
             get_network_id_str(),
         )?;
+        // The records stored under the root dir before sharding them across the data dirs are
+        // moved to their data dir
+        let legacy_storage_dirs: Vec<PathBuf> = if storage_dir_paths
+            .contains(&root_storage_dir_path)
+            || !root_storage_dir_path.is_dir()
+        {
+            vec![]
+        } else {
+            vec![root_storage_dir_path]
+        };
+        if wiped {
+            for extra_dir in storage_dir_paths.iter().skip(1).chain(&legacy_storage_dirs) {
+                let _ = fs::remove_dir_all(extra_dir);
+            }
+        }
 
         // Configures the disk_store to store records under the provided path and increase the max record size
         // The storage dir is appendixed with key_version str to avoid bringing records from old network into new
 
-        if let Err(error) = std::fs::create_dir_all(&storage_dir_path) {
-            return Err(NetworkError::FailedToCreateRecordStoreDir {
-                path: storage_dir_path,
-                source: error,
-            });
+        for storage_dir_path in &storage_dir_paths {
+            if let Err(error) = std::fs::create_dir_all(storage_dir_path) {
+                return Err(NetworkError::FailedToCreateRecordStoreDir {
+                    path: storage_dir_path.clone(),
+                    source: error,
+                });
+            }
         }
-        let peer_id = PeerId::from(config.keypair.public());
+        let storage_dir_path = storage_dir_paths.remove(0);
         let encryption_seed: [u8; 16] = peer_id
             .to_bytes()
             .get(..16)
@@ -166,14 +229,19 @@ pub(super) fn init_driver(
         NodeRecordStoreConfig {
             max_value_bytes: MAX_PACKET_SIZE, // TODO, does this need to be _less_ than MAX_PACKET_SIZE
             storage_dir: storage_dir_path,
+            extra_storage_dirs: storage_dir_paths,
+            legacy_storage_dirs,
             historic_quote_dir: config.root_dir.clone(),
             encryption_seed,
+            hot_records_cache_size: config.hot_records_cache_size.unwrap_or_default(),
+            storage_quotas: config.storage_quotas.clone(),
             ..Default::default()
         }
     };
 
     // Listen on the provided address
     let listen_socket_addr = config.listen_addr;
+    let tcp_fallback = config.tcp_fallback;
 
     let (events_receiver, mut swarm_driver) =
         init_swarm_driver(kad_cfg, store_cfg, ProtocolSupport::Full, config);
@@ -186,6 +254,14 @@ pub(super) fn init_driver(
         .listen_on(addr_quic)
         .expect("Multiaddr should be supported by our configured transports");
 
+    if tcp_fallback {
+        let addr_tcp =
+            Multiaddr::from(listen_socket_addr.ip()).with(Protocol::Tcp(listen_socket_addr.port()));
+        swarm_driver
+            .listen_on(addr_tcp)
+            .expect("Multiaddr should be supported by our configured transports");
+    }
+
     Ok((swarm_driver, events_receiver))
 }
 
@@ -218,9 +294,13 @@ fn init_swarm_driver(
 
     // ==== Transport ====
     #[cfg(feature = "open-metrics")]
-    let main_transport = transport::build_transport(&config.keypair, &mut metrics_registries);
+    let main_transport = transport::build_transport(
+        &config.keypair,
+        config.tcp_fallback,
+        &mut metrics_registries,
+    );
     #[cfg(not(feature = "open-metrics"))]
-    let main_transport = transport::build_transport(&config.keypair);
+    let main_transport = transport::build_transport(&config.keypair, config.tcp_fallback);
     let transport = if !config.local {
         debug!("Preventing non-global dials");
         // Wrap upper in a transport that prevents dialing local addresses.
@@ -311,6 +391,8 @@ fn init_swarm_driver(
     let (network_swarm_cmd_sender, network_swarm_cmd_receiver) =
         mpsc::channel(NETWORKING_CHANNEL_SIZE);
     let (local_swarm_cmd_sender, local_swarm_cmd_receiver) = mpsc::channel(NETWORKING_CHANNEL_SIZE);
+    let (background_swarm_cmd_sender, background_swarm_cmd_receiver) =
+        mpsc::channel(BACKGROUND_CMD_CHANNEL_SIZE);
 
     // Kademlia Behaviour
     let kademlia = {
@@ -324,6 +406,21 @@ fn init_swarm_driver(
             #[cfg(feature = "open-metrics")]
             record_stored_metrics,
         );
+        #[cfg(feature = "open-metrics")]
+        let node_record_store = {
+            let mut store = node_record_store;
+            if let Some(recorder) = &metrics_recorder {
+                store.set_hot_records_cache_metrics(HotRecordCacheMetrics {
+                    hits: recorder.hot_records_cache_hits.clone(),
+                    misses: recorder.hot_records_cache_misses.clone(),
+                });
+                store.set_storage_quota_metrics(StorageQuotaMetrics {
+                    records_per_type: recorder.records_stored_per_type.clone(),
+                    quota_rejections: recorder.storage_quota_rejections.clone(),
+                });
+            }
+            store
+        };
 
         let store = node_record_store;
         debug!("Using Kademlia with NodeRecordStore!");
@@ -355,21 +452,24 @@ fn init_swarm_driver(
     .into(); // Into `Toggle<T>`
 
     let relay_server = if !config.relay_client {
-        let relay_server_cfg = relay::Config {
-            max_reservations: 128,             // Amount of peers we are relaying for
-            max_circuits: 1024, // The total amount of relayed connections at any given moment.
-            max_circuits_per_peer: 256, // Amount of relayed connections per peer (both dst and src)
-            circuit_src_rate_limiters: vec![], // No extra rate limiting for now
-            // We should at least be able to relay packets with chunks etc.
-            max_circuit_bytes: MAX_PACKET_SIZE as u64,
-            ..Default::default()
-        };
-        Some(libp2p::relay::Behaviour::new(peer_id, relay_server_cfg))
+        Some(relay_server_behaviour(peer_id))
     } else {
         None
     }
     .into();
 
+    // Peers are asked to dial the node back, to tell whether it is reachable. Every node also
+    // serves these dial-back requests.
+    let autonat = libp2p::autonat::Behaviour::new(
+        peer_id,
+        libp2p::autonat::Config {
+            boot_delay: AUTONAT_BOOT_DELAY,
+            refresh_interval: AUTONAT_REFRESH_INTERVAL,
+            only_global_ips: !config.local,
+            ..Default::default()
+        },
+    );
+
     let behaviour = NodeBehaviour {
         blocklist: libp2p::allow_block_list::Behaviour::default(),
         do_not_disturb: crate::networking::driver::behaviour::do_not_disturb::Behaviour::default(),
@@ -377,6 +477,7 @@ fn init_swarm_driver(
         // clients.
         relay_client: relay_behaviour,
         relay_server,
+        autonat,
         upnp,
         request_response,
         kademlia,
@@ -388,7 +489,11 @@ fn init_swarm_driver(
 
     let swarm = Swarm::new(transport, behaviour, peer_id, swarm_config);
 
-    let replication_fetcher = ReplicationFetcher::new(peer_id, network_event_sender.clone());
+    let mut replication_fetcher = ReplicationFetcher::new(peer_id, network_event_sender.clone());
+    if let Some(max_parallel_fetches) = config.replication_max_parallel_fetches {
+        info!("Replication fetcher max parallel fetches set to {max_parallel_fetches}");
+        replication_fetcher.set_max_parallel_fetches(max_parallel_fetches);
+    }
 
     // Enable relay manager to allow the node to act as a relay client and connect via relay servers to the network
     let relay_manager = if config.relay_client {
@@ -437,6 +542,8 @@ fn init_swarm_driver(
         network_cmd_receiver: network_swarm_cmd_receiver,
         local_cmd_sender: local_swarm_cmd_sender,
         local_cmd_receiver: local_swarm_cmd_receiver,
+        background_cmd_sender: background_swarm_cmd_sender,
+        background_cmd_receiver: background_swarm_cmd_receiver,
         event_sender: network_event_sender,
         pending_get_closest_peers: Default::default(),
         pending_requests: Default::default(),
@@ -457,16 +564,39 @@ fn init_swarm_driver(
         peers_version: Default::default(),
         dial_queue: Default::default(),
         blocklist_cache: CircularVec::new(BLOCKLIST_CACHE_SIZE),
+        peer_reputation: PeerReputation::new(&config.root_dir),
+        behaviour_health: Default::default(),
+        reachability_checker: ReachabilityChecker::new(),
+        relay_fallback: config.relay_fallback,
+        in_relay_fallback: false,
+        hibernating: false,
+        max_drain_time: config.max_drain_time.unwrap_or(DEFAULT_MAX_DRAIN_TIME),
+        shutdown_complete_tx: config.shutdown_complete_tx,
     };
 
     (network_event_receiver, swarm_driver)
 }
 
+/// Returns true if the storage dir was wiped out.
+/// The relay server run by the nodes which are not relay clients.
+pub(crate) fn relay_server_behaviour(peer_id: PeerId) -> relay::Behaviour {
+    let relay_server_cfg = relay::Config {
+        max_reservations: 128,             // Amount of peers we are relaying for
+        max_circuits: 1024, // The total amount of relayed connections at any given moment.
+        max_circuits_per_peer: 256, // Amount of relayed connections per peer (both dst and src)
+        circuit_src_rate_limiters: vec![], // No extra rate limiting for now
+        // We should at least be able to relay packets with chunks etc.
+        max_circuit_bytes: MAX_PACKET_SIZE as u64,
+        ..Default::default()
+    };
+    relay::Behaviour::new(peer_id, relay_server_cfg)
+}
+
 fn check_and_wipe_storage_dir_if_necessary(
     root_dir: PathBuf,
     storage_dir_path: PathBuf,
     cur_version_str: String,
-) -> Result<()> {
+) -> Result<bool> {
     let mut prev_version_str = String::new();
     let version_file = root_dir.join("network_key_version");
     {
@@ -498,9 +628,10 @@ fn check_and_wipe_storage_dir_if_necessary(
             .open(version_file.clone())?;
         info!("Writing cur_version {cur_version_str:?} into version file at {version_file:?}");
         file.write_all(cur_version_str.as_bytes())?;
+        return Ok(true);
     }
 
-    Ok(())
+    Ok(false)
 }
 
 #[cfg(test)]
@@ -20,15 +20,31 @@ use futures::StreamExt;
 use futures::future::select_all;
 use futures::stream::FuturesUnordered;
 use libp2p::kad::{KBucketDistance, Record, RecordKey};
+use libp2p::request_response::OutboundFailure;
 use libp2p::swarm::ConnectionId;
 use libp2p::{Multiaddr, PeerId, identity::Keypair};
+#[cfg(feature = "open-metrics")]
+use prometheus_client::metrics::counter::Counter;
+use tokio::sync::mpsc::error::TrySendError;
 use tokio::sync::{mpsc, oneshot};
-use tokio::time::sleep;
+use tokio::time::{sleep, timeout};
 
 use super::driver::event::MsgResponder;
 use super::error::{NetworkError, Result};
-use super::interface::{LocalSwarmCmd, NetworkSwarmCmd};
-use super::{Addresses, NetworkEvent, NodeIssue, SwarmLocalState};
+use super::interface::{
+    BackgroundSwarmCmd, CmdPriority, LocalSwarmCmd, NetworkSwarmCmd, SwarmCmdQueue,
+};
+#[cfg(feature = "open-metrics")]
+use super::metrics::{
+    cmd_queue::{SwarmCmdQueueDepths, SwarmCmdQueueLabels},
+    latency::{OperationLatencies, OperationLatencyLabels},
+    requests::{RequestOutcome, RequestOutcomeLabels, RequestOutcomes},
+};
+use super::request_limits::RequestLimiter;
+use super::{
+    Addresses, BehaviourHealth, ChurnStats, LatencyOperation, LatencyOutcome, NetworkEvent,
+    NodeIssue, Reachability, RecordStoreStats, RequestKind, SwarmLocalState,
+};
 
 mod init;
 
@@ -39,7 +55,7 @@ const CLOSEST_PEERS_RETRY_MIN_WAIT_SECS: u64 = 2;
 /// Maximum backoff wait time in seconds between retry attempts
 const CLOSEST_PEERS_RETRY_MAX_WAIT_SECS: u64 = 8;
 
-pub(crate) use init::NetworkConfig;
+pub(crate) use init::{NetworkConfig, relay_server_behaviour};
 
 #[derive(Clone, Debug)]
 /// API to interact with the underlying Swarm
@@ -53,8 +69,16 @@ pub(crate) struct Network {
 struct NetworkInner {
     network_swarm_cmd_sender: mpsc::Sender<NetworkSwarmCmd>,
     local_swarm_cmd_sender: mpsc::Sender<LocalSwarmCmd>,
+    background_swarm_cmd_sender: mpsc::Sender<BackgroundSwarmCmd>,
     peer_id: PeerId,
     keypair: Keypair,
+    request_limiter: RequestLimiter,
+    #[cfg(feature = "open-metrics")]
+    operation_latencies: Option<OperationLatencies>,
+    #[cfg(feature = "open-metrics")]
+    request_outcomes: Option<RequestOutcomes>,
+    #[cfg(feature = "open-metrics")]
+    swarm_cmd_queue_metrics: Option<(SwarmCmdQueueDepths, Counter)>,
 }
 
 impl Network {
@@ -65,17 +89,48 @@ impl Network {
         let peer_id = PeerId::from(config.keypair.public());
         let keypair = config.keypair.clone();
         let shutdown_rx = config.shutdown_rx.clone();
+        let request_limiter = RequestLimiter::new(&config.request_limits);
 
         // setup the swarm driver
         let (swarm_driver, network_event_receiver) = init::init_driver(config)?;
 
+        #[cfg(feature = "open-metrics")]
+        let operation_latencies = swarm_driver
+            .metrics_recorder
+            .as_ref()
+            .map(|metrics_recorder| metrics_recorder.operation_latencies.clone());
+        #[cfg(feature = "open-metrics")]
+        let request_outcomes = swarm_driver
+            .metrics_recorder
+            .as_ref()
+            .map(|metrics_recorder| metrics_recorder.request_outcomes.clone());
+        #[cfg(feature = "open-metrics")]
+        let swarm_cmd_queue_metrics =
+            swarm_driver
+                .metrics_recorder
+                .as_ref()
+                .map(|metrics_recorder| {
+                    (
+                        metrics_recorder.swarm_cmd_queue_depths.clone(),
+                        metrics_recorder.swarm_cmds_rejected.clone(),
+                    )
+                });
+
         // create a new network instance
         let network = Network {
             inner: Arc::new(NetworkInner {
                 network_swarm_cmd_sender: swarm_driver.network_cmd_sender.clone(),
                 local_swarm_cmd_sender: swarm_driver.local_cmd_sender.clone(),
+                background_swarm_cmd_sender: swarm_driver.background_cmd_sender.clone(),
                 peer_id,
                 keypair,
+                request_limiter,
+                #[cfg(feature = "open-metrics")]
+                operation_latencies,
+                #[cfg(feature = "open-metrics")]
+                request_outcomes,
+                #[cfg(feature = "open-metrics")]
+                swarm_cmd_queue_metrics,
             }),
         };
 
@@ -95,6 +150,44 @@ impl Network {
         &self.inner.keypair
     }
 
+    /// Record how long an operation took, only when the metrics server is enabled.
+    #[cfg_attr(not(feature = "open-metrics"), allow(unused_variables))]
+    pub(crate) fn record_latency(
+        &self,
+        operation: LatencyOperation,
+        outcome: LatencyOutcome,
+        elapsed: Duration,
+    ) {
+        #[cfg(feature = "open-metrics")]
+        if let Some(operation_latencies) = &self.inner.operation_latencies {
+            operation_latencies
+                .get_or_create(&OperationLatencyLabels { operation, outcome })
+                .observe(elapsed.as_secs_f64());
+        }
+    }
+
+    /// Count the outcome of a request sent to a peer, only when the metrics server is enabled.
+    #[cfg_attr(not(feature = "open-metrics"), allow(unused_variables))]
+    fn record_request_outcome(
+        &self,
+        kind: RequestKind,
+        result: &Result<(Response, Option<ConnectionInfo>)>,
+    ) {
+        #[cfg(feature = "open-metrics")]
+        if let Some(request_outcomes) = &self.inner.request_outcomes {
+            let outcome = match result {
+                Ok(_) => RequestOutcome::Success,
+                Err(NetworkError::OutboundError(OutboundFailure::Timeout)) => {
+                    RequestOutcome::Timeout
+                }
+                Err(_) => RequestOutcome::Failure,
+            };
+            let _ = request_outcomes
+                .get_or_create(&RequestOutcomeLabels { kind, outcome })
+                .inc();
+        }
+    }
+
     /// Signs the given data with the node's keypair.
     pub(crate) fn sign(&self, msg: &[u8]) -> Result<Vec<u8>> {
         self.keypair().sign(msg).map_err(NetworkError::from)
@@ -202,6 +295,23 @@ impl Network {
             .map_err(|e| NetworkError::EventChannelFailure(format!("{e:?}")))
     }
 
+    /// Read a held `Record` straight from its file on disk, bypassing the in-memory caches.
+    /// Returns `None` if the record is not held, or its file is missing or cannot be decrypted.
+    pub(crate) async fn read_local_record_from_disk(
+        &self,
+        key: &RecordKey,
+    ) -> Result<Option<Record>> {
+        let (sender, receiver) = oneshot::channel();
+        self.send_local_swarm_cmd(LocalSwarmCmd::ReadLocalRecordFromDisk {
+            key: key.clone(),
+            sender,
+        });
+
+        receiver
+            .await
+            .map_err(|e| NetworkError::EventChannelFailure(format!("{e:?}")))
+    }
+
     /// Whether the target peer is considered blacklisted by self
     pub(crate) async fn is_peer_shunned(&self, target: NetworkAddress) -> Result<bool> {
         let (sender, receiver) = oneshot::channel();
@@ -218,6 +328,11 @@ impl Network {
         self.send_local_swarm_cmd(LocalSwarmCmd::FetchCompleted((key, record_type)))
     }
 
+    /// Remove a `Record` from the local RecordStore, as its payment is no longer valid
+    pub(crate) fn remove_unpaid_local_record(&self, key: RecordKey) {
+        self.send_local_swarm_cmd(LocalSwarmCmd::RemoveUnpaidLocalRecord { key })
+    }
+
     /// Put `Record` to the local RecordStore
     /// Must be called after the validations are performed on the Record
     pub(crate) fn put_local_record(&self, record: Record, is_client_put: bool) {
@@ -264,21 +379,40 @@ impl Network {
     /// then the `Request` is forwarded to itself and handled, and a corresponding `Response` is created
     /// and returned to itself. Hence the flow remains the same and there is no branching at the upper
     /// layers.
+    ///
+    /// The timeout and concurrency limits of the kind of request are applied.
     pub(crate) async fn send_request(
         &self,
         req: Request,
         peer: PeerId,
         addrs: Addresses,
     ) -> Result<(Response, Option<ConnectionInfo>)> {
-        let (sender, receiver) = oneshot::channel();
+        let kind = RequestKind::from(&req);
+        let (request_timeout, semaphore) = self.inner.request_limiter.get(kind);
+        let _permit = semaphore
+            .acquire_owned()
+            .await
+            .map_err(|_| NetworkError::InternalMsgChannelDropped)?;
 
+        let (sender, receiver) = oneshot::channel();
         self.send_network_swarm_cmd(NetworkSwarmCmd::SendRequest {
             req: req.clone(),
             peer,
             addrs,
             sender: Some(sender),
         });
-        receiver.await?
+
+        let result = match timeout(request_timeout, receiver).await {
+            Ok(response) => response
+                .map_err(NetworkError::from)
+                .and_then(|result| result),
+            Err(_elapsed) => {
+                warn!("Request {kind:?} to {peer:?} timed out after {request_timeout:?}");
+                Err(NetworkError::OutboundError(OutboundFailure::Timeout))
+            }
+        };
+        self.record_request_outcome(kind, &result);
+        result
     }
 
     /// Send a `Response` through the channel opened by the requester.
@@ -346,7 +480,7 @@ impl Network {
     ) -> Result<Vec<(NetworkAddress, Vec<Multiaddr>)>> {
         use ant_protocol::messages::{Query, QueryResponse};
 
-        let req = Request::Query(Query::GetClosestPeers {
+        let req = Request::from(Query::GetClosestPeers {
             key: addr.clone(),
             num_of_peers,
             range: None,
@@ -612,7 +746,7 @@ impl Network {
                         let network = self.clone();
                         async move {
                             let req =
-                                Request::Query(Query::GetVersion(NetworkAddress::from(peer_id)));
+                                Request::from(Query::GetVersion(NetworkAddress::from(peer_id)));
                             let result = network.send_request(req, peer_id, addrs.clone()).await;
                             match result {
                                 Ok((
@@ -826,10 +960,77 @@ impl Network {
         Ok(density)
     }
 
+    /// Get the reputation scores of the peers reported with issues, lowest first.
+    pub(crate) async fn get_peer_scores(&self) -> Result<Vec<(PeerId, i32)>> {
+        let (sender, receiver) = oneshot::channel();
+        self.send_local_swarm_cmd(LocalSwarmCmd::GetPeerScores { sender });
+
+        receiver
+            .await
+            .map_err(|_e| NetworkError::InternalMsgChannelDropped)
+    }
+
+    /// Get the health of each of the swarm behaviours.
+    pub(crate) async fn get_behaviour_health(&self) -> Result<BehaviourHealth> {
+        let (sender, receiver) = oneshot::channel();
+        self.send_local_swarm_cmd(LocalSwarmCmd::GetBehaviourHealth { sender });
+
+        receiver
+            .await
+            .map_err(|_e| NetworkError::InternalMsgChannelDropped)
+    }
+
+    /// Get the churn analytics of the routing table.
+    pub(crate) async fn get_churn_stats(&self) -> Result<ChurnStats> {
+        let (sender, receiver) = oneshot::channel();
+        self.send_local_swarm_cmd(LocalSwarmCmd::GetChurnStats { sender });
+
+        receiver
+            .await
+            .map_err(|_e| NetworkError::InternalMsgChannelDropped)
+    }
+
+    /// Get the outcome of the reachability check of the node.
+    pub(crate) async fn get_reachability(&self) -> Result<Reachability> {
+        let (sender, receiver) = oneshot::channel();
+        self.send_local_swarm_cmd(LocalSwarmCmd::GetReachability { sender });
+
+        receiver
+            .await
+            .map_err(|_e| NetworkError::InternalMsgChannelDropped)
+    }
+
+    /// Get the statistics of the record store, with a random sample of the held addresses.
+    pub(crate) async fn get_record_store_stats(
+        &self,
+        sample_size: usize,
+    ) -> Result<RecordStoreStats> {
+        let (sender, receiver) = oneshot::channel();
+        self.send_local_swarm_cmd(LocalSwarmCmd::GetRecordStoreStats {
+            sample_size,
+            sender,
+        });
+
+        receiver
+            .await
+            .map_err(|_e| NetworkError::InternalMsgChannelDropped)
+    }
+
+    /// Enter or leave hibernation. See [`LocalSwarmCmd::SetHibernation`].
+    pub(crate) fn set_hibernation(&self, hibernating: bool) {
+        self.send_local_swarm_cmd(LocalSwarmCmd::SetHibernation { hibernating })
+    }
+
     /// Helper to send NetworkSwarmCmd
     fn send_network_swarm_cmd(&self, cmd: NetworkSwarmCmd) {
+        if cmd.priority() == CmdPriority::Low {
+            self.send_background_swarm_cmd(BackgroundSwarmCmd::Network(cmd));
+            return;
+        }
+
         let swarm_cmd_sender = self.inner.network_swarm_cmd_sender.clone();
         let capacity = swarm_cmd_sender.capacity();
+        self.record_queue_depth(SwarmCmdQueue::Network, &swarm_cmd_sender);
 
         if capacity == 0 {
             error!(
@@ -848,9 +1049,51 @@ impl Network {
 
     /// Helper to send LocalSwarmCmd
     fn send_local_swarm_cmd(&self, cmd: LocalSwarmCmd) {
+        if cmd.priority() == CmdPriority::Low {
+            self.send_background_swarm_cmd(BackgroundSwarmCmd::Local(cmd));
+            return;
+        }
+
         let swarm_cmd_sender = self.inner.local_swarm_cmd_sender.clone();
+        self.record_queue_depth(SwarmCmdQueue::Local, &swarm_cmd_sender);
         send_local_swarm_cmd(swarm_cmd_sender, cmd);
     }
+
+    /// Helper to queue a low priority cmd.
+    /// Rather than waiting for capacity, the cmd is rejected when the queue is full.
+    fn send_background_swarm_cmd(&self, cmd: BackgroundSwarmCmd) {
+        let swarm_cmd_sender = &self.inner.background_swarm_cmd_sender;
+        self.record_queue_depth(SwarmCmdQueue::Background, swarm_cmd_sender);
+
+        match swarm_cmd_sender.try_send(cmd) {
+            Ok(()) => {}
+            Err(TrySendError::Full(cmd)) => {
+                error!("{}: {cmd:?}", NetworkError::SwarmCmdQueueOverloaded);
+                #[cfg(feature = "open-metrics")]
+                if let Some((_, swarm_cmds_rejected)) = &self.inner.swarm_cmd_queue_metrics {
+                    let _ = swarm_cmds_rejected.inc();
+                }
+                if let BackgroundSwarmCmd::Network(cmd) = cmd {
+                    cmd.reject();
+                }
+            }
+            Err(TrySendError::Closed(cmd)) => {
+                error!("Failed to send SwarmCmd, the channel is closed: {cmd:?}");
+            }
+        }
+    }
+
+    /// Record the number of cmds waiting in the queue, only when the metrics server is enabled.
+    #[cfg_attr(not(feature = "open-metrics"), allow(unused_variables))]
+    fn record_queue_depth<T>(&self, queue: SwarmCmdQueue, sender: &mpsc::Sender<T>) {
+        #[cfg(feature = "open-metrics")]
+        if let Some((swarm_cmd_queue_depths, _)) = &self.inner.swarm_cmd_queue_metrics {
+            let depth = sender.max_capacity().saturating_sub(sender.capacity());
+            let _ = swarm_cmd_queue_depths
+                .get_or_create(&SwarmCmdQueueLabels { queue })
+                .set(depth as i64);
+        }
+    }
 }
 
 pub(crate) fn send_local_swarm_cmd(
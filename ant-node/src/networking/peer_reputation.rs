@@ -0,0 +1,279 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use crate::networking::NodeIssue;
+use libp2p::PeerId;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::spawn;
+
+const PEER_REPUTATION_FILENAME: &str = "peer_reputation";
+
+/// Interval over which the scores recover by `SCORE_RECOVERY_STEP`.
+pub(crate) const PEER_REPUTATION_DECAY_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How much a penalised score recovers every `PEER_REPUTATION_DECAY_INTERVAL`.
+const SCORE_RECOVERY_STEP: i32 = 1;
+
+/// Peers scoring at or below this are removed from the RT, and not re-added until they recover.
+const DEMOTION_THRESHOLD: i32 = -40;
+
+/// Peers scoring at or below this are disconnected as well, and not dialed for a while.
+const DISCONNECTION_THRESHOLD: i32 = -80;
+
+/// Lowest possible score, to bound the time it takes to recover.
+const MIN_SCORE: i32 = -100;
+
+/// Action to take against a peer whose score crossed a threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ReputationAction {
+    /// Remove the peer from the RT
+    Demote,
+    /// Remove the peer from the RT, disconnect and temporarily stop dialing it
+    Disconnect,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PersistedScores {
+    /// Seconds since UNIX epoch when the scores were written
+    timestamp: u64,
+    scores: Vec<(Vec<u8>, i32)>,
+}
+
+/// Tracks a score per peer, lowered by each issue the peer is reported with.
+///
+/// A peer with no reported issues has a neutral score of 0. Scores slowly recover back to 0 over
+/// time, so that a demotion or disconnection is only temporary. Only penalised peers are tracked,
+/// and the scores are persisted under the node's root dir to survive restarts.
+pub(crate) struct PeerReputation {
+    scores: HashMap<PeerId, i32>,
+    file_path: PathBuf,
+    has_changed: bool,
+}
+
+impl PeerReputation {
+    /// Create a new `PeerReputation`, restoring the scores persisted under `root_dir` if any.
+    pub(crate) fn new(root_dir: &Path) -> Self {
+        let file_path = root_dir.join(PEER_REPUTATION_FILENAME);
+        let scores = Self::restore_scores(&file_path).unwrap_or_default();
+        if !scores.is_empty() {
+            info!("Restored the reputation scores of {} peers", scores.len());
+        }
+        Self {
+            scores,
+            file_path,
+            has_changed: false,
+        }
+    }
+
+    /// Lower the score of the peer according to the reported issue.
+    /// Returns the action to take if the new score crossed a threshold.
+    pub(crate) fn penalise(
+        &mut self,
+        peer_id: PeerId,
+        issue: &NodeIssue,
+    ) -> Option<ReputationAction> {
+        let score = self.scores.entry(peer_id).or_default();
+        let previous = *score;
+        *score = previous.saturating_sub(issue_penalty(issue)).max(MIN_SCORE);
+        self.has_changed = true;
+        debug!(
+            "Peer {peer_id:?} reputation score lowered from {previous} to {score} due to {issue:?}"
+        );
+
+        if *score <= DISCONNECTION_THRESHOLD && previous > DISCONNECTION_THRESHOLD {
+            Some(ReputationAction::Disconnect)
+        } else if *score <= DEMOTION_THRESHOLD && previous > DEMOTION_THRESHOLD {
+            Some(ReputationAction::Demote)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the peer scores too low to be part of our RT.
+    pub(crate) fn is_demoted(&self, peer_id: &PeerId) -> bool {
+        self.scores
+            .get(peer_id)
+            .is_some_and(|score| *score <= DEMOTION_THRESHOLD)
+    }
+
+    /// The scores of all the penalised peers, lowest first.
+    pub(crate) fn scores(&self) -> Vec<(PeerId, i32)> {
+        let mut scores: Vec<_> = self
+            .scores
+            .iter()
+            .map(|(peer_id, score)| (*peer_id, *score))
+            .collect();
+        scores.sort_by_key(|(_, score)| *score);
+        scores
+    }
+
+    /// Recover the scores by one step, forgetting about the peers back to neutral.
+    /// Persists the scores if any have changed since the last call.
+    pub(crate) fn decay(&mut self) {
+        self.recover(1);
+        if self.has_changed {
+            self.flush();
+            self.has_changed = false;
+        }
+    }
+
+    fn recover(&mut self, steps: i32) {
+        if self.scores.is_empty() {
+            return;
+        }
+        let recovery = SCORE_RECOVERY_STEP.saturating_mul(steps);
+        self.scores.retain(|_peer_id, score| {
+            *score = score.saturating_add(recovery).min(0);
+            *score < 0
+        });
+        self.has_changed = true;
+    }
+
+    fn flush(&self) {
+        let persisted = PersistedScores {
+            timestamp: now_secs(),
+            scores: self
+                .scores
+                .iter()
+                .map(|(peer_id, score)| (peer_id.to_bytes(), *score))
+                .collect(),
+        };
+        let bytes = match rmp_serde::to_vec(&persisted) {
+            Ok(bytes) => bytes,
+            Err(err) => {
+                warn!("Failed to serialise peer reputation scores: {err:?}");
+                return;
+            }
+        };
+        let file_path = self.file_path.clone();
+
+        #[allow(clippy::let_underscore_future)]
+        let _ = spawn(async move {
+            if let Err(err) = tokio::fs::write(&file_path, bytes).await {
+                warn!("Failed to persist peer reputation scores to {file_path:?}: {err:?}");
+            }
+        });
+    }
+
+    fn restore_scores(file_path: &Path) -> Option<HashMap<PeerId, i32>> {
+        let file = std::fs::File::open(file_path).ok()?;
+        let persisted: PersistedScores = rmp_serde::from_read(&file).ok()?;
+
+        let mut reputation = Self {
+            scores: persisted
+                .scores
+                .into_iter()
+                .filter_map(|(bytes, score)| {
+                    PeerId::from_bytes(&bytes)
+                        .ok()
+                        .map(|peer_id| (peer_id, score))
+                })
+                .collect(),
+            file_path: file_path.to_path_buf(),
+            has_changed: false,
+        };
+
+        // The scores keep recovering while the node is down.
+        let elapsed = now_secs().saturating_sub(persisted.timestamp);
+        let steps = elapsed / PEER_REPUTATION_DECAY_INTERVAL.as_secs();
+        reputation.recover(i32::try_from(steps).unwrap_or(i32::MAX));
+
+        Some(reputation.scores)
+    }
+}
+
+/// How much the score of a peer is lowered for each reported issue.
+fn issue_penalty(issue: &NodeIssue) -> i32 {
+    match issue {
+        NodeIssue::ConnectionIssue | NodeIssue::RequestTimeout => 5,
+        NodeIssue::ReplicationFailure | NodeIssue::BadQuoting | NodeIssue::CloseNodesShunning => 10,
+        NodeIssue::InvalidRecord | NodeIssue::FailedChunkProofCheck => 20,
+        NodeIssue::WrongPeerId | NodeIssue::ProtocolViolation => 40,
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_penalise_crosses_thresholds_once() {
+        let mut reputation =
+            PeerReputation::new(&std::env::temp_dir().join(PeerId::random().to_string()));
+        let peer_id = PeerId::random();
+
+        assert_eq!(
+            reputation.penalise(peer_id, &NodeIssue::InvalidRecord),
+            None
+        );
+        assert_eq!(
+            reputation.penalise(peer_id, &NodeIssue::InvalidRecord),
+            Some(ReputationAction::Demote)
+        );
+        assert!(reputation.is_demoted(&peer_id));
+        assert_eq!(
+            reputation.penalise(peer_id, &NodeIssue::InvalidRecord),
+            None
+        );
+        assert_eq!(
+            reputation.penalise(peer_id, &NodeIssue::InvalidRecord),
+            Some(ReputationAction::Disconnect)
+        );
+        assert_eq!(
+            reputation.penalise(peer_id, &NodeIssue::ProtocolViolation),
+            None
+        );
+        assert_eq!(reputation.scores(), vec![(peer_id, MIN_SCORE)]);
+    }
+
+    #[test]
+    fn test_scores_recover_to_neutral() {
+        let mut reputation =
+            PeerReputation::new(&std::env::temp_dir().join(PeerId::random().to_string()));
+        let peer_id = PeerId::random();
+
+        let _ = reputation.penalise(peer_id, &NodeIssue::WrongPeerId);
+        assert!(reputation.is_demoted(&peer_id));
+
+        reputation.recover(1);
+        assert!(!reputation.is_demoted(&peer_id));
+
+        reputation.recover(100);
+        assert!(reputation.scores().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_scores_are_restored() {
+        let root_dir = std::env::temp_dir().join(PeerId::random().to_string());
+        std::fs::create_dir_all(&root_dir).expect("Failed to create root dir");
+
+        let mut reputation = PeerReputation::new(&root_dir);
+        let peer_id = PeerId::random();
+        let _ = reputation.penalise(peer_id, &NodeIssue::InvalidRecord);
+        reputation.flush();
+        // flush writes the file off thread
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        let restored = PeerReputation::new(&root_dir);
+        assert_eq!(restored.scores(), vec![(peer_id, -20)]);
+
+        let _ = std::fs::remove_dir_all(root_dir);
+    }
+}
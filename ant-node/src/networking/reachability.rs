@@ -0,0 +1,294 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Continuous reachability check of the node.
+//!
+//! A node is reachable when its peers manage to dial it directly. The peers dialing the node
+//! without going through a relay are counted over rounds of [`REACHABILITY_CHECK_INTERVAL`], and a
+//! round passes the check when enough distinct peers did. The check goes on for the whole life of
+//! the node, so that a node which becomes unreachable after a change of router or IP address fails
+//! the next round.
+//!
+//! When peers start observing the node at another IP address, a shorter probe round is run right
+//! away, rather than waiting for the end of the current round.
+//!
+//! This check only reports on the reachability of the node. The relay fallback relies on the
+//! active dial-back probes of AutoNAT instead, which don't depend on the addresses the node
+//! advertises.
+
+use crate::networking::relay_manager::is_a_relayed_peer;
+use libp2p::{Multiaddr, PeerId, multiaddr::Protocol};
+#[cfg(feature = "open-metrics")]
+use prometheus_client::encoding::EncodeLabelValue;
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+/// Duration of a round of the reachability check.
+const REACHABILITY_CHECK_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Duration of a probe round, run when the external address of the node may have changed.
+const REACHABILITY_PROBE_DURATION: Duration = Duration::from_secs(2 * 60);
+
+/// Interval over which the driver checks whether the current round is complete.
+pub(crate) const REACHABILITY_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Number of distinct peers which must dial the node directly within a round for it to pass.
+const MIN_INCOMING_PEERS: usize = 3;
+
+/// Outcome of the last round of the reachability check.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReachabilityStatus {
+    /// No round has completed yet
+    #[default]
+    NotChecked,
+    /// Enough peers dialed the node directly
+    Reachable,
+    /// Too few peers dialed the node directly
+    Unreachable,
+}
+
+/// The IP version of an address.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "open-metrics", derive(EncodeLabelValue))]
+pub enum AddressFamily {
+    Ipv4,
+    Ipv6,
+}
+
+impl AddressFamily {
+    fn of(addr: &Multiaddr) -> Option<Self> {
+        addr.iter().find_map(|protocol| match protocol {
+            Protocol::Ip4(_) => Some(Self::Ipv4),
+            Protocol::Ip6(_) => Some(Self::Ipv6),
+            _ => None,
+        })
+    }
+}
+
+/// The reachability of the node, as last checked.
+#[derive(Debug, Clone, Default)]
+pub struct Reachability {
+    /// Outcome of the last completed round
+    pub status: ReachabilityStatus,
+    /// Number of distinct peers which dialed the node directly in the last completed round
+    pub incoming_peers: usize,
+    /// The IP version most of these peers dialed the node over, `None` if none did
+    pub address_family: Option<AddressFamily>,
+    /// Whether the node advertises relayed addresses, i.e. is reached through relays
+    pub relayed: bool,
+    /// Progress of the current round towards passing the check, from 0 to 100
+    pub progress: u8,
+    /// Number of completed rounds
+    pub rounds: u64,
+}
+
+/// Counts the peers dialing the node directly over the current round.
+#[derive(Debug)]
+pub(crate) struct ReachabilityChecker {
+    /// The peers which dialed the node directly in the current round, with the IP version they used
+    incoming_peers: HashMap<PeerId, Option<AddressFamily>>,
+    reachability: Reachability,
+    round_started: Instant,
+    round_duration: Duration,
+    /// When the last probe round started, to not run them back to back
+    last_probe: Option<Instant>,
+}
+
+impl ReachabilityChecker {
+    pub(crate) fn new() -> Self {
+        Self {
+            incoming_peers: HashMap::new(),
+            reachability: Reachability::default(),
+            round_started: Instant::now(),
+            round_duration: REACHABILITY_CHECK_INTERVAL,
+            last_probe: None,
+        }
+    }
+
+    /// Whether the current round has lasted long enough to be completed.
+    pub(crate) fn is_round_due(&self) -> bool {
+        self.round_started.elapsed() >= self.round_duration
+    }
+
+    /// Restart the current round as a shorter probe, as the connections counted so far may have
+    /// been dialed to an address the node is no longer reachable at. Probes are at least a full
+    /// round apart.
+    pub(crate) fn start_probe(&mut self) {
+        if self
+            .last_probe
+            .is_some_and(|last_probe| last_probe.elapsed() < REACHABILITY_CHECK_INTERVAL)
+        {
+            return;
+        }
+        info!("Starting a reachability probe of {REACHABILITY_PROBE_DURATION:?}");
+        self.incoming_peers.clear();
+        self.reachability.progress = 0;
+        self.round_started = Instant::now();
+        self.round_duration = REACHABILITY_PROBE_DURATION;
+        self.last_probe = Some(self.round_started);
+    }
+
+    /// The reachability as of the last completed round, with the progress of the current round.
+    pub(crate) fn reachability(&self) -> &Reachability {
+        &self.reachability
+    }
+
+    /// Record a connection dialed by `peer_id` from `send_back_addr`. Relayed connections are not
+    /// counted, as they do not show that the node can be dialed directly.
+    pub(crate) fn on_incoming_connection(&mut self, peer_id: PeerId, send_back_addr: &Multiaddr) {
+        if is_a_relayed_peer(std::iter::once(send_back_addr)) {
+            return;
+        }
+        let _ = self
+            .incoming_peers
+            .insert(peer_id, AddressFamily::of(send_back_addr));
+        self.reachability.progress = self.progress();
+    }
+
+    fn progress(&self) -> u8 {
+        (self.incoming_peers.len().min(MIN_INCOMING_PEERS) * 100 / MIN_INCOMING_PEERS) as u8
+    }
+
+    /// Complete the current round and start the next one. `relayed` is whether the node currently
+    /// advertises relayed addresses.
+    pub(crate) fn complete_round(&mut self, relayed: bool) {
+        let incoming_peers = std::mem::take(&mut self.incoming_peers);
+        let status = if incoming_peers.len() >= MIN_INCOMING_PEERS {
+            ReachabilityStatus::Reachable
+        } else {
+            ReachabilityStatus::Unreachable
+        };
+
+        let ipv6_peers = incoming_peers
+            .values()
+            .filter(|family| **family == Some(AddressFamily::Ipv6))
+            .count();
+        let ipv4_peers = incoming_peers
+            .values()
+            .filter(|family| **family == Some(AddressFamily::Ipv4))
+            .count();
+        let address_family = if ipv4_peers == 0 && ipv6_peers == 0 {
+            None
+        } else if ipv6_peers > ipv4_peers {
+            Some(AddressFamily::Ipv6)
+        } else {
+            Some(AddressFamily::Ipv4)
+        };
+
+        let round_duration = self.round_started.elapsed();
+        match (self.reachability.status, status) {
+            (ReachabilityStatus::Reachable, ReachabilityStatus::Unreachable) => warn!(
+                "The node is no longer reachable, only {} peers dialed it directly in the last {round_duration:?}",
+                incoming_peers.len()
+            ),
+            (previous, current) if previous != current => info!(
+                "Reachability check: {current:?}, {} peers dialed the node directly in the last {round_duration:?}",
+                incoming_peers.len()
+            ),
+            _ => debug!(
+                "Reachability check: {status:?}, {} peers dialed the node directly",
+                incoming_peers.len()
+            ),
+        }
+
+        self.reachability = Reachability {
+            status,
+            incoming_peers: incoming_peers.len(),
+            address_family,
+            relayed,
+            progress: 0,
+            rounds: self.reachability.rounds + 1,
+        };
+        self.round_started = Instant::now();
+        self.round_duration = REACHABILITY_CHECK_INTERVAL;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addr(addr: &str) -> Multiaddr {
+        addr.parse().unwrap()
+    }
+
+    #[test]
+    fn rounds_should_pass_with_enough_direct_incoming_peers() {
+        let mut checker = ReachabilityChecker::new();
+        assert_eq!(
+            checker.reachability().status,
+            ReachabilityStatus::NotChecked
+        );
+
+        checker.on_incoming_connection(PeerId::random(), &addr("/ip4/1.2.3.4/udp/1200/quic-v1"));
+        checker
+            .on_incoming_connection(PeerId::random(), &addr("/ip6/2001:db8::1/udp/1200/quic-v1"));
+        assert_eq!(checker.reachability().progress, 66);
+        checker.on_incoming_connection(PeerId::random(), &addr("/ip4/5.6.7.8/udp/1200/quic-v1"));
+        assert_eq!(checker.reachability().progress, 100);
+
+        checker.complete_round(false);
+        let reachability = checker.reachability();
+        assert_eq!(reachability.status, ReachabilityStatus::Reachable);
+        assert_eq!(reachability.incoming_peers, 3);
+        assert_eq!(reachability.address_family, Some(AddressFamily::Ipv4));
+        assert_eq!(reachability.progress, 0);
+        assert_eq!(reachability.rounds, 1);
+
+        // The next round starts from scratch
+        checker.complete_round(false);
+        let reachability = checker.reachability();
+        assert_eq!(reachability.status, ReachabilityStatus::Unreachable);
+        assert_eq!(reachability.address_family, None);
+        assert_eq!(reachability.rounds, 2);
+    }
+
+    #[test]
+    fn relayed_connections_should_not_count() {
+        let mut checker = ReachabilityChecker::new();
+        let relayed = addr(
+            "/ip4/1.2.3.4/udp/1200/quic-v1/p2p/12D3KooWGQu92xCXuiK6AysbHn6kHyfXqyzNDxNGnnDTgd56eveq/p2p-circuit",
+        );
+        for _ in 0..MIN_INCOMING_PEERS {
+            checker.on_incoming_connection(PeerId::random(), &relayed);
+        }
+
+        checker.complete_round(true);
+        let reachability = checker.reachability();
+        assert_eq!(reachability.status, ReachabilityStatus::Unreachable);
+        assert_eq!(reachability.incoming_peers, 0);
+        assert!(reachability.relayed);
+    }
+
+    #[test]
+    fn probes_should_restart_the_round_and_be_rate_limited() {
+        let mut checker = ReachabilityChecker::new();
+        assert!(!checker.is_round_due());
+        checker.round_duration = Duration::ZERO;
+        assert!(checker.is_round_due());
+        checker.on_incoming_connection(PeerId::random(), &addr("/ip4/1.2.3.4/udp/1200/quic-v1"));
+
+        checker.start_probe();
+        assert!(!checker.is_round_due());
+        assert_eq!(checker.reachability().progress, 0);
+        assert_eq!(checker.round_duration, REACHABILITY_PROBE_DURATION);
+
+        checker.complete_round(false);
+        assert_eq!(
+            checker.reachability().status,
+            ReachabilityStatus::Unreachable
+        );
+        assert_eq!(checker.round_duration, REACHABILITY_CHECK_INTERVAL);
+
+        // Another probe right after the previous one is ignored
+        checker.start_probe();
+        assert_eq!(checker.round_duration, REACHABILITY_CHECK_INTERVAL);
+    }
+}
@@ -7,7 +7,7 @@
 // permissions and limitations relating to use of the SAFE Network Software.
 #![allow(clippy::mutable_key_type)] // for the Bytes in NetworkAddress
 
-use crate::networking::interface::{LocalSwarmCmd, NetworkEvent};
+use crate::networking::interface::{LocalSwarmCmd, NetworkEvent, RecordStoreStats};
 use crate::networking::log_markers::Marker;
 use crate::networking::network::send_local_swarm_cmd;
 use aes_gcm_siv::{
@@ -30,7 +30,11 @@ use libp2p::{
     },
 };
 #[cfg(feature = "open-metrics")]
-use prometheus_client::metrics::gauge::Gauge;
+use prometheus_client::{
+    encoding::EncodeLabelSet,
+    metrics::{counter::Counter, family::Family, gauge::Gauge},
+};
+use rand::seq::IteratorRandom;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use serde::{Deserialize, Serialize};
 use sha2::Sha256;
@@ -40,7 +44,11 @@ use std::{
     collections::{BTreeMap, HashMap},
     fs,
     path::{Path, PathBuf},
-    time::SystemTime,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::{SystemTime, UNIX_EPOCH},
     vec,
 };
 use tokio::spawn;
@@ -62,6 +70,10 @@ const MAX_RECORDS_CACHE_SIZE: usize = 25;
 /// File name of the recorded historical quoting metrics.
 const HISTORICAL_QUOTING_METRICS_FILENAME: &str = "historic_quoting_metrics";
 
+/// Extension of the files the records are written to before being renamed, so that an abrupt
+/// stop can't leave a partially written record behind.
+const PARTIAL_RECORD_EXTENSION: &str = "partial";
+
 /// Defines when the entries inside the cache shall be pruned to free space up.
 /// Shall be two times of the PERIODIC_REPLICATION_INTERVAL_MAX_S
 const CACHE_TIMEOUT: Duration = Duration::from_secs(360);
@@ -147,6 +159,107 @@ impl RecordCache {
     }
 }
 
+/// LRU cache of the records read from disk, so that popular records are served from memory.
+///
+/// Records are read through `&self` by the `RecordStore` trait, hence the interior mutability.
+struct HotRecordCache {
+    inner: Mutex<HotRecordCacheInner>,
+    capacity: usize,
+    #[cfg(feature = "open-metrics")]
+    metrics: Option<HotRecordCacheMetrics>,
+}
+
+#[derive(Default)]
+struct HotRecordCacheInner {
+    /// Records along with the tick of their last read
+    records: HashMap<Key, (Record, u64)>,
+    tick: u64,
+}
+
+/// Counters of the hot records cache, the hit rate being `hits / (hits + misses)`.
+#[cfg(feature = "open-metrics")]
+pub(crate) struct HotRecordCacheMetrics {
+    pub(crate) hits: Counter,
+    pub(crate) misses: Counter,
+}
+
+/// Metrics of the records held by the store per data type, and of those refused by its storage
+/// quotas.
+#[cfg(feature = "open-metrics")]
+pub(crate) struct StorageQuotaMetrics {
+    pub(crate) records_per_type: Family<RecordTypeLabels, Gauge>,
+    pub(crate) quota_rejections: Family<RecordTypeLabels, Counter>,
+}
+
+#[cfg(feature = "open-metrics")]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub(crate) struct RecordTypeLabels {
+    record_type: DataTypes,
+}
+
+impl HotRecordCache {
+    fn new(capacity: usize) -> Self {
+        HotRecordCache {
+            inner: Mutex::new(HotRecordCacheInner::default()),
+            capacity,
+            #[cfg(feature = "open-metrics")]
+            metrics: None,
+        }
+    }
+
+    fn get(&self, key: &Key) -> Option<Record> {
+        if self.capacity == 0 {
+            return None;
+        }
+        let Ok(mut inner) = self.inner.lock() else {
+            return None;
+        };
+        inner.tick += 1;
+        let tick = inner.tick;
+        let record = inner.records.get_mut(key).map(|(record, last_read)| {
+            *last_read = tick;
+            record.clone()
+        });
+
+        #[cfg(feature = "open-metrics")]
+        if let Some(metrics) = &self.metrics {
+            let _ = match record {
+                Some(_) => metrics.hits.inc(),
+                None => metrics.misses.inc(),
+            };
+        }
+        record
+    }
+
+    fn insert(&self, key: Key, record: Record) {
+        if self.capacity == 0 {
+            return;
+        }
+        let Ok(mut inner) = self.inner.lock() else {
+            return;
+        };
+        if !inner.records.contains_key(&key) && inner.records.len() >= self.capacity {
+            let least_recently_read = inner
+                .records
+                .iter()
+                .min_by_key(|(_key, (_record, last_read))| *last_read)
+                .map(|(key, _)| key.clone());
+            if let Some(evicted) = least_recently_read {
+                let _ = inner.records.remove(&evicted);
+            }
+        }
+        inner.tick += 1;
+        let tick = inner.tick;
+        let _ = inner.records.insert(key, (record, tick));
+    }
+
+    fn remove(&self, key: &Key) {
+        if let Ok(mut inner) = self.inner.lock() {
+            let _ = inner.records.remove(key);
+        }
+    }
+}
+
 /// A `RecordStore` that stores records on disk.
 pub(crate) struct NodeRecordStore {
     /// The address of the peer owning the store
@@ -155,10 +268,14 @@ pub(crate) struct NodeRecordStore {
     config: NodeRecordStoreConfig,
     /// Main records store remains unchanged for compatibility
     records: HashMap<Key, (NetworkAddress, ValidationType, DataTypes)>,
+    /// Number of the held records of each data type, kept along `records`
+    records_of_type: HashMap<DataTypes, usize>,
     /// Additional index organizing records by distance
     records_by_distance: BTreeMap<Distance, Key>,
     /// FIFO simple cache of records to reduce read times
     records_cache: RecordCache,
+    /// LRU cache of the records most read from disk
+    hot_records: HotRecordCache,
     /// Send network events to the node layer.
     network_event_sender: mpsc::Sender<NetworkEvent>,
     /// Send cmds to the network layer. Used to interact with self in an async fashion.
@@ -179,6 +296,12 @@ pub(crate) struct NodeRecordStore {
     timestamp: SystemTime,
     /// Farthest record to self
     farthest_record: Option<(Key, Distance)>,
+    /// Number of records being written to disk
+    pending_writes: Arc<AtomicUsize>,
+    /// Expiry of the temporary records, in seconds since the UNIX epoch
+    expiring_records: HashMap<Key, u64>,
+    #[cfg(feature = "open-metrics")]
+    storage_quota_metrics: Option<StorageQuotaMetrics>,
 }
 
 /// Configuration for a `DiskBackedRecordStore`.
@@ -186,6 +309,12 @@ pub(crate) struct NodeRecordStore {
 pub(crate) struct NodeRecordStoreConfig {
     /// The directory where the records are stored.
     pub storage_dir: PathBuf,
+    /// Further directories, e.g. on other disks, to shard the records across along with
+    /// `storage_dir`, by the hash of their key.
+    pub extra_storage_dirs: Vec<PathBuf>,
+    /// Directories of a previous layout, e.g. the record store under the root dir before the
+    /// records got sharded, whose records are moved to their storage dir on startup.
+    pub legacy_storage_dirs: Vec<PathBuf>,
     /// The directory where the historic quote to be stored
     /// (normally to be the parent dir of the storage_dir)
     pub historic_quote_dir: PathBuf,
@@ -195,6 +324,12 @@ pub(crate) struct NodeRecordStoreConfig {
     pub max_value_bytes: usize,
     /// The maximum number of records to cache in memory.
     pub records_cache_size: usize,
+    /// The maximum number of frequently read records to keep in memory, 0 (the default) to
+    /// disable.
+    pub hot_records_cache_size: usize,
+    /// The maximum fraction, from 0 to 1, of `max_records` the records of a data type may take.
+    /// Data types without a quota may take the whole store.
+    pub storage_quotas: HashMap<DataTypes, f64>,
     /// The seed to generate record_store encryption_details
     pub encryption_seed: [u8; 16],
 }
@@ -204,15 +339,47 @@ impl Default for NodeRecordStoreConfig {
         let historic_quote_dir = std::env::temp_dir();
         Self {
             storage_dir: historic_quote_dir.clone(),
+            extra_storage_dirs: vec![],
+            legacy_storage_dirs: vec![],
             historic_quote_dir,
             max_records: MAX_RECORDS_COUNT,
             max_value_bytes: MAX_PACKET_SIZE,
             records_cache_size: MAX_RECORDS_CACHE_SIZE,
+            hot_records_cache_size: 0,
+            storage_quotas: HashMap::new(),
             encryption_seed: [0u8; 16],
         }
     }
 }
 
+impl NodeRecordStoreConfig {
+    /// All the directories the records are sharded across.
+    fn storage_dirs(&self) -> impl Iterator<Item = &PathBuf> {
+        std::iter::once(&self.storage_dir).chain(self.extra_storage_dirs.iter())
+    }
+
+    /// The directory a record is stored in, picked by the hash of its key.
+    ///
+    /// The keys a node is responsible for share their leading bits with the node's address, hence
+    /// are hashed again to be spread evenly across the storage dirs.
+    fn storage_dir_for(&self, key: &Key) -> &Path {
+        if self.extra_storage_dirs.is_empty() {
+            return &self.storage_dir;
+        }
+        let shards = 1 + self.extra_storage_dirs.len() as u64;
+        let hash = XorName::from_content(key.as_ref());
+        let mut leading_bytes = [0u8; 8];
+        leading_bytes.copy_from_slice(&hash.0[..8]);
+        match u64::from_be_bytes(leading_bytes) % shards {
+            0 => &self.storage_dir,
+            shard => self
+                .extra_storage_dirs
+                .get(shard as usize - 1)
+                .unwrap_or(&self.storage_dir),
+        }
+    }
+}
+
 /// Generate an encryption nonce for a given record key and nonce_starter bytes.
 fn generate_nonce_for_record(nonce_starter: &[u8; 4], key: &Key) -> Nonce {
     let mut nonce_bytes = nonce_starter.to_vec();
@@ -230,11 +397,15 @@ struct HistoricQuotingMetrics {
 }
 
 impl NodeRecordStore {
-    /// If a directory for our node already exists, repopulate the records from the files in the dir
+    /// If a directory for our node already exists, repopulate the records from the files in the dir,
+    /// along with the expiry of the temporary ones.
     fn update_records_from_an_existing_store(
         config: &NodeRecordStoreConfig,
         encryption_details: &(Aes256GcmSiv, [u8; 4]),
-    ) -> HashMap<Key, (NetworkAddress, ValidationType, DataTypes)> {
+    ) -> (
+        HashMap<Key, (NetworkAddress, ValidationType, DataTypes)>,
+        HashMap<Key, u64>,
+    ) {
         let process_entry = |entry: &DirEntry| -> _ {
             let path = entry.path();
             if path.is_file() {
@@ -257,8 +428,26 @@ impl NodeRecordStore {
                         return None;
                     }
                 };
+                // a record that was being written when the node stopped, named after its key
+                if path
+                    .extension()
+                    .is_some_and(|ext| ext == PARTIAL_RECORD_EXTENSION)
+                    && path
+                        .file_stem()
+                        .and_then(|stem| stem.to_str())
+                        .and_then(Self::get_data_from_filename)
+                        .is_some()
+                {
+                    info!("Removing the partially written record {filename:?}");
+                    if let Err(e) = fs::remove_file(&path) {
+                        warn!("Failed to remove partially written record {filename:?}: {e:?}");
+                    }
+                    return None;
+                }
                 // get the record key from the filename
                 let key = Self::get_data_from_filename(filename)?;
+                let path = Self::move_to_storage_dir(config, path, filename, &key);
+                let path = path.as_path();
                 let record = match fs::read(path) {
                     Ok(bytes) => {
                         // and the stored record
@@ -295,9 +484,12 @@ impl NodeRecordStore {
                             }
                         };
 
+                        let expiry = RecordHeader::from_record(&record)
+                            .ok()
+                            .and_then(|header| header.expiry);
                         let address = NetworkAddress::from(&key);
                         info!("Existing record {address:?} loaded from: {path:?}");
-                        return Some((key, (address, validate_type, data_type)));
+                        return Some((key, (address, validate_type, data_type), expiry));
                     }
                     Err(error) => {
                         warn!(
@@ -320,13 +512,56 @@ impl NodeRecordStore {
         };
 
         info!("Attempting to repopulate records from existing store...");
-        WalkDir::new(&config.storage_dir)
-            .into_iter()
-            .filter_map(|e| e.ok())
+        let entries: Vec<_> = config
+            .storage_dirs()
+            .chain(config.legacy_storage_dirs.iter())
+            .flat_map(|dir| WalkDir::new(dir).into_iter().filter_map(|e| e.ok()))
             .collect_vec()
             .par_iter()
             .filter_map(process_entry)
-            .collect()
+            .collect();
+
+        let expiring_records = entries
+            .iter()
+            .filter_map(|(key, _, expiry)| expiry.map(|expiry| (key.clone(), expiry)))
+            .collect();
+        let records = entries
+            .into_iter()
+            .map(|(key, record, _)| (key, record))
+            .collect();
+        (records, expiring_records)
+    }
+
+    /// Move a record file to the storage dir of its shard if not already there, so that the
+    /// records are rebalanced when storage dirs are added or removed.
+    /// Returns the path of the record file.
+    fn move_to_storage_dir(
+        config: &NodeRecordStoreConfig,
+        path: &Path,
+        filename: &str,
+        key: &Key,
+    ) -> PathBuf {
+        let storage_dir = config.storage_dir_for(key);
+        if path.parent() == Some(storage_dir) {
+            return path.to_path_buf();
+        }
+
+        let new_path = storage_dir.join(filename);
+        // renaming fails across file systems, hence the fallback to copying
+        let moved = fs::rename(path, &new_path)
+            .or_else(|_| fs::copy(path, &new_path).and_then(|_| fs::remove_file(path)));
+        match moved {
+            Ok(()) => {
+                debug!("Moved record {filename:?} to its storage dir {storage_dir:?}");
+                new_path
+            }
+            Err(err) => {
+                warn!(
+                    "Failed to move record {filename:?} to its storage dir {storage_dir:?}: {err:?}"
+                );
+                path.to_path_buf()
+            }
+        }
     }
 
     /// If quote_metrics file already exists, using the existing parameters.
@@ -343,6 +578,21 @@ impl NodeRecordStore {
     }
 
     fn flush_historic_quoting_metrics(&self) {
+        let (file_path, historic_quoting_metrics) = self.historic_quoting_metrics();
+
+        #[allow(clippy::let_underscore_future)]
+        let _ = spawn(async move {
+            Self::write_historic_quoting_metrics(&file_path, &historic_quoting_metrics);
+        });
+    }
+
+    /// Write the quoting metrics to disk before returning, used when the node shuts down.
+    pub(crate) fn flush_historic_quoting_metrics_now(&self) {
+        let (file_path, historic_quoting_metrics) = self.historic_quoting_metrics();
+        Self::write_historic_quoting_metrics(&file_path, &historic_quoting_metrics);
+    }
+
+    fn historic_quoting_metrics(&self) -> (PathBuf, HistoricQuotingMetrics) {
         let file_path = self
             .config
             .historic_quote_dir
@@ -352,14 +602,22 @@ impl NodeRecordStore {
             received_payment_count: self.received_payment_count,
             timestamp: self.timestamp,
         };
+        (file_path, historic_quoting_metrics)
+    }
 
-        #[allow(clippy::let_underscore_future)]
-        let _ = spawn(async move {
-            if let Ok(mut file) = fs::File::create(file_path) {
-                let mut serialiser = rmp_serde::encode::Serializer::new(&mut file);
-                let _ = historic_quoting_metrics.serialize(&mut serialiser);
-            }
-        });
+    fn write_historic_quoting_metrics(
+        file_path: &Path,
+        historic_quoting_metrics: &HistoricQuotingMetrics,
+    ) {
+        if let Ok(mut file) = fs::File::create(file_path) {
+            let mut serialiser = rmp_serde::encode::Serializer::new(&mut file);
+            let _ = historic_quoting_metrics.serialize(&mut serialiser);
+        }
+    }
+
+    /// Returns the number of records still being written to disk.
+    pub(crate) fn pending_writes(&self) -> usize {
+        self.pending_writes.load(Ordering::Acquire)
     }
 
     /// Creates a new `DiskBackedStore` with the given configuration.
@@ -386,23 +644,29 @@ impl NodeRecordStore {
             (0, SystemTime::now())
         };
 
-        let records = Self::update_records_from_an_existing_store(&config, &encryption_details);
+        let (records, expiring_records) =
+            Self::update_records_from_an_existing_store(&config, &encryption_details);
         let local_address = NetworkAddress::from(local_id);
 
         // Initialize records_by_distance
         let mut records_by_distance: BTreeMap<Distance, Key> = BTreeMap::new();
-        for (key, (addr, _record_type, _data_type)) in records.iter() {
+        let mut records_of_type: HashMap<DataTypes, usize> = HashMap::new();
+        for (key, (addr, _record_type, data_type)) in records.iter() {
             let distance = &local_address.distance(addr);
             let _ = records_by_distance.insert(*distance, key.clone());
+            *records_of_type.entry(*data_type).or_insert(0) += 1;
         }
 
         let cache_size = config.records_cache_size;
+        let hot_records_cache_size = config.hot_records_cache_size;
         let mut record_store = NodeRecordStore {
             local_address,
             config,
             records,
+            records_of_type,
             records_by_distance,
             records_cache: RecordCache::new(cache_size, CACHE_TIMEOUT),
+            hot_records: HotRecordCache::new(hot_records_cache_size),
             network_event_sender,
             local_swarm_cmd_sender: swarm_cmd_sender,
             responsible_distance_range: None,
@@ -412,6 +676,10 @@ impl NodeRecordStore {
             encryption_details,
             timestamp,
             farthest_record: None,
+            pending_writes: Arc::new(AtomicUsize::new(0)),
+            expiring_records,
+            #[cfg(feature = "open-metrics")]
+            storage_quota_metrics: None,
         };
 
         record_store.farthest_record = record_store.calculate_farthest();
@@ -426,6 +694,31 @@ impl NodeRecordStore {
         record_store
     }
 
+    /// Set the counters of the hits and misses of the hot records cache.
+    #[cfg(feature = "open-metrics")]
+    pub(crate) fn set_hot_records_cache_metrics(&mut self, metrics: HotRecordCacheMetrics) {
+        self.hot_records.metrics = Some(metrics);
+    }
+
+    /// Set the metrics of the records per data type and of the storage quota rejections.
+    #[cfg(feature = "open-metrics")]
+    pub(crate) fn set_storage_quota_metrics(&mut self, metrics: StorageQuotaMetrics) {
+        self.storage_quota_metrics = Some(metrics);
+        for record_type in self.records_of_type.keys() {
+            self.record_type_count_metric(*record_type);
+        }
+    }
+
+    #[cfg(feature = "open-metrics")]
+    fn record_type_count_metric(&self, record_type: DataTypes) {
+        if let Some(metrics) = &self.storage_quota_metrics {
+            let _ = metrics
+                .records_per_type
+                .get_or_create(&RecordTypeLabels { record_type })
+                .set(self.count_of_type(record_type) as i64);
+        }
+    }
+
     /// Returns the current responsible distance range.
     pub(crate) fn get_responsible_distance_range(&self) -> Option<Distance> {
         self.responsible_distance_range
@@ -458,6 +751,15 @@ impl NodeRecordStore {
 
         match cipher.decrypt(&nonce, bytes.as_slice()) {
             Ok(value) => {
+                // Records stored before a change of the header format are served in the current one.
+                let value = match RecordHeader::migrate(&value) {
+                    Ok(Some(migrated)) => migrated,
+                    Ok(None) => value,
+                    Err(error) => {
+                        warn!("Failed to migrate the header of record {key:?}: {error:?}");
+                        value
+                    }
+                };
                 let record = Record {
                     key: key.clone(),
                     value,
@@ -501,6 +803,20 @@ impl NodeRecordStore {
         }
     }
 
+    /// Read a held record straight from its file on disk, skipping the caches, so that its
+    /// integrity can be checked.
+    pub(crate) fn read_record_from_disk(&self, key: &Key) -> Option<Record> {
+        if !self.records.contains_key(key) {
+            return None;
+        }
+        Self::read_from_disk(
+            &self.encryption_details,
+            key,
+            self.config.storage_dir_for(key),
+        )
+        .map(Cow::into_owned)
+    }
+
     // Returns the farthest record_key to self.
     pub(crate) fn get_farthest(&self) -> Option<Key> {
         if let Some((ref key, _distance)) = self.farthest_record {
@@ -527,8 +843,70 @@ impl NodeRecordStore {
         }
     }
 
+    /// Number of records of a data type held by the store.
+    fn count_of_type(&self, data_type: DataTypes) -> usize {
+        self.records_of_type.get(&data_type).copied().unwrap_or(0)
+    }
+
+    /// Count a record of `data_type` added to `records`, replacing a record of `replaced_type`.
+    fn count_record_added(&mut self, data_type: DataTypes, replaced_type: Option<DataTypes>) {
+        if let Some(replaced_type) = replaced_type {
+            self.count_record_removed(replaced_type);
+        }
+        *self.records_of_type.entry(data_type).or_insert(0) += 1;
+        #[cfg(feature = "open-metrics")]
+        self.record_type_count_metric(data_type);
+    }
+
+    /// Count a record of `data_type` removed from `records`.
+    fn count_record_removed(&mut self, data_type: DataTypes) {
+        if let Some(count) = self.records_of_type.get_mut(&data_type) {
+            *count = count.saturating_sub(1);
+        }
+        #[cfg(feature = "open-metrics")]
+        self.record_type_count_metric(data_type);
+    }
+
+    /// Whether the store is holding `max_records`.
+    pub(crate) fn is_full(&self) -> bool {
+        self.records.len() >= self.config.max_records
+    }
+
+    /// Returns Ok if a new record of `data_type` fits in the quota of its data type, so that cheap
+    /// records can't take the space of the others. Updates of records already held always fit.
+    ///
+    /// Err MaxRecords if its data type already takes its share of the store
+    fn check_storage_quota(&self, key: &Key, data_type: DataTypes) -> Result<()> {
+        let Some(quota) = self.config.storage_quotas.get(&data_type) else {
+            return Ok(());
+        };
+        if self.records.contains_key(key) {
+            return Ok(());
+        }
+        let max_records_of_type = (quota * self.config.max_records as f64) as usize;
+        if self.count_of_type(data_type) < max_records_of_type {
+            return Ok(());
+        }
+
+        warn!(
+            "Record {:?} not stored, the {data_type:?} records already take their quota of {max_records_of_type} records",
+            PrettyPrintRecordKey::from(key)
+        );
+        #[cfg(feature = "open-metrics")]
+        if let Some(metrics) = &self.storage_quota_metrics {
+            let _ = metrics
+                .quota_rejections
+                .get_or_create(&RecordTypeLabels {
+                    record_type: data_type,
+                })
+                .inc();
+        }
+        Err(Error::MaxRecords)
+    }
+
     /// Prune the records in the store to ensure that we free up space
     /// for the incoming record.
+    ///
     /// Returns Ok if the record can be stored because it is closer to the local peer
     /// or we are not full.
     ///
@@ -621,6 +999,44 @@ impl NodeRecordStore {
             );
         }
     }
+
+    /// Removes the temporary records whose lease has expired.
+    pub(crate) fn remove_expired_records(&mut self) {
+        let now = now_secs();
+        let expired_keys: Vec<Key> = self
+            .expiring_records
+            .iter()
+            .filter(|(_key, expiry)| **expiry <= now)
+            .map(|(key, _expiry)| key.clone())
+            .collect();
+
+        for key in &expired_keys {
+            debug!(
+                "Removing expired temporary record {:?}",
+                PrettyPrintRecordKey::from(key)
+            );
+            self.remove(key);
+        }
+
+        if !expired_keys.is_empty() {
+            info!("Removed {} expired temporary records", expired_keys.len());
+        }
+    }
+
+    /// Returns `true` if the record is a temporary one whose lease has expired.
+    fn is_expired(&self, key: &Key) -> bool {
+        self.expiring_records
+            .get(key)
+            .is_some_and(|expiry| *expiry <= now_secs())
+    }
+}
+
+/// The current time, in seconds since the UNIX epoch.
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
 }
 
 impl NodeRecordStore {
@@ -631,10 +1047,12 @@ impl NodeRecordStore {
 
     /// Pruning out-of-sync entry from the indexing cache
     pub(crate) fn pruning_indexing_cache(&mut self, k: &Key) {
-        if let Some((addr, _, _)) = self.records.remove(k) {
+        if let Some((addr, _, data_type)) = self.records.remove(k) {
             let distance = self.local_address.distance(&addr);
             let _ = self.records_by_distance.remove(&distance);
+            self.count_record_removed(data_type);
         }
+        let _ = self.expiring_records.remove(k);
     }
 
     /// Returns the set of `NetworkAddress::RecordKey` held by the store
@@ -671,10 +1089,17 @@ impl NodeRecordStore {
         let addr = NetworkAddress::from(&key);
         let distance = self.local_address.distance(&addr);
 
+        // A read may have cached the previous version while the new one was being written
+        self.hot_records.remove(&key);
+
         // Update main records store
-        let _ = self
+        let replaced = self
             .records
             .insert(key.clone(), (addr.clone(), validate_type, data_type));
+        self.count_record_added(
+            data_type,
+            replaced.map(|(_, _, replaced_type)| replaced_type),
+        );
 
         #[cfg(feature = "open-metrics")]
         if let Some(metric) = &self.record_count_metric {
@@ -730,6 +1155,9 @@ impl NodeRecordStore {
         let record_key = PrettyPrintRecordKey::from(&r.key).into_owned();
         debug!("PUTting a verified Record: {record_key:?}");
 
+        // mutable records can be updated, so stop serving the previous version from memory
+        self.hot_records.remove(key);
+
         // if cache already has the record :
         //   * if with same content, do nothing and return early
         //   * if with different content, remove the existing one
@@ -749,13 +1177,31 @@ impl NodeRecordStore {
             self.records_cache.push_back(key.clone(), r.clone());
         }
 
+        if let Ok(data_type) = RecordHeader::get_data_type(&r) {
+            self.check_storage_quota(key, data_type)?;
+        }
         self.prune_records_if_needed(key)?;
 
+        // a temporary record may be renewed, or updated, with a new expiry
+        match RecordHeader::from_record(&r)
+            .ok()
+            .and_then(|header| header.expiry)
+        {
+            Some(expiry) => {
+                let _ = self.expiring_records.insert(key.clone(), expiry);
+            }
+            None => {
+                let _ = self.expiring_records.remove(key);
+            }
+        }
+
         let filename = Self::generate_filename(key);
-        let file_path = self.config.storage_dir.join(&filename);
+        let file_path = self.config.storage_dir_for(key).join(&filename);
 
         let encryption_details = self.encryption_details.clone();
         let cloned_cmd_sender = self.local_swarm_cmd_sender.clone();
+        let pending_writes = Arc::clone(&self.pending_writes);
+        let _ = pending_writes.fetch_add(1, Ordering::AcqRel);
 
         let record_key2 = record_key.clone();
         #[allow(clippy::let_underscore_future)]
@@ -767,11 +1213,16 @@ impl NodeRecordStore {
                     error!(
                         "Error get data_type of record {record_key2:?} filename: {filename}, error: {err:?}"
                     );
+                    let _ = pending_writes.fetch_sub(1, Ordering::AcqRel);
                     return;
                 }
             };
             if let Some(bytes) = Self::prepare_record_bytes(r, encryption_details) {
-                let cmd = match fs::write(&file_path, bytes) {
+                let partial_file_path = file_path.with_extension(PARTIAL_RECORD_EXTENSION);
+                let write_result = fs::write(&partial_file_path, bytes)
+                    .and_then(|_| fs::rename(&partial_file_path, &file_path));
+                let _ = pending_writes.fetch_sub(1, Ordering::AcqRel);
+                let cmd = match write_result {
                     Ok(_) => {
                         // vdash metric (if modified please notify at https://github.com/happybeing/vdash/issues):
                         info!("Wrote record {record_key2:?} to disk! filename: {filename}");
@@ -791,6 +1242,8 @@ impl NodeRecordStore {
                 };
 
                 send_local_swarm_cmd(cloned_cmd_sender, cmd);
+            } else {
+                let _ = pending_writes.fetch_sub(1, Ordering::AcqRel);
             }
         });
 
@@ -841,7 +1294,8 @@ impl NodeRecordStore {
         // vdash metric (if modified please notify at https://github.com/happybeing/vdash/issues):
         info!("Quoting_metrics {quoting_metrics:?}");
 
-        let is_stored = self.contains(key);
+        // A temporary record is quoted again, for its lease to be renewed
+        let is_stored = self.contains(key) && !self.expiring_records.contains_key(key);
         (quoting_metrics, is_stored)
     }
 
@@ -870,12 +1324,57 @@ impl NodeRecordStore {
         self.responsible_distance_range = Some(responsible_distance);
     }
 
-    fn records_per_type(&self) -> Vec<(u32, u32)> {
-        let mut map = BTreeMap::new();
+    /// Statistics of the held records, with a random sample of `sample_size` of their addresses.
+    /// The size of the records on disk is left to the caller, see [`Self::record_file_paths`].
+    pub(crate) fn stats(&self, sample_size: usize) -> RecordStoreStats {
+        let mut records_per_type: BTreeMap<u32, usize> = BTreeMap::new();
         for (_, _, data_type) in self.records.values() {
-            *map.entry(data_type.get_index()).or_insert(0) += 1;
+            *records_per_type.entry(data_type.get_index()).or_insert(0) += 1;
+        }
+
+        let mut records_per_distance = BTreeMap::new();
+        for distance in self.records_by_distance.keys() {
+            if let Some(ilog2) = distance.ilog2() {
+                *records_per_distance.entry(ilog2).or_insert(0) += 1;
+            }
         }
-        map.into_iter().collect()
+
+        let sample = self
+            .records
+            .values()
+            .map(|(addr, _, _)| addr.clone())
+            .choose_multiple(&mut rand::thread_rng(), sample_size);
+
+        RecordStoreStats {
+            records_per_type: records_per_type
+                .into_iter()
+                .filter_map(|(index, count)| DataTypes::from_index(index).map(|ty| (ty, count)))
+                .collect(),
+            total_bytes: 0,
+            records_per_distance,
+            sample,
+        }
+    }
+
+    /// The paths of the files of the held records.
+    pub(crate) fn record_file_paths(&self) -> Vec<PathBuf> {
+        self.records
+            .keys()
+            .map(|key| {
+                self.config
+                    .storage_dir_for(key)
+                    .join(Self::generate_filename(key))
+            })
+            .collect()
+    }
+
+    fn records_per_type(&self) -> Vec<(u32, u32)> {
+        self.records_of_type
+            .iter()
+            .filter(|(_, count)| **count > 0)
+            .map(|(data_type, count)| (data_type.get_index(), *count as u32))
+            .sorted()
+            .collect()
     }
 }
 
@@ -889,6 +1388,11 @@ impl RecordStore for NodeRecordStore {
         // ignored if we don't have the record locally.
         let key = PrettyPrintRecordKey::from(k);
 
+        if self.is_expired(k) {
+            debug!("Record {key:?} has expired, it is no longer served");
+            return None;
+        }
+
         let cached_record = self.records_cache.get(k);
         // first return from FIFO cache if existing there
         if let Some((record, _timestamp)) = cached_record {
@@ -902,7 +1406,17 @@ impl RecordStore for NodeRecordStore {
 
         debug!("GET request for Record key: {key}");
 
-        let result = Self::read_from_disk(&self.encryption_details, k, &self.config.storage_dir);
+        // then from the LRU cache of the records most read
+        if let Some(record) = self.hot_records.get(k) {
+            return Some(Cow::Owned(record));
+        }
+
+        let result =
+            Self::read_from_disk(&self.encryption_details, k, self.config.storage_dir_for(k));
+        if let Some(record) = &result {
+            self.hot_records
+                .insert(k.clone(), record.clone().into_owned());
+        }
 
         // In case the indexing cache being out-of-sync with the disk files,
         // the indexing cache shall be pruned, to allow further replication to be triggered.
@@ -1000,12 +1514,15 @@ impl RecordStore for NodeRecordStore {
 
     fn remove(&mut self, k: &Key) {
         // Remove from main store
-        if let Some((addr, _, _)) = self.records.remove(k) {
+        if let Some((addr, _, data_type)) = self.records.remove(k) {
             let distance = self.local_address.distance(&addr);
             let _ = self.records_by_distance.remove(&distance);
+            self.count_record_removed(data_type);
         }
+        let _ = self.expiring_records.remove(k);
 
         let _ = self.records_cache.remove(k);
+        self.hot_records.remove(k);
 
         #[cfg(feature = "open-metrics")]
         if let Some(metric) = &self.record_count_metric {
@@ -1019,7 +1536,7 @@ impl RecordStore for NodeRecordStore {
         }
 
         let filename = Self::generate_filename(k);
-        let file_path = self.config.storage_dir.join(&filename);
+        let file_path = self.config.storage_dir_for(k).join(&filename);
 
         let _handle = spawn(async move {
             match fs::remove_file(file_path) {
@@ -1067,10 +1584,11 @@ mod tests {
 
     use ant_protocol::storage::{
         Chunk, ChunkAddress, DataTypes, Scratchpad, try_deserialize_record, try_serialize_record,
+        try_serialize_record_with_header,
     };
     use assert_fs::{
         TempDir,
-        fixture::{PathChild, PathCreateDir},
+        fixture::{FileWriteBin, PathChild, PathCreateDir},
     };
     use bytes::Bytes;
     use eyre::ContextCompat;
@@ -1131,10 +1649,15 @@ mod tests {
         let r = r.0;
         let (network_event_sender, mut network_event_receiver) = mpsc::channel(1);
         let (swarm_cmd_sender, _) = mpsc::channel(1);
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let store_config = NodeRecordStoreConfig {
+            storage_dir: temp_dir.to_path_buf(),
+            ..Default::default()
+        };
 
         let mut store = NodeRecordStore::with_config(
             PeerId::random(),
-            Default::default(),
+            store_config,
             network_event_sender,
             swarm_cmd_sender,
             #[cfg(feature = "open-metrics")]
@@ -1197,6 +1720,103 @@ mod tests {
         assert!(store.get(&r.key).is_none());
     }
 
+    #[test]
+    fn records_are_sharded_and_moved_to_their_storage_dir() -> eyre::Result<()> {
+        let tmp_dir = TempDir::new()?;
+        let dir_a = tmp_dir.child("a");
+        let dir_b = tmp_dir.child("b");
+        dir_a.create_dir_all()?;
+        dir_b.create_dir_all()?;
+
+        let store_config = NodeRecordStoreConfig {
+            storage_dir: dir_a.to_path_buf(),
+            extra_storage_dirs: vec![dir_b.to_path_buf()],
+            ..Default::default()
+        };
+        // Keys close to each other, as the ones a node is responsible for, are spread across dirs
+        let keys: Vec<Key> = (0..64u8)
+            .map(|i| {
+                let mut key = vec![0xab; 32];
+                key[31] = i;
+                Key::from(key)
+            })
+            .collect();
+        let (keys_a, keys_b): (Vec<_>, Vec<_>) = keys
+            .iter()
+            .partition(|key| store_config.storage_dir_for(key) == dir_a.path());
+        assert!(!keys_a.is_empty());
+        assert!(!keys_b.is_empty());
+        assert!(
+            keys_b
+                .iter()
+                .all(|key| store_config.storage_dir_for(key) == dir_b.path())
+        );
+
+        // A record stored while there was a single storage dir is moved to its shard
+        let key_b = keys_b[0];
+        let filename = NodeRecordStore::generate_filename(key_b);
+        let old_path = dir_a.path().join(&filename);
+        fs::write(&old_path, b"record")?;
+        let new_path =
+            NodeRecordStore::move_to_storage_dir(&store_config, &old_path, &filename, key_b);
+        assert_eq!(new_path, dir_b.path().join(&filename));
+        assert!(!old_path.exists());
+        assert_eq!(fs::read(&new_path)?, b"record");
+
+        // and left in place once there
+        assert_eq!(
+            NodeRecordStore::move_to_storage_dir(&store_config, &new_path, &filename, key_b),
+            new_path
+        );
+
+        // A record of the record store under the root dir is moved to its shard as well
+        let legacy_dir = tmp_dir.child("record_store");
+        legacy_dir.create_dir_all()?;
+        let key_a = keys_a[0];
+        let filename = NodeRecordStore::generate_filename(key_a);
+        let legacy_path = legacy_dir.path().join(&filename);
+        fs::write(&legacy_path, b"legacy record")?;
+        let new_path =
+            NodeRecordStore::move_to_storage_dir(&store_config, &legacy_path, &filename, key_a);
+        assert_eq!(new_path, dir_a.path().join(&filename));
+        assert!(!legacy_path.exists());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn partially_written_records_are_removed_on_restart() -> eyre::Result<()> {
+        let tmp_dir = TempDir::new()?;
+        let key = RecordKey::new(&XorName::random(&mut rand::thread_rng()));
+        let partial_record = tmp_dir.child(format!(
+            "{}.{PARTIAL_RECORD_EXTENSION}",
+            NodeRecordStore::generate_filename(&key)
+        ));
+        partial_record.write_binary(b"partially written record")?;
+        let foreign_file = tmp_dir.child(format!("not-a-record.{PARTIAL_RECORD_EXTENSION}"));
+        foreign_file.write_binary(b"not a record")?;
+
+        let store_config = NodeRecordStoreConfig {
+            storage_dir: tmp_dir.to_path_buf(),
+            ..Default::default()
+        };
+        let (network_event_sender, _) = mpsc::channel(1);
+        let (swarm_cmd_sender, _) = mpsc::channel(1);
+        let store = NodeRecordStore::with_config(
+            PeerId::random(),
+            store_config,
+            network_event_sender,
+            swarm_cmd_sender,
+            #[cfg(feature = "open-metrics")]
+            None,
+        );
+
+        assert!(!store.contains(&key));
+        assert!(!partial_record.path().exists());
+        // Only the files named after a record are removed
+        assert!(foreign_file.path().exists());
+        Ok(())
+    }
+
     #[tokio::test]
     async fn can_store_after_restart() -> eyre::Result<()> {
         let tmp_dir = TempDir::new()?;
@@ -1318,9 +1938,9 @@ mod tests {
 
     #[tokio::test]
     async fn can_store_and_retrieve_chunk() {
-        let temp_dir = std::env::temp_dir();
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
         let store_config = NodeRecordStoreConfig {
-            storage_dir: temp_dir,
+            storage_dir: temp_dir.to_path_buf(),
             ..Default::default()
         };
         let self_id = PeerId::random();
@@ -1386,9 +2006,9 @@ mod tests {
 
     #[tokio::test]
     async fn can_store_and_retrieve_scratchpad() -> eyre::Result<()> {
-        let temp_dir = std::env::temp_dir();
+        let temp_dir = TempDir::new()?;
         let store_config = NodeRecordStoreConfig {
-            storage_dir: temp_dir,
+            storage_dir: temp_dir.to_path_buf(),
             ..Default::default()
         };
         let self_id = PeerId::random();
@@ -1468,6 +2088,205 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn expired_records_are_not_served_and_removed() -> eyre::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let store_config = NodeRecordStoreConfig {
+            storage_dir: temp_dir.to_path_buf(),
+            ..Default::default()
+        };
+        let (network_event_sender, _) = mpsc::channel(1);
+        let (swarm_cmd_sender, _) = mpsc::channel(1);
+        let mut store = NodeRecordStore::with_config(
+            PeerId::random(),
+            store_config,
+            network_event_sender,
+            swarm_cmd_sender,
+            #[cfg(feature = "open-metrics")]
+            None,
+        );
+
+        let scratchpad = Scratchpad::new(
+            &SecretKey::random(),
+            0,
+            &Bytes::from_static(b"Temporary data"),
+            0,
+        );
+        let key = NetworkAddress::ScratchpadAddress(*scratchpad.address()).to_record_key();
+        fn put_with_expiry(
+            store: &mut NodeRecordStore,
+            key: &Key,
+            scratchpad: &Scratchpad,
+            expiry: u64,
+        ) -> eyre::Result<()> {
+            let header =
+                RecordHeader::with_expiry(RecordKind::DataOnly(DataTypes::Scratchpad), expiry);
+            let record = Record {
+                key: key.clone(),
+                value: try_serialize_record_with_header(scratchpad, header)?.to_vec(),
+                expires: None,
+                publisher: None,
+            };
+            let validation_type = ValidationType::NonChunk(XorName::from_content(&record.value));
+            store.put_verified(record, validation_type.clone(), true)?;
+            store.mark_as_stored(key.clone(), validation_type, DataTypes::Scratchpad);
+            Ok(())
+        }
+
+        // A record within its lease is served and kept
+        put_with_expiry(&mut store, &key, &scratchpad, now_secs() + 3600)?;
+        assert!(store.get(&key).is_some());
+        store.remove_expired_records();
+        assert!(store.contains(&key));
+
+        // Once expired, it is no longer served, then removed
+        put_with_expiry(&mut store, &key, &scratchpad, now_secs() - 1)?;
+        assert!(store.get(&key).is_none());
+        store.remove_expired_records();
+        assert!(!store.contains(&key));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn read_record_from_disk_skips_the_caches() -> eyre::Result<()> {
+        let tmp_dir = TempDir::new()?;
+        let store_config = NodeRecordStoreConfig {
+            storage_dir: tmp_dir.to_path_buf(),
+            ..Default::default()
+        };
+        let (network_event_sender, _network_event_receiver) = mpsc::channel(1);
+        let (swarm_cmd_sender, mut swarm_cmd_receiver) = mpsc::channel(1);
+        let mut store = NodeRecordStore::with_config(
+            PeerId::random(),
+            store_config,
+            network_event_sender,
+            swarm_cmd_sender,
+            #[cfg(feature = "open-metrics")]
+            None,
+        );
+
+        let chunk = Chunk::new(Bytes::from_static(b"Chunk to verify"));
+        let record = Record {
+            key: NetworkAddress::ChunkAddress(*chunk.address()).to_record_key(),
+            value: try_serialize_record(&chunk, RecordKind::DataOnly(DataTypes::Chunk))?.to_vec(),
+            expires: None,
+            publisher: None,
+        };
+        store.put_verified(record.clone(), ValidationType::Chunk, true)?;
+
+        // Wait for the async write operation to complete
+        if let Some(LocalSwarmCmd::AddLocalRecordAsStored {
+            key,
+            record_type,
+            data_type,
+        }) = swarm_cmd_receiver.recv().await
+        {
+            store.mark_as_stored(key, record_type, data_type);
+        }
+        let on_disk = store.read_record_from_disk(&record.key);
+        assert_eq!(on_disk.map(|r| r.value), Some(record.value.clone()));
+
+        // Once the file is corrupted, the cached copy is still served but the disk read fails
+        let file_path = tmp_dir.join(NodeRecordStore::generate_filename(&record.key));
+        fs::write(&file_path, b"corrupted")?;
+        assert!(store.get(&record.key).is_some());
+        assert!(store.read_record_from_disk(&record.key).is_none());
+
+        // A record that is not held is never read
+        assert!(
+            store
+                .read_record_from_disk(&Key::from(vec![0x00; 32]))
+                .is_none()
+        );
+
+        Ok(())
+    }
+    #[tokio::test]
+    async fn storage_quota_caps_the_records_of_a_data_type() -> eyre::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let store_config = NodeRecordStoreConfig {
+            max_records: 20,
+            storage_dir: temp_dir.to_path_buf(),
+            storage_quotas: HashMap::from([(DataTypes::Scratchpad, 0.1)]),
+            ..Default::default()
+        };
+        let (network_event_sender, _) = mpsc::channel(1);
+        let (swarm_cmd_sender, _) = mpsc::channel(1);
+        let mut store = NodeRecordStore::with_config(
+            PeerId::random(),
+            store_config,
+            network_event_sender,
+            swarm_cmd_sender,
+            #[cfg(feature = "open-metrics")]
+            None,
+        );
+
+        let record_of_type = |data_type: DataTypes| -> eyre::Result<Record> {
+            let content = (0..50).map(|_| rand::random::<u8>()).collect::<Bytes>();
+            Ok(Record {
+                key: NetworkAddress::from(PeerId::random()).to_record_key(),
+                value: try_serialize_record(&content, RecordKind::DataOnly(data_type))?.to_vec(),
+                publisher: None,
+                expires: None,
+            })
+        };
+        let put = |store: &mut NodeRecordStore, record: Record, data_type: DataTypes| {
+            let key = record.key.clone();
+            let validation_type = ValidationType::NonChunk(XorName::from_content(&record.value));
+            let result = store.put_verified(record, validation_type.clone(), false);
+            if result.is_ok() {
+                store.mark_as_stored(key, validation_type, data_type);
+            }
+            result
+        };
+
+        // The quota of 10% of 20 records lets 2 scratchpads in
+        let first_scratchpad = record_of_type(DataTypes::Scratchpad)?;
+        put(&mut store, first_scratchpad.clone(), DataTypes::Scratchpad)?;
+        put(
+            &mut store,
+            record_of_type(DataTypes::Scratchpad)?,
+            DataTypes::Scratchpad,
+        )?;
+        assert!(matches!(
+            put(
+                &mut store,
+                record_of_type(DataTypes::Scratchpad)?,
+                DataTypes::Scratchpad
+            ),
+            Err(Error::MaxRecords)
+        ));
+
+        // Updates of a held scratchpad and records of other types are still accepted
+        let updated_scratchpad = Record {
+            value: record_of_type(DataTypes::Scratchpad)?.value,
+            ..first_scratchpad.clone()
+        };
+        put(&mut store, updated_scratchpad, DataTypes::Scratchpad)?;
+        put(
+            &mut store,
+            record_of_type(DataTypes::Pointer)?,
+            DataTypes::Pointer,
+        )?;
+        assert_eq!(store.count(), 3);
+        assert!(!store.is_full());
+        assert_eq!(store.count_of_type(DataTypes::Scratchpad), 2);
+        assert_eq!(store.count_of_type(DataTypes::Pointer), 1);
+
+        // Removing a scratchpad frees a place in the quota
+        store.remove(&first_scratchpad.key);
+        assert_eq!(store.count_of_type(DataTypes::Scratchpad), 1);
+        put(
+            &mut store,
+            record_of_type(DataTypes::Scratchpad)?,
+            DataTypes::Scratchpad,
+        )?;
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn pruning_on_full() -> Result<()> {
         let max_iterations = 10;
@@ -1694,6 +2513,57 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn record_store_stats() -> eyre::Result<()> {
+        let temp_dir = std::env::temp_dir();
+        let unique_dir_name = uuid::Uuid::new_v4().to_string();
+        let storage_dir = temp_dir.join(unique_dir_name);
+
+        let store_config = NodeRecordStoreConfig {
+            storage_dir,
+            ..Default::default()
+        };
+        let self_id = PeerId::random();
+        let (network_event_sender, _) = mpsc::channel(1);
+        let (swarm_cmd_sender, _) = mpsc::channel(1);
+        let mut store = NodeRecordStore::with_config(
+            self_id,
+            store_config,
+            network_event_sender,
+            swarm_cmd_sender,
+            #[cfg(feature = "open-metrics")]
+            None,
+        );
+
+        for i in 0..10 {
+            let record_key = NetworkAddress::from(PeerId::random()).to_record_key();
+            let data_type = if i < 7 {
+                DataTypes::Chunk
+            } else {
+                DataTypes::Scratchpad
+            };
+            store.mark_as_stored(record_key, ValidationType::Chunk, data_type);
+        }
+
+        let stats = store.stats(4);
+        assert_eq!(
+            stats.records_per_type,
+            vec![(DataTypes::Chunk, 7), (DataTypes::Scratchpad, 3)]
+        );
+        assert_eq!(stats.records_per_distance.values().sum::<usize>(), 10);
+        assert_eq!(stats.sample.len(), 4);
+        assert!(
+            stats
+                .sample
+                .iter()
+                .all(|addr| store.contains(&addr.to_record_key()))
+        );
+        assert_eq!(store.stats(20).sample.len(), 10);
+        assert_eq!(store.record_file_paths().len(), 10);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn historic_quoting_metrics() -> Result<()> {
         let temp_dir = std::env::temp_dir();
@@ -1815,4 +2685,35 @@ mod tests {
         // Verify new record is present
         assert!(cache.get(&record5.key).is_some());
     }
+
+    #[test]
+    fn test_hot_records_cache_evicts_least_recently_read() {
+        let cache = HotRecordCache::new(2);
+        let records: Vec<_> = (0..3)
+            .map(|i| Record {
+                key: RecordKey::new(&format!("key{i}").into_bytes()),
+                value: vec![i],
+                publisher: None,
+                expires: None,
+            })
+            .collect();
+
+        cache.insert(records[0].key.clone(), records[0].clone());
+        cache.insert(records[1].key.clone(), records[1].clone());
+        // Reading the first record makes the second one the least recently read
+        assert_eq!(cache.get(&records[0].key), Some(records[0].clone()));
+        cache.insert(records[2].key.clone(), records[2].clone());
+
+        assert!(cache.get(&records[0].key).is_some());
+        assert!(cache.get(&records[1].key).is_none());
+        assert!(cache.get(&records[2].key).is_some());
+
+        cache.remove(&records[2].key);
+        assert!(cache.get(&records[2].key).is_none());
+
+        // A zero sized cache is disabled
+        let disabled = HotRecordCache::new(0);
+        disabled.insert(records[0].key.clone(), records[0].clone());
+        assert!(disabled.get(&records[0].key).is_none());
+    }
 }
@@ -270,6 +270,21 @@ impl RelayManager {
         }
     }
 
+    /// Stop listening through the relay servers, and advertising the relayed addresses, when the
+    /// node leaves relay mode.
+    pub(in crate::networking) fn remove_relayed_listeners(self, swarm: &mut Swarm<NodeBehaviour>) {
+        for listener_id in self.relayed_listener_id_map.keys() {
+            let _ = swarm.remove_listener(*listener_id);
+        }
+        for addr in self.connected_relay_servers.into_values() {
+            info!("Removing external addr: {addr:?}");
+            swarm.remove_external_address(&addr);
+            if let Ok(addr_with_self_peer_id) = addr.with_p2p(self.self_peer_id) {
+                swarm.remove_external_address(&addr_with_self_peer_id);
+            }
+        }
+    }
+
     /// Update client state if the reservation has been cancelled or if the relay has closed.
     pub(in crate::networking) fn on_listener_closed(
         &mut self,
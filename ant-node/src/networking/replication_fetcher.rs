@@ -27,8 +27,8 @@ use crate::networking::log_markers::Marker;
 #[cfg(feature = "open-metrics")]
 use crate::networking::metrics::NetworkMetricsRecorder;
 
-// Max parallel fetches that can be undertaken at the same time.
-const MAX_PARALLEL_FETCH: usize = 5;
+// Default max parallel fetches that can be undertaken at the same time.
+pub(crate) const MAX_PARALLEL_FETCH: usize = 5;
 
 // The duration after which a peer will be considered failed to fetch data from,
 // if no response got from that peer.
@@ -85,6 +85,8 @@ pub(crate) struct ReplicationFetcher {
     /// Avoid fetching same chunk from different nodes AND carry out too many parallel tasks.
     on_going_fetches: HashMap<(RecordKey, ValidationType), (PeerId, ReplicationTimeout)>,
     event_sender: mpsc::Sender<NetworkEvent>,
+    /// Max number of fetches undertaken at the same time.
+    max_parallel_fetches: usize,
     /// Distance range that the incoming key shall be fetched
     distance_range: Option<Distance>,
     /// Restrict fetch range to closer than this value
@@ -121,6 +123,7 @@ impl ReplicationFetcher {
             to_be_fetched: HashMap::new(),
             on_going_fetches: HashMap::new(),
             event_sender,
+            max_parallel_fetches: MAX_PARALLEL_FETCH,
             distance_range: None,
             farthest_acceptable_distance: None,
             peers_scores: HashMap::new(),
@@ -128,6 +131,11 @@ impl ReplicationFetcher {
         }
     }
 
+    /// Set the max number of fetches undertaken at the same time.
+    pub(crate) fn set_max_parallel_fetches(&mut self, max_parallel_fetches: usize) {
+        self.max_parallel_fetches = max_parallel_fetches.max(1);
+    }
+
     /// Set the distance range.
     pub(crate) fn set_replication_distance_range(&mut self, distance_range: Distance) {
         self.distance_range = Some(distance_range);
@@ -266,13 +274,16 @@ impl ReplicationFetcher {
 
     // Returns the set of keys that has to be fetched from the peer/network.
     // Target must not be under-fetching
-    // and no more than MAX_PARALLEL_FETCH fetches to be undertaken at the same time.
+    // and no more than `max_parallel_fetches` fetches to be undertaken at the same time.
+    //
+    // Mutable records (pointers, scratchpads, graph entries) are fetched before chunks,
+    // then records closest to us come first.
     pub(crate) fn next_keys_to_fetch(&mut self) -> Vec<(PeerId, RecordKey)> {
         self.prune_expired_keys_and_slow_nodes();
 
         debug!("Next to fetch....");
 
-        if self.on_going_fetches.len() >= MAX_PARALLEL_FETCH {
+        if self.on_going_fetches.len() >= self.max_parallel_fetches {
             warn!(
                 "Replication Fetcher doesn't have free fetch capacity. Currently has {} entries in queue.",
                 self.to_be_fetched.len()
@@ -291,25 +302,24 @@ impl ReplicationFetcher {
         );
 
         // Pre-allocate vectors with known capacity
-        let remaining_capacity = MAX_PARALLEL_FETCH - self.on_going_fetches.len();
+        let remaining_capacity = self.max_parallel_fetches - self.on_going_fetches.len();
         let mut data_to_fetch = Vec::with_capacity(remaining_capacity);
 
-        // Sort to_be_fetched by key closeness to our PeerId
+        // Sort to_be_fetched by data type priority, then by key closeness to our PeerId
         let mut to_be_fetched_sorted: Vec<_> = self.to_be_fetched.iter_mut().collect();
 
         let self_address = NetworkAddress::from(self.self_peer_id);
 
-        to_be_fetched_sorted.sort_by(|((a, _, _), _), ((b, _, _), _)| {
-            let a = NetworkAddress::from(a);
-            let b = NetworkAddress::from(b);
-            self_address.distance(&a).cmp(&self_address.distance(&b))
+        to_be_fetched_sorted.sort_by_cached_key(|((key, t, _), _)| {
+            let addr = NetworkAddress::from(&*key);
+            (fetch_priority(t), self_address.distance(&addr))
         });
 
         for ((key, t, holder), _) in to_be_fetched_sorted {
             // Already carried out expiration pruning above.
             // Hence here only need to check whether is ongoing fetching.
             // Also avoid fetching same record from different nodes.
-            if self.on_going_fetches.len() < MAX_PARALLEL_FETCH
+            if self.on_going_fetches.len() < self.max_parallel_fetches
                 && !self
                     .on_going_fetches
                     .contains_key(&(key.clone(), t.clone()))
@@ -322,7 +332,7 @@ impl ReplicationFetcher {
             }
 
             // break out the loop early if we can do no more now
-            if self.on_going_fetches.len() >= MAX_PARALLEL_FETCH {
+            if self.on_going_fetches.len() >= self.max_parallel_fetches {
                 break;
             }
         }
@@ -833,6 +843,15 @@ impl ReplicationFetcher {
     }
 }
 
+/// Fetch priority of a record, lower is fetched first.
+/// Mutable records are prioritized over chunks.
+fn fetch_priority(validation_type: &ValidationType) -> u8 {
+    match validation_type {
+        ValidationType::NonChunk(_) => 0,
+        ValidationType::Chunk => 1,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{FETCH_TIMEOUT, MAX_PARALLEL_FETCH, ReplicationFetcher};
@@ -841,6 +860,7 @@ mod tests {
     use libp2p::{PeerId, kad::RecordKey};
     use std::{collections::HashMap, time::Duration};
     use tokio::{sync::mpsc, time::sleep};
+    use xor_name::XorName;
 
     #[tokio::test]
     async fn verify_max_parallel_fetches() -> Result<()> {
@@ -1025,6 +1045,61 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn verify_mutable_records_fetched_first() {
+        let peer_id = PeerId::random();
+        let (event_sender, _event_receiver) = mpsc::channel(4);
+        let mut replication_fetcher = ReplicationFetcher::new(peer_id, event_sender);
+        replication_fetcher.set_max_parallel_fetches(3);
+
+        // using single random peer to simulate the closest group
+        let self_addr = NetworkAddress::from(peer_id);
+        let farthest_peer = NetworkAddress::from(PeerId::random());
+        let distance_range = self_addr.distance(&farthest_peer);
+        let random_key = || loop {
+            let random_data: Vec<u8> = (0..50).map(|_| rand::random::<u8>()).collect();
+            let candidate_key = NetworkAddress::from(&RecordKey::from(random_data));
+            if self_addr.distance(&candidate_key) < distance_range {
+                break candidate_key;
+            }
+        };
+
+        let mut incoming_keys: Vec<_> = (0..MAX_PARALLEL_FETCH * 2)
+            .map(|_| (random_key(), ValidationType::Chunk))
+            .collect();
+        let mutable_keys: Vec<_> = (0..2)
+            .map(|i| {
+                let key = random_key();
+                let content_hash = XorName::from_content(&[i]);
+                (key, ValidationType::NonChunk(content_hash))
+            })
+            .collect();
+        incoming_keys.extend(mutable_keys.clone());
+
+        // Two holders are required to reach majority
+        let mut keys_to_fetch = Vec::new();
+        for _ in 0..2 {
+            keys_to_fetch = replication_fetcher.add_keys(
+                PeerId::random(),
+                incoming_keys.clone(),
+                &Default::default(),
+                false,
+                vec![farthest_peer.clone()],
+                #[cfg(feature = "open-metrics")]
+                None,
+            );
+        }
+
+        assert_eq!(keys_to_fetch.len(), 3, "Fetch budget shall be respected");
+        for (mutable_key, _) in mutable_keys {
+            let mutable_key = mutable_key.to_record_key();
+            assert!(
+                keys_to_fetch.iter().any(|(_, key)| *key == mutable_key),
+                "Mutable records shall be fetched before chunks"
+            );
+        }
+    }
+
     #[test]
     fn verify_in_range_check() {
         //random peer_id
@@ -0,0 +1,163 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use ant_protocol::messages::{Query, Request};
+#[cfg(feature = "open-metrics")]
+use prometheus_client::encoding::EncodeLabelValue;
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use tokio::sync::Semaphore;
+
+/// Kinds of requests sent to peers, each with its own timeout and concurrency limit.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "open-metrics", derive(EncodeLabelValue))]
+pub enum RequestKind {
+    /// Store cost and Merkle candidate quotes
+    Quote,
+    /// Records fetched from peers during replication
+    ReplicatedRecord,
+    /// Chunk existence proofs and storage statements
+    StorageChallenge,
+    /// Records, record headers and chunk ranges
+    Record,
+    /// Closest peers, version and node status queries
+    PeerInfo,
+    /// Replication and bad peer notifications
+    Cmd,
+}
+
+impl From<&Request> for RequestKind {
+    fn from(req: &Request) -> Self {
+        match req {
+            Request::Cmd(_) => Self::Cmd,
+            Request::Query(query) | Request::TracedQuery { query, .. } => Self::from(query),
+        }
+    }
+}
+
+impl From<&Query> for RequestKind {
+    fn from(query: &Query) -> Self {
+        match query {
+            Query::GetStoreQuote { .. } | Query::GetMerkleCandidateQuote { .. } => Self::Quote,
+            Query::GetReplicatedRecord { .. } => Self::ReplicatedRecord,
+            Query::GetChunkExistenceProof { .. } | Query::GetStorageStatement { .. } => {
+                Self::StorageChallenge
+            }
+            Query::PutRecord { .. } | Query::GetRecordHeader(_) | Query::GetChunkRange { .. } => {
+                Self::Record
+            }
+            Query::CheckNodeInProblem(_) | Query::GetClosestPeers { .. } | Query::GetVersion(_) => {
+                Self::PeerInfo
+            }
+            #[cfg(feature = "developer")]
+            Query::DevGetClosestPeersFromNetwork { .. } => Self::PeerInfo,
+        }
+    }
+}
+
+/// Timeout and concurrency limit applied to a kind of request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestLimits {
+    /// How long to wait for the response before the request is considered as timed out.
+    /// Capped by the request timeout of the underlying request-response protocol.
+    pub timeout: Duration,
+    /// Max number of requests of this kind awaiting a response at the same time.
+    pub max_concurrency: usize,
+}
+
+impl RequestLimits {
+    /// The default limits of a kind of request.
+    pub fn default_for(kind: RequestKind) -> Self {
+        let (timeout_s, max_concurrency) = match kind {
+            // Quotes are on the critical path of uploads, a slow peer is better skipped.
+            RequestKind::Quote => (30, 64),
+            // Fetching a record during replication includes the holder reading it from disk.
+            RequestKind::ReplicatedRecord => (120, 64),
+            // The peer has to read the record to produce its proof.
+            RequestKind::StorageChallenge => (60, 32),
+            RequestKind::Record => (60, 64),
+            RequestKind::PeerInfo => (30, 128),
+            RequestKind::Cmd => (60, 128),
+        };
+        Self {
+            timeout: Duration::from_secs(timeout_s),
+            max_concurrency,
+        }
+    }
+}
+
+/// Applies the `RequestLimits` of each kind of request.
+#[derive(Debug)]
+pub(crate) struct RequestLimiter {
+    limits: HashMap<RequestKind, (RequestLimits, Arc<Semaphore>)>,
+}
+
+impl RequestLimiter {
+    /// Create a limiter using the default limits, unless overridden for a kind.
+    pub(crate) fn new(overrides: &HashMap<RequestKind, RequestLimits>) -> Self {
+        let limits = [
+            RequestKind::Quote,
+            RequestKind::ReplicatedRecord,
+            RequestKind::StorageChallenge,
+            RequestKind::Record,
+            RequestKind::PeerInfo,
+            RequestKind::Cmd,
+        ]
+        .into_iter()
+        .map(|kind| {
+            let limits = overrides
+                .get(&kind)
+                .copied()
+                .unwrap_or_else(|| RequestLimits::default_for(kind));
+            info!("Request limits for {kind:?}: {limits:?}");
+            // A kind of request shall never be fully blocked.
+            let semaphore = Arc::new(Semaphore::new(limits.max_concurrency.max(1)));
+            (kind, (limits, semaphore))
+        })
+        .collect();
+
+        Self { limits }
+    }
+
+    /// The limits of the kind and the semaphore bounding its concurrency.
+    pub(crate) fn get(&self, kind: RequestKind) -> (Duration, Arc<Semaphore>) {
+        match self.limits.get(&kind) {
+            Some((limits, semaphore)) => (limits.timeout, semaphore.clone()),
+            None => {
+                let limits = RequestLimits::default_for(kind);
+                (
+                    limits.timeout,
+                    Arc::new(Semaphore::new(limits.max_concurrency)),
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overrides_replace_default_limits() {
+        let custom = RequestLimits {
+            timeout: Duration::from_secs(5),
+            max_concurrency: 2,
+        };
+        let limiter = RequestLimiter::new(&HashMap::from([(RequestKind::Quote, custom)]));
+
+        let (timeout, semaphore) = limiter.get(RequestKind::Quote);
+        assert_eq!(timeout, custom.timeout);
+        assert_eq!(semaphore.available_permits(), 2);
+
+        let (timeout, _) = limiter.get(RequestKind::ReplicatedRecord);
+        assert_eq!(
+            timeout,
+            RequestLimits::default_for(RequestKind::ReplicatedRecord).timeout
+        );
+    }
+}
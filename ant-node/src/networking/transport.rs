@@ -8,21 +8,39 @@
 
 #[cfg(feature = "open-metrics")]
 use crate::networking::MetricsRegistries;
+use futures::future::Either;
 use libp2p::{
     PeerId, Transport as _,
-    core::{muxing::StreamMuxerBox, transport},
+    core::{muxing::StreamMuxerBox, transport, upgrade},
     identity::Keypair,
 };
 
+/// Build the transport of the node: QUIC, with TCP as a fallback when `tcp_fallback` is set.
+///
+/// TCP connections are secured with noise and multiplexed with yamux, allowing peers sitting
+/// behind networks that block UDP to still reach the node.
 pub(crate) fn build_transport(
     keypair: &Keypair,
+    tcp_fallback: bool,
     #[cfg(feature = "open-metrics")] registries: &mut MetricsRegistries,
 ) -> transport::Boxed<(PeerId, StreamMuxerBox)> {
-    let trans = generate_quic_transport(keypair);
-    #[cfg(feature = "open-metrics")]
-    let trans = libp2p::metrics::BandwidthTransport::new(trans, &mut registries.standard_metrics);
+    let quic = generate_quic_transport(keypair)
+        .map(|(peer_id, muxer), _| (peer_id, StreamMuxerBox::new(muxer)))
+        .boxed();
+
+    let trans = if tcp_fallback {
+        quic.or_transport(generate_tcp_transport(keypair))
+            .map(|either_output, _| match either_output {
+                Either::Left(output) | Either::Right(output) => output,
+            })
+            .boxed()
+    } else {
+        quic
+    };
 
-    let trans = trans.map(|(peer_id, muxer), _| (peer_id, StreamMuxerBox::new(muxer)));
+    #[cfg(feature = "open-metrics")]
+    let trans = libp2p::metrics::BandwidthTransport::new(trans, &mut registries.standard_metrics)
+        .map(|(peer_id, muxer), _| (peer_id, StreamMuxerBox::new(muxer)));
 
     trans.boxed()
 }
@@ -36,3 +54,15 @@ fn generate_quic_transport(
 
     libp2p::quic::tokio::Transport::new(quic_config)
 }
+
+fn generate_tcp_transport(keypair: &Keypair) -> transport::Boxed<(PeerId, StreamMuxerBox)> {
+    libp2p::tcp::tokio::Transport::new(libp2p::tcp::Config::default().nodelay(true))
+        .upgrade(upgrade::Version::V1Lazy)
+        .authenticate(
+            libp2p::noise::Config::new(keypair)
+                .expect("Signing libp2p-noise static DH keypair failed."),
+        )
+        .multiplex(libp2p::yamux::Config::default())
+        .map(|(peer_id, muxer), _| (peer_id, StreamMuxerBox::new(muxer)))
+        .boxed()
+}
@@ -9,11 +9,20 @@
 use super::{
     Marker, NodeEvent, error::Result, event::NodeEventsChannel, quote::quotes_verification,
 };
+use crate::disk_space::{DEFAULT_MIN_FREE_DISK_SPACE, DISK_SPACE_CHECK_INTERVAL, DiskSpaceMonitor};
+use crate::free_updates::{FreeUpdateLimiter, FreeUpdateLimits};
 #[cfg(feature = "open-metrics")]
 use crate::metrics::NodeMetricsRecorder;
 #[cfg(feature = "open-metrics")]
 use crate::networking::MetricsRegistries;
-use crate::networking::{Addresses, Network, NetworkConfig, NetworkEvent, NodeIssue};
+use crate::networking::{
+    Addresses, ChurnStats, LatencyOperation, LatencyOutcome, Network, NetworkConfig, NetworkEvent,
+    NodeIssue, RequestKind, RequestLimits,
+};
+use crate::payment_cache::PaymentVerificationCache;
+use crate::payment_confirmation::{PENDING_PAYMENTS_CHECK_INTERVAL, PendingPayments};
+use crate::replication::adaptive_replication_interval;
+use crate::rewards_ledger::RewardsLedger;
 use crate::{PutValidationError, RunningNode};
 use ant_bootstrap::bootstrap::Bootstrap;
 use ant_evm::EvmNetwork;
@@ -22,8 +31,11 @@ use ant_evm::merkle_payments::MERKLE_PAYMENT_EXPIRATION;
 use ant_protocol::{
     CLOSE_GROUP_SIZE, NetworkAddress, PrettyPrintRecordKey,
     error::Error as ProtocolError,
-    messages::{ChunkProof, CmdResponse, Nonce, Query, QueryResponse, Request, Response},
-    storage::{Chunk, ValidationType, try_deserialize_record},
+    messages::{
+        ChunkProof, CmdResponse, Nonce, Query, QueryResponse, RecordMetadata, Request, Response,
+        StorageStatement,
+    },
+    storage::{Chunk, DataTypes, RecordHeader, ValidationType, try_deserialize_record},
 };
 use bytes::Bytes;
 use futures::stream::{self, StreamExt};
@@ -46,20 +58,29 @@ use std::{
     path::PathBuf,
     sync::{
         Arc,
-        atomic::{AtomicUsize, Ordering},
+        atomic::{AtomicBool, AtomicUsize, Ordering},
     },
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 use tokio::sync::{Mutex, watch};
 use tokio::{
     sync::mpsc::Receiver,
     task::{JoinSet, spawn},
 };
+use tracing::Instrument;
+use xor_name::XorName;
 
 /// Interval to trigger replication of all records to all peers.
-/// This is the max time it should take. Minimum interval at any node will be half this
+/// This is the max time it should take, while the routing table is stable. Minimum interval at any
+/// node will be half this, churn only bringing the interval closer to the minimum.
 pub const PERIODIC_REPLICATION_INTERVAL_MAX_S: u64 = 180;
 
+/// Lower bound of the replication interval, approached during a mass churn event. Replicating
+/// more often would flood the network when most nodes churn at once, so lower bounds are raised
+/// to this.
+const MIN_REPLICATION_INTERVAL: Duration =
+    Duration::from_secs(PERIODIC_REPLICATION_INTERVAL_MAX_S / 2);
+
 /// Interval to trigger storage challenge.
 /// This is the max time it should take. Minimum interval at any node will be half this
 const STORE_CHALLENGE_INTERVAL_MAX_S: u64 = 7200;
@@ -123,16 +144,34 @@ const CLOSE_GROUP_RESTART_SUPPRESSION: Duration = Duration::from_secs(90);
 pub struct NodeBuilder {
     addr: SocketAddr,
     bootstrap: Bootstrap,
+    data_dirs: Vec<PathBuf>,
     evm_address: RewardsAddress,
     evm_network: EvmNetwork,
+    free_update_limits: Option<FreeUpdateLimits>,
+    #[cfg(feature = "health-check")]
+    /// Set to Some to enable the health server
+    health_server_addr: Option<SocketAddr>,
+    #[cfg(feature = "health-check")]
+    health_min_peers: Option<usize>,
     identity_keypair: Keypair,
     local: bool,
+    max_drain_time: Option<Duration>,
     #[cfg(feature = "open-metrics")]
     /// Set to Some to enable the metrics server
     metrics_server_port: Option<u16>,
+    min_free_disk_space: Option<u64>,
+    min_replication_interval: Option<Duration>,
+    max_replication_interval: Option<Duration>,
     no_upnp: bool,
+    payment_confirmation_depth: u64,
     relay_client: bool,
+    relay_fallback: bool,
+    replication_max_parallel_fetches: Option<usize>,
+    hot_records_cache_size: Option<usize>,
+    request_limits: HashMap<RequestKind, RequestLimits>,
     root_dir: PathBuf,
+    storage_quotas: HashMap<DataTypes, f64>,
+    tcp_fallback: bool,
 }
 
 impl NodeBuilder {
@@ -149,15 +188,32 @@ impl NodeBuilder {
         Self {
             addr,
             bootstrap: bootstrap_flow,
+            data_dirs: vec![],
             evm_address,
             evm_network,
+            free_update_limits: None,
+            #[cfg(feature = "health-check")]
+            health_server_addr: None,
+            #[cfg(feature = "health-check")]
+            health_min_peers: None,
             identity_keypair,
             local: false,
+            max_drain_time: None,
             #[cfg(feature = "open-metrics")]
             metrics_server_port: None,
+            min_free_disk_space: None,
+            min_replication_interval: None,
+            max_replication_interval: None,
             no_upnp: false,
+            payment_confirmation_depth: 0,
             relay_client: false,
+            relay_fallback: false,
+            replication_max_parallel_fetches: None,
+            hot_records_cache_size: None,
+            request_limits: HashMap::new(),
             root_dir,
+            storage_quotas: HashMap::new(),
+            tcp_fallback: false,
         }
     }
 
@@ -166,12 +222,38 @@ impl NodeBuilder {
         self.local = local;
     }
 
+    #[cfg(feature = "health-check")]
+    /// Set the address of the health server, serving the `/healthz` and `/readyz` endpoints.
+    /// The server is not started if not set
+    pub fn health_server_addr(&mut self, addr: Option<SocketAddr>) {
+        self.health_server_addr = addr;
+    }
+
+    #[cfg(feature = "health-check")]
+    /// Set the number of peers the routing table must hold for the node to be reported as ready.
+    /// Defaults to `CLOSE_GROUP_SIZE` if not set
+    pub fn health_min_peers(&mut self, min_peers: Option<usize>) {
+        self.health_min_peers = min_peers;
+    }
+
     #[cfg(feature = "open-metrics")]
     /// Set the port for the OpenMetrics server. Defaults to a random port if not set
     pub fn metrics_server_port(&mut self, port: Option<u16>) {
         self.metrics_server_port = port;
     }
 
+    /// Set the max time spent on shutdown completing the in-flight record writes and flushing the
+    /// state to disk. Defaults to 10 seconds if not set
+    pub fn max_drain_time(&mut self, max_drain_time: Option<Duration>) {
+        self.max_drain_time = max_drain_time;
+    }
+
+    /// Set the free disk space in bytes under which the node stops accepting new data.
+    /// Defaults to 1 GiB if not set
+    pub fn min_free_disk_space(&mut self, min_free_disk_space: Option<u64>) {
+        self.min_free_disk_space = min_free_disk_space;
+    }
+
     /// Set the flag to make the node act as a relay client
     pub fn relay_client(&mut self, relay_client: bool) {
         self.relay_client = relay_client;
@@ -182,6 +264,69 @@ impl NodeBuilder {
         self.no_upnp = no_upnp;
     }
 
+    /// Set the flag to switch to relay mode when the peers fail to dial the node back, and back to
+    /// direct mode once they succeed again. Disabled by default.
+    pub fn relay_fallback(&mut self, relay_fallback: bool) {
+        self.relay_fallback = relay_fallback;
+    }
+
+    /// Set the flag to also listen and dial over TCP, as a fallback for peers unable to use QUIC
+    pub fn tcp_fallback(&mut self, tcp_fallback: bool) {
+        self.tcp_fallback = tcp_fallback;
+    }
+
+    /// Set the max number of records fetched in parallel during replication.
+    /// Defaults to a conservative value if not set
+    pub fn replication_max_parallel_fetches(&mut self, max_parallel_fetches: Option<usize>) {
+        self.replication_max_parallel_fetches = max_parallel_fetches;
+    }
+
+    /// Set the bounds of the interval between two periodic replications, which is adapted to the
+    /// churn of the routing table. Defaults to 90 and 180 seconds if not set, the min bound can't
+    /// be lower than 90 seconds
+    pub fn replication_interval_bounds(&mut self, min: Option<Duration>, max: Option<Duration>) {
+        self.min_replication_interval = min;
+        self.max_replication_interval = max;
+    }
+
+    /// Set the max number of frequently read records kept in memory to serve them faster,
+    /// 0 to disable. Disabled if not set
+    pub fn hot_records_cache_size(&mut self, cache_size: Option<usize>) {
+        self.hot_records_cache_size = cache_size;
+    }
+
+    /// Set the directories, e.g. one per disk, to shard the records across instead of storing them
+    /// under the root dir. Records are rebalanced across them on restart when the list changes
+    pub fn data_dirs(&mut self, data_dirs: Vec<PathBuf>) {
+        self.data_dirs = data_dirs;
+    }
+
+    /// Override the default timeout and concurrency limits of a kind of request sent to peers.
+    pub fn request_limits(&mut self, kind: RequestKind, limits: RequestLimits) {
+        let _ = self.request_limits.insert(kind, limits);
+    }
+
+    /// Set the limits on the free updates of existing pointers and scratchpads, over which updates
+    /// must be paid for. Defaults to `FreeUpdateLimits::default()` if not set
+    pub fn free_update_limits(&mut self, limits: Option<FreeUpdateLimits>) {
+        self.free_update_limits = limits;
+    }
+
+    /// Set the number of blocks a payment must be confirmed by to be settled. Records whose payment
+    /// is dropped by a chain reorganisation before that are verified again, and removed if no longer
+    /// paid for. Payments are trusted as soon as they are verified if set to 0, the default
+    pub fn payment_confirmation_depth(&mut self, confirmation_depth: u64) {
+        self.payment_confirmation_depth = confirmation_depth;
+    }
+
+    /// Cap the fraction, from 0 to 1, of the record store the records of a data type may take, so
+    /// that cheap mutable records can't take the space of paid chunks. Uncapped if not set
+    pub fn storage_quota(&mut self, data_type: DataTypes, fraction: f64) {
+        let _ = self
+            .storage_quotas
+            .insert(data_type, fraction.clamp(0.0, 1.0));
+    }
+
     /// Asynchronously runs a new node instance, setting up the swarm driver,
     /// creating a data storage, and handling network events. Returns the
     /// created `RunningNode` which contains a `NodeEventsChannel` for listening
@@ -209,6 +354,7 @@ impl NodeBuilder {
 
         // create a shutdown signal channel
         let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let (shutdown_complete_tx, shutdown_complete_rx) = watch::channel(false);
 
         // init network
         let network_config = NetworkConfig {
@@ -220,7 +366,16 @@ impl NodeBuilder {
             bootstrap: self.bootstrap,
             no_upnp: self.no_upnp,
             relay_client: self.relay_client,
+            relay_fallback: self.relay_fallback,
+            tcp_fallback: self.tcp_fallback,
             custom_request_timeout: None,
+            replication_max_parallel_fetches: self.replication_max_parallel_fetches,
+            hot_records_cache_size: self.hot_records_cache_size,
+            storage_quotas: self.storage_quotas,
+            data_dirs: self.data_dirs.clone(),
+            request_limits: self.request_limits,
+            max_drain_time: self.max_drain_time,
+            shutdown_complete_tx,
             #[cfg(feature = "open-metrics")]
             metrics_registries,
             #[cfg(feature = "open-metrics")]
@@ -230,6 +385,7 @@ impl NodeBuilder {
 
         // init node
         let node_events_channel = NodeEventsChannel::default();
+        let hibernating = Arc::new(AtomicBool::new(false));
         let node = NodeInner {
             network: network.clone(),
             events_channel: node_events_channel.clone(),
@@ -238,6 +394,27 @@ impl NodeBuilder {
             #[cfg(feature = "open-metrics")]
             metrics_recorder,
             evm_network: self.evm_network,
+            payment_verification_cache: PaymentVerificationCache::default(),
+            pending_payments: PendingPayments::new(self.payment_confirmation_depth),
+            free_update_limiter: FreeUpdateLimiter::new(
+                self.free_update_limits.unwrap_or_default(),
+            ),
+            rewards_ledger: RewardsLedger::new(&self.root_dir),
+            disk_space_monitor: DiskSpaceMonitor::new(
+                std::iter::once(self.root_dir.clone())
+                    .chain(self.data_dirs.iter().cloned())
+                    .collect(),
+                self.min_free_disk_space
+                    .unwrap_or(DEFAULT_MIN_FREE_DISK_SPACE),
+            ),
+            hibernating: Arc::clone(&hibernating),
+            min_replication_interval: self
+                .min_replication_interval
+                .unwrap_or(MIN_REPLICATION_INTERVAL)
+                .max(MIN_REPLICATION_INTERVAL),
+            max_replication_interval: self
+                .max_replication_interval
+                .unwrap_or(Duration::from_secs(PERIODIC_REPLICATION_INTERVAL_MAX_S)),
         };
         let node = Node {
             inner: Arc::new(node),
@@ -247,12 +424,23 @@ impl NodeBuilder {
         node.run(network_event_receiver, shutdown_rx);
         let running_node = RunningNode {
             shutdown_sender: shutdown_tx,
+            shutdown_complete_rx,
             network,
             node_events_channel,
             root_dir_path: self.root_dir,
             rewards_address: self.evm_address,
+            hibernating,
         };
 
+        #[cfg(feature = "health-check")]
+        if let Some(addr) = self.health_server_addr {
+            crate::health::run_health_server(
+                addr,
+                running_node.clone(),
+                self.health_min_peers.unwrap_or(CLOSE_GROUP_SIZE),
+            );
+        }
+
         Ok(running_node)
     }
 }
@@ -275,6 +463,14 @@ struct NodeInner {
     metrics_recorder: Option<NodeMetricsRecorder>,
     reward_address: RewardsAddress,
     evm_network: EvmNetwork,
+    payment_verification_cache: PaymentVerificationCache,
+    pending_payments: PendingPayments,
+    free_update_limiter: FreeUpdateLimiter,
+    rewards_ledger: RewardsLedger,
+    disk_space_monitor: DiskSpaceMonitor,
+    hibernating: Arc<AtomicBool>,
+    min_replication_interval: Duration,
+    max_replication_interval: Duration,
 }
 
 impl Node {
@@ -292,6 +488,44 @@ impl Node {
         &self.inner.close_group_tracker
     }
 
+    /// Returns the cache of payments already verified on chain
+    pub(crate) fn payment_verification_cache(&self) -> &PaymentVerificationCache {
+        &self.inner.payment_verification_cache
+    }
+
+    /// Returns the payments not yet confirmed by enough blocks
+    pub(crate) fn pending_payments(&self) -> &PendingPayments {
+        &self.inner.pending_payments
+    }
+
+    /// Returns the limiter of the free updates of pointers and scratchpads
+    pub(crate) fn free_update_limiter(&self) -> &FreeUpdateLimiter {
+        &self.inner.free_update_limiter
+    }
+
+    /// Returns the ledger of the rewards received by the node
+    pub(crate) fn rewards_ledger(&self) -> &RewardsLedger {
+        &self.inner.rewards_ledger
+    }
+
+    /// Returns the monitor of the node's free disk space
+    pub(crate) fn disk_space_monitor(&self) -> &DiskSpaceMonitor {
+        &self.inner.disk_space_monitor
+    }
+
+    /// Returns true while the node is hibernating, i.e. refusing new data and not replicating
+    pub(crate) fn is_hibernating(&self) -> bool {
+        self.inner.hibernating.load(Ordering::Relaxed)
+    }
+
+    /// Returns the bounds of the periodic replication interval
+    pub(crate) fn replication_interval_bounds(&self) -> (Duration, Duration) {
+        (
+            self.inner.min_replication_interval,
+            self.inner.max_replication_interval,
+        )
+    }
+
     #[cfg(feature = "open-metrics")]
     /// Returns a reference to the NodeMetricsRecorder if the `open-metrics` feature flag is enabled
     /// This is used to record various metrics for the node.
@@ -321,11 +555,13 @@ impl Node {
 
         let _node_task = spawn(async move {
             // use a random activity timeout to ensure that the nodes do not sync when messages
-            // are being transmitted.
-            let replication_interval: u64 = rng.gen_range(
-                PERIODIC_REPLICATION_INTERVAL_MAX_S / 2..PERIODIC_REPLICATION_INTERVAL_MAX_S,
+            // are being transmitted. The interval is then adapted to the churn of the RT.
+            let replication_jitter: f64 = rng.gen_range(0.0..1.0);
+            let replication_interval_time = adaptive_replication_interval(
+                &ChurnStats::default(),
+                self.replication_interval_bounds(),
+                replication_jitter,
             );
-            let replication_interval_time = Duration::from_secs(replication_interval);
             debug!("Replication interval set to {replication_interval_time:?}");
 
             let mut replication_interval = tokio::time::interval(replication_interval_time);
@@ -358,6 +594,13 @@ impl Node {
                 tokio::time::interval(storage_challenge_interval_time);
             let _ = storage_challenge_interval.tick().await; // first tick completes immediately
 
+            let mut disk_space_check_interval = tokio::time::interval(DISK_SPACE_CHECK_INTERVAL);
+            let _ = disk_space_check_interval.tick().await; // first tick completes immediately
+
+            let mut pending_payments_check_interval =
+                tokio::time::interval(PENDING_PAYMENTS_CHECK_INTERVAL);
+            let _ = pending_payments_check_interval.tick().await; // first tick completes immediately
+
             loop {
                 let peers_connected = &peers_connected;
 
@@ -405,6 +648,11 @@ impl Node {
                             Self::try_interval_replication(network);
                             trace!("Periodic replication took {:?}", start.elapsed());
                         });
+
+                        if let Some(next_interval) = self.next_replication_interval(replication_interval.period(), replication_jitter).await {
+                            replication_interval = tokio::time::interval(next_interval);
+                            let _ = replication_interval.tick().await; // first tick completes immediately
+                        }
                     }
                     _ = uptime_metrics_update_interval.tick() => {
                         #[cfg(feature = "open-metrics")]
@@ -412,6 +660,24 @@ impl Node {
                             let _ = metrics_recorder.uptime.set(metrics_recorder.started_instant.elapsed().as_secs() as i64);
                         }
                     }
+                    _ = disk_space_check_interval.tick() => {
+                        let _available_space = self.disk_space_monitor().refresh();
+                        #[cfg(feature = "open-metrics")]
+                        if let Some(metrics_recorder) = self.metrics_recorder() {
+                            if let Some(available_space) = _available_space {
+                                let _ = metrics_recorder.free_disk_space.set(available_space as i64);
+                            }
+                            let _ = metrics_recorder.storage_full.set(self.disk_space_monitor().is_full() as i64);
+                        }
+                    }
+                    _ = pending_payments_check_interval.tick() => {
+                        if self.pending_payments().len() > 0 {
+                            let node = self.clone();
+                            let _handle = spawn(async move {
+                                node.check_pending_payments().await;
+                            });
+                        }
+                    }
                     _ = irrelevant_records_cleanup_interval.tick() => {
                         let network = self.network().clone();
 
@@ -421,6 +687,10 @@ impl Node {
                     }
                     // runs every storage_challenge_interval time
                     _ = storage_challenge_interval.tick() => {
+                        if self.is_hibernating() {
+                            debug!("Skipping periodic storage challenge as hibernating");
+                            continue;
+                        }
                         let start = Instant::now();
                         debug!("Periodic storage challenge triggered");
                         let network = self.network().clone();
@@ -551,24 +821,41 @@ impl Node {
                     error!("Error while trying to fetch replicated data {err:?}");
                 }
             }
-            NetworkEvent::QueryRequestReceived { query, channel } => {
+            NetworkEvent::QueryRequestReceived {
+                query,
+                channel,
+                trace_context,
+                source,
+            } => {
                 event_header = "QueryRequestReceived";
                 let node = self.clone();
                 let payment_address = *self.reward_address();
 
-                let _handle = spawn(async move {
-                    let network = node.network().clone();
-                    let res = Self::handle_query(node, query, payment_address).await;
+                // Continue the trace of the sender, so the handling shows up under its request.
+                let span = debug_span!("handle_query");
+                #[cfg(feature = "otlp")]
+                if let Some(traceparent) = &trace_context {
+                    ant_logging::trace_context::set_span_parent(&span, traceparent);
+                }
+                #[cfg(not(feature = "otlp"))]
+                let _ = trace_context;
 
-                    // Reducing non-mandatory logging
-                    if let Response::Query(QueryResponse::GetVersion { .. }) = res {
-                        trace!("Sending response {res:?}");
-                    } else {
-                        debug!("Sending response {res:?}");
-                    }
+                let _handle = spawn(
+                    async move {
+                        let network = node.network().clone();
+                        let res = Self::handle_query(node, query, source, payment_address).await;
 
-                    network.send_response(res, channel);
-                });
+                        // Reducing non-mandatory logging
+                        if let Response::Query(QueryResponse::GetVersion { .. }) = res {
+                            trace!("Sending response {res:?}");
+                        } else {
+                            debug!("Sending response {res:?}");
+                        }
+
+                        network.send_response(res, channel);
+                    }
+                    .instrument(span),
+                );
             }
             NetworkEvent::UnverifiedRecord(record) => {
                 event_header = "UnverifiedRecord";
@@ -576,7 +863,8 @@ impl Node {
                 let self_clone = self.clone();
                 let _handle = spawn(async move {
                     let key = PrettyPrintRecordKey::from(&record.key).into_owned();
-                    match self_clone.validate_and_store_record(record).await {
+                    // The peer which sent the record through kad is not known
+                    match self_clone.validate_and_store_record(record, None).await {
                         Ok(()) => debug!("UnverifiedRecord {key} has been stored"),
                         Err(err) => {
                             self_clone.record_metrics(Marker::RecordRejected(&key, &err));
@@ -708,7 +996,12 @@ impl Node {
         Ok(())
     }
 
-    async fn handle_query(node: Self, query: Query, payment_address: RewardsAddress) -> Response {
+    async fn handle_query(
+        node: Self,
+        query: Query,
+        source: Option<PeerId>,
+        payment_address: RewardsAddress,
+    ) -> Response {
         let network = node.network();
         let resp: QueryResponse = match query {
             Query::GetStoreQuote {
@@ -739,8 +1032,33 @@ impl Node {
                 };
 
                 match maybe_quoting_metrics {
+                    Ok(_) if node.is_hibernating() => {
+                        info!("Refusing to quote for {key:?} as the node is hibernating");
+                        QueryResponse::GetStoreQuote {
+                            quote: Err(ProtocolError::StorageFull),
+                            peer_address: NetworkAddress::from(self_id),
+                            storage_proofs,
+                        }
+                    }
+                    Ok(_) if node.disk_space_monitor().is_full() => {
+                        info!(
+                            "Refusing to quote for {key:?} as the node is running out of disk space"
+                        );
+                        QueryResponse::GetStoreQuote {
+                            quote: Err(ProtocolError::StorageFull),
+                            peer_address: NetworkAddress::from(self_id),
+                            storage_proofs,
+                        }
+                    }
                     Ok((quoting_metrics, is_already_stored)) => {
-                        if is_already_stored {
+                        // Over the limits, the updates of a pointer or scratchpad are paid for
+                        let is_free_update_limited = is_already_stored
+                            && matches!(
+                                DataTypes::from_index(data_type),
+                                Some(DataTypes::Pointer | DataTypes::Scratchpad)
+                            )
+                            && node.free_update_limiter().is_limited(&record_key, source);
+                        if is_already_stored && !is_free_update_limited {
                             QueryResponse::GetStoreQuote {
                                 quote: Err(ProtocolError::RecordExists(
                                     PrettyPrintRecordKey::from(&record_key).into_owned(),
@@ -749,13 +1067,20 @@ impl Node {
                                 storage_proofs,
                             }
                         } else {
+                            let start = Instant::now();
+                            let quote = Self::create_quote_for_storecost(
+                                network,
+                                &key,
+                                &quoting_metrics,
+                                &payment_address,
+                            );
+                            network.record_latency(
+                                LatencyOperation::Quote,
+                                LatencyOutcome::from(&quote),
+                                start.elapsed(),
+                            );
                             QueryResponse::GetStoreQuote {
-                                quote: Self::create_quote_for_storecost(
-                                    network,
-                                    &key,
-                                    &quoting_metrics,
-                                    &payment_address,
-                                ),
+                                quote,
                                 peer_address: NetworkAddress::from(self_id),
                                 storage_proofs,
                             }
@@ -789,6 +1114,42 @@ impl Node {
 
                 QueryResponse::GetReplicatedRecord(result)
             }
+            Query::GetRecordHeader(key) => {
+                let our_address = NetworkAddress::from(network.peer_id());
+                let record_key = key.to_record_key();
+
+                let result = match network.get_local_record(&record_key).await {
+                    Ok(Some(record)) => RecordHeader::get_data_type(&record).map(|data_type| {
+                        let validation_type = if data_type == DataTypes::Chunk {
+                            ValidationType::Chunk
+                        } else {
+                            ValidationType::NonChunk(XorName::from_content(&record.value))
+                        };
+                        let metadata = RecordMetadata {
+                            data_type,
+                            size: record.value.len(),
+                            validation_type,
+                        };
+                        (our_address, metadata)
+                    }),
+                    Ok(None) => Err(ProtocolError::ReplicatedRecordNotFound {
+                        holder: Box::new(our_address),
+                        key: Box::new(key.clone()),
+                    }),
+                    // Use `PutRecordFailed` as place holder
+                    Err(err) => Err(ProtocolError::PutRecordFailed(format!(
+                        "Error to fetch local record for GetRecordHeader {err:?}"
+                    ))),
+                };
+
+                QueryResponse::GetRecordHeader(result)
+            }
+            Query::GetChunkRange { addr, offset, len } => {
+                Self::respond_chunk_range(network, addr, offset, len).await
+            }
+            Query::GetStorageStatement { key, nonce } => {
+                Self::respond_storage_statement(network, key, nonce).await
+            }
             Query::GetChunkExistenceProof {
                 key,
                 nonce,
@@ -842,8 +1203,22 @@ impl Node {
                 };
 
                 let key = PrettyPrintRecordKey::from(&record.key).into_owned();
-                let result = match node.validate_and_store_record(record).await {
+                let start = Instant::now();
+                let validation_result = node.validate_and_store_record(record, source).await;
+                network.record_latency(
+                    LatencyOperation::Put,
+                    LatencyOutcome::from(&validation_result),
+                    start.elapsed(),
+                );
+                let result = match validation_result {
                     Ok(()) => Ok(()),
+                    Err(PutValidationError::StorageFull) => {
+                        node.record_metrics(Marker::RecordRejected(
+                            &key,
+                            &PutValidationError::StorageFull,
+                        ));
+                        Err(ProtocolError::StorageFull)
+                    }
                     Err(PutValidationError::OutdatedRecordCounter { counter, expected }) => {
                         node.record_metrics(Marker::RecordRejected(
                             &key,
@@ -851,6 +1226,13 @@ impl Node {
                         ));
                         Err(ProtocolError::OutdatedRecordCounter { counter, expected })
                     }
+                    Err(err @ PutValidationError::FreeUpdateRateLimited(_)) => {
+                        node.record_metrics(Marker::RecordRejected(&key, &err));
+                        Err(ProtocolError::FreeUpdateRateLimited {
+                            key,
+                            work_difficulty: node.free_update_limiter().work_difficulty(),
+                        })
+                    }
                     Err(PutValidationError::TopologyVerificationFailed {
                         target_address,
                         valid_count,
@@ -896,6 +1278,14 @@ impl Node {
                 data_size,
                 merkle_payment_timestamp,
             } => {
+                if node.is_hibernating() || node.disk_space_monitor().is_full() {
+                    info!(
+                        "Refusing Merkle candidate quote for {key:?} as the node is hibernating or running out of disk space"
+                    );
+                    return Response::Query(QueryResponse::GetMerkleCandidateQuote(Err(
+                        ProtocolError::StorageFull,
+                    )));
+                }
                 Self::respond_merkle_candidate_quote(
                     network,
                     key,
@@ -1135,6 +1525,89 @@ impl Node {
         QueryResponse::GetMerkleCandidateQuote(Ok(candidate))
     }
 
+    async fn respond_chunk_range(
+        network: &Network,
+        addr: NetworkAddress,
+        offset: usize,
+        len: usize,
+    ) -> QueryResponse {
+        let record_key = addr.to_record_key();
+
+        let result = match network.get_local_record(&record_key).await {
+            Ok(Some(record)) => match RecordHeader::is_record_of_type_chunk(&record) {
+                Ok(true) => try_deserialize_record::<Chunk>(&record)
+                    .map(|chunk| (Self::chunk_range(&chunk, offset, len), chunk.value().len())),
+                Ok(false) => Err(ProtocolError::ChunkDoesNotExist(addr)),
+                Err(err) => Err(err),
+            },
+            Ok(None) => Err(ProtocolError::ChunkDoesNotExist(addr)),
+            // Use `PutRecordFailed` as place holder
+            Err(err) => Err(ProtocolError::PutRecordFailed(format!(
+                "Error to fetch local record for GetChunkRange {err:?}"
+            ))),
+        };
+
+        QueryResponse::GetChunkRange(result)
+    }
+
+    async fn respond_storage_statement(
+        network: &Network,
+        key: NetworkAddress,
+        nonce: Nonce,
+    ) -> QueryResponse {
+        let our_address = NetworkAddress::from(network.peer_id());
+
+        let record = match network.get_local_record(&key.to_record_key()).await {
+            Ok(Some(record)) => record,
+            Ok(None) => {
+                return QueryResponse::GetStorageStatement(Err(
+                    ProtocolError::ReplicatedRecordNotFound {
+                        holder: Box::new(our_address),
+                        key: Box::new(key),
+                    },
+                ));
+            }
+            Err(err) => {
+                // Use `PutRecordFailed` as place holder
+                return QueryResponse::GetStorageStatement(Err(ProtocolError::PutRecordFailed(
+                    format!("Error to fetch local record for GetStorageStatement {err:?}"),
+                )));
+            }
+        };
+
+        let content_hash = StorageStatement::content_hash(&record.value, nonce);
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let bytes = StorageStatement::bytes_to_sign(&key, &content_hash, timestamp, nonce);
+
+        let result = match network.sign(&bytes) {
+            Ok(signature) => Ok(StorageStatement {
+                key,
+                content_hash,
+                timestamp,
+                nonce,
+                pub_key: network.get_pub_key(),
+                signature,
+            }),
+            Err(err) => {
+                error!("Failed to sign the storage statement of {key:?}: {err}");
+                Err(ProtocolError::StorageStatementSigningFailed)
+            }
+        };
+
+        QueryResponse::GetStorageStatement(result)
+    }
+
+    /// Bytes of the chunk content within `offset..offset + len`, truncated to the chunk size.
+    fn chunk_range(chunk: &Chunk, offset: usize, len: usize) -> Bytes {
+        let value = chunk.value();
+        let start = offset.min(value.len());
+        let end = offset.saturating_add(len).min(value.len());
+        value.slice(start..end)
+    }
+
     // Nodes only check ChunkProof each other, to avoid `multi-version` issue
     // Client check proof against all records, as have to fetch from network anyway.
     async fn respond_x_closest_record_proof(
@@ -1270,7 +1743,7 @@ impl Node {
                 error!("Local record {addr:?} cann't be loaded from disk.");
             }
         }
-        let request = Request::Query(Query::GetChunkExistenceProof {
+        let request = Request::from(Query::GetChunkExistenceProof {
             key: target.clone(),
             nonce,
             difficulty,
@@ -1460,7 +1933,7 @@ impl Node {
         record_address: NetworkAddress,
         peers: Vec<(PeerId, Addresses)>,
     ) -> (Vec<PeerId>, Vec<PeerId>) {
-        let request = Request::Query(Query::GetReplicatedRecord {
+        let request = Request::from(Query::GetReplicatedRecord {
             requester: NetworkAddress::from(network.peer_id()),
             key: record_address.clone(),
         });
@@ -1474,9 +1947,22 @@ impl Node {
             let network_clone = network.clone();
             let request_clone = request.clone();
             async move {
+                let start = Instant::now();
                 let result = network_clone
                     .send_request(request_clone, peer_id, addrs.clone())
                     .await;
+                let outcome = if matches!(
+                    result,
+                    Ok((
+                        Response::Query(QueryResponse::GetReplicatedRecord(Ok(_))),
+                        _
+                    ))
+                ) {
+                    LatencyOutcome::Success
+                } else {
+                    LatencyOutcome::Failure
+                };
+                network_clone.record_latency(LatencyOperation::Get, outcome, start.elapsed());
                 (peer_id, addrs, result)
             }
         }))
@@ -1625,7 +2111,7 @@ impl Node {
 
     /// Query peer's version and update local knowledge.
     async fn try_query_peer_version(network: Network, peer: PeerId, addrs: Addresses) {
-        let request = Request::Query(Query::GetVersion(NetworkAddress::from(peer)));
+        let request = Request::from(Query::GetVersion(NetworkAddress::from(peer)));
         // We can skip passing `addrs` here as the new peer should be part of the kad::RT and swarm can get the addr.
         let version = match network.send_request(request, peer, addrs).await {
             Ok((Response::Query(QueryResponse::GetVersion { version, .. }), _conn_info)) => {
@@ -2254,6 +2740,19 @@ mod tests {
         assert_eq!(result, vec![]);
     }
 
+    #[test]
+    fn test_chunk_range_is_truncated_to_chunk_size() {
+        let chunk = Chunk::new(Bytes::from_static(b"0123456789"));
+
+        assert_eq!(Node::chunk_range(&chunk, 2, 3), Bytes::from_static(b"234"));
+        assert_eq!(Node::chunk_range(&chunk, 8, 5), Bytes::from_static(b"89"));
+        assert_eq!(Node::chunk_range(&chunk, 10, 5), Bytes::new());
+        assert_eq!(
+            Node::chunk_range(&chunk, usize::MAX, usize::MAX),
+            Bytes::new()
+        );
+    }
+
     #[test]
     fn test_fewer_local_peers_than_num_of_peers() {
         let local_peers: Vec<(PeerId, Vec<Multiaddr>)> = vec![
@@ -0,0 +1,134 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use ant_evm::QuoteHash;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How long a successful on-chain payment verification is trusted for.
+pub(crate) const PAYMENT_VERIFICATION_CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Max number of verified quote hashes kept in memory.
+pub(crate) const PAYMENT_VERIFICATION_CACHE_CAPACITY: usize = 10_000;
+
+/// Cache of quote hashes whose payment has already been verified on chain.
+///
+/// Clients retrying a PUT (or re-sending the same paid record) will hit this cache,
+/// sparing the EVM RPC a `verifyPayment` call for a payment we have already accepted.
+/// Only successful verifications are cached, a failure is always re-checked on chain.
+#[derive(Debug)]
+pub(crate) struct PaymentVerificationCache {
+    verified: Mutex<HashMap<QuoteHash, Instant>>,
+    ttl: Duration,
+    capacity: usize,
+}
+
+impl Default for PaymentVerificationCache {
+    fn default() -> Self {
+        Self::new(
+            PAYMENT_VERIFICATION_CACHE_TTL,
+            PAYMENT_VERIFICATION_CACHE_CAPACITY,
+        )
+    }
+}
+
+impl PaymentVerificationCache {
+    pub(crate) fn new(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            verified: Mutex::new(HashMap::new()),
+            ttl,
+            capacity,
+        }
+    }
+
+    /// Returns true if all the provided quote hashes have a non-expired successful verification.
+    pub(crate) fn contains_all(&self, quote_hashes: &[QuoteHash]) -> bool {
+        if quote_hashes.is_empty() {
+            return false;
+        }
+
+        let Ok(verified) = self.verified.lock() else {
+            return false;
+        };
+        quote_hashes.iter().all(|hash| {
+            verified
+                .get(hash)
+                .is_some_and(|verified_at| verified_at.elapsed() < self.ttl)
+        })
+    }
+
+    /// Record a successful verification for the provided quote hashes.
+    pub(crate) fn insert(&self, quote_hashes: &[QuoteHash]) {
+        let Ok(mut verified) = self.verified.lock() else {
+            return;
+        };
+
+        if verified.len() + quote_hashes.len() > self.capacity {
+            verified.retain(|_, verified_at| verified_at.elapsed() < self.ttl);
+        }
+
+        // Still full after purging the expired entries, evict the oldest ones
+        while !verified.is_empty() && verified.len() + quote_hashes.len() > self.capacity {
+            let oldest = verified
+                .iter()
+                .min_by_key(|(_, verified_at)| **verified_at)
+                .map(|(hash, _)| *hash);
+            if let Some(oldest) = oldest {
+                let _ = verified.remove(&oldest);
+            }
+        }
+
+        let now = Instant::now();
+        for hash in quote_hashes {
+            let _ = verified.insert(*hash, now);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_hit_and_miss() {
+        let cache = PaymentVerificationCache::default();
+        let hash_a = QuoteHash::repeat_byte(1);
+        let hash_b = QuoteHash::repeat_byte(2);
+
+        assert!(!cache.contains_all(&[hash_a]));
+        assert!(!cache.contains_all(&[]));
+
+        cache.insert(&[hash_a]);
+        assert!(cache.contains_all(&[hash_a]));
+        assert!(!cache.contains_all(&[hash_a, hash_b]));
+    }
+
+    #[test]
+    fn test_cache_expiry() {
+        let cache = PaymentVerificationCache::new(Duration::ZERO, 10);
+        let hash = QuoteHash::repeat_byte(1);
+
+        cache.insert(&[hash]);
+        assert!(!cache.contains_all(&[hash]));
+    }
+
+    #[test]
+    fn test_cache_is_bounded() {
+        let cache = PaymentVerificationCache::new(PAYMENT_VERIFICATION_CACHE_TTL, 2);
+        let hashes: Vec<_> = (1..=3).map(QuoteHash::repeat_byte).collect();
+
+        for hash in &hashes {
+            cache.insert(&[*hash]);
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        assert!(!cache.contains_all(&[hashes[0]]));
+        assert!(cache.contains_all(&[hashes[1], hashes[2]]));
+    }
+}
@@ -0,0 +1,593 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use crate::error::PutValidationError;
+use crate::node::Node;
+use ant_evm::payment_events::{
+    DataPaymentEvent, block_hash, latest_block_number, unconfirmed_payments,
+};
+use ant_evm::payment_vault::error::Error as PaymentVaultError;
+use ant_evm::{Amount, Hash, QuoteHash, QuotingMetrics, RewardsAddress};
+use ant_protocol::PrettyPrintRecordKey;
+use libp2p::kad::RecordKey;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// Interval between two checks of the payments not yet confirmed by enough blocks.
+pub(crate) const PENDING_PAYMENTS_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Max number of pending payments tracked, further records are not tracked until some are settled.
+const MAX_PENDING_PAYMENTS: usize = 10_000;
+
+/// Duration over which the records removed for lack of payment are not accepted from replication,
+/// so that the peers still holding them don't replicate them back right away.
+const REMOVED_RECORDS_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// A payment accepted for a record while not yet confirmed by enough blocks.
+#[derive(Clone, Debug)]
+pub(crate) struct PendingPayment {
+    /// The quotes of the payment made to this node
+    pub(crate) owned_quote_hashes: Vec<QuoteHash>,
+    /// The whole payment, to verify it again on chain
+    pub(crate) payments_to_verify: Vec<(QuoteHash, QuotingMetrics, RewardsAddress)>,
+    /// The reward credited to this node, to revert if the payment is no longer valid
+    pub(crate) reward_amount: Amount,
+    /// Where the payment was found on chain
+    pub(crate) events: Vec<DataPaymentEvent>,
+}
+
+impl PendingPayment {
+    /// The number of the most recent block holding a part of the payment.
+    pub(crate) fn last_block(&self) -> u64 {
+        self.events
+            .iter()
+            .map(|event| event.block_number)
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+/// What the chain looks like at the time of a check of the pending payments, as fetched once for
+/// all the payments checked.
+#[derive(Debug, Default)]
+pub(crate) struct ChainView {
+    /// The number of the latest block
+    pub(crate) latest_block: u64,
+    /// The hash of each block holding a pending payment, `None` if the chain no longer has it.
+    /// Blocks which could not be looked up are missing.
+    pub(crate) block_hashes: HashMap<u64, Option<Hash>>,
+    /// The payments found within the last blocks for the pending payments not yet located, `None`
+    /// if they could not be looked up.
+    pub(crate) recent_payments: Option<Vec<DataPaymentEvent>>,
+}
+
+/// The outcome of the check of a pending payment against a [`ChainView`].
+#[derive(Debug, PartialEq)]
+pub(crate) enum PaymentCheck {
+    /// Confirmed by enough blocks
+    Settled,
+    /// Not yet confirmed by enough blocks, found in the given blocks
+    Pending(Vec<DataPaymentEvent>),
+    /// A block holding the payment left the chain, the payment must be verified again
+    Reorged,
+    /// The chain could not be looked up, checked again on the next round
+    Unknown,
+}
+
+/// Check a pending payment against the chain.
+pub(crate) fn check_payment(
+    payment: &PendingPayment,
+    chain: &ChainView,
+    confirmation_depth: u64,
+) -> PaymentCheck {
+    // Not yet located on chain, e.g. after being verified again
+    if payment.events.is_empty() {
+        let Some(recent_payments) = &chain.recent_payments else {
+            return PaymentCheck::Unknown;
+        };
+        let events: Vec<_> = recent_payments
+            .iter()
+            .filter(|event| payment.owned_quote_hashes.contains(&event.quote_hash))
+            .cloned()
+            .collect();
+        return if events.is_empty() {
+            PaymentCheck::Settled
+        } else {
+            PaymentCheck::Pending(events)
+        };
+    }
+
+    let mut unknown = false;
+    for event in &payment.events {
+        match chain.block_hashes.get(&event.block_number) {
+            Some(None) => return PaymentCheck::Reorged,
+            Some(Some(hash))
+                if event
+                    .block_hash
+                    .is_some_and(|block_hash| block_hash != *hash) =>
+            {
+                return PaymentCheck::Reorged;
+            }
+            Some(Some(_)) => {}
+            None => unknown = true,
+        }
+    }
+    if unknown {
+        return PaymentCheck::Unknown;
+    }
+
+    let confirmations = chain.latest_block.saturating_sub(payment.last_block()) + 1;
+    if confirmations >= confirmation_depth {
+        PaymentCheck::Settled
+    } else {
+        PaymentCheck::Pending(payment.events.clone())
+    }
+}
+
+/// The records whose payment is not yet buried under `confirmation_depth` blocks, and could still
+/// be dropped by a chain reorganisation.
+///
+/// They are re-validated periodically: a payment in a block that left the chain is verified again,
+/// and the record is dropped if the payment is no longer valid. A payment is settled once confirmed
+/// by enough blocks. Pending payments are only kept in memory.
+#[derive(Debug)]
+pub(crate) struct PendingPayments {
+    confirmation_depth: u64,
+    pending: Mutex<HashMap<RecordKey, PendingPayment>>,
+    /// The records recently removed for lack of payment, with the time they were removed
+    removed: Mutex<HashMap<RecordKey, Instant>>,
+    /// Whether a check of the pending payments is in flight
+    checking: AtomicBool,
+}
+
+/// A check of the pending payments in flight, which ends when dropped.
+pub(crate) struct CheckInFlight<'a>(&'a AtomicBool);
+
+impl Drop for CheckInFlight<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+impl PendingPayments {
+    pub(crate) fn new(confirmation_depth: u64) -> Self {
+        Self {
+            confirmation_depth,
+            pending: Mutex::new(HashMap::new()),
+            removed: Mutex::new(HashMap::new()),
+            checking: AtomicBool::new(false),
+        }
+    }
+
+    /// The number of blocks a payment must be confirmed by. 0 disables the check.
+    pub(crate) fn confirmation_depth(&self) -> u64 {
+        self.confirmation_depth
+    }
+
+    /// Track the payment of a record. Returns false if too many payments are already tracked.
+    pub(crate) fn insert(&self, key: RecordKey, payment: PendingPayment) -> bool {
+        let Ok(mut pending) = self.pending.lock() else {
+            return false;
+        };
+        if pending.len() >= MAX_PENDING_PAYMENTS && !pending.contains_key(&key) {
+            return false;
+        }
+        let _ = pending.insert(key, payment);
+        true
+    }
+
+    /// Stop tracking the payment of a record.
+    pub(crate) fn remove(&self, key: &RecordKey) -> Option<PendingPayment> {
+        self.pending.lock().ok()?.remove(key)
+    }
+
+    /// A snapshot of the pending payments, to check them without holding the lock.
+    pub(crate) fn snapshot(&self) -> Vec<(RecordKey, PendingPayment)> {
+        self.pending
+            .lock()
+            .map(|pending| {
+                pending
+                    .iter()
+                    .map(|(key, payment)| (key.clone(), payment.clone()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.pending
+            .lock()
+            .map(|pending| pending.len())
+            .unwrap_or(0)
+    }
+
+    /// Start a check of the pending payments, unless one is already in flight.
+    pub(crate) fn start_check(&self) -> Option<CheckInFlight<'_>> {
+        self.checking
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .ok()
+            .map(|_| CheckInFlight(&self.checking))
+    }
+
+    /// Remember that a record was removed for lack of payment.
+    pub(crate) fn mark_removed(&self, key: RecordKey) {
+        let Ok(mut removed) = self.removed.lock() else {
+            return;
+        };
+        removed.retain(|_, removed_at| removed_at.elapsed() < REMOVED_RECORDS_TTL);
+        if removed.len() < MAX_PENDING_PAYMENTS {
+            let _ = removed.insert(key, Instant::now());
+        }
+    }
+
+    /// Whether a record was recently removed for lack of payment, and must not be accepted back
+    /// from replication.
+    pub(crate) fn was_removed(&self, key: &RecordKey) -> bool {
+        self.removed.lock().is_ok_and(|removed| {
+            removed
+                .get(key)
+                .is_some_and(|removed_at| removed_at.elapsed() < REMOVED_RECORDS_TTL)
+        })
+    }
+}
+
+impl Node {
+    /// Track the payment of a record just accepted if it is not yet confirmed by enough blocks.
+    /// Returns true if the payment is pending.
+    pub(crate) async fn track_payment_confirmation(
+        &self,
+        key: &RecordKey,
+        owned_quote_hashes: Vec<QuoteHash>,
+        payments_to_verify: Vec<(QuoteHash, QuotingMetrics, RewardsAddress)>,
+        reward_amount: Amount,
+    ) -> bool {
+        let confirmation_depth = self.pending_payments().confirmation_depth();
+        if confirmation_depth == 0 {
+            return false;
+        }
+        let pretty_key = PrettyPrintRecordKey::from(key);
+        let lookup = match latest_block_number(self.evm_network()).await {
+            Ok(latest_block) => {
+                unconfirmed_payments(
+                    self.evm_network(),
+                    owned_quote_hashes.clone(),
+                    confirmation_depth,
+                    latest_block,
+                )
+                .await
+            }
+            Err(err) => Err(err),
+        };
+        let events = match lookup {
+            Ok(events) if events.is_empty() => return false,
+            Ok(events) => events,
+            Err(err) => {
+                // Can't tell how deep the payment is, it will be looked up again later on
+                warn!("Failed to look up the confirmations of the payment for {pretty_key}: {err}");
+                vec![]
+            }
+        };
+        debug!("Payment for {pretty_key} is not yet confirmed by {confirmation_depth} blocks");
+        let tracked = self.pending_payments().insert(
+            key.clone(),
+            PendingPayment {
+                owned_quote_hashes,
+                payments_to_verify,
+                reward_amount,
+                events,
+            },
+        );
+        if !tracked {
+            warn!("Too many pending payments, not tracking the payment for {pretty_key}");
+        }
+        tracked
+    }
+
+    /// Check the pending payments: settle the ones confirmed by enough blocks, and verify again
+    /// the ones dropped by a chain reorganisation, removing the records no longer paid for.
+    ///
+    /// The chain is looked up once for all the pending payments, and a single check runs at once.
+    pub(crate) async fn check_pending_payments(&self) {
+        let Some(_check) = self.pending_payments().start_check() else {
+            debug!("A check of the pending payments is already in flight");
+            return;
+        };
+        let pending = self.pending_payments().snapshot();
+        if pending.is_empty() {
+            return;
+        }
+        debug!("Checking {} pending payment(s)", pending.len());
+        let confirmation_depth = self.pending_payments().confirmation_depth();
+
+        let Some(chain) = self.fetch_chain_view(&pending, confirmation_depth).await else {
+            return;
+        };
+
+        for (key, payment) in pending {
+            let pretty_key = PrettyPrintRecordKey::from(&key).into_owned();
+            match check_payment(&payment, &chain, confirmation_depth) {
+                PaymentCheck::Settled => {
+                    debug!("Payment for {pretty_key} is confirmed");
+                    let _ = self.pending_payments().remove(&key);
+                }
+                PaymentCheck::Pending(events) => {
+                    if events != payment.events {
+                        let _ = self
+                            .pending_payments()
+                            .insert(key, PendingPayment { events, ..payment });
+                    }
+                }
+                PaymentCheck::Unknown => {
+                    debug!("Could not check the payment for {pretty_key}, checking it next round");
+                }
+                PaymentCheck::Reorged => self.verify_reorged_payment(key, payment).await,
+            }
+        }
+    }
+
+    /// Look up the chain once for all the pending payments: the latest block, the blocks holding
+    /// the located payments, and the recent payments for the others.
+    async fn fetch_chain_view(
+        &self,
+        pending: &[(RecordKey, PendingPayment)],
+        confirmation_depth: u64,
+    ) -> Option<ChainView> {
+        let latest_block = match latest_block_number(self.evm_network()).await {
+            Ok(latest_block) => latest_block,
+            Err(err) => {
+                warn!("Failed to get the latest block to check the pending payments: {err}");
+                return None;
+            }
+        };
+
+        let block_numbers: BTreeSet<u64> = pending
+            .iter()
+            .flat_map(|(_, payment)| payment.events.iter().map(|event| event.block_number))
+            .collect();
+        let mut block_hashes = HashMap::new();
+        for block_number in block_numbers {
+            match block_hash(self.evm_network(), block_number).await {
+                Ok(hash) => {
+                    let _ = block_hashes.insert(block_number, hash);
+                }
+                Err(err) => warn!("Failed to look up block {block_number}: {err}"),
+            }
+        }
+
+        let unlocated_quote_hashes: Vec<QuoteHash> = pending
+            .iter()
+            .filter(|(_, payment)| payment.events.is_empty())
+            .flat_map(|(_, payment)| payment.owned_quote_hashes.iter().copied())
+            .collect();
+        let recent_payments = match unconfirmed_payments(
+            self.evm_network(),
+            unlocated_quote_hashes,
+            confirmation_depth,
+            latest_block,
+        )
+        .await
+        {
+            Ok(events) => Some(events),
+            Err(err) => {
+                warn!("Failed to look up the recent payments: {err}");
+                None
+            }
+        };
+
+        Some(ChainView {
+            latest_block,
+            block_hashes,
+            recent_payments,
+        })
+    }
+
+    /// Verify again a payment dropped by a chain reorganisation, removing the record and reverting
+    /// its reward if the payment is no longer valid.
+    async fn verify_reorged_payment(&self, key: RecordKey, payment: PendingPayment) {
+        let pretty_key = PrettyPrintRecordKey::from(&key).into_owned();
+        warn!(
+            "The payment for {pretty_key} was dropped by a chain reorganisation, verifying it again"
+        );
+        match self
+            .verify_data_payment_with_retries(
+                &pretty_key,
+                payment.owned_quote_hashes.clone(),
+                payment.payments_to_verify.clone(),
+            )
+            .await
+        {
+            Ok(_) => {
+                // The payment made it to another block, it is located again next round
+                let _ = self.pending_payments().insert(
+                    key,
+                    PendingPayment {
+                        events: vec![],
+                        ..payment
+                    },
+                );
+            }
+            Err(PutValidationError::PaymentVerificationFailed {
+                error: PaymentVaultError::PaymentInvalid,
+                ..
+            }) => {
+                error!("The payment for {pretty_key} is no longer valid, removing the record");
+                let _ = self.pending_payments().remove(&key);
+                self.pending_payments().mark_removed(key.clone());
+                self.network().remove_unpaid_local_record(key);
+                self.revert_reward(&pretty_key, &payment).await;
+            }
+            Err(err) => {
+                warn!("Failed to verify again the payment for {pretty_key}: {err}");
+            }
+        }
+    }
+
+    /// Revert the reward accounted for a payment no longer valid.
+    async fn revert_reward(
+        &self,
+        pretty_key: &PrettyPrintRecordKey<'static>,
+        payment: &PendingPayment,
+    ) {
+        if payment.reward_amount.is_zero() {
+            return;
+        }
+        self.rewards_ledger()
+            .revert(
+                &pretty_key.to_string(),
+                &payment.owned_quote_hashes,
+                payment.reward_amount,
+            )
+            .await;
+
+        #[cfg(feature = "open-metrics")]
+        if let Some(metrics_recorder) = self.metrics_recorder() {
+            let current_value = metrics_recorder.current_reward_wallet_balance.get();
+            let new_value = current_value
+                .saturating_sub(payment.reward_amount.try_into().unwrap_or(i64::MAX))
+                .max(0);
+            let _ = metrics_recorder
+                .current_reward_wallet_balance
+                .set(new_value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(block_number: u64, block_hash: Option<Hash>) -> DataPaymentEvent {
+        DataPaymentEvent {
+            rewards_address: RewardsAddress::repeat_byte(2),
+            amount: Amount::from(1u64),
+            quote_hash: QuoteHash::repeat_byte(1),
+            block_number,
+            block_hash,
+            transaction_hash: None,
+        }
+    }
+
+    fn payment(events: Vec<DataPaymentEvent>) -> PendingPayment {
+        PendingPayment {
+            owned_quote_hashes: vec![QuoteHash::repeat_byte(1)],
+            payments_to_verify: vec![],
+            reward_amount: Amount::from(1u64),
+            events,
+        }
+    }
+
+    fn chain(latest_block: u64, blocks: &[(u64, Option<Hash>)]) -> ChainView {
+        ChainView {
+            latest_block,
+            block_hashes: blocks.iter().copied().collect(),
+            recent_payments: Some(vec![]),
+        }
+    }
+
+    #[test]
+    fn pending_payments_are_tracked_until_removed() {
+        let pending = PendingPayments::new(12);
+        let key = RecordKey::new(&[1]);
+        assert_eq!(pending.len(), 0);
+
+        assert!(pending.insert(
+            key.clone(),
+            payment(vec![event(100, None), event(105, None)])
+        ));
+        assert_eq!(pending.len(), 1);
+        let snapshot = pending.snapshot();
+        assert_eq!(snapshot[0].0, key);
+        assert_eq!(snapshot[0].1.last_block(), 105);
+
+        assert!(pending.remove(&key).is_some());
+        assert!(pending.remove(&key).is_none());
+        assert_eq!(pending.len(), 0);
+    }
+
+    #[test]
+    fn payments_should_settle_once_deep_enough() {
+        let hash = Some(Hash::repeat_byte(7));
+        let payment = payment(vec![event(100, hash)]);
+
+        assert_eq!(
+            check_payment(&payment, &chain(110, &[(100, hash)]), 12),
+            PaymentCheck::Pending(payment.events.clone())
+        );
+        assert_eq!(
+            check_payment(&payment, &chain(111, &[(100, hash)]), 12),
+            PaymentCheck::Settled
+        );
+    }
+
+    #[test]
+    fn payments_should_be_reverified_when_their_block_left_the_chain() {
+        let hash = Some(Hash::repeat_byte(7));
+        let payment = payment(vec![event(100, hash)]);
+
+        // Another block at the same height
+        let replaced = chain(105, &[(100, Some(Hash::repeat_byte(8)))]);
+        assert_eq!(
+            check_payment(&payment, &replaced, 12),
+            PaymentCheck::Reorged
+        );
+        // The chain got shorter
+        assert_eq!(
+            check_payment(&payment, &chain(99, &[(100, None)]), 12),
+            PaymentCheck::Reorged
+        );
+        // The block could not be looked up
+        assert_eq!(
+            check_payment(&payment, &chain(105, &[]), 12),
+            PaymentCheck::Unknown
+        );
+    }
+
+    #[test]
+    fn reverified_payments_should_be_located_again() {
+        let payment = payment(vec![]);
+        let mut chain = chain(200, &[]);
+
+        chain.recent_payments = None;
+        assert_eq!(check_payment(&payment, &chain, 12), PaymentCheck::Unknown);
+
+        let found = event(195, Some(Hash::repeat_byte(9)));
+        let mut unrelated = event(196, None);
+        unrelated.quote_hash = QuoteHash::repeat_byte(3);
+        chain.recent_payments = Some(vec![found.clone(), unrelated]);
+        assert_eq!(
+            check_payment(&payment, &chain, 12),
+            PaymentCheck::Pending(vec![found])
+        );
+
+        // Not within the last blocks: buried deep enough
+        chain.recent_payments = Some(vec![]);
+        assert_eq!(check_payment(&payment, &chain, 12), PaymentCheck::Settled);
+    }
+
+    #[test]
+    fn a_single_check_should_be_in_flight() {
+        let pending = PendingPayments::new(12);
+        let check = pending.start_check();
+        assert!(check.is_some());
+        assert!(pending.start_check().is_none());
+        drop(check);
+        assert!(pending.start_check().is_some());
+    }
+
+    #[test]
+    fn removed_records_should_be_remembered() {
+        let pending = PendingPayments::new(12);
+        let key = RecordKey::new(&[1]);
+        assert!(!pending.was_removed(&key));
+        pending.mark_removed(key.clone());
+        assert!(pending.was_removed(&key));
+        assert!(!pending.was_removed(&RecordKey::new(&[2])));
+    }
+}
@@ -9,31 +9,41 @@
 use std::collections::BTreeSet;
 
 use crate::error::PutValidationError;
+use crate::free_updates::FreeUpdate;
+use crate::networking::{LatencyOperation, LatencyOutcome};
 use crate::{Marker, Result, node::Node};
-use ant_evm::ProofOfPayment;
 use ant_evm::merkle_payment_vault::{
     get_merkle_payment_info, get_merkle_payment_packed_commitments,
 };
 use ant_evm::merkle_payments::CANDIDATES_PER_POOL;
 use ant_evm::merkle_payments::MerklePaymentProof;
 use ant_evm::payment_vault::verify_data_payment;
+use ant_evm::{Amount, ProofOfPayment, QuoteHash, QuotingMetrics, RewardsAddress};
 use ant_protocol::storage::GraphEntry;
 use ant_protocol::{
     NetworkAddress, PrettyPrintRecordKey,
     storage::{
-        Chunk, ChunkAddress, DataTypes, GraphEntryAddress, Pointer, PointerAddress, RecordHeader,
-        RecordKind, Scratchpad, ValidationType, try_deserialize_record, try_serialize_record,
+        Chunk, ChunkAddress, DataTypes, GraphEntryAddress, Pointer, PointerAddress,
+        RECORD_EXPIRY_CLOCK_SKEW, RECORD_LEASE_PERIOD, RecordHeader, RecordKind, Scratchpad,
+        ValidationType, try_deserialize_record, try_serialize_record,
+        try_serialize_record_with_header, update_work::is_update_work_valid,
     },
 };
 use libp2p::PeerId;
 use libp2p::kad::{KBucketDistance as Distance, Record, RecordKey, U256};
 use std::collections::HashSet;
-use std::time::Duration;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use xor_name::XorName;
 
 // We retry the payment verification once after waiting this many seconds to rule out the possibility of an EVM node state desync
 const RETRY_PAYMENT_VERIFICATION_WAIT_TIME_SECS: u64 = 5;
 
+// Max number of extra payment verification attempts when the EVM RPC is rate limiting us
+const MAX_RATE_LIMITED_PAYMENT_VERIFICATION_RETRIES: u32 = 3;
+
+// Initial backoff between rate limited payment verification attempts, doubled on each retry
+const RATE_LIMITED_PAYMENT_VERIFICATION_BACKOFF: Duration = Duration::from_millis(500);
+
 // Maximum number of attempts when fetching on-chain calldata for cost unit verification
 const MAX_COST_UNIT_FETCH_RETRIES: u32 = 3;
 
@@ -41,14 +51,38 @@ const MAX_COST_UNIT_FETCH_RETRIES: u32 = 3;
 const PEERS_TO_QUERY: usize = CANDIDATES_PER_POOL + (CANDIDATES_PER_POOL / 4);
 
 impl Node {
-    /// Validate a record and its payment, and store the record to the RecordStore
+    /// Validate a record and its payment, and store the record to the RecordStore.
+    /// `source` is the peer which sent the record, if known.
     pub(crate) async fn validate_and_store_record(
         &self,
         record: Record,
+        source: Option<PeerId>,
     ) -> Result<(), PutValidationError> {
         let record_header = RecordHeader::from_record(&record)
             .map_err(|_| PutValidationError::InvalidRecordHeader)?;
 
+        // New paid data is refused once the disk is running out of space, or while hibernating,
+        // free updates of existing mutable records are still accepted.
+        if matches!(
+            record_header.kind,
+            RecordKind::DataWithPayment(_) | RecordKind::DataWithMerklePayment(_)
+        ) {
+            if self.is_hibernating() {
+                warn!(
+                    "Rejecting paid record {:?} as the node is hibernating",
+                    PrettyPrintRecordKey::from(&record.key)
+                );
+                return Err(PutValidationError::StorageFull);
+            }
+            if self.disk_space_monitor().is_full() {
+                warn!(
+                    "Rejecting paid record {:?} as the node is running out of disk space",
+                    PrettyPrintRecordKey::from(&record.key)
+                );
+                return Err(PutValidationError::StorageFull);
+            }
+        }
+
         match record_header.kind {
             RecordKind::DataWithPayment(DataTypes::Chunk) => {
                 let record_key = record.key.clone();
@@ -164,7 +198,14 @@ impl Node {
                 // So that when the replicate target asking for the copy,
                 // the node can have a higher chance to respond.
                 let store_scratchpad_result = self
-                    .validate_and_store_scratchpad_record(scratchpad, record_key.clone(), true)
+                    .validate_and_store_scratchpad_record(
+                        scratchpad,
+                        record_key.clone(),
+                        true,
+                        record_header.expiry,
+                        true,
+                        None,
+                    )
                     .await;
 
                 match store_scratchpad_result {
@@ -208,10 +249,18 @@ impl Node {
                         PrettyPrintRecordKey::from(&record.key).into_owned(),
                     ));
                 }
+                let free_update = self.check_free_update(&record, &record_header, source)?;
 
                 // store the scratchpad
-                self.validate_and_store_scratchpad_record(scratchpad, key, true)
-                    .await
+                self.validate_and_store_scratchpad_record(
+                    scratchpad,
+                    key,
+                    true,
+                    record_header.expiry,
+                    false,
+                    free_update,
+                )
+                .await
             }
             RecordKind::DataOnly(DataTypes::GraphEntry) => {
                 // Transactions should always be paid for
@@ -315,9 +364,15 @@ impl Node {
                         PrettyPrintRecordKey::from(&record.key).into_owned(),
                     ));
                 }
+                let free_update = self.check_free_update(&record, &record_header, source)?;
 
                 let res = self
-                    .validate_and_store_pointer_record(pointer, record.key.clone(), true)
+                    .validate_and_store_pointer_record(
+                        pointer,
+                        record.key.clone(),
+                        true,
+                        free_update,
+                    )
                     .await;
                 if res.is_ok() {
                     let content_hash = XorName::from_content(&record.value);
@@ -368,7 +423,7 @@ impl Node {
                 }
 
                 let res = self
-                    .validate_and_store_pointer_record(pointer, record.key.clone(), true)
+                    .validate_and_store_pointer_record(pointer, record.key.clone(), true, None)
                     .await;
                 if res.is_ok() {
                     let content_hash = XorName::from_content(&record.value);
@@ -399,6 +454,16 @@ impl Node {
             "Storing record which was replicated to us {:?}",
             PrettyPrintRecordKey::from(&record.key)
         );
+        // Don't take back a record this node just removed as it is no longer paid for
+        if self.pending_payments().was_removed(&record.key) {
+            warn!(
+                "Rejecting replicated record {:?} removed for lack of payment",
+                PrettyPrintRecordKey::from(&record.key)
+            );
+            return Err(PutValidationError::UnpaidRecordRemoved(
+                PrettyPrintRecordKey::from(&record.key).into_owned(),
+            ));
+        }
         let record_header = RecordHeader::from_record(&record)
             .map_err(|_| PutValidationError::InvalidRecordHeader)?;
         match record_header.kind {
@@ -435,6 +500,15 @@ impl Node {
                     return Ok(());
                 }
 
+                // Only mutable records keep being replicated in once the disk is running out of space
+                if self.disk_space_monitor().is_full() {
+                    debug!(
+                        "Skipping replicated chunk {:?} as the node is running out of disk space",
+                        chunk.network_address()
+                    );
+                    return Err(PutValidationError::StorageFull);
+                }
+
                 self.store_chunk(&chunk, false)
             }
             RecordKind::DataOnly(DataTypes::Scratchpad) => {
@@ -444,8 +518,15 @@ impl Node {
                         PrettyPrintRecordKey::from(&record.key).into_owned(),
                     )
                 })?;
-                self.validate_and_store_scratchpad_record(scratchpad, key, false)
-                    .await
+                self.validate_and_store_scratchpad_record(
+                    scratchpad,
+                    key,
+                    false,
+                    record_header.expiry,
+                    false,
+                    None,
+                )
+                .await
             }
             RecordKind::DataOnly(DataTypes::GraphEntry) => {
                 let record_key = record.key.clone();
@@ -465,12 +546,50 @@ impl Node {
                     )
                 })?;
                 let key = record.key.clone();
-                self.validate_and_store_pointer_record(pointer, key, false)
+                self.validate_and_store_pointer_record(pointer, key, false, None)
                     .await
             }
         }
     }
 
+    /// Check a free update of an existing pointer or scratchpad sent by `source`, if known, against
+    /// the free update limits.
+    ///
+    /// Over the limits, it is rejected unless it carries a valid proof of work. Within them, it is
+    /// returned to be counted once fully validated, right before being stored. Updates carrying a
+    /// valid proof of work are not counted.
+    fn check_free_update(
+        &self,
+        record: &Record,
+        record_header: &RecordHeader,
+        source: Option<PeerId>,
+    ) -> Result<Option<FreeUpdate>, PutValidationError> {
+        let pretty_key = PrettyPrintRecordKey::from(&record.key);
+        let difficulty = self.free_update_limiter().work_difficulty();
+        if difficulty > 0
+            && let Some(nonce) = record_header.work_nonce
+        {
+            let (_, _, header_size) = RecordHeader::parse(&record.value)
+                .map_err(|_| PutValidationError::InvalidRecordHeader)?;
+            let content = record.value.get(header_size..).unwrap_or_default();
+            if is_update_work_valid(&record.key, content, nonce, difficulty) {
+                debug!("Free update of {pretty_key:?} carries a valid proof of work");
+                return Ok(None);
+            }
+            warn!("Free update of {pretty_key:?} carries an invalid proof of work");
+        }
+
+        if self.free_update_limiter().is_limited(&record.key, source) {
+            warn!(
+                "Too many free updates of {pretty_key:?}, from {source:?}, rejecting PUT without payment"
+            );
+            return Err(PutValidationError::FreeUpdateRateLimited(
+                pretty_key.into_owned(),
+            ));
+        }
+        Ok(Some(FreeUpdate { source }))
+    }
+
     /// Check key is valid compared to the network name, and if we already have this data or not.
     /// returns true if data already exists locally
     pub(crate) async fn validate_key_and_existence(
@@ -559,11 +678,18 @@ impl Node {
     /// Check Counter: It MUST ensure that the new counter value is strictly greater than the currently stored value to prevent replay attacks.
     /// Verify Signature: It MUST use the public key to verify the BLS12-381 signature against the content hash and the counter.
     /// Accept or Reject: If all verifications succeed, the node MUST accept the packet and replace any previous version. Otherwise, it MUST reject the update.
+    ///
+    /// A temporary scratchpad carries the `expiry` of its lease. A paid put may set it up to one
+    /// lease period from now, renewing the lease of the same scratchpad. Otherwise the expiry of
+    /// the local copy is kept, see `merge_expiry`.
     pub(crate) async fn validate_and_store_scratchpad_record(
         &self,
         scratchpad: Scratchpad,
         record_key: RecordKey,
         is_client_put: bool,
+        expiry: Option<u64>,
+        is_paid: bool,
+        free_update: Option<FreeUpdate>,
     ) -> Result<(), PutValidationError> {
         // owner PK is defined herein, so as long as record key and this match, we're good
         let addr = scratchpad.address();
@@ -577,23 +703,34 @@ impl Node {
             return Err(PutValidationError::RecordKeyMismatch);
         }
 
+        if let Some(expiry) = expiry {
+            check_expiry(&scratchpad_key, expiry)?;
+        }
+
         // check if the Scratchpad is present locally that we don't have a newer version
-        if let Some(local_pad) = self
+        let mut expiry = expiry;
+        if let Some(local_record) = self
             .network()
             .get_local_record(&scratchpad_key)
             .await
             .map_err(|_| PutValidationError::LocalSwarmError)?
         {
-            let local_pad = try_deserialize_record::<Scratchpad>(&local_pad).map_err(|_| {
+            let local_expiry = RecordHeader::from_record(&local_record)
+                .map_err(|_| PutValidationError::InvalidRecordHeader)?
+                .expiry;
+            let local_pad = try_deserialize_record::<Scratchpad>(&local_record).map_err(|_| {
                 PutValidationError::InvalidRecord(
                     PrettyPrintRecordKey::from(&scratchpad_key).into_owned(),
                 )
             })?;
+            expiry = merge_expiry(Some(local_expiry), expiry, is_paid);
             if local_pad == scratchpad {
-                debug!("Scratchpad at {addr:?} already exists locally, skip to success");
-                return Ok(());
-            }
-            if local_pad.counter() >= scratchpad.counter() {
+                if expiry == local_expiry {
+                    debug!("Scratchpad at {addr:?} already exists locally, skip to success");
+                    return Ok(());
+                }
+                info!("Renewing the lease of scratchpad {addr:?} until {expiry:?}");
+            } else if local_pad.counter() >= scratchpad.counter() {
                 warn!(
                     "Rejecting Scratchpad PUT with counter less than or equal to the current counter"
                 );
@@ -602,6 +739,12 @@ impl Node {
                     expected: local_pad.counter(),
                 });
             }
+        } else if is_client_put && !is_paid {
+            // The free updates are checked to have a local copy, unless it has expired since
+            warn!("Rejecting the update of scratchpad {addr:?} as its lease has expired");
+            return Err(PutValidationError::RecordExpired(
+                PrettyPrintRecordKey::from(&scratchpad_key).into_owned(),
+            ));
         }
 
         // ensure data integrity
@@ -616,14 +759,32 @@ impl Node {
             return Err(PutValidationError::ScratchpadTooBig(scratchpad.size()));
         }
 
+        if let Some(FreeUpdate { source }) = free_update
+            && !self
+                .free_update_limiter()
+                .try_acquire(&scratchpad_key, source)
+        {
+            warn!(
+                "Too many free updates of scratchpad {addr:?}, from {source:?}, rejecting PUT without payment"
+            );
+            return Err(PutValidationError::FreeUpdateRateLimited(
+                PrettyPrintRecordKey::from(&scratchpad_key).into_owned(),
+            ));
+        }
+
         info!(
             "Storing sratchpad {addr:?} with content of {:?} as Record locally",
             scratchpad.encrypted_data_hash()
         );
 
+        let kind = RecordKind::DataOnly(DataTypes::Scratchpad);
+        let header = match expiry {
+            Some(expiry) => RecordHeader::with_expiry(kind, expiry),
+            None => RecordHeader::new(kind),
+        };
         let record = Record {
             key: scratchpad_key.clone(),
-            value: try_serialize_record(&scratchpad, RecordKind::DataOnly(DataTypes::Scratchpad))
+            value: try_serialize_record_with_header(&scratchpad, header)
                 .map_err(|_| {
                     PutValidationError::RecordSerializationFailed(
                         PrettyPrintRecordKey::from(&scratchpad_key).into_owned(),
@@ -813,34 +974,46 @@ impl Node {
             }
         }
 
-        // check if payment is valid on chain
-        let payments_to_verify = payment.digest();
+        // check if payment is valid on chain, unless we have already verified it recently
         let owned_payment_quotes: Vec<_> = own_quotes.iter().map(|quote| quote.hash()).collect();
-        let reward_amount = match verify_data_payment(
-            self.evm_network(),
-            owned_payment_quotes.clone(),
-            payments_to_verify.clone(),
-        )
-        .await
+        let reward_amount = if self
+            .payment_verification_cache()
+            .contains_all(&owned_payment_quotes)
         {
-            Ok(amount) => amount,
-            Err(e) => {
-                warn!("Failed to verify record payment on the first attempt: {e}");
-                // Try again, because there could be a possible EVM node desync
-                tokio::time::sleep(Duration::from_secs(
-                    RETRY_PAYMENT_VERIFICATION_WAIT_TIME_SECS,
-                ))
+            self.record_metrics(Marker::PaymentVerificationCacheHit(&pretty_key));
+            // The reward of a cached payment has already been accounted for
+            Amount::ZERO
+        } else {
+            self.record_metrics(Marker::PaymentVerificationCacheMiss(&pretty_key));
+            let start = Instant::now();
+            let verification_result = self
+                .verify_data_payment_with_retries(
+                    &pretty_key,
+                    owned_payment_quotes.clone(),
+                    payment.digest(),
+                )
                 .await;
-                verify_data_payment(self.evm_network(), owned_payment_quotes, payments_to_verify)
-                    .await
-                    .inspect_err(|e| {
-                        warn!("Failed to verify record payment on the second attempt: {e}");
-                    })
-                    .map_err(|e| PutValidationError::PaymentVerificationFailed {
-                        record_key: pretty_key.clone(),
-                        error: e,
-                    })?
+            self.network().record_latency(
+                LatencyOperation::PaymentVerification,
+                LatencyOutcome::from(&verification_result),
+                start.elapsed(),
+            );
+            let amount = verification_result?;
+            // A payment which could still be dropped by a chain reorganisation is verified again
+            // on a new PUT, until confirmed
+            let pending = self
+                .track_payment_confirmation(
+                    &key,
+                    owned_payment_quotes.clone(),
+                    payment.digest(),
+                    amount,
+                )
+                .await;
+            if !pending {
+                self.payment_verification_cache()
+                    .insert(&owned_payment_quotes);
             }
+            amount
         };
 
         debug!("Payment of {reward_amount:?} is valid for record {pretty_key}");
@@ -848,6 +1021,13 @@ impl Node {
         if !reward_amount.is_zero() {
             // Notify `record_store` that the node received a payment.
             self.network().notify_payment_received();
+            self.rewards_ledger()
+                .record(
+                    &pretty_key.to_string(),
+                    &owned_payment_quotes,
+                    reward_amount,
+                )
+                .await;
 
             #[cfg(feature = "open-metrics")]
             if let Some(metrics_recorder) = self.metrics_recorder() {
@@ -886,6 +1066,59 @@ impl Node {
         Ok(())
     }
 
+    /// Verify a data payment on chain.
+    ///
+    /// Retries once after a pause to rule out an EVM node desync, and a bounded number of times
+    /// with exponential backoff when the RPC endpoint is rate limiting us.
+    pub(crate) async fn verify_data_payment_with_retries(
+        &self,
+        pretty_key: &PrettyPrintRecordKey<'static>,
+        owned_payment_quotes: Vec<QuoteHash>,
+        payments_to_verify: Vec<(QuoteHash, QuotingMetrics, RewardsAddress)>,
+    ) -> Result<Amount, PutValidationError> {
+        let mut rate_limited_retries = 0;
+        let mut desync_retried = false;
+
+        loop {
+            let err = match verify_data_payment(
+                self.evm_network(),
+                owned_payment_quotes.clone(),
+                payments_to_verify.clone(),
+            )
+            .await
+            {
+                Ok(amount) => return Ok(amount),
+                Err(err) => err,
+            };
+
+            if err.is_rate_limited()
+                && rate_limited_retries < MAX_RATE_LIMITED_PAYMENT_VERIFICATION_RETRIES
+            {
+                rate_limited_retries += 1;
+                let delay =
+                    RATE_LIMITED_PAYMENT_VERIFICATION_BACKOFF * 2u32.pow(rate_limited_retries - 1);
+                warn!(
+                    "Payment verification for record {pretty_key} was rate limited by the EVM RPC, retry #{rate_limited_retries} in {delay:?}: {err}"
+                );
+                tokio::time::sleep(delay).await;
+            } else if !desync_retried {
+                desync_retried = true;
+                warn!("Failed to verify record payment on the first attempt: {err}");
+                // Try again, because there could be a possible EVM node desync
+                tokio::time::sleep(Duration::from_secs(
+                    RETRY_PAYMENT_VERIFICATION_WAIT_TIME_SECS,
+                ))
+                .await;
+            } else {
+                warn!("Failed to verify record payment on the second attempt: {err}");
+                return Err(PutValidationError::PaymentVerificationFailed {
+                    record_key: pretty_key.clone(),
+                    error: err,
+                });
+            }
+        }
+    }
+
     /// Get the local GraphEntries for the provided `GraphEntryAddress`
     /// This only fetches the GraphEntries from the local store and does not perform any network operations.
     async fn get_local_graphentries(
@@ -965,6 +1198,7 @@ impl Node {
         pointer: Pointer,
         key: RecordKey,
         is_client_put: bool,
+        free_update: Option<FreeUpdate>,
     ) -> Result<(), PutValidationError> {
         // Verify the pointer's signature
         if !pointer.verify_signature() {
@@ -998,6 +1232,17 @@ impl Node {
             }
         }
 
+        if let Some(FreeUpdate { source }) = free_update
+            && !self.free_update_limiter().try_acquire(&key, source)
+        {
+            warn!(
+                "Too many free updates of pointer at {key:?}, from {source:?}, rejecting PUT without payment"
+            );
+            return Err(PutValidationError::FreeUpdateRateLimited(
+                PrettyPrintRecordKey::from(&key).into_owned(),
+            ));
+        }
+
         // Store the pointer
         let record = Record {
             key: key.clone(),
@@ -1085,8 +1330,18 @@ impl Node {
                 self.verify_merkle_payment(&proof, &net_addr).await?;
 
                 // Store scratchpad
+                let expiry = RecordHeader::from_record(&record)
+                    .map_err(|_| PutValidationError::InvalidRecordHeader)?
+                    .expiry;
                 let result = self
-                    .validate_and_store_scratchpad_record(scratchpad, record_key.clone(), true)
+                    .validate_and_store_scratchpad_record(
+                        scratchpad,
+                        record_key.clone(),
+                        true,
+                        expiry,
+                        true,
+                        None,
+                    )
                     .await;
                 if result.is_ok() {
                     let content_hash = XorName::from_content(&record.value);
@@ -1130,7 +1385,7 @@ impl Node {
 
                 // Store pointer
                 let result = self
-                    .validate_and_store_pointer_record(pointer, record_key.clone(), true)
+                    .validate_and_store_pointer_record(pointer, record_key.clone(), true, None)
                     .await;
                 if result.is_ok() {
                     let content_hash = XorName::from_content(&record.value);
@@ -1143,6 +1398,24 @@ impl Node {
         }
     }
 
+    /// Verify Merkle batch payment for a data address, recording how long it took
+    async fn verify_merkle_payment(
+        &self,
+        proof: &MerklePaymentProof,
+        target_address: &NetworkAddress,
+    ) -> Result<(), PutValidationError> {
+        let start = Instant::now();
+        let result = self
+            .verify_merkle_payment_and_topology(proof, target_address)
+            .await;
+        self.network().record_latency(
+            LatencyOperation::PaymentVerification,
+            LatencyOutcome::from(&result),
+            start.elapsed(),
+        );
+        result
+    }
+
     /// Verify Merkle batch payment for a data address
     ///
     /// This performs:
@@ -1150,7 +1423,7 @@ impl Node {
     /// 1. Query smart contract to get payment info
     /// 2. Verify Merkle proof structure and signatures
     /// 3. Verify network topology (paid nodes among closest 20 peers with majority knowledge)
-    async fn verify_merkle_payment(
+    async fn verify_merkle_payment_and_topology(
         &self,
         proof: &MerklePaymentProof,
         target_address: &NetworkAddress,
@@ -1475,3 +1748,40 @@ impl Node {
         Ok(())
     }
 }
+
+/// The expiry a scratchpad is stored with, given the expiry of the local copy if any, and the
+/// incoming one.
+///
+/// A record stored for good is never made temporary, and a lease is only ever extended. A paid put
+/// without expiry makes a temporary record permanent.
+fn merge_expiry(local: Option<Option<u64>>, incoming: Option<u64>, is_paid: bool) -> Option<u64> {
+    match (local, incoming) {
+        (None, incoming) => incoming,
+        (Some(None), _) => None,
+        (Some(Some(local)), Some(incoming)) => Some(local.max(incoming)),
+        (Some(Some(_)), None) if is_paid => None,
+        (Some(Some(local)), None) => Some(local),
+    }
+}
+
+/// Reject the expiries already past, and the ones beyond a lease period from now.
+fn check_expiry(record_key: &RecordKey, expiry: u64) -> Result<(), PutValidationError> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0);
+    let pretty_key = PrettyPrintRecordKey::from(record_key);
+    if expiry <= now {
+        debug!("Rejecting record {pretty_key:?} expired at {expiry}");
+        return Err(PutValidationError::RecordExpired(pretty_key.into_owned()));
+    }
+    let max_expiry = now + RECORD_LEASE_PERIOD.as_secs() + RECORD_EXPIRY_CLOCK_SKEW.as_secs();
+    if expiry > max_expiry {
+        warn!("Rejecting record {pretty_key:?} with an expiry {expiry} beyond {max_expiry}");
+        return Err(PutValidationError::InvalidRecordExpiry {
+            record_key: pretty_key.into_owned(),
+            expiry,
+        });
+    }
+    Ok(())
+}
@@ -12,7 +12,6 @@ use ant_evm::{PaymentQuote, QuotingMetrics, RewardsAddress};
 use ant_protocol::{NetworkAddress, error::Error as ProtocolError, storage::ChunkAddress};
 use libp2p::PeerId;
 use std::time::Duration;
-use xor_name::XorName;
 
 impl Node {
     pub(crate) fn create_quote_for_storecost(
@@ -21,13 +20,7 @@ impl Node {
         quoting_metrics: &QuotingMetrics,
         payment_address: &RewardsAddress,
     ) -> Result<PaymentQuote, ProtocolError> {
-        let content = match address {
-            NetworkAddress::ChunkAddress(addr) => *addr.xorname(),
-            NetworkAddress::GraphEntryAddress(addr) => addr.xorname(),
-            NetworkAddress::ScratchpadAddress(addr) => addr.xorname(),
-            NetworkAddress::PointerAddress(addr) => addr.xorname(),
-            NetworkAddress::PeerId(_) | NetworkAddress::RecordKey(_) => XorName::default(),
-        };
+        let content = address.xorname().unwrap_or_default();
         let timestamp = std::time::SystemTime::now();
         let bytes =
             PaymentQuote::bytes_for_signing(content, timestamp, quoting_metrics, payment_address);
@@ -57,13 +50,7 @@ pub(crate) fn verify_quote_for_storecost(
     debug!("Verifying payment quote for {address:?}: {quote:?}");
 
     // check address
-    let content = match address {
-        NetworkAddress::ChunkAddress(addr) => *addr.xorname(),
-        NetworkAddress::GraphEntryAddress(addr) => addr.xorname(),
-        NetworkAddress::ScratchpadAddress(addr) => addr.xorname(),
-        NetworkAddress::PointerAddress(addr) => addr.xorname(),
-        NetworkAddress::PeerId(_) | NetworkAddress::RecordKey(_) => XorName::default(),
-    };
+    let content = address.xorname().unwrap_or_default();
     if content != quote.content {
         return Err(Error::InvalidQuoteContent);
     }
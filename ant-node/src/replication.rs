@@ -6,11 +6,16 @@
 // KIND, either express or implied. Please review the Licences for the specific language governing
 // permissions and limitations relating to use of the SAFE Network Software.
 
-use crate::networking::{Addresses, Network};
-use crate::{error::Result, node::Node};
+use crate::networking::{
+    Addresses, ChurnStats, LatencyOperation, LatencyOutcome, Network, NodeIssue,
+};
+use crate::{
+    error::{Error, Result},
+    node::Node,
+};
 use ant_evm::ProofOfPayment;
-use ant_protocol::CLOSE_GROUP_SIZE;
 use ant_protocol::messages::Cmd;
+use ant_protocol::{CLOSE_GROUP_SIZE, close_group::close_group_majority};
 use ant_protocol::{
     NetworkAddress, PrettyPrintRecordKey,
     messages::{Query, QueryResponse, Request, Response},
@@ -21,14 +26,85 @@ use libp2p::{
     kad::{Record, RecordKey},
 };
 use prometheus_client::metrics::histogram::Histogram;
+use std::time::{Duration, Instant};
 use tokio::task::spawn;
 
+/// Number of peers joining or leaving the RT per hour for which the replication interval is halved.
+const REPLICATION_CHURN_HALVING_RATE: f64 = 30.0;
+
+/// The smallest window the replication interval is spread over is the min bound divided by this.
+/// It is the window used during a mass churn event, when the nodes are the most likely to sync.
+const MIN_REPLICATION_JITTER_SPREAD_DIVISOR: u32 = 3;
+
+/// The interval until the next periodic replication given the churn of the RT.
+///
+/// The interval is spread over a window so that the nodes do not replicate in sync, `jitter`,
+/// from 0 to 1, picking the interval within it. The window is from half the upper end, but no less
+/// than the min bound, to its upper end. That upper end shrinks from the max bound as more peers
+/// join and leave the RT, down to a third over the min bound, which is used during a mass churn
+/// event.
+pub(crate) fn adaptive_replication_interval(
+    churn: &ChurnStats,
+    (min, max): (Duration, Duration),
+    jitter: f64,
+) -> Duration {
+    let min = min.min(max);
+    let smallest_upper = (min + min / MIN_REPLICATION_JITTER_SPREAD_DIVISOR).min(max);
+    let upper = if churn.mass_churn {
+        smallest_upper
+    } else {
+        let churn_per_hour: usize = churn
+            .buckets
+            .values()
+            .map(|bucket| bucket.joins_per_hour + bucket.leaves_per_hour)
+            .sum();
+        max.div_f64(1.0 + churn_per_hour as f64 / REPLICATION_CHURN_HALVING_RATE)
+            .max(smallest_upper)
+    };
+    let lower = (upper / 2).max(min);
+    lower + (upper - lower).mul_f64(jitter.clamp(0.0, 1.0))
+}
+
 impl Node {
     /// Sends _all_ record keys every interval to all peers within the REPLICATE_RANGE.
     pub(crate) fn try_interval_replication(network: Network) {
         network.trigger_interval_replication()
     }
 
+    /// Adapt the periodic replication interval to the current churn of the RT. Returns the new
+    /// interval if it differs from `current_interval`.
+    pub(crate) async fn next_replication_interval(
+        &self,
+        current_interval: Duration,
+        jitter: f64,
+    ) -> Option<Duration> {
+        let churn = match self.network().get_churn_stats().await {
+            Ok(churn) => churn,
+            Err(err) => {
+                warn!(
+                    "Failed to get the churn of the RT, keeping the replication interval: {err:?}"
+                );
+                return None;
+            }
+        };
+        let next_interval =
+            adaptive_replication_interval(&churn, self.replication_interval_bounds(), jitter);
+        #[cfg(feature = "open-metrics")]
+        if let Some(metrics_recorder) = self.metrics_recorder() {
+            let _ = metrics_recorder
+                .replication_interval
+                .set(next_interval.as_secs() as i64);
+        }
+        if next_interval == current_interval {
+            return None;
+        }
+        debug!(
+            "Replication interval set to {next_interval:?}, the mass churn being {} and the bucket churn {:?}",
+            churn.mass_churn, churn.buckets
+        );
+        Some(next_interval)
+    }
+
     /// Cleanup unrelevant records if accumulated too many.
     pub(crate) fn trigger_irrelevant_record_cleanup(network: Network) {
         network.trigger_irrelevant_record_cleanup()
@@ -45,7 +121,15 @@ impl Node {
             let _handle = spawn(async move {
                 let pretty_key = PrettyPrintRecordKey::from(&key).into_owned();
                 debug!("Fetching record {pretty_key:?} from node {holder:?}");
-                let req = Request::Query(Query::GetReplicatedRecord {
+                let start = Instant::now();
+                let record_failure = || {
+                    node.network().record_latency(
+                        LatencyOperation::Replication,
+                        LatencyOutcome::Failure,
+                        start.elapsed(),
+                    )
+                };
+                let req = Request::from(Query::GetReplicatedRecord {
                     requester,
                     key: NetworkAddress::from(&key),
                 });
@@ -69,6 +153,7 @@ impl Node {
                             info!(
                                 "Failed fetch record {pretty_key:?} from holder {holder:?}, with error {err:?}"
                             );
+                            record_failure();
                             return;
                         }
                     },
@@ -76,20 +161,32 @@ impl Node {
                         info!(
                             "Cannot fetch record {pretty_key:?} from holder {holder:?}, with response {other:?}"
                         );
+                        record_failure();
                         return;
                     }
                     Err(err) => {
                         info!(
                             "Failed to send request to fetch record {pretty_key:?} from holder {holder:?}, with error {err:?}"
                         );
+                        record_failure();
                         return;
                     }
                 };
 
-                if let Err(err) = node.store_replicated_in_record(record).await {
+                let store_result = node.store_replicated_in_record(record).await;
+                node.network().record_latency(
+                    LatencyOperation::Replication,
+                    LatencyOutcome::from(&store_result),
+                    start.elapsed(),
+                );
+                if let Err(err) = store_result {
                     error!(
                         "During store replication fetched {pretty_key:?} from holder {holder:?}, got error {err:?}"
                     );
+                    if err.is_invalid_record() {
+                        node.network()
+                            .record_node_issues(holder, NodeIssue::InvalidRecord);
+                    }
                 } else {
                     debug!(
                         "Completed storing Replication Record {pretty_key:?} from holder {holder:?}."
@@ -257,7 +354,7 @@ impl Node {
                 .map(|recorder| recorder.network_wide_replication_holders.clone());
             let network = self.network().clone();
             let _handle = spawn(async move {
-                Self::network_wide_replication_per_key(
+                let _ = Self::network_wide_replication_per_key(
                     network,
                     key,
                     val_type,
@@ -268,12 +365,35 @@ impl Node {
         }
     }
 
+    /// Re-check which peers of the current close group of a record held by us have it, and push
+    /// it to the ones missing it. Returns the peers the record was pushed to.
+    pub(crate) async fn replicate_record_to_close_group(
+        network: Network,
+        key: NetworkAddress,
+    ) -> Result<Vec<PeerId>> {
+        let key = NetworkAddress::from(&key.to_record_key());
+        let Some(val_type) = network.get_all_local_record_addresses().await?.remove(&key) else {
+            return Err(Error::RecordNotHeld(key));
+        };
+
+        info!("Replicating record {key:?} to its close group on request");
+        Ok(Self::network_wide_replication_per_key(
+            network,
+            key,
+            val_type,
+            #[cfg(feature = "open-metrics")]
+            None,
+        )
+        .await)
+    }
+
+    /// Returns the peers the record was pushed to.
     async fn network_wide_replication_per_key(
         network: Network,
         key: NetworkAddress,
         val_type: ValidationType,
         #[cfg(feature = "open-metrics")] network_wide_replication_holders: Option<Histogram>,
-    ) {
+    ) -> Vec<PeerId> {
         // get closest to the key
         let peers = match network.get_closest_peers(&key).await {
             Ok(mut peers) => {
@@ -287,12 +407,12 @@ impl Node {
                 warn!(
                     "Failed to get closest peers for network wide replication for key {key:?} with error {err:?}"
                 );
-                return;
+                return vec![];
             }
         };
 
         // check if these peers have the record
-        let req = Request::Query(Query::GetReplicatedRecord {
+        let req = Request::from(Query::GetReplicatedRecord {
             requester: NetworkAddress::from(network.peer_id()),
             key: key.clone(),
         });
@@ -332,7 +452,7 @@ impl Node {
             }
         }
 
-        if to_replicate.len() >= (CLOSE_GROUP_SIZE / 2 + 1) {
+        if to_replicate.len() >= close_group_majority() {
             info!(
                 "Some peers do not have the record {key:?} during network wide replication, notifying the swarm"
             );
@@ -346,7 +466,7 @@ impl Node {
             recorder.observe(fraction_holders);
         }
 
-        for peer in to_replicate {
+        for peer in to_replicate.iter().copied() {
             let request = Request::Cmd(Cmd::Replicate {
                 holder: NetworkAddress::from(network.peer_id()),
                 keys: vec![(key.clone(), val_type.clone())],
@@ -359,5 +479,69 @@ impl Node {
                 "Sent Cmd::Replicate for key {key:?} to peer {peer:?} during network wide replication"
             );
         }
+        to_replicate
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::networking::BucketChurn;
+
+    const BOUNDS: (Duration, Duration) = (Duration::from_secs(90), Duration::from_secs(180));
+
+    #[test]
+    fn replication_interval_should_shrink_with_churn() {
+        let mut churn = ChurnStats::default();
+        assert_eq!(
+            adaptive_replication_interval(&churn, BOUNDS, 1.0),
+            Duration::from_secs(180)
+        );
+        assert_eq!(
+            adaptive_replication_interval(&churn, BOUNDS, 0.0),
+            Duration::from_secs(90)
+        );
+
+        let _ = churn.buckets.insert(
+            250,
+            BucketChurn {
+                joins_per_hour: 10,
+                leaves_per_hour: 20,
+            },
+        );
+        // Halved, but still spread over a third of the min bound
+        assert_eq!(
+            adaptive_replication_interval(&churn, BOUNDS, 1.0),
+            Duration::from_secs(120)
+        );
+        // The jitter does not go under the min bound
+        assert_eq!(
+            adaptive_replication_interval(&churn, BOUNDS, 0.0),
+            Duration::from_secs(90)
+        );
+
+        churn.mass_churn = true;
+        assert_eq!(
+            adaptive_replication_interval(&churn, BOUNDS, 0.0),
+            Duration::from_secs(90)
+        );
+        assert_eq!(
+            adaptive_replication_interval(&churn, BOUNDS, 0.5),
+            Duration::from_secs(105)
+        );
+        assert_eq!(
+            adaptive_replication_interval(&churn, BOUNDS, 1.0),
+            Duration::from_secs(120)
+        );
+    }
+
+    #[test]
+    fn replication_interval_should_handle_inverted_bounds() {
+        let churn = ChurnStats::default();
+        let bounds = (Duration::from_secs(300), Duration::from_secs(120));
+        assert_eq!(
+            adaptive_replication_interval(&churn, bounds, 0.5),
+            Duration::from_secs(120)
+        );
     }
 }
@@ -0,0 +1,303 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Ledger of the rewards received by the node, and monthly statements built from it.
+//!
+//! Every payment accepted for a record is appended as a JSON line to a file in the node's root
+//! directory, with the record it paid for, the quote hashes it paid and the amount credited to
+//! the node. The transaction hashes of the payments are not known to the node, which only checks
+//! the payments against the payment vault contract, so they must be looked up on chain from the
+//! quote hashes if needed.
+//!
+//! A payment dropped by a chain reorganisation after being accepted is reverted by appending
+//! another entry, which cancels the payment out when the ledger is read.
+
+use ant_evm::{Amount, QuoteHash};
+use chrono::{TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::OpenOptions;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Name of the rewards ledger file, in the node's root directory.
+pub const REWARDS_LEDGER_FILENAME: &str = "rewards_ledger.jsonl";
+
+/// A payment accepted by the node for storing a record.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RewardEntry {
+    /// Time the payment was accepted, in seconds since the UNIX epoch
+    pub timestamp: u64,
+    /// The key of the paid record
+    pub record_key: String,
+    /// The hashes of the node's quotes that were paid
+    pub quote_hashes: Vec<String>,
+    /// The amount credited to the node, in atto tokens
+    pub amount: String,
+    /// Whether this entry reverts the payment with the same record key and quote hashes
+    #[serde(default)]
+    pub reverted: bool,
+}
+
+/// The rewards received by the node during a calendar month (UTC).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MonthlyStatement {
+    /// The month, as `YYYY-MM`
+    pub month: String,
+    /// The total amount received during the month, in atto tokens
+    pub total: String,
+    /// The payments received during the month, oldest first
+    pub rewards: Vec<RewardEntry>,
+}
+
+impl MonthlyStatement {
+    /// Header of the CSV statements.
+    pub const CSV_HEADER: &'static str = "month,date,record_key,quote_hashes,amount_atto";
+
+    /// The statement as CSV rows, one per payment, without the header.
+    pub fn to_csv_rows(&self) -> String {
+        self.rewards
+            .iter()
+            .map(|reward| {
+                format!(
+                    "{},{},{},{},{}\n",
+                    self.month,
+                    format_date(reward.timestamp),
+                    reward.record_key,
+                    reward.quote_hashes.join(";"),
+                    reward.amount
+                )
+            })
+            .collect()
+    }
+}
+
+/// Append-only ledger of the rewards received by a running node.
+///
+/// The entries are written from a blocking task, not to hold the async runtime on disk IO.
+#[derive(Debug)]
+pub(crate) struct RewardsLedger {
+    path: PathBuf,
+    write_lock: Arc<Mutex<()>>,
+}
+
+impl RewardsLedger {
+    pub(crate) fn new(root_dir: &Path) -> Self {
+        Self {
+            path: root_dir.join(REWARDS_LEDGER_FILENAME),
+            write_lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// Record a payment accepted for a record. Failures are logged, they must not fail the PUT.
+    pub(crate) async fn record(
+        &self,
+        record_key: &str,
+        quote_hashes: &[QuoteHash],
+        amount: Amount,
+    ) {
+        self.append_entry(record_key, quote_hashes, amount, false)
+            .await;
+    }
+
+    /// Revert a payment recorded for a record, which is no longer valid. Failures are logged.
+    pub(crate) async fn revert(
+        &self,
+        record_key: &str,
+        quote_hashes: &[QuoteHash],
+        amount: Amount,
+    ) {
+        self.append_entry(record_key, quote_hashes, amount, true)
+            .await;
+    }
+
+    async fn append_entry(
+        &self,
+        record_key: &str,
+        quote_hashes: &[QuoteHash],
+        amount: Amount,
+        reverted: bool,
+    ) {
+        let entry = RewardEntry {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            record_key: record_key.to_string(),
+            quote_hashes: quote_hashes.iter().map(|hash| hash.to_string()).collect(),
+            amount: amount.to_string(),
+            reverted,
+        };
+        let path = self.path.clone();
+        let write_lock = Arc::clone(&self.write_lock);
+        let result = tokio::task::spawn_blocking(move || append(&path, &write_lock, &entry))
+            .await
+            .unwrap_or_else(|err| Err(io::Error::other(err)));
+        if let Err(err) = result {
+            error!(
+                "Failed to record the reward for {record_key} in {:?}: {err}",
+                self.path
+            );
+        }
+    }
+}
+
+fn append(path: &Path, write_lock: &Mutex<()>, entry: &RewardEntry) -> io::Result<()> {
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+    let _guard = write_lock
+        .lock()
+        .map_err(|_| io::Error::other("rewards ledger lock poisoned"))?;
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(line.as_bytes())
+}
+
+/// Read the rewards ledger of the node with the given root directory, leaving out the payments
+/// reverted afterwards.
+///
+/// A node that has not received any reward yet has an empty ledger.
+pub fn read_rewards_ledger(root_dir: &Path) -> io::Result<Vec<RewardEntry>> {
+    let file = match std::fs::File::open(root_dir.join(REWARDS_LEDGER_FILENAME)) {
+        Ok(file) => file,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(err) => return Err(err),
+    };
+    let mut entries = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry: RewardEntry = serde_json::from_str(&line)?;
+        if entry.reverted {
+            if let Some(index) = entries.iter().position(|recorded: &RewardEntry| {
+                recorded.record_key == entry.record_key
+                    && recorded.quote_hashes == entry.quote_hashes
+            }) {
+                let _ = entries.remove(index);
+            }
+            continue;
+        }
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+/// Group rewards into monthly statements, in chronological order.
+pub fn monthly_statements(rewards: Vec<RewardEntry>) -> Vec<MonthlyStatement> {
+    let mut months: BTreeMap<String, Vec<RewardEntry>> = BTreeMap::new();
+    for reward in rewards {
+        months
+            .entry(format_month(reward.timestamp))
+            .or_default()
+            .push(reward);
+    }
+
+    months
+        .into_iter()
+        .map(|(month, mut rewards)| {
+            rewards.sort_by_key(|reward| reward.timestamp);
+            let total = rewards
+                .iter()
+                .filter_map(|reward| Amount::from_str(&reward.amount).ok())
+                .fold(Amount::ZERO, |total, amount| total.saturating_add(amount));
+            MonthlyStatement {
+                month,
+                total: total.to_string(),
+                rewards,
+            }
+        })
+        .collect()
+}
+
+fn format_month(timestamp: u64) -> String {
+    format_timestamp(timestamp, "%Y-%m")
+}
+
+fn format_date(timestamp: u64) -> String {
+    format_timestamp(timestamp, "%Y-%m-%dT%H:%M:%SZ")
+}
+
+fn format_timestamp(timestamp: u64, format: &str) -> String {
+    Utc.timestamp_opt(timestamp as i64, 0)
+        .single()
+        .map(|datetime| datetime.format(format).to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_monthly_statements() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let ledger = RewardsLedger::new(dir.path());
+        assert!(
+            read_rewards_ledger(dir.path())
+                .expect("empty ledger")
+                .is_empty()
+        );
+
+        ledger
+            .record("key-a", &[QuoteHash::repeat_byte(1)], Amount::from(10u64))
+            .await;
+        let rewards = read_rewards_ledger(dir.path()).expect("read ledger");
+        assert_eq!(rewards.len(), 1);
+        assert_eq!(rewards[0].record_key, "key-a");
+        assert_eq!(rewards[0].amount, "10");
+
+        ledger
+            .record("key-b", &[QuoteHash::repeat_byte(2)], Amount::from(4u64))
+            .await;
+        ledger
+            .revert("key-a", &[QuoteHash::repeat_byte(1)], Amount::from(10u64))
+            .await;
+        let rewards = read_rewards_ledger(dir.path()).expect("read ledger");
+        assert_eq!(rewards.len(), 1);
+        assert_eq!(rewards[0].record_key, "key-b");
+
+        // 2024-01-31T23:00:00Z, 2024-02-01T01:00:00Z and 2024-01-15T00:00:00Z
+        let rewards = vec![
+            RewardEntry {
+                timestamp: 1_706_742_000,
+                record_key: "key-b".to_string(),
+                quote_hashes: vec![],
+                amount: "5".to_string(),
+                reverted: false,
+            },
+            RewardEntry {
+                timestamp: 1_706_749_200,
+                record_key: "key-c".to_string(),
+                quote_hashes: vec!["0x01".to_string(), "0x02".to_string()],
+                amount: "7".to_string(),
+                reverted: false,
+            },
+            RewardEntry {
+                timestamp: 1_705_276_800,
+                record_key: "key-d".to_string(),
+                quote_hashes: vec![],
+                amount: "3".to_string(),
+                reverted: false,
+            },
+        ];
+        let statements = monthly_statements(rewards);
+        assert_eq!(statements.len(), 2);
+        assert_eq!(statements[0].month, "2024-01");
+        assert_eq!(statements[0].total, "8");
+        assert_eq!(statements[0].rewards[0].record_key, "key-d");
+        assert_eq!(statements[1].month, "2024-02");
+        assert_eq!(
+            statements[1].to_csv_rows(),
+            "2024-02,2024-02-01T01:00:00Z,key-c,0x01;0x02,7\n"
+        );
+    }
+}
@@ -8,3 +8,5 @@
 
 pub mod network_spawner;
 pub mod node_spawner;
+#[cfg(feature = "test-network")]
+pub mod test_network;
@@ -0,0 +1,170 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Ephemeral local networks for integration tests.
+//!
+//! A [`TestNetwork`] runs its own EVM testnet and a set of local nodes within the test process,
+//! so that tests do not depend on a network launched beforehand with `antctl local run`.
+
+use crate::RunningNode;
+use crate::spawn::network_spawner::{NetworkSpawner, RunningNetwork};
+use ant_bootstrap::BootstrapConfig;
+use ant_evm::{Amount, EvmNetwork, EvmWallet};
+use evmlib::testnet::Testnet;
+use eyre::{Result, eyre};
+use libp2p::Multiaddr;
+
+/// Number of funded wallets handed out by [`TestNetwork::spawn`].
+const DEFAULT_FUNDED_WALLETS: usize = 3;
+
+/// Payment tokens transferred to each funded wallet.
+const FUNDED_WALLET_TOKENS: u64 = 1_000_000_000_000_000_000;
+
+/// Gas tokens transferred to each funded wallet.
+const FUNDED_WALLET_GAS: u64 = 10_000_000_000_000_000_000;
+
+/// A local network with its own EVM testnet, torn down when dropped.
+///
+/// The Anvil node of the testnet listens on the port set by `ANVIL_PORT` (or its default), so
+/// tests running several networks concurrently have to be serialised.
+pub struct TestNetwork {
+    network: Option<RunningNetwork>,
+    bootstrap_peers: Vec<Multiaddr>,
+    evm_network: EvmNetwork,
+    wallets: Vec<EvmWallet>,
+    // Declared last, so the EVM testnet is only stopped once the nodes have been shut down.
+    _testnet: Testnet,
+}
+
+impl TestNetwork {
+    /// Launch an EVM testnet and `n_nodes` local nodes connected to it, along with a few funded
+    /// wallets.
+    pub async fn spawn(n_nodes: usize) -> Result<Self> {
+        Self::spawn_with_wallets(n_nodes, DEFAULT_FUNDED_WALLETS).await
+    }
+
+    /// Launch an EVM testnet and `n_nodes` local nodes connected to it, along with `n_wallets`
+    /// wallets funded with payment and gas tokens.
+    pub async fn spawn_with_wallets(n_nodes: usize, n_wallets: usize) -> Result<Self> {
+        if n_nodes == 0 {
+            return Err(eyre!("A test network requires at least one node"));
+        }
+
+        let testnet = Testnet::new().await;
+        let evm_network = testnet.to_network();
+        let wallets = fund_wallets(&testnet, &evm_network, n_wallets).await?;
+
+        let bootstrap_config = BootstrapConfig::new(true)
+            .with_disable_cache_reading(true)
+            .with_disable_env_peers(true);
+        let network = NetworkSpawner::new()
+            .with_evm_network(evm_network.clone())
+            .with_rewards_address(EvmWallet::new_with_random_wallet(evm_network.clone()).address())
+            .with_bootstrap_config(bootstrap_config)
+            .with_no_upnp(true)
+            .with_size(n_nodes)
+            .spawn()
+            .await?;
+
+        let mut bootstrap_peers = vec![];
+        for node in network.running_nodes() {
+            if let Ok(addrs) = node.get_listen_addrs_with_peer_id().await {
+                bootstrap_peers.extend(addrs);
+            }
+        }
+        info!(
+            "Spawned a test network of {n_nodes} nodes, with {} bootstrap addrs",
+            bootstrap_peers.len()
+        );
+
+        Ok(Self {
+            network: Some(network),
+            bootstrap_peers,
+            evm_network,
+            wallets,
+            _testnet: testnet,
+        })
+    }
+
+    /// The listen addrs of the nodes, to bootstrap clients from.
+    pub fn bootstrap_peers(&self) -> &[Multiaddr] {
+        &self.bootstrap_peers
+    }
+
+    /// The EVM testnet the nodes are paid on.
+    pub fn evm_network(&self) -> &EvmNetwork {
+        &self.evm_network
+    }
+
+    /// Wallets funded with payment and gas tokens.
+    pub fn funded_wallets(&self) -> &[EvmWallet] {
+        &self.wallets
+    }
+
+    /// The running nodes of the network.
+    pub fn running_nodes(&self) -> &[RunningNode] {
+        self.network
+            .as_ref()
+            .map(|network| network.running_nodes().as_slice())
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for TestNetwork {
+    fn drop(&mut self) {
+        if let Some(network) = self.network.take() {
+            debug!("Shutting down the test network");
+            network.shutdown();
+        }
+    }
+}
+
+/// Create `count` random wallets, funded from the default wallet of the testnet.
+async fn fund_wallets(
+    testnet: &Testnet,
+    evm_network: &EvmNetwork,
+    count: usize,
+) -> Result<Vec<EvmWallet>> {
+    let funding_wallet =
+        EvmWallet::new_from_private_key(evm_network.clone(), &testnet.default_wallet_private_key())
+            .map_err(|err| eyre!("Failed to load the testnet default wallet: {err}"))?;
+
+    let mut wallets = Vec::with_capacity(count);
+    for _ in 0..count {
+        let wallet = EvmWallet::new_with_random_wallet(evm_network.clone());
+        let _ = funding_wallet
+            .transfer_tokens(wallet.address(), Amount::from(FUNDED_WALLET_TOKENS))
+            .await
+            .map_err(|err| eyre!("Failed to fund wallet with tokens: {err}"))?;
+        let _ = funding_wallet
+            .transfer_gas_tokens(wallet.address(), Amount::from(FUNDED_WALLET_GAS))
+            .await
+            .map_err(|err| eyre!("Failed to fund wallet with gas: {err}"))?;
+        wallets.push(wallet);
+    }
+    Ok(wallets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_spawn_test_network() {
+        let network = TestNetwork::spawn_with_wallets(5, 2).await.unwrap();
+
+        assert_eq!(network.running_nodes().len(), 5);
+        assert!(!network.bootstrap_peers().is_empty());
+        for wallet in network.funded_wallets() {
+            assert_eq!(
+                wallet.balance_of_tokens().await.unwrap(),
+                Amount::from(FUNDED_WALLET_TOKENS)
+            );
+        }
+    }
+}
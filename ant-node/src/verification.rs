@@ -0,0 +1,232 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use crate::networking::Network;
+use crate::{error::Result, node::Node};
+use ant_protocol::{
+    CLOSE_GROUP_SIZE, NetworkAddress,
+    messages::{Query, QueryResponse, Request, Response},
+    storage::{Chunk, DataTypes, RecordHeader, RecordKind, ValidationType, try_deserialize_record},
+};
+use libp2p::kad::Record;
+use rand::seq::SliceRandom;
+use xor_name::XorName;
+
+/// The outcome of a storage proof sampling job over the records held by the node.
+#[derive(Debug, Clone, Default)]
+pub struct RecordsVerification {
+    /// The number of records sampled
+    pub sampled: usize,
+    /// The sampled records whose file is missing, unreadable or no longer matches their address
+    pub corrupted: Vec<NetworkAddress>,
+    /// The number of intact records cross-checked against the other holders of their close group
+    pub cross_checked: usize,
+    /// The cross-checked records held with a different content by at least one other holder
+    pub diverging: Vec<NetworkAddress>,
+    /// The cross-checked records that no other holder of the close group returned
+    pub unconfirmed: Vec<NetworkAddress>,
+}
+
+impl RecordsVerification {
+    /// The number of sampled records found intact on disk.
+    pub fn intact(&self) -> usize {
+        self.sampled.saturating_sub(self.corrupted.len())
+    }
+}
+
+impl Node {
+    /// Randomly sample `sample_size` of the held records, re-read and re-hash each of them from
+    /// disk, then cross-check `cross_check_count` of the intact ones against the other holders of
+    /// their close group.
+    pub(crate) async fn verify_records(
+        network: Network,
+        sample_size: usize,
+        cross_check_count: usize,
+    ) -> Result<RecordsVerification> {
+        let all_records = network.get_all_local_record_addresses().await?;
+        let sample: Vec<_> = all_records
+            .iter()
+            .collect::<Vec<_>>()
+            .choose_multiple(&mut rand::thread_rng(), sample_size)
+            .map(|(addr, val_type)| ((*addr).clone(), (*val_type).clone()))
+            .collect();
+        info!(
+            "Verifying a sample of {} out of {} held records",
+            sample.len(),
+            all_records.len()
+        );
+
+        let mut verification = RecordsVerification {
+            sampled: sample.len(),
+            ..Default::default()
+        };
+        let mut intact = vec![];
+        for (addr, val_type) in sample {
+            let key = addr.to_record_key();
+            match network.read_local_record_from_disk(&key).await? {
+                Some(record) if is_record_intact(&record, &val_type) => intact.push((addr, record)),
+                Some(_) => {
+                    warn!("Record {addr:?} no longer matches its address or content hash");
+                    verification.corrupted.push(addr);
+                }
+                None => {
+                    warn!("Record {addr:?} could not be read back from disk");
+                    verification.corrupted.push(addr);
+                }
+            }
+        }
+
+        for (addr, record) in intact.into_iter().take(cross_check_count) {
+            verification.cross_checked += 1;
+            let (agreeing, diverging) =
+                Self::cross_check_record(network.clone(), addr.clone(), &record).await;
+            if diverging > 0 {
+                warn!(
+                    "Record {addr:?} diverges from {diverging} other holder(s) of its close group"
+                );
+                verification.diverging.push(addr);
+            } else if agreeing == 0 {
+                warn!("Record {addr:?} could not be confirmed by any other holder");
+                verification.unconfirmed.push(addr);
+            }
+        }
+
+        info!(
+            "Verified {} records: {} corrupted, {} cross-checked, {} diverging, {} unconfirmed",
+            verification.sampled,
+            verification.corrupted.len(),
+            verification.cross_checked,
+            verification.diverging.len(),
+            verification.unconfirmed.len()
+        );
+        Ok(verification)
+    }
+
+    /// Fetch the record from the other peers of its close group and compare their copy with ours.
+    /// Returns the number of peers holding the same content, and the number holding a different one.
+    ///
+    /// Only the decoded content is compared, as the header of a same record may legitimately differ
+    /// between holders, e.g. when written with the legacy layout or carrying a proof of work.
+    async fn cross_check_record(
+        network: Network,
+        addr: NetworkAddress,
+        record: &Record,
+    ) -> (usize, usize) {
+        let self_id = network.peer_id();
+        let peers = match network.get_closest_peers(&addr).await {
+            Ok(mut peers) => {
+                peers.retain(|(peer_id, _addrs)| *peer_id != self_id);
+                peers.sort_by_key(|(peer_id, _addrs)| {
+                    addr.distance(&NetworkAddress::from(*peer_id))
+                });
+                peers.truncate(CLOSE_GROUP_SIZE);
+                peers
+            }
+            Err(err) => {
+                warn!("Failed to get the closest peers to cross-check {addr:?}: {err:?}");
+                return (0, 0);
+            }
+        };
+
+        let req = Request::from(Query::GetReplicatedRecord {
+            requester: NetworkAddress::from(self_id),
+            key: addr.clone(),
+        });
+        let content = record_content(&record.value);
+        let mut agreeing = 0;
+        let mut diverging = 0;
+        for (peer_id, response) in network.send_and_get_responses(&peers, &req, true).await {
+            match response {
+                Ok((Response::Query(QueryResponse::GetReplicatedRecord(Ok((_, value)))), _)) => {
+                    if content.is_some() && record_content(&value) == content {
+                        agreeing += 1;
+                    } else {
+                        debug!("Peer {peer_id:?} holds a different copy of {addr:?}");
+                        diverging += 1;
+                    }
+                }
+                Ok((resp, _)) => {
+                    debug!("Peer {peer_id:?} did not return {addr:?} for cross-check: {resp:?}");
+                }
+                Err(err) => {
+                    debug!("Failed to cross-check {addr:?} with peer {peer_id:?}: {err:?}");
+                }
+            }
+        }
+        (agreeing, diverging)
+    }
+}
+
+/// The data type and content of a record value, without its header.
+fn record_content(value: &[u8]) -> Option<(DataTypes, &[u8])> {
+    let (header, _format, header_size) = RecordHeader::parse(value).ok()?;
+    let data_type = match header.kind {
+        RecordKind::DataOnly(data_type)
+        | RecordKind::DataWithPayment(data_type)
+        | RecordKind::DataWithMerklePayment(data_type) => data_type,
+    };
+    Some((data_type, value.get(header_size..)?))
+}
+
+/// Whether the record read back from disk still matches the address and content hash it was
+/// indexed with.
+fn is_record_intact(record: &Record, val_type: &ValidationType) -> bool {
+    match val_type {
+        ValidationType::Chunk => match try_deserialize_record::<Chunk>(record) {
+            Ok(chunk) => chunk.network_address().to_record_key() == record.key,
+            Err(_) => false,
+        },
+        ValidationType::NonChunk(content_hash) => {
+            XorName::from_content(&record.value) == *content_hash
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ant_protocol::storage::{RecordHeaderFormat, try_serialize_record};
+    use bytes::Bytes;
+
+    fn chunk_value(content: &'static [u8], format: RecordHeaderFormat) -> Vec<u8> {
+        let chunk = Chunk::new(Bytes::from_static(content));
+        let header = RecordHeader::new(RecordKind::DataOnly(DataTypes::Chunk));
+        let mut value = header
+            .try_serialize_as(format)
+            .expect("Failed to serialize header")
+            .to_vec();
+        value.extend(rmp_serde::to_vec(&chunk).expect("Failed to serialize chunk"));
+        value
+    }
+
+    #[test]
+    fn record_content_ignores_the_header_layout() {
+        let legacy = chunk_value(b"Chunk to cross-check", RecordHeaderFormat::Legacy);
+        let versioned = chunk_value(b"Chunk to cross-check", RecordHeaderFormat::Versioned);
+        assert_ne!(legacy, versioned);
+        assert!(record_content(&legacy).is_some());
+        assert_eq!(record_content(&legacy), record_content(&versioned));
+
+        let other = chunk_value(b"Another chunk", RecordHeaderFormat::Legacy);
+        assert_ne!(record_content(&legacy), record_content(&other));
+    }
+
+    #[test]
+    fn record_content_rejects_values_without_header() {
+        assert_eq!(record_content(&[]), None);
+        let value = try_serialize_record(
+            &Chunk::new(Bytes::from_static(b"Chunk")),
+            RecordKind::DataOnly(DataTypes::Chunk),
+        )
+        .expect("Failed to serialize record");
+        assert_eq!(
+            record_content(&value).map(|(data_type, _)| data_type),
+            Some(DataTypes::Chunk)
+        );
+    }
+}
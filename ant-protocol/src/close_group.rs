@@ -0,0 +1,133 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Close group calculations, as used by the nodes to decide which peers are responsible for an
+//! address and which records they shall replicate.
+//!
+//! These are the functions the node runs, exposed so that clients, simulations and external
+//! tooling reproduce the exact same logic.
+
+use crate::{NetworkAddress, constants::CLOSE_GROUP_SIZE};
+use libp2p::{
+    PeerId,
+    kad::{KBucketDistance as Distance, KBucketKey, U256},
+};
+
+/// Majority of the close group (i.e. > 1/2).
+pub const fn close_group_majority() -> usize {
+    CLOSE_GROUP_SIZE / 2 + 1
+}
+
+/// Sort the provided peers by their distance to the given `KBucketKey`, closest first.
+/// Returns at most `expected_entries` peers.
+pub fn sort_peers_by_key<K, T>(
+    peers: Vec<(PeerId, T)>,
+    key: &KBucketKey<K>,
+    expected_entries: usize,
+) -> Vec<(PeerId, T)> {
+    // Compute each distance once, rather than on every comparison.
+    let mut peer_distances: Vec<(Distance, PeerId, T)> = peers
+        .into_iter()
+        .map(|(peer_id, value)| {
+            let distance = key.distance(&NetworkAddress::from(peer_id).as_kbucket_key());
+            (distance, peer_id, value)
+        })
+        .collect();
+
+    peer_distances.sort_by(|a, b| a.0.cmp(&b.0));
+
+    peer_distances
+        .into_iter()
+        .take(expected_entries)
+        .map(|(_, peer_id, value)| (peer_id, value))
+        .collect()
+}
+
+/// Sort the provided peers by their distance to the given address, closest first.
+/// Returns at most `expected_entries` peers.
+pub fn sort_peers_by_address<T>(
+    peers: Vec<(PeerId, T)>,
+    address: &NetworkAddress,
+    expected_entries: usize,
+) -> Vec<(PeerId, T)> {
+    sort_peers_by_key(peers, &address.as_kbucket_key(), expected_entries)
+}
+
+/// Returns the peers within `range` of the given address, keeping their order.
+pub fn peers_in_range<T: Clone>(
+    peers: &[(PeerId, T)],
+    address: &NetworkAddress,
+    range: Distance,
+) -> Vec<(PeerId, T)> {
+    peers
+        .iter()
+        .filter(|(peer_id, _)| address.distance(&NetworkAddress::from(*peer_id)) <= range)
+        .cloned()
+        .collect()
+}
+
+/// The distance range a node is responsible for, i.e. the range of records it stores and replicates.
+///
+/// The network density (average distance among nodes) is estimated as
+/// `U256::MAX / estimated_network_size`, so a close group spans `CLOSE_GROUP_SIZE` times that.
+/// As the estimate can be too narrow, the distance to the node's close peers is used when wider.
+pub fn responsible_distance_range(
+    estimated_network_size: usize,
+    close_peers_distance: Distance,
+) -> Distance {
+    let density_distance = match estimated_network_size {
+        0 => U256::MAX,
+        size => (U256::MAX / U256::from(size)).saturating_mul(U256::from(CLOSE_GROUP_SIZE)),
+    };
+    std::cmp::max(Distance(density_distance), close_peers_distance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sort_peers_by_address() {
+        let target = NetworkAddress::from(PeerId::random());
+        let peers: Vec<(PeerId, usize)> = (0..20).map(|i| (PeerId::random(), i)).collect();
+
+        let sorted = sort_peers_by_address(peers.clone(), &target, CLOSE_GROUP_SIZE);
+        assert_eq!(sorted.len(), CLOSE_GROUP_SIZE);
+
+        let distances: Vec<_> = sorted
+            .iter()
+            .map(|(peer_id, _)| target.distance(&NetworkAddress::from(*peer_id)))
+            .collect();
+        assert!(distances.windows(2).all(|pair| pair[0] <= pair[1]));
+
+        // Nobody left out is closer than the farthest of the sorted peers.
+        let farthest = distances[CLOSE_GROUP_SIZE - 1];
+        let in_range = peers_in_range(&peers, &target, farthest);
+        assert_eq!(in_range.len(), CLOSE_GROUP_SIZE);
+    }
+
+    #[test]
+    fn test_responsible_distance_range() {
+        let narrow = Distance(U256::from(1u64));
+        let range = responsible_distance_range(1000, narrow);
+        assert_eq!(
+            range,
+            Distance(U256::MAX / U256::from(1000u64) * U256::from(CLOSE_GROUP_SIZE))
+        );
+
+        let wide = Distance(U256::MAX / U256::from(2u64));
+        assert_eq!(responsible_distance_range(1000, wide), wide);
+        assert_eq!(responsible_distance_range(0, narrow), Distance(U256::MAX));
+    }
+
+    #[test]
+    fn test_close_group_majority() {
+        assert!(close_group_majority() * 2 > CLOSE_GROUP_SIZE);
+        assert!((close_group_majority() - 1) * 2 <= CLOSE_GROUP_SIZE);
+    }
+}
@@ -100,6 +100,24 @@ pub enum Error {
         paid_peers: Vec<libp2p::PeerId>,
     },
 
+    // ---------- Audit errors
+    #[error("Failed to sign the storage statement")]
+    StorageStatementSigningFailed,
+
+    // ---------- Free updates errors
+    #[error(
+        "Too many free updates of the record {key:?}, further updates must be paid for or carry a proof of work of difficulty {work_difficulty}"
+    )]
+    FreeUpdateRateLimited {
+        key: PrettyPrintRecordKey<'static>,
+        /// The difficulty of the proof of work accepted instead of a payment, 0 if none is
+        work_difficulty: u8,
+    },
+
+    // ---------- Storage errors
+    #[error("The node is running out of disk space and does not accept new data")]
+    StorageFull,
+
     // Dev Note: add new variants above this one for backward compatibility with older protocol versions
     // ---------- Unknown/fallback variant for retro compatibility
     /// Unknown error variant (for backward compatibility with newer protocol versions)
@@ -17,6 +17,8 @@
 #[macro_use]
 extern crate tracing;
 
+/// Close group calculations
+pub mod close_group;
 /// Constants
 pub mod constants;
 /// Errors.
@@ -43,7 +45,9 @@ pub mod antnode_proto {
 pub use error::Error;
 pub use error::Error as NetworkError;
 
-use self::storage::{ChunkAddress, GraphEntryAddress, PointerAddress, ScratchpadAddress};
+use self::storage::{
+    ChunkAddress, DataTypes, GraphEntryAddress, PointerAddress, ScratchpadAddress,
+};
 
 /// Re-export of Bytes used throughout the protocol
 pub use bytes::Bytes;
@@ -127,6 +131,53 @@ impl NetworkAddress {
         }
     }
 
+    /// Return the type of data stored at this `NetworkAddress`, if it is a data address.
+    pub fn data_type(&self) -> Option<DataTypes> {
+        match self {
+            NetworkAddress::ChunkAddress(_) => Some(DataTypes::Chunk),
+            NetworkAddress::GraphEntryAddress(_) => Some(DataTypes::GraphEntry),
+            NetworkAddress::ScratchpadAddress(_) => Some(DataTypes::Scratchpad),
+            NetworkAddress::PointerAddress(_) => Some(DataTypes::Pointer),
+            NetworkAddress::PeerId(_) | NetworkAddress::RecordKey(_) => None,
+        }
+    }
+
+    /// Try to return the represented `ChunkAddress`.
+    pub fn as_chunk_address(&self) -> Option<ChunkAddress> {
+        if let NetworkAddress::ChunkAddress(addr) = self {
+            Some(*addr)
+        } else {
+            None
+        }
+    }
+
+    /// Try to return the represented `GraphEntryAddress`.
+    pub fn as_graph_entry_address(&self) -> Option<GraphEntryAddress> {
+        if let NetworkAddress::GraphEntryAddress(addr) = self {
+            Some(*addr)
+        } else {
+            None
+        }
+    }
+
+    /// Try to return the represented `ScratchpadAddress`.
+    pub fn as_scratchpad_address(&self) -> Option<ScratchpadAddress> {
+        if let NetworkAddress::ScratchpadAddress(addr) = self {
+            Some(*addr)
+        } else {
+            None
+        }
+    }
+
+    /// Try to return the represented `PointerAddress`.
+    pub fn as_pointer_address(&self) -> Option<PointerAddress> {
+        if let NetworkAddress::PointerAddress(addr) = self {
+            Some(*addr)
+        } else {
+            None
+        }
+    }
+
     /// Try to return the represented `PeerId`.
     pub fn as_peer_id(&self) -> Option<PeerId> {
         if let NetworkAddress::PeerId(bytes) = self
@@ -199,6 +250,36 @@ impl From<PointerAddress> for NetworkAddress {
     }
 }
 
+impl From<&ChunkAddress> for NetworkAddress {
+    fn from(chunk_address: &ChunkAddress) -> Self {
+        NetworkAddress::ChunkAddress(*chunk_address)
+    }
+}
+
+impl From<&GraphEntryAddress> for NetworkAddress {
+    fn from(graph_entry_address: &GraphEntryAddress) -> Self {
+        NetworkAddress::GraphEntryAddress(*graph_entry_address)
+    }
+}
+
+impl From<&ScratchpadAddress> for NetworkAddress {
+    fn from(scratchpad_address: &ScratchpadAddress) -> Self {
+        NetworkAddress::ScratchpadAddress(*scratchpad_address)
+    }
+}
+
+impl From<&PointerAddress> for NetworkAddress {
+    fn from(pointer_address: &PointerAddress) -> Self {
+        NetworkAddress::PointerAddress(*pointer_address)
+    }
+}
+
+impl From<&NetworkAddress> for RecordKey {
+    fn from(address: &NetworkAddress) -> Self {
+        address.to_record_key()
+    }
+}
+
 impl From<PeerId> for NetworkAddress {
     fn from(peer_id: PeerId) -> Self {
         NetworkAddress::PeerId(Bytes::from(peer_id.to_bytes()))
@@ -406,8 +487,9 @@ mod tests {
     use crate::{
         NetworkAddress, PeerId,
         messages::{Nonce, Query},
-        storage::GraphEntryAddress,
+        storage::{ChunkAddress, DataTypes, GraphEntryAddress, PointerAddress, ScratchpadAddress},
     };
+    use libp2p::kad::RecordKey;
     use serde::{Deserialize, Serialize};
 
     #[test]
@@ -422,6 +504,49 @@ mod tests {
         assert!(net_addr_fmt.contains(graph_entry_addr_hex));
     }
 
+    #[test]
+    fn verify_data_addresses_are_uniform() {
+        let pk = bls::SecretKey::random().public_key();
+        let chunk_addr = ChunkAddress::new(xor_name::XorName::random(&mut rand::thread_rng()));
+        let graph_entry_addr = GraphEntryAddress::new(pk);
+        let scratchpad_addr = ScratchpadAddress::new(pk);
+        let pointer_addr = PointerAddress::new(pk);
+
+        let cases = [
+            (
+                NetworkAddress::from(&chunk_addr),
+                DataTypes::Chunk,
+                *chunk_addr.xorname(),
+            ),
+            (
+                NetworkAddress::from(&graph_entry_addr),
+                DataTypes::GraphEntry,
+                graph_entry_addr.xorname(),
+            ),
+            (
+                NetworkAddress::from(&scratchpad_addr),
+                DataTypes::Scratchpad,
+                scratchpad_addr.xorname(),
+            ),
+            (
+                NetworkAddress::from(&pointer_addr),
+                DataTypes::Pointer,
+                pointer_addr.xorname(),
+            ),
+        ];
+
+        for (net_addr, data_type, xorname) in cases {
+            assert_eq!(net_addr.data_type(), Some(data_type));
+            assert_eq!(net_addr.xorname(), Some(xorname));
+            assert_eq!(RecordKey::from(&net_addr), RecordKey::new(&xorname));
+        }
+
+        let net_addr = NetworkAddress::from(&pointer_addr);
+        assert_eq!(net_addr.as_pointer_address(), Some(pointer_addr));
+        assert_eq!(net_addr.as_scratchpad_address(), None);
+        assert_eq!(NetworkAddress::from(PeerId::random()).data_type(), None);
+    }
+
     #[derive(Eq, PartialEq, PartialOrd, Clone, Serialize, Deserialize, Debug)]
     enum QueryExtended {
         GetStoreQuote {
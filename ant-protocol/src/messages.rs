@@ -13,6 +13,7 @@ mod connection_info;
 mod node_id;
 mod query;
 mod response;
+mod storage_statement;
 
 pub use self::{
     chunk_proof::{ChunkProof, Nonce},
@@ -20,7 +21,8 @@ pub use self::{
     connection_info::ConnectionInfo,
     node_id::NodeId,
     query::Query,
-    response::{CmdResponse, QueryResponse},
+    response::{CmdResponse, QueryResponse, RecordMetadata},
+    storage_statement::StorageStatement,
 };
 
 // Re-export merkle types from ant-evm
@@ -37,6 +39,15 @@ pub enum Request {
     Cmd(Cmd),
     /// A query sent to peers. Queries are read-only.
     Query(Query),
+    /// A query carrying the W3C `traceparent` of the span that sent it, so the receiver can
+    /// continue its trace. Peers older than this variant can't decode it, so it is only sent when
+    /// tracing is enabled.
+    TracedQuery {
+        /// The query itself
+        query: Query,
+        /// W3C trace context of the span that sent the query
+        trace_context: String,
+    },
 }
 
 /// A response to peers in the network.
@@ -54,9 +65,27 @@ impl Request {
     pub fn dst(&self) -> NetworkAddress {
         match self {
             Request::Cmd(cmd) => cmd.dst(),
-            Request::Query(query) => query.dst(),
+            Request::Query(query) | Request::TracedQuery { query, .. } => query.dst(),
         }
     }
+
+    /// The query of a query request, along with its trace context if it carries one.
+    pub fn into_query(self) -> Option<(Query, Option<String>)> {
+        match self {
+            Request::Cmd(_) => None,
+            Request::Query(query) => Some((query, None)),
+            Request::TracedQuery {
+                query,
+                trace_context,
+            } => Some((query, Some(trace_context))),
+        }
+    }
+}
+
+impl From<Query> for Request {
+    fn from(query: Query) -> Self {
+        Request::Query(query)
+    }
 }
 
 impl std::fmt::Display for Response {
@@ -70,3 +99,54 @@ impl std::fmt::Display for Request {
         write!(f, "{self:?}")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The shape of `Request` before the trace context was added.
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum LegacyRequest {
+        Cmd(Cmd),
+        Query(Query),
+    }
+
+    fn query() -> Query {
+        Query::GetVersion(NetworkAddress::from(libp2p::PeerId::random()))
+    }
+
+    fn to_cbor<T: Serialize>(value: &T) -> Vec<u8> {
+        let mut bytes = vec![];
+        ciborium::into_writer(value, &mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn untraced_query_is_encoded_as_before() {
+        let query = query();
+        let request = Request::from(query.clone());
+        let legacy = LegacyRequest::Query(query);
+        assert_eq!(to_cbor(&request), to_cbor(&legacy));
+
+        let decoded: LegacyRequest = ciborium::from_reader(to_cbor(&request).as_slice()).unwrap();
+        assert_eq!(decoded, legacy);
+        let decoded: Request = ciborium::from_reader(to_cbor(&legacy).as_slice()).unwrap();
+        assert_eq!(decoded, request);
+    }
+
+    #[test]
+    fn trace_context_is_carried() {
+        let request = Request::TracedQuery {
+            query: query(),
+            trace_context: "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01".to_string(),
+        };
+        let decoded: Request = ciborium::from_reader(to_cbor(&request).as_slice()).unwrap();
+        assert_eq!(decoded, request);
+        assert_eq!(
+            decoded.into_query().map(|(_, trace_context)| trace_context),
+            Some(Some(
+                "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01".to_string()
+            ))
+        );
+    }
+}
@@ -102,6 +102,37 @@ pub enum Query {
         /// Node verifies this is not expired/future, then signs its state with it
         merkle_payment_timestamp: u64,
     },
+    /// Retrieve the metadata of a record held by a specific peer, without its payload.
+    ///
+    /// This should eventually lead to a [`GetRecordHeader`] response.
+    ///
+    /// [`GetRecordHeader`]: super::QueryResponse::GetRecordHeader
+    GetRecordHeader(NetworkAddress),
+    /// Retrieve a byte range of a chunk held by a specific peer.
+    ///
+    /// The range applies to the chunk content, it is truncated to the end of the chunk.
+    /// This should eventually lead to a [`GetChunkRange`] response.
+    ///
+    /// [`GetChunkRange`]: super::QueryResponse::GetChunkRange
+    GetChunkRange {
+        /// Address of the chunk
+        addr: NetworkAddress,
+        /// Offset of the first byte to read
+        offset: usize,
+        /// Number of bytes to read
+        len: usize,
+    },
+    /// Ask a peer for a signed statement that it currently holds the record at the given address.
+    ///
+    /// This should eventually lead to a [`GetStorageStatement`] response.
+    ///
+    /// [`GetStorageStatement`]: super::QueryResponse::GetStorageStatement
+    GetStorageStatement {
+        /// Address of the audited record
+        key: NetworkAddress,
+        /// The random nonce to be included in the signed statement
+        nonce: Nonce,
+    },
     /// Developer/analytics query: Ask a node to query the network for closest peers.
     /// Unlike GetClosestPeers which returns local routing table, this triggers a full network lookup.
     /// Only available when the `developer` feature is enabled.
@@ -118,16 +149,20 @@ impl Query {
     /// Used to send a query to the close group of the address.
     pub fn dst(&self) -> NetworkAddress {
         match self {
-            Query::CheckNodeInProblem(address) | Query::GetVersion(address) => address.clone(),
+            Query::CheckNodeInProblem(address)
+            | Query::GetVersion(address)
+            | Query::GetRecordHeader(address) => address.clone(),
             // Shall not be called for this, as this is a `one-to-one` message,
             // and the destination shall be decided by the requester already.
             Query::GetStoreQuote { key, .. }
             | Query::GetReplicatedRecord { key, .. }
             | Query::GetChunkExistenceProof { key, .. }
             | Query::GetClosestPeers { key, .. }
-            | Query::GetMerkleCandidateQuote { key, .. } => key.clone(),
+            | Query::GetMerkleCandidateQuote { key, .. }
+            | Query::GetStorageStatement { key, .. } => key.clone(),
             #[cfg(feature = "developer")]
             Query::DevGetClosestPeersFromNetwork { key, .. } => key.clone(),
+            Query::GetChunkRange { addr, .. } => addr.clone(),
             Query::PutRecord { holder, .. } => holder.clone(),
         }
     }
@@ -202,6 +237,15 @@ impl std::fmt::Display for Query {
                     "Query::GetMerkleCandidateQuote({key:?} {data_type} {data_size} timestamp={merkle_payment_timestamp})"
                 )
             }
+            Query::GetRecordHeader(address) => {
+                write!(f, "Query::GetRecordHeader({address:?})")
+            }
+            Query::GetChunkRange { addr, offset, len } => {
+                write!(f, "Query::GetChunkRange({addr:?} {offset} {len})")
+            }
+            Query::GetStorageStatement { key, nonce } => {
+                write!(f, "Query::GetStorageStatement({key:?} {nonce})")
+            }
             #[cfg(feature = "developer")]
             Query::DevGetClosestPeersFromNetwork { key, num_of_peers } => {
                 write!(
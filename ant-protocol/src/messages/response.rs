@@ -8,7 +8,8 @@
 
 use crate::{NetworkAddress, error::Result};
 
-use super::ChunkProof;
+use super::{ChunkProof, StorageStatement};
+use crate::storage::{DataTypes, ValidationType};
 use ant_evm::PaymentQuote;
 use bytes::Bytes;
 use core::fmt;
@@ -98,6 +99,24 @@ pub enum QueryResponse {
     ///
     /// [`GetMerkleCandidateQuote`]: crate::messages::Query::GetMerkleCandidateQuote
     GetMerkleCandidateQuote(Result<ant_evm::merkle_payments::MerklePaymentCandidateNode>),
+    // ===== GetRecordHeader =====
+    //
+    /// Response to [`GetRecordHeader`]
+    ///
+    /// [`GetRecordHeader`]: crate::messages::Query::GetRecordHeader
+    GetRecordHeader(Result<(NetworkAddress, RecordMetadata)>),
+    // ===== GetChunkRange =====
+    //
+    /// Response to [`GetChunkRange`], with the requested bytes and the full size of the chunk
+    ///
+    /// [`GetChunkRange`]: crate::messages::Query::GetChunkRange
+    GetChunkRange(Result<(Bytes, usize)>),
+    // ===== GetStorageStatement =====
+    //
+    /// Response to [`GetStorageStatement`]
+    ///
+    /// [`GetStorageStatement`]: crate::messages::Query::GetStorageStatement
+    GetStorageStatement(Result<StorageStatement>),
     // ===== DevGetClosestPeersFromNetwork =====
     //
     /// Response to [`DevGetClosestPeersFromNetwork`]
@@ -191,6 +210,24 @@ impl Debug for QueryResponse {
                     write!(f, "GetMerkleCandidateQuote(Err({err:?}))")
                 }
             },
+            QueryResponse::GetRecordHeader(result) => {
+                write!(f, "GetRecordHeader({result:?})")
+            }
+            QueryResponse::GetChunkRange(result) => match result {
+                Ok((data, chunk_size)) => {
+                    write!(
+                        f,
+                        "GetChunkRange(Ok((datalen: {}, chunk_size: {chunk_size})))",
+                        data.len()
+                    )
+                }
+                Err(err) => {
+                    write!(f, "GetChunkRange(Err({err:?}))")
+                }
+            },
+            QueryResponse::GetStorageStatement(result) => {
+                write!(f, "GetStorageStatement({result:?})")
+            }
             #[cfg(feature = "developer")]
             QueryResponse::DevGetClosestPeersFromNetwork {
                 target,
@@ -208,6 +245,19 @@ impl Debug for QueryResponse {
     }
 }
 
+/// Metadata of a record held by a node, as replied to [`GetRecordHeader`].
+///
+/// [`GetRecordHeader`]: crate::messages::Query::GetRecordHeader
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordMetadata {
+    /// Type of the data held in the record
+    pub data_type: DataTypes,
+    /// Size of the stored record in bytes, header included
+    pub size: usize,
+    /// How the node validated the record: as a chunk, or as a mutable record with its content hash
+    pub validation_type: ValidationType,
+}
+
 /// The response to a Cmd, containing the query result.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CmdResponse {
@@ -0,0 +1,143 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use super::Nonce;
+use crate::NetworkAddress;
+use libp2p::{PeerId, identity::PublicKey};
+use serde::{Deserialize, Serialize};
+use xor_name::XorName;
+
+/// A node's signed statement that it holds a record at a given time.
+///
+/// The nonce is chosen by the auditor, so a statement can't be prepared in advance and replayed.
+/// The content hash covers the nonce as well, hence can only be computed with the record at hand.
+#[derive(Clone, Serialize, Deserialize, Eq, PartialEq, custom_debug::Debug)]
+pub struct StorageStatement {
+    /// Address of the held record
+    pub key: NetworkAddress,
+    /// Hash of the nonce and of the record bytes stored by the node, see [`Self::content_hash`]
+    pub content_hash: XorName,
+    /// Time of the statement, in seconds since the unix epoch
+    pub timestamp: u64,
+    /// The nonce provided by the auditor
+    pub nonce: Nonce,
+    /// Protobuf encoded public key of the node
+    #[debug(skip)]
+    pub pub_key: Vec<u8>,
+    /// Signature of the above by the node
+    #[debug(skip)]
+    pub signature: Vec<u8>,
+}
+
+impl StorageStatement {
+    /// Hash of the record bytes `content`, bound to the `nonce` of the auditor so that it can't be
+    /// computed in advance and kept instead of the record.
+    pub fn content_hash(content: &[u8], nonce: Nonce) -> XorName {
+        XorName::from_content_parts(&[&nonce.to_be_bytes(), content])
+    }
+
+    /// Whether the statement was made within `tolerance` seconds of `now`, in seconds since the
+    /// unix epoch, rejecting both the stale statements and the ones from the future.
+    pub fn is_timely(&self, now: u64, tolerance: u64) -> bool {
+        self.timestamp.abs_diff(now) <= tolerance
+    }
+
+    /// Bytes to be signed by the node, covering the content hash
+    pub fn bytes_to_sign(
+        key: &NetworkAddress,
+        content_hash: &XorName,
+        timestamp: u64,
+        nonce: Nonce,
+    ) -> Vec<u8> {
+        let mut bytes = key.as_bytes();
+        bytes.extend_from_slice(&content_hash.0);
+        bytes.extend_from_slice(&timestamp.to_be_bytes());
+        bytes.extend_from_slice(&nonce.to_be_bytes());
+        bytes
+    }
+
+    /// Check the statement is signed by the claimed peer
+    pub fn verify(&self, claimed_peer: &PeerId) -> bool {
+        let Ok(pub_key) = PublicKey::try_decode_protobuf(&self.pub_key) else {
+            warn!("Can't parse PublicKey of the storage statement from protobuf");
+            return false;
+        };
+
+        if PeerId::from(pub_key.clone()) != *claimed_peer {
+            warn!(
+                "Storage statement of {:?} is not from {claimed_peer:?}",
+                self.key
+            );
+            return false;
+        }
+
+        let bytes = Self::bytes_to_sign(&self.key, &self.content_hash, self.timestamp, self.nonce);
+        pub_key.verify(&bytes, &self.signature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libp2p::identity::Keypair;
+
+    fn signed_statement(keypair: &Keypair) -> StorageStatement {
+        let key = NetworkAddress::from(XorName::random(&mut rand::thread_rng()));
+        let content_hash = StorageStatement::content_hash(b"record bytes", 7);
+        let bytes = StorageStatement::bytes_to_sign(&key, &content_hash, 1_700_000_000, 7);
+        StorageStatement {
+            key,
+            content_hash,
+            timestamp: 1_700_000_000,
+            nonce: 7,
+            pub_key: keypair.public().encode_protobuf(),
+            signature: keypair.sign(&bytes).expect("signing shall succeed"),
+        }
+    }
+
+    #[test]
+    fn test_storage_statement_verification() {
+        let keypair = Keypair::generate_ed25519();
+        let peer_id = PeerId::from(keypair.public());
+        let statement = signed_statement(&keypair);
+
+        assert!(statement.verify(&peer_id));
+        assert!(!statement.verify(&PeerId::random()));
+
+        let mut tampered = statement.clone();
+        tampered.nonce += 1;
+        assert!(!tampered.verify(&peer_id));
+
+        let mut tampered = statement.clone();
+        tampered.content_hash = StorageStatement::content_hash(b"other bytes", 7);
+        assert!(!tampered.verify(&peer_id));
+    }
+
+    #[test]
+    fn test_storage_statement_content_hash_is_bound_to_the_nonce() {
+        assert_eq!(
+            StorageStatement::content_hash(b"record bytes", 7),
+            StorageStatement::content_hash(b"record bytes", 7)
+        );
+        assert_ne!(
+            StorageStatement::content_hash(b"record bytes", 7),
+            StorageStatement::content_hash(b"record bytes", 8)
+        );
+    }
+
+    #[test]
+    fn test_storage_statement_timeliness() {
+        let statement = signed_statement(&Keypair::generate_ed25519());
+
+        assert!(statement.is_timely(1_700_000_000, 60));
+        assert!(statement.is_timely(1_700_000_060, 60));
+        assert!(statement.is_timely(1_699_999_940, 60));
+        assert!(!statement.is_timely(1_700_000_061, 60));
+        assert!(!statement.is_timely(1_699_999_939, 60));
+    }
+}
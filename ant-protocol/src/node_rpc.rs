@@ -33,5 +33,8 @@ pub enum StopResult {
     /// Node is stopping after a successful upgrade. The service manager should
     /// restart the process so it picks up the new binary.
     UpgradeSuccess(String),
+    /// Node is stopping after receiving a termination signal (SIGINT or SIGTERM), holding the
+    /// number of the signal.
+    Signal(i32),
     Error(Error),
 }
@@ -14,6 +14,7 @@ use prometheus_client::encoding::EncodeLabelValue;
 use rmp_serde::Serializer;
 use serde::{Deserialize, Serialize};
 use std::fmt::Display;
+use std::time::Duration;
 use xor_name::XorName;
 
 /// Data types that natively suppported by autonomi network.
@@ -57,9 +58,91 @@ pub enum ValidationType {
     NonChunk(XorName),
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// The header prefixed to the value of every record.
+///
+/// Two layouts are understood:
+/// - the legacy one, the MessagePack encoding of the kind alone, [`RecordHeader::SIZE`] bytes long;
+/// - the versioned one, `magic | version | length | kind | flags [| expiry] [| work nonce]`, where
+///   `length` is the size of the whole header. Later versions may only append fields, so a reader can always
+///   get the kind and flags of a header of a newer version, and skip the fields it does not know.
+///
+/// The expiry of a temporary record, set along with [`RECORD_FLAG_EXPIRES`], and the nonce of the
+/// proof of work of an update, set along with [`RECORD_FLAG_UPDATE_WORK`], can only be carried by a
+/// versioned header: such a header is always written in that format.
+///
+/// Records are written in the [`RECORD_HEADER_WRITE_FORMAT`], see [`RecordHeader::migrate`] for
+/// the records stored before it changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct RecordHeader {
     pub kind: RecordKind,
+    /// See the `RECORD_FLAG_*` constants. Unknown flags are kept rather than rejected, so that
+    /// flags can be added without breaking the readers.
+    pub flags: u8,
+    /// The time after which a temporary record is dropped by the nodes, in seconds since the UNIX
+    /// epoch. `None` for the records stored for good.
+    pub expiry: Option<u64>,
+    /// The nonce of the proof of work done for a free update, see [`crate::storage::update_work`].
+    pub work_nonce: Option<u64>,
+}
+
+/// The legacy header layout, kept byte-for-byte for compatibility with the stored records and
+/// the peers that only know it.
+#[derive(Serialize, Deserialize)]
+struct LegacyRecordHeader {
+    kind: RecordKind,
+}
+
+/// The layout of a record header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordHeaderFormat {
+    Legacy,
+    Versioned,
+}
+
+/// The format records are written with.
+///
+/// Both formats are read, but the versioned one shall only be written once the nodes and clients
+/// reading it are widespread on the network.
+pub const RECORD_HEADER_WRITE_FORMAT: RecordHeaderFormat = RecordHeaderFormat::Legacy;
+
+/// The magic bytes opening a versioned header. A legacy header opens with a MessagePack array
+/// marker, so the two cannot be mistaken for each other.
+pub const RECORD_HEADER_MAGIC: [u8; 2] = *b"AR";
+
+/// The version of the versioned headers written by this release. Version 2 added the expiry,
+/// version 3 the work nonce.
+pub const RECORD_HEADER_VERSION: u8 = 3;
+
+/// The size of a version 1 header, and the minimum size of a versioned header of any version.
+pub const VERSIONED_RECORD_HEADER_SIZE: usize = 6;
+
+/// The size of a versioned header carrying an expiry.
+pub const EXPIRING_RECORD_HEADER_SIZE: usize = VERSIONED_RECORD_HEADER_SIZE + 8;
+
+/// The size of the optional `u64` fields of a versioned header.
+const OPTIONAL_FIELD_SIZE: usize = 8;
+
+/// The record is temporary: the header carries its expiry, as a big endian `u64` following the
+/// flags.
+pub const RECORD_FLAG_EXPIRES: u8 = 0b0000_0001;
+
+/// The record is a free update carrying the nonce of a proof of work, as a big endian `u64`
+/// following the expiry if any.
+pub const RECORD_FLAG_UPDATE_WORK: u8 = 0b0000_0010;
+
+/// The longest lease a temporary record is stored for at once, from the time it is paid for.
+/// A longer lease is renewed before it expires, paying again for each period.
+pub const RECORD_LEASE_PERIOD: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// Allowance for the clock skew between the clients and the nodes when checking an expiry.
+pub const RECORD_EXPIRY_CLOCK_SKEW: Duration = Duration::from_secs(10 * 60);
+
+/// The number of lease periods, hence of payments, needed to keep a temporary record for `lease`.
+pub fn lease_periods(lease: Duration) -> u64 {
+    lease
+        .as_secs()
+        .div_ceil(RECORD_LEASE_PERIOD.as_secs())
+        .max(1)
 }
 
 /// To be used between client and nodes, hence need to indicate whehter payment info involved.
@@ -78,12 +161,9 @@ pub const RECORD_KIND_PAYMENT_STARTING_INDEX: u32 = 10;
 /// Starting index for Merkle payment records
 pub const RECORD_KIND_MERKLE_PAYMENT_STARTING_INDEX: u32 = 20;
 
-impl Serialize for RecordKind {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        let index = match self {
+impl RecordKind {
+    pub fn get_index(&self) -> u32 {
+        match self {
             Self::DataOnly(data_types) => data_types.get_index(),
             Self::DataWithPayment(data_types) => {
                 RECORD_KIND_PAYMENT_STARTING_INDEX + data_types.get_index()
@@ -91,8 +171,28 @@ impl Serialize for RecordKind {
             Self::DataWithMerklePayment(data_types) => {
                 RECORD_KIND_MERKLE_PAYMENT_STARTING_INDEX + data_types.get_index()
             }
-        };
-        serializer.serialize_u32(index)
+        }
+    }
+
+    pub fn from_index(index: u32) -> Option<Self> {
+        if index < RECORD_KIND_PAYMENT_STARTING_INDEX {
+            DataTypes::from_index(index).map(Self::DataOnly)
+        } else if index < RECORD_KIND_MERKLE_PAYMENT_STARTING_INDEX {
+            DataTypes::from_index(index - RECORD_KIND_PAYMENT_STARTING_INDEX)
+                .map(Self::DataWithPayment)
+        } else {
+            DataTypes::from_index(index - RECORD_KIND_MERKLE_PAYMENT_STARTING_INDEX)
+                .map(Self::DataWithMerklePayment)
+        }
+    }
+}
+
+impl Serialize for RecordKind {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_u32(self.get_index())
     }
 }
 
@@ -102,27 +202,8 @@ impl<'de> Deserialize<'de> for RecordKind {
         D: serde::Deserializer<'de>,
     {
         let num = u32::deserialize(deserializer)?;
-
-        let (kind_type, data_type_index) = if num < RECORD_KIND_PAYMENT_STARTING_INDEX {
-            ("DataOnly", num)
-        } else if num < RECORD_KIND_MERKLE_PAYMENT_STARTING_INDEX {
-            ("DataWithPayment", num - RECORD_KIND_PAYMENT_STARTING_INDEX)
-        } else {
-            (
-                "DataWithMerklePayment",
-                num - RECORD_KIND_MERKLE_PAYMENT_STARTING_INDEX,
-            )
-        };
-
-        let data_type = DataTypes::from_index(data_type_index).ok_or_else(|| {
+        Self::from_index(num).ok_or_else(|| {
             serde::de::Error::custom(format!("Unexpected index {num} for RecordKind variant"))
-        })?;
-
-        Ok(match kind_type {
-            "DataOnly" => Self::DataOnly(data_type),
-            "DataWithPayment" => Self::DataWithPayment(data_type),
-            "DataWithMerklePayment" => Self::DataWithMerklePayment(data_type),
-            _ => unreachable!(),
         })
     }
 }
@@ -133,35 +214,183 @@ impl Display for RecordKind {
 }
 
 impl RecordHeader {
+    /// The size of a legacy header.
     pub const SIZE: usize = 2;
 
-    pub fn try_serialize(self) -> Result<BytesMut, Error> {
-        let bytes = BytesMut::new();
-        let mut buf = bytes.writer();
+    pub fn new(kind: RecordKind) -> Self {
+        Self {
+            kind,
+            flags: 0,
+            expiry: None,
+            work_nonce: None,
+        }
+    }
 
-        self.serialize(&mut Serializer::new(&mut buf))
-            .map_err(|err| {
-                error!("Failed to serialized RecordHeader {self:?} with error: {err:?}");
-                Error::RecordHeaderParsingFailed
-            })?;
+    /// The header of a temporary record, dropped by the nodes after `expiry`, in seconds since
+    /// the UNIX epoch.
+    pub fn with_expiry(kind: RecordKind, expiry: u64) -> Self {
+        Self {
+            kind,
+            flags: RECORD_FLAG_EXPIRES,
+            expiry: Some(expiry),
+            work_nonce: None,
+        }
+    }
+
+    /// The same header, carrying the nonce of the proof of work done for a free update.
+    pub fn with_work_nonce(self, work_nonce: u64) -> Self {
+        Self {
+            flags: self.flags | RECORD_FLAG_UPDATE_WORK,
+            work_nonce: Some(work_nonce),
+            ..self
+        }
+    }
+
+    /// Whether the record has expired at `now`, in seconds since the UNIX epoch.
+    pub fn is_expired_at(&self, now: u64) -> bool {
+        self.expiry.is_some_and(|expiry| expiry <= now)
+    }
 
-        let b = buf.into_inner();
+    /// Serialize the header in the [`RECORD_HEADER_WRITE_FORMAT`], or in the versioned format if
+    /// it carries an expiry or a work nonce.
+    pub fn try_serialize(self) -> Result<BytesMut, Error> {
+        if self.expiry.is_some() || self.work_nonce.is_some() {
+            self.try_serialize_as(RecordHeaderFormat::Versioned)
+        } else {
+            self.try_serialize_as(RECORD_HEADER_WRITE_FORMAT)
+        }
+    }
 
-        Ok(b)
+    pub fn try_serialize_as(self, format: RecordHeaderFormat) -> Result<BytesMut, Error> {
+        if (self.flags & RECORD_FLAG_EXPIRES != 0) != self.expiry.is_some()
+            || (self.flags & RECORD_FLAG_UPDATE_WORK != 0) != self.work_nonce.is_some()
+        {
+            error!("The optional fields of {self:?} do not match its flags");
+            return Err(Error::RecordHeaderParsingFailed);
+        }
+        match format {
+            RecordHeaderFormat::Legacy => {
+                if self.flags != 0 {
+                    error!("Cannot serialize the flags of {self:?} in the legacy format");
+                    return Err(Error::RecordHeaderParsingFailed);
+                }
+                let mut buf = BytesMut::new().writer();
+                LegacyRecordHeader { kind: self.kind }
+                    .serialize(&mut Serializer::new(&mut buf))
+                    .map_err(|err| {
+                        error!("Failed to serialized RecordHeader {self:?} with error: {err:?}");
+                        Error::RecordHeaderParsingFailed
+                    })?;
+                Ok(buf.into_inner())
+            }
+            RecordHeaderFormat::Versioned => {
+                let size = VERSIONED_RECORD_HEADER_SIZE
+                    + [self.expiry, self.work_nonce]
+                        .iter()
+                        .filter(|field| field.is_some())
+                        .count()
+                        * OPTIONAL_FIELD_SIZE;
+                let mut bytes = BytesMut::with_capacity(size);
+                bytes.put_slice(&RECORD_HEADER_MAGIC);
+                bytes.put_u8(RECORD_HEADER_VERSION);
+                bytes.put_u8(size as u8);
+                bytes.put_u8(self.kind.get_index() as u8);
+                bytes.put_u8(self.flags);
+                if let Some(expiry) = self.expiry {
+                    bytes.put_u64(expiry);
+                }
+                if let Some(work_nonce) = self.work_nonce {
+                    bytes.put_u64(work_nonce);
+                }
+                Ok(bytes)
+            }
+        }
     }
 
+    /// Deserialize a header in either format, ignoring any trailing bytes.
     pub fn try_deserialize(bytes: &[u8]) -> Result<Self, Error> {
-        rmp_serde::from_slice(bytes).map_err(|err| {
-            error!("Failed to deserialize RecordHeader with error: {err:?}");
+        Self::parse(bytes).map(|(header, _)| header)
+    }
+
+    /// Parse the header opening a record value, returning it along with its format and size,
+    /// i.e. the offset of the content in the value.
+    pub fn parse(value: &[u8]) -> Result<(Self, RecordHeaderFormat, usize), Error> {
+        if !value.starts_with(&RECORD_HEADER_MAGIC) {
+            let bytes = value.get(..Self::SIZE).ok_or_else(|| {
+                error!("Record value too short for a RecordHeader");
+                Error::RecordHeaderParsingFailed
+            })?;
+            let legacy: LegacyRecordHeader = rmp_serde::from_slice(bytes).map_err(|err| {
+                error!("Failed to deserialize RecordHeader with error: {err:?}");
+                Error::RecordHeaderParsingFailed
+            })?;
+            return Ok((
+                Self::new(legacy.kind),
+                RecordHeaderFormat::Legacy,
+                Self::SIZE,
+            ));
+        }
+
+        // Fields are only ever appended, so the ones of version 1 can be read from any version.
+        let (version, size) = match value {
+            [_, _, version, size, ..] => (*version, *size as usize),
+            _ => {
+                error!("Record value too short for a versioned RecordHeader");
+                return Err(Error::RecordHeaderParsingFailed);
+            }
+        };
+        if size < VERSIONED_RECORD_HEADER_SIZE || value.len() < size {
+            error!(
+                "Invalid size {size} of a versioned RecordHeader in a value of {} bytes",
+                value.len()
+            );
+            return Err(Error::RecordHeaderParsingFailed);
+        }
+        if version > RECORD_HEADER_VERSION {
+            debug!("Reading a RecordHeader of version {version}, newer than ours");
+        }
+        let kind = RecordKind::from_index(value[4] as u32).ok_or_else(|| {
+            error!("Unexpected index {} for RecordKind variant", value[4]);
             Error::RecordHeaderParsingFailed
-        })
+        })?;
+        let flags = value[5];
+        // The optional fields follow each other in the order of their flags
+        let mut offset = VERSIONED_RECORD_HEADER_SIZE;
+        let mut read_field = |flag: u8, name: &str| -> Result<Option<u64>, Error> {
+            if flags & flag == 0 {
+                return Ok(None);
+            }
+            let end = offset + OPTIONAL_FIELD_SIZE;
+            let bytes = value
+                .get(offset..end)
+                .filter(|_| size >= end)
+                .ok_or_else(|| {
+                    error!("Versioned RecordHeader of size {size} too short for its {name}");
+                    Error::RecordHeaderParsingFailed
+                })?;
+            offset = end;
+            let mut field = [0u8; OPTIONAL_FIELD_SIZE];
+            field.copy_from_slice(bytes);
+            Ok(Some(u64::from_be_bytes(field)))
+        };
+        let expiry = read_field(RECORD_FLAG_EXPIRES, "expiry")?;
+        let work_nonce = read_field(RECORD_FLAG_UPDATE_WORK, "work nonce")?;
+        let header = Self {
+            kind,
+            flags,
+            expiry,
+            work_nonce,
+        };
+        Ok((header, RecordHeaderFormat::Versioned, size))
     }
 
     pub fn from_record(record: &Record) -> Result<Self, Error> {
-        if record.value.len() < RecordHeader::SIZE + 1 {
+        let (header, _, size) = Self::parse(&record.value)?;
+        // A header is always followed by some content.
+        if record.value.len() <= size {
             return Err(Error::RecordHeaderParsingFailed);
         }
-        Self::try_deserialize(&record.value[..RecordHeader::SIZE + 1])
+        Ok(header)
     }
 
     pub fn is_record_of_type_chunk(record: &Record) -> Result<bool, Error> {
@@ -177,15 +406,34 @@ impl RecordHeader {
             | RecordKind::DataWithMerklePayment(data_type) => Ok(data_type),
         }
     }
+
+    /// Rewrite the header of a stored record value in the [`RECORD_HEADER_WRITE_FORMAT`], so that
+    /// the records stored before the format changed are served in the current one.
+    ///
+    /// Only legacy headers are rewritten: a versioned header may carry fields of a newer version,
+    /// which would be lost. Returns `None` if the value is left as is.
+    pub fn migrate(value: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        Self::migrate_to(value, RECORD_HEADER_WRITE_FORMAT)
+    }
+
+    /// See [`RecordHeader::migrate`].
+    pub fn migrate_to(value: &[u8], format: RecordHeaderFormat) -> Result<Option<Vec<u8>>, Error> {
+        let (header, current_format, size) = Self::parse(value)?;
+        if current_format == format || current_format == RecordHeaderFormat::Versioned {
+            return Ok(None);
+        }
+        let mut migrated = header.try_serialize_as(format)?;
+        migrated.extend_from_slice(&value[size..]);
+        Ok(Some(migrated.to_vec()))
+    }
 }
 
 /// Utility to deserialize a `KAD::Record` into any type.
 /// Use `RecordHeader::from_record` if you want the `RecordHeader` instead.
 pub fn try_deserialize_record<T: serde::de::DeserializeOwned>(record: &Record) -> Result<T, Error> {
-    let bytes = if record.value.len() > RecordHeader::SIZE {
-        &record.value[RecordHeader::SIZE..]
-    } else {
-        return Err(Error::RecordParsingFailed);
+    let bytes = match RecordHeader::parse(&record.value) {
+        Ok((_, _, size)) if record.value.len() > size => &record.value[size..],
+        _ => return Err(Error::RecordParsingFailed),
     };
     rmp_serde::from_slice(bytes).map_err(|err| {
         error!(
@@ -202,7 +450,16 @@ pub fn try_serialize_record<T: serde::Serialize>(
     data: &T,
     record_kind: RecordKind,
 ) -> Result<Bytes, Error> {
-    let mut buf = RecordHeader { kind: record_kind }.try_serialize()?.writer();
+    try_serialize_record_with_header(data, RecordHeader::new(record_kind))
+}
+
+/// Utility to serialize the provided data along with the given header, e.g. one carrying the
+/// expiry of a temporary record.
+pub fn try_serialize_record_with_header<T: serde::Serialize>(
+    data: &T,
+    header: RecordHeader,
+) -> Result<Bytes, Error> {
+    let mut buf = header.try_serialize()?.writer();
     data.serialize(&mut Serializer::new(&mut buf))
         .map_err(|err| {
             error!("Failed to serialized Records with error: {err:?}");
@@ -219,71 +476,53 @@ mod tests {
 
     #[test]
     fn verify_record_header_encoded_size() -> Result<()> {
-        let chunk_with_payment = RecordHeader {
-            kind: RecordKind::DataWithPayment(DataTypes::Chunk),
-        }
-        .try_serialize()?;
+        let chunk_with_payment =
+            RecordHeader::new(RecordKind::DataWithPayment(DataTypes::Chunk)).try_serialize()?;
         assert_eq!(chunk_with_payment.len(), RecordHeader::SIZE);
 
-        let chunk = RecordHeader {
-            kind: RecordKind::DataOnly(DataTypes::Chunk),
-        }
-        .try_serialize()?;
+        let chunk = RecordHeader::new(RecordKind::DataOnly(DataTypes::Chunk)).try_serialize()?;
         assert_eq!(chunk.len(), RecordHeader::SIZE);
 
-        let graphentry = RecordHeader {
-            kind: RecordKind::DataOnly(DataTypes::GraphEntry),
-        }
-        .try_serialize()?;
+        let graphentry =
+            RecordHeader::new(RecordKind::DataOnly(DataTypes::GraphEntry)).try_serialize()?;
         assert_eq!(graphentry.len(), RecordHeader::SIZE);
 
-        let scratchpad = RecordHeader {
-            kind: RecordKind::DataOnly(DataTypes::Scratchpad),
-        }
-        .try_serialize()?;
+        let scratchpad =
+            RecordHeader::new(RecordKind::DataOnly(DataTypes::Scratchpad)).try_serialize()?;
         assert_eq!(scratchpad.len(), RecordHeader::SIZE);
 
-        let scratchpad_with_payment = RecordHeader {
-            kind: RecordKind::DataWithPayment(DataTypes::Scratchpad),
-        }
-        .try_serialize()?;
+        let scratchpad_with_payment =
+            RecordHeader::new(RecordKind::DataWithPayment(DataTypes::Scratchpad))
+                .try_serialize()?;
         assert_eq!(scratchpad_with_payment.len(), RecordHeader::SIZE);
 
-        let pointer = RecordHeader {
-            kind: RecordKind::DataOnly(DataTypes::Pointer),
-        }
-        .try_serialize()?;
+        let pointer =
+            RecordHeader::new(RecordKind::DataOnly(DataTypes::Pointer)).try_serialize()?;
         assert_eq!(pointer.len(), RecordHeader::SIZE);
 
-        let pointer_with_payment = RecordHeader {
-            kind: RecordKind::DataWithPayment(DataTypes::Pointer),
-        }
-        .try_serialize()?;
+        let pointer_with_payment =
+            RecordHeader::new(RecordKind::DataWithPayment(DataTypes::Pointer)).try_serialize()?;
         assert_eq!(pointer_with_payment.len(), RecordHeader::SIZE);
 
         // Test Merkle payment variants
-        let chunk_with_merkle_payment = RecordHeader {
-            kind: RecordKind::DataWithMerklePayment(DataTypes::Chunk),
-        }
-        .try_serialize()?;
+        let chunk_with_merkle_payment =
+            RecordHeader::new(RecordKind::DataWithMerklePayment(DataTypes::Chunk))
+                .try_serialize()?;
         assert_eq!(chunk_with_merkle_payment.len(), RecordHeader::SIZE);
 
-        let graphentry_with_merkle_payment = RecordHeader {
-            kind: RecordKind::DataWithMerklePayment(DataTypes::GraphEntry),
-        }
-        .try_serialize()?;
+        let graphentry_with_merkle_payment =
+            RecordHeader::new(RecordKind::DataWithMerklePayment(DataTypes::GraphEntry))
+                .try_serialize()?;
         assert_eq!(graphentry_with_merkle_payment.len(), RecordHeader::SIZE);
 
-        let pointer_with_merkle_payment = RecordHeader {
-            kind: RecordKind::DataWithMerklePayment(DataTypes::Pointer),
-        }
-        .try_serialize()?;
+        let pointer_with_merkle_payment =
+            RecordHeader::new(RecordKind::DataWithMerklePayment(DataTypes::Pointer))
+                .try_serialize()?;
         assert_eq!(pointer_with_merkle_payment.len(), RecordHeader::SIZE);
 
-        let scratchpad_with_merkle_payment = RecordHeader {
-            kind: RecordKind::DataWithMerklePayment(DataTypes::Scratchpad),
-        }
-        .try_serialize()?;
+        let scratchpad_with_merkle_payment =
+            RecordHeader::new(RecordKind::DataWithMerklePayment(DataTypes::Scratchpad))
+                .try_serialize()?;
         assert_eq!(scratchpad_with_merkle_payment.len(), RecordHeader::SIZE);
 
         Ok(())
@@ -307,8 +546,8 @@ mod tests {
         ];
 
         for kind in kinds {
-            let header = RecordHeader { kind };
-            let header2 = RecordHeader { kind };
+            let header = RecordHeader::new(kind);
+            let header2 = RecordHeader::new(kind);
 
             let serialized = header.try_serialize()?;
             let deserialized = RecordHeader::try_deserialize(&serialized)?;
@@ -335,7 +574,7 @@ mod tests {
         ];
 
         for (kind, expected_index) in test_cases {
-            let header = RecordHeader { kind };
+            let header = RecordHeader::new(kind);
             let serialized = header.try_serialize()?;
 
             // Parse the messagepack format to extract the u32 value
@@ -371,10 +610,7 @@ mod tests {
         assert_eq!(
             RecordKind::DataOnly(DataTypes::Chunk),
             RecordHeader::try_deserialize(
-                &RecordHeader {
-                    kind: RecordKind::DataOnly(DataTypes::Chunk)
-                }
-                .try_serialize()?
+                &RecordHeader::new(RecordKind::DataOnly(DataTypes::Chunk)).try_serialize()?
             )?
             .kind
         );
@@ -383,10 +619,8 @@ mod tests {
         assert_eq!(
             RecordKind::DataWithPayment(DataTypes::Chunk),
             RecordHeader::try_deserialize(
-                &RecordHeader {
-                    kind: RecordKind::DataWithPayment(DataTypes::Chunk)
-                }
-                .try_serialize()?
+                &RecordHeader::new(RecordKind::DataWithPayment(DataTypes::Chunk))
+                    .try_serialize()?
             )?
             .kind
         );
@@ -395,10 +629,8 @@ mod tests {
         assert_eq!(
             RecordKind::DataWithMerklePayment(DataTypes::Chunk),
             RecordHeader::try_deserialize(
-                &RecordHeader {
-                    kind: RecordKind::DataWithMerklePayment(DataTypes::Chunk)
-                }
-                .try_serialize()?
+                &RecordHeader::new(RecordKind::DataWithMerklePayment(DataTypes::Chunk))
+                    .try_serialize()?
             )?
             .kind
         );
@@ -418,7 +650,7 @@ mod tests {
 
         for data_type in data_types {
             let original = RecordKind::DataWithMerklePayment(data_type);
-            let header = RecordHeader { kind: original };
+            let header = RecordHeader::new(original);
 
             let serialized = header.try_serialize()?;
             let deserialized = RecordHeader::try_deserialize(&serialized)?;
@@ -431,4 +663,148 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_versioned_record_header() -> Result<()> {
+        let header = RecordHeader::new(RecordKind::DataWithPayment(DataTypes::Pointer));
+        let serialized = header.try_serialize_as(RecordHeaderFormat::Versioned)?;
+        assert_eq!(serialized.len(), VERSIONED_RECORD_HEADER_SIZE);
+        assert!(serialized.starts_with(&RECORD_HEADER_MAGIC));
+        assert_eq!(
+            RecordHeader::parse(&serialized)?,
+            (
+                header,
+                RecordHeaderFormat::Versioned,
+                VERSIONED_RECORD_HEADER_SIZE
+            )
+        );
+
+        let legacy = header.try_serialize_as(RecordHeaderFormat::Legacy)?;
+        assert_eq!(
+            RecordHeader::parse(&legacy)?,
+            (header, RecordHeaderFormat::Legacy, RecordHeader::SIZE)
+        );
+
+        // A header of a newer version, with unknown flags and an extra field, is still read.
+        let mut newer = serialized.to_vec();
+        newer[2] = RECORD_HEADER_VERSION + 1;
+        newer[3] = VERSIONED_RECORD_HEADER_SIZE as u8 + 2;
+        newer[5] = 0b1000_0000;
+        newer.extend_from_slice(&[0xff, 0xff, 0x42]);
+        let (parsed, _, size) = RecordHeader::parse(&newer)?;
+        assert_eq!(parsed.kind, header.kind);
+        assert_eq!(parsed.flags, 0b1000_0000);
+        assert_eq!(size, VERSIONED_RECORD_HEADER_SIZE + 2);
+
+        // Truncated headers are rejected.
+        assert!(RecordHeader::parse(&serialized[..4]).is_err());
+        newer[3] = 16;
+        assert!(RecordHeader::parse(&newer).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_expiring_record_header() -> Result<()> {
+        let kind = RecordKind::DataWithPayment(DataTypes::Scratchpad);
+        let header = RecordHeader::with_expiry(kind, 1_700_000_000);
+
+        // An expiry is always written in the versioned format, which alone can carry it.
+        let serialized = header.try_serialize()?;
+        assert_eq!(serialized.len(), EXPIRING_RECORD_HEADER_SIZE);
+        assert_eq!(
+            RecordHeader::parse(&serialized)?,
+            (
+                header,
+                RecordHeaderFormat::Versioned,
+                EXPIRING_RECORD_HEADER_SIZE
+            )
+        );
+        assert!(header.try_serialize_as(RecordHeaderFormat::Legacy).is_err());
+        assert!(header.is_expired_at(1_700_000_000));
+        assert!(!header.is_expired_at(1_699_999_999));
+        assert!(!RecordHeader::new(kind).is_expired_at(u64::MAX));
+
+        // The content follows the expiry.
+        let content = 42u64;
+        let value = try_serialize_record_with_header(&content, header)?;
+        let record = Record::new(libp2p::kad::RecordKey::new(&[1u8; 32]), value.to_vec());
+        assert_eq!(RecordHeader::from_record(&record)?, header);
+        let deserialized: u64 = try_deserialize_record(&record)?;
+        assert_eq!(deserialized, content);
+
+        // A header flagged as expiring but too short for the expiry is rejected.
+        let mut truncated = RecordHeader::new(kind)
+            .try_serialize_as(RecordHeaderFormat::Versioned)?
+            .to_vec();
+        truncated[5] = RECORD_FLAG_EXPIRES;
+        truncated.extend_from_slice(&[0u8; 8]);
+        assert!(RecordHeader::parse(&truncated).is_err());
+
+        // The work nonce follows the expiry.
+        let header = RecordHeader::with_expiry(kind, 1_700_000_000).with_work_nonce(7);
+        let serialized = header.try_serialize()?;
+        assert_eq!(serialized.len(), EXPIRING_RECORD_HEADER_SIZE + 8);
+        assert_eq!(
+            &serialized[EXPIRING_RECORD_HEADER_SIZE..],
+            &7u64.to_be_bytes()
+        );
+        assert_eq!(RecordHeader::try_deserialize(&serialized)?, header);
+        let header = RecordHeader::new(kind).with_work_nonce(7);
+        let serialized = header.try_serialize()?;
+        assert_eq!(serialized.len(), VERSIONED_RECORD_HEADER_SIZE + 8);
+        assert_eq!(RecordHeader::try_deserialize(&serialized)?, header);
+        assert!(RecordHeader::parse(&serialized[..VERSIONED_RECORD_HEADER_SIZE + 4]).is_err());
+
+        assert_eq!(lease_periods(Duration::ZERO), 1);
+        assert_eq!(lease_periods(RECORD_LEASE_PERIOD), 1);
+        assert_eq!(
+            lease_periods(RECORD_LEASE_PERIOD + Duration::from_secs(1)),
+            2
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_record_header_migration() -> Result<()> {
+        let kind = RecordKind::DataOnly(DataTypes::Scratchpad);
+        let content = 42u64;
+        let value = try_serialize_record(&content, kind)?;
+        let record = Record::new(libp2p::kad::RecordKey::new(&[1u8; 32]), value.to_vec());
+        assert_eq!(RecordHeader::from_record(&record)?.kind, kind);
+        let deserialized: u64 = try_deserialize_record(&record)?;
+        assert_eq!(deserialized, content);
+
+        // The values stored in the write format are left as is.
+        assert_eq!(RecordHeader::migrate(&value)?, None);
+
+        // Legacy values are upgraded to the versioned format, keeping their content.
+        let migrated = RecordHeader::migrate_to(&value, RecordHeaderFormat::Versioned)?
+            .expect("legacy value shall be migrated");
+        assert_eq!(
+            RecordHeader::parse(&migrated)?,
+            (
+                RecordHeader::new(kind),
+                RecordHeaderFormat::Versioned,
+                VERSIONED_RECORD_HEADER_SIZE
+            )
+        );
+        assert_eq!(
+            &migrated[VERSIONED_RECORD_HEADER_SIZE..],
+            &value[RecordHeader::SIZE..]
+        );
+
+        // Values in the versioned format are read alike, and never downgraded.
+        let mut versioned = RecordHeader::new(kind)
+            .try_serialize_as(RecordHeaderFormat::Versioned)?
+            .to_vec();
+        versioned.extend_from_slice(&value[RecordHeader::SIZE..]);
+        assert_eq!(RecordHeader::migrate(&versioned)?, None);
+        let record = Record::new(record.key, versioned);
+        let deserialized: u64 = try_deserialize_record(&record)?;
+        assert_eq!(deserialized, content);
+
+        Ok(())
+    }
 }
@@ -12,6 +12,7 @@ mod graph;
 mod header;
 mod pointer;
 mod scratchpad;
+pub mod update_work;
 
 pub use self::{
     address::AddressParseError,
@@ -19,8 +20,9 @@ pub use self::{
     chunks::Chunk,
     graph::{GraphContent, GraphEntry},
     header::{
-        DataTypes, RecordHeader, RecordKind, ValidationType, try_deserialize_record,
-        try_serialize_record,
+        DataTypes, RECORD_EXPIRY_CLOCK_SKEW, RECORD_HEADER_WRITE_FORMAT, RECORD_LEASE_PERIOD,
+        RecordHeader, RecordHeaderFormat, RecordKind, ValidationType, lease_periods,
+        try_deserialize_record, try_serialize_record, try_serialize_record_with_header,
     },
     pointer::{Pointer, PointerTarget},
     scratchpad::Scratchpad,
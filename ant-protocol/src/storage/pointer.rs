@@ -96,7 +96,7 @@ impl Pointer {
     }
 
     /// Get the bytes that the signature is calculated from
-    fn bytes_to_sign(owner: &PublicKey, counter: u64, target: &PointerTarget) -> Vec<u8> {
+    pub fn bytes_to_sign(owner: &PublicKey, counter: u64, target: &PointerTarget) -> Vec<u8> {
         // to support retrocompatibility with old pointers (u32 counter), we need to cast the counter to u32
         // the support is limited to counters under u32::MAX
         let counter_bytes: Vec<u8> = if counter > u32::MAX as u64 {
@@ -0,0 +1,116 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Proof of work done for the free update of a pointer or scratchpad.
+//!
+//! Over its free update limits, a node may accept an update without payment if it carries the
+//! nonce of a proof of work, in its [`RecordHeader`]: the hash of the record
+//! key, of the hash of the record content, and of the nonce must start with as many zero bits as
+//! the difficulty asked for by the node.
+
+use super::{RecordHeader, try_serialize_record_with_header};
+use crate::error::Error;
+use bytes::Bytes;
+use libp2p::kad::RecordKey;
+use xor_name::XorName;
+
+/// The hardest proof of work a client does for an update, the harder ones are paid for instead.
+pub const MAX_UPDATE_WORK_DIFFICULTY: u8 = 28;
+
+/// Whether `nonce` proves `difficulty` bits of work for the update of `key` to `content`, the
+/// record value without its header.
+pub fn is_update_work_valid(key: &RecordKey, content: &[u8], nonce: u64, difficulty: u8) -> bool {
+    let content_hash = XorName::from_content(content);
+    work_bits(key, &content_hash, nonce) >= u32::from(difficulty)
+}
+
+/// Find a nonce proving `difficulty` bits of work for the update of `key` to `content`, the
+/// record value without its header.
+///
+/// This takes about `2^difficulty` hashes, hence shall be run on a blocking thread.
+pub fn compute_update_work(key: &RecordKey, content: &[u8], difficulty: u8) -> u64 {
+    let content_hash = XorName::from_content(content);
+    (0..=u64::MAX)
+        .find(|nonce| work_bits(key, &content_hash, *nonce) >= u32::from(difficulty))
+        .unwrap_or_default()
+}
+
+/// Serialize `data` as the value of the free update of `key`, its `header` carrying the nonce of a
+/// proof of work of `difficulty` bits.
+///
+/// This takes about `2^difficulty` hashes, hence shall be run on a blocking thread.
+pub fn try_serialize_record_with_work<T: serde::Serialize>(
+    data: &T,
+    header: RecordHeader,
+    key: &RecordKey,
+    difficulty: u8,
+) -> Result<Bytes, Error> {
+    // The content does not depend on the header it follows
+    let value = try_serialize_record_with_header(data, header)?;
+    let (_, _, header_size) = RecordHeader::parse(&value)?;
+    let nonce = compute_update_work(key, &value[header_size..], difficulty);
+    try_serialize_record_with_header(data, header.with_work_nonce(nonce))
+}
+
+/// The number of leading zero bits of the work hash.
+fn work_bits(key: &RecordKey, content_hash: &XorName, nonce: u64) -> u32 {
+    let hash = XorName::from_content_parts(&[key.as_ref(), &content_hash.0, &nonce.to_be_bytes()]);
+    let mut bits = 0;
+    for byte in hash.0 {
+        if byte != 0 {
+            return bits + byte.leading_zeros();
+        }
+        bits += 8;
+    }
+    bits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialized_update_should_carry_valid_work() -> crate::error::Result<()> {
+        use crate::storage::{DataTypes, RecordKind};
+
+        let key = RecordKey::new(&[1u8; 32]);
+        let header = RecordHeader::new(RecordKind::DataOnly(DataTypes::Pointer));
+        let value = try_serialize_record_with_work(&42u64, header, &key, 8)?;
+
+        let (parsed, _, header_size) = RecordHeader::parse(&value)?;
+        let nonce = parsed
+            .work_nonce
+            .expect("the header shall carry the work nonce");
+        assert!(is_update_work_valid(&key, &value[header_size..], nonce, 8));
+        Ok(())
+    }
+
+    #[test]
+    fn update_work_should_be_bound_to_the_update() {
+        let key = RecordKey::new(&[1u8; 32]);
+        let content = b"pointer update";
+        let difficulty = 16;
+
+        let nonce = compute_update_work(&key, content, difficulty);
+        assert!(is_update_work_valid(&key, content, nonce, difficulty));
+        assert!(is_update_work_valid(&key, content, nonce, 0));
+
+        assert!(!is_update_work_valid(
+            &key,
+            b"another update",
+            nonce,
+            difficulty
+        ));
+        assert!(!is_update_work_valid(
+            &RecordKey::new(&[2u8; 32]),
+            content,
+            nonce,
+            difficulty
+        ));
+    }
+}
@@ -35,11 +35,7 @@ pub static NETWORK_ID: LazyLock<RwLock<u8>> = LazyLock::new(|| RwLock::new(1));
 /// - Other nodes recognize this as a fellow routing participant
 /// - Peers with this identifier are added to routing tables (RT)
 pub fn construct_node_user_agent(node_version: String) -> String {
-    format!(
-        "ant/node/{ant_protocol_version}/{node_version}/{network_id}",
-        ant_protocol_version = get_truncate_version_str(),
-        network_id = get_network_id(),
-    )
+    NetworkProtocol::current().node_user_agent(node_version)
 }
 
 /// Client user-agent identifier for peer recognition and routing exclusion.
@@ -55,11 +51,7 @@ pub fn construct_node_user_agent(node_version: String) -> String {
 /// - Nodes search for "client" in this identifier and they are **excluded** from routing tables (RT)
 /// - Treated as network consumers rather than routing participants
 pub fn construct_client_user_agent(client_version: String) -> String {
-    format!(
-        "ant/client/{ant_protocol_version}/{client_version}/{network_id}",
-        ant_protocol_version = get_truncate_version_str(),
-        network_id = get_network_id(),
-    )
+    NetworkProtocol::current().client_user_agent(client_version)
 }
 
 /// The req/response protocol version
@@ -76,13 +68,8 @@ pub fn construct_client_user_agent(client_version: String) -> String {
 ///   - Different versions cannot communicate
 ///   - Different network IDs are isolated
 ///   - Connection remains open for other protocols
-pub static REQ_RESPONSE_VERSION_STR: LazyLock<RwLock<String>> = LazyLock::new(|| {
-    RwLock::new(format!(
-        "/ant/{ant_protocol_version}/{network_id}",
-        ant_protocol_version = get_truncate_version_str(),
-        network_id = get_network_id()
-    ))
-});
+pub static REQ_RESPONSE_VERSION_STR: LazyLock<RwLock<String>> =
+    LazyLock::new(|| RwLock::new(NetworkProtocol::current().req_response_version_str()));
 
 /// Identify protocol version string for peer compatibility verification.
 ///
@@ -99,13 +86,8 @@ pub static REQ_RESPONSE_VERSION_STR: LazyLock<RwLock<String>> = LazyLock::new(||
 ///   - Considered incompatible
 ///   - Added to the blocklist
 ///   - Protected against cross-network contamination
-pub static IDENTIFY_PROTOCOL_STR: LazyLock<RwLock<String>> = LazyLock::new(|| {
-    RwLock::new(format!(
-        "ant/{ant_protocol_version}/{network_id}",
-        ant_protocol_version = get_truncate_version_str(),
-        network_id = get_network_id(),
-    ))
-});
+pub static IDENTIFY_PROTOCOL_STR: LazyLock<RwLock<String>> =
+    LazyLock::new(|| RwLock::new(NetworkProtocol::current().identify_protocol_str()));
 
 /// Update the NETWORK_ID.
 ///
@@ -129,14 +111,14 @@ pub fn set_network_id(id: u8) {
         let mut req_response = REQ_RESPONSE_VERSION_STR
             .write()
             .expect("Failed to obtain write lock for REQ_RESPONSE_VERSION_STR");
-        *req_response = format!("/ant/{}/{}", get_truncate_version_str(), id);
+        *req_response = NetworkProtocol::new(id).req_response_version_str();
     }
 
     {
         let mut identify_protocol = IDENTIFY_PROTOCOL_STR
             .write()
             .expect("Failed to obtain write lock for IDENTIFY_PROTOCOL_STR");
-        *identify_protocol = format!("ant/{}/{}", get_truncate_version_str(), id);
+        *identify_protocol = NetworkProtocol::new(id).identify_protocol_str();
     }
 
     info!("Network id set to: {id} and all protocol strings updated");
@@ -159,6 +141,144 @@ pub fn get_network_id_str() -> String {
     )
 }
 
+/// The protocol strings of a single network.
+///
+/// The process-wide strings above all follow the [`NETWORK_ID`] set by [`set_network_id`], so a
+/// process can only talk to one network through them. A process talking to several networks at
+/// once, e.g. a gateway or a test harness, uses an instance per network instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NetworkProtocol {
+    network_id: u8,
+}
+
+impl NetworkProtocol {
+    pub fn new(network_id: u8) -> Self {
+        Self { network_id }
+    }
+
+    /// The protocol of the process-wide network id.
+    pub fn current() -> Self {
+        Self::new(get_network_id())
+    }
+
+    pub fn network_id(&self) -> u8 {
+        self.network_id
+    }
+
+    /// See [`REQ_RESPONSE_VERSION_STR`].
+    pub fn req_response_version_str(&self) -> String {
+        format!("/ant/{}/{}", get_truncate_version_str(), self.network_id)
+    }
+
+    /// See [`IDENTIFY_PROTOCOL_STR`].
+    pub fn identify_protocol_str(&self) -> String {
+        format!("ant/{}/{}", get_truncate_version_str(), self.network_id)
+    }
+
+    /// Negotiate with a peer from the identify protocol it announced: peers can only talk to each
+    /// other if they announce the same one.
+    pub fn negotiate(&self, their_identify_protocol: &str) -> Result<(), ProtocolMismatch> {
+        let ours = self.identify_protocol_str();
+        if ours == their_identify_protocol {
+            Ok(())
+        } else {
+            Err(ProtocolMismatch {
+                ours,
+                theirs: their_identify_protocol.to_string(),
+            })
+        }
+    }
+
+    /// See [`construct_node_user_agent`].
+    pub fn node_user_agent(&self, node_version: String) -> String {
+        format!(
+            "ant/node/{}/{node_version}/{}",
+            get_truncate_version_str(),
+            self.network_id
+        )
+    }
+
+    /// See [`construct_client_user_agent`].
+    pub fn client_user_agent(&self, client_version: String) -> String {
+        format!(
+            "ant/client/{}/{client_version}/{}",
+            get_truncate_version_str(),
+            self.network_id
+        )
+    }
+}
+
+/// Why two peers cannot talk to each other, see [`ProtocolMismatch::reason`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MismatchReason {
+    /// The peer belongs to a different network
+    DifferentNetwork { ours: u8, theirs: u8 },
+    /// The peer runs an older protocol version than ours
+    OutdatedPeer,
+    /// The peer runs a newer protocol version than ours
+    OutdatedSelf,
+    /// The peer's protocol is not one of ours at all
+    Unknown,
+}
+
+/// The identify protocols of two peers that cannot talk to each other, either because they belong
+/// to different networks or because they run incompatible protocol versions.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Protocol mismatch, ours is {ours:?} and theirs is {theirs:?}: {}", self.reason_str())]
+pub struct ProtocolMismatch {
+    pub ours: String,
+    pub theirs: String,
+}
+
+impl ProtocolMismatch {
+    pub fn reason(&self) -> MismatchReason {
+        let (Some((our_version, our_id)), Some((their_version, their_id))) = (
+            parse_identify_protocol_str(&self.ours),
+            parse_identify_protocol_str(&self.theirs),
+        ) else {
+            return MismatchReason::Unknown;
+        };
+        if our_id != their_id {
+            MismatchReason::DifferentNetwork {
+                ours: our_id,
+                theirs: their_id,
+            }
+        } else if their_version < our_version {
+            MismatchReason::OutdatedPeer
+        } else if their_version > our_version {
+            MismatchReason::OutdatedSelf
+        } else {
+            MismatchReason::Unknown
+        }
+    }
+
+    fn reason_str(&self) -> String {
+        match self.reason() {
+            MismatchReason::DifferentNetwork { ours, theirs } => {
+                format!("the peer belongs to network {theirs}, while we belong to network {ours}")
+            }
+            MismatchReason::OutdatedPeer => "the peer runs an older protocol version".to_string(),
+            MismatchReason::OutdatedSelf => "the peer runs a newer protocol version".to_string(),
+            MismatchReason::Unknown => "the peer runs an unknown protocol".to_string(),
+        }
+    }
+}
+
+/// Parse an identify protocol string, `ant/{major}.{minor}/{network_id}`, into its version and
+/// network id.
+fn parse_identify_protocol_str(protocol: &str) -> Option<((u64, u64), u8)> {
+    let mut parts = protocol.split('/');
+    if parts.next()? != "ant" {
+        return None;
+    }
+    let (major, minor) = parts.next()?.split_once('.')?;
+    let network_id = parts.next()?.parse().ok()?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some(((major.parse().ok()?, minor.parse().ok()?), network_id))
+}
+
 // Protocol support shall be downward compatible for patch only version update.
 // i.e. versions of `A.B.X` or `A.B.X-alpha.Y` shall be considered as a same protocol of `A.B`
 pub fn get_truncate_version_str() -> String {
@@ -210,4 +330,56 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_network_protocols_are_independent() {
+        let mainnet = NetworkProtocol::new(MAINNET_ID);
+        let alphanet = NetworkProtocol::new(ALPHANET_ID);
+        let version = get_truncate_version_str();
+
+        assert_eq!(
+            mainnet.req_response_version_str(),
+            format!("/ant/{version}/1")
+        );
+        assert_eq!(alphanet.identify_protocol_str(), format!("ant/{version}/2"));
+        assert_eq!(
+            alphanet.client_user_agent("1.0.0".to_string()),
+            format!("ant/client/{version}/1.0.0/2")
+        );
+        assert_eq!(
+            mainnet.node_user_agent("1.0.0".to_string()),
+            format!("ant/node/{version}/1.0.0/1")
+        );
+        assert_ne!(
+            mainnet.identify_protocol_str(),
+            alphanet.identify_protocol_str()
+        );
+    }
+
+    #[test]
+    fn test_protocol_negotiation() {
+        let mainnet = NetworkProtocol::new(MAINNET_ID);
+        assert!(mainnet.negotiate(&mainnet.identify_protocol_str()).is_ok());
+
+        let mismatch = mainnet
+            .negotiate(&NetworkProtocol::new(ALPHANET_ID).identify_protocol_str())
+            .expect_err("different networks");
+        assert_eq!(
+            mismatch.reason(),
+            MismatchReason::DifferentNetwork {
+                ours: MAINNET_ID,
+                theirs: ALPHANET_ID
+            }
+        );
+
+        let older = mainnet.negotiate("ant/0.0/1").expect_err("older version");
+        let newer = mainnet.negotiate("ant/999.0/1").expect_err("newer version");
+        assert_eq!(older.reason(), MismatchReason::OutdatedPeer);
+        assert_eq!(newer.reason(), MismatchReason::OutdatedSelf);
+
+        let unknown = mainnet
+            .negotiate("ipfs/0.1.0")
+            .expect_err("unknown protocol");
+        assert_eq!(unknown.reason(), MismatchReason::Unknown);
+    }
 }
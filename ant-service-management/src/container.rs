@@ -0,0 +1,383 @@
+// Copyright (C) 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Nodes running in Docker or Podman containers.
+//!
+//! Instead of being installed as a native service, a node can run in a container created from an
+//! `antnode` image, tagged with the version of the node. The containers are driven through the CLI
+//! of the runtime. Each container is labelled with the binary path recorded for its node in the
+//! registry, which is how its process is found back when the registry is refreshed.
+
+use crate::{
+    control::{ServiceControl, ServiceController},
+    error::{Error, Result},
+};
+use serde::{Deserialize, Serialize};
+use service_manager::{RestartPolicy, ServiceInstallCtx};
+use std::{ffi::OsString, fmt, path::Path, process::Command, str::FromStr};
+
+/// The image nodes are created from, unless another one is given.
+pub const DEFAULT_ANTNODE_IMAGE: &str = "maidsafe/antnode";
+
+/// The label holding the binary path of the node in the registry.
+const BIN_PATH_LABEL: &str = "io.autonomi.antnode.bin-path";
+
+/// The node arguments holding host paths, which are mounted at the same path in the container.
+const MOUNTED_PATH_ARGS: [&str; 3] = ["--root-dir", "--log-output-dest", "--bootstrap-cache-dir"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ContainerRuntime {
+    Docker,
+    Podman,
+}
+
+impl ContainerRuntime {
+    /// The command of the runtime CLI.
+    pub fn command(&self) -> &'static str {
+        match self {
+            ContainerRuntime::Docker => "docker",
+            ContainerRuntime::Podman => "podman",
+        }
+    }
+}
+
+impl fmt::Display for ContainerRuntime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.command())
+    }
+}
+
+impl FromStr for ContainerRuntime {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "docker" => Ok(ContainerRuntime::Docker),
+            "podman" => Ok(ContainerRuntime::Podman),
+            _ => Err(Error::InvalidContainerRuntime(s.to_string())),
+        }
+    }
+}
+
+/// The container a node runs in, as recorded in the registry.
+///
+/// The image is tagged with the version of the node, so the tag is not part of `image`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeContainer {
+    pub runtime: ContainerRuntime,
+    pub image: String,
+}
+
+impl NodeContainer {
+    /// The reference of the image of the given node version.
+    pub fn image_ref(&self, version: &str) -> String {
+        format!("{}:{version}", self.image)
+    }
+
+    /// The [`ServiceControl`] creating containers of the given node version.
+    pub fn service_control(&self, version: &str) -> ContainerServiceControl {
+        ContainerServiceControl::new(self.runtime, self.image_ref(version))
+    }
+}
+
+/// A [`ServiceControl`] running services as containers rather than native services.
+///
+/// The entrypoint of the image is expected to be `antnode`. Containers use the network of the host,
+/// so the ports of the node are the ones it is configured with, and its data and log directories
+/// are mounted at the same paths they have on the host.
+pub struct ContainerServiceControl {
+    runtime: ContainerRuntime,
+    image_ref: String,
+}
+
+impl ContainerServiceControl {
+    pub fn new(runtime: ContainerRuntime, image_ref: String) -> Self {
+        Self { runtime, image_ref }
+    }
+
+    /// Run a command of the runtime CLI, returning its output.
+    fn run(&self, args: &[OsString]) -> Result<String> {
+        trace!("Running {} {args:?}", self.runtime);
+        let output = Command::new(self.runtime.command())
+            .args(args)
+            .output()
+            .inspect_err(|err| error!("Failed to execute {}: {err:?}", self.runtime))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            error!("The {} command {args:?} failed: {stderr}", self.runtime);
+            return Err(Error::ContainerCommandFailed(
+                self.runtime.to_string(),
+                stderr,
+            ));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// The arguments creating the container of a service.
+    fn create_args(&self, install_ctx: &ServiceInstallCtx) -> Vec<OsString> {
+        let mut args = vec![
+            OsString::from("create"),
+            OsString::from("--name"),
+            OsString::from(install_ctx.label.to_string()),
+            OsString::from("--label"),
+            OsString::from(format!(
+                "{BIN_PATH_LABEL}={}",
+                install_ctx.program.to_string_lossy()
+            )),
+            OsString::from("--network"),
+            OsString::from("host"),
+            OsString::from("--restart"),
+            OsString::from(restart_policy_arg(&install_ctx.restart_policy)),
+        ];
+        for pair in install_ctx.args.windows(2) {
+            if MOUNTED_PATH_ARGS.iter().any(|arg| pair[0] == *arg) {
+                let path = pair[1].to_string_lossy();
+                args.push(OsString::from("--volume"));
+                args.push(OsString::from(format!("{path}:{path}")));
+            }
+        }
+        for (key, value) in install_ctx.environment.iter().flatten() {
+            args.push(OsString::from("--env"));
+            args.push(OsString::from(format!("{key}={value}")));
+        }
+        args.push(OsString::from(&self.image_ref));
+        args.extend(install_ctx.args.iter().cloned());
+        args
+    }
+}
+
+impl ServiceControl for ContainerServiceControl {
+    fn create_service_user(&self, username: &str) -> Result<()> {
+        debug!(
+            "Nodes run in {} containers, no {username} user is needed",
+            self.runtime
+        );
+        Ok(())
+    }
+
+    fn get_available_port(&self) -> Result<u16> {
+        // The containers use the network of the host
+        ServiceController {}.get_available_port()
+    }
+
+    fn install(&self, install_ctx: ServiceInstallCtx, _user_mode: bool) -> Result<()> {
+        debug!("Creating {} container: {install_ctx:?}", self.runtime);
+        let id = self.run(&self.create_args(&install_ctx))?;
+        info!(
+            "Created container {id} for {} from {}",
+            install_ctx.label, self.image_ref
+        );
+        Ok(())
+    }
+
+    fn get_process_pid(&self, bin_path: &Path) -> Result<u32> {
+        debug!(
+            "Searching for the container of {}",
+            bin_path.to_string_lossy()
+        );
+        let ids = self.run(&[
+            OsString::from("ps"),
+            OsString::from("--quiet"),
+            OsString::from("--filter"),
+            OsString::from(format!(
+                "label={BIN_PATH_LABEL}={}",
+                bin_path.to_string_lossy()
+            )),
+        ])?;
+        let Some(id) = ids.lines().next() else {
+            error!("No running container was located for {bin_path:?}");
+            return Err(Error::ServiceProcessNotFound(
+                bin_path.to_string_lossy().to_string(),
+            ));
+        };
+        let pid: u32 = self
+            .run(&[
+                OsString::from("inspect"),
+                OsString::from("--format"),
+                OsString::from("{{.State.Pid}}"),
+                OsString::from(id),
+            ])?
+            .parse()?;
+        if pid == 0 {
+            return Err(Error::ServiceProcessNotFound(
+                bin_path.to_string_lossy().to_string(),
+            ));
+        }
+        trace!("Found container {id} for {bin_path:?} with PID: {pid}");
+        Ok(pid)
+    }
+
+    fn get_process_version(&self, pid: u32) -> Result<Option<String>> {
+        let ids = self.run(&[OsString::from("ps"), OsString::from("--quiet")])?;
+        if ids.is_empty() {
+            return Ok(None);
+        }
+        let mut args = vec![
+            OsString::from("inspect"),
+            OsString::from("--format"),
+            OsString::from("{{.State.Pid}} {{.Config.Image}}"),
+        ];
+        args.extend(ids.lines().map(OsString::from));
+        let containers = self.run(&args)?;
+        let version = containers
+            .lines()
+            .filter_map(|line| line.split_once(' '))
+            .find(|(container_pid, _)| *container_pid == pid.to_string())
+            .and_then(|(_, image)| version_from_image_ref(image));
+        debug!("Version of the container with PID {pid}: {version:?}");
+        Ok(version)
+    }
+
+    fn start(&self, service_name: &str, _user_mode: bool) -> Result<()> {
+        debug!("Starting container: {service_name}");
+        let _ = self.run(&[OsString::from("start"), OsString::from(service_name)])?;
+        Ok(())
+    }
+
+    fn stop(&self, service_name: &str, _user_mode: bool) -> Result<()> {
+        debug!("Stopping container: {service_name}");
+        let _ = self.run(&[OsString::from("stop"), OsString::from(service_name)])?;
+        Ok(())
+    }
+
+    fn uninstall(&self, service_name: &str, _user_mode: bool) -> Result<()> {
+        debug!("Removing container: {service_name}");
+        match self.run(&[
+            OsString::from("rm"),
+            OsString::from("--force"),
+            OsString::from(service_name),
+        ]) {
+            Ok(_) => Ok(()),
+            Err(Error::ContainerCommandFailed(_, stderr))
+                if stderr.to_lowercase().contains("no such container") =>
+            {
+                Err(Error::ServiceDoesNotExists(service_name.to_string()))
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn verify_process_by_pid(&self, pid: u32, expected_name: &str) -> Result<bool> {
+        // The processes of containers are processes of the host
+        ServiceController {}.verify_process_by_pid(pid, expected_name)
+    }
+
+    fn wait(&self, delay: u64) {
+        trace!("Waiting for {delay} milliseconds");
+        std::thread::sleep(std::time::Duration::from_millis(delay));
+    }
+}
+
+/// The `--restart` policy of the runtime closest to the restart policy of the service.
+fn restart_policy_arg(restart_policy: &RestartPolicy) -> &'static str {
+    match restart_policy {
+        RestartPolicy::Never => "no",
+        RestartPolicy::OnFailure { .. } => "on-failure",
+        _ => "unless-stopped",
+    }
+}
+
+/// The version of a node from the tag of its image.
+fn version_from_image_ref(image_ref: &str) -> Option<String> {
+    let (_, tag) = image_ref.rsplit_once(':')?;
+    // A colon before the last slash belongs to the port of a registry
+    (!tag.contains('/')).then(|| tag.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_container_runtime_from_str() {
+        assert_eq!(
+            "docker".parse::<ContainerRuntime>().unwrap(),
+            ContainerRuntime::Docker
+        );
+        assert_eq!(
+            "Podman".parse::<ContainerRuntime>().unwrap(),
+            ContainerRuntime::Podman
+        );
+        assert!("lxc".parse::<ContainerRuntime>().is_err());
+    }
+
+    #[test]
+    fn test_version_from_image_ref() {
+        assert_eq!(
+            version_from_image_ref("maidsafe/antnode:0.4.9"),
+            Some("0.4.9".to_string())
+        );
+        assert_eq!(
+            version_from_image_ref("registry:5000/antnode:0.4.10-rc.1"),
+            Some("0.4.10-rc.1".to_string())
+        );
+        assert_eq!(version_from_image_ref("registry:5000/antnode"), None);
+        assert_eq!(version_from_image_ref("antnode"), None);
+    }
+
+    #[test]
+    fn test_create_args_mount_directories_and_pass_node_args() {
+        let container = NodeContainer {
+            runtime: ContainerRuntime::Podman,
+            image: DEFAULT_ANTNODE_IMAGE.to_string(),
+        };
+        let control = container.service_control("0.4.9");
+        let install_ctx = ServiceInstallCtx {
+            args: vec![
+                OsString::from("--rpc"),
+                OsString::from("127.0.0.1:8081"),
+                OsString::from("--root-dir"),
+                OsString::from("/var/antctl/services/antnode1"),
+                OsString::from("--log-output-dest"),
+                OsString::from("/var/log/antnode/antnode1"),
+            ],
+            autostart: false,
+            contents: None,
+            environment: Some(vec![("ANT_LOG".to_string(), "all".to_string())]),
+            label: "antnode1".parse().unwrap(),
+            program: PathBuf::from("/var/antctl/services/antnode1/antnode"),
+            restart_policy: RestartPolicy::OnFailure { delay_secs: None },
+            username: None,
+            working_directory: None,
+        };
+
+        let args: Vec<String> = control
+            .create_args(&install_ctx)
+            .iter()
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect();
+        assert_eq!(
+            args,
+            vec![
+                "create",
+                "--name",
+                "antnode1",
+                "--label",
+                "io.autonomi.antnode.bin-path=/var/antctl/services/antnode1/antnode",
+                "--network",
+                "host",
+                "--restart",
+                "on-failure",
+                "--volume",
+                "/var/antctl/services/antnode1:/var/antctl/services/antnode1",
+                "--volume",
+                "/var/log/antnode/antnode1:/var/log/antnode/antnode1",
+                "--env",
+                "ANT_LOG=all",
+                "maidsafe/antnode:0.4.9",
+                "--rpc",
+                "127.0.0.1:8081",
+                "--root-dir",
+                "/var/antctl/services/antnode1",
+                "--log-output-dest",
+                "/var/log/antnode/antnode1",
+            ]
+        );
+    }
+}
@@ -17,10 +17,14 @@ pub type Result<T, E = Error> = std::result::Result<T, E>;
 pub enum Error {
     #[error(transparent)]
     AddrParseError(#[from] std::net::AddrParseError),
+    #[error("The {0} command failed: {1}")]
+    ContainerCommandFailed(String, String),
     #[error("The endpoint for the daemon has not been set")]
     DaemonEndpointNotSet,
     #[error(transparent)]
     Io(#[from] std::io::Error),
+    #[error("'{0}' is not a supported container runtime, use docker or podman")]
+    InvalidContainerRuntime(String),
     #[error(transparent)]
     Json(#[from] serde_json::Error),
     #[error(transparent)]
@@ -31,20 +35,36 @@ pub enum Error {
     ParseIntError(#[from] std::num::ParseIntError),
     #[error(transparent)]
     PeerIdParseError(#[from] libp2p_identity::ParseError),
+    #[error("Could not obtain behaviour health through RPC: {0}")]
+    RpcBehaviourHealthError(String),
     #[error("Could not connect to RPC endpoint '{0}'")]
     RpcConnectionError(String),
+    #[error("Could not put node into hibernation through RPC: {0}")]
+    RpcNodeHibernateError(String),
     #[error("Could not obtain node info through RPC: {0}")]
     RpcNodeInfoError(String),
     #[error("Could not obtain network info through RPC: {0}")]
     RpcNetworkInfoError(String),
     #[error("Could not restart node through RPC: {0}")]
     RpcNodeRestartError(String),
+    #[error("Could not resume node from hibernation through RPC: {0}")]
+    RpcNodeResumeError(String),
     #[error("Could not stop node through RPC: {0}")]
     RpcNodeStopError(String),
     #[error("Could not update node through RPC: {0}")]
     RpcNodeUpdateError(String),
+    #[error("Could not obtain the reachability through RPC: {0}")]
+    RpcReachabilityError(String),
     #[error("Could not obtain record addresses through RPC: {0}")]
     RpcRecordAddressError(String),
+    #[error("Could not obtain record store stats through RPC: {0}")]
+    RpcRecordStoreStatsError(String),
+    #[error("Could not replicate record through RPC: {0}")]
+    RpcReplicateRecordError(String),
+    #[error("Could not set the log level of the node through RPC: {0}")]
+    RpcSetLogLevelError(String),
+    #[error("Could not verify records through RPC: {0}")]
+    RpcVerifyRecordsError(String),
     #[error("Could not find process at '{0}'")]
     ServiceProcessNotFound(String),
     #[error("The service '{0}' does not exists and cannot be removed.")]
@@ -10,12 +10,14 @@
 #![allow(clippy::unwrap_used)]
 #![allow(clippy::expect_used)]
 
+pub mod container;
 pub mod control;
 pub mod daemon;
 pub mod error;
 pub mod node;
 pub mod registry;
 pub mod rpc;
+pub mod systemd;
 
 #[macro_use]
 extern crate tracing;
@@ -32,10 +34,11 @@ use semver::Version;
 use serde::{Deserialize, Serialize};
 use service_manager::ServiceInstallCtx;
 
+pub use container::{ContainerRuntime, ContainerServiceControl, NodeContainer};
 pub use daemon::{DaemonService, DaemonServiceData};
 pub use error::{Error, Result};
 pub use node::{NodeService, NodeServiceData};
-pub use registry::{NodeRegistryManager, StatusSummary, get_local_node_registry_path};
+pub use registry::{NodeFilter, NodeRegistryManager, StatusSummary, get_local_node_registry_path};
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum ServiceStatus {
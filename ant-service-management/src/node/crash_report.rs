@@ -0,0 +1,145 @@
+// Copyright (C) 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use serde::{Deserialize, Serialize};
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+/// Directory, inside the node's data dir, holding the crash reports.
+pub const CRASH_REPORTS_DIR: &str = "crash_reports";
+/// Number of crash reports kept, the oldest ones are removed.
+pub const MAX_CRASH_REPORTS: usize = 10;
+
+/// What brought the node down.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CrashKind {
+    Panic,
+    FatalError,
+}
+
+impl std::fmt::Display for CrashKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CrashKind::Panic => write!(f, "panic"),
+            CrashKind::FatalError => write!(f, "fatal error"),
+        }
+    }
+}
+
+/// A structured report written by antnode when it panics or stops on a fatal error.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrashReport {
+    /// Time of the crash, in seconds since the UNIX epoch
+    pub timestamp: u64,
+    pub kind: CrashKind,
+    /// The panic message or the error that stopped the node
+    pub reason: String,
+    /// Where the panic occurred, if known
+    pub location: Option<String>,
+    pub backtrace: Option<String>,
+    /// The version of the antnode binary
+    pub version: String,
+    /// Hash of the options the node was started with, to tell apart crashes of different configs
+    pub config_hash: String,
+    /// The last lines of the node's log before the crash
+    pub last_log_lines: Vec<String>,
+}
+
+impl CrashReport {
+    /// The time of the crash.
+    pub fn datetime(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        chrono::DateTime::from_timestamp(self.timestamp as i64, 0)
+    }
+
+    /// The path of the report in the data dir of the node.
+    pub fn path(&self, data_dir: &Path) -> PathBuf {
+        data_dir
+            .join(CRASH_REPORTS_DIR)
+            .join(format!("crash_{}.json", self.timestamp))
+    }
+
+    /// Write the report in the crash reports dir of the node, removing the oldest reports above
+    /// `MAX_CRASH_REPORTS`. Returns the path of the report.
+    pub fn write(&self, data_dir: &Path) -> io::Result<PathBuf> {
+        let dir = data_dir.join(CRASH_REPORTS_DIR);
+        std::fs::create_dir_all(&dir)?;
+        let path = self.path(data_dir);
+        std::fs::write(&path, serde_json::to_vec_pretty(self)?)?;
+
+        let reports = report_paths(&dir)?;
+        if reports.len() > MAX_CRASH_REPORTS {
+            for old_report in &reports[..reports.len() - MAX_CRASH_REPORTS] {
+                let _ = std::fs::remove_file(old_report);
+            }
+        }
+        Ok(path)
+    }
+
+    /// Read the latest crash report of the node, if any.
+    pub fn latest(data_dir: &Path) -> Option<CrashReport> {
+        let reports = report_paths(&data_dir.join(CRASH_REPORTS_DIR)).ok()?;
+        let content = std::fs::read(reports.last()?).ok()?;
+        serde_json::from_slice(&content).ok()
+    }
+}
+
+/// The paths of the reports in the dir, oldest first.
+fn report_paths(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut reports: Vec<(u64, PathBuf)> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let path = entry.path();
+            let timestamp = path
+                .file_stem()?
+                .to_str()?
+                .strip_prefix("crash_")?
+                .parse()
+                .ok()?;
+            Some((timestamp, path))
+        })
+        .collect();
+    reports.sort();
+    Ok(reports.into_iter().map(|(_, path)| path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(timestamp: u64) -> CrashReport {
+        CrashReport {
+            timestamp,
+            kind: CrashKind::Panic,
+            reason: format!("crash at {timestamp}"),
+            location: Some("src/main.rs:1:1".to_string()),
+            backtrace: None,
+            version: "0.4.0".to_string(),
+            config_hash: "abcd".to_string(),
+            last_log_lines: vec!["last line".to_string()],
+        }
+    }
+
+    #[test]
+    fn latest_crash_report_is_read_and_old_ones_are_pruned() {
+        let dir = std::env::temp_dir().join(format!("crash_reports_test_{}", std::process::id()));
+        assert!(CrashReport::latest(&dir).is_none());
+
+        for timestamp in 0..(MAX_CRASH_REPORTS as u64 + 2) {
+            let _ = report(1_700_000_000 + timestamp).write(&dir).unwrap();
+        }
+
+        let latest = CrashReport::latest(&dir).unwrap();
+        assert_eq!(latest, report(1_700_000_000 + MAX_CRASH_REPORTS as u64 + 1));
+        let reports = report_paths(&dir.join(CRASH_REPORTS_DIR)).unwrap();
+        assert_eq!(reports.len(), MAX_CRASH_REPORTS);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
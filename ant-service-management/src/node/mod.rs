@@ -6,15 +6,22 @@
 // KIND, either express or implied. Please review the Licences for the specific language governing
 // permissions and limitations relating to use of the SAFE Network Software.
 
+mod crash_report;
+mod node_info;
 mod node_service_data;
 mod node_service_data_v0;
 mod node_service_data_v1;
 mod node_service_data_v2;
 
 // Re-export types
+pub use crash_report::{CRASH_REPORTS_DIR, CrashKind, CrashReport, MAX_CRASH_REPORTS};
+pub use node_info::{NODE_INFO_FILENAME, NodeInfoFile};
 pub use node_service_data::{NODE_SERVICE_DATA_SCHEMA_LATEST, NodeServiceData};
 
-use crate::{ServiceStateActions, ServiceStatus, UpgradeOptions, error::Result, rpc::RpcActions};
+use crate::{
+    ServiceStateActions, ServiceStatus, UpgradeOptions, error::Result, rpc::RpcActions,
+    systemd::node_unit_contents,
+};
 use ant_bootstrap::InitialPeersConfig;
 use ant_evm::EvmNetwork;
 use ant_protocol::get_port_from_multiaddr;
@@ -117,6 +124,8 @@ impl ServiceStateActions for NodeService {
         // Always pass --stop-on-upgrade for service-managed nodes so the node exits
         // (letting the service manager handle restart) instead of spawning a child process.
         args.push(OsString::from("--stop-on-upgrade"));
+        // The status of the service is reported from the node info
+        args.push(OsString::from("--write-node-info"));
 
         args.push(OsString::from("--rewards-address"));
         args.push(OsString::from(service_data.rewards_address.to_string()));
@@ -128,7 +137,14 @@ impl ServiceStateActions for NodeService {
         args.push(OsString::from(service_data.evm_network.to_string()));
         if let EvmNetwork::Custom(custom_network) = &service_data.evm_network {
             args.push(OsString::from("--rpc-url"));
-            args.push(OsString::from(custom_network.rpc_url_http.to_string()));
+            // The fallback endpoints follow the primary one
+            let rpc_urls: Vec<String> = service_data
+                .evm_network
+                .rpc_urls()
+                .iter()
+                .map(ToString::to_string)
+                .collect();
+            args.push(OsString::from(rpc_urls.join(",")));
             args.push(OsString::from("--payment-token-address"));
             args.push(OsString::from(
                 custom_network.payment_token_address.to_string(),
@@ -143,7 +159,7 @@ impl ServiceStateActions for NodeService {
             }
         }
 
-        Ok(ServiceInstallCtx {
+        let mut install_ctx = ServiceInstallCtx {
             args,
             autostart: options.auto_restart,
             contents: None,
@@ -164,7 +180,15 @@ impl ServiceStateActions for NodeService {
             },
             username: service_data.user.clone(),
             working_directory: None,
-        })
+        };
+        if let Some(watchdog_secs) = service_data.watchdog_secs {
+            install_ctx.contents = Some(node_unit_contents(
+                &install_ctx,
+                service_data.user_mode,
+                watchdog_secs,
+            ));
+        }
+        Ok(install_ctx)
     }
 
     async fn data_dir_path(&self) -> PathBuf {
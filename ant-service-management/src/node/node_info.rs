@@ -0,0 +1,101 @@
+// Copyright (C) 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use serde::{Deserialize, Serialize};
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+/// File, inside the node's root dir, holding the node info.
+pub const NODE_INFO_FILENAME: &str = "node-info.json";
+
+/// A machine-readable description of a running node, written by antnode to its root dir when
+/// started with `--write-node-info`, and rewritten when its listen addresses change.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeInfoFile {
+    pub peer_id: String,
+    /// The addresses the node listens on, ending with its peer id
+    pub listen_addrs: Vec<String>,
+    /// Port of the metrics server, if enabled on a fixed port
+    pub metrics_port: Option<u16>,
+    /// Port of the RPC service, if enabled
+    pub rpc_port: Option<u16>,
+    pub network_id: u8,
+    /// The version of the antnode binary
+    pub version: String,
+    /// The id of the node process, to tell whether the info is stale
+    pub pid: u32,
+    /// Time of the last update, in seconds since the UNIX epoch
+    pub updated_at: u64,
+}
+
+impl NodeInfoFile {
+    /// The path of the node info in the root dir of the node.
+    pub fn path(root_dir: &Path) -> PathBuf {
+        root_dir.join(NODE_INFO_FILENAME)
+    }
+
+    /// The time of the last update.
+    pub fn updated_datetime(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        chrono::DateTime::from_timestamp(self.updated_at as i64, 0)
+    }
+
+    /// Write the info in the root dir of the node, through a temporary file renamed over the
+    /// previous one so that readers never see a partial file. Returns the path of the info.
+    pub fn write(&self, root_dir: &Path) -> io::Result<PathBuf> {
+        let path = Self::path(root_dir);
+        let tmp_path = path.with_extension("json.tmp");
+        std::fs::write(&tmp_path, serde_json::to_vec_pretty(self)?)?;
+        std::fs::rename(&tmp_path, &path)?;
+        Ok(path)
+    }
+
+    /// Read the info written by the node, if any.
+    pub fn read(root_dir: &Path) -> Option<NodeInfoFile> {
+        let content = std::fs::read(Self::path(root_dir)).ok()?;
+        serde_json::from_slice(&content).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::TempDir;
+
+    #[test]
+    fn node_info_is_written_and_read_back() {
+        let tmp_dir = TempDir::new().unwrap();
+        let dir = tmp_dir.path();
+        assert!(NodeInfoFile::read(dir).is_none());
+
+        let info = NodeInfoFile {
+            peer_id: "12D3KooWGQu92xCXuiK6AysbHn6kHyfXqyzNDxNGnnDTgd56eveq".to_string(),
+            listen_addrs: vec![
+                "/ip4/127.0.0.1/udp/12000/quic-v1/p2p/12D3KooWGQu92xCXuiK6AysbHn6kHyfXqyzNDxNGnnDTgd56eveq"
+                    .to_string(),
+            ],
+            metrics_port: Some(13000),
+            rpc_port: None,
+            network_id: 1,
+            version: "0.4.0".to_string(),
+            pid: 42,
+            updated_at: 1_700_000_000,
+        };
+        let path = info.write(dir).unwrap();
+        assert_eq!(path, dir.join(NODE_INFO_FILENAME));
+        assert_eq!(NodeInfoFile::read(dir), Some(info.clone()));
+
+        let updated = NodeInfoFile {
+            listen_addrs: vec![],
+            ..info
+        };
+        let _ = updated.write(dir).unwrap();
+        assert_eq!(NodeInfoFile::read(dir), Some(updated));
+    }
+}
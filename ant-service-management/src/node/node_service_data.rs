@@ -6,7 +6,10 @@
 // KIND, either express or implied. Please review the Licences for the specific language governing
 // permissions and limitations relating to use of the SAFE Network Software.
 
-use crate::error::Result;
+use crate::{
+    control::{ServiceControl, ServiceController},
+    error::Result,
+};
 use ant_protocol::get_port_from_multiaddr;
 use libp2p::PeerId;
 use serde::{Deserialize, Deserializer, Serializer, de::Error as DeError};
@@ -111,6 +114,31 @@ impl NodeServiceData {
         None
     }
 
+    /// Returns the latest crash report of the node, if it ever crashed.
+    pub fn get_latest_crash_report(&self) -> Option<super::CrashReport> {
+        super::CrashReport::latest(&self.data_dir_path)
+    }
+
+    /// Returns the info written by the node, if it runs with `--write-node-info`.
+    pub fn get_node_info(&self) -> Option<super::NodeInfoFile> {
+        super::NodeInfoFile::read(&self.data_dir_path)
+    }
+
+    /// Returns the [`ServiceControl`] managing the node: the runtime of its container, or the
+    /// native service manager.
+    pub fn service_control(&self) -> Box<dyn ServiceControl + Send> {
+        self.service_control_for_version(&self.version)
+    }
+
+    /// Same as [`NodeServiceData::service_control`], but a container node gets its containers
+    /// created from the image of the given version, as needed for an upgrade.
+    pub fn service_control_for_version(&self, version: &str) -> Box<dyn ServiceControl + Send> {
+        match &self.container {
+            Some(container) => Box::new(container.service_control(version)),
+            None => Box::new(ServiceController {}),
+        }
+    }
+
     pub fn serialize_peer_id<S>(value: &Option<PeerId>, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
@@ -86,6 +86,7 @@ impl From<NodeServiceDataV1> for NodeServiceDataV2 {
             antnode_path: v1.antnode_path,
             auto_restart: v1.auto_restart,
             connected_peers: v1.connected_peers,
+            container: None,
             data_dir_path: v1.data_dir_path,
             evm_network: v1.evm_network,
             initial_peers_config: v1.initial_peers_config,
@@ -112,6 +113,7 @@ impl From<NodeServiceDataV1> for NodeServiceDataV2 {
             user: v1.user,
             user_mode: v1.user_mode,
             version: v1.version,
+            watchdog_secs: None,
             write_older_cache_files: false, // Default value for upgraded instances
         }
     }
@@ -7,7 +7,7 @@
 // permissions and limitations relating to use of the SAFE Network Software.
 
 use super::NodeServiceData;
-use crate::{ServiceStatus, error::Result};
+use crate::{ServiceStatus, container::NodeContainer, error::Result};
 use ant_bootstrap::InitialPeersConfig;
 use ant_evm::{AttoTokens, EvmNetwork, RewardsAddress};
 use ant_logging::LogFormat;
@@ -36,6 +36,10 @@ pub struct NodeServiceDataV2 {
     pub auto_restart: bool,
     #[serde(serialize_with = "NodeServiceData::serialize_connected_peers")]
     pub connected_peers: Option<Vec<PeerId>>,
+    /// The container the node runs in, `None` for a native service.
+    /// Serde::default is used here for backward compatibility
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub container: Option<NodeContainer>,
     pub data_dir_path: PathBuf,
     #[serde(default)]
     pub evm_network: EvmNetwork,
@@ -67,6 +71,10 @@ pub struct NodeServiceDataV2 {
     pub user: Option<String>,
     pub user_mode: bool,
     pub version: String,
+    /// The watchdog timeout of the systemd unit of the node, `None` if it has no watchdog.
+    /// Serde::default is used here for backward compatibility
+    #[serde(default)]
+    pub watchdog_secs: Option<u64>,
     /// New field in V2: indicates if older cache files should be written
     /// Serde::default is used here for backward compatibility
     #[serde(default)]
@@ -89,6 +97,8 @@ impl NodeServiceDataV2 {
             auto_restart: bool,
             #[serde(deserialize_with = "NodeServiceData::deserialize_connected_peers")]
             connected_peers: Option<Vec<PeerId>>,
+            #[serde(default)]
+            container: Option<NodeContainer>,
             data_dir_path: PathBuf,
             #[serde(default)]
             evm_network: EvmNetwork,
@@ -121,6 +131,8 @@ impl NodeServiceDataV2 {
             user_mode: bool,
             version: String,
             #[serde(default)]
+            watchdog_secs: Option<u64>,
+            #[serde(default)]
             alpha: bool,
             #[serde(default)]
             write_older_cache_files: bool,
@@ -133,6 +145,7 @@ impl NodeServiceDataV2 {
             antnode_path: helper.antnode_path,
             auto_restart: helper.auto_restart,
             connected_peers: helper.connected_peers,
+            container: helper.container,
             data_dir_path: helper.data_dir_path,
             evm_network: helper.evm_network,
             initial_peers_config: helper.initial_peers_config,
@@ -158,6 +171,7 @@ impl NodeServiceDataV2 {
             user: helper.user,
             user_mode: helper.user_mode,
             version: helper.version,
+            watchdog_secs: helper.watchdog_secs,
             alpha: helper.alpha,
             write_older_cache_files: helper.write_older_cache_files,
         })
@@ -199,6 +213,7 @@ mod tests {
             relay: true,
             auto_restart: false,
             connected_peers: None,
+            container: None,
             evm_network: EvmNetwork::ArbitrumSepoliaTest,
             initial_peers_config: InitialPeersConfig {
                 first: false,
@@ -221,6 +236,7 @@ mod tests {
             rewards_address: Default::default(),
             reward_balance: None,
             user: None,
+            watchdog_secs: None,
             write_older_cache_files: false,
         };
 
@@ -7,14 +7,22 @@
 // permissions and limitations relating to use of the SAFE Network Software.
 
 use crate::error::{Error, Result};
-use crate::{DaemonServiceData, NatDetectionStatus, NodeServiceData};
+use crate::{DaemonServiceData, NatDetectionStatus, NodeServiceData, ServiceStatus};
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
 use std::{
     io::{Read, Write},
     path::{Path, PathBuf},
+    time::{Duration, SystemTime},
 };
-use tokio::sync::RwLock;
+use tokio::sync::{RwLock, watch};
+
+/// How often the registry file is checked for the changes saved by other processes, while there
+/// are subscribers. See [`NodeRegistryManager::subscribe`].
+const REGISTRY_WATCH_INTERVAL: Duration = Duration::from_secs(2);
 
 /// Used to manage the NodeRegistry data and allows us to share the data across multiple threads.
 ///
@@ -27,6 +35,12 @@ pub struct NodeRegistryManager {
     pub nat_status: Arc<RwLock<Option<NatDetectionStatus>>>,
     pub nodes: Arc<RwLock<Vec<Arc<RwLock<NodeServiceData>>>>>,
     pub save_path: PathBuf,
+    /// The revision of the registry, bumped on every save. See [`NodeRegistryManager::subscribe`].
+    revision: Arc<watch::Sender<u64>>,
+    /// The modification time of the registry file when last loaded or saved by this manager
+    file_modified: Arc<RwLock<Option<SystemTime>>>,
+    /// Whether the registry file is being watched for the subscribers
+    watching: Arc<AtomicBool>,
 }
 
 impl From<NodeRegistry> for NodeRegistryManager {
@@ -45,6 +59,9 @@ impl From<NodeRegistry> for NodeRegistryManager {
                     .collect(),
             )),
             save_path: registry.save_path,
+            revision: Arc::new(watch::Sender::new(0)),
+            file_modified: Arc::new(RwLock::new(None)),
+            watching: Arc::new(AtomicBool::new(false)),
         }
     }
 }
@@ -60,6 +77,9 @@ impl NodeRegistryManager {
             nat_status: Arc::new(RwLock::new(None)),
             nodes: Arc::new(RwLock::new(Vec::new())),
             save_path,
+            revision: Arc::new(watch::Sender::new(0)),
+            file_modified: Arc::new(RwLock::new(None)),
+            watching: Arc::new(AtomicBool::new(false)),
         }
     }
 
@@ -79,10 +99,11 @@ impl NodeRegistryManager {
 
     /// Loads the node registry from the specified path.
     /// If the file does not exist, it returns a default `NodeRegistryManager` with an empty state.
-    #[allow(clippy::unused_async)]
     pub async fn load(path: &Path) -> Result<Self> {
+        let file_modified = file_modified(path).await;
         let registry = NodeRegistry::load(path)?;
         let manager = NodeRegistryManager::from(registry);
+        *manager.file_modified.write().await = file_modified;
 
         Ok(manager)
     }
@@ -91,9 +112,179 @@ impl NodeRegistryManager {
     pub async fn save(&self) -> Result<()> {
         let registry = self.to_registry().await;
         registry.save()?;
+        *self.file_modified.write().await = file_modified(&self.save_path).await;
+        self.notify_change();
         Ok(())
     }
 
+    /// Subscribe to the changes of the registry.
+    ///
+    /// The receiver holds the revision of the registry, which is bumped whenever the registry is
+    /// saved, including by [`NodeRegistryManager::update_nodes`]. While there are subscribers,
+    /// the registry file is also checked every [`REGISTRY_WATCH_INTERVAL`] for the changes saved
+    /// by other processes, e.g. another `antctl` command, which are then loaded into this manager
+    /// before bumping the revision. Consumers can wait for [`watch::Receiver::changed`] and then
+    /// query the nodes they need, instead of polling and re-parsing the registry file.
+    ///
+    /// The registry file is only watched when subscribing from within a Tokio runtime.
+    pub fn subscribe(&self) -> watch::Receiver<u64> {
+        let receiver = self.revision.subscribe();
+        self.watch_file();
+        receiver
+    }
+
+    fn notify_change(&self) {
+        self.revision.send_modify(|revision| *revision += 1);
+    }
+
+    /// Spawn the task reloading the registry when its file is changed by another process, unless
+    /// already running. The task stops once there are no subscribers left.
+    fn watch_file(&self) {
+        if self.watching.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            debug!("Not in a Tokio runtime, the node registry file is not watched");
+            self.watching.store(false, Ordering::Release);
+            return;
+        };
+
+        let manager = self.clone();
+        let _handle = runtime.spawn(async move {
+            debug!("Watching the node registry file {:?}", manager.save_path);
+            let mut interval = tokio::time::interval(REGISTRY_WATCH_INTERVAL);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                let _ = interval.tick().await;
+                if manager.revision.receiver_count() == 0 {
+                    break;
+                }
+                if let Err(err) = manager.reload_if_modified().await {
+                    warn!("Failed to reload the changed node registry: {err:?}");
+                }
+            }
+            manager.watching.store(false, Ordering::Release);
+            debug!("Stopped watching the node registry file");
+        });
+    }
+
+    /// Load the registry file if it was saved by another process since it was last loaded or
+    /// saved by this manager, and notify the subscribers.
+    ///
+    /// The nodes are updated in place, so that the handles to them held by callers stay valid.
+    async fn reload_if_modified(&self) -> Result<()> {
+        let modified = file_modified(&self.save_path).await;
+        if modified.is_none() || modified == *self.file_modified.read().await {
+            return Ok(());
+        }
+
+        let path = self.save_path.clone();
+        let registry = tokio::task::spawn_blocking(move || NodeRegistry::load(&path))
+            .await
+            .map_err(|err| std::io::Error::other(err.to_string()))??;
+        debug!(
+            "The node registry file was changed by another process, reloaded {} nodes",
+            registry.nodes.len()
+        );
+
+        *self.daemon.write().await = registry.daemon.map(|daemon| Arc::new(RwLock::new(daemon)));
+        *self.environment_variables.write().await = registry.environment_variables;
+        *self.nat_status.write().await = registry.nat_status;
+        {
+            let mut nodes = self.nodes.write().await;
+            let mut reloaded = Vec::with_capacity(registry.nodes.len());
+            for node in registry.nodes {
+                let mut existing = None;
+                for current in nodes.iter() {
+                    if current.read().await.service_name == node.service_name {
+                        existing = Some(Arc::clone(current));
+                        break;
+                    }
+                }
+                match existing {
+                    Some(existing) => {
+                        *existing.write().await = node;
+                        reloaded.push(existing);
+                    }
+                    None => reloaded.push(Arc::new(RwLock::new(node))),
+                }
+            }
+            *nodes = reloaded;
+        }
+
+        *self.file_modified.write().await = modified;
+        self.notify_change();
+        Ok(())
+    }
+
+    /// Returns the nodes matching the filter.
+    pub async fn nodes_matching(&self, filter: &NodeFilter) -> Vec<NodeServiceData> {
+        let mut matching = Vec::new();
+        for node in self.nodes.read().await.iter() {
+            let node = node.read().await;
+            if filter.matches(&node) {
+                matching.push(node.clone());
+            }
+        }
+        matching
+    }
+
+    /// Returns the node with the given service name, if any.
+    pub async fn node_by_service_name(&self, service_name: &str) -> Option<NodeServiceData> {
+        for node in self.nodes.read().await.iter() {
+            let node = node.read().await;
+            if node.service_name == service_name {
+                return Some(node.clone());
+            }
+        }
+        None
+    }
+
+    /// Applies `update` to every node matching the filter and saves the registry, as a single
+    /// atomic operation: the nodes are locked throughout, and the update is only kept once saved,
+    /// so either all the matching nodes are updated, in memory and on disk, or none of them.
+    ///
+    /// Returns the number of nodes updated.
+    pub async fn update_nodes<F>(&self, filter: &NodeFilter, mut update: F) -> Result<usize>
+    where
+        F: FnMut(&mut NodeServiceData),
+    {
+        let nodes = self.nodes.write().await;
+        let mut guards = Vec::with_capacity(nodes.len());
+        for node in nodes.iter() {
+            guards.push(node.write().await);
+        }
+
+        let mut updated_nodes: Vec<NodeServiceData> =
+            guards.iter().map(|node| (**node).clone()).collect();
+        let mut updated = 0;
+        for node in updated_nodes.iter_mut().filter(|node| filter.matches(node)) {
+            update(node);
+            updated += 1;
+        }
+
+        let mut daemon = None;
+        if let Some(d) = self.daemon.read().await.as_ref() {
+            daemon = Some(d.read().await.clone());
+        }
+        let registry = NodeRegistry {
+            daemon,
+            environment_variables: self.environment_variables.read().await.clone(),
+            nat_status: self.nat_status.read().await.clone(),
+            nodes: updated_nodes,
+            save_path: self.save_path.clone(),
+        };
+        registry.save()?;
+
+        for (guard, node) in guards.iter_mut().zip(registry.nodes) {
+            **guard = node;
+        }
+        *self.file_modified.write().await = file_modified(&self.save_path).await;
+        debug!("Updated {updated} nodes of the node registry");
+        self.notify_change();
+        Ok(updated)
+    }
+
     /// Converts the current state of the `NodeRegistryManager` to a `NodeRegistry`.
     async fn to_registry(&self) -> NodeRegistry {
         let nodes = self.get_node_service_data().await;
@@ -140,6 +331,70 @@ impl NodeRegistryManager {
     }
 }
 
+/// A query on the nodes of the registry. Nodes match when they match every criteria set, and
+/// the default filter matches every node.
+///
+/// ```
+/// use ant_service_management::ServiceStatus;
+/// use ant_service_management::registry::NodeFilter;
+///
+/// let filter = NodeFilter::new()
+///     .with_status(ServiceStatus::Running)
+///     .with_version("0.4.0");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NodeFilter {
+    pub status: Option<ServiceStatus>,
+    pub version: Option<String>,
+    pub port: Option<u16>,
+}
+
+impl NodeFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only match the nodes with the given status.
+    pub fn with_status(mut self, status: ServiceStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Only match the nodes running the given version of antnode.
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Only match the nodes using the given port, as their node, metrics or RPC port.
+    pub fn with_port(mut self, port: u16) -> Self {
+        self.port = Some(port);
+        self
+    }
+
+    /// Whether the node matches the filter.
+    pub fn matches(&self, node: &NodeServiceData) -> bool {
+        if let Some(status) = &self.status
+            && node.status != *status
+        {
+            return false;
+        }
+        if let Some(version) = &self.version
+            && node.version != *version
+        {
+            return false;
+        }
+        if let Some(port) = self.port
+            && node.node_port != Some(port)
+            && node.metrics_port != Some(port)
+            && node.rpc_socket_addr.port() != port
+        {
+            return false;
+        }
+        true
+    }
+}
+
 /// The struct that is written to the fs.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 struct NodeRegistry {
@@ -169,11 +424,17 @@ impl NodeRegistry {
             })?;
         }
 
+        // Write to a temporary file first and rename it, so readers never see a partial registry
         let json = serde_json::to_string(self)?;
-        let mut file = std::fs::File::create(self.save_path.clone())
+        let tmp_path = path.with_extension("json.tmp");
+        let mut file = std::fs::File::create(&tmp_path)
             .inspect_err(|err| error!("Error creating node registry file: {err:?}"))?;
         file.write_all(json.as_bytes())
             .inspect_err(|err| error!("Error writing to node registry: {err:?}"))?;
+        file.sync_all()
+            .inspect_err(|err| error!("Error syncing node registry: {err:?}"))?;
+        std::fs::rename(&tmp_path, path)
+            .inspect_err(|err| error!("Error replacing node registry: {err:?}"))?;
 
         Ok(())
     }
@@ -227,6 +488,14 @@ impl NodeRegistry {
     }
 }
 
+/// The modification time of the registry file, if it exists.
+async fn file_modified(path: &Path) -> Option<SystemTime> {
+    tokio::fs::metadata(path)
+        .await
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
 pub fn get_local_node_registry_path() -> Result<PathBuf> {
     let path = dirs_next::data_dir()
         .ok_or_else(|| {
@@ -241,3 +510,116 @@ pub fn get_local_node_registry_path() -> Result<PathBuf> {
     }
     Ok(path)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(service_name: &str, status: &str, version: &str, node_port: u16) -> NodeServiceData {
+        serde_json::from_value(serde_json::json!({
+            "schema_version": 1,
+            "antnode_path": "/usr/bin/antnode",
+            "auto_restart": false,
+            "connected_peers": null,
+            "data_dir_path": "/home/user/.local/share/autonomi/node/1",
+            "evm_network": "ArbitrumSepoliaTest",
+            "initial_peers_config": {
+                "first": false,
+                "local": false,
+                "addrs": [],
+                "network_contacts_url": [],
+                "ignore_cache": false,
+                "bootstrap_cache_dir": null
+            },
+            "listen_addr": null,
+            "log_dir_path": "/home/user/.local/share/autonomi/node/1/logs",
+            "log_format": null,
+            "max_archived_log_files": null,
+            "max_log_files": null,
+            "metrics_port": 8080,
+            "network_id": 1,
+            "node_ip": null,
+            "node_port": node_port,
+            "no_upnp": false,
+            "number": 1,
+            "peer_id": null,
+            "pid": null,
+            "relay": false,
+            "rewards_address": "0x1234567890123456789012345678901234567890",
+            "reward_balance": null,
+            "rpc_socket_addr": "127.0.0.1:8000",
+            "service_name": service_name,
+            "status": status,
+            "user": null,
+            "user_mode": true,
+            "version": version
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn node_filter_matches_every_criteria() {
+        let node = node("antnode1", "Running", "0.4.0", 12000);
+
+        assert!(NodeFilter::new().matches(&node));
+        assert!(
+            NodeFilter::new()
+                .with_status(ServiceStatus::Running)
+                .with_version("0.4.0")
+                .matches(&node)
+        );
+        assert!(
+            !NodeFilter::new()
+                .with_status(ServiceStatus::Running)
+                .with_version("0.3.0")
+                .matches(&node)
+        );
+        assert!(
+            !NodeFilter::new()
+                .with_status(ServiceStatus::Stopped)
+                .matches(&node)
+        );
+    }
+
+    #[test]
+    fn node_filter_matches_any_port_of_the_node() {
+        let node = node("antnode1", "Running", "0.4.0", 12000);
+
+        assert!(NodeFilter::new().with_port(12000).matches(&node));
+        assert!(NodeFilter::new().with_port(8080).matches(&node));
+        assert!(NodeFilter::new().with_port(8000).matches(&node));
+        assert!(!NodeFilter::new().with_port(12001).matches(&node));
+    }
+
+    #[tokio::test]
+    async fn subscribers_see_the_changes_saved_by_other_processes() {
+        let tmp_dir = assert_fs::TempDir::new().unwrap();
+        let path = tmp_dir.path().join("node_registry.json");
+        let manager = NodeRegistryManager::load(&path).await.unwrap();
+        manager
+            .push_node(node("antnode1", "Running", "0.4.0", 12000))
+            .await;
+        manager.save().await.unwrap();
+        let mut changes = manager.subscribe();
+        let _ = changes.borrow_and_update();
+
+        // Let the modification time of the file change, even on coarse filesystems
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+        // Another process, e.g. antctl, stops the node
+        let other = NodeRegistryManager::load(&path).await.unwrap();
+        let updated = other
+            .update_nodes(&NodeFilter::new(), |node| {
+                node.status = ServiceStatus::Stopped
+            })
+            .await
+            .unwrap();
+        assert_eq!(updated, 1);
+
+        tokio::time::timeout(Duration::from_secs(10), changes.changed())
+            .await
+            .expect("the change shall be noticed")
+            .unwrap();
+        let node = manager.node_by_service_name("antnode1").await.unwrap();
+        assert_eq!(node.status, ServiceStatus::Stopped);
+    }
+}
@@ -10,15 +10,17 @@ use crate::error::{Error, Result};
 use ant_protocol::{
     CLOSE_GROUP_SIZE,
     antnode_proto::{
-        NetworkInfoRequest, NodeInfoRequest, RecordAddressesRequest, RestartRequest, StopRequest,
-        UpdateLogLevelRequest, UpdateRequest, ant_node_client::AntNodeClient,
+        BehaviourHealthRequest, HibernateRequest, NetworkInfoRequest, NodeInfoRequest,
+        ReachabilityRequest, RecordAddressesRequest, RecordStoreStatsRequest, ReplicateRequest,
+        RestartRequest, ResumeRequest, SetLogLevelRequest, StopRequest, UpdateLogLevelRequest,
+        UpdateRequest, VerifyRecordsRequest, ant_node_client::AntNodeClient, reachability_response,
     },
 };
 use async_trait::async_trait;
 use libp2p::{Multiaddr, PeerId, kad::RecordKey};
-use std::{net::SocketAddr, path::PathBuf, str::FromStr};
+use std::{collections::BTreeMap, net::SocketAddr, path::PathBuf, str::FromStr};
 use tokio::time::Duration;
-use tonic::Request;
+use tonic::{Code, Request};
 use tracing::error;
 
 #[derive(Debug, Clone)]
@@ -43,16 +45,92 @@ pub struct RecordAddress {
     pub key: RecordKey,
 }
 
+/// Health of each of the node's networking behaviours.
+/// The failure counts are since the node started.
+#[derive(Debug, Clone, Default)]
+pub struct BehaviourHealth {
+    pub peers_in_routing_table: u64,
+    pub non_empty_kbuckets: u64,
+    pub ongoing_kad_queries: u64,
+    pub inflight_outbound_requests: u64,
+    pub outbound_request_failures: u64,
+    pub inbound_request_failures: u64,
+    pub identify_received: u64,
+    pub identify_errors: u64,
+}
+
+/// Outcome of the node's continuous reachability check.
+#[derive(Debug, Clone, Default)]
+pub struct Reachability {
+    /// Whether enough peers dialed the node directly in the last round of the check, `None` if no
+    /// round has completed yet
+    pub reachable: Option<bool>,
+    pub incoming_peers: u64,
+    /// The IP version most of these peers dialed the node over, `IPv4` or `IPv6`
+    pub address_family: Option<String>,
+    /// Whether the node advertises relayed addresses
+    pub relayed: bool,
+    /// Progress of the current round towards passing the check, from 0 to 100
+    pub progress: u32,
+    pub rounds: u64,
+}
+
+/// Statistics of the records held by the node.
+#[derive(Debug, Clone, Default)]
+pub struct RecordStoreStats {
+    /// The number of records, keyed by data type
+    pub records_per_type: BTreeMap<String, u64>,
+    pub total_bytes: u64,
+    /// The number of records, keyed by the ilog2 of their distance from the node
+    pub records_per_distance: BTreeMap<u32, u64>,
+    /// A random sample of the keys of the held records
+    pub sample: Vec<RecordKey>,
+}
+
+/// The outcome of sampling the records held by the node, re-hashing them from disk and
+/// cross-checking some of them with the other holders of their close group.
+#[derive(Debug, Clone, Default)]
+pub struct RecordsVerification {
+    pub sampled: u32,
+    /// The sampled records missing, unreadable or not matching their address on disk
+    pub corrupted: Vec<RecordKey>,
+    pub cross_checked: u32,
+    /// The cross-checked records held with a different content by another holder
+    pub diverging: Vec<RecordKey>,
+    /// The cross-checked records that no other holder returned
+    pub unconfirmed: Vec<RecordKey>,
+}
+
+impl RecordsVerification {
+    /// The number of sampled records found intact on disk.
+    pub fn intact(&self) -> u32 {
+        self.sampled.saturating_sub(self.corrupted.len() as u32)
+    }
+}
+
 #[async_trait]
 pub trait RpcActions: Sync {
     async fn node_info(&self) -> Result<NodeInfo>;
     async fn network_info(&self) -> Result<NetworkInfo>;
     async fn record_addresses(&self) -> Result<Vec<RecordAddress>>;
+    async fn behaviour_health(&self) -> Result<BehaviourHealth>;
+    async fn reachability(&self) -> Result<Reachability>;
+    async fn record_store_stats(&self, sample_size: u32) -> Result<RecordStoreStats>;
     async fn node_restart(&self, delay_millis: u64, retain_peer_id: bool) -> Result<()>;
     async fn node_stop(&self, delay_millis: u64) -> Result<()>;
     async fn node_update(&self, delay_millis: u64) -> Result<()>;
     async fn is_node_connected_to_network(&self, timeout: Duration) -> Result<()>;
     async fn update_log_level(&self, log_levels: String) -> Result<()>;
+    async fn set_log_level(&self, target: String, level: String) -> Result<()>;
+    async fn node_hibernate(&self) -> Result<()>;
+    async fn node_resume(&self) -> Result<()>;
+    /// Returns the peers the record was pushed to, or `None` if the node does not hold it.
+    async fn replicate_record(&self, address: RecordAddress) -> Result<Option<Vec<PeerId>>>;
+    async fn verify_records(
+        &self,
+        sample_size: u32,
+        cross_check_count: u32,
+    ) -> Result<RecordsVerification>;
 }
 
 #[derive(Debug, Clone)]
@@ -188,6 +266,86 @@ impl RpcActions for RpcClient {
         Ok(record_addresses)
     }
 
+    async fn behaviour_health(&self) -> Result<BehaviourHealth> {
+        let mut client = self.connect_with_retry().await?;
+        let response = client
+            .behaviour_health(Request::new(BehaviourHealthRequest {}))
+            .await
+            .map_err(|e| {
+                error!("Could not obtain behaviour health through RPC: {e:?}");
+                Error::RpcBehaviourHealthError(e.to_string())
+            })?;
+        let health_resp = response.get_ref();
+
+        let mut health = BehaviourHealth::default();
+        if let Some(kademlia) = &health_resp.kademlia {
+            health.peers_in_routing_table = kademlia.peers_in_routing_table;
+            health.non_empty_kbuckets = kademlia.non_empty_kbuckets;
+            health.ongoing_kad_queries = kademlia.ongoing_queries;
+        }
+        if let Some(request_response) = &health_resp.request_response {
+            health.inflight_outbound_requests = request_response.inflight_outbound_requests;
+            health.outbound_request_failures = request_response.outbound_failures;
+            health.inbound_request_failures = request_response.inbound_failures;
+        }
+        if let Some(identify) = &health_resp.identify {
+            health.identify_received = identify.received;
+            health.identify_errors = identify.errors;
+        }
+        Ok(health)
+    }
+
+    async fn reachability(&self) -> Result<Reachability> {
+        let mut client = self.connect_with_retry().await?;
+        let response = client
+            .reachability(Request::new(ReachabilityRequest {}))
+            .await
+            .map_err(|e| {
+                error!("Could not obtain the reachability through RPC: {e:?}");
+                Error::RpcReachabilityError(e.to_string())
+            })?
+            .into_inner();
+
+        let reachable = match reachability_response::Status::from_i32(response.status) {
+            Some(reachability_response::Status::Reachable) => Some(true),
+            Some(reachability_response::Status::Unreachable) => Some(false),
+            Some(reachability_response::Status::NotChecked) | None => None,
+        };
+        let address_family =
+            match reachability_response::AddressFamily::from_i32(response.address_family) {
+                Some(reachability_response::AddressFamily::Ipv4) => Some("IPv4".to_string()),
+                Some(reachability_response::AddressFamily::Ipv6) => Some("IPv6".to_string()),
+                Some(reachability_response::AddressFamily::Unknown) | None => None,
+            };
+        Ok(Reachability {
+            reachable,
+            incoming_peers: response.incoming_peers,
+            address_family,
+            relayed: response.relayed,
+            progress: response.progress,
+            rounds: response.rounds,
+        })
+    }
+
+    async fn record_store_stats(&self, sample_size: u32) -> Result<RecordStoreStats> {
+        let mut client = self.connect_with_retry().await?;
+        let response = client
+            .record_store_stats(Request::new(RecordStoreStatsRequest { sample_size }))
+            .await
+            .map_err(|e| {
+                error!("Could not obtain record store stats through RPC: {e:?}");
+                Error::RpcRecordStoreStatsError(e.to_string())
+            })?
+            .into_inner();
+
+        Ok(RecordStoreStats {
+            records_per_type: response.records_per_type.into_iter().collect(),
+            total_bytes: response.total_bytes,
+            records_per_distance: response.records_per_distance.into_iter().collect(),
+            sample: response.sample.into_iter().map(RecordKey::from).collect(),
+        })
+    }
+
     async fn node_restart(&self, delay_millis: u64, retain_peer_id: bool) -> Result<()> {
         let mut client = self.connect_with_retry().await?;
         let _response = client
@@ -285,4 +443,101 @@ impl RpcActions for RpcClient {
             })?;
         Ok(())
     }
+
+    async fn set_log_level(&self, target: String, level: String) -> Result<()> {
+        let mut client = self.connect_with_retry().await?;
+        let _response = client
+            .set_log_level(Request::new(SetLogLevelRequest { target, level }))
+            .await
+            .map_err(|e| {
+                error!("Could not set the log level of the node through RPC: {e:?}");
+                Error::RpcSetLogLevelError(e.to_string())
+            })?;
+        Ok(())
+    }
+
+    async fn node_hibernate(&self) -> Result<()> {
+        let mut client = self.connect_with_retry().await?;
+        let _response = client
+            .hibernate(Request::new(HibernateRequest {}))
+            .await
+            .map_err(|e| {
+                error!("Could not put node into hibernation through RPC: {e:?}");
+                Error::RpcNodeHibernateError(e.to_string())
+            })?;
+        Ok(())
+    }
+
+    async fn node_resume(&self) -> Result<()> {
+        let mut client = self.connect_with_retry().await?;
+        let _response = client
+            .resume(Request::new(ResumeRequest {}))
+            .await
+            .map_err(|e| {
+                error!("Could not resume node from hibernation through RPC: {e:?}");
+                Error::RpcNodeResumeError(e.to_string())
+            })?;
+        Ok(())
+    }
+
+    async fn replicate_record(&self, address: RecordAddress) -> Result<Option<Vec<PeerId>>> {
+        let mut client = self.connect_with_retry().await?;
+        let response = match client
+            .replicate(Request::new(ReplicateRequest {
+                address: address.key.to_vec(),
+            }))
+            .await
+        {
+            Ok(response) => response,
+            Err(status) if status.code() == Code::NotFound => return Ok(None),
+            Err(e) => {
+                error!("Could not replicate record through RPC: {e:?}");
+                return Err(Error::RpcReplicateRecordError(e.to_string()));
+            }
+        };
+        let mut replicated_to = vec![];
+        for bytes in response.get_ref().replicated_to.iter() {
+            replicated_to.push(PeerId::from_bytes(bytes)?);
+        }
+        Ok(Some(replicated_to))
+    }
+
+    async fn verify_records(
+        &self,
+        sample_size: u32,
+        cross_check_count: u32,
+    ) -> Result<RecordsVerification> {
+        let mut client = self.connect_with_retry().await?;
+        let response = client
+            .verify_records(Request::new(VerifyRecordsRequest {
+                sample_size,
+                cross_check_count,
+            }))
+            .await
+            .map_err(|e| {
+                error!("Could not verify records through RPC: {e:?}");
+                Error::RpcVerifyRecordsError(e.to_string())
+            })?
+            .into_inner();
+
+        Ok(RecordsVerification {
+            sampled: response.sampled,
+            corrupted: response
+                .corrupted
+                .into_iter()
+                .map(RecordKey::from)
+                .collect(),
+            cross_checked: response.cross_checked,
+            diverging: response
+                .diverging
+                .into_iter()
+                .map(RecordKey::from)
+                .collect(),
+            unconfirmed: response
+                .unconfirmed
+                .into_iter()
+                .map(RecordKey::from)
+                .collect(),
+        })
+    }
 }
@@ -0,0 +1,149 @@
+// Copyright (C) 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Unit files of nodes supervised by systemd.
+//!
+//! `antnode` notifies systemd once it has started and keeps pinging the watchdog of its unit while
+//! its networking loop is responsive. A node which hangs is then restarted by systemd itself,
+//! rather than being found out when `antctl` next polls it.
+
+use service_manager::ServiceInstallCtx;
+
+/// The watchdog timeout of the units of nodes.
+pub const NODE_WATCHDOG_SECS: u64 = 120;
+
+/// Time to wait before restarting a node which failed.
+const NODE_RESTART_SECS: u64 = 10;
+
+/// Whether systemd manages the services of this host.
+pub fn is_systemd_available() -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        use service_manager::{ServiceManager, SystemdServiceManager};
+
+        SystemdServiceManager::default()
+            .available()
+            .inspect_err(|err| debug!("Could not determine whether systemd is available: {err}"))
+            .unwrap_or(false)
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        false
+    }
+}
+
+/// The content of the `Type=notify` unit file of a node service, with a watchdog of
+/// `watchdog_secs` seconds and restarts on failure.
+pub fn node_unit_contents(
+    install_ctx: &ServiceInstallCtx,
+    user_mode: bool,
+    watchdog_secs: u64,
+) -> String {
+    let exec_start = std::iter::once(install_ctx.program.as_os_str())
+        .chain(install_ctx.args.iter().map(|arg| arg.as_os_str()))
+        .map(|arg| quote(&arg.to_string_lossy()))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut service = vec![
+        "Type=notify".to_string(),
+        "NotifyAccess=main".to_string(),
+        format!("ExecStart={exec_start}"),
+        "Restart=on-failure".to_string(),
+        format!("RestartSec={NODE_RESTART_SECS}"),
+        format!("WatchdogSec={watchdog_secs}"),
+    ];
+    if !user_mode && let Some(user) = &install_ctx.username {
+        service.push(format!("User={user}"));
+    }
+    if let Some(working_directory) = &install_ctx.working_directory {
+        service.push(format!(
+            "WorkingDirectory={}",
+            working_directory.to_string_lossy()
+        ));
+    }
+    for (key, value) in install_ctx.environment.iter().flatten() {
+        service.push(format!("Environment={}", quote(&format!("{key}={value}"))));
+    }
+
+    let wanted_by = if user_mode {
+        "default.target"
+    } else {
+        "multi-user.target"
+    };
+    format!(
+        "[Unit]\nDescription={}\nWants=network-online.target\nAfter=network-online.target\n\n\
+        [Service]\n{}\n\n[Install]\nWantedBy={wanted_by}\n",
+        install_ctx.label,
+        service.join("\n")
+    )
+}
+
+/// Quote a value for a unit file, escaping the specifiers and variables systemd would expand.
+fn quote(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('%', "%%")
+        .replace('$', "$$");
+    format!("\"{escaped}\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use service_manager::RestartPolicy;
+    use std::{ffi::OsString, path::PathBuf};
+
+    #[test]
+    fn test_node_unit_contents() {
+        let install_ctx = ServiceInstallCtx {
+            args: vec![
+                OsString::from("--rpc"),
+                OsString::from("127.0.0.1:8081"),
+                OsString::from("--root-dir"),
+                OsString::from("/var/antctl/services/antnode1"),
+            ],
+            autostart: true,
+            contents: None,
+            environment: Some(vec![("ANT_LOG".to_string(), "all".to_string())]),
+            label: "antnode1".parse().unwrap(),
+            program: PathBuf::from("/var/antctl/services/antnode1/antnode"),
+            restart_policy: RestartPolicy::OnSuccess { delay_secs: None },
+            username: Some("ant".to_string()),
+            working_directory: None,
+        };
+
+        assert_eq!(
+            node_unit_contents(&install_ctx, false, 120),
+            "[Unit]
+Description=antnode1
+Wants=network-online.target
+After=network-online.target
+
+[Service]
+Type=notify
+NotifyAccess=main
+ExecStart=\"/var/antctl/services/antnode1/antnode\" \"--rpc\" \"127.0.0.1:8081\" \"--root-dir\" \"/var/antctl/services/antnode1\"
+Restart=on-failure
+RestartSec=10
+WatchdogSec=120
+User=ant
+Environment=\"ANT_LOG=all\"
+
+[Install]
+WantedBy=multi-user.target
+"
+        );
+    }
+
+    #[test]
+    fn test_quote_escapes_expansions() {
+        assert_eq!(quote(r#"a "b" 100% $HOME"#), r#""a \"b\" 100%% $$HOME""#);
+    }
+}
@@ -0,0 +1,558 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Swift and Kotlin bindings for the autonomi client, generated with uniffi.
+//!
+//! Addresses, data maps and keys cross the FFI boundary as hex strings. Every network operation
+//! takes an optional [`CancellationToken`], so that an app can stop it when the OS is about to
+//! suspend a background task. Files are uploaded and downloaded by streaming them from and to
+//! disk, keeping the memory footprint of large files low.
+
+// Allow unused_async as uniffi exports methods as async for the foreign languages
+#![allow(clippy::unused_async)]
+
+use autonomi::chunk::DataMapChunk;
+use autonomi::client::payment::{BulkPaymentOption, PaymentOption};
+use autonomi::data::DataAddress;
+use autonomi::register::RegisterAddress;
+use autonomi::{
+    BootstrapConfig, ClientConfig, ClientOperatingStrategy, ConnectionBudget, Multiaddr, Network,
+    SecretKey,
+};
+use std::future::Future;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::sync::watch;
+
+uniffi::setup_scaffolding!();
+
+/// Errors returned to Swift and Kotlin.
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+#[uniffi(flat_error)]
+pub enum ClientError {
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+    #[error("Failed to connect to the network: {0}")]
+    Connect(String),
+    #[error("Network operation failed: {0}")]
+    Network(String),
+    #[error("The operation was cancelled")]
+    Cancelled,
+}
+
+/// Describe an error along with its sources, which the foreign languages can't walk.
+fn describe<E: std::error::Error>(err: E) -> String {
+    let mut description = err.to_string();
+    let mut source = err.source();
+    while let Some(err) = source {
+        description.push_str(&format!("\n Caused by: {err}"));
+        source = err.source();
+    }
+    description
+}
+
+fn invalid<E: std::error::Error>(what: &str) -> impl FnOnce(E) -> ClientError {
+    move |err| ClientError::InvalidInput(format!("{what}: {}", describe(err)))
+}
+
+fn network_error<E: std::error::Error>(err: E) -> ClientError {
+    ClientError::Network(describe(err))
+}
+
+/// Cancels the operations it is passed to, from any thread.
+///
+/// Typically cancelled from the expiration handler of an iOS background task, or when an Android
+/// `WorkManager` worker is stopped.
+#[derive(uniffi::Object)]
+pub struct CancellationToken {
+    sender: watch::Sender<bool>,
+}
+
+#[uniffi::export]
+impl CancellationToken {
+    #[uniffi::constructor]
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self {
+            sender: watch::Sender::new(false),
+        })
+    }
+
+    /// Cancel the operations using this token. Operations started with it later fail right away.
+    pub fn cancel(&self) {
+        self.sender.send_replace(true);
+    }
+
+    /// Whether [`CancellationToken::cancel`] was called.
+    pub fn is_cancelled(&self) -> bool {
+        *self.sender.borrow()
+    }
+}
+
+impl CancellationToken {
+    async fn cancelled(&self) {
+        let mut receiver = self.sender.subscribe();
+        let _ = receiver.wait_for(|cancelled| *cancelled).await;
+    }
+}
+
+/// Run `operation` until it completes or the token is cancelled. Dropping the operation on
+/// cancellation stops its network requests.
+async fn cancellable<T>(
+    cancel: Option<Arc<CancellationToken>>,
+    operation: impl Future<Output = Result<T, ClientError>>,
+) -> Result<T, ClientError> {
+    let Some(cancel) = cancel else {
+        return operation.await;
+    };
+    tokio::select! {
+        result = operation => result,
+        () = cancel.cancelled() => Err(ClientError::Cancelled),
+    }
+}
+
+/// Configuration of a client on a constrained device.
+#[derive(uniffi::Record)]
+pub struct MobileConfig {
+    /// Connect to a local network instead of the main network
+    #[uniffi(default = false)]
+    pub local: bool,
+    /// Peers to bootstrap from, the default bootstrap peers when empty
+    #[uniffi(default = [])]
+    pub peers: Vec<String>,
+    /// Directory in the app sandbox to cache peers, chunks and payments in. Nothing is cached
+    /// when not set.
+    #[uniffi(default = None)]
+    pub cache_dir: Option<String>,
+    /// Maximum number of connections being dialed at once
+    #[uniffi(default = None)]
+    pub max_concurrent_dials: Option<u32>,
+    /// Maximum number of concurrent request streams over all peers
+    #[uniffi(default = None)]
+    pub max_concurrent_streams: Option<u64>,
+    /// Maximum number of concurrent streams to a single peer
+    #[uniffi(default = None)]
+    pub max_streams_per_peer: Option<u32>,
+}
+
+impl MobileConfig {
+    fn into_client_config(self) -> Result<ClientConfig, ClientError> {
+        let initial_peers = self
+            .peers
+            .iter()
+            .map(|peer| Multiaddr::from_str(peer))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(invalid("peer address"))?;
+        let evm_network = Network::new(self.local).map_err(invalid("EVM network"))?;
+
+        let mut bootstrap_config = BootstrapConfig {
+            local: self.local,
+            initial_peers,
+            ..Default::default()
+        };
+        let mut strategy = ClientOperatingStrategy::default();
+        match self.cache_dir.map(PathBuf::from) {
+            Some(cache_dir) => {
+                bootstrap_config.cache_dir = cache_dir.join("bootstrap_cache");
+                strategy.chunk_cache_dir = Some(cache_dir.join("chunk_cache"));
+            }
+            None => {
+                bootstrap_config.disable_cache_reading = true;
+                bootstrap_config.disable_cache_writing = true;
+                strategy.chunk_cache_enabled = false;
+            }
+        }
+
+        Ok(ClientConfig {
+            bootstrap_config,
+            evm_network,
+            network_id: None,
+            strategy,
+            tcp_fallback: false,
+            connection_budget: ConnectionBudget {
+                max_concurrent_dials: self.max_concurrent_dials,
+                max_concurrent_streams: self.max_concurrent_streams.map(|n| n as usize),
+                max_streams_per_peer: self.max_streams_per_peer,
+            },
+        })
+    }
+}
+
+/// A wallet paying for uploads.
+#[derive(uniffi::Object)]
+pub struct Wallet(autonomi::Wallet);
+
+#[uniffi::export]
+impl Wallet {
+    /// The address of the wallet, as a hex string.
+    pub fn address(&self) -> String {
+        self.0.address().to_string()
+    }
+}
+
+/// A client of the Autonomi Network.
+#[derive(uniffi::Object)]
+pub struct Client(autonomi::Client);
+
+#[uniffi::export(async_runtime = "tokio")]
+impl Client {
+    /// Connect to the main network with the default configuration.
+    #[uniffi::constructor]
+    pub async fn init() -> Result<Arc<Self>, ClientError> {
+        let client = autonomi::Client::init()
+            .await
+            .map_err(|err| ClientError::Connect(describe(err)))?;
+        Ok(Arc::new(Self(client)))
+    }
+
+    /// Connect to a local network.
+    #[uniffi::constructor]
+    pub async fn init_local() -> Result<Arc<Self>, ClientError> {
+        let client = autonomi::Client::init_local()
+            .await
+            .map_err(|err| ClientError::Connect(describe(err)))?;
+        Ok(Arc::new(Self(client)))
+    }
+
+    /// Connect to the network with the given configuration.
+    #[uniffi::constructor]
+    pub async fn init_with_config(config: MobileConfig) -> Result<Arc<Self>, ClientError> {
+        let client = autonomi::Client::init_with_config(config.into_client_config()?)
+            .await
+            .map_err(|err| ClientError::Connect(describe(err)))?;
+        Ok(Arc::new(Self(client)))
+    }
+
+    /// Load the wallet of the given private key, on the EVM network of the client.
+    pub fn wallet_from_private_key(&self, private_key: String) -> Result<Arc<Wallet>, ClientError> {
+        let wallet =
+            autonomi::Wallet::new_from_private_key(self.0.evm_network().clone(), &private_key)
+                .map_err(invalid("private key"))?;
+        Ok(Arc::new(Wallet(wallet)))
+    }
+
+    /// Fetch public data, in memory. Use [`Client::file_download_public`] for large files.
+    pub async fn data_get_public(
+        &self,
+        address: String,
+        cancel: Option<Arc<CancellationToken>>,
+    ) -> Result<Vec<u8>, ClientError> {
+        let address = DataAddress::from_hex(&address).map_err(invalid("data address"))?;
+        cancellable(cancel, async {
+            let data = self
+                .0
+                .data_get_public(&address)
+                .await
+                .map_err(network_error)?;
+            Ok(data.to_vec())
+        })
+        .await
+    }
+
+    /// Fetch private data from its hex encoded data map, in memory. Use [`Client::file_download`]
+    /// for large files.
+    pub async fn data_get(
+        &self,
+        data_map: String,
+        cancel: Option<Arc<CancellationToken>>,
+    ) -> Result<Vec<u8>, ClientError> {
+        let data_map = DataMapChunk::from_hex(&data_map).map_err(invalid("data map"))?;
+        cancellable(cancel, async {
+            let data = self.0.data_get(&data_map).await.map_err(network_error)?;
+            Ok(data.to_vec())
+        })
+        .await
+    }
+
+    /// Upload public data, returning its address.
+    pub async fn data_put_public(
+        &self,
+        data: Vec<u8>,
+        wallet: Arc<Wallet>,
+        cancel: Option<Arc<CancellationToken>>,
+    ) -> Result<String, ClientError> {
+        cancellable(cancel, async {
+            let (_cost, address) = self
+                .0
+                .data_put_public(data.into(), PaymentOption::from(&wallet.0))
+                .await
+                .map_err(network_error)?;
+            Ok(address.to_hex())
+        })
+        .await
+    }
+
+    /// Upload private data, returning its hex encoded data map, which only its holder can
+    /// read the data with.
+    pub async fn data_put(
+        &self,
+        data: Vec<u8>,
+        wallet: Arc<Wallet>,
+        cancel: Option<Arc<CancellationToken>>,
+    ) -> Result<String, ClientError> {
+        cancellable(cancel, async {
+            let (_cost, data_map) = self
+                .0
+                .data_put(data.into(), PaymentOption::from(&wallet.0))
+                .await
+                .map_err(network_error)?;
+            Ok(data_map.to_hex())
+        })
+        .await
+    }
+
+    /// Upload a public file, streamed from disk, returning its address.
+    pub async fn file_upload_public(
+        &self,
+        path: String,
+        wallet: Arc<Wallet>,
+        cancel: Option<Arc<CancellationToken>>,
+    ) -> Result<String, ClientError> {
+        cancellable(cancel, async {
+            let (_cost, address) = self
+                .0
+                .file_content_upload_public(
+                    PathBuf::from(path),
+                    BulkPaymentOption::from(wallet.0.clone()),
+                )
+                .await
+                .map_err(network_error)?;
+            Ok(address.to_hex())
+        })
+        .await
+    }
+
+    /// Download a public file, streamed to `dest_path`.
+    pub async fn file_download_public(
+        &self,
+        address: String,
+        dest_path: String,
+        cancel: Option<Arc<CancellationToken>>,
+    ) -> Result<(), ClientError> {
+        let address = DataAddress::from_hex(&address).map_err(invalid("data address"))?;
+        cancellable(cancel, async {
+            self.0
+                .file_download_public(&address, PathBuf::from(dest_path))
+                .await
+                .map_err(network_error)
+        })
+        .await
+    }
+
+    /// Download a private file from its hex encoded data map, streamed to `dest_path`.
+    pub async fn file_download(
+        &self,
+        data_map: String,
+        dest_path: String,
+        cancel: Option<Arc<CancellationToken>>,
+    ) -> Result<(), ClientError> {
+        let data_map = DataMapChunk::from_hex(&data_map).map_err(invalid("data map"))?;
+        cancellable(cancel, async {
+            self.0
+                .file_download(&data_map, PathBuf::from(dest_path))
+                .await
+                .map_err(network_error)
+        })
+        .await
+    }
+
+    /// Create a register owned by the hex encoded secret key, returning its address.
+    /// The value is at most 32 bytes.
+    pub async fn register_create(
+        &self,
+        owner: String,
+        value: Vec<u8>,
+        wallet: Arc<Wallet>,
+        cancel: Option<Arc<CancellationToken>>,
+    ) -> Result<String, ClientError> {
+        let owner = SecretKey::from_hex(&owner).map_err(invalid("register owner"))?;
+        let value = autonomi::Client::register_value_from_bytes(&value)
+            .map_err(invalid("register value"))?;
+        cancellable(cancel, async {
+            let (_cost, address) = self
+                .0
+                .register_create(&owner, value, PaymentOption::from(&wallet.0))
+                .await
+                .map_err(network_error)?;
+            Ok(address.to_hex())
+        })
+        .await
+    }
+
+    /// Update the value of the register owned by the hex encoded secret key.
+    pub async fn register_update(
+        &self,
+        owner: String,
+        value: Vec<u8>,
+        wallet: Arc<Wallet>,
+        cancel: Option<Arc<CancellationToken>>,
+    ) -> Result<(), ClientError> {
+        let owner = SecretKey::from_hex(&owner).map_err(invalid("register owner"))?;
+        let value = autonomi::Client::register_value_from_bytes(&value)
+            .map_err(invalid("register value"))?;
+        cancellable(cancel, async {
+            self.0
+                .register_update(&owner, value, PaymentOption::from(&wallet.0))
+                .await
+                .map_err(network_error)?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Fetch the current value of the register at the given address.
+    pub async fn register_get(
+        &self,
+        address: String,
+        cancel: Option<Arc<CancellationToken>>,
+    ) -> Result<Vec<u8>, ClientError> {
+        let address = RegisterAddress::from_hex(&address).map_err(invalid("register address"))?;
+        cancellable(cancel, async {
+            let value = self.0.register_get(&address).await.map_err(network_error)?;
+            Ok(value.to_vec())
+        })
+        .await
+    }
+}
+
+/// Derive the hex encoded secret key of the register of the given name, from the hex encoded
+/// secret key of its owner, so that one key can own many registers.
+#[uniffi::export]
+pub fn register_key_from_name(owner: String, name: String) -> Result<String, ClientError> {
+    let owner = SecretKey::from_hex(&owner).map_err(invalid("register owner"))?;
+    Ok(autonomi::Client::register_key_from_name(&owner, &name).to_hex())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn config() -> MobileConfig {
+        MobileConfig {
+            local: false,
+            peers: vec![],
+            cache_dir: None,
+            max_concurrent_dials: None,
+            max_concurrent_streams: None,
+            max_streams_per_peer: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cancellable_without_token_completes() {
+        let result = cancellable(None, async { Ok(42) }).await;
+        assert!(matches!(result, Ok(42)));
+    }
+
+    #[tokio::test]
+    async fn test_cancellable_fails_right_away_once_cancelled() {
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        assert!(cancel.is_cancelled());
+
+        let result: Result<(), _> = cancellable(Some(cancel), std::future::pending()).await;
+        assert!(matches!(result, Err(ClientError::Cancelled)));
+    }
+
+    #[tokio::test]
+    async fn test_cancellable_stops_a_running_operation() {
+        let cancel = CancellationToken::new();
+        let canceller = Arc::clone(&cancel);
+        let _handle = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            canceller.cancel();
+        });
+
+        let result: Result<(), _> = cancellable(Some(cancel), std::future::pending()).await;
+        assert!(matches!(result, Err(ClientError::Cancelled)));
+    }
+
+    #[tokio::test]
+    async fn test_cancellable_keeps_the_result_of_a_completed_operation() {
+        let cancel = CancellationToken::new();
+        let result = cancellable(Some(Arc::clone(&cancel)), async { Ok("done") }).await;
+        assert!(matches!(result, Ok("done")));
+        assert!(!cancel.is_cancelled());
+    }
+
+    #[test]
+    fn test_config_without_cache_dir_disables_caches() {
+        let client_config = MobileConfig {
+            max_concurrent_dials: Some(4),
+            max_concurrent_streams: Some(32),
+            max_streams_per_peer: Some(2),
+            ..config()
+        }
+        .into_client_config()
+        .unwrap();
+
+        assert!(client_config.bootstrap_config.disable_cache_reading);
+        assert!(client_config.bootstrap_config.disable_cache_writing);
+        assert!(!client_config.strategy.chunk_cache_enabled);
+        assert_eq!(
+            client_config.connection_budget.max_concurrent_dials,
+            Some(4)
+        );
+        assert_eq!(
+            client_config.connection_budget.max_concurrent_streams,
+            Some(32)
+        );
+        assert_eq!(
+            client_config.connection_budget.max_streams_per_peer,
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn test_config_caches_in_cache_dir() {
+        let client_config = MobileConfig {
+            cache_dir: Some("/sandbox/cache".to_string()),
+            ..config()
+        }
+        .into_client_config()
+        .unwrap();
+
+        assert_eq!(
+            client_config.bootstrap_config.cache_dir,
+            PathBuf::from("/sandbox/cache/bootstrap_cache")
+        );
+        assert_eq!(
+            client_config.strategy.chunk_cache_dir,
+            Some(PathBuf::from("/sandbox/cache/chunk_cache"))
+        );
+    }
+
+    #[test]
+    fn test_config_rejects_invalid_peers() {
+        let result = MobileConfig {
+            peers: vec!["not a multiaddr".to_string()],
+            ..config()
+        }
+        .into_client_config();
+        assert!(matches!(result, Err(ClientError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_register_key_from_name() {
+        let owner = SecretKey::random().to_hex();
+        let key = register_key_from_name(owner.clone(), "profile".to_string()).unwrap();
+        assert_eq!(
+            register_key_from_name(owner.clone(), "profile".to_string()).unwrap(),
+            key
+        );
+        assert_ne!(
+            register_key_from_name(owner, "settings".to_string()).unwrap(),
+            key
+        );
+        assert!(matches!(
+            register_key_from_name("not hex".to_string(), "profile".to_string()),
+            Err(ClientError::InvalidInput(_))
+        ));
+    }
+}
@@ -590,6 +590,8 @@ export declare class Metadata {
   get size(): bigint
   /** Get the extra metadata */
   get extra(): string | null
+  /** Get the MIME type of the file, if detected on upload */
+  get contentType(): string | null
 }
 export declare class RegisterAddress {
   /** Creates a new RegisterAddress. */
@@ -1581,6 +1581,7 @@ impl TransactionConfig {
     pub fn auto() -> Self {
         Self(autonomi::TransactionConfig {
             max_fee_per_gas: autonomi::MaxFeePerGas::Auto,
+            ..Default::default()
         })
     }
 
@@ -1597,6 +1598,7 @@ impl TransactionConfig {
 
         Ok(Self(autonomi::TransactionConfig {
             max_fee_per_gas: autonomi::MaxFeePerGas::LimitedAuto(value),
+            ..Default::default()
         }))
     }
 
@@ -1605,6 +1607,7 @@ impl TransactionConfig {
     pub fn unlimited() -> Self {
         Self(autonomi::TransactionConfig {
             max_fee_per_gas: autonomi::MaxFeePerGas::Unlimited,
+            ..Default::default()
         })
     }
 
@@ -1621,6 +1624,7 @@ impl TransactionConfig {
 
         Ok(Self(autonomi::TransactionConfig {
             max_fee_per_gas: autonomi::MaxFeePerGas::Custom(value),
+            ..Default::default()
         }))
     }
 }
@@ -2278,6 +2282,8 @@ impl Metadata {
             modified,
             size,
             extra,
+            content_type: None,
+            packed: None,
         }))
     }
 
@@ -2310,6 +2316,12 @@ impl Metadata {
     pub fn extra(&self) -> Option<String> {
         self.0.extra.clone()
     }
+
+    /// Get the MIME type of the file, if detected on upload
+    #[napi(getter)]
+    pub fn content_type(&self) -> Option<String> {
+        self.0.content_type.clone()
+    }
 }
 
 #[napi]
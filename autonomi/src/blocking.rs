@@ -0,0 +1,477 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Synchronous facade of the client API, for applications and scripts which don't run an async
+//! runtime.
+//!
+//! The blocking [`Client`] owns a Tokio runtime, on which it runs the async [`crate::Client`]. Its
+//! methods block the calling thread until the operation completes, so they must not be called from
+//! within an async runtime.
+//!
+//! ```no_run
+//! use autonomi::blocking::Client;
+//! use autonomi::Bytes;
+//! use autonomi::data::DataAddress;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = Client::init()?;
+//! let addr = DataAddress::from_hex(
+//!     "5887aa56ea38ec9eb1a5908f141dca53bf964620dc8c22df9f24f3950ff98189",
+//! )?;
+//! let data: Bytes = client.data_get_public(&addr)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::chunk::DataMapChunk;
+use crate::client::builder::ClientBuilder;
+use crate::client::payment::{BulkPaymentOption, PaymentOption};
+use crate::client::quote::CostError;
+use crate::client::{ConnectError, GetError, PutError};
+use crate::data::DataAddress;
+use crate::files::archive_private::PrivateArchiveDataMap;
+use crate::files::archive_public::ArchiveAddress;
+use crate::files::{DownloadError, PrivateArchive, PublicArchive, UploadError};
+use crate::graph::GraphError;
+use crate::pointer::{PointerError, PointerTarget};
+use crate::register::{RegisterAddress, RegisterError, RegisterValue};
+use crate::scratchpad::ScratchpadError;
+use crate::{
+    AttoTokens, Bytes, Chunk, ChunkAddress, ClientConfig, GraphEntry, GraphEntryAddress, Multiaddr,
+    Pointer, PointerAddress, PublicKey, Scratchpad, ScratchpadAddress, SecretKey, Wallet,
+};
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::runtime::{Handle, Runtime};
+
+/// Error returned when initializing a blocking [`Client`].
+#[derive(Debug, thiserror::Error)]
+pub enum InitError {
+    /// The runtime of the client could not be started.
+    #[error("Failed to start the runtime of the client: {0}")]
+    Runtime(#[from] std::io::Error),
+
+    /// The client could not connect to the network.
+    #[error(transparent)]
+    Connect(#[from] ConnectError),
+}
+
+/// A client for the Autonomi network, whose methods block until they complete.
+///
+/// It is cheap to clone, the clones share the same runtime and connections.
+#[derive(Clone, Debug)]
+pub struct Client {
+    inner: crate::Client,
+    runtime: Arc<ClientRuntime>,
+}
+
+/// The runtime shared by a blocking [`Client`] and its clones.
+///
+/// It is shut down without waiting for its tasks once dropped, as the tasks of the network never
+/// complete on their own, and so that a client can be dropped from within an async runtime.
+#[derive(Debug)]
+struct ClientRuntime {
+    handle: Handle,
+    runtime: Option<Runtime>,
+}
+
+impl ClientRuntime {
+    fn new() -> std::io::Result<Self> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()?;
+        Ok(Self {
+            handle: runtime.handle().clone(),
+            runtime: Some(runtime),
+        })
+    }
+
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.handle.block_on(future)
+    }
+}
+
+impl Drop for ClientRuntime {
+    fn drop(&mut self) {
+        if let Some(runtime) = self.runtime.take() {
+            runtime.shutdown_background();
+        }
+    }
+}
+
+impl Client {
+    /// Connect to the main network with the default configuration, see [`crate::Client::init`].
+    pub fn init() -> Result<Self, InitError> {
+        Self::connect(crate::Client::init())
+    }
+
+    /// Connect to a local network, see [`crate::Client::init_local`].
+    pub fn init_local() -> Result<Self, InitError> {
+        Self::connect(crate::Client::init_local())
+    }
+
+    /// Connect to the alpha network, see [`crate::Client::init_alpha`].
+    pub fn init_alpha() -> Result<Self, InitError> {
+        Self::connect(crate::Client::init_alpha())
+    }
+
+    /// Connect to a network bootstrapping from `peers`, see [`crate::Client::init_with_peers`].
+    pub fn init_with_peers(peers: Vec<Multiaddr>) -> Result<Self, InitError> {
+        Self::connect(crate::Client::init_with_peers(peers))
+    }
+
+    /// Connect to a network with the given configuration, see [`crate::Client::init_with_config`].
+    pub fn init_with_config(config: ClientConfig) -> Result<Self, InitError> {
+        Self::connect(crate::Client::init_with_config(config))
+    }
+
+    /// Connect to a network with the options set on `builder`, see [`crate::Client::builder`].
+    pub fn from_builder(builder: ClientBuilder) -> Result<Self, InitError> {
+        Self::connect(builder.build())
+    }
+
+    fn connect(
+        init: impl Future<Output = Result<crate::Client, ConnectError>>,
+    ) -> Result<Self, InitError> {
+        let runtime = ClientRuntime::new()?;
+        let inner = runtime.block_on(init)?;
+        Ok(Self {
+            inner,
+            runtime: Arc::new(runtime),
+        })
+    }
+
+    /// The async client, for the operations without a blocking equivalent.
+    pub fn inner(&self) -> &crate::Client {
+        &self.inner
+    }
+
+    /// Run `future` on the runtime of the client, blocking until it completes.
+    ///
+    /// ```no_run
+    /// # use autonomi::files::archive_public::ArchiveAddress;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = autonomi::blocking::Client::init()?;
+    /// # let addr = ArchiveAddress::from_hex(
+    /// #     "5887aa56ea38ec9eb1a5908f141dca53bf964620dc8c22df9f24f3950ff98189",
+    /// # )?;
+    /// let archive = client.block_on(client.inner().archive_get_public(&addr))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    // ---------- Data
+
+    /// See [`crate::Client::data_get_public`].
+    pub fn data_get_public(&self, addr: &DataAddress) -> Result<Bytes, GetError> {
+        self.block_on(self.inner.data_get_public(addr))
+    }
+
+    /// See [`crate::Client::data_put_public`].
+    pub fn data_put_public(
+        &self,
+        data: Bytes,
+        payment_option: PaymentOption,
+    ) -> Result<(AttoTokens, DataAddress), PutError> {
+        self.block_on(self.inner.data_put_public(data, payment_option))
+    }
+
+    /// See [`crate::Client::data_get`].
+    pub fn data_get(&self, data_map: &DataMapChunk) -> Result<Bytes, GetError> {
+        self.block_on(self.inner.data_get(data_map))
+    }
+
+    /// See [`crate::Client::data_put`].
+    pub fn data_put(
+        &self,
+        data: Bytes,
+        payment_option: PaymentOption,
+    ) -> Result<(AttoTokens, DataMapChunk), PutError> {
+        self.block_on(self.inner.data_put(data, payment_option))
+    }
+
+    /// See [`crate::Client::data_cost`].
+    pub fn data_cost(&self, data: Bytes) -> Result<AttoTokens, CostError> {
+        self.block_on(self.inner.data_cost(data))
+    }
+
+    // ---------- Files
+
+    /// See [`crate::Client::file_content_upload_public`].
+    pub fn file_content_upload_public(
+        &self,
+        path: PathBuf,
+        payment_option: BulkPaymentOption,
+    ) -> Result<(AttoTokens, DataAddress), UploadError> {
+        self.block_on(self.inner.file_content_upload_public(path, payment_option))
+    }
+
+    /// See [`crate::Client::file_download_public`].
+    pub fn file_download_public(
+        &self,
+        data_addr: &DataAddress,
+        to_dest: PathBuf,
+    ) -> Result<(), DownloadError> {
+        self.block_on(self.inner.file_download_public(data_addr, to_dest))
+    }
+
+    /// See [`crate::Client::file_content_upload`].
+    pub fn file_content_upload(
+        &self,
+        path: PathBuf,
+        payment_option: BulkPaymentOption,
+    ) -> Result<(AttoTokens, DataMapChunk), UploadError> {
+        self.block_on(self.inner.file_content_upload(path, payment_option))
+    }
+
+    /// See [`crate::Client::file_download`].
+    pub fn file_download(
+        &self,
+        data_map: &DataMapChunk,
+        to_dest: PathBuf,
+    ) -> Result<(), DownloadError> {
+        self.block_on(self.inner.file_download(data_map, to_dest))
+    }
+
+    /// See [`crate::Client::dir_upload_public`].
+    pub fn dir_upload_public(
+        &self,
+        dir_path: PathBuf,
+        wallet: &Wallet,
+    ) -> Result<(AttoTokens, ArchiveAddress), UploadError> {
+        self.block_on(self.inner.dir_upload_public(dir_path, wallet))
+    }
+
+    /// See [`crate::Client::dir_content_upload_public`].
+    pub fn dir_content_upload_public(
+        &self,
+        dir_path: PathBuf,
+        payment_option: BulkPaymentOption,
+    ) -> Result<(AttoTokens, PublicArchive), UploadError> {
+        self.block_on(
+            self.inner
+                .dir_content_upload_public(dir_path, payment_option),
+        )
+    }
+
+    /// See [`crate::Client::dir_download_public`].
+    pub fn dir_download_public(
+        &self,
+        archive_addr: &ArchiveAddress,
+        to_dest: PathBuf,
+    ) -> Result<(), DownloadError> {
+        self.block_on(self.inner.dir_download_public(archive_addr, to_dest))
+    }
+
+    /// See [`crate::Client::dir_upload`].
+    pub fn dir_upload(
+        &self,
+        dir_path: PathBuf,
+        wallet: &Wallet,
+    ) -> Result<(AttoTokens, PrivateArchiveDataMap), UploadError> {
+        self.block_on(self.inner.dir_upload(dir_path, wallet))
+    }
+
+    /// See [`crate::Client::dir_content_upload`].
+    pub fn dir_content_upload(
+        &self,
+        dir_path: PathBuf,
+        payment_option: BulkPaymentOption,
+    ) -> Result<(AttoTokens, PrivateArchive), UploadError> {
+        self.block_on(self.inner.dir_content_upload(dir_path, payment_option))
+    }
+
+    /// See [`crate::Client::dir_download`].
+    pub fn dir_download(
+        &self,
+        archive_access: &PrivateArchiveDataMap,
+        to_dest: PathBuf,
+    ) -> Result<(), DownloadError> {
+        self.block_on(self.inner.dir_download(archive_access, to_dest))
+    }
+
+    // ---------- Chunks
+
+    /// See [`crate::Client::chunk_get`].
+    pub fn chunk_get(&self, addr: &ChunkAddress) -> Result<Chunk, GetError> {
+        self.block_on(self.inner.chunk_get(addr))
+    }
+
+    /// See [`crate::Client::chunk_put`].
+    pub fn chunk_put(
+        &self,
+        chunk: &Chunk,
+        payment_option: PaymentOption,
+    ) -> Result<(AttoTokens, ChunkAddress), PutError> {
+        self.block_on(self.inner.chunk_put(chunk, payment_option))
+    }
+
+    // ---------- Pointers
+
+    /// See [`crate::Client::pointer_get`].
+    pub fn pointer_get(&self, address: &PointerAddress) -> Result<Pointer, PointerError> {
+        self.block_on(self.inner.pointer_get(address))
+    }
+
+    /// See [`crate::Client::pointer_create`].
+    pub fn pointer_create(
+        &self,
+        owner: &SecretKey,
+        target: PointerTarget,
+        payment_option: PaymentOption,
+    ) -> Result<(AttoTokens, PointerAddress), PointerError> {
+        self.block_on(self.inner.pointer_create(owner, target, payment_option))
+    }
+
+    /// See [`crate::Client::pointer_update`].
+    pub fn pointer_update(
+        &self,
+        owner: &SecretKey,
+        target: PointerTarget,
+    ) -> Result<(), PointerError> {
+        self.block_on(self.inner.pointer_update(owner, target))
+    }
+
+    // ---------- Scratchpads
+
+    /// See [`crate::Client::scratchpad_get_from_public_key`].
+    pub fn scratchpad_get_from_public_key(
+        &self,
+        public_key: &PublicKey,
+    ) -> Result<Scratchpad, ScratchpadError> {
+        self.block_on(self.inner.scratchpad_get_from_public_key(public_key))
+    }
+
+    /// See [`crate::Client::scratchpad_get`].
+    pub fn scratchpad_get(
+        &self,
+        address: &ScratchpadAddress,
+    ) -> Result<Scratchpad, ScratchpadError> {
+        self.block_on(self.inner.scratchpad_get(address))
+    }
+
+    /// See [`crate::Client::scratchpad_create`].
+    pub fn scratchpad_create(
+        &self,
+        owner: &SecretKey,
+        content_type: u64,
+        initial_data: &Bytes,
+        payment_option: PaymentOption,
+    ) -> Result<(AttoTokens, ScratchpadAddress), ScratchpadError> {
+        self.block_on(self.inner.scratchpad_create(
+            owner,
+            content_type,
+            initial_data,
+            payment_option,
+        ))
+    }
+
+    /// See [`crate::Client::scratchpad_update`].
+    pub fn scratchpad_update(
+        &self,
+        owner: &SecretKey,
+        content_type: u64,
+        data: &Bytes,
+    ) -> Result<(), ScratchpadError> {
+        self.block_on(self.inner.scratchpad_update(owner, content_type, data))
+    }
+
+    // ---------- Graph entries
+
+    /// See [`crate::Client::graph_entry_get`].
+    pub fn graph_entry_get(&self, address: &GraphEntryAddress) -> Result<GraphEntry, GraphError> {
+        self.block_on(self.inner.graph_entry_get(address))
+    }
+
+    /// See [`crate::Client::graph_entry_put`].
+    pub fn graph_entry_put(
+        &self,
+        entry: GraphEntry,
+        payment_option: PaymentOption,
+    ) -> Result<(AttoTokens, GraphEntryAddress), GraphError> {
+        self.block_on(self.inner.graph_entry_put(entry, payment_option))
+    }
+
+    // ---------- Registers
+
+    /// See [`crate::Client::register_get`].
+    pub fn register_get(&self, addr: &RegisterAddress) -> Result<RegisterValue, RegisterError> {
+        self.block_on(self.inner.register_get(addr))
+    }
+
+    /// See [`crate::Client::register_create`].
+    pub fn register_create(
+        &self,
+        owner: &SecretKey,
+        initial_value: RegisterValue,
+        payment_option: PaymentOption,
+    ) -> Result<(AttoTokens, RegisterAddress), RegisterError> {
+        self.block_on(
+            self.inner
+                .register_create(owner, initial_value, payment_option),
+        )
+    }
+
+    /// See [`crate::Client::register_update`].
+    pub fn register_update(
+        &self,
+        owner: &SecretKey,
+        new_value: RegisterValue,
+        payment_option: PaymentOption,
+    ) -> Result<AttoTokens, RegisterError> {
+        self.block_on(self.inner.register_update(owner, new_value, payment_option))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_runtime_runs_futures_to_completion() {
+        let runtime = ClientRuntime::new().unwrap();
+        let handle = runtime.handle.clone();
+        let value = runtime.block_on(async move {
+            // The tasks spawned by the client run on the runtime too
+            handle.spawn(async { 21 * 2 }).await
+        });
+        assert_eq!(value.unwrap(), 42);
+    }
+
+    #[test]
+    fn test_dropping_the_runtime_does_not_wait_for_its_tasks() {
+        let runtime = ClientRuntime::new().unwrap();
+        let _task = runtime.handle.spawn(std::future::pending::<()>());
+        let _blocking_task = runtime
+            .handle
+            .spawn_blocking(|| std::thread::sleep(Duration::from_secs(5)));
+
+        let start = Instant::now();
+        drop(runtime);
+        assert!(start.elapsed() < Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_runtime_can_be_dropped_within_an_async_runtime() {
+        let runtime = Arc::new(ClientRuntime::new().unwrap());
+        let clone = Arc::clone(&runtime);
+        drop(runtime);
+
+        let outer = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        // The last reference of the runtime of a client is dropped from an async context
+        outer.block_on(async move { drop(clone) });
+    }
+}
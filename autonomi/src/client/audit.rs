@@ -0,0 +1,321 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Audit of the records held by the network.
+//!
+//! For every audited record, the close group is asked for a signed statement that it holds the
+//! record, bound to a fresh nonce. The statements are verified and compared to produce a report.
+//! Statements too far from the time of the network, in the past or in the future, are rejected.
+
+use crate::Client;
+use crate::client::config::CHUNK_DOWNLOAD_BATCH_SIZE;
+use crate::networking::{NetworkError, StorageStatement};
+use crate::utils::process_tasks_with_max_concurrency;
+use ant_protocol::{CLOSE_GROUP_SIZE, NetworkAddress};
+use futures::future::join_all;
+use libp2p::PeerId;
+use rand::seq::SliceRandom;
+use std::collections::HashMap;
+use std::time::{Duration, UNIX_EPOCH};
+use xor_name::XorName;
+
+/// How far the timestamp of a storage statement may be from the time of the network.
+const STATEMENT_TIMESTAMP_TOLERANCE: Duration = Duration::from_secs(5 * 60);
+
+/// Audit result of a single record.
+#[derive(Debug, Clone)]
+pub struct RecordAudit {
+    /// Address of the audited record
+    pub address: NetworkAddress,
+    /// Close group peers that returned a valid signed statement
+    pub holders: Vec<(PeerId, StorageStatement)>,
+    /// Close group peers that did not return a valid signed statement
+    pub missing: Vec<PeerId>,
+    /// The content hash, bound to the nonce of the audit, reported by most holders, if any
+    pub content_hash: Option<XorName>,
+    /// Holders reporting a content hash that differs from the majority
+    pub mismatched: Vec<PeerId>,
+}
+
+impl RecordAudit {
+    fn new(
+        address: NetworkAddress,
+        nonce: u64,
+        now: u64,
+        responses: Vec<(PeerId, Option<StorageStatement>)>,
+    ) -> Self {
+        let mut holders = vec![];
+        let mut missing = vec![];
+        for (peer_id, statement) in responses {
+            match statement {
+                Some(statement)
+                    if statement.key == address
+                        && statement.nonce == nonce
+                        && statement.is_timely(now, STATEMENT_TIMESTAMP_TOLERANCE.as_secs())
+                        && statement.verify(&peer_id) =>
+                {
+                    holders.push((peer_id, statement));
+                }
+                Some(_) => {
+                    warn!("Invalid storage statement of {address:?} from {peer_id:?}");
+                    missing.push(peer_id);
+                }
+                None => missing.push(peer_id),
+            }
+        }
+
+        let mut hash_counts: HashMap<XorName, usize> = HashMap::new();
+        for (_, statement) in &holders {
+            *hash_counts.entry(statement.content_hash).or_default() += 1;
+        }
+        let content_hash = hash_counts
+            .into_iter()
+            .max_by_key(|(_, count)| *count)
+            .map(|(hash, _)| hash);
+        let mismatched = holders
+            .iter()
+            .filter(|(_, statement)| Some(statement.content_hash) != content_hash)
+            .map(|(peer_id, _)| *peer_id)
+            .collect();
+
+        Self {
+            address,
+            holders,
+            missing,
+            content_hash,
+            mismatched,
+        }
+    }
+
+    /// Returns true if the record is held by at least one peer and all holders agree on its content.
+    pub fn is_held(&self) -> bool {
+        !self.holders.is_empty() && self.mismatched.is_empty()
+    }
+}
+
+/// Report of an audit over a sample of records.
+#[derive(Debug, Clone, Default)]
+pub struct AuditReport {
+    /// Audit results of the sampled records
+    pub records: Vec<RecordAudit>,
+    /// Sampled records that could not be audited, e.g. when their close group could not be found
+    pub failed: Vec<(NetworkAddress, NetworkError)>,
+}
+
+impl AuditReport {
+    /// Number of sampled records held consistently by the network.
+    pub fn held_count(&self) -> usize {
+        self.records
+            .iter()
+            .filter(|record| record.is_held())
+            .count()
+    }
+
+    /// Returns true if every sampled record was audited and is held consistently.
+    pub fn is_healthy(&self) -> bool {
+        self.failed.is_empty() && self.records.iter().all(RecordAudit::is_held)
+    }
+}
+
+impl Client {
+    /// Audit a random sample of the given records.
+    ///
+    /// Each of the `sample_size` sampled records is checked against its close group: every peer is
+    /// asked for a signed statement that it holds the record, bound to a fresh nonce.
+    pub async fn audit(&self, addresses: &[NetworkAddress], sample_size: usize) -> AuditReport {
+        let sample: Vec<NetworkAddress> = addresses
+            .choose_multiple(&mut rand::thread_rng(), sample_size)
+            .cloned()
+            .collect();
+        info!(
+            "Auditing a sample of {} records out of {}",
+            sample.len(),
+            addresses.len()
+        );
+
+        let tasks = sample.into_iter().map(|address| async move {
+            let result = self.audit_record(address.clone()).await;
+            (address, result)
+        });
+
+        let mut report = AuditReport::default();
+        for (address, result) in
+            process_tasks_with_max_concurrency(tasks, *CHUNK_DOWNLOAD_BATCH_SIZE).await
+        {
+            match result {
+                Ok(record) => report.records.push(record),
+                Err(err) => {
+                    warn!("Failed to audit {address:?}: {err}");
+                    report.failed.push((address, err));
+                }
+            }
+        }
+        report
+    }
+
+    async fn audit_record(&self, address: NetworkAddress) -> Result<RecordAudit, NetworkError> {
+        let peers = self
+            .network
+            .get_closest_peers_with_retries(address.clone(), Some(CLOSE_GROUP_SIZE))
+            .await?;
+        let nonce: u64 = rand::random();
+
+        let responses = join_all(peers.into_iter().map(|peer| {
+            let address = address.clone();
+            async move {
+                let peer_id = peer.peer_id;
+                let statement = self
+                    .network
+                    .get_storage_statement_from_peer(address, peer, nonce)
+                    .await
+                    .unwrap_or_else(|err| {
+                        debug!("Failed to get storage statement from {peer_id:?}: {err}");
+                        None
+                    });
+                (peer_id, statement)
+            }
+        }))
+        .await;
+
+        let now = self
+            .network_time()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let audit = RecordAudit::new(address, nonce, now, responses);
+        debug!(
+            "Audit of {:?}: {} holders, {} missing, {} mismatched",
+            audit.address,
+            audit.holders.len(),
+            audit.missing.len(),
+            audit.mismatched.len()
+        );
+        Ok(audit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libp2p::identity::Keypair;
+
+    const NOW: u64 = 1_700_000_000;
+
+    fn statement(
+        keypair: &Keypair,
+        key: &NetworkAddress,
+        content: &[u8],
+        nonce: u64,
+    ) -> StorageStatement {
+        statement_at(keypair, key, content, nonce, NOW)
+    }
+
+    fn statement_at(
+        keypair: &Keypair,
+        key: &NetworkAddress,
+        content: &[u8],
+        nonce: u64,
+        timestamp: u64,
+    ) -> StorageStatement {
+        let content_hash = StorageStatement::content_hash(content, nonce);
+        let bytes = StorageStatement::bytes_to_sign(key, &content_hash, timestamp, nonce);
+        StorageStatement {
+            key: key.clone(),
+            content_hash,
+            timestamp,
+            nonce,
+            pub_key: keypair.public().encode_protobuf(),
+            signature: keypair.sign(&bytes).expect("signing shall succeed"),
+        }
+    }
+
+    #[test]
+    fn test_record_audit_classifies_peers() {
+        let address = NetworkAddress::from(XorName::random(&mut rand::thread_rng()));
+        let nonce = 42;
+        let keypairs: Vec<_> = (0..4).map(|_| Keypair::generate_ed25519()).collect();
+        let peer_ids: Vec<_> = keypairs
+            .iter()
+            .map(|keypair| PeerId::from(keypair.public()))
+            .collect();
+
+        let responses = vec![
+            (
+                peer_ids[0],
+                Some(statement(&keypairs[0], &address, b"data", nonce)),
+            ),
+            (
+                peer_ids[1],
+                Some(statement(&keypairs[1], &address, b"data", nonce)),
+            ),
+            // replayed statement for an older nonce
+            (
+                peer_ids[2],
+                Some(statement(&keypairs[2], &address, b"data", nonce + 1)),
+            ),
+            (peer_ids[3], None),
+        ];
+
+        let audit = RecordAudit::new(address, nonce, NOW, responses);
+        assert_eq!(audit.holders.len(), 2);
+        assert_eq!(audit.missing, vec![peer_ids[2], peer_ids[3]]);
+        assert_eq!(
+            audit.content_hash,
+            Some(StorageStatement::content_hash(b"data", nonce))
+        );
+        assert!(audit.mismatched.is_empty());
+        assert!(audit.is_held());
+    }
+
+    #[test]
+    fn test_record_audit_detects_mismatched_content() {
+        let address = NetworkAddress::from(XorName::random(&mut rand::thread_rng()));
+        let keypairs: Vec<_> = (0..3).map(|_| Keypair::generate_ed25519()).collect();
+        let responses = keypairs
+            .iter()
+            .zip([b"data", b"data", b"evil"])
+            .map(|(keypair, content)| {
+                (
+                    PeerId::from(keypair.public()),
+                    Some(statement(keypair, &address, content, 1)),
+                )
+            })
+            .collect();
+
+        let audit = RecordAudit::new(address, 1, NOW, responses);
+        assert_eq!(audit.mismatched, vec![PeerId::from(keypairs[2].public())]);
+        assert!(!audit.is_held());
+    }
+
+    #[test]
+    fn test_record_audit_rejects_stale_and_future_statements() {
+        let address = NetworkAddress::from(XorName::random(&mut rand::thread_rng()));
+        let tolerance = STATEMENT_TIMESTAMP_TOLERANCE.as_secs();
+        let keypairs: Vec<_> = (0..3).map(|_| Keypair::generate_ed25519()).collect();
+        let peer_ids: Vec<_> = keypairs
+            .iter()
+            .map(|keypair| PeerId::from(keypair.public()))
+            .collect();
+
+        let responses = keypairs
+            .iter()
+            .zip([NOW - tolerance, NOW - tolerance - 1, NOW + tolerance + 1])
+            .map(|(keypair, timestamp)| {
+                (
+                    PeerId::from(keypair.public()),
+                    Some(statement_at(keypair, &address, b"data", 1, timestamp)),
+                )
+            })
+            .collect();
+
+        let audit = RecordAudit::new(address, 1, NOW, responses);
+        assert_eq!(audit.holders.len(), 1);
+        assert_eq!(audit.holders[0].0, peer_ids[0]);
+        assert_eq!(audit.missing, vec![peer_ids[1], peer_ids[2]]);
+    }
+}
@@ -0,0 +1,509 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Builder of a [`Client`], validating the combination of its options before connecting.
+//!
+//! ```no_run
+//! use autonomi::client::builder::{BootstrapCache, ClientNetwork, RetryPolicy};
+//! use autonomi::Client;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = Client::builder()
+//!     .network(ClientNetwork::Local)
+//!     .peers(vec!["/ip4/127.0.0.1/udp/1234/quic-v1".parse()?])
+//!     .bootstrap_cache(BootstrapCache::Disabled)
+//!     .retry_policy(RetryPolicy {
+//!         failed_uploads: 1,
+//!         ..Default::default()
+//!     })
+//!     .build()
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::Client;
+use crate::client::ConnectError;
+use crate::client::config::{ClientConfig, ClientOperatingStrategy};
+use crate::client::quote::PaymentMode;
+use crate::networking::{ConnectionBudget, Multiaddr, RetryStrategy, multiaddr_is_global};
+use ant_bootstrap::{BootstrapConfig, contacts_fetcher::ALPHANET_CONTACTS};
+use ant_evm::EvmNetwork;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// The id of the main network.
+const MAIN_NETWORK_ID: u8 = 1;
+/// The id of the alpha network.
+const ALPHA_NETWORK_ID: u8 = 2;
+
+/// The network a [`Client`] connects to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ClientNetwork {
+    /// The main network, paying on Arbitrum One
+    #[default]
+    Mainnet,
+    /// The alpha network (Impossible Futures), paying on Arbitrum Sepolia
+    Alpha,
+    /// A network running on the local machine or LAN, with the EVM network set by the environment
+    Local,
+    /// Another network, identified by its id
+    Custom(u8),
+}
+
+impl ClientNetwork {
+    /// The id of the network, `None` to use the current default of the process.
+    pub fn network_id(&self) -> Option<u8> {
+        match self {
+            ClientNetwork::Mainnet | ClientNetwork::Local => None,
+            ClientNetwork::Alpha => Some(ALPHA_NETWORK_ID),
+            ClientNetwork::Custom(network_id) => Some(*network_id),
+        }
+    }
+
+    /// The built-in URLs to fetch the contacts of the network from.
+    pub fn network_contacts_url(&self) -> Vec<String> {
+        match self {
+            ClientNetwork::Alpha => ALPHANET_CONTACTS.iter().map(|s| s.to_string()).collect(),
+            ClientNetwork::Mainnet | ClientNetwork::Local | ClientNetwork::Custom(_) => vec![],
+        }
+    }
+
+    /// The EVM network the network pays on. The one of a local or custom network is read from
+    /// the environment.
+    pub fn evm_network(&self) -> Result<EvmNetwork, ConnectError> {
+        match self {
+            ClientNetwork::Mainnet => Ok(EvmNetwork::default()),
+            ClientNetwork::Alpha => Ok(EvmNetwork::ArbitrumSepoliaTest),
+            ClientNetwork::Local => {
+                EvmNetwork::new(true).map_err(|err| ConnectError::EvmNetworkError(err.to_string()))
+            }
+            ClientNetwork::Custom(network_id) => {
+                ant_evm::utils::get_evm_network(false, Some(*network_id))
+                    .map_err(|err| ConnectError::EvmNetworkError(err.to_string()))
+            }
+        }
+    }
+
+    /// All the settings of the network, see [`NetworkProfile`].
+    pub fn profile(&self) -> Result<NetworkProfile, ConnectError> {
+        Ok(NetworkProfile {
+            network_id: self.network_id(),
+            network_contacts_url: self.network_contacts_url(),
+            evm_network: self.evm_network()?,
+            local: *self == ClientNetwork::Local,
+        })
+    }
+}
+
+impl FromStr for ClientNetwork {
+    type Err = ClientConfigError;
+
+    /// Parse the name of a network, `mainnet`, `alpha` or `local`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "mainnet" => Ok(ClientNetwork::Mainnet),
+            "alpha" => Ok(ClientNetwork::Alpha),
+            "local" => Ok(ClientNetwork::Local),
+            _ => Err(ClientConfigError::UnknownNetwork(s.to_string())),
+        }
+    }
+}
+
+impl std::fmt::Display for ClientNetwork {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientNetwork::Mainnet => write!(f, "mainnet"),
+            ClientNetwork::Alpha => write!(f, "alpha"),
+            ClientNetwork::Local => write!(f, "local"),
+            ClientNetwork::Custom(network_id) => write!(f, "custom network {network_id}"),
+        }
+    }
+}
+
+/// The settings bundled under the name of a network, so that connecting to it only takes its
+/// name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkProfile {
+    /// The id of the network, `None` to use the current default of the process
+    pub network_id: Option<u8>,
+    /// The URLs to fetch the contacts of the network from
+    pub network_contacts_url: Vec<String>,
+    /// The EVM network the network pays on
+    pub evm_network: EvmNetwork,
+    /// Whether the peers of the network run on the local machine or LAN
+    pub local: bool,
+}
+
+/// How the bootstrap cache, holding the peers met in previous sessions, is used.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum BootstrapCache {
+    /// Read and write the cache in its default directory
+    #[default]
+    Enabled,
+    /// Read and write the cache in the given directory
+    Dir(PathBuf),
+    /// Neither read nor write the cache
+    Disabled,
+}
+
+/// How the client retries the operations which failed.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RetryPolicy {
+    /// Number of times the chunks which failed to upload are retried, 0 for no retry.
+    pub failed_uploads: u64,
+    /// The retries of the puts of every data type, overriding the ones of the strategy if set.
+    pub put_retry: Option<RetryStrategy>,
+    /// The retries of the gets of every data type, overriding the ones of the strategy if set.
+    pub get_retry: Option<RetryStrategy>,
+}
+
+/// A combination of options of [`ClientBuilder`] which can't work.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ClientConfigError {
+    /// A local network has no peers with a global address.
+    #[error("The peer {0} has a global address, which peers of a local network can't have")]
+    GlobalPeerOnLocalNetwork(Multiaddr),
+
+    /// The contacts of a local network can't be fetched, its peers must be provided instead.
+    #[error("Network contacts can't be fetched for a local network, provide its peers instead")]
+    ContactsUrlOnLocalNetwork,
+
+    /// The id of a custom network is the one of the main or alpha network.
+    #[error(
+        "The network id {0} is reserved, use ClientNetwork::Mainnet or ClientNetwork::Alpha instead"
+    )]
+    ReservedNetworkId(u8),
+
+    /// A custom network has no built-in contacts, and nothing else to bootstrap from.
+    #[error(
+        "Custom network {0} needs peers, a network contacts URL or the bootstrap cache to bootstrap from"
+    )]
+    NoBootstrapSource(u8),
+
+    /// The name of a network is not one of the known ones.
+    #[error("Unknown network {0}, expected mainnet, alpha or local")]
+    UnknownNetwork(String),
+}
+
+/// Builder of a [`Client`], see [`Client::builder`].
+#[derive(Debug, Clone, Default)]
+pub struct ClientBuilder {
+    network: ClientNetwork,
+    peers: Vec<Multiaddr>,
+    network_contacts_url: Vec<String>,
+    evm_network: Option<EvmNetwork>,
+    strategy: ClientOperatingStrategy,
+    bootstrap_cache: BootstrapCache,
+    retry_policy: RetryPolicy,
+    payment_mode: Option<PaymentMode>,
+    read_only: bool,
+    tcp_fallback: bool,
+    connection_budget: ConnectionBudget,
+}
+
+impl ClientBuilder {
+    /// A builder of a client of the main network, with the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the network to connect to. Defaults to [`ClientNetwork::Mainnet`].
+    pub fn network(mut self, network: ClientNetwork) -> Self {
+        self.network = network;
+        self
+    }
+
+    /// Set the peers to bootstrap from, in addition to the contacts of the network.
+    pub fn peers(mut self, peers: Vec<Multiaddr>) -> Self {
+        self.peers = peers;
+        self
+    }
+
+    /// Set the URLs to fetch the contacts of the network from, instead of its built-in ones.
+    pub fn network_contacts_url(mut self, urls: Vec<String>) -> Self {
+        self.network_contacts_url = urls;
+        self
+    }
+
+    /// Set the EVM network to pay on, instead of the one of the network.
+    pub fn evm_network(mut self, evm_network: EvmNetwork) -> Self {
+        self.evm_network = Some(evm_network);
+        self
+    }
+
+    /// Set the strategy of the data operations.
+    pub fn strategy(mut self, strategy: ClientOperatingStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Set how the bootstrap cache is used. Defaults to [`BootstrapCache::Enabled`].
+    pub fn bootstrap_cache(mut self, bootstrap_cache: BootstrapCache) -> Self {
+        self.bootstrap_cache = bootstrap_cache;
+        self
+    }
+
+    /// Set how failed operations are retried.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Set the payment mode of uploads.
+    pub fn payment_mode(mut self, payment_mode: PaymentMode) -> Self {
+        self.payment_mode = Some(payment_mode);
+        self
+    }
+
+    /// Set whether the client refuses to pay for uploads, see [`Client::init_read_only`].
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Set whether to also dial peers over TCP, for networks where UDP is blocked.
+    pub fn tcp_fallback(mut self, tcp_fallback: bool) -> Self {
+        self.tcp_fallback = tcp_fallback;
+        self
+    }
+
+    /// Set the limits on the concurrent dials and streams of the client.
+    pub fn connection_budget(mut self, connection_budget: ConnectionBudget) -> Self {
+        self.connection_budget = connection_budget;
+        self
+    }
+
+    /// Check the combination of options, and connect to the network.
+    ///
+    /// This will block until [`crate::client::CLOSE_GROUP_SIZE`] peers have been added to the
+    /// routing table, see [`Client::init_with_config`].
+    pub async fn build(self) -> Result<Client, ConnectError> {
+        let retry_failed = self.retry_policy.failed_uploads;
+        let payment_mode = self.payment_mode;
+        let read_only = self.read_only;
+
+        let client = Client::init_with_config(self.into_config()?).await?;
+        let client = client
+            .with_retry_failed(retry_failed)
+            .with_read_only(read_only);
+        Ok(match payment_mode {
+            Some(payment_mode) => client.with_payment_mode(payment_mode),
+            None => client,
+        })
+    }
+
+    /// Check the combination of options, and turn them into the configuration of the client.
+    fn into_config(self) -> Result<ClientConfig, ConnectError> {
+        self.validate()?;
+
+        let local = self.network == ClientNetwork::Local;
+        let network_contacts_url = if self.network_contacts_url.is_empty() {
+            self.network.network_contacts_url()
+        } else {
+            self.network_contacts_url
+        };
+
+        let mut bootstrap_config = BootstrapConfig {
+            initial_peers: self.peers,
+            network_contacts_url,
+            ..BootstrapConfig::new(local)
+        };
+        match self.bootstrap_cache {
+            BootstrapCache::Enabled => {}
+            BootstrapCache::Dir(cache_dir) => bootstrap_config.cache_dir = cache_dir,
+            BootstrapCache::Disabled => {
+                bootstrap_config.disable_cache_reading = true;
+                bootstrap_config.disable_cache_writing = true;
+            }
+        }
+
+        let evm_network = match self.evm_network {
+            Some(evm_network) => evm_network,
+            None => self.network.evm_network()?,
+        };
+
+        let mut strategy = self.strategy;
+        for data_type in [
+            &mut strategy.chunks,
+            &mut strategy.graph_entry,
+            &mut strategy.pointer,
+            &mut strategy.scratchpad,
+        ] {
+            if let Some(put_retry) = self.retry_policy.put_retry {
+                data_type.put_retry = put_retry;
+            }
+            if let Some(get_retry) = self.retry_policy.get_retry {
+                data_type.get_retry = get_retry;
+            }
+        }
+
+        Ok(ClientConfig {
+            bootstrap_config,
+            evm_network,
+            network_id: self.network.network_id(),
+            strategy,
+            tcp_fallback: self.tcp_fallback,
+            connection_budget: self.connection_budget,
+        })
+    }
+
+    fn validate(&self) -> Result<(), ClientConfigError> {
+        match self.network {
+            ClientNetwork::Local => {
+                if let Some(peer) = self.peers.iter().find(|peer| multiaddr_is_global(peer)) {
+                    return Err(ClientConfigError::GlobalPeerOnLocalNetwork(peer.clone()));
+                }
+                if !self.network_contacts_url.is_empty() {
+                    return Err(ClientConfigError::ContactsUrlOnLocalNetwork);
+                }
+            }
+            ClientNetwork::Custom(network_id) => {
+                if network_id == MAIN_NETWORK_ID || network_id == ALPHA_NETWORK_ID {
+                    return Err(ClientConfigError::ReservedNetworkId(network_id));
+                }
+                if self.peers.is_empty()
+                    && self.network_contacts_url.is_empty()
+                    && self.bootstrap_cache == BootstrapCache::Disabled
+                {
+                    return Err(ClientConfigError::NoBootstrapSource(network_id));
+                }
+            }
+            ClientNetwork::Mainnet | ClientNetwork::Alpha => {}
+        }
+        Ok(())
+    }
+}
+
+impl ClientConfig {
+    /// The default configuration of a client of `network`, with the settings of its profile.
+    pub fn for_network(network: ClientNetwork) -> Result<Self, ConnectError> {
+        ClientBuilder::new().network(network).into_config()
+    }
+}
+
+impl Client {
+    /// A builder of a client, to set its options before connecting.
+    ///
+    /// See [`ClientBuilder`].
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn peer(addr: &str) -> Multiaddr {
+        addr.parse().unwrap()
+    }
+
+    fn config_error(builder: ClientBuilder) -> Option<ClientConfigError> {
+        match builder.into_config() {
+            Err(ConnectError::InvalidConfig(err)) => Some(err),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn local_network_should_reject_global_peers_and_contacts() {
+        let global = peer("/ip4/8.8.8.8/udp/1234/quic-v1");
+        let builder = Client::builder().network(ClientNetwork::Local).peers(vec![
+            peer("/ip4/127.0.0.1/udp/1234/quic-v1"),
+            global.clone(),
+        ]);
+        assert_eq!(
+            config_error(builder),
+            Some(ClientConfigError::GlobalPeerOnLocalNetwork(global))
+        );
+
+        let builder = Client::builder()
+            .network(ClientNetwork::Local)
+            .network_contacts_url(vec!["https://example.com/contacts".to_string()]);
+        assert_eq!(
+            config_error(builder),
+            Some(ClientConfigError::ContactsUrlOnLocalNetwork)
+        );
+    }
+
+    #[test]
+    fn custom_network_should_need_a_free_id_and_a_bootstrap_source() {
+        let builder = Client::builder().network(ClientNetwork::Custom(MAIN_NETWORK_ID));
+        assert_eq!(
+            config_error(builder),
+            Some(ClientConfigError::ReservedNetworkId(MAIN_NETWORK_ID))
+        );
+
+        let builder = Client::builder()
+            .network(ClientNetwork::Custom(7))
+            .bootstrap_cache(BootstrapCache::Disabled);
+        assert_eq!(
+            config_error(builder),
+            Some(ClientConfigError::NoBootstrapSource(7))
+        );
+
+        let config = Client::builder()
+            .network(ClientNetwork::Custom(7))
+            .evm_network(EvmNetwork::ArbitrumSepoliaTest)
+            .peers(vec![peer("/ip4/8.8.8.8/udp/1234/quic-v1")])
+            .bootstrap_cache(BootstrapCache::Disabled)
+            .into_config()
+            .unwrap();
+        assert_eq!(config.network_id, Some(7));
+        assert!(!config.bootstrap_config.local);
+        assert!(config.bootstrap_config.disable_cache_reading);
+        assert!(config.bootstrap_config.disable_cache_writing);
+    }
+
+    #[test]
+    fn retry_policy_should_override_the_strategy() {
+        let config = Client::builder()
+            .network(ClientNetwork::Alpha)
+            .retry_policy(RetryPolicy {
+                put_retry: Some(RetryStrategy::Persistent),
+                ..Default::default()
+            })
+            .into_config()
+            .unwrap();
+        assert_eq!(config.network_id, Some(ALPHA_NETWORK_ID));
+        assert!(!config.bootstrap_config.network_contacts_url.is_empty());
+        assert_eq!(config.strategy.chunks.put_retry.attempts(), 10);
+        assert_eq!(config.strategy.scratchpad.put_retry.attempts(), 10);
+        // Gets keep the retries of the default strategy
+        assert_eq!(config.strategy.chunks.get_retry.attempts(), 1);
+    }
+
+    #[test]
+    fn networks_should_be_parsed_by_name_and_bundle_their_settings() {
+        assert_eq!("mainnet".parse(), Ok(ClientNetwork::Mainnet));
+        assert_eq!("Alpha".parse(), Ok(ClientNetwork::Alpha));
+        assert_eq!("local".parse(), Ok(ClientNetwork::Local));
+        assert_eq!(
+            "testnet".parse::<ClientNetwork>(),
+            Err(ClientConfigError::UnknownNetwork("testnet".to_string()))
+        );
+
+        let alpha = ClientNetwork::Alpha.profile().unwrap();
+        assert_eq!(alpha.network_id, Some(ALPHA_NETWORK_ID));
+        assert_eq!(alpha.evm_network, EvmNetwork::ArbitrumSepoliaTest);
+        assert!(!alpha.network_contacts_url.is_empty());
+        assert!(!alpha.local);
+
+        let mainnet = ClientNetwork::Mainnet.profile().unwrap();
+        assert_eq!(mainnet.network_id, None);
+        assert_eq!(mainnet.evm_network, EvmNetwork::default());
+
+        let config = ClientConfig::for_network(ClientNetwork::Alpha).unwrap();
+        assert_eq!(config.network_id, alpha.network_id);
+        assert_eq!(
+            config.bootstrap_config.network_contacts_url,
+            alpha.network_contacts_url
+        );
+    }
+}
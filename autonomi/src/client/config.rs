@@ -6,7 +6,10 @@
 // KIND, either express or implied. Please review the Licences for the specific language governing
 // permissions and limitations relating to use of the SAFE Network Software.
 
+use crate::client::quote::QuoteSelectionStrategy;
+pub use crate::networking::ConnectionBudget;
 use crate::networking::{Quorum, RetryStrategy, Strategy};
+use crate::self_encryption::Compression;
 pub use ant_bootstrap::{
     Bootstrap, BootstrapConfig, InitialPeersConfig, error::Error as BootstrapError,
 };
@@ -131,6 +134,8 @@ pub(crate) async fn upload_retry_pause() {
 }
 
 /// Configuration for the [`crate::Client`] which can be provided through: [`crate::Client::init_with_config`].
+///
+/// Prefer [`crate::Client::builder`], which checks that the options can be combined.
 #[derive(Debug, Clone, Default)]
 pub struct ClientConfig {
     /// Configuration for bootstrapping into the network and caching peers.
@@ -141,10 +146,21 @@ pub struct ClientConfig {
 
     /// The network ID to use for the client.
     /// This is used to differentiate between different networks.
+    ///
+    /// The ID is scoped to the client, so clients of different networks can run in the same
+    /// process. Defaults to the process-wide ID, see [`ant_protocol::version::set_network_id`].
     pub network_id: Option<u8>,
 
     /// Strategy for data operations by the client.
     pub strategy: ClientOperatingStrategy,
+
+    /// Also dial peers over TCP, for networks where UDP (and so QUIC) is blocked.
+    /// Only peers running with the TCP fallback enabled can be reached this way.
+    pub tcp_fallback: bool,
+
+    /// Limits on the concurrent dials and streams of the client, to tune it for constrained
+    /// environments like mobile devices or CI runners.
+    pub connection_budget: ConnectionBudget,
 }
 
 /// Strategy configuration for data operations by the client.
@@ -160,6 +176,14 @@ pub struct ClientOperatingStrategy {
     pub chunk_cache_enabled: bool,
     /// Custom chunk cache directory (if None, uses default)
     pub chunk_cache_dir: Option<std::path::PathBuf>,
+    /// How the nodes to pay are selected among the quotes of a record
+    pub quote_selection: QuoteSelectionStrategy,
+    /// The number of chunk batches paid for ahead of the one being uploaded, so that payment
+    /// confirmations overlap with network PUTs. 0 pays for and uploads each batch in turn.
+    pub pipeline_depth: usize,
+    /// Compression applied to data before self-encryption. Only data small enough to be
+    /// encrypted in memory is compressed, larger files are streamed uncompressed.
+    pub compression: Compression,
 }
 
 impl ClientOperatingStrategy {
@@ -182,6 +206,7 @@ impl Default for ClientOperatingStrategy {
                 verification_quorum: Quorum::N(two),
                 get_quorum: Quorum::One, // chunks are content addressed so one is enough as there is no fork possible
                 get_retry: RetryStrategy::None, // leave to the fall-back approach of fetching from closest_20
+                verify_after_write: false,
             },
             graph_entry: Strategy {
                 put_quorum: Quorum::Majority,
@@ -189,6 +214,7 @@ impl Default for ClientOperatingStrategy {
                 verification_quorum: Quorum::N(two),
                 get_quorum: Quorum::N(two), // forks are rare but possible, balance between resilience and speed
                 get_retry: RetryStrategy::Quick,
+                verify_after_write: false,
             },
             pointer: Strategy {
                 put_quorum: Quorum::Majority,
@@ -196,6 +222,7 @@ impl Default for ClientOperatingStrategy {
                 verification_quorum: Quorum::N(two),
                 get_quorum: Quorum::Majority, // majority to catch possible differences in versions
                 get_retry: RetryStrategy::Quick,
+                verify_after_write: false,
             },
             scratchpad: Strategy {
                 put_quorum: Quorum::Majority,
@@ -203,9 +230,13 @@ impl Default for ClientOperatingStrategy {
                 verification_quorum: Quorum::N(two),
                 get_quorum: Quorum::Majority, // majority to catch possible differences in versions
                 get_retry: RetryStrategy::Quick,
+                verify_after_write: false,
             },
             chunk_cache_enabled: true,
             chunk_cache_dir: None,
+            quote_selection: QuoteSelectionStrategy::default(),
+            pipeline_depth: 0,
+            compression: Compression::None,
         }
     }
 }
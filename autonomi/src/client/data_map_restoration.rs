@@ -9,7 +9,7 @@
 use crate::ChunkAddress;
 use crate::client::data_types::chunk::DataMapChunk;
 use crate::client::{Client, GetError};
-use crate::self_encryption::DataMapLevel;
+use crate::self_encryption::{Compression, DataMapLevel, unwrap_data_map_chunk};
 use bytes::Bytes;
 use eyre::Result;
 use self_encryption::{ChunkInfo, DataMap, get_root_data_map};
@@ -33,6 +33,12 @@ impl Client {
     ) -> Result<DataMap, GetError> {
         let mut data_map_bytes = data_map_chunk.0.value().clone();
 
+        // The data map of compressed data is wrapped with the compression used
+        if let Some((compression, inner_data_map_bytes)) = unwrap_data_map_chunk(&data_map_bytes) {
+            debug!("Data map chunk {data_map_chunk:?} holds {compression:?} compressed data");
+            data_map_bytes = inner_data_map_bytes;
+        }
+
         // In case the input is the new data_map root, restore it further,
         // before going into the further recursive.
         if let Ok(data_map) = rmp_serde::from_slice::<DataMap>(&data_map_bytes) {
@@ -111,6 +117,14 @@ impl Client {
         Ok(result_data_map)
     }
 
+    /// The compression applied to the data of a data map chunk before it was encrypted, which has
+    /// to be undone after decryption.
+    pub fn data_map_compression(data_map_chunk: &DataMapChunk) -> Compression {
+        unwrap_data_map_chunk(data_map_chunk.0.value())
+            .map(|(compression, _)| compression)
+            .unwrap_or_default()
+    }
+
     /// Deserialize datamap from bytes, handling both old and new formats
     pub fn deserialize_data_map(data_map_bytes: &Bytes) -> Result<DataMap, GetError> {
         // Try new format first
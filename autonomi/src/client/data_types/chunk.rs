@@ -23,7 +23,7 @@ use crate::{
 use ant_evm::{Amount, AttoTokens, ClientProofOfPayment};
 pub use ant_protocol::storage::{Chunk, ChunkAddress};
 use ant_protocol::{
-    NetworkAddress,
+    CLOSE_GROUP_SIZE, NetworkAddress,
     storage::{DataTypes, RecordHeader, RecordKind, try_deserialize_record, try_serialize_record},
 };
 use bytes::Bytes;
@@ -193,6 +193,55 @@ impl Client {
         Ok(chunk)
     }
 
+    /// Get a byte range of a chunk from the network, without downloading the whole chunk.
+    ///
+    /// The range applies to the chunk content as stored on the network and is truncated to the
+    /// end of the chunk. Holders are asked one after the other, closest first.
+    pub async fn chunk_get_range(
+        &self,
+        addr: &ChunkAddress,
+        offset: usize,
+        len: usize,
+    ) -> Result<Bytes, GetError> {
+        info!("Getting range {offset}+{len} of chunk: {addr:?}");
+
+        if let Some(cached_chunk) = self.try_load_chunk_from_cache(addr)? {
+            let value = cached_chunk.value();
+            let start = offset.min(value.len());
+            let end = offset.saturating_add(len).min(value.len());
+            return Ok(value.slice(start..end));
+        }
+
+        let key = NetworkAddress::from(*addr);
+        let peers = self
+            .network
+            .get_closest_peers(key.clone(), Some(CLOSE_GROUP_SIZE))
+            .await?;
+
+        for peer in peers {
+            let peer_id = peer.peer_id;
+            match self
+                .network
+                .get_chunk_range_from_peer(key.clone(), peer, offset, len)
+                .await
+            {
+                Ok(Some((data, chunk_size))) => {
+                    debug!(
+                        "Got {} bytes of chunk {addr:?} ({chunk_size} bytes) from {peer_id:?}",
+                        data.len()
+                    );
+                    return Ok(data);
+                }
+                Ok(None) => debug!("Peer {peer_id:?} did not return a range of chunk {addr:?}"),
+                Err(err) => {
+                    warn!("Failed to get a range of chunk {addr:?} from {peer_id:?}: {err}")
+                }
+            }
+        }
+
+        Err(GetError::RecordNotFound)
+    }
+
     /// Manually upload a chunk to the network.
     /// It is recommended to use the [`Client::data_put`] method instead to upload data.
     pub async fn chunk_put(
@@ -0,0 +1,94 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use std::fmt;
+use std::sync::Arc;
+
+/// Max number of times an update is retried on top of a competing version before giving up.
+pub const MAX_CONFLICT_RETRIES: usize = 5;
+
+/// Merge callback, called with the competing version found on the network and the content of our
+/// update, and returning the content to write on top of the competing version.
+pub type MergeFn<T, C> = Arc<dyn Fn(&T, &C) -> C + Send + Sync>;
+
+/// What to do when an update of a mutable data type conflicts with a version written concurrently
+/// by another writer, i.e. when the network holds a version of the same or a higher counter than
+/// the one we wrote, with a different content.
+///
+/// `T` is the data type (e.g. a `Pointer`) and `C` the content of an update (e.g. a `PointerTarget`).
+pub enum OnConflict<T, C> {
+    /// Fail the update, returning the competing version
+    Fail,
+    /// Write the merge of our content and the competing version, on top of the competing version
+    Merge(MergeFn<T, C>),
+    /// Write our content again, on top of the competing version
+    Retry,
+}
+
+impl<T, C> OnConflict<T, C> {
+    /// Create a merge strategy from a callback.
+    pub fn merge(f: impl Fn(&T, &C) -> C + Send + Sync + 'static) -> Self {
+        Self::Merge(Arc::new(f))
+    }
+
+    /// The content to write on top of the `competing` version, or `None` if the update shall fail.
+    pub(crate) fn resolve(&self, competing: &T, ours: &C) -> Option<C>
+    where
+        C: Clone,
+    {
+        match self {
+            Self::Fail => None,
+            Self::Merge(merge) => Some(merge(competing, ours)),
+            Self::Retry => Some(ours.clone()),
+        }
+    }
+}
+
+impl<T, C> Clone for OnConflict<T, C> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Fail => Self::Fail,
+            Self::Merge(merge) => Self::Merge(Arc::clone(merge)),
+            Self::Retry => Self::Retry,
+        }
+    }
+}
+
+impl<T, C> fmt::Debug for OnConflict<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fail => write!(f, "Fail"),
+            Self::Merge(_) => write!(f, "Merge(..)"),
+            Self::Retry => write!(f, "Retry"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve() {
+        let competing = vec![1, 2];
+        let ours = vec![3];
+
+        assert_eq!(
+            OnConflict::<Vec<u8>, Vec<u8>>::Fail.resolve(&competing, &ours),
+            None
+        );
+        assert_eq!(
+            OnConflict::<Vec<u8>, Vec<u8>>::Retry.resolve(&competing, &ours),
+            Some(vec![3])
+        );
+        let merge = OnConflict::merge(|competing: &Vec<u8>, ours: &Vec<u8>| {
+            [competing.as_slice(), ours.as_slice()].concat()
+        });
+        assert_eq!(merge.resolve(&competing, &ours), Some(vec![1, 2, 3]));
+    }
+}
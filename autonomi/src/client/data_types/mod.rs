@@ -7,6 +7,7 @@
 // permissions and limitations relating to use of the SAFE Network Software.
 
 pub mod chunk;
+pub mod conflict;
 pub mod graph;
 pub mod pointer;
 pub mod scratchpad;
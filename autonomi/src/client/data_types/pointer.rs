@@ -6,6 +6,7 @@
 // KIND, either express or implied. Please review the Licences for the specific language governing
 // permissions and limitations relating to use of the SAFE Network Software.
 
+use super::conflict::MAX_CONFLICT_RETRIES;
 use super::resolve_records_from_peers;
 
 use crate::{
@@ -20,11 +21,15 @@ use crate::{
 use ant_evm::{Amount, AttoTokens, EvmWalletError};
 use ant_protocol::{
     NetworkAddress,
-    storage::{DataTypes, RecordHeader, RecordKind, try_deserialize_record, try_serialize_record},
+    storage::{
+        DataTypes, RecordHeader, RecordKind, try_deserialize_record, try_serialize_record,
+        update_work::{MAX_UPDATE_WORK_DIFFICULTY, try_serialize_record_with_work},
+    },
 };
 use std::collections::HashSet;
 use tracing::{debug, error, info, trace, warn};
 
+pub use super::conflict::OnConflict;
 pub use ant_protocol::storage::{Pointer, PointerAddress, PointerTarget};
 pub use bls::{PublicKey, SecretKey};
 
@@ -57,6 +62,8 @@ pub enum PointerError {
     CannotUpdateNewPointer,
     #[error("Got multiple conflicting pointers with the latest version")]
     Fork(Vec<Pointer>),
+    #[error("Pointer update conflicts with a version written concurrently: {0:?}")]
+    Conflict(Pointer),
 }
 
 impl Client {
@@ -334,14 +341,126 @@ impl Client {
         let new_counter = current.counter() + 1;
         info!("Updating pointer at address {address:?} to version {new_counter}");
         let pointer = Pointer::new(owner, new_counter, new_target);
+        self.pointer_put_update(pointer.clone()).await?;
+        Ok(pointer)
+    }
+
+    /// Update an existing pointer to point to a new target, resolving the conflicts with
+    /// concurrent writers according to `on_conflict`.
+    ///
+    /// Once stored, the pointer is fetched again. If the network holds a version of the same or a
+    /// higher counter pointing elsewhere, or rejected the update as outdated, that competing
+    /// version is passed to `on_conflict` to decide whether to fail, or to write again on top of it.
+    /// Returns the pointer that was finally stored.
+    pub async fn pointer_update_with_conflict_resolution(
+        &self,
+        owner: &SecretKey,
+        target: PointerTarget,
+        on_conflict: OnConflict<Pointer, PointerTarget>,
+    ) -> Result<Pointer, PointerError> {
+        let address = PointerAddress::new(owner.public_key());
+        let mut base = match self.pointer_get(&address).await {
+            Ok(pointer) => pointer,
+            Err(PointerError::Fork(pointers)) => pointers
+                .into_iter()
+                .max_by_key(|pointer| pointer.counter())
+                .ok_or(PointerError::CannotUpdateNewPointer)?,
+            Err(PointerError::GetError(GetError::RecordNotFound)) => {
+                return Err(PointerError::CannotUpdateNewPointer);
+            }
+            Err(err) => return Err(err),
+        };
+        let mut target = target;
+
+        for _ in 0..=MAX_CONFLICT_RETRIES {
+            let pointer = Pointer::new(owner, base.counter() + 1, target.clone());
+            info!(
+                "Updating pointer at address {address:?} to version {}",
+                pointer.counter()
+            );
+
+            let competing = match self.pointer_put_update(pointer.clone()).await {
+                Ok(()) => competing_pointer(self.pointer_get(&address).await, &pointer)?,
+                Err(
+                    err @ PointerError::PutError(PutError::Network {
+                        network_error: NetworkError::OutdatedRecordRejected { .. },
+                        ..
+                    }),
+                ) => match competing_pointer(self.pointer_get(&address).await, &pointer)? {
+                    Some(competing) => Some(competing),
+                    None => return Err(err),
+                },
+                Err(err) => return Err(err),
+            };
+
+            let Some(competing) = competing else {
+                return Ok(pointer);
+            };
+            warn!(
+                "Pointer update at {address:?} to version {} conflicts with {competing:?}, resolving with {on_conflict:?}",
+                pointer.counter()
+            );
+            match on_conflict.resolve(&competing, &target) {
+                Some(resolved) => {
+                    target = resolved;
+                    base = competing;
+                }
+                None => return Err(PointerError::Conflict(competing)),
+            }
+        }
+
+        error!(
+            "Pointer update at {address:?} still conflicting after {MAX_CONFLICT_RETRIES} retries"
+        );
+        Err(PointerError::Conflict(base))
+    }
+
+    /// Store an already signed update of an existing pointer, without paying for it
+    ///
+    /// The pointer counter must be higher than the one of the pointer stored on the network.
+    /// This is useful when the pointer signature was computed elsewhere than from a [`SecretKey`],
+    /// e.g. combined from threshold signature shares.
+    pub async fn pointer_put_update(&self, pointer: Pointer) -> Result<(), PointerError> {
+        self.pointer_put_update_with_work(pointer, 0).await
+    }
+
+    /// Store an update of an existing pointer like [`Client::pointer_put_update`], carrying a proof
+    /// of work of `work_difficulty` bits, as asked for by the nodes over their free update limits.
+    /// No proof of work is done for a difficulty of 0.
+    async fn pointer_put_update_with_work(
+        &self,
+        pointer: Pointer,
+        work_difficulty: u8,
+    ) -> Result<(), PointerError> {
+        let address = pointer.address();
+        if !pointer.verify_signature() {
+            return Err(PointerError::BadSignature);
+        }
 
         // prepare the record to be stored
         let net_addr = NetworkAddress::from(address);
+        let key = net_addr.to_record_key();
+        let kind = RecordKind::DataOnly(DataTypes::Pointer);
+        let value = if work_difficulty > 0 {
+            debug!("Doing a proof of work of difficulty {work_difficulty} for pointer {address:?}");
+            let (pointer, key) = (pointer.clone(), key.clone());
+            tokio::task::spawn_blocking(move || {
+                try_serialize_record_with_work(
+                    &pointer,
+                    RecordHeader::new(kind),
+                    &key,
+                    work_difficulty,
+                )
+            })
+            .await
+            .map_err(|_| PointerError::Serialization)?
+        } else {
+            try_serialize_record(&pointer, kind)
+        }
+        .map_err(|_| PointerError::Serialization)?;
         let record = Record {
-            key: net_addr.to_record_key(),
-            value: try_serialize_record(&pointer, RecordKind::DataOnly(DataTypes::Pointer))
-                .map_err(|_| PointerError::Serialization)?
-                .to_vec(),
+            key,
+            value: value.to_vec(),
             publisher: None,
             expires: None,
         };
@@ -372,7 +491,50 @@ impl Client {
                 })
             })?;
 
-        Ok(pointer)
+        Ok(())
+    }
+
+    /// Store an update of an existing pointer like [`Client::pointer_put_update`]. If the nodes
+    /// rate limit the free updates of this pointer, the update is sent again with the proof of work
+    /// they ask for, up to [`MAX_UPDATE_WORK_DIFFICULTY`] bits, else paid for with `payment_option`.
+    /// Returns the cost of the update, zero if it was free.
+    pub async fn pointer_put_update_or_pay(
+        &self,
+        pointer: Pointer,
+        payment_option: PaymentOption,
+    ) -> Result<AttoTokens, PointerError> {
+        let address = pointer.address();
+        let mut result = self.pointer_put_update(pointer.clone()).await;
+        if let Err(PointerError::PutError(PutError::Network {
+            network_error:
+                NetworkError::FreeUpdateRateLimited {
+                    work_difficulty, ..
+                },
+            ..
+        })) = result
+            && (1..=MAX_UPDATE_WORK_DIFFICULTY).contains(&work_difficulty)
+        {
+            info!(
+                "Free updates of pointer at {address:?} are rate limited, doing the proof of work asked for"
+            );
+            result = self
+                .pointer_put_update_with_work(pointer.clone(), work_difficulty)
+                .await;
+        }
+        match result {
+            Ok(()) => Ok(AttoTokens::zero()),
+            Err(PointerError::PutError(PutError::Network {
+                network_error: NetworkError::FreeUpdateRateLimited { .. },
+                ..
+            })) => {
+                warn!(
+                    "Free updates of pointer at {address:?} are rate limited, paying for the update"
+                );
+                let (cost, _address) = self.pointer_put(pointer, payment_option).await?;
+                Ok(cost)
+            }
+            Err(err) => Err(err),
+        }
     }
 
     /// Calculate the cost of storing a pointer
@@ -396,6 +558,23 @@ impl Client {
     }
 }
 
+/// The version competing with the pointer we wrote, from the result of fetching it after writing:
+/// any other version of the same or a higher counter.
+fn competing_pointer(
+    fetched: Result<Pointer, PointerError>,
+    ours: &Pointer,
+) -> Result<Option<Pointer>, PointerError> {
+    let candidates = match fetched {
+        Ok(pointer) => vec![pointer],
+        Err(PointerError::Fork(pointers)) => pointers,
+        Err(err) => return Err(err),
+    };
+    Ok(candidates
+        .into_iter()
+        .filter(|pointer| pointer != ours && pointer.counter() >= ours.counter())
+        .max_by_key(|pointer| pointer.counter()))
+}
+
 /// Deserialize a pointer from a record
 fn pointer_from_record(record: Record) -> Result<Pointer, PointerError> {
     let key = &record.key;
@@ -419,3 +598,40 @@ fn pointer_from_record(record: Record) -> Result<Pointer, PointerError> {
 
     Ok(pointer)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ant_protocol::storage::ChunkAddress;
+    use xor_name::XorName;
+
+    fn random_target() -> PointerTarget {
+        PointerTarget::ChunkAddress(ChunkAddress::new(XorName::random(&mut rand::thread_rng())))
+    }
+
+    #[test]
+    fn test_competing_pointer() {
+        let owner = SecretKey::random();
+        let ours = Pointer::new(&owner, 2, random_target());
+        let theirs = Pointer::new(&owner, 2, random_target());
+        let outdated = Pointer::new(&owner, 1, random_target());
+
+        assert_eq!(competing_pointer(Ok(ours.clone()), &ours).unwrap(), None);
+        assert_eq!(
+            competing_pointer(Ok(outdated.clone()), &ours).unwrap(),
+            None
+        );
+        assert_eq!(
+            competing_pointer(Ok(theirs.clone()), &ours).unwrap(),
+            Some(theirs.clone())
+        );
+        assert_eq!(
+            competing_pointer(
+                Err(PointerError::Fork(vec![ours.clone(), theirs.clone()])),
+                &ours
+            )
+            .unwrap(),
+            Some(theirs)
+        );
+    }
+}
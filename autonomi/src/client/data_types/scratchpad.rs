@@ -6,6 +6,7 @@
 // KIND, either express or implied. Please review the Licences for the specific language governing
 // permissions and limitations relating to use of the SAFE Network Software.
 
+use super::conflict::MAX_CONFLICT_RETRIES;
 use super::resolve_records_from_peers;
 
 use crate::{
@@ -20,13 +21,19 @@ use crate::{
 
 use ant_protocol::{
     NetworkAddress,
-    storage::{DataTypes, RecordKind, try_deserialize_record, try_serialize_record},
+    storage::{
+        DataTypes, RecordHeader, RecordKind, lease_periods, try_deserialize_record,
+        try_serialize_record, try_serialize_record_with_header,
+        update_work::{MAX_UPDATE_WORK_DIFFICULTY, try_serialize_record_with_work},
+    },
 };
 use libp2p::kad::Record;
 use std::collections::HashSet;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+pub use super::conflict::OnConflict;
 pub use crate::Bytes;
-pub use ant_protocol::storage::{Scratchpad, ScratchpadAddress};
+pub use ant_protocol::storage::{RECORD_LEASE_PERIOD, Scratchpad, ScratchpadAddress};
 pub use bls::{PublicKey, SecretKey, Signature};
 
 const SCRATCHPAD_MAX_SIZE: usize = Scratchpad::MAX_SIZE;
@@ -61,6 +68,12 @@ pub enum ScratchpadError {
         "Got multiple conflicting scratchpads with the latest version, the fork can be resolved by putting a new scratchpad with a higher counter"
     )]
     Fork(Vec<Scratchpad>),
+    #[error("Scratchpad update conflicts with a version written concurrently: {0:?}")]
+    Conflict(Scratchpad),
+    #[error(
+        "The lease of the temporary scratchpad at {0:?} was not renewed, the nodes did not ask for a payment"
+    )]
+    LeaseNotRenewed(ScratchpadAddress),
 }
 
 /// Print detailed fork analysis for conflicting scratchpads
@@ -247,6 +260,19 @@ impl Client {
         &self,
         scratchpad: Scratchpad,
         payment_option: PaymentOption,
+    ) -> Result<(AttoTokens, ScratchpadAddress), ScratchpadError> {
+        self.scratchpad_put_with_expiry(scratchpad, None, payment_option)
+            .await
+    }
+
+    /// Store a scratchpad on the network, as a temporary one dropped by the nodes after `expiry`,
+    /// in seconds since the UNIX epoch, if set. The lease of a temporary scratchpad is paid for
+    /// even if it is already stored, which renews it.
+    async fn scratchpad_put_with_expiry(
+        &self,
+        scratchpad: Scratchpad,
+        expiry: Option<u64>,
+        payment_option: PaymentOption,
     ) -> Result<(AttoTokens, ScratchpadAddress), ScratchpadError> {
         let address = scratchpad.address();
         Self::scratchpad_verify(&scratchpad)?;
@@ -274,6 +300,10 @@ impl Client {
             }
         };
         let total_cost = *price;
+        if expiry.is_some() && proof.is_none() {
+            warn!("Scratchpad at address: {address:?} was not paid for, its lease is not renewed");
+            return Err(ScratchpadError::LeaseNotRenewed(*address));
+        }
 
         let net_addr = NetworkAddress::from(*address);
         let (record, target_nodes) = if let Some(proof) = proof {
@@ -285,11 +315,16 @@ impl Client {
                     addrs: addrs.clone(),
                 })
                 .collect();
+            let kind = RecordKind::DataWithPayment(DataTypes::Scratchpad);
+            let header = match expiry {
+                Some(expiry) => RecordHeader::with_expiry(kind, expiry),
+                None => RecordHeader::new(kind),
+            };
             let record = Record {
                 key: net_addr.to_record_key(),
-                value: try_serialize_record(
+                value: try_serialize_record_with_header(
                     &(proof.to_proof_of_payment(), &scratchpad),
-                    RecordKind::DataWithPayment(DataTypes::Scratchpad),
+                    header,
                 )
                 .map_err(|_| ScratchpadError::Serialization)?
                 .to_vec(),
@@ -368,6 +403,52 @@ impl Client {
         self.scratchpad_put(scratchpad, payment_option).await
     }
 
+    /// Create a new temporary scratchpad to the network, kept by the nodes for one lease period
+    /// ([`RECORD_LEASE_PERIOD`]) unless renewed with [`Client::scratchpad_renew_lease`].
+    ///
+    /// It is updated for free like any scratchpad, the updates keeping its lease.
+    /// Returns the cost, the address of the scratchpad, and the expiry of its lease in seconds
+    /// since the UNIX epoch.
+    pub async fn scratchpad_create_temporary(
+        &self,
+        owner: &SecretKey,
+        content_type: u64,
+        initial_data: &Bytes,
+        payment_option: PaymentOption,
+    ) -> Result<(AttoTokens, ScratchpadAddress, u64), ScratchpadError> {
+        let address = ScratchpadAddress::new(owner.public_key());
+        let already_exists = self.scratchpad_check_existence(&address).await?;
+        if already_exists {
+            return Err(ScratchpadError::ScratchpadAlreadyExists(address));
+        }
+
+        let scratchpad = Scratchpad::new(owner, content_type, initial_data, 0);
+        let expiry = lease_expiry();
+        let (cost, address) = self
+            .scratchpad_put_with_expiry(scratchpad, Some(expiry), payment_option)
+            .await?;
+        Ok((cost, address, expiry))
+    }
+
+    /// Renew the lease of a temporary scratchpad for one lease period from now, paying for it.
+    ///
+    /// A renewal before the current lease expires does not add up to it: a lease longer than a
+    /// period is kept by renewing it once per period.
+    /// Returns the cost and the new expiry of the lease, in seconds since the UNIX epoch.
+    pub async fn scratchpad_renew_lease(
+        &self,
+        address: &ScratchpadAddress,
+        payment_option: PaymentOption,
+    ) -> Result<(AttoTokens, u64), ScratchpadError> {
+        let scratchpad = self.scratchpad_get(address).await?;
+        let expiry = lease_expiry();
+        info!("Renewing the lease of scratchpad at address {address:?} until {expiry}");
+        let (cost, _address) = self
+            .scratchpad_put_with_expiry(scratchpad, Some(expiry), payment_option)
+            .await?;
+        Ok((cost, expiry))
+    }
+
     /// Update an existing scratchpad to the network.
     /// The scratchpad needs to be created first with [`Client::scratchpad_create`].
     /// This operation is free as the scratchpad was already paid for at creation.
@@ -429,6 +510,81 @@ impl Client {
         Ok(scratchpad)
     }
 
+    /// Update an existing scratchpad, resolving the conflicts with concurrent writers according to
+    /// `on_conflict`.
+    ///
+    /// Once stored, the scratchpad is fetched again. If the network holds a version of the same or
+    /// a higher counter with another content, or rejected the update as outdated, that competing
+    /// version is passed to `on_conflict` to decide whether to fail, or to write again on top of it.
+    /// A merge callback can decrypt the competing version with the owner key.
+    /// Returns the scratchpad that was finally stored.
+    pub async fn scratchpad_update_with_conflict_resolution(
+        &self,
+        owner: &SecretKey,
+        content_type: u64,
+        data: &Bytes,
+        on_conflict: OnConflict<Scratchpad, Bytes>,
+    ) -> Result<Scratchpad, ScratchpadError> {
+        let address = ScratchpadAddress::new(owner.public_key());
+        let mut base = match self.scratchpad_get(&address).await {
+            Ok(scratchpad) => scratchpad,
+            Err(ScratchpadError::Fork(scratchpads)) => scratchpads
+                .into_iter()
+                .max_by_key(|scratchpad| scratchpad.counter())
+                .ok_or(ScratchpadError::CannotUpdateNewScratchpad)?,
+            Err(ScratchpadError::NotFound(..)) => {
+                return Err(ScratchpadError::CannotUpdateNewScratchpad);
+            }
+            Err(err) => return Err(err),
+        };
+        let mut data = data.clone();
+
+        for _ in 0..=MAX_CONFLICT_RETRIES {
+            let scratchpad = Scratchpad::new(owner, content_type, &data, base.counter() + 1);
+            info!(
+                "Updating scratchpad at address {address:?} to version {}",
+                scratchpad.counter()
+            );
+
+            let competing = match self.scratchpad_put_update(scratchpad.clone()).await {
+                Ok(()) => competing_scratchpad(self.scratchpad_get(&address).await, &scratchpad)?,
+                Err(
+                    err @ ScratchpadError::PutError(PutError::Network {
+                        network_error: NetworkError::OutdatedRecordRejected { .. },
+                        ..
+                    }),
+                ) => {
+                    match competing_scratchpad(self.scratchpad_get(&address).await, &scratchpad)? {
+                        Some(competing) => Some(competing),
+                        None => return Err(err),
+                    }
+                }
+                Err(err) => return Err(err),
+            };
+
+            let Some(competing) = competing else {
+                return Ok(scratchpad);
+            };
+            warn!(
+                "Scratchpad update at {address:?} to version {} conflicts with version {}, resolving with {on_conflict:?}",
+                scratchpad.counter(),
+                competing.counter()
+            );
+            match on_conflict.resolve(&competing, &data) {
+                Some(resolved) => {
+                    data = resolved;
+                    base = competing;
+                }
+                None => return Err(ScratchpadError::Conflict(competing)),
+            }
+        }
+
+        error!(
+            "Scratchpad update at {address:?} still conflicting after {MAX_CONFLICT_RETRIES} retries"
+        );
+        Err(ScratchpadError::Conflict(base))
+    }
+
     /// Store a fully formed, pre-signed scratchpad verbatim after verification.
     /// This method is intended for updates and does not require payment.
     ///
@@ -440,17 +596,47 @@ impl Client {
     pub async fn scratchpad_put_update(
         &self,
         scratchpad: Scratchpad,
+    ) -> Result<(), ScratchpadError> {
+        self.scratchpad_put_update_with_work(scratchpad, 0).await
+    }
+
+    /// Store an update of an existing scratchpad like [`Client::scratchpad_put_update`], carrying a
+    /// proof of work of `work_difficulty` bits, as asked for by the nodes over their free update
+    /// limits. No proof of work is done for a difficulty of 0.
+    async fn scratchpad_put_update_with_work(
+        &self,
+        scratchpad: Scratchpad,
+        work_difficulty: u8,
     ) -> Result<(), ScratchpadError> {
         let address = scratchpad.address();
         Self::scratchpad_verify(&scratchpad)?;
 
         // prepare the record to be stored
         let net_addr = NetworkAddress::from(*address);
+        let key = net_addr.to_record_key();
+        let kind = RecordKind::DataOnly(DataTypes::Scratchpad);
+        let value = if work_difficulty > 0 {
+            debug!(
+                "Doing a proof of work of difficulty {work_difficulty} for scratchpad {address:?}"
+            );
+            let (scratchpad, key) = (scratchpad.clone(), key.clone());
+            tokio::task::spawn_blocking(move || {
+                try_serialize_record_with_work(
+                    &scratchpad,
+                    RecordHeader::new(kind),
+                    &key,
+                    work_difficulty,
+                )
+            })
+            .await
+            .map_err(|_| ScratchpadError::Serialization)?
+        } else {
+            try_serialize_record(&scratchpad, kind)
+        }
+        .map_err(|_| ScratchpadError::Serialization)?;
         let record = Record {
-            key: net_addr.to_record_key(),
-            value: try_serialize_record(&scratchpad, RecordKind::DataOnly(DataTypes::Scratchpad))
-                .map_err(|_| ScratchpadError::Serialization)?
-                .to_vec(),
+            key,
+            value: value.to_vec(),
             publisher: None,
             expires: None,
         };
@@ -487,6 +673,49 @@ impl Client {
         Ok(())
     }
 
+    /// Store an update of an existing scratchpad like [`Client::scratchpad_put_update`]. If the
+    /// nodes rate limit the free updates of this scratchpad, the update is sent again with the
+    /// proof of work they ask for, up to [`MAX_UPDATE_WORK_DIFFICULTY`] bits, else paid for with
+    /// `payment_option`. Returns the cost of the update, zero if it was free.
+    pub async fn scratchpad_put_update_or_pay(
+        &self,
+        scratchpad: Scratchpad,
+        payment_option: PaymentOption,
+    ) -> Result<AttoTokens, ScratchpadError> {
+        let address = *scratchpad.address();
+        let mut result = self.scratchpad_put_update(scratchpad.clone()).await;
+        if let Err(ScratchpadError::PutError(PutError::Network {
+            network_error:
+                NetworkError::FreeUpdateRateLimited {
+                    work_difficulty, ..
+                },
+            ..
+        })) = result
+            && (1..=MAX_UPDATE_WORK_DIFFICULTY).contains(&work_difficulty)
+        {
+            info!(
+                "Free updates of scratchpad at {address:?} are rate limited, doing the proof of work asked for"
+            );
+            result = self
+                .scratchpad_put_update_with_work(scratchpad.clone(), work_difficulty)
+                .await;
+        }
+        match result {
+            Ok(()) => Ok(AttoTokens::zero()),
+            Err(ScratchpadError::PutError(PutError::Network {
+                network_error: NetworkError::FreeUpdateRateLimited { .. },
+                ..
+            })) => {
+                warn!(
+                    "Free updates of scratchpad at {address:?} are rate limited, paying for the update"
+                );
+                let (cost, _address) = self.scratchpad_put(scratchpad, payment_option).await?;
+                Ok(cost)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
     /// Get the cost of creating a new Scratchpad
     pub async fn scratchpad_cost(&self, owner: &PublicKey) -> Result<AttoTokens, CostError> {
         info!("Getting cost for scratchpad");
@@ -509,4 +738,47 @@ impl Client {
 
         Ok(total_cost)
     }
+
+    /// Get the cost of keeping a temporary Scratchpad for `lease`: its creation, then a renewal for
+    /// each further lease period, at the current price.
+    pub async fn scratchpad_lease_cost(
+        &self,
+        owner: &PublicKey,
+        lease: Duration,
+    ) -> Result<AttoTokens, CostError> {
+        let cost_per_period = self.scratchpad_cost(owner).await?;
+        let periods = lease_periods(lease);
+        info!("Cost of a scratchpad lease of {lease:?}: {periods} periods at {cost_per_period}");
+        Ok(AttoTokens::from_atto(
+            cost_per_period
+                .as_atto()
+                .saturating_mul(Amount::from(periods)),
+        ))
+    }
+}
+
+/// The expiry of a lease taken now, in seconds since the UNIX epoch.
+fn lease_expiry() -> u64 {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0);
+    now + RECORD_LEASE_PERIOD.as_secs()
+}
+
+/// The version competing with the scratchpad we wrote, from the result of fetching it after
+/// writing: any other version of the same or a higher counter.
+fn competing_scratchpad(
+    fetched: Result<Scratchpad, ScratchpadError>,
+    ours: &Scratchpad,
+) -> Result<Option<Scratchpad>, ScratchpadError> {
+    let candidates = match fetched {
+        Ok(scratchpad) => vec![scratchpad],
+        Err(ScratchpadError::Fork(scratchpads)) => scratchpads,
+        Err(err) => return Err(err),
+    };
+    Ok(candidates
+        .into_iter()
+        .filter(|scratchpad| scratchpad != ours && scratchpad.counter() >= ours.counter())
+        .max_by_key(|scratchpad| scratchpad.counter()))
 }
@@ -0,0 +1,279 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Data map management: inspect, split and re-pack the data maps of private data.
+
+use crate::Client;
+use crate::chunk::{Chunk, ChunkAddress, DataMapChunk};
+use crate::client::GetError;
+use crate::self_encryption::{
+    Compression, DataMapLevel, unwrap_data_map_chunk, wrap_data_map_chunk,
+};
+use bytes::Bytes;
+use self_encryption::{DataMap, shrink_data_map};
+
+/// Errors that can occur when managing data maps.
+#[derive(Debug, thiserror::Error)]
+pub enum DataMapError {
+    #[error("Not a data map: {0}")]
+    InvalidDataMap(#[from] GetError),
+    #[error("Failed to serialize data map: {0}")]
+    Serialization(#[from] rmp_serde::encode::Error),
+    #[error("Self-encryption error: {0}")]
+    SelfEncryption(#[from] self_encryption::Error),
+    #[error("Encryption error: {0}")]
+    Encryption(#[from] crate::self_encryption::Error),
+}
+
+/// Serialization format of a data map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataMapFormat {
+    /// The current format
+    Current,
+    /// The format of data uploaded by older clients, which can be re-packed
+    Legacy,
+}
+
+/// Description of a data map.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataMapInfo {
+    pub format: DataMapFormat,
+    /// Number of chunks the data map refers to
+    pub chunk_count: usize,
+    /// Total size of the content of those chunks, before encryption
+    pub total_size: usize,
+    pub chunk_addresses: Vec<ChunkAddress>,
+    /// Level of the data map, when its chunks hold another data map rather than the data itself
+    pub child: Option<usize>,
+    /// Compression applied to the data before encryption
+    pub compression: Compression,
+}
+
+impl DataMapInfo {
+    /// Returns true if the chunks of the data map hold another data map rather than the data itself.
+    pub fn points_to_a_data_map(&self) -> bool {
+        self.child.is_some()
+    }
+}
+
+/// Inspect a data map chunk, without fetching anything from the network.
+pub fn inspect(data_map_chunk: &DataMapChunk) -> Result<DataMapInfo, DataMapError> {
+    let (compression, bytes) = unwrap_data_map_chunk(data_map_chunk.0.value())
+        .unwrap_or_else(|| (Compression::None, data_map_chunk.0.value().clone()));
+    let format = if rmp_serde::from_slice::<DataMap>(&bytes).is_ok() {
+        DataMapFormat::Current
+    } else if rmp_serde::from_slice::<DataMapLevel>(&bytes).is_ok() {
+        DataMapFormat::Legacy
+    } else {
+        return Err(DataMapError::InvalidDataMap(GetError::UnrecognizedDataMap(
+            "neither a current nor a legacy data map".to_string(),
+        )));
+    };
+    let data_map = Client::deserialize_data_map(&bytes)?;
+
+    Ok(DataMapInfo {
+        format,
+        chunk_count: data_map.infos().len(),
+        total_size: data_map.infos().iter().map(|info| info.src_size).sum(),
+        chunk_addresses: data_map
+            .infos()
+            .iter()
+            .map(|info| ChunkAddress::new(info.dst_hash))
+            .collect(),
+        child: data_map.child,
+        compression,
+    })
+}
+
+/// Split a data map into a hierarchy of data maps, until the root one only refers to a few chunks.
+///
+/// Returns the root data map, along with the chunks holding the lower levels. These chunks have to
+/// be uploaded for the root data map to be usable.
+pub fn split(data_map: DataMap) -> Result<(DataMap, Vec<Chunk>), DataMapError> {
+    let (root, encrypted_chunks) = shrink_data_map(data_map, |_, _| Ok(()))?;
+    let chunks = encrypted_chunks
+        .into_iter()
+        .map(|chunk| Chunk::new(chunk.content))
+        .collect();
+    Ok((root, chunks))
+}
+
+/// Pack a data map into a [`DataMapChunk`] of the current format.
+pub fn pack(data_map: &DataMap) -> Result<DataMapChunk, DataMapError> {
+    let bytes = rmp_serde::to_vec(data_map)?;
+    Ok(DataMapChunk::from(Chunk::new(Bytes::from(bytes))))
+}
+
+/// Split a full data map and pack its root into a [`DataMapChunk`] of the current format,
+/// recording the `compression` of the data.
+///
+/// Returns the new data map chunk along with the chunks holding the lower levels.
+pub fn repack(
+    data_map: DataMap,
+    compression: Compression,
+) -> Result<(DataMapChunk, Vec<Chunk>), DataMapError> {
+    let (root, chunks) = split(data_map)?;
+    let data_map_chunk = wrap_data_map_chunk(pack(&root)?.0, compression)?;
+    Ok((DataMapChunk(data_map_chunk), chunks))
+}
+
+impl Client {
+    /// Re-pack a data map chunk into the current format, upgrading legacy data maps.
+    ///
+    /// The full data map is restored first, fetching its lower levels from the network if any,
+    /// then split and packed again. Returns the new data map chunk along with the chunks holding
+    /// its lower levels, which have to be uploaded for the new data map chunk to be usable.
+    /// The compression of the data, if any, is kept.
+    pub async fn data_map_repack(
+        &self,
+        data_map_chunk: &DataMapChunk,
+    ) -> Result<(DataMapChunk, Vec<Chunk>), DataMapError> {
+        let data_map = self.restore_data_map_from_chunk(data_map_chunk).await?;
+        debug!(
+            "Re-packing data map of {} chunks from {:?}",
+            data_map.infos().len(),
+            data_map_chunk.address()
+        );
+        repack(data_map, Self::data_map_compression(data_map_chunk))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use self_encryption::{ChunkInfo, get_root_data_map};
+    use std::collections::HashMap;
+    use xor_name::XorName;
+
+    /// A data map too big to be held by a single chunk, referring to `count` made up chunks.
+    fn big_data_map(count: usize) -> DataMap {
+        let chunk_identifiers = (0..count)
+            .map(|index| ChunkInfo {
+                index,
+                dst_hash: XorName::random(&mut rand::thread_rng()),
+                src_hash: XorName::random(&mut rand::thread_rng()),
+                src_size: 1024,
+            })
+            .collect();
+        DataMap {
+            chunk_identifiers,
+            child: None,
+        }
+    }
+
+    /// Restore the full data map from its root, fetching the lower levels from `chunks`.
+    fn restore(root: DataMap, chunks: &[Chunk]) -> DataMap {
+        let store: HashMap<XorName, Bytes> = chunks
+            .iter()
+            .map(|chunk| (*chunk.name(), chunk.value.clone()))
+            .collect();
+        get_root_data_map(root, &mut |name: XorName| {
+            store
+                .get(&name)
+                .cloned()
+                .ok_or_else(|| self_encryption::Error::Generic(format!("Missing chunk {name:?}")))
+        })
+        .expect("the data map shall be restored from the split chunks")
+    }
+
+    #[test]
+    fn test_split_roundtrip() {
+        let data_map = big_data_map(200);
+
+        let (root, chunks) = split(data_map.clone()).expect("splitting shall succeed");
+        assert!(root.is_child());
+        assert!(root.infos().len() < data_map.infos().len());
+        assert!(!chunks.is_empty());
+
+        let restored = restore(root, &chunks);
+        assert_eq!(restored.infos(), data_map.infos());
+        assert!(!restored.is_child());
+    }
+
+    #[test]
+    fn test_repack_roundtrip_keeps_the_compression() {
+        let data_map = big_data_map(200);
+
+        let (data_map_chunk, chunks) =
+            repack(data_map.clone(), Compression::Zstd(3)).expect("re-packing shall succeed");
+        let info = inspect(&data_map_chunk).expect("inspection shall succeed");
+        assert_eq!(info.format, DataMapFormat::Current);
+        assert_eq!(info.compression, Compression::Zstd(3));
+        assert!(info.points_to_a_data_map());
+
+        let (_, root_bytes) = unwrap_data_map_chunk(data_map_chunk.0.value())
+            .expect("the data map chunk shall record the compression");
+        let root = Client::deserialize_data_map(&root_bytes).expect("root shall deserialize");
+        let restored = restore(root, &chunks);
+        assert_eq!(restored.infos(), data_map.infos());
+    }
+
+    #[test]
+    fn test_repack_upgrades_legacy_data_maps() {
+        let old_data_map = self_encryption_old::DataMap::new(
+            (0..5)
+                .map(|index| self_encryption_old::ChunkInfo {
+                    index,
+                    dst_hash: XorName::random(&mut rand::thread_rng()),
+                    src_hash: XorName::random(&mut rand::thread_rng()),
+                    src_size: 1024,
+                })
+                .collect(),
+        );
+        let legacy_chunk = DataMapChunk::from(Chunk::new(Bytes::from(
+            rmp_serde::to_vec(&DataMapLevel::First(old_data_map)).expect("legacy shall serialize"),
+        )));
+        assert_eq!(
+            inspect(&legacy_chunk)
+                .expect("inspection shall succeed")
+                .format,
+            DataMapFormat::Legacy
+        );
+
+        let data_map = Client::deserialize_data_map(legacy_chunk.0.value())
+            .expect("legacy data map shall deserialize");
+        let (data_map_chunk, chunks) =
+            repack(data_map.clone(), Compression::None).expect("re-packing shall succeed");
+        assert_eq!(
+            inspect(&data_map_chunk)
+                .expect("inspection shall succeed")
+                .format,
+            DataMapFormat::Current
+        );
+
+        let root = rmp_serde::from_slice::<DataMap>(data_map_chunk.0.value())
+            .expect("root shall be a current data map");
+        let restored = if root.is_child() {
+            restore(root, &chunks)
+        } else {
+            root
+        };
+        assert_eq!(restored.infos(), data_map.infos());
+    }
+
+    #[test]
+    fn test_inspect_split_and_pack() {
+        let data = Bytes::from(vec![7u8; 1024 * 1024]);
+        let (data_map, _chunks) = self_encryption::encrypt(data).expect("encryption shall succeed");
+
+        let info = inspect(&pack(&data_map).expect("packing shall succeed"))
+            .expect("inspection shall succeed");
+        assert_eq!(info.format, DataMapFormat::Current);
+        assert_eq!(info.chunk_count, data_map.infos().len());
+        assert_eq!(info.chunk_addresses.len(), info.chunk_count);
+        assert_eq!(info.points_to_a_data_map(), data_map.is_child());
+        assert_eq!(info.compression, Compression::None);
+
+        assert!(
+            inspect(&DataMapChunk::from(Chunk::new(Bytes::from_static(
+                b"not a datamap"
+            ))))
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,172 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Probe of the health of the network, as seen from the client.
+//!
+//! Measures the connectivity of the client, the state of its routing table, the latency of GETs
+//! of records known to exist and the success of store quotes, to tell a problem of the client
+//! from a problem of the network.
+
+use crate::Client;
+use crate::networking::{NetworkAddress, Quorum};
+use crate::self_encryption::MAX_CHUNK_SIZE;
+use ant_protocol::storage::DataTypes;
+use futures::future::join_all;
+use libp2p::kad::K_VALUE;
+use rand::seq::SliceRandom;
+use std::time::{Duration, Instant};
+use xor_name::XorName;
+
+/// The default number of GETs and store quotes made by a health probe.
+pub const DEFAULT_HEALTH_PROBES: usize = 5;
+
+/// Report of a probe of the health of the network.
+#[derive(Debug, Clone, Default)]
+pub struct NetworkHealthReport {
+    /// Time from the start of the client to its first connected peer, if any
+    pub time_to_first_peer: Option<Duration>,
+    /// The number of peers in the routing table
+    pub peers_in_routing_table: usize,
+    /// The share of the capacity of the non-empty kbuckets that is filled, from 0 to 1
+    pub routing_table_fill_rate: f64,
+    /// The number of GETs of known records attempted
+    pub gets_attempted: usize,
+    /// The latencies of the GETs that returned the record
+    pub get_latencies: Vec<Duration>,
+    /// The number of store quotes attempted, for random addresses
+    pub quotes_attempted: usize,
+    /// The number of store quotes that returned at least one quote
+    pub quotes_succeeded: usize,
+}
+
+impl NetworkHealthReport {
+    /// Median latency of the successful GETs, if any.
+    pub fn median_get_latency(&self) -> Option<Duration> {
+        let mut latencies = self.get_latencies.clone();
+        latencies.sort();
+        latencies.get(latencies.len() / 2).copied()
+    }
+
+    /// Share of the GETs that returned the record, if any were attempted.
+    pub fn get_success_rate(&self) -> Option<f64> {
+        rate(self.get_latencies.len(), self.gets_attempted)
+    }
+
+    /// Share of the store quotes that succeeded, if any were attempted.
+    pub fn store_quote_success_rate(&self) -> Option<f64> {
+        rate(self.quotes_succeeded, self.quotes_attempted)
+    }
+}
+
+fn rate(succeeded: usize, attempted: usize) -> Option<f64> {
+    (attempted > 0).then(|| succeeded as f64 / attempted as f64)
+}
+
+impl Client {
+    /// Probe the health of the network.
+    ///
+    /// Up to `probes` of the `known_addresses`, which should be held by the network, are fetched
+    /// to measure the GET latency, and `probes` random addresses are quoted to measure the success
+    /// of store quotes. Nothing is uploaded or paid for.
+    pub async fn network_health(
+        &self,
+        known_addresses: &[NetworkAddress],
+        probes: usize,
+    ) -> NetworkHealthReport {
+        let mut report = NetworkHealthReport::default();
+
+        match self.network.get_connectivity_info().await {
+            Ok(info) => {
+                report.time_to_first_peer = info.time_to_first_connection;
+                report.peers_in_routing_table = info.peers_in_routing_table;
+                if info.non_empty_kbuckets > 0 {
+                    report.routing_table_fill_rate = info.peers_in_routing_table as f64
+                        / (info.non_empty_kbuckets * K_VALUE.get()) as f64;
+                }
+            }
+            Err(err) => warn!("Failed to get the connectivity info: {err}"),
+        }
+
+        let sample: Vec<NetworkAddress> = known_addresses
+            .choose_multiple(&mut rand::thread_rng(), probes)
+            .cloned()
+            .collect();
+        report.gets_attempted = sample.len();
+        let get_results = join_all(sample.into_iter().map(|address| async move {
+            let start = Instant::now();
+            let result = self
+                .network
+                .get_record_and_holders(address.clone(), Quorum::One)
+                .await;
+            match result {
+                Ok((Some(_), _)) => Some(start.elapsed()),
+                Ok((None, _)) => {
+                    warn!("Health probe could not find the known record {address:?}");
+                    None
+                }
+                Err(err) => {
+                    warn!("Health probe failed to get the known record {address:?}: {err}");
+                    None
+                }
+            }
+        }))
+        .await;
+        report.get_latencies = get_results.into_iter().flatten().collect();
+
+        let quote_addrs: Vec<(XorName, usize)> = (0..probes)
+            .map(|_| (XorName::random(&mut rand::thread_rng()), MAX_CHUNK_SIZE))
+            .collect();
+        report.quotes_attempted = quote_addrs.len();
+        for result in self
+            .get_raw_quotes(DataTypes::Chunk, quote_addrs.into_iter())
+            .await
+        {
+            match result {
+                Ok((_, quotes)) if !quotes.is_empty() => report.quotes_succeeded += 1,
+                Ok((content_addr, _)) => {
+                    warn!("Health probe got no quotes for {content_addr:?}");
+                }
+                Err(err) => warn!("Health probe failed to get store quotes: {err:?}"),
+            }
+        }
+
+        info!("Network health report: {report:?}");
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_network_health_report_rates() {
+        let report = NetworkHealthReport {
+            gets_attempted: 4,
+            get_latencies: vec![
+                Duration::from_millis(300),
+                Duration::from_millis(100),
+                Duration::from_millis(200),
+            ],
+            quotes_attempted: 5,
+            quotes_succeeded: 4,
+            ..Default::default()
+        };
+        assert_eq!(
+            report.median_get_latency(),
+            Some(Duration::from_millis(200))
+        );
+        assert_eq!(report.get_success_rate(), Some(0.75));
+        assert_eq!(report.store_quote_success_rate(), Some(0.8));
+
+        let empty = NetworkHealthReport::default();
+        assert_eq!(empty.median_get_latency(), None);
+        assert_eq!(empty.get_success_rate(), None);
+        assert_eq!(empty.store_quote_success_rate(), None);
+    }
+}
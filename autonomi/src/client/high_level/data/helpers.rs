@@ -12,7 +12,9 @@ use crate::client::config::{UPLOAD_FLOW_BATCH_SIZE, upload_retry_pause};
 use crate::client::merkle_payments::MerklePaymentReceipt;
 use crate::client::payment::PayError::EvmWalletError;
 use crate::client::payment::PaymentOption;
-use crate::client::payment::Receipt;
+use crate::client::payment::{Receipt, reusable_payments};
+use crate::client::put_error_state::ChunkBatchUploadState;
+use crate::client::upload_plan::UploadBatchPlan;
 use crate::client::{ClientEvent, PutError, UploadSummary};
 use crate::self_encryption::EncryptionStream;
 use crate::utils::format_upload_error;
@@ -20,9 +22,127 @@ use ant_evm::{Amount, AttoTokens};
 use ant_protocol::storage::{Chunk, DataTypes};
 use bytes::Bytes;
 use evmlib::wallet::Error::InsufficientTokensForQuotes;
+use std::collections::VecDeque;
+use xor_name::XorName;
 
 type AggregatedChunks = Vec<((String, usize, usize), Chunk)>;
 
+/// A batch of chunks paid for, waiting to be uploaded.
+struct PaidChunkBatch {
+    batch: AggregatedChunks,
+    /// The payments made for the batch
+    receipt: Receipt,
+    /// The payments made for the batch, and the previous ones reused
+    upload_receipt: Receipt,
+    free_chunks: usize,
+}
+
+/// The outcome of paying for a batch of chunks.
+enum BatchPayment {
+    Paid(PaidChunkBatch),
+    /// The chunks to retry if a retry is scheduled, the error otherwise
+    Failed {
+        retry_chunks: AggregatedChunks,
+        put_error: Option<PutError>,
+    },
+}
+
+/// The state of a file upload whose payments are pipelined with its uploads.
+struct PaymentPipeline<'a> {
+    file: &'a mut EncryptionStream,
+    payment_option: PaymentOption,
+    processed_chunks: usize,
+    est_total_todo: usize,
+    /// Chunks that failed to upload or to be paid for, to pay for again with the next batch
+    to_retry: AggregatedChunks,
+    attempted_uploads: usize,
+    allowed_attempts: usize,
+    retry_on_failure: bool,
+}
+
+/// Attach the payments of the batches paid for ahead but not uploaded to the error ending a
+/// pipelined upload, so that they can be reused on resume instead of being paid for again.
+///
+/// The payments are merged into the error if it already carries some, otherwise the error is
+/// turned into a batch error listing the chunks not uploaded.
+fn with_unused_payments(
+    err: PutError,
+    unused: impl IntoIterator<Item = PaidChunkBatch>,
+) -> PutError {
+    let mut chunks = vec![];
+    let mut payments = Receipt::new();
+    for paid in unused {
+        chunks.extend(paid.batch.iter().map(|(_, chunk)| *chunk.address()));
+        payments.extend(paid.upload_receipt);
+    }
+    if payments.is_empty() {
+        return err;
+    }
+    info!(
+        "Upload failed with {} chunks paid for ahead but not uploaded, returning their payments",
+        chunks.len()
+    );
+
+    match err {
+        PutError::Batch(mut upload_state) => {
+            upload_state
+                .payment
+                .get_or_insert_with(Receipt::new)
+                .extend(payments);
+            upload_state.failed.extend(
+                chunks
+                    .into_iter()
+                    .map(|addr| (addr, "Paid for but not uploaded".to_string())),
+            );
+            PutError::Batch(upload_state)
+        }
+        PutError::Network {
+            address,
+            network_error,
+            payment,
+        } => {
+            let mut payment = payment.unwrap_or_default();
+            payment.extend(payments);
+            PutError::Network {
+                address,
+                network_error,
+                payment: Some(payment),
+            }
+        }
+        err => {
+            let reason = err.to_string();
+            PutError::Batch(ChunkBatchUploadState {
+                successful: vec![],
+                failed: chunks
+                    .into_iter()
+                    .map(|addr| (addr, reason.clone()))
+                    .collect(),
+                payment: Some(payments),
+            })
+        }
+    }
+}
+
+/// Takes up to `limit` of the next chunks of the file, with their position in the file.
+fn next_file_batch(
+    file: &mut EncryptionStream,
+    limit: usize,
+    processed_chunks: &mut usize,
+    est_total_todo: usize,
+) -> AggregatedChunks {
+    let Some(next_batch) = file.next_batch(limit) else {
+        return vec![];
+    };
+    let path = file.file_path.clone();
+    let aggr_batch: AggregatedChunks = next_batch
+        .into_iter()
+        .enumerate()
+        .map(|(i, chunk)| ((path.clone(), *processed_chunks + i, est_total_todo), chunk))
+        .collect();
+    *processed_chunks += aggr_batch.len();
+    aggr_batch
+}
+
 impl Client {
     /// Send an upload completion event to the client event channel.
     pub(crate) async fn send_upload_complete(
@@ -68,6 +188,28 @@ impl Client {
         }
     }
 
+    /// Send the plan of an upload batch to the client event channel.
+    pub(crate) async fn send_upload_batch_planned(&self, plan: &UploadBatchPlan) {
+        if let Some(sender) = &self.client_event_sender
+            && let Err(err) = sender
+                .send(ClientEvent::UploadBatchPlanned(plan.clone()))
+                .await
+        {
+            error!("Failed to send upload batch plan event: {err:?}");
+        }
+    }
+
+    /// Send the addresses re-quoted because their quotes expired to the client event channel.
+    pub(crate) async fn send_quotes_refreshed(&self, addresses: &[XorName]) {
+        if let Some(sender) = &self.client_event_sender
+            && let Err(err) = sender
+                .send(ClientEvent::QuotesRefreshed(addresses.to_vec()))
+                .await
+        {
+            error!("Failed to send quotes refreshed event: {err:?}");
+        }
+    }
+
     /// Returns total tokens spent or the first encountered upload error
     pub(crate) async fn calculate_total_cost(
         &self,
@@ -158,6 +300,12 @@ impl Client {
         payment_option: PaymentOption,
         file: &mut EncryptionStream,
     ) -> Result<(usize, usize, Vec<Receipt>), PutError> {
+        if self.config.pipeline_depth > 0 {
+            return self
+                .pay_and_upload_file_pipelined(payment_option, file)
+                .await;
+        }
+
         let est_total_todo = file.total_chunks();
         let mut processed_chunks = 0;
         let mut total_free_chunks = 0;
@@ -166,29 +314,20 @@ impl Client {
         // Allow up to `retry_failed` * est_total_chunks total uploads to be attempted
         let mut retry_on_failure = true;
         let mut attempted_uploads = 0;
-        let allowed_attempts =
-            est_total_todo + std::cmp::max(20, est_total_todo * self.retry_failed as usize);
+        let allowed_attempts = self.allowed_upload_attempts(est_total_todo);
 
         // Process all chunks for this file in batches
         let mut current_batch = vec![];
 
         loop {
             // Get next batch if current_batch has space and file has more chunks
-            if let Some(next_batch) = file.next_batch(*UPLOAD_FLOW_BATCH_SIZE - current_batch.len())
-            {
-                // prepare batch
-                let next_batch_len = next_batch.len();
-                let path = file.file_path.clone();
-                let aggr_batch: AggregatedChunks = next_batch
-                    .into_iter()
-                    .enumerate()
-                    .map(|(i, chunk)| ((path.clone(), processed_chunks + i, est_total_todo), chunk))
-                    .collect();
-                current_batch.extend(aggr_batch);
-
-                // process batch
-                processed_chunks += next_batch_len;
-            }
+            let space = *UPLOAD_FLOW_BATCH_SIZE - current_batch.len();
+            current_batch.extend(next_file_batch(
+                file,
+                space,
+                &mut processed_chunks,
+                est_total_todo,
+            ));
 
             // If we have no chunks to process, break the loop
             if current_batch.is_empty() {
@@ -224,15 +363,176 @@ impl Client {
         Ok((processed_chunks, total_free_chunks, receipts))
     }
 
+    /// Same as `pay_and_upload_file`, but pays for the next batches while the current one is
+    /// uploaded, keeping up to `pipeline_depth` batches paid for ahead of the upload, so that
+    /// payment confirmations and network PUTs overlap instead of alternating.
+    /// Batches are still uploaded in order, and the chunks failing to upload are paid for
+    /// again with the next batch. If the upload fails, the error carries the payments of the
+    /// batches paid for ahead, see [`with_unused_payments`].
+    /// Returns: (processed_chunks, total_free_chunks, receipt)
+    async fn pay_and_upload_file_pipelined(
+        &self,
+        payment_option: PaymentOption,
+        file: &mut EncryptionStream,
+    ) -> Result<(usize, usize, Vec<Receipt>), PutError> {
+        let pipeline_depth = self.config.pipeline_depth;
+        let est_total_todo = file.total_chunks();
+        let mut pipeline = PaymentPipeline {
+            file,
+            payment_option,
+            processed_chunks: 0,
+            est_total_todo,
+            to_retry: vec![],
+            attempted_uploads: 0,
+            allowed_attempts: self.allowed_upload_attempts(est_total_todo),
+            retry_on_failure: true,
+        };
+        let mut total_free_chunks = 0;
+        let mut receipts = vec![];
+        let mut paid_batches = VecDeque::new();
+
+        loop {
+            // Nothing paid for yet, or all the paid batches were uploaded: pay for the next one
+            if paid_batches.is_empty() {
+                let (paid, put_error) = self.pay_next_batches(&mut pipeline, 1).await;
+                if let Some(err) = put_error {
+                    return Err(with_unused_payments(err, paid));
+                }
+                if paid.is_empty() {
+                    if pipeline.to_retry.is_empty() {
+                        break;
+                    }
+                    // the payment failed, and is scheduled for retry
+                    upload_retry_pause().await;
+                    continue;
+                }
+                paid_batches.extend(paid);
+            }
+            let Some(paid_batch) = paid_batches.pop_front() else {
+                continue;
+            };
+            total_free_chunks += paid_batch.free_chunks;
+            receipts.push(paid_batch.receipt.clone());
+
+            // Upload the oldest paid batch while paying for the next ones
+            let pay_ahead = pipeline_depth.saturating_sub(paid_batches.len());
+            let ((retry_chunks, upload_error), (paid, payment_error)) = tokio::join!(
+                self.upload_paid_chunk_batch(paid_batch, pipeline.retry_on_failure),
+                self.pay_next_batches(&mut pipeline, pay_ahead)
+            );
+            paid_batches.extend(paid);
+
+            if let Some(err) = upload_error.or(payment_error) {
+                return Err(with_unused_payments(err, paid_batches));
+            }
+
+            if !retry_chunks.is_empty() {
+                crate::loud_info!(
+                    "🔄 Retrying {} chunks with the next batch...",
+                    retry_chunks.len()
+                );
+                pipeline.to_retry.extend(retry_chunks);
+            }
+        }
+
+        Ok((pipeline.processed_chunks, total_free_chunks, receipts))
+    }
+
+    /// Pay for up to `count` batches, made of the chunks to retry first, then of the next chunks
+    /// of the file. Stops at the first payment failure, its chunks being scheduled for retry.
+    /// Returns: (paid_batches, error_if_retry_on_failure_not_enabled)
+    async fn pay_next_batches(
+        &self,
+        pipeline: &mut PaymentPipeline<'_>,
+        count: usize,
+    ) -> (Vec<PaidChunkBatch>, Option<PutError>) {
+        let mut paid_batches = vec![];
+        while paid_batches.len() < count {
+            let take = (*UPLOAD_FLOW_BATCH_SIZE).min(pipeline.to_retry.len());
+            let mut batch: AggregatedChunks = pipeline.to_retry.drain(..take).collect();
+            let space = *UPLOAD_FLOW_BATCH_SIZE - batch.len();
+            batch.extend(next_file_batch(
+                pipeline.file,
+                space,
+                &mut pipeline.processed_chunks,
+                pipeline.est_total_todo,
+            ));
+            if batch.is_empty() {
+                break;
+            }
+
+            if pipeline.attempted_uploads > pipeline.allowed_attempts {
+                pipeline.retry_on_failure = false;
+            }
+            pipeline.attempted_uploads += batch.len();
+            match self
+                .pay_chunk_batch(
+                    batch,
+                    pipeline.payment_option.clone(),
+                    pipeline.retry_on_failure,
+                )
+                .await
+            {
+                BatchPayment::Paid(paid) => paid_batches.push(paid),
+                BatchPayment::Failed {
+                    retry_chunks,
+                    put_error,
+                } => {
+                    pipeline.to_retry.extend(retry_chunks);
+                    return (paid_batches, put_error);
+                }
+            }
+        }
+        (paid_batches, None)
+    }
+
+    /// The total number of chunk uploads allowed for a file, retries included.
+    fn allowed_upload_attempts(&self, est_total_todo: usize) -> usize {
+        est_total_todo + std::cmp::max(20, est_total_todo * self.retry_failed as usize)
+    }
+
     /// Processes a single batch of chunks (quote -> pay -> upload)
     /// Returns: (failed_chunks_for_retry, receipt, free_chunks_counts, error_if_retry_on_failure_not_enabled)
-    #[allow(clippy::too_many_arguments)]
     async fn process_chunk_batch(
         &self,
-        mut batch: AggregatedChunks,
+        batch: AggregatedChunks,
         payment_option: PaymentOption,
         retry_on_failure: bool,
     ) -> (AggregatedChunks, Vec<Receipt>, usize, Option<PutError>) {
+        let paid = match self
+            .pay_chunk_batch(batch, payment_option, retry_on_failure)
+            .await
+        {
+            BatchPayment::Paid(paid) => paid,
+            BatchPayment::Failed {
+                retry_chunks,
+                put_error,
+            } => return (retry_chunks, vec![], 0, put_error),
+        };
+
+        let receipt = paid.receipt.clone();
+        let free_chunks = paid.free_chunks;
+        let (retry_chunks, put_error) = self.upload_paid_chunk_batch(paid, retry_on_failure).await;
+        (retry_chunks, vec![receipt], free_chunks, put_error)
+    }
+
+    /// Quotes and pays for a batch of chunks, reusing the previous payments still valid.
+    async fn pay_chunk_batch(
+        &self,
+        mut batch: AggregatedChunks,
+        payment_option: PaymentOption,
+        retry_on_failure: bool,
+    ) -> BatchPayment {
+        // Order the batch so that chunks stored by the same peers are processed together
+        let plan = UploadBatchPlan::new(batch.iter().map(|(_, chunk)| *chunk.name()));
+        batch.sort_by_key(|(_, chunk)| *chunk.name());
+        debug!(
+            "Planned batch of {} chunks in {} neighbourhoods",
+            plan.chunk_count(),
+            plan.neighbourhoods.len()
+        );
+        self.send_upload_batch_planned(&plan).await;
+
         // Prepare payment info for batch
         let payment_info: Vec<_> = batch
             .iter()
@@ -241,16 +541,7 @@ impl Client {
 
         crate::loud_info!("Processing batch of {} chunks", batch.len());
 
-        let mut file_infos = vec![];
-        let mut batch_chunks = vec![];
-        let mut put_error = None;
-
-        for (chunk_info, chunk) in batch.clone() {
-            file_infos.push(chunk_info);
-            batch_chunks.push(chunk);
-        }
-
-        for (file_name, i, est_total) in file_infos.iter() {
+        for ((file_name, i, est_total), _) in batch.iter() {
             let maybe_file = if !file_name.is_empty() {
                 &format!(" of {file_name}")
             } else {
@@ -259,30 +550,66 @@ impl Client {
             crate::loud_info!("Processing chunk ({}/{est_total}){maybe_file}", i + 1);
         }
 
+        // Reuse the still valid payments of chunks paid for by a previous upload attempt.
+        // Expired payments are dropped as per the timestamp of their quotes, on the network clock
+        let (payment_option, previous_payments) = match payment_option {
+            PaymentOption::ContinueWithReceipt(wallet, receipt) => {
+                let previous_payments = reusable_payments(
+                    &receipt,
+                    payment_info.iter().map(|(addr, _)| addr),
+                    self.network_time(),
+                );
+                (PaymentOption::Wallet(wallet), previous_payments)
+            }
+            payment_option => (payment_option, Receipt::new()),
+        };
+        if !previous_payments.is_empty() {
+            crate::loud_info!(
+                "Reusing previous payments of {} chunks in this batch",
+                previous_payments.len()
+            );
+        }
+        let payment_info: Vec<_> = payment_info
+            .into_iter()
+            .filter(|(addr, _)| !previous_payments.contains_key(addr))
+            .collect();
+
         // Check if this is a new payment (wallet) vs cached receipt
         let is_new_payment = matches!(&payment_option, PaymentOption::Wallet(_));
 
         // Process payment for this batch
-        let (receipt, free_chunks) = match self
-            .pay_for_content_addrs(DataTypes::Chunk, payment_info.into_iter(), payment_option)
-            .await
-        {
+        let payment_result = if payment_info.is_empty() {
+            Ok((Receipt::new(), 0))
+        } else {
+            self.pay_for_content_addrs(DataTypes::Chunk, payment_info.into_iter(), payment_option)
+                .await
+        };
+        let (receipt, free_chunks) = match payment_result {
             Ok((receipt, free_chunks)) => (receipt, free_chunks),
             Err(err) if matches!(err, EvmWalletError(InsufficientTokensForQuotes(_, _))) => {
                 crate::loud_error!("Insufficient tokens: {err:?}. Returning immediately.");
-                return (vec![], vec![], 0, Some(PutError::from(err)));
+                return BatchPayment::Failed {
+                    retry_chunks: vec![],
+                    put_error: Some(PutError::from(err)),
+                };
             }
             Err(err) => {
                 return if retry_on_failure {
                     crate::loud_error!(
                         "Quoting or payment error encountered, retry scheduled: {err}"
                     );
-                    (batch, vec![], 0, None)
+                    BatchPayment::Failed {
+                        retry_chunks: batch,
+                        put_error: None,
+                    }
                 } else {
                     crate::loud_error!(
                         "Quoting or payment error encountered, no retry scheduled: {err}"
                     );
-                    (vec![], vec![], 0, Some(PutError::from(err)))
+                    BatchPayment::Failed {
+                        retry_chunks: vec![],
+                        put_error: Some(PutError::from(err)),
+                    }
                 };
             }
         };
@@ -290,7 +617,7 @@ impl Client {
         if free_chunks > 0 {
             crate::loud_info!(
                 "{free_chunks} chunks were free in this batch {}",
-                batch_chunks.len()
+                batch.len()
             );
         }
 
@@ -301,10 +628,43 @@ impl Client {
             self.send_regular_batch_payment_complete(&receipt).await;
         }
 
+        // Reused payments are only used for the upload, they were accounted for when made
+        let mut upload_receipt = previous_payments;
+        upload_receipt.extend(
+            receipt
+                .iter()
+                .map(|(addr, payment)| (*addr, payment.clone())),
+        );
+
+        BatchPayment::Paid(PaidChunkBatch {
+            batch,
+            receipt,
+            upload_receipt,
+            free_chunks,
+        })
+    }
+
+    /// Uploads a paid batch of chunks.
+    /// Returns: (failed_chunks_for_retry, error_if_retry_on_failure_not_enabled)
+    async fn upload_paid_chunk_batch(
+        &self,
+        paid: PaidChunkBatch,
+        retry_on_failure: bool,
+    ) -> (AggregatedChunks, Option<PutError>) {
+        let PaidChunkBatch {
+            mut batch,
+            upload_receipt,
+            ..
+        } = paid;
+
         // Upload all chunks in batch, schedule failed_chunks for retry (if retry_failed set)
         let mut retry_chunks = vec![];
+        let mut put_error = None;
         match self
-            .chunk_batch_upload(batch_chunks.iter().collect(), &receipt)
+            .chunk_batch_upload(
+                batch.iter().map(|(_, chunk)| chunk).collect(),
+                &upload_receipt,
+            )
             .await
         {
             // No upload failure encountered
@@ -331,7 +691,7 @@ impl Client {
             Err(err) => put_error = Some(err),
         }
 
-        (retry_chunks, vec![receipt], free_chunks, put_error)
+        (retry_chunks, put_error)
     }
 
     /// Internal helper for uploading in-memory data.
@@ -342,7 +702,14 @@ impl Client {
         payment_option: PaymentOption,
         is_public: bool,
     ) -> Result<(AttoTokens, DataMapChunk), PutError> {
-        let (chunk_stream, data_map_chunk) = EncryptionStream::new_in_memory(data, is_public)?;
+        let (chunk_stream, data_map_chunk) = EncryptionStream::new_in_memory_compressed(
+            "".to_string(),
+            Default::default(),
+            Default::default(),
+            is_public,
+            data,
+            self.config.compression,
+        )?;
         let mut chunk_streams = vec![chunk_stream];
         let total_cost = self
             .pay_and_upload(payment_option, &mut chunk_streams)
@@ -350,3 +717,78 @@ impl Client {
         Ok((total_cost, data_map_chunk))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ant_evm::ClientProofOfPayment;
+    use ant_protocol::storage::ChunkAddress;
+
+    fn paid_batch(content: &'static [u8]) -> PaidChunkBatch {
+        let chunk = Chunk::new(Bytes::from_static(content));
+        let receipt = Receipt::from([(
+            *chunk.name(),
+            (
+                ClientProofOfPayment {
+                    peer_quotes: vec![],
+                },
+                AttoTokens::from_atto(Amount::from(1u64)),
+            ),
+        )]);
+        PaidChunkBatch {
+            batch: vec![((String::new(), 0, 1), chunk)],
+            receipt: receipt.clone(),
+            upload_receipt: receipt,
+            free_chunks: 0,
+        }
+    }
+
+    #[test]
+    fn unused_payments_are_merged_into_a_batch_error() {
+        let failed = ChunkAddress::new(XorName::random(&mut rand::thread_rng()));
+        let err = PutError::Batch(ChunkBatchUploadState {
+            successful: vec![],
+            failed: vec![(failed, "failed".to_string())],
+            payment: None,
+        });
+        let unused = paid_batch(b"paid ahead");
+        let unused_addr = *unused.batch[0].1.address();
+
+        let PutError::Batch(upload_state) = with_unused_payments(err, vec![unused]) else {
+            panic!("A batch error shall stay a batch error");
+        };
+        let payment = upload_state
+            .payment
+            .expect("The payments shall be returned");
+        assert!(payment.contains_key(unused_addr.xorname()));
+        assert_eq!(upload_state.failed.len(), 2);
+        assert_eq!(upload_state.failed[1].0, unused_addr);
+    }
+
+    #[test]
+    fn unused_payments_turn_other_errors_into_a_batch_error() {
+        let err = PutError::PayeesMissing;
+        let unused = vec![
+            paid_batch(b"first paid ahead"),
+            paid_batch(b"second paid ahead"),
+        ];
+
+        let PutError::Batch(upload_state) = with_unused_payments(err, unused) else {
+            panic!("The error shall carry the unused payments");
+        };
+        assert_eq!(upload_state.payment.map(|payment| payment.len()), Some(2));
+        assert_eq!(upload_state.failed.len(), 2);
+        assert!(
+            upload_state
+                .failed
+                .iter()
+                .all(|(_, reason)| *reason == PutError::PayeesMissing.to_string())
+        );
+    }
+
+    #[test]
+    fn errors_are_kept_without_unused_payments() {
+        let err = with_unused_payments(PutError::PayeesMissing, vec![]);
+        assert!(matches!(err, PutError::PayeesMissing));
+    }
+}
@@ -16,8 +16,10 @@ pub mod private;
 pub mod public;
 
 mod helpers;
+mod options;
 mod stream;
 
+pub use options::{MIN_CHUNK_SIZE_TARGET, PutOptions, PutOptionsError};
 pub use stream::DataStream;
 
 /// A [`DataAddress`] which points to a DataMap
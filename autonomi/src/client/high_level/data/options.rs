@@ -0,0 +1,268 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use crate::Client;
+use crate::client::data_types::chunk::DataMapChunk;
+use crate::client::high_level::files::{Segment, pack_segments};
+use crate::client::payment::PaymentOption;
+use crate::client::quote::add_costs;
+use crate::client::{PutError, high_level::data::DataAddress};
+use crate::self_encryption::{EncryptionStream, MAX_CHUNK_SIZE};
+use ant_evm::AttoTokens;
+use bytes::Bytes;
+use std::collections::HashSet;
+
+/// The smallest chunk size target accepted. Every chunk is quoted and paid for on its own, so
+/// smaller chunks would multiply the cost of storing data.
+pub const MIN_CHUNK_SIZE_TARGET: usize = 64 * 1024;
+
+/// Self-encryption splits data into at least this many chunks.
+const MIN_CHUNKS_PER_ENCRYPTION: usize = 3;
+
+/// Errors of invalid [`PutOptions`].
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum PutOptionsError {
+    #[error("Chunk size {size} is out of the {min}..={max} bytes accepted by the network")]
+    ChunkSizeOutOfBounds { size: usize, min: usize, max: usize },
+    #[error("Minimum split size {size} is above the {max} bytes encrypted in at most 3 chunks")]
+    MinSplitSizeTooLarge { size: usize, max: usize },
+}
+
+/// Tuning of the self-encryption of uploaded data.
+///
+/// Self-encryption splits data into at least 3 chunks, of at most [`MAX_CHUNK_SIZE`] bytes. A
+/// smaller chunk size target makes data be encrypted in segments of 3 chunks of at most that
+/// size, each segment costing the chunks of its own data map on top of its content: smaller chunks
+/// spread data over more nodes and are faster to fetch in parallel, but cost more to store. The
+/// data maps of the segments are listed in a single chunk, so an upload split in more segments
+/// than fit in a chunk fails before paying.
+///
+/// The default options encrypt data as a whole, like [`Client::data_put`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PutOptions {
+    chunk_size: usize,
+    min_split_size: usize,
+}
+
+impl Default for PutOptions {
+    fn default() -> Self {
+        Self {
+            chunk_size: MAX_CHUNK_SIZE,
+            min_split_size: 0,
+        }
+    }
+}
+
+impl PutOptions {
+    /// Create the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the target size of the chunks, from [`MIN_CHUNK_SIZE_TARGET`] to [`MAX_CHUNK_SIZE`].
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Result<Self, PutOptionsError> {
+        if !(MIN_CHUNK_SIZE_TARGET..=MAX_CHUNK_SIZE).contains(&chunk_size) {
+            return Err(PutOptionsError::ChunkSizeOutOfBounds {
+                size: chunk_size,
+                min: MIN_CHUNK_SIZE_TARGET,
+                max: MAX_CHUNK_SIZE,
+            });
+        }
+        self.chunk_size = chunk_size;
+        Ok(self)
+    }
+
+    /// Set the size of data below which it is encrypted as a whole in the minimum of 3 chunks,
+    /// larger than the chunk size target if need be, saving the data maps of its segments. At most
+    /// 3 times [`MAX_CHUNK_SIZE`], so that no chunk exceeds the size accepted by the network.
+    pub fn with_min_split_size(mut self, min_split_size: usize) -> Result<Self, PutOptionsError> {
+        let max = MIN_CHUNKS_PER_ENCRYPTION * MAX_CHUNK_SIZE;
+        if min_split_size > max {
+            return Err(PutOptionsError::MinSplitSizeTooLarge {
+                size: min_split_size,
+                max,
+            });
+        }
+        self.min_split_size = min_split_size;
+        Ok(self)
+    }
+
+    /// The target size of the chunks.
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    /// The size of data below which it is encrypted as a whole.
+    pub fn min_split_size(&self) -> usize {
+        self.min_split_size
+    }
+
+    /// The size of the segments `data_len` bytes of data are encrypted in, `None` to encrypt the
+    /// data as a whole.
+    fn segment_size(&self, data_len: usize) -> Option<usize> {
+        let segment_size = MIN_CHUNKS_PER_ENCRYPTION * self.chunk_size;
+        if self.chunk_size >= MAX_CHUNK_SIZE
+            || data_len <= segment_size
+            || data_len <= self.min_split_size
+        {
+            return None;
+        }
+        Some(segment_size)
+    }
+}
+
+/// Split data into segments of `segment_size` bytes. A last segment too small to be
+/// self-encrypted is merged into the one before it.
+fn split_segments(data: &Bytes, segment_size: usize) -> Vec<Bytes> {
+    let mut segments: Vec<Bytes> = vec![];
+    for start in (0..data.len()).step_by(segment_size) {
+        let end = (start + segment_size).min(data.len());
+        match segments.last_mut() {
+            Some(last) if end - start < MIN_CHUNKS_PER_ENCRYPTION => {
+                *last = data.slice(start - last.len()..end);
+            }
+            _ => segments.push(data.slice(start..end)),
+        }
+    }
+    segments
+}
+
+impl Client {
+    /// Same as [`Client::data_put`], tuning self-encryption with the given [`PutOptions`].
+    pub async fn data_put_with_options(
+        &self,
+        data: Bytes,
+        payment_option: PaymentOption,
+        options: PutOptions,
+    ) -> Result<(AttoTokens, DataMapChunk), PutError> {
+        self.data_put_internal_with_options(data, payment_option, false, options)
+            .await
+    }
+
+    /// Same as [`Client::data_put_public`], tuning self-encryption with the given [`PutOptions`].
+    pub async fn data_put_public_with_options(
+        &self,
+        data: Bytes,
+        payment_option: PaymentOption,
+        options: PutOptions,
+    ) -> Result<(AttoTokens, DataAddress), PutError> {
+        let (total_cost, data_map_chunk) = self
+            .data_put_internal_with_options(data, payment_option, true, options)
+            .await?;
+        let data_address = DataAddress::new(*data_map_chunk.0.address().xorname());
+        Ok((total_cost, data_address))
+    }
+
+    async fn data_put_internal_with_options(
+        &self,
+        data: Bytes,
+        payment_option: PaymentOption,
+        is_public: bool,
+        options: PutOptions,
+    ) -> Result<(AttoTokens, DataMapChunk), PutError> {
+        let Some(segment_size) = options.segment_size(data.len()) else {
+            return self
+                .data_put_internal(data, payment_option, is_public)
+                .await;
+        };
+
+        let mut segments = vec![];
+        let mut streams = vec![];
+        let mut known = HashSet::new();
+        for segment in split_segments(&data, segment_size) {
+            let size = segment.len() as u64;
+            let (stream, data_map_chunk) = EncryptionStream::new_in_memory(segment, false)?;
+            let data_map = data_map_chunk.0.value().clone();
+            // Identical segments share their chunks
+            if known.insert(data_map.clone()) {
+                streams.push(stream);
+            }
+            segments.push(Segment { size, data_map });
+        }
+        debug!(
+            "Encrypted {} bytes in {} segments of {segment_size} bytes",
+            data.len(),
+            segments.len()
+        );
+
+        // Fail before paying when the list of segments doesn't fit in a chunk
+        let data_map_chunk = pack_segments(segments)?;
+        let mut total_cost = self
+            .pay_and_upload(payment_option.clone(), &mut streams)
+            .await?;
+        if is_public {
+            let (cost, _addr) = self.chunk_put(&data_map_chunk.0, payment_option).await?;
+            total_cost = add_costs(total_cost, cost)?;
+        }
+        Ok((total_cost, data_map_chunk))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_put_options_bounds() {
+        assert!(
+            PutOptions::new()
+                .with_chunk_size(MIN_CHUNK_SIZE_TARGET)
+                .is_ok()
+        );
+        assert!(PutOptions::new().with_chunk_size(MAX_CHUNK_SIZE).is_ok());
+        assert!(matches!(
+            PutOptions::new().with_chunk_size(MIN_CHUNK_SIZE_TARGET - 1),
+            Err(PutOptionsError::ChunkSizeOutOfBounds { .. })
+        ));
+        assert!(matches!(
+            PutOptions::new().with_chunk_size(MAX_CHUNK_SIZE + 1),
+            Err(PutOptionsError::ChunkSizeOutOfBounds { .. })
+        ));
+        assert!(matches!(
+            PutOptions::new().with_min_split_size(3 * MAX_CHUNK_SIZE + 1),
+            Err(PutOptionsError::MinSplitSizeTooLarge { .. })
+        ));
+    }
+
+    #[test]
+    fn test_segment_size() {
+        // data is encrypted as a whole by default
+        assert_eq!(PutOptions::new().segment_size(100 * MAX_CHUNK_SIZE), None);
+
+        let options = PutOptions::new()
+            .with_chunk_size(MIN_CHUNK_SIZE_TARGET)
+            .unwrap();
+        let segment_size = 3 * MIN_CHUNK_SIZE_TARGET;
+        assert_eq!(options.segment_size(segment_size), None);
+        assert_eq!(options.segment_size(segment_size + 1), Some(segment_size));
+
+        let options = options.with_min_split_size(10 * segment_size).unwrap();
+        assert_eq!(options.segment_size(10 * segment_size), None);
+        assert_eq!(
+            options.segment_size(10 * segment_size + 1),
+            Some(segment_size)
+        );
+    }
+
+    #[test]
+    fn test_split_segments_merges_tiny_tail() {
+        let data = Bytes::from(vec![1u8; 20]);
+        let segments = split_segments(&data, 6);
+        assert_eq!(
+            segments.iter().map(Bytes::len).collect::<Vec<_>>(),
+            vec![6, 6, 8]
+        );
+        assert_eq!(segments.concat(), data.to_vec());
+
+        let segments = split_segments(&Bytes::from(vec![1u8; 21]), 6);
+        assert_eq!(
+            segments.iter().map(Bytes::len).collect::<Vec<_>>(),
+            vec![6, 6, 6, 3]
+        );
+    }
+}
@@ -8,8 +8,10 @@
 
 use crate::AttoTokens;
 use crate::Client;
+use crate::client::high_level::files::is_segmented;
 use crate::client::payment::PaymentOption;
 use crate::client::{GetError, PutError};
+use crate::self_encryption::decompress;
 
 pub use crate::Bytes;
 pub use crate::client::data_types::chunk::DataMapChunk;
@@ -37,6 +39,10 @@ impl Client {
             data_map.0.address()
         );
 
+        if is_segmented(data_map) {
+            return self.segmented_data_get(data_map).await;
+        }
+
         let mut datamap = self.restore_data_map_from_chunk(data_map).await?;
         let chunk_count = datamap.infos().len();
 
@@ -51,6 +57,8 @@ impl Client {
 
         datamap.child = None;
         let data = self.fetch_from_data_map(&datamap).await?;
+        let data =
+            decompress(data, Self::data_map_compression(data_map)).map_err(GetError::Decryption)?;
         debug!(
             "Successfully fetched private data ({} chunks) in-memory",
             chunk_count
@@ -83,6 +91,13 @@ impl Client {
             data_map.0.address()
         );
 
+        if is_segmented(data_map) {
+            return Err(GetError::Configuration(
+                "Data uploaded in segments can't be streamed, download it with file_download instead"
+                    .to_string(),
+            ));
+        }
+
         let datamap = self.restore_data_map_from_chunk(data_map).await?;
         let chunk_count = datamap.infos().len();
 
@@ -91,7 +106,8 @@ impl Client {
             chunk_count
         );
 
-        DataStream::new(self.clone(), datamap)
+        // Compressed data is decompressed as it is decrypted
+        DataStream::new(self.clone(), datamap, Self::data_map_compression(data_map))
     }
 
     /// Upload a piece of private data to the network. This data will be self-encrypted.
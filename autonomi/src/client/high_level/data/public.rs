@@ -14,7 +14,7 @@ use crate::client::payment::PaymentOption;
 use crate::client::quote::CostError;
 use crate::client::{GetError, PutError};
 use crate::data::DataStream;
-use crate::self_encryption::encrypt;
+use crate::self_encryption::{compress, encrypt, wrap_data_map_chunk};
 use ant_evm::{Amount, AttoTokens};
 use ant_protocol::storage::DataTypes;
 use bytes::Bytes;
@@ -85,7 +85,9 @@ impl Client {
         data: Bytes,
     ) -> Result<Vec<(XorName, usize)>, CostError> {
         let now = Instant::now();
+        let (data, compression) = compress(data, self.config.compression)?;
         let (data_map_chunk, chunks) = encrypt(data)?;
+        let data_map_chunk = wrap_data_map_chunk(data_map_chunk, compression)?;
 
         debug!("Encryption took: {:.2?}", now.elapsed());
 
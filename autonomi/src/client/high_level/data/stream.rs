@@ -12,16 +12,29 @@ use xor_name::XorName;
 use crate::Bytes;
 use crate::Client;
 use crate::client::GetError;
+use crate::self_encryption::Compression;
+use zstd::stream::raw::{Decoder, Operation};
 
 type ChunkFetcher =
     Box<dyn Fn(&[(usize, XorName)]) -> self_encryption::Result<Vec<(usize, Bytes)>> + Send + Sync>;
 
+/// Size of the buffer the decompressed data is written to.
+const DECOMPRESSION_BUFFER_SIZE: usize = 128 * 1024;
+
 pub struct DataStream {
     streaming_decrypt: self_encryption::DecryptionStream<ChunkFetcher>,
+    /// Decompresses the decrypted data, for data compressed before encryption
+    decompression: Option<Decoder<'static>>,
 }
 
 impl DataStream {
-    pub(crate) fn new(client: Client, datamap: self_encryption::DataMap) -> Result<Self, GetError> {
+    /// Stream the data of `datamap`, decompressing it as it is decrypted when it was compressed
+    /// with `compression` before encryption.
+    pub(crate) fn new(
+        client: Client,
+        datamap: self_encryption::DataMap,
+        compression: Compression,
+    ) -> Result<Self, GetError> {
         let client_clone = client.clone();
 
         // Create the chunk fetcher function that the streaming decrypt will use
@@ -45,14 +58,37 @@ impl DataStream {
         let streaming_decrypt = self_encryption::streaming_decrypt(&datamap, chunk_fetcher)
             .map_err(|e| GetError::Decryption(crate::self_encryption::Error::SelfEncryption(e)))?;
 
-        Ok(Self { streaming_decrypt })
+        let decompression = match compression {
+            Compression::None => None,
+            Compression::Zstd(_) => Some(Decoder::new().map_err(|e| {
+                GetError::Decryption(crate::self_encryption::Error::Compression(e))
+            })?),
+        };
+
+        Ok(Self {
+            streaming_decrypt,
+            decompression,
+        })
     }
 
-    /// Returns the original data size
+    /// Returns the original data size, or the size of the compressed data if the data was
+    /// compressed before encryption.
     pub fn data_size(&self) -> usize {
         self.streaming_decrypt.file_size()
     }
 
+    /// The decrypted data, for random access. Compressed data can only be read sequentially,
+    /// through the iterator.
+    fn random_access(&self) -> Result<&self_encryption::DecryptionStream<ChunkFetcher>, GetError> {
+        if self.decompression.is_some() {
+            return Err(GetError::Configuration(
+                "Compressed data can't be read by range, iterate over the stream instead"
+                    .to_string(),
+            ));
+        }
+        Ok(&self.streaming_decrypt)
+    }
+
     /// Decrypts and returns a specific byte range from the encrypted data.
     ///
     /// This method provides random access to any portion of the encrypted file
@@ -67,42 +103,42 @@ impl DataStream {
     ///
     /// * `Result<Bytes>` - The decrypted range of data or an error if chunks are missing/corrupted
     pub fn get_range(&self, start: usize, len: usize) -> Result<Bytes, GetError> {
-        self.streaming_decrypt
+        self.random_access()?
             .get_range(start, len)
             .map_err(|e| GetError::Decryption(crate::self_encryption::Error::SelfEncryption(e)))
     }
 
     /// Convenience method to get a range using Range syntax.
     pub fn range(&self, range: std::ops::Range<usize>) -> Result<Bytes, GetError> {
-        self.streaming_decrypt
+        self.random_access()?
             .range(range)
             .map_err(|e| GetError::Decryption(crate::self_encryption::Error::SelfEncryption(e)))
     }
 
     /// Convenience method to get a range from a starting position to the end of the file.
     pub fn range_from(&self, start: usize) -> Result<Bytes, GetError> {
-        self.streaming_decrypt
+        self.random_access()?
             .range_from(start)
             .map_err(|e| GetError::Decryption(crate::self_encryption::Error::SelfEncryption(e)))
     }
 
     /// Convenience method to get a range from the beginning of the file to an end position.
     pub fn range_to(&self, end: usize) -> Result<Bytes, GetError> {
-        self.streaming_decrypt
+        self.random_access()?
             .range_to(end)
             .map_err(|e| GetError::Decryption(crate::self_encryption::Error::SelfEncryption(e)))
     }
 
     /// Convenience method to get the entire file content.
     pub fn range_full(&self) -> Result<Bytes, GetError> {
-        self.streaming_decrypt
+        self.random_access()?
             .range_full()
             .map_err(|e| GetError::Decryption(crate::self_encryption::Error::SelfEncryption(e)))
     }
 
     /// Convenience method to get an inclusive range.
     pub fn range_inclusive(&self, start: usize, end: usize) -> Result<Bytes, GetError> {
-        self.streaming_decrypt
+        self.random_access()?
             .range_inclusive(start, end)
             .map_err(|e| GetError::Decryption(crate::self_encryption::Error::SelfEncryption(e)))
     }
@@ -112,6 +148,29 @@ impl Iterator for DataStream {
     type Item = Result<Bytes, GetError>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if let Some(decoder) = &mut self.decompression {
+            // A decrypted chunk may not decompress to any data yet, e.g. a frame header only
+            loop {
+                let chunk_bytes = match self.streaming_decrypt.next()? {
+                    Ok(chunk_bytes) => chunk_bytes,
+                    Err(e) => {
+                        return Some(Err(GetError::Decryption(
+                            crate::self_encryption::Error::SelfEncryption(e),
+                        )));
+                    }
+                };
+                match decompress_chunk(decoder, &chunk_bytes) {
+                    Ok(data) if data.is_empty() => continue,
+                    Ok(data) => return Some(Ok(data)),
+                    Err(e) => {
+                        return Some(Err(GetError::Decryption(
+                            crate::self_encryption::Error::Compression(e),
+                        )));
+                    }
+                }
+            }
+        }
+
         // Get the next chunk from the streaming decrypt iterator
         match self.streaming_decrypt.next() {
             Some(Ok(chunk_bytes)) => {
@@ -132,6 +191,22 @@ impl Iterator for DataStream {
     }
 }
 
+/// Decompress a decrypted chunk of compressed data, returning the data decompressed so far.
+fn decompress_chunk(decoder: &mut Decoder<'static>, chunk_bytes: &[u8]) -> std::io::Result<Bytes> {
+    let mut input = chunk_bytes;
+    let mut output = vec![0u8; DECOMPRESSION_BUFFER_SIZE];
+    let mut data = Vec::new();
+    loop {
+        let status = decoder.run_on_buffers(input, &mut output)?;
+        data.extend_from_slice(&output[..status.bytes_written]);
+        input = &input[status.bytes_read..];
+        // Once the output buffer isn't filled up, the decoder has flushed all it could
+        if input.is_empty() && status.bytes_written < output.len() {
+            return Ok(Bytes::from(data));
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[allow(unused_imports)]
@@ -181,7 +256,10 @@ mod tests {
                 .expect("Failed to create streaming decrypt");
 
         // Create DataStream with our mocked streaming_decrypt
-        let data_stream = DataStream { streaming_decrypt };
+        let data_stream = DataStream {
+            streaming_decrypt,
+            decompression: None,
+        };
 
         // Test data_size method
         assert_eq!(data_stream.data_size(), test_data.len());
@@ -258,7 +336,10 @@ mod tests {
                 .expect("Failed to create streaming decrypt");
 
         // Create DataStream with our mocked streaming_decrypt
-        let data_stream = DataStream { streaming_decrypt };
+        let data_stream = DataStream {
+            streaming_decrypt,
+            decompression: None,
+        };
 
         // Test range beyond file size
         let beyond_range = data_stream.get_range(10000, 1000).unwrap();
@@ -369,4 +450,63 @@ mod tests {
             "data_get and data_stream results don't match"
         );
     }
+
+    #[tokio::test]
+    async fn test_data_stream_of_compressed_data() {
+        use crate::self_encryption::{compress, unwrap_data_map_chunk, wrap_data_map_chunk};
+        use std::collections::HashMap;
+
+        // Compressible data spanning several chunks once compressed
+        let test_data = crate::Bytes::from(
+            (0..200_000u32)
+                .flat_map(|i| (i % 1000).to_le_bytes())
+                .collect::<Vec<u8>>(),
+        );
+        let (compressed, compression) = compress(test_data.clone(), Compression::Zstd(3))
+            .expect("Failed to compress test data");
+        assert_eq!(compression, Compression::Zstd(3));
+
+        let (data_map_chunk, chunks) =
+            crate::self_encryption::encrypt(compressed).expect("Failed to encrypt test data");
+        let data_map_chunk = wrap_data_map_chunk(data_map_chunk, compression)
+            .expect("Failed to wrap the data map chunk");
+
+        let chunk_storage: HashMap<_, _> = chunks
+            .into_iter()
+            .map(|chunk| (xor_name::XorName::from_content(&chunk.value), chunk))
+            .collect();
+        let chunk_fetcher: ChunkFetcher = Box::new(move |chunk_names: &[(usize, xor_name::XorName)]| -> self_encryption::Result<Vec<(usize, crate::Bytes)>> {
+            let mut results = Vec::new();
+            for (i, hash) in chunk_names {
+                let chunk = chunk_storage.get(hash)
+                    .ok_or_else(|| self_encryption::Error::Generic("Chunk not found".to_string()))?;
+                results.push((*i, chunk.value.clone()));
+            }
+            Ok(results)
+        });
+
+        // The data map chunk is unwrapped before the data map is read, as `data_stream` does
+        let (unwrapped_compression, data_map_bytes) = unwrap_data_map_chunk(&data_map_chunk.value)
+            .expect("The data map chunk shall record the compression");
+        assert_eq!(unwrapped_compression, compression);
+        let restored_data_map: self_encryption::DataMap =
+            rmp_serde::from_slice(&data_map_bytes).expect("Failed to deserialize data map");
+        let streaming_decrypt =
+            self_encryption::streaming_decrypt(&restored_data_map, chunk_fetcher)
+                .expect("Failed to create streaming decrypt");
+        let data_stream = DataStream {
+            streaming_decrypt,
+            decompression: Some(Decoder::new().expect("Failed to create decoder")),
+        };
+
+        // Compressed data can only be read sequentially
+        assert!(data_stream.range_full().is_err());
+
+        let mut data_from_stream = Vec::new();
+        for chunk_result in data_stream {
+            let chunk = chunk_result.expect("Failed to get chunk from stream");
+            data_from_stream.extend_from_slice(&chunk);
+        }
+        assert_eq!(crate::Bytes::from(data_from_stream), test_data);
+    }
 }
@@ -116,6 +116,14 @@ impl PublicArchive {
         &self.map
     }
 
+    /// Get the MIME type of a file in the archive, as detected on upload.
+    /// Returns `None` for unknown files, unknown types, and archives created by older clients.
+    pub fn content_type(&self, path: &Path) -> Option<&str> {
+        self.map
+            .get(&normalize_path(path.to_path_buf()))
+            .and_then(|(_, meta)| meta.content_type.as_deref())
+    }
+
     /// Deserialize from bytes.
     pub fn from_bytes(data: Bytes) -> Result<PublicArchive, rmp_serde::decode::Error> {
         let root: PublicArchiveVersioned = rmp_serde::from_slice(&data[..])?;
@@ -306,6 +314,58 @@ mod test {
         assert!(PublicArchive::from_bytes(Bytes::from(arch_p1_ser)).is_ok());
     }
 
+    #[test]
+    fn content_type_compatibility() {
+        /// The metadata of archives created before content types were recorded.
+        #[derive(Debug, Default, Serialize, Deserialize)]
+        pub struct MetadataWithoutContentType {
+            created: u64,
+            modified: u64,
+            size: u64,
+            extra: Option<String>,
+        }
+        #[derive(Debug, Default, Serialize, Deserialize)]
+        pub struct OldPublicArchive {
+            map: BTreeMap<PathBuf, (DataAddress, MetadataWithoutContentType)>,
+        }
+        #[derive(Debug, Serialize, Deserialize)]
+        pub enum OldPublicArchiveVersioned {
+            V0(OldPublicArchive),
+        }
+
+        let mut old_arch = OldPublicArchive::default();
+        old_arch.map.insert(
+            PathBuf::from_str("index.html").unwrap(),
+            (
+                DataAddress::new(XorName::random(&mut rand::thread_rng())),
+                MetadataWithoutContentType::default(),
+            ),
+        );
+        let old_arch_ser =
+            rmp_serde::to_vec_named(&OldPublicArchiveVersioned::V0(old_arch)).unwrap();
+        let arch = PublicArchive::from_bytes(Bytes::from(old_arch_ser)).unwrap();
+        assert_eq!(arch.content_type(Path::new("index.html")), None);
+
+        let mut arch = PublicArchive::new();
+        arch.add_file(
+            PathBuf::from_str("index.html").unwrap(),
+            DataAddress::new(XorName::random(&mut rand::thread_rng())),
+            Metadata {
+                content_type: Some("text/html".to_string()),
+                ..Metadata::new_with_size(1)
+            },
+        );
+        let arch = PublicArchive::from_bytes(arch.to_bytes().unwrap()).unwrap();
+        assert_eq!(
+            arch.content_type(Path::new("index.html")),
+            Some("text/html")
+        );
+
+        // Older clients can still read archives with content types
+        let _: OldPublicArchiveVersioned =
+            rmp_serde::from_slice(&arch.to_bytes().unwrap()[..]).unwrap();
+    }
+
     #[test]
     fn test_archive_merge() {
         let mut arch = PublicArchive::new();
@@ -0,0 +1,150 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Detection of the MIME type of files, stored in the archive [`super::Metadata`] so that HTTP
+//! gateways can serve them with the right `Content-Type`.
+
+use std::io::Read;
+use std::path::Path;
+
+/// Number of leading bytes read from a file to detect its type from its content.
+const MAGIC_BYTES_LEN: usize = 16;
+
+/// MIME types by (lowercase) file extension.
+const BY_EXTENSION: &[(&str, &str)] = &[
+    ("html", "text/html"),
+    ("htm", "text/html"),
+    ("css", "text/css"),
+    ("js", "text/javascript"),
+    ("mjs", "text/javascript"),
+    ("json", "application/json"),
+    ("xml", "application/xml"),
+    ("txt", "text/plain"),
+    ("md", "text/markdown"),
+    ("csv", "text/csv"),
+    ("svg", "image/svg+xml"),
+    ("png", "image/png"),
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("gif", "image/gif"),
+    ("webp", "image/webp"),
+    ("ico", "image/vnd.microsoft.icon"),
+    ("avif", "image/avif"),
+    ("bmp", "image/bmp"),
+    ("mp3", "audio/mpeg"),
+    ("ogg", "audio/ogg"),
+    ("wav", "audio/wav"),
+    ("flac", "audio/flac"),
+    ("mp4", "video/mp4"),
+    ("webm", "video/webm"),
+    ("mkv", "video/x-matroska"),
+    ("pdf", "application/pdf"),
+    ("zip", "application/zip"),
+    ("gz", "application/gzip"),
+    ("tar", "application/x-tar"),
+    ("wasm", "application/wasm"),
+    ("woff", "font/woff"),
+    ("woff2", "font/woff2"),
+    ("ttf", "font/ttf"),
+    ("otf", "font/otf"),
+];
+
+/// MIME types by the magic bytes files start with.
+const BY_MAGIC_BYTES: &[(&[u8], &str)] = &[
+    (b"\x89PNG\r\n\x1a\n", "image/png"),
+    (b"\xff\xd8\xff", "image/jpeg"),
+    (b"GIF87a", "image/gif"),
+    (b"GIF89a", "image/gif"),
+    (b"%PDF-", "application/pdf"),
+    (b"PK\x03\x04", "application/zip"),
+    (b"\x1f\x8b", "application/gzip"),
+    (b"\0asm", "application/wasm"),
+    (b"wOFF", "font/woff"),
+    (b"wOF2", "font/woff2"),
+    (b"OggS", "audio/ogg"),
+    (b"fLaC", "audio/flac"),
+    (b"ID3", "audio/mpeg"),
+    (b"\x1a\x45\xdf\xa3", "video/webm"),
+    (b"<!DOCTYPE html", "text/html"),
+    (b"<html", "text/html"),
+];
+
+/// Detect the MIME type of content from its file name, falling back to its leading bytes.
+/// Returns `None` when the type is unknown, which gateways serve as `application/octet-stream`.
+pub fn detect_content_type(path: &Path, head: &[u8]) -> Option<&'static str> {
+    by_extension(path).or_else(|| by_magic_bytes(head))
+}
+
+/// Detect the MIME type of a file on disk, from its extension or its leading bytes.
+pub fn detect_file_content_type(path: &Path) -> Option<&'static str> {
+    if let Some(content_type) = by_extension(path) {
+        return Some(content_type);
+    }
+
+    let mut head = Vec::with_capacity(MAGIC_BYTES_LEN);
+    match std::fs::File::open(path) {
+        Ok(file) => {
+            if let Err(err) = file.take(MAGIC_BYTES_LEN as u64).read_to_end(&mut head) {
+                debug!("Failed to read leading bytes of {path:?}: {err}");
+            }
+        }
+        Err(err) => debug!("Failed to open {path:?} to detect its content type: {err}"),
+    }
+    by_magic_bytes(&head)
+}
+
+fn by_extension(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    BY_EXTENSION
+        .iter()
+        .find(|(ext, _)| *ext == extension)
+        .map(|(_, content_type)| *content_type)
+}
+
+fn by_magic_bytes(head: &[u8]) -> Option<&'static str> {
+    if head.len() >= 12 && &head[..4] == b"RIFF" && &head[8..12] == b"WEBP" {
+        return Some("image/webp");
+    }
+    if head.len() >= 12 && &head[..4] == b"RIFF" && &head[8..12] == b"WAVE" {
+        return Some("audio/wav");
+    }
+    if head.len() >= 8 && &head[4..8] == b"ftyp" {
+        return Some("video/mp4");
+    }
+    BY_MAGIC_BYTES
+        .iter()
+        .find(|(magic, _)| head.starts_with(magic))
+        .map(|(_, content_type)| *content_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_content_type() {
+        assert_eq!(
+            detect_content_type(Path::new("site/index.HTML"), b""),
+            Some("text/html")
+        );
+        assert_eq!(
+            detect_content_type(Path::new("photo"), b"\x89PNG\r\n\x1a\n\0\0"),
+            Some("image/png")
+        );
+        assert_eq!(
+            detect_content_type(Path::new("clip"), b"\0\0\0\x18ftypmp42"),
+            Some("video/mp4")
+        );
+        // The extension takes precedence over the content
+        assert_eq!(
+            detect_content_type(Path::new("notes.txt"), b"%PDF-1.7"),
+            Some("text/plain")
+        );
+        assert_eq!(detect_content_type(Path::new("blob.bin"), b"\0\x01"), None);
+    }
+}
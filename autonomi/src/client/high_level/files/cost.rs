@@ -206,7 +206,7 @@ impl Client {
         path: PathBuf,
         is_public: bool,
     ) -> Result<Vec<XorName>, FileCostError> {
-        let streams = encrypt_directory_files(path, is_public).await?;
+        let streams = encrypt_directory_files(path, is_public, self.config.compression).await?;
 
         let mut all_xor_names = Vec::new();
 
@@ -0,0 +1,435 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Delta uploads of large files.
+//!
+//! Self-encryption splits a file at fixed offsets, so inserting a single byte shifts, and changes,
+//! every chunk after it. Here the file is first split into segments at content-defined boundaries
+//! (FastCDC), which only move around an edit, and each segment is self-encrypted on its own. Since
+//! self-encryption is deterministic, an unchanged segment yields the same chunks, which are already
+//! on the Network. Updating a file then only pays for the chunks of the segments that changed.
+
+use super::{DownloadError, UploadError};
+use crate::client::data_types::chunk::{Chunk, DataMapChunk};
+use crate::client::payment::PaymentOption;
+use crate::client::quote::add_costs;
+use crate::client::{Client, GetError, PutError};
+use crate::self_encryption::EncryptionStream;
+use ant_evm::AttoTokens;
+use bytes::Bytes;
+use fastcdc::v2020::StreamCDC;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Minimum size of a segment, only the last segment of a file can be smaller.
+const SEGMENT_MIN_SIZE: u32 = 1024 * 1024;
+/// Average size of a segment. The smaller the segments, the less is uploaded again around an edit,
+/// but each segment costs the chunks holding its own data map.
+const SEGMENT_AVG_SIZE: u32 = 4 * 1024 * 1024;
+/// Maximum size of a segment.
+const SEGMENT_MAX_SIZE: u32 = 16 * 1024 * 1024;
+/// Size of the changed segments paid for and uploaded together.
+const SEGMENT_UPLOAD_BATCH_SIZE: usize = 64 * 1024 * 1024;
+/// Self-encryption needs at least 3 bytes.
+const MIN_SEGMENT_BYTES: usize = 3;
+
+/// Marks a data map chunk as the list of segments of a file, so it can't be mistaken for a data map.
+const SEGMENTED_DATA_MAP_TAG: &str = "autonomi-segments-v1";
+/// Upper bound of the bytes a segment takes in the data map chunk of its file, on top of its own
+/// data map, and of the bytes taken by the tag and list of segments.
+const SEGMENT_ENCODING_OVERHEAD: usize = 32;
+
+/// The content of the data map chunk of a file uploaded in segments.
+#[derive(Serialize, Deserialize)]
+struct SegmentedDataMap {
+    tag: String,
+    segments: Vec<Segment>,
+}
+
+/// A segment of a file, self-encrypted on its own.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct Segment {
+    /// Size of the segment in bytes
+    pub(crate) size: u64,
+    /// The content of the data map chunk of the segment
+    pub(crate) data_map: Bytes,
+}
+
+impl Client {
+    /// Upload a private file split into content-defined segments, so that later versions of the
+    /// file can be uploaded with [`Client::file_update`], paying only for what changed.
+    ///
+    /// Returns the [`DataMapChunk`] of the file, which the usual download methods understand.
+    pub async fn file_upload_segmented(
+        &self,
+        path: PathBuf,
+        payment_option: PaymentOption,
+    ) -> Result<(AttoTokens, DataMapChunk), UploadError> {
+        self.file_update_internal(None, path, payment_option).await
+    }
+
+    /// Upload a new version of a private file, reusing the chunks of the segments which didn't
+    /// change since the version at `old_data_map`.
+    ///
+    /// Only the segments of a file uploaded with [`Client::file_upload_segmented`] or updated with
+    /// this method can be reused, any other `old_data_map` uploads the whole file again.
+    /// Returns the cost of the changed chunks along with the new [`DataMapChunk`] of the file.
+    pub async fn file_update(
+        &self,
+        old_data_map: &DataMapChunk,
+        new_file: PathBuf,
+        payment_option: PaymentOption,
+    ) -> Result<(AttoTokens, DataMapChunk), UploadError> {
+        let old_segments = unpack_segments(old_data_map);
+        if old_segments.is_none() {
+            warn!(
+                "The old version of {new_file:?} was not uploaded in segments, none can be reused"
+            );
+        }
+        self.file_update_internal(old_segments, new_file, payment_option)
+            .await
+    }
+
+    async fn file_update_internal(
+        &self,
+        old_segments: Option<Vec<Segment>>,
+        path: PathBuf,
+        payment_option: PaymentOption,
+    ) -> Result<(AttoTokens, DataMapChunk), UploadError> {
+        info!("Uploading {path:?} in content-defined segments");
+        let file = std::fs::File::open(&path)?;
+        let mut reader = content_defined_segments(file);
+
+        let mut plan = SegmentPlan::new(old_segments);
+        let mut pending = vec![];
+        let mut pending_size = 0;
+        let mut total_cost = AttoTokens::zero();
+
+        loop {
+            let encrypted = tokio::task::block_in_place(|| {
+                reader.next().map(|segment| {
+                    let segment = Bytes::from(segment?);
+                    let size = segment.len();
+                    EncryptionStream::new_in_memory(segment, false)
+                        .map(|(stream, data_map_chunk)| (size, stream, data_map_chunk))
+                        .map_err(|err| UploadError::Encryption(err.to_string()))
+                })
+            });
+            let Some(encrypted) = encrypted else {
+                break;
+            };
+            let (size, stream, data_map_chunk) = encrypted?;
+
+            if plan.add(size as u64, data_map_chunk.0.value().clone()) {
+                pending.push(stream);
+                pending_size += size;
+            }
+            // Fail before paying for segments whose list would not fit in a chunk
+            if plan.packed_size > Chunk::MAX_SIZE {
+                return Err(PutError::Serialization(format!(
+                    "{path:?} has too many segments for their list to fit in a chunk of {} bytes",
+                    Chunk::MAX_SIZE
+                ))
+                .into());
+            }
+
+            if pending_size >= SEGMENT_UPLOAD_BATCH_SIZE {
+                let cost = self
+                    .pay_and_upload(payment_option.clone(), &mut pending)
+                    .await?;
+                total_cost = add_costs(total_cost, cost).map_err(PutError::from)?;
+                pending.clear();
+                pending_size = 0;
+            }
+        }
+        if !pending.is_empty() {
+            let cost = self.pay_and_upload(payment_option, &mut pending).await?;
+            total_cost = add_costs(total_cost, cost).map_err(PutError::from)?;
+        }
+
+        let SegmentPlan {
+            segments, changed, ..
+        } = plan;
+        if segments.is_empty() {
+            return Err(UploadError::Encryption(format!(
+                "Cannot encrypt file {path:?}, as it is smaller than {MIN_SEGMENT_BYTES} bytes"
+            )));
+        }
+        info!(
+            "Uploaded {path:?} in {} segments, {changed} of which changed, for {total_cost}",
+            segments.len()
+        );
+
+        let data_map_chunk = pack_segments(segments)?;
+        Ok((total_cost, data_map_chunk))
+    }
+
+    /// Fetch the data of a file uploaded in segments, in memory.
+    pub(crate) async fn segmented_data_get(
+        &self,
+        data_map: &DataMapChunk,
+    ) -> Result<Bytes, GetError> {
+        let segments = unpack_segments(data_map).ok_or_else(not_segmented)?;
+        let mut data = Vec::with_capacity(segments.iter().map(|s| s.size as usize).sum());
+        for segment in &segments {
+            data.extend_from_slice(&self.segment_get(segment).await?);
+        }
+        Ok(Bytes::from(data))
+    }
+
+    /// Download a file uploaded in segments to `to_dest`, one segment at a time.
+    pub(crate) async fn segmented_file_download(
+        &self,
+        data_map: &DataMapChunk,
+        to_dest: &Path,
+    ) -> Result<(), DownloadError> {
+        let segments = unpack_segments(data_map).ok_or_else(not_segmented)?;
+        info!(
+            "Downloading {} segments of file to {to_dest:?}",
+            segments.len()
+        );
+        let mut file = std::fs::File::create(to_dest)?;
+        for segment in &segments {
+            let bytes = self.segment_get(segment).await?;
+            file.write_all(&bytes)?;
+        }
+        Ok(())
+    }
+
+    /// Fetch the data of a segment.
+    async fn segment_get(&self, segment: &Segment) -> Result<Bytes, GetError> {
+        let data_map_chunk = DataMapChunk(Chunk::new(segment.data_map.clone()));
+        let mut data_map = self.restore_data_map_from_chunk(&data_map_chunk).await?;
+        data_map.child = None;
+        self.fetch_from_data_map(&data_map).await
+    }
+}
+
+/// The segments of a new version of a file, and which of them are to be uploaded and paid for.
+struct SegmentPlan {
+    /// Data maps of the segments already on the Network, or about to be uploaded
+    known: HashSet<Bytes>,
+    segments: Vec<Segment>,
+    /// Number of segments to upload
+    changed: usize,
+    /// Upper bound of the size of the data map chunk listing the segments
+    packed_size: usize,
+}
+
+impl SegmentPlan {
+    fn new(old_segments: Option<Vec<Segment>>) -> Self {
+        Self {
+            known: old_segments
+                .into_iter()
+                .flatten()
+                .map(|segment| segment.data_map)
+                .collect(),
+            segments: vec![],
+            changed: 0,
+            packed_size: SEGMENTED_DATA_MAP_TAG.len() + SEGMENT_ENCODING_OVERHEAD,
+        }
+    }
+
+    /// Add the next segment of the file. Returns whether its chunks are to be uploaded, which is
+    /// not the case for a segment of the old version or one already added.
+    fn add(&mut self, size: u64, data_map: Bytes) -> bool {
+        let changed = self.known.insert(data_map.clone());
+        if changed {
+            self.changed += 1;
+        }
+        self.packed_size += data_map.len() + SEGMENT_ENCODING_OVERHEAD;
+        self.segments.push(Segment { size, data_map });
+        changed
+    }
+}
+
+fn not_segmented() -> GetError {
+    GetError::UnrecognizedDataMap("not the data map of a file uploaded in segments".to_string())
+}
+
+/// Split the data read from `reader` into content-defined segments. A last segment too small to
+/// be self-encrypted is merged into the one before it.
+fn content_defined_segments<R: Read>(reader: R) -> impl Iterator<Item = std::io::Result<Vec<u8>>> {
+    let mut cdc = StreamCDC::new(reader, SEGMENT_MIN_SIZE, SEGMENT_AVG_SIZE, SEGMENT_MAX_SIZE)
+        .map(|chunk| chunk.map(|chunk| chunk.data).map_err(std::io::Error::other))
+        .peekable();
+    std::iter::from_fn(move || {
+        let mut segment = match cdc.next()? {
+            Ok(segment) => segment,
+            Err(err) => return Some(Err(err)),
+        };
+        if let Some(Ok(next)) = cdc.peek()
+            && next.len() < MIN_SEGMENT_BYTES
+        {
+            segment.extend_from_slice(next);
+            let _ = cdc.next();
+        }
+        if segment.len() < MIN_SEGMENT_BYTES {
+            return None;
+        }
+        Some(Ok(segment))
+    })
+}
+
+/// The data map chunk listing the segments of a file. Fails when the list doesn't fit in a chunk.
+pub(crate) fn pack_segments(segments: Vec<Segment>) -> Result<DataMapChunk, PutError> {
+    let count = segments.len();
+    let bytes = rmp_serde::to_vec(&SegmentedDataMap {
+        tag: SEGMENTED_DATA_MAP_TAG.to_string(),
+        segments,
+    })
+    .map_err(|err| PutError::Serialization(err.to_string()))?;
+    let chunk = Chunk::new(Bytes::from(bytes));
+    if chunk.is_too_big() {
+        return Err(PutError::Serialization(format!(
+            "The list of {count} segments is {} bytes, when max chunk size is {}",
+            chunk.size(),
+            Chunk::MAX_SIZE
+        )));
+    }
+    Ok(DataMapChunk(chunk))
+}
+
+/// The segments of a file uploaded in segments, `None` for any other data map chunk.
+fn unpack_segments(data_map: &DataMapChunk) -> Option<Vec<Segment>> {
+    let segmented: SegmentedDataMap = rmp_serde::from_slice(data_map.0.value()).ok()?;
+    (segmented.tag == SEGMENTED_DATA_MAP_TAG).then_some(segmented.segments)
+}
+
+/// Whether the data map chunk is the one of a file uploaded in segments.
+pub(crate) fn is_segmented(data_map: &DataMapChunk) -> bool {
+    unpack_segments(data_map).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{Rng, SeedableRng};
+
+    fn segments_of(data: &[u8]) -> Vec<Vec<u8>> {
+        content_defined_segments(data)
+            .collect::<std::io::Result<_>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_edit_only_changes_nearby_segments() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let mut data = vec![0u8; 24 * 1024 * 1024];
+        rng.fill(&mut data[..]);
+        let before = segments_of(&data);
+        assert!(before.len() > 2);
+        assert_eq!(before.concat(), data);
+
+        // insert a few bytes in the middle of the data
+        let _ = data.splice(12 * 1024 * 1024..12 * 1024 * 1024, *b"edit");
+        let after = segments_of(&data);
+        assert_eq!(after.concat(), data);
+
+        let before: HashSet<_> = before.into_iter().collect();
+        let changed = after.iter().filter(|s| !before.contains(*s)).count();
+        assert!(
+            changed <= 2,
+            "{changed} of {} segments changed",
+            after.len()
+        );
+    }
+
+    /// Plan the upload of `data` as a new version of the file with `old_segments`. Returns the
+    /// plan and the number of chunks it pays for.
+    fn plan_upload(data: &[u8], old_segments: Option<Vec<Segment>>) -> (SegmentPlan, usize) {
+        let mut plan = SegmentPlan::new(old_segments);
+        let mut paid_chunks = 0;
+        for segment in segments_of(data) {
+            let size = segment.len() as u64;
+            let (stream, data_map_chunk) =
+                EncryptionStream::new_in_memory(Bytes::from(segment), false).unwrap();
+            if plan.add(size, data_map_chunk.0.value().clone()) {
+                paid_chunks += stream.total_chunks();
+            }
+        }
+        (plan, paid_chunks)
+    }
+
+    #[test]
+    fn test_update_only_pays_for_changed_segments() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let mut data = vec![0u8; 24 * 1024 * 1024];
+        rng.fill(&mut data[..]);
+
+        let (first, first_paid) = plan_upload(&data, None);
+        assert!(first.segments.len() > 2);
+        assert_eq!(first.changed, first.segments.len());
+        // the old segments are read back from the data map chunk of the file, like file_update
+        let old_segments = unpack_segments(&pack_segments(first.segments).unwrap());
+
+        let (same, same_paid) = plan_upload(&data, old_segments.clone());
+        assert_eq!(same.changed, 0);
+        assert_eq!(same_paid, 0);
+
+        let _ = data.splice(12 * 1024 * 1024..12 * 1024 * 1024, *b"edit");
+        let (update, update_paid) = plan_upload(&data, old_segments);
+        assert!(update.changed >= 1);
+        assert!(
+            update.changed <= 2,
+            "{} of {} segments paid for",
+            update.changed,
+            update.segments.len()
+        );
+        assert!(update_paid > 0 && update_paid < first_paid);
+        let size: u64 = update.segments.iter().map(|segment| segment.size).sum();
+        assert_eq!(size, data.len() as u64);
+    }
+
+    #[test]
+    fn test_tiny_last_segment_is_merged() {
+        assert!(segments_of(b"ab").is_empty());
+        assert_eq!(segments_of(b"abc"), vec![b"abc".to_vec()]);
+    }
+
+    #[test]
+    fn test_segmented_data_map_roundtrip() {
+        let segments = vec![Segment {
+            size: 11,
+            data_map: Bytes::from_static(b"data map"),
+        }];
+        let data_map_chunk = pack_segments(segments.clone()).unwrap();
+        assert_eq!(unpack_segments(&data_map_chunk), Some(segments));
+        assert!(is_segmented(&data_map_chunk));
+
+        let data_map = DataMapChunk(Chunk::new(Bytes::from_static(b"not segmented")));
+        assert!(!is_segmented(&data_map));
+    }
+
+    #[test]
+    fn test_segmented_data_map_fits_in_a_chunk() {
+        let segment = Segment {
+            size: u64::MAX,
+            data_map: Bytes::from(vec![1u8; 1000]),
+        };
+        let mut plan = SegmentPlan::new(None);
+        let fitting = Chunk::MAX_SIZE / (segment.data_map.len() + SEGMENT_ENCODING_OVERHEAD) - 1;
+        for _ in 0..fitting {
+            let _ = plan.add(segment.size, segment.data_map.clone());
+        }
+        assert!(plan.packed_size <= Chunk::MAX_SIZE);
+        let packed_size = pack_segments(plan.segments.clone()).unwrap().0.size();
+        assert!(packed_size <= plan.packed_size);
+
+        let _ = plan.add(segment.size, segment.data_map.clone());
+        let _ = plan.add(segment.size, segment.data_map.clone());
+        assert!(plan.packed_size > Chunk::MAX_SIZE);
+        let too_many = vec![segment; fitting + 100];
+        assert!(matches!(
+            pack_segments(too_many),
+            Err(PutError::Serialization(_))
+        ));
+    }
+}
@@ -7,11 +7,14 @@
 // permissions and limitations relating to use of the SAFE Network Software.
 
 use super::archive_private::{PrivateArchive, PrivateArchiveDataMap};
-use super::{DownloadError, UploadError, bulk_upload_internal, file_upload_internal};
+use super::{
+    DownloadError, PackCache, UploadError, bulk_upload_internal, file_upload_internal, is_segmented,
+};
 use crate::client::PutError;
 use crate::client::data_types::chunk::DataMapChunk;
 use crate::client::payment::{BulkPaymentOption, PaymentOption};
 use crate::client::quote::add_costs;
+use crate::self_encryption::Compression;
 use crate::{AttoTokens, Client};
 use std::path::PathBuf;
 
@@ -29,6 +32,20 @@ impl Client {
             tokio::fs::create_dir_all(parent).await?;
         }
 
+        if is_segmented(data_map) {
+            self.segmented_file_download(data_map, &to_dest).await?;
+            debug!("Successfully downloaded segmented private file to {to_dest:?}");
+            return Ok(());
+        }
+
+        // Compressed data is small enough to be fetched and decompressed in memory
+        if Self::data_map_compression(data_map) != Compression::None {
+            let data = self.data_get(data_map).await?;
+            tokio::fs::write(&to_dest, data).await?;
+            debug!("Successfully downloaded compressed private file to {to_dest:?}");
+            return Ok(());
+        }
+
         let datamap = self.restore_data_map_from_chunk(data_map).await?;
         self.stream_download_from_datamap(datamap, &to_dest)?;
 
@@ -43,8 +60,10 @@ impl Client {
         to_dest: PathBuf,
     ) -> Result<(), DownloadError> {
         let archive = self.archive_get(archive_access).await?;
-        for (path, addr, _meta) in archive.iter() {
-            self.file_download(addr, to_dest.join(path)).await?;
+        let mut packs = PackCache::default();
+        for (path, addr, meta) in archive.iter() {
+            self.archive_file_download_cached(addr, meta, to_dest.join(path), &mut packs)
+                .await?;
         }
         debug!("Downloaded directory to {to_dest:?}");
         Ok(())
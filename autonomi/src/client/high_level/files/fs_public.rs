@@ -7,7 +7,10 @@
 // permissions and limitations relating to use of the SAFE Network Software.
 
 use super::archive_public::{ArchiveAddress, PublicArchive};
-use super::{DownloadError, Metadata, UploadError, bulk_upload_internal, file_upload_internal};
+use super::content_type::detect_file_content_type;
+use super::{
+    DownloadError, Metadata, PackCache, UploadError, bulk_upload_internal, file_upload_internal,
+};
 use crate::AttoTokens;
 use crate::client::data_types::chunk::{ChunkAddress, DataMapChunk};
 use crate::client::high_level::data::DataAddress;
@@ -43,8 +46,10 @@ impl Client {
     ) -> Result<(), DownloadError> {
         let archive = self.archive_get_public(archive_addr).await?;
         debug!("Downloaded archive for the directory from the network at {archive_addr:?}");
-        for (path, addr, _meta) in archive.iter() {
-            self.file_download_public(addr, to_dest.join(path)).await?;
+        let mut packs = PackCache::default();
+        for (path, addr, meta) in archive.iter() {
+            self.archive_file_download_public_cached(addr, meta, to_dest.join(path), &mut packs)
+                .await?;
         }
         debug!(
             "All files in the directory downloaded to {:?} from the network address {:?}",
@@ -154,6 +159,8 @@ pub(crate) fn metadata_from_entry(entry: &walkdir::DirEntry) -> Metadata {
                 modified: 0,
                 size: 0,
                 extra: None,
+                content_type: None,
+                packed: None,
             };
         }
     };
@@ -184,5 +191,7 @@ pub(crate) fn metadata_from_entry(entry: &walkdir::DirEntry) -> Metadata {
         modified,
         size: fs_metadata.len(),
         extra: None,
+        content_type: detect_file_content_type(entry.path()).map(str::to_string),
+        packed: None,
     }
 }
@@ -0,0 +1,363 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Verification manifests of downloaded directories: the path, size, BLAKE3 checksum and chunk
+//! addresses of every file, so that the local copy can be re-validated later on.
+//!
+//! The checksums are computed over the data as fetched from the Network, so that a file corrupted
+//! while being written to disk is caught by the first verification rather than recorded as valid.
+
+use super::archive_public::{ArchiveAddress, PublicArchive};
+use super::{DownloadError, Metadata, PackCache};
+use crate::Client;
+use crate::client::data_types::chunk::{ChunkAddress, DataMapChunk};
+use crate::client::high_level::data::DataAddress;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Size of the blocks read from disk when hashing a file.
+const HASH_BUFFER_SIZE: usize = 64 * 1024;
+
+/// The verification manifest of a public archive downloaded to the local file system.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DownloadManifest {
+    /// Hex address of the downloaded archive
+    pub archive: String,
+    /// The downloaded files, with their path relative to the download directory
+    pub files: Vec<ManifestEntry>,
+}
+
+/// A downloaded file of a [`DownloadManifest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// Path of the file, relative to the download directory
+    pub path: PathBuf,
+    /// Size of the file in bytes
+    pub size: u64,
+    /// Hex BLAKE3 checksum of the content of the file
+    pub blake3: String,
+    /// Hex data address of the file (points to its DataMap)
+    pub address: String,
+    /// Hex addresses of the content chunks of the file
+    pub chunks: Vec<String>,
+}
+
+/// A file of a [`DownloadManifest`] which no longer matches its local copy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ManifestMismatch {
+    /// The file is missing from the download directory
+    Missing(PathBuf),
+    /// The file doesn't have the size recorded in the manifest
+    Size {
+        path: PathBuf,
+        expected: u64,
+        actual: u64,
+    },
+    /// The content of the file doesn't match the checksum recorded in the manifest
+    Checksum(PathBuf),
+}
+
+impl DownloadManifest {
+    /// Serialize the manifest to pretty printed JSON.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Deserialize a manifest from JSON.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Re-validate the files of a download directory against the manifest.
+    /// Returns the files which are missing or no longer match their size or checksum, an empty list
+    /// meaning the download is intact.
+    pub fn verify(&self, dir: &Path) -> Result<Vec<ManifestMismatch>, std::io::Error> {
+        let mut mismatches = vec![];
+        for entry in &self.files {
+            let (size, blake3) = match file_checksum(&dir.join(&entry.path)) {
+                Ok(checksum) => checksum,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                    mismatches.push(ManifestMismatch::Missing(entry.path.clone()));
+                    continue;
+                }
+                Err(err) => return Err(err),
+            };
+            if size != entry.size {
+                mismatches.push(ManifestMismatch::Size {
+                    path: entry.path.clone(),
+                    expected: entry.size,
+                    actual: size,
+                });
+            } else if blake3 != entry.blake3 {
+                mismatches.push(ManifestMismatch::Checksum(entry.path.clone()));
+            }
+        }
+        info!(
+            "Verified {} downloaded files of archive {}: {} mismatches",
+            self.files.len(),
+            self.archive,
+            mismatches.len()
+        );
+        Ok(mismatches)
+    }
+}
+
+impl Client {
+    /// Same as [`Client::dir_download_public`], but also returns the verification manifest of the
+    /// downloaded files. See [`Client::verify_download`] to re-validate them later.
+    ///
+    /// The checksums are computed over the data fetched from the Network while it is written.
+    pub async fn dir_download_public_with_manifest(
+        &self,
+        archive_addr: &ArchiveAddress,
+        to_dest: PathBuf,
+    ) -> Result<DownloadManifest, DownloadError> {
+        let archive = self.archive_get_public(archive_addr).await?;
+        let mut packs = PackCache::default();
+        let mut files = vec![];
+        for (path, addr, meta) in archive.iter() {
+            let to_path = to_dest.join(path);
+            if let Some(parent) = to_path.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            let file = std::fs::File::create(&to_path)?;
+            let checksum = self
+                .archive_file_fetch_public(addr, meta, &mut packs, file)
+                .await?;
+            files.push(self.manifest_entry(path, addr, checksum).await?);
+        }
+        debug!(
+            "Downloaded {} files of archive {archive_addr:?} to {to_dest:?} with their manifest",
+            files.len()
+        );
+        Ok(DownloadManifest {
+            archive: archive_addr.to_hex(),
+            files,
+        })
+    }
+
+    /// Build the verification manifest of a public archive from its data on the Network, without
+    /// writing it to disk.
+    pub async fn download_manifest(
+        &self,
+        archive_addr: &ArchiveAddress,
+        archive: &PublicArchive,
+    ) -> Result<DownloadManifest, DownloadError> {
+        let mut packs = PackCache::default();
+        let mut files = vec![];
+        for (path, addr, meta) in archive.iter() {
+            let checksum = self
+                .archive_file_fetch_public(addr, meta, &mut packs, std::io::sink())
+                .await?;
+            files.push(self.manifest_entry(path, addr, checksum).await?);
+        }
+        debug!(
+            "Built the download manifest of {} files of archive {archive_addr:?}",
+            files.len()
+        );
+        Ok(DownloadManifest {
+            archive: archive_addr.to_hex(),
+            files,
+        })
+    }
+
+    /// Re-validate the files of a download directory against their manifest.
+    /// See [`DownloadManifest::verify`].
+    pub fn verify_download(
+        &self,
+        manifest: &DownloadManifest,
+        dir: &Path,
+    ) -> Result<Vec<ManifestMismatch>, std::io::Error> {
+        manifest.verify(dir)
+    }
+
+    /// Fetch a file of a public archive from the Network, unpacking it if it was packed, and
+    /// write it to `out`. Returns the size and hex BLAKE3 checksum of the fetched data.
+    async fn archive_file_fetch_public<W: Write>(
+        &self,
+        data_addr: &DataAddress,
+        metadata: &Metadata,
+        packs: &mut PackCache,
+        out: W,
+    ) -> Result<(u64, String), DownloadError> {
+        let mut out = ChecksumWriter::new(out);
+        if let Some(range) = &metadata.packed {
+            let pack = self.pack_get_public(data_addr, packs).await?;
+            out.write_all(&range.unpack(&pack)?)?;
+        } else {
+            for chunk in self.data_stream_public(data_addr).await? {
+                out.write_all(&chunk?)?;
+            }
+        }
+        Ok(out.finish()?)
+    }
+
+    /// The manifest entry of a file, given the size and checksum of its fetched data.
+    async fn manifest_entry(
+        &self,
+        path: &Path,
+        addr: &DataAddress,
+        (size, blake3): (u64, String),
+    ) -> Result<ManifestEntry, DownloadError> {
+        let chunks = self
+            .data_chunk_addresses(addr)
+            .await?
+            .iter()
+            .map(ChunkAddress::to_hex)
+            .collect();
+        Ok(ManifestEntry {
+            path: path.to_path_buf(),
+            size,
+            blake3,
+            address: addr.to_hex(),
+            chunks,
+        })
+    }
+
+    /// The addresses of the content chunks of the data at the given address.
+    async fn data_chunk_addresses(
+        &self,
+        addr: &DataAddress,
+    ) -> Result<Vec<ChunkAddress>, DownloadError> {
+        let data_map_chunk =
+            DataMapChunk(self.chunk_get(&ChunkAddress::new(*addr.xorname())).await?);
+        let data_map = self.restore_data_map_from_chunk(&data_map_chunk).await?;
+        Ok(data_map
+            .infos()
+            .iter()
+            .map(|info| ChunkAddress::new(info.dst_hash))
+            .collect())
+    }
+}
+
+/// Hashes the data written through it, counting its size.
+struct ChecksumWriter<W> {
+    inner: W,
+    hasher: blake3::Hasher,
+    size: u64,
+}
+
+impl<W: Write> ChecksumWriter<W> {
+    fn new(inner: W) -> Self {
+        Self {
+            inner,
+            hasher: blake3::Hasher::new(),
+            size: 0,
+        }
+    }
+
+    fn write_all(&mut self, data: &[u8]) -> Result<(), std::io::Error> {
+        self.inner.write_all(data)?;
+        self.hasher.update(data);
+        self.size += data.len() as u64;
+        Ok(())
+    }
+
+    /// The size and hex BLAKE3 checksum of the data written.
+    fn finish(mut self) -> Result<(u64, String), std::io::Error> {
+        self.inner.flush()?;
+        Ok((self.size, self.hasher.finalize().to_hex().to_string()))
+    }
+}
+
+/// The size and hex BLAKE3 checksum of the file.
+fn file_checksum(path: &Path) -> Result<(u64, String), std::io::Error> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = blake3::Hasher::new();
+    let mut buffer = vec![0; HASH_BUFFER_SIZE];
+    let mut size = 0;
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        size += read as u64;
+    }
+    Ok((size, hasher.finalize().to_hex().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_file_checksum() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"hello world").unwrap();
+
+        let (size, checksum) = file_checksum(file.path()).unwrap();
+        assert_eq!(size, 11);
+        assert_eq!(
+            checksum,
+            "d74981efa70a0c880b8d8c1985d075dbcbf679b99a5f9914e5aaf96b831a9e24"
+        );
+    }
+
+    #[test]
+    fn test_checksum_writer_hashes_what_it_writes() {
+        let mut written = vec![];
+        let mut writer = ChecksumWriter::new(&mut written);
+        writer.write_all(b"hello ").unwrap();
+        writer.write_all(b"world").unwrap();
+        let (size, checksum) = writer.finish().unwrap();
+
+        assert_eq!(written, b"hello world");
+        assert_eq!(size, 11);
+        assert_eq!(
+            checksum,
+            "d74981efa70a0c880b8d8c1985d075dbcbf679b99a5f9914e5aaf96b831a9e24"
+        );
+    }
+
+    #[test]
+    fn test_manifest_json_roundtrip() {
+        let manifest = DownloadManifest {
+            archive: "00".repeat(32),
+            files: vec![ManifestEntry {
+                path: PathBuf::from("dir/file.txt"),
+                size: 11,
+                blake3: "ab".repeat(32),
+                address: "01".repeat(32),
+                chunks: vec!["02".repeat(32), "03".repeat(32), "04".repeat(32)],
+            }],
+        };
+        let json = manifest.to_json().unwrap();
+        assert_eq!(DownloadManifest::from_json(&json).unwrap(), manifest);
+    }
+
+    #[test]
+    fn test_verify_detects_changed_and_missing_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.txt"), b"hello world").unwrap();
+        std::fs::write(dir.path().join("b.txt"), b"hello world").unwrap();
+        let (size, blake3) = file_checksum(&dir.path().join("a.txt")).unwrap();
+        let entry = |path: &str| ManifestEntry {
+            path: PathBuf::from(path),
+            size,
+            blake3: blake3.clone(),
+            address: "01".repeat(32),
+            chunks: vec![],
+        };
+        let manifest = DownloadManifest {
+            archive: "00".repeat(32),
+            files: vec![entry("a.txt"), entry("b.txt"), entry("c.txt")],
+        };
+
+        std::fs::write(dir.path().join("b.txt"), b"hello World").unwrap();
+        assert_eq!(
+            manifest.verify(dir.path()).unwrap(),
+            vec![
+                ManifestMismatch::Checksum(PathBuf::from("b.txt")),
+                ManifestMismatch::Missing(PathBuf::from("c.txt")),
+            ]
+        );
+    }
+}
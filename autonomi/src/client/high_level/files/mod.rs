@@ -35,12 +35,21 @@ use xor_name::XorName;
 
 pub mod archive_private;
 pub mod archive_public;
+pub mod content_type;
 mod cost;
+mod delta;
 pub mod fs_private;
 pub mod fs_public;
+pub mod manifest;
+mod pack;
+pub mod source;
 
 pub use archive_private::PrivateArchive;
 pub use archive_public::PublicArchive;
+pub(crate) use delta::{Segment, is_segmented, pack_segments};
+pub use manifest::{DownloadManifest, ManifestEntry, ManifestMismatch};
+pub use pack::{PACKED_FILE_MAX_SIZE, PackCache, PackedRange};
+pub use source::{S3Credentials, SourceError};
 
 /// Estimate chunk count for a directory or file.
 /// Returns at least 3 chunks per file (self-encryption minimum).
@@ -155,6 +164,17 @@ where
             let results = streams_to_file_results(streams)?;
             Ok((cost, build_archive(results)))
         }
+        BulkPaymentOption::ContinueRegular(wallet, receipt) => {
+            let (cost, streams) = client
+                .dir_content_upload_internal(
+                    dir_path,
+                    PaymentOption::ContinueWithReceipt(wallet, receipt),
+                    is_public,
+                )
+                .await?;
+            let results = streams_to_file_results(streams)?;
+            Ok((cost, build_archive(results)))
+        }
         BulkPaymentOption::MerkleReceipt(receipt) => {
             let (cost, results) = client
                 .files_put_with_merkle_payment(
@@ -216,6 +236,14 @@ pub struct Metadata {
 
     /// Optional extra metadata with undefined structure, e.g. JSON.
     pub extra: Option<String>,
+    /// MIME type of the file, detected on upload (e.g. `text/html`), for HTTP gateways to serve
+    /// the file with. `None` for unknown types and archives created by older clients.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    /// Range of the file within the pack it was bundled in with other small files, whose data map
+    /// the archive holds instead of one of its own. `None` for files uploaded on their own.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub packed: Option<PackedRange>,
 }
 
 impl Default for Metadata {
@@ -237,6 +265,8 @@ impl Metadata {
             modified: now,
             size,
             extra: None,
+            content_type: None,
+            packed: None,
         }
     }
 
@@ -247,6 +277,8 @@ impl Metadata {
             modified: 0,
             size: 0,
             extra: None,
+            content_type: None,
+            packed: None,
         }
     }
 }
@@ -289,6 +321,8 @@ pub enum UploadError {
     Encryption(String),
     #[error("Merkle upload error: {0}")]
     MerkleUpload(#[from] MerkleUploadErrorWithReceipt),
+    #[error("Failed to read the upload source: {0}")]
+    Source(#[from] SourceError),
 }
 
 /// Errors that can occur during the download operation.
@@ -453,8 +487,12 @@ impl Client {
     ) -> Result<(AttoTokens, Vec<EncryptionStream>), UploadError> {
         info!("Uploading directory: {dir_path:?}, public: {is_public}");
 
-        let encryption_results =
-            crate::self_encryption::encrypt_directory_files(dir_path, is_public).await?;
+        let encryption_results = crate::self_encryption::encrypt_directory_files(
+            dir_path,
+            is_public,
+            self.config.compression,
+        )
+        .await?;
         let mut chunk_iterators = vec![];
 
         for encryption_result in encryption_results {
@@ -0,0 +1,484 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Packing of small files into shared chunks.
+//!
+//! Self-encryption turns any file into at least 3 chunks, so a directory of many tiny files, like
+//! the assets of a website, pays for 3 chunks per file. Here small files are concatenated into
+//! packs, each self-encrypted as a single piece of data. Every packed file points to the data map
+//! of its pack in the archive, and its [`Metadata::packed`] range locates it within the pack.
+
+use super::archive_private::PrivateArchive;
+use super::archive_public::PublicArchive;
+use super::fs_public::metadata_from_entry;
+use super::{
+    DownloadError, Metadata, UploadError, get_relative_file_path_from_abs_file_and_folder_path,
+    streams_to_file_results,
+};
+use crate::client::data_types::chunk::DataMapChunk;
+use crate::client::high_level::data::DataAddress;
+use crate::client::payment::PaymentOption;
+use crate::client::{Client, GetError, PutError};
+use crate::self_encryption::{EncryptionStream, encrypt_file};
+use ant_evm::{Amount, AttoTokens};
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use xor_name::XorName;
+
+/// Files up to this size are packed, larger files are uploaded on their own.
+pub const PACKED_FILE_MAX_SIZE: u64 = 256 * 1024;
+/// Maximum size of a pack. Fetching a packed file fetches its whole pack.
+const PACK_MAX_SIZE: usize = 4 * 1024 * 1024;
+/// Self-encryption needs at least 3 bytes, smaller packs are padded.
+const PACK_MIN_SIZE: usize = 3;
+/// Number of full packs uploaded together, so that the small files of a large directory are not
+/// all held in memory until the upload.
+const PACKS_PER_UPLOAD: usize = 8;
+/// Number of packs kept by a [`PackCache`].
+const PACK_CACHE_CAPACITY: usize = 16;
+
+/// The range of a packed file within the data of its pack.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PackedRange {
+    /// Offset of the file in the pack, in bytes
+    pub offset: u64,
+    /// Length of the file in bytes
+    pub length: u64,
+}
+
+impl PackedRange {
+    /// Extract the content of the packed file from the data of its pack.
+    pub fn unpack(&self, pack: &Bytes) -> Result<Bytes, GetError> {
+        let end = self.offset.saturating_add(self.length);
+        if end > pack.len() as u64 {
+            return Err(GetError::Configuration(format!(
+                "Packed file range {}..{end} is out of its pack of {} bytes",
+                self.offset,
+                pack.len()
+            )));
+        }
+        Ok(pack.slice(self.offset as usize..end as usize))
+    }
+}
+
+/// Small files concatenated into a single piece of data.
+#[derive(Default)]
+struct Pack {
+    data: Vec<u8>,
+    files: Vec<(PathBuf, Metadata)>,
+}
+
+impl Pack {
+    fn padded(mut self) -> Self {
+        if self.data.len() < PACK_MIN_SIZE {
+            self.data.resize(PACK_MIN_SIZE, 0);
+        }
+        self
+    }
+}
+
+/// Bundles files into packs of at most [`PACK_MAX_SIZE`] bytes as they come, recording the range
+/// of each file.
+#[derive(Default)]
+struct Packer {
+    current: Pack,
+}
+
+impl Packer {
+    /// Add a file to the current pack. Returns the previous pack once full, when the file doesn't
+    /// fit in it.
+    fn add(&mut self, path: PathBuf, mut metadata: Metadata, content: &[u8]) -> Option<Pack> {
+        let full = (!self.current.files.is_empty()
+            && self.current.data.len() + content.len() > PACK_MAX_SIZE)
+            .then(|| std::mem::take(&mut self.current).padded());
+        metadata.packed = Some(PackedRange {
+            offset: self.current.data.len() as u64,
+            length: content.len() as u64,
+        });
+        self.current.data.extend_from_slice(content);
+        self.current.files.push((path, metadata));
+        full
+    }
+
+    /// The last pack, if any file was added since the previous one.
+    fn finish(self) -> Option<Pack> {
+        (!self.current.files.is_empty()).then(|| self.current.padded())
+    }
+}
+
+/// The packs fetched during a download, so that the files bundled in a same pack only fetch it
+/// once. Only the last [`PACK_CACHE_CAPACITY`] packs are kept, the files of a pack being
+/// usually downloaded one after the other.
+#[derive(Debug, Default)]
+pub struct PackCache {
+    packs: VecDeque<(XorName, Bytes)>,
+}
+
+impl PackCache {
+    fn get(&self, addr: &XorName) -> Option<Bytes> {
+        self.packs
+            .iter()
+            .find(|(pack_addr, _)| pack_addr == addr)
+            .map(|(_, pack)| pack.clone())
+    }
+
+    fn insert(&mut self, addr: XorName, pack: Bytes) {
+        if self.packs.len() >= PACK_CACHE_CAPACITY {
+            let _ = self.packs.pop_front();
+        }
+        self.packs.push_back((addr, pack));
+    }
+}
+
+impl Client {
+    /// Same as [`Client::dir_content_upload`], but files up to [`PACKED_FILE_MAX_SIZE`] bytes are
+    /// bundled into shared packs instead of costing at least 3 chunks each.
+    ///
+    /// Packed files are unpacked by [`Client::dir_download`] and
+    /// [`Client::archive_file_download`]. Older clients download the whole pack of a packed file.
+    pub async fn dir_content_upload_packed(
+        &self,
+        dir_path: PathBuf,
+        payment_option: PaymentOption,
+    ) -> Result<(AttoTokens, PrivateArchive), UploadError> {
+        let (cost, results) = self
+            .dir_content_upload_packed_internal(dir_path, payment_option, false)
+            .await?;
+        let mut archive = PrivateArchive::new();
+        for (path, data_map, metadata) in results {
+            archive.add_file(path, data_map, metadata);
+        }
+        Ok((cost, archive))
+    }
+
+    /// Same as [`Client::dir_content_upload_public`], but files up to [`PACKED_FILE_MAX_SIZE`]
+    /// bytes are bundled into shared packs instead of costing at least 3 chunks each.
+    ///
+    /// Packed files are unpacked by [`Client::dir_download_public`] and
+    /// [`Client::archive_file_download_public`]. Older clients download the whole pack of a
+    /// packed file.
+    pub async fn dir_content_upload_public_packed(
+        &self,
+        dir_path: PathBuf,
+        payment_option: PaymentOption,
+    ) -> Result<(AttoTokens, PublicArchive), UploadError> {
+        let (cost, results) = self
+            .dir_content_upload_packed_internal(dir_path, payment_option, true)
+            .await?;
+        let mut archive = PublicArchive::new();
+        for (path, data_map, metadata) in results {
+            archive.add_file(path, DataAddress::new(*data_map.0.name()), metadata);
+        }
+        Ok((cost, archive))
+    }
+
+    async fn dir_content_upload_packed_internal(
+        &self,
+        dir_path: PathBuf,
+        payment_option: PaymentOption,
+        is_public: bool,
+    ) -> Result<(AttoTokens, Vec<(PathBuf, DataMapChunk, Metadata)>), UploadError> {
+        info!("Uploading directory: {dir_path:?} with small files packed, public: {is_public}");
+
+        let mut total_cost = Amount::ZERO;
+        let mut packer = Packer::default();
+        let mut full_packs = vec![];
+        let mut packed_files = vec![];
+        let mut pack_count = 0;
+        let mut streams = vec![];
+        for entry in walkdir::WalkDir::new(&dir_path) {
+            let entry = entry?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let metadata = metadata_from_entry(&entry);
+            let file_path = entry.path().to_path_buf();
+            let relative_path =
+                get_relative_file_path_from_abs_file_and_folder_path(&file_path, &dir_path)
+                    .map_err(UploadError::Encryption)?;
+            if metadata.size <= PACKED_FILE_MAX_SIZE {
+                let content = tokio::fs::read(&file_path).await?;
+                full_packs.extend(packer.add(relative_path, metadata, &content));
+                // Upload the full packs as they come, rather than holding every small file
+                if full_packs.len() >= PACKS_PER_UPLOAD {
+                    pack_count += full_packs.len();
+                    let (mut pack_streams, files) =
+                        self.encrypt_packs(std::mem::take(&mut full_packs), is_public)?;
+                    let cost = self
+                        .pay_and_upload(payment_option.clone(), &mut pack_streams)
+                        .await?;
+                    total_cost = total_cost.saturating_add(cost.as_atto());
+                    packed_files.extend(files);
+                }
+            } else {
+                let size = metadata.size as usize;
+                let stream = encrypt_file(
+                    relative_path,
+                    file_path,
+                    size,
+                    metadata,
+                    is_public,
+                    self.config.compression,
+                )
+                .await
+                .map_err(UploadError::Encryption)?;
+                streams.push(stream);
+            }
+        }
+        let file_streams = streams.len();
+
+        // The last packs are uploaded along with the files uploaded on their own
+        full_packs.extend(packer.finish());
+        pack_count += full_packs.len();
+        let (pack_streams, files) = self.encrypt_packs(full_packs, is_public)?;
+        streams.extend(pack_streams);
+        packed_files.extend(files);
+        crate::loud_info!(
+            "Packed {} small files into {pack_count} packs, uploading {file_streams} other files on their own",
+            packed_files.len()
+        );
+
+        let cost = self.pay_and_upload(payment_option, &mut streams).await?;
+        total_cost = total_cost.saturating_add(cost.as_atto());
+
+        // The packs are referenced by the data maps of their files
+        streams.truncate(file_streams);
+        let mut results = streams_to_file_results(streams)?;
+        results.extend(packed_files);
+        Ok((AttoTokens::from_atto(total_cost), results))
+    }
+
+    /// Self-encrypt packs, returning their streams to upload along with the packed files, each
+    /// pointing to the data map of its pack.
+    #[allow(clippy::type_complexity)]
+    fn encrypt_packs(
+        &self,
+        packs: Vec<Pack>,
+        is_public: bool,
+    ) -> Result<
+        (
+            Vec<EncryptionStream>,
+            Vec<(PathBuf, DataMapChunk, Metadata)>,
+        ),
+        PutError,
+    > {
+        let mut streams = Vec::with_capacity(packs.len());
+        let mut packed_files = vec![];
+        for pack in packs {
+            let size = pack.data.len() as u64;
+            let (stream, data_map_chunk) = EncryptionStream::new_in_memory_compressed(
+                format!("pack of {} files", pack.files.len()),
+                PathBuf::new(),
+                Metadata::new_with_size(size),
+                is_public,
+                Bytes::from(pack.data),
+                self.config.compression,
+            )?;
+            streams.push(stream);
+            packed_files.extend(
+                pack.files
+                    .into_iter()
+                    .map(|(path, metadata)| (path, data_map_chunk.clone(), metadata)),
+            );
+        }
+        Ok((streams, packed_files))
+    }
+
+    /// Download a file of a [`PrivateArchive`] to `to_dest`, unpacking it if it was packed.
+    pub async fn archive_file_download(
+        &self,
+        data_map: &DataMapChunk,
+        metadata: &Metadata,
+        to_dest: PathBuf,
+    ) -> Result<(), DownloadError> {
+        self.archive_file_download_cached(data_map, metadata, to_dest, &mut PackCache::default())
+            .await
+    }
+
+    /// Same as [`Client::archive_file_download`], reusing the packs already fetched for the
+    /// previous files of the archive.
+    pub async fn archive_file_download_cached(
+        &self,
+        data_map: &DataMapChunk,
+        metadata: &Metadata,
+        to_dest: PathBuf,
+        packs: &mut PackCache,
+    ) -> Result<(), DownloadError> {
+        let Some(range) = &metadata.packed else {
+            return self.file_download(data_map, to_dest).await;
+        };
+        let pack_addr = *data_map.0.name();
+        let pack = match packs.get(&pack_addr) {
+            Some(pack) => pack,
+            None => {
+                let pack = self.data_get(data_map).await?;
+                packs.insert(pack_addr, pack.clone());
+                pack
+            }
+        };
+        write_packed_file(range, &pack, &to_dest).await
+    }
+
+    /// Download a file of a [`PublicArchive`] to `to_dest`, unpacking it if it was packed.
+    pub async fn archive_file_download_public(
+        &self,
+        data_addr: &DataAddress,
+        metadata: &Metadata,
+        to_dest: PathBuf,
+    ) -> Result<(), DownloadError> {
+        self.archive_file_download_public_cached(
+            data_addr,
+            metadata,
+            to_dest,
+            &mut PackCache::default(),
+        )
+        .await
+    }
+
+    /// Same as [`Client::archive_file_download_public`], reusing the packs already fetched for
+    /// the previous files of the archive.
+    pub async fn archive_file_download_public_cached(
+        &self,
+        data_addr: &DataAddress,
+        metadata: &Metadata,
+        to_dest: PathBuf,
+        packs: &mut PackCache,
+    ) -> Result<(), DownloadError> {
+        let Some(range) = &metadata.packed else {
+            return self.file_download_public(data_addr, to_dest).await;
+        };
+        let pack = self.pack_get_public(data_addr, packs).await?;
+        write_packed_file(range, &pack, &to_dest).await
+    }
+
+    /// The pack at `data_addr`, fetched unless it is already in `packs`.
+    pub(super) async fn pack_get_public(
+        &self,
+        data_addr: &DataAddress,
+        packs: &mut PackCache,
+    ) -> Result<Bytes, GetError> {
+        let pack_addr = *data_addr.xorname();
+        if let Some(pack) = packs.get(&pack_addr) {
+            return Ok(pack);
+        }
+        let pack = self.data_get_public(data_addr).await?;
+        packs.insert(pack_addr, pack.clone());
+        Ok(pack)
+    }
+}
+
+async fn write_packed_file(
+    range: &PackedRange,
+    pack: &Bytes,
+    to_dest: &Path,
+) -> Result<(), DownloadError> {
+    if let Some(parent) = to_dest.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    tokio::fs::write(to_dest, range.unpack(pack)?).await?;
+    debug!(
+        "Unpacked {} bytes of packed file to {to_dest:?}",
+        range.length
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pack_files(files: Vec<(PathBuf, Metadata, Vec<u8>)>) -> Vec<Pack> {
+        let mut packer = Packer::default();
+        let mut packs: Vec<_> = files
+            .into_iter()
+            .filter_map(|(path, metadata, content)| packer.add(path, metadata, &content))
+            .collect();
+        packs.extend(packer.finish());
+        packs
+    }
+
+    fn file(name: &str, size: usize) -> (PathBuf, Metadata, Vec<u8>) {
+        (
+            PathBuf::from(name),
+            Metadata::new_with_size(size as u64),
+            vec![name.as_bytes()[0]; size],
+        )
+    }
+
+    #[test]
+    fn test_pack_files_roundtrip() {
+        let files = vec![
+            file("a", 10),
+            file("b", PACK_MAX_SIZE - 20),
+            file("c", 30),
+            file("d", 0),
+        ];
+        let packs = pack_files(files.clone());
+        assert_eq!(packs.len(), 2);
+        assert_eq!(packs[0].files.len(), 2);
+        assert_eq!(packs[1].files.len(), 2);
+
+        let unpacked: Vec<_> = packs
+            .iter()
+            .flat_map(|pack| {
+                let data = Bytes::from(pack.data.clone());
+                pack.files.iter().map(move |(path, metadata)| {
+                    let range = metadata.packed.unwrap();
+                    (path.clone(), range.unpack(&data).unwrap().to_vec())
+                })
+            })
+            .collect();
+        let expected: Vec<_> = files
+            .into_iter()
+            .map(|(path, _, content)| (path, content))
+            .collect();
+        assert_eq!(unpacked, expected);
+    }
+
+    #[test]
+    fn test_tiny_pack_is_padded() {
+        let packs = pack_files(vec![file("a", 1)]);
+        assert_eq!(packs[0].data.len(), PACK_MIN_SIZE);
+        let range = packs[0].files[0].1.packed.unwrap();
+        assert_eq!(
+            range,
+            PackedRange {
+                offset: 0,
+                length: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_unpack_out_of_range() {
+        let range = PackedRange {
+            offset: 2,
+            length: 5,
+        };
+        assert!(range.unpack(&Bytes::from_static(b"abcdef")).is_err());
+    }
+
+    #[test]
+    fn test_pack_cache_keeps_the_last_packs() {
+        let mut cache = PackCache::default();
+        let addrs: Vec<_> = (0..=PACK_CACHE_CAPACITY)
+            .map(|_| XorName::random(&mut rand::thread_rng()))
+            .collect();
+        for (i, addr) in addrs.iter().enumerate() {
+            cache.insert(*addr, Bytes::from(vec![i as u8]));
+        }
+        assert!(cache.get(&addrs[0]).is_none());
+        assert_eq!(cache.get(&addrs[1]), Some(Bytes::from(vec![1u8])));
+        assert_eq!(
+            cache.get(&addrs[PACK_CACHE_CAPACITY]),
+            Some(Bytes::from(vec![PACK_CACHE_CAPACITY as u8]))
+        );
+    }
+}
@@ -0,0 +1,361 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Secondary indexes, mapping terms to lists of addresses.
+//!
+//! An index is owned by an application key. Each term of the index is stored as pages of up to
+//! [`INDEX_PAGE_SIZE`] addresses, each page being public data behind its own [`Pointer`], and a
+//! head [`Pointer`] to the pointer of the last page. The keys of the pointers are derived from
+//! the application key and the term, so anyone knowing the application's public key can query the
+//! index, while only the application can insert into it.
+//!
+//! ```ignore
+//! term head pointer ---> page 2 pointer ---> [addresses 2000..]
+//!                        page 1 pointer ---> [addresses 1000..2000]
+//!                        page 0 pointer ---> [addresses 0..1000]
+//! ```
+//!
+//! Inserts are not safe against concurrent inserts to the same term by several processes. An
+//! insert interrupted between the pointer of a new page and the head is completed by the next
+//! insert to the term, so a failed insert can simply be retried.
+//!
+//! [`Pointer`]: crate::Pointer
+
+use crate::client::data_types::pointer::{PointerError, PointerTarget};
+use crate::client::high_level::data::DataAddress;
+use crate::client::key_derivation::{DerivationIndex, MainPubkey, MainSecretKey};
+use crate::client::payment::PaymentOption;
+use crate::client::{Client, GetError, PutError};
+use crate::networking::NetworkAddress;
+use crate::{AttoTokens, PublicKey, SecretKey};
+use ant_protocol::storage::{ChunkAddress, PointerAddress};
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use xor_name::XorName;
+
+/// The maximum number of addresses in a page of a term
+pub const INDEX_PAGE_SIZE: usize = 1_000;
+
+/// Domain separator of the derivation of the term keys
+const INDEX_TERM_DERIVATION_DOMAIN: &[u8] = b"autonomi-index/";
+
+#[derive(Error, Debug)]
+pub enum IndexError {
+    #[error("Underlying PointerError: {0}")]
+    Pointer(#[from] PointerError),
+    #[error("Failed to put an index page: {0}")]
+    Put(#[from] PutError),
+    #[error("Failed to get an index page: {0}")]
+    Get(#[from] GetError),
+    #[error("Failed to serialize an index page: {0}")]
+    Serialization(String),
+    #[error("Corrupt index: {0}")]
+    Corrupt(String),
+    #[error("Page {page} is out of range, the term has {pages} page(s)")]
+    PageOutOfRange { page: u64, pages: u64 },
+    #[error("Invalid cost")]
+    InvalidCost,
+}
+
+/// A page of the addresses indexed under a term, as stored on the network
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct StoredIndexPage {
+    page: u64,
+    addresses: Vec<NetworkAddress>,
+}
+
+/// A page of the result of [`Client::index_query`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexQueryPage {
+    /// The addresses of the page, in insertion order
+    pub addresses: Vec<NetworkAddress>,
+    /// The number of the page, from 0
+    pub page: u64,
+    /// The number of pages of the term, 0 if nothing is indexed under it
+    pub pages: u64,
+}
+
+impl Client {
+    /// Index an address under a term of the index owned by `index_key`.
+    ///
+    /// Inserting an address already indexed under the term is a no-op. Every insert pays for the
+    /// upload of the updated page, and a new page also pays for its pointer.
+    pub async fn index_insert(
+        &self,
+        index_key: &SecretKey,
+        term: &str,
+        address: NetworkAddress,
+        payment_option: PaymentOption,
+    ) -> Result<AttoTokens, IndexError> {
+        let term_key = index_term_secret_key(index_key, term);
+        let head_addr = PointerAddress::new(term_key.public_key());
+
+        let mut last_page = match self.pointer_get(&head_addr).await {
+            Ok(head) => Some(
+                self.index_get_page_from_pointer_target(head.target())
+                    .await?,
+            ),
+            Err(PointerError::GetError(GetError::RecordNotFound)) => None,
+            Err(err) => return Err(err.into()),
+        };
+
+        // an interrupted insert may have created the pointer of the next page without moving the
+        // head to it, in which case that page is the last one and the head is repaired below
+        let next_page = last_page.as_ref().map_or(0, |page| page.page + 1);
+        let next_page_addr =
+            PointerAddress::new(index_page_secret_key(&term_key, next_page).public_key());
+        let head_is_stale = self.pointer_check_existence(&next_page_addr).await?;
+        if head_is_stale {
+            warn!("Head of term {term:?} is behind page {next_page}, completing the last insert");
+            last_page = Some(
+                self.index_get_page_from_pointer_target(&PointerTarget::PointerAddress(
+                    next_page_addr,
+                ))
+                .await?,
+            );
+        }
+
+        match last_page {
+            Some(page) if page.addresses.contains(&address) => {
+                debug!("Address {address:?} already indexed under term {term:?}");
+                if head_is_stale {
+                    let head_target = PointerTarget::PointerAddress(next_page_addr);
+                    return self
+                        .index_set_pointer(&term_key, head_target, payment_option)
+                        .await;
+                }
+                Ok(AttoTokens::zero())
+            }
+            Some(mut page) if page.addresses.len() < INDEX_PAGE_SIZE => {
+                page.addresses.push(address);
+                let (data_cost, data_addr) =
+                    self.index_put_page(&page, payment_option.clone()).await?;
+                let page_key = index_page_secret_key(&term_key, page.page);
+                let target = PointerTarget::ChunkAddress(ChunkAddress::new(*data_addr.xorname()));
+                self.pointer_update(&page_key, target).await?;
+
+                let head_cost = if head_is_stale {
+                    let head_target = PointerTarget::PointerAddress(next_page_addr);
+                    self.index_set_pointer(&term_key, head_target, payment_option)
+                        .await?
+                } else {
+                    AttoTokens::zero()
+                };
+                data_cost
+                    .checked_add(head_cost)
+                    .ok_or(IndexError::InvalidCost)
+            }
+            last_page => {
+                let page = StoredIndexPage {
+                    page: last_page.map_or(0, |page| page.page + 1),
+                    addresses: vec![address],
+                };
+                debug!("Starting page {} of term {term:?}", page.page);
+                let (data_cost, data_addr) =
+                    self.index_put_page(&page, payment_option.clone()).await?;
+                let page_key = index_page_secret_key(&term_key, page.page);
+                let target = PointerTarget::ChunkAddress(ChunkAddress::new(*data_addr.xorname()));
+                let page_cost = self
+                    .index_set_pointer(&page_key, target, payment_option.clone())
+                    .await?;
+
+                let page_addr = PointerAddress::new(page_key.public_key());
+                let head_target = PointerTarget::PointerAddress(page_addr);
+                let head_cost = self
+                    .index_set_pointer(&term_key, head_target, payment_option)
+                    .await?;
+
+                data_cost
+                    .checked_add(page_cost)
+                    .and_then(|cost| cost.checked_add(head_cost))
+                    .ok_or(IndexError::InvalidCost)
+            }
+        }
+    }
+
+    /// Get a page of the addresses indexed under a term of the index owned by `index`.
+    ///
+    /// Pages are numbered from 0, in insertion order. The returned page tells the number of pages
+    /// of the term, so that the next ones can be queried.
+    pub async fn index_query(
+        &self,
+        index: &PublicKey,
+        term: &str,
+        page: u64,
+    ) -> Result<IndexQueryPage, IndexError> {
+        let term_pk = index_term_public_key(index, term);
+        let head = match self.pointer_get(&PointerAddress::new(term_pk)).await {
+            Ok(head) => head,
+            Err(PointerError::GetError(GetError::RecordNotFound)) if page == 0 => {
+                return Ok(IndexQueryPage {
+                    addresses: vec![],
+                    page,
+                    pages: 0,
+                });
+            }
+            Err(PointerError::GetError(GetError::RecordNotFound)) => {
+                return Err(IndexError::PageOutOfRange { page, pages: 0 });
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        // the head points to the last page, which holds the number of pages
+        let last_page = self
+            .index_get_page_from_pointer_target(head.target())
+            .await?;
+        let pages = last_page.page + 1;
+        let stored = if page == last_page.page {
+            last_page
+        } else if page < pages {
+            let page_pk = index_page_public_key(&term_pk, page);
+            let target = PointerTarget::PointerAddress(PointerAddress::new(page_pk));
+            self.index_get_page_from_pointer_target(&target).await?
+        } else {
+            return Err(IndexError::PageOutOfRange { page, pages });
+        };
+
+        if stored.page != page {
+            return Err(IndexError::Corrupt(format!(
+                "page {page} of term {term:?} claims to be page {}",
+                stored.page
+            )));
+        }
+        Ok(IndexQueryPage {
+            addresses: stored.addresses,
+            page,
+            pages,
+        })
+    }
+
+    /// Get the page behind a page pointer, or behind the head pointer targeting a page pointer
+    async fn index_get_page_from_pointer_target(
+        &self,
+        target: &PointerTarget,
+    ) -> Result<StoredIndexPage, IndexError> {
+        let page_pointer = match target {
+            PointerTarget::PointerAddress(addr) => self.pointer_get(addr).await?,
+            other => {
+                return Err(IndexError::Corrupt(format!(
+                    "expected a page pointer, got {other:?}"
+                )));
+            }
+        };
+        let data_addr = match page_pointer.target() {
+            PointerTarget::ChunkAddress(addr) => DataAddress::new(*addr.xorname()),
+            other => {
+                return Err(IndexError::Corrupt(format!(
+                    "expected a page data address, got {other:?}"
+                )));
+            }
+        };
+        let bytes = self.data_get_public(&data_addr).await?;
+        rmp_serde::from_slice(&bytes).map_err(|err| {
+            IndexError::Corrupt(format!("failed to deserialize page at {data_addr}: {err}"))
+        })
+    }
+
+    /// Point the pointer owned by `key` to `target`, creating the pointer if it doesn't exist yet
+    async fn index_set_pointer(
+        &self,
+        key: &SecretKey,
+        target: PointerTarget,
+        payment_option: PaymentOption,
+    ) -> Result<AttoTokens, IndexError> {
+        let addr = PointerAddress::new(key.public_key());
+        if self.pointer_check_existence(&addr).await? {
+            self.pointer_update(key, target).await?;
+            Ok(AttoTokens::zero())
+        } else {
+            let (cost, _) = self.pointer_create(key, target, payment_option).await?;
+            Ok(cost)
+        }
+    }
+
+    async fn index_put_page(
+        &self,
+        page: &StoredIndexPage,
+        payment_option: PaymentOption,
+    ) -> Result<(AttoTokens, DataAddress), IndexError> {
+        let bytes =
+            rmp_serde::to_vec(page).map_err(|err| IndexError::Serialization(err.to_string()))?;
+        Ok(self
+            .data_put_public(Bytes::from(bytes), payment_option)
+            .await?)
+    }
+}
+
+fn term_derivation_index(term: &str) -> DerivationIndex {
+    let mut bytes = INDEX_TERM_DERIVATION_DOMAIN.to_vec();
+    bytes.extend_from_slice(term.as_bytes());
+    DerivationIndex::from_bytes(XorName::from_content(&bytes).0)
+}
+
+fn page_derivation_index(page: u64) -> DerivationIndex {
+    DerivationIndex::from_bytes(XorName::from_content(&page.to_be_bytes()).0)
+}
+
+fn index_term_secret_key(index_key: &SecretKey, term: &str) -> SecretKey {
+    MainSecretKey::new(index_key.clone())
+        .derive_key(&term_derivation_index(term))
+        .into()
+}
+
+fn index_term_public_key(index: &PublicKey, term: &str) -> PublicKey {
+    MainPubkey::from(*index)
+        .derive_key(&term_derivation_index(term))
+        .into()
+}
+
+fn index_page_secret_key(term_key: &SecretKey, page: u64) -> SecretKey {
+    MainSecretKey::new(term_key.clone())
+        .derive_key(&page_derivation_index(page))
+        .into()
+}
+
+fn index_page_public_key(term_pk: &PublicKey, page: u64) -> PublicKey {
+    MainPubkey::from(*term_pk)
+        .derive_key(&page_derivation_index(page))
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_key_derivation() {
+        let index_key = SecretKey::random();
+        let index = index_key.public_key();
+
+        let term_key = index_term_secret_key(&index_key, "cats");
+        assert_eq!(term_key.public_key(), index_term_public_key(&index, "cats"));
+        assert_ne!(term_key.public_key(), index_term_public_key(&index, "dogs"));
+
+        let page_key = index_page_secret_key(&term_key, 3);
+        assert_eq!(
+            page_key.public_key(),
+            index_page_public_key(&term_key.public_key(), 3)
+        );
+        assert_ne!(
+            page_key.public_key(),
+            index_page_public_key(&term_key.public_key(), 4)
+        );
+    }
+
+    #[test]
+    fn test_index_page_serialization() {
+        let page = StoredIndexPage {
+            page: 2,
+            addresses: vec![NetworkAddress::from(ChunkAddress::new(XorName([1; 32])))],
+        };
+        let bytes = rmp_serde::to_vec(&page).expect("serialize");
+        let deserialized: StoredIndexPage = rmp_serde::from_slice(&bytes).expect("deserialize");
+        assert_eq!(page, deserialized);
+    }
+}
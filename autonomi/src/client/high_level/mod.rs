@@ -8,6 +8,8 @@
 
 pub mod data;
 pub mod files;
+pub mod index;
+pub mod site;
 pub mod vault;
 
 /// Registers are a mutable piece of data on the Network.
@@ -0,0 +1,204 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Audit trail of register updates.
+//!
+//! An audited update also puts an audit [`GraphEntry`] linking the old head entry of the register
+//! to the new one, signed by a key derived from the register owner's key, at an address derived
+//! from the new head. The audit entry records the new value and the time of the update, so that
+//! the update chain can later be checked against what its writer signed.
+
+use crate::AttoTokens;
+use crate::client::data_types::graph::{GraphContent, GraphEntry, GraphEntryAddress, GraphError};
+use crate::client::high_level::register::{
+    PublicKey, RegisterAddress, RegisterError, RegisterValue, SecretKey,
+};
+use crate::client::key_derivation::{DerivationIndex, MainPubkey, MainSecretKey};
+use crate::client::payment::PaymentOption;
+use crate::client::{Client, GetError};
+use futures::future::join_all;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use xor_name::XorName;
+
+/// Domain separator of the derivation of the audit entry keys
+const AUDIT_DERIVATION_DOMAIN: &[u8] = b"register-audit";
+
+/// An update of a register, from its audit trail.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegisterAuditRecord {
+    /// The head entry before the update
+    pub old_head: GraphEntryAddress,
+    /// The head entry after the update
+    pub new_head: GraphEntryAddress,
+    /// The value written by the update
+    pub value: RegisterValue,
+    /// The audit entry of the update and the time of the update claimed by its writer, `None` if
+    /// the update was not audited
+    pub audit: Option<(GraphEntryAddress, SystemTime)>,
+}
+
+impl Client {
+    /// Update the value of a register and record the update in the register's audit trail.
+    ///
+    /// This pays for an audit [`GraphEntry`] on top of the update itself, see
+    /// [`Client::register_audit_trail`]. Only single owner registers can be audited.
+    pub async fn register_update_audited(
+        &self,
+        owner: &SecretKey,
+        new_value: RegisterValue,
+        payment_option: PaymentOption,
+    ) -> Result<AttoTokens, RegisterError> {
+        let (update_cost, old_head, new_head) = self
+            .register_update_heads(owner, new_value, payment_option.clone())
+            .await?;
+
+        let audit_entry = create_audit_entry(owner, &old_head, &new_head, new_value);
+        let (audit_cost, audit_addr) = self.graph_entry_put(audit_entry, payment_option).await?;
+        debug!("Recorded the update of register to {new_head:?} in audit entry {audit_addr:?}");
+
+        update_cost
+            .checked_add(audit_cost)
+            .ok_or(RegisterError::InvalidCost)
+    }
+
+    /// Reconstruct the update chain of a register, from its first update to its latest one.
+    ///
+    /// Each update is checked against its audit entry, if any: the audit entry must be signed by
+    /// the register owner and link the same heads and value, or
+    /// [`RegisterError::TamperedAuditTrail`] is returned.
+    pub async fn register_audit_trail(
+        &self,
+        addr: &RegisterAddress,
+    ) -> Result<Vec<RegisterAuditRecord>, RegisterError> {
+        let register_owner = MainPubkey::from(addr.owner());
+
+        // walk the register entries from the root to the head
+        let mut heads = Vec::new();
+        let mut current = addr.to_underlying_graph_root();
+        loop {
+            let (entry, next_derivation) = match self
+                .register_get_graph_entry_and_next_derivation_index(&current)
+                .await
+            {
+                Ok(res) => res,
+                Err(RegisterError::GraphError(GraphError::GetError(GetError::RecordNotFound))) => {
+                    break;
+                }
+                Err(err) => return Err(err),
+            };
+            heads.push((current, entry.content));
+            let next_pk: PublicKey = register_owner.derive_key(&next_derivation).into();
+            current = GraphEntryAddress::new(next_pk);
+        }
+
+        let updates = heads.windows(2).map(|pair| {
+            let (old_head, _) = pair[0];
+            let (new_head, value) = pair[1];
+            async move {
+                let audit_pk: PublicKey = register_owner
+                    .derive_key(&audit_derivation_index(&new_head))
+                    .into();
+                let audit_addr = GraphEntryAddress::new(audit_pk);
+                let audit = match self.graph_entry_get(&audit_addr).await {
+                    Ok(entry) => {
+                        let timestamp = verify_audit_entry(&entry, &old_head, &new_head, value)?;
+                        Some((audit_addr, timestamp))
+                    }
+                    Err(GraphError::GetError(GetError::RecordNotFound)) => None,
+                    Err(err) => return Err(RegisterError::GraphError(err)),
+                };
+                Ok(RegisterAuditRecord {
+                    old_head,
+                    new_head,
+                    value,
+                    audit,
+                })
+            }
+        });
+        join_all(updates).await.into_iter().collect()
+    }
+}
+
+/// The derivation of the key of the audit entry of the update to the given head
+fn audit_derivation_index(new_head: &GraphEntryAddress) -> DerivationIndex {
+    let mut bytes = AUDIT_DERIVATION_DOMAIN.to_vec();
+    bytes.extend_from_slice(&new_head.owner().to_bytes());
+    DerivationIndex::from_bytes(XorName::from_content(&bytes).0)
+}
+
+fn create_audit_entry(
+    owner: &SecretKey,
+    old_head: &GraphEntryAddress,
+    new_head: &GraphEntryAddress,
+    value: RegisterValue,
+) -> GraphEntry {
+    let audit_key = MainSecretKey::new(owner.clone()).derive_key(&audit_derivation_index(new_head));
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64;
+    let mut content: GraphContent = [0; 32];
+    content[..8].copy_from_slice(&millis.to_be_bytes());
+    GraphEntry::new(
+        &audit_key.into(),
+        vec![*old_head.owner()],
+        content,
+        vec![(*new_head.owner(), value)],
+    )
+}
+
+/// Check that an audit entry records the given update, returning the time of the update.
+fn verify_audit_entry(
+    entry: &GraphEntry,
+    old_head: &GraphEntryAddress,
+    new_head: &GraphEntryAddress,
+    value: RegisterValue,
+) -> Result<SystemTime, RegisterError> {
+    if !entry.verify_signature() {
+        return Err(RegisterError::TamperedAuditTrail(format!(
+            "invalid signature of the audit entry of {new_head:?}"
+        )));
+    }
+    if entry.parents != [*old_head.owner()] || entry.descendants != [(*new_head.owner(), value)] {
+        return Err(RegisterError::TamperedAuditTrail(format!(
+            "the audit entry of {new_head:?} does not record the update from {old_head:?}"
+        )));
+    }
+    let mut millis = [0; 8];
+    millis.copy_from_slice(&entry.content[..8]);
+    Ok(UNIX_EPOCH + Duration::from_millis(u64::from_be_bytes(millis)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audit_entry_verification() {
+        let owner = SecretKey::random();
+        let old_head = GraphEntryAddress::new(SecretKey::random().public_key());
+        let new_head = GraphEntryAddress::new(SecretKey::random().public_key());
+        let value = [7; 32];
+
+        let entry = create_audit_entry(&owner, &old_head, &new_head, value);
+        let expected_pk: PublicKey = MainPubkey::from(owner.public_key())
+            .derive_key(&audit_derivation_index(&new_head))
+            .into();
+        assert_eq!(entry.owner, expected_pk);
+        let timestamp =
+            verify_audit_entry(&entry, &old_head, &new_head, value).expect("valid audit entry");
+        assert!(timestamp <= SystemTime::now());
+
+        assert!(verify_audit_entry(&entry, &old_head, &new_head, [8; 32]).is_err());
+        assert!(verify_audit_entry(&entry, &new_head, &old_head, value).is_err());
+
+        let mut tampered = entry;
+        tampered.content[31] = 1;
+        assert!(verify_audit_entry(&tampered, &old_head, &new_head, value).is_err());
+    }
+}
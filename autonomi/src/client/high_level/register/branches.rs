@@ -0,0 +1,280 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Branches of a forked register history.
+//!
+//! Concurrent updates of a register by several writers put different entries at the same address
+//! of its graph, forking its history. Each entry of a fork links to its own next address, so the
+//! history becomes a tree whose leaves are the heads of its branches. A merge entry lists the
+//! heads of all the branches as parents, which closes them.
+
+use crate::AttoTokens;
+use crate::client::data_types::graph::{GraphEntry, GraphEntryAddress, GraphError};
+use crate::client::data_types::pointer::PointerTarget;
+use crate::client::high_level::register::{
+    PublicKey, RegisterAddress, RegisterError, RegisterValue, SecretKey,
+    get_derivation_from_graph_entry,
+};
+use crate::client::key_derivation::MainPubkey;
+use crate::client::payment::PaymentOption;
+use crate::client::{Client, GetError};
+use std::collections::HashSet;
+
+/// An entry of the history of a register.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegisterHistoryEntry {
+    /// The address of the entry in the register graph
+    pub address: GraphEntryAddress,
+    /// The value written by the entry
+    pub value: RegisterValue,
+    /// The entries the entry links to as parents
+    pub parents: Vec<GraphEntryAddress>,
+}
+
+/// A branch of the history of a register, from the root entry to its head.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegisterBranch {
+    /// The entries of the branch, from the root entry to the head
+    pub entries: Vec<RegisterHistoryEntry>,
+    /// Whether the head of the branch is a parent of a later entry, i.e. was merged or updated
+    pub merged: bool,
+}
+
+impl RegisterBranch {
+    /// The latest entry of the branch.
+    pub fn head(&self) -> Option<&RegisterHistoryEntry> {
+        self.entries.last()
+    }
+}
+
+/// The branches of the history of a register.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegisterBranches {
+    /// The branches of the history, the one followed by register updates first
+    pub branches: Vec<RegisterBranch>,
+    /// The entry targeted by the head pointer of the register, `None` if it can't be fetched
+    pub head_pointer: Option<GraphEntryAddress>,
+}
+
+impl RegisterBranches {
+    /// The branches whose head has not been merged yet.
+    pub fn open_branches(&self) -> impl Iterator<Item = &RegisterBranch> {
+        self.branches.iter().filter(|branch| !branch.merged)
+    }
+
+    /// Whether the register history has diverged into several open branches.
+    pub fn is_forked(&self) -> bool {
+        self.open_branches().count() > 1
+    }
+
+    /// The number of entries shared by all the branches, from the root entry.
+    pub fn common_len(&self) -> usize {
+        let Some((first, others)) = self.branches.split_first() else {
+            return 0;
+        };
+        (0..first.entries.len())
+            .take_while(|i| {
+                others
+                    .iter()
+                    .all(|branch| branch.entries.get(*i) == first.entries.get(*i))
+            })
+            .count()
+    }
+}
+
+impl Client {
+    /// Get the branches of the history of a register, to see the heads a fork diverged into.
+    ///
+    /// This fetches every entry of the register history, all the entries of a fork included.
+    pub async fn register_branches(
+        &self,
+        addr: &RegisterAddress,
+    ) -> Result<RegisterBranches, RegisterError> {
+        let owner = MainPubkey::from(addr.owner());
+        let mut branches = vec![];
+        let mut stack = vec![(addr.to_underlying_graph_root(), vec![])];
+        while let Some((entry_addr, path)) = stack.pop() {
+            let mut entries: Vec<_> = self
+                .register_entries_at(&entry_addr)
+                .await?
+                .into_iter()
+                .map(|entry| {
+                    let derivation = get_derivation_from_graph_entry(&entry).ok();
+                    (entry, derivation)
+                })
+                .collect();
+            if entries.is_empty() {
+                if !path.is_empty() {
+                    branches.push(path);
+                }
+                continue;
+            }
+
+            // Updates follow the entry with the smallest derivation index, explore it first
+            entries.sort_by_key(|(_, derivation)| (derivation.is_none(), *derivation));
+            for (entry, derivation) in entries.into_iter().rev() {
+                let mut path = path.clone();
+                path.push(RegisterHistoryEntry {
+                    address: entry_addr,
+                    value: entry.content,
+                    parents: entry
+                        .parents
+                        .iter()
+                        .map(|parent| GraphEntryAddress::new(*parent))
+                        .collect(),
+                });
+                match derivation {
+                    Some(derivation) => {
+                        let next: PublicKey = owner.derive_key(&derivation).into();
+                        stack.push((GraphEntryAddress::new(next), path));
+                    }
+                    // A corrupt entry without a single descendant ends its branch
+                    None => branches.push(path),
+                }
+            }
+        }
+        if branches.is_empty() {
+            return Err(GraphError::GetError(GetError::RecordNotFound).into());
+        }
+
+        let head_pointer = match self.pointer_get(&addr.to_underlying_head_pointer()).await {
+            Ok(pointer) => match pointer.target() {
+                PointerTarget::GraphEntryAddress(target) => Some(*target),
+                other => {
+                    warn!("Register {addr} head pointer targets a non graph entry: {other:?}");
+                    None
+                }
+            },
+            Err(err) => {
+                warn!("Failed to get the head pointer of register {addr}: {err}");
+                None
+            }
+        };
+
+        Ok(RegisterBranches {
+            branches: mark_merged_branches(branches),
+            head_pointer,
+        })
+    }
+
+    /// Merge the open branches of a forked register with a new entry holding `value`, linking the
+    /// heads of all the branches as its parents. The register then resolves to `value`.
+    pub async fn register_merge(
+        &self,
+        owner: &SecretKey,
+        value: RegisterValue,
+        payment_option: PaymentOption,
+    ) -> Result<AttoTokens, RegisterError> {
+        let addr = RegisterAddress::new(owner.public_key());
+        let branches = self.register_branches(&addr).await?;
+        if !branches.is_forked() {
+            return Err(RegisterError::NotForked);
+        }
+
+        let heads: Vec<PublicKey> = branches
+            .open_branches()
+            .filter_map(|branch| branch.head())
+            .map(|head| *head.address.owner())
+            .collect();
+        info!(
+            "Merging {} branches of register {addr} into a new entry",
+            heads.len()
+        );
+        let (cost, _old_head, new_head) = self
+            .register_update_heads_with_parents(owner, value, &heads, payment_option)
+            .await?;
+        debug!("Merged the branches of register {addr} at {new_head:?}");
+        Ok(cost)
+    }
+
+    /// All the entries at an address of a register graph, several if it is forked there.
+    async fn register_entries_at(
+        &self,
+        addr: &GraphEntryAddress,
+    ) -> Result<Vec<GraphEntry>, RegisterError> {
+        match self.graph_entry_get(addr).await {
+            Ok(entry) => Ok(vec![entry]),
+            Err(GraphError::Fork(entries)) => Ok(entries),
+            Err(GraphError::GetError(GetError::RecordNotFound)) => Ok(vec![]),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+/// Flag the branches whose head is the parent of an entry of the history.
+fn mark_merged_branches(paths: Vec<Vec<RegisterHistoryEntry>>) -> Vec<RegisterBranch> {
+    let parents: HashSet<GraphEntryAddress> = paths
+        .iter()
+        .flatten()
+        .flat_map(|entry| entry.parents.iter().copied())
+        .collect();
+    paths
+        .into_iter()
+        .map(|entries| {
+            let merged = entries
+                .last()
+                .is_some_and(|head| parents.contains(&head.address));
+            RegisterBranch { entries, merged }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(
+        address: GraphEntryAddress,
+        value: u8,
+        parents: &[GraphEntryAddress],
+    ) -> RegisterHistoryEntry {
+        RegisterHistoryEntry {
+            address,
+            value: [value; 32],
+            parents: parents.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_merged_branches_are_closed() {
+        let [root, fork, next, merge] =
+            [(); 4].map(|_| GraphEntryAddress::new(SecretKey::random().public_key()));
+
+        // root -> fork (two concurrent entries) -> next on the followed branch
+        let followed = vec![
+            entry(root, 0, &[]),
+            entry(fork, 1, &[root]),
+            entry(next, 3, &[fork]),
+        ];
+        let other = vec![entry(root, 0, &[]), entry(fork, 2, &[root])];
+        let branches = RegisterBranches {
+            branches: mark_merged_branches(vec![followed.clone(), other.clone()]),
+            head_pointer: Some(next),
+        };
+        // updating on top of the fork closes the other entry of the fork
+        assert!(!branches.is_forked());
+        assert_eq!(branches.common_len(), 1);
+
+        // a fork at the head stays open until merged
+        let followed = vec![entry(root, 0, &[]), entry(fork, 1, &[root])];
+        let branches = RegisterBranches {
+            branches: mark_merged_branches(vec![followed.clone(), other.clone()]),
+            head_pointer: Some(fork),
+        };
+        assert!(branches.is_forked());
+        assert_eq!(branches.open_branches().count(), 2);
+
+        let mut merged = followed;
+        merged.push(entry(merge, 4, &[fork]));
+        let branches = RegisterBranches {
+            branches: mark_merged_branches(vec![merged, other]),
+            head_pointer: Some(merge),
+        };
+        assert!(!branches.is_forked());
+    }
+}
@@ -17,10 +17,16 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use xor_name::XorName;
 
+mod audit;
+mod branches;
 mod history;
+mod writers;
 
 pub use crate::{PublicKey, SecretKey};
+pub use audit::RegisterAuditRecord;
+pub use branches::{RegisterBranch, RegisterBranches, RegisterHistoryEntry};
 pub use history::RegisterHistory;
+pub use writers::{RegisterWriteProposal, RegisterWriteSignature, RegisterWriters};
 
 /// A Register is addressed at a [`RegisterAddress`] which is in fact the owner's [`PublicKey`].
 /// There can only be one register stored at [`PublicKey`].
@@ -101,6 +107,16 @@ pub enum RegisterError {
         "Invalid register value length: {0}, expected something within {REGISTER_VALUE_SIZE} bytes"
     )]
     InvalidRegisterValueLength(usize),
+    #[error("Invalid register writer set: {0}")]
+    InvalidWriterSet(String),
+    #[error("Not enough writer signatures: got {got}, {required} required")]
+    NotEnoughSignatures { got: usize, required: usize },
+    #[error("Invalid writer signatures: {0}")]
+    InvalidSignatures(String),
+    #[error("Tampered register audit trail: {0}")]
+    TamperedAuditTrail(String),
+    #[error("Register is not forked, there are no branches to merge")]
+    NotForked,
 }
 
 /// Hard coded derivation index for the register head pointer
@@ -179,6 +195,33 @@ impl Client {
         new_value: RegisterValue,
         payment_option: PaymentOption,
     ) -> Result<AttoTokens, RegisterError> {
+        let (cost, _old_head, _new_head) = self
+            .register_update_heads(owner, new_value, payment_option)
+            .await?;
+        Ok(cost)
+    }
+
+    /// Update the value of a register, returning the cost along with the addresses of the old and
+    /// new head entries.
+    async fn register_update_heads(
+        &self,
+        owner: &SecretKey,
+        new_value: RegisterValue,
+        payment_option: PaymentOption,
+    ) -> Result<(AttoTokens, GraphEntryAddress, GraphEntryAddress), RegisterError> {
+        self.register_update_heads_with_parents(owner, new_value, &[], payment_option)
+            .await
+    }
+
+    /// Same as [`Client::register_update_heads`], also linking the new head entry to the
+    /// `extra_parents` entries on top of the old head, e.g. the heads of the branches it merges.
+    async fn register_update_heads_with_parents(
+        &self,
+        owner: &SecretKey,
+        new_value: RegisterValue,
+        extra_parents: &[PublicKey],
+        payment_option: PaymentOption,
+    ) -> Result<(AttoTokens, GraphEntryAddress, GraphEntryAddress), RegisterError> {
         // get the pointer of the register head
         let addr = RegisterAddress(owner.public_key());
         let pointer_addr = register_head_pointer_address(&addr);
@@ -204,7 +247,12 @@ impl Client {
         // create a new entry with the new value
         let main_key = MainSecretKey::new(owner.clone());
         let new_key = main_key.derive_key(&new_derivation);
-        let parents = vec![parent_entry.owner];
+        let mut parents = vec![parent_entry.owner];
+        for parent in extra_parents {
+            if !parents.contains(parent) {
+                parents.push(*parent);
+            }
+        }
         let next_derivation = DerivationIndex::random(&mut rand::thread_rng());
         let next_pk = main_key.public_key().derive_key(&next_derivation);
         let descendants = vec![(next_pk.into(), next_derivation.into_bytes())];
@@ -233,7 +281,7 @@ impl Client {
         let pointer_key = register_head_pointer_sk(&main_key.into());
         self.pointer_update(&pointer_key, target).await?;
 
-        Ok(cost)
+        Ok((cost, parent_entry.address(), new_graph_entry_addr))
     }
 
     /// Get the current value of the register
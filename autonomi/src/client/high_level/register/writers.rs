@@ -0,0 +1,376 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Multi-writer registers.
+//!
+//! A multi-writer register is owned by a BLS threshold key set instead of a single secret key.
+//! Each writer holds a [`SecretKeyShare`] and any `m` of the `n` writers can write to the register
+//! by each signing a [`RegisterWriteProposal`] and combining their signature shares.
+//!
+//! The combined signatures are regular BLS signatures of the key set's public key (and of its
+//! derived keys), so the entries are validated by nodes exactly like single owner registers and
+//! no writer ever needs to know the full secret key.
+
+use super::{
+    PublicKey, REGISTER_HEAD_DERIVATION_INDEX, RegisterAddress, RegisterError, RegisterValue,
+    register_head_pointer_address, register_head_pointer_pk,
+};
+use crate::AttoTokens;
+use crate::client::Client;
+use crate::client::GetError;
+use crate::client::data_types::graph::{GraphContent, GraphEntry, GraphEntryAddress};
+use crate::client::data_types::pointer::{PointerError, PointerTarget};
+use crate::client::key_derivation::DerivationIndex;
+use crate::client::payment::PaymentOption;
+use ant_protocol::storage::Pointer;
+use bls::{PublicKeySet, SecretKeySet, SecretKeyShare, SignatureShare};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The writer set of a multi-writer register: `n` writers, `m` of which need to sign every write.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegisterWriters {
+    public_key_set: PublicKeySet,
+    writers: usize,
+}
+
+impl RegisterWriters {
+    /// Generate the key shares of a new writer set of `writers` writers requiring
+    /// `required_signatures` of them to sign every write.
+    ///
+    /// The returned shares are indexed by writer, the share at index `i` must be handed out to
+    /// writer `i` and the caller must not keep them around.
+    pub fn generate(
+        writers: usize,
+        required_signatures: usize,
+    ) -> Result<(Self, Vec<SecretKeyShare>), RegisterError> {
+        if required_signatures == 0 || required_signatures > writers {
+            return Err(RegisterError::InvalidWriterSet(format!(
+                "cannot require {required_signatures} signatures out of {writers} writers"
+            )));
+        }
+
+        let secret_key_set = SecretKeySet::random(required_signatures - 1, &mut rand::thread_rng());
+        let shares = (0..writers)
+            .map(|i| secret_key_set.secret_key_share(i))
+            .collect();
+        let writer_set = Self {
+            public_key_set: secret_key_set.public_keys(),
+            writers,
+        };
+        Ok((writer_set, shares))
+    }
+
+    /// Create a writer set from a [`PublicKeySet`] generated elsewhere (e.g. by a DKG)
+    pub fn from_public_key_set(public_key_set: PublicKeySet, writers: usize) -> Self {
+        Self {
+            public_key_set,
+            writers,
+        }
+    }
+
+    /// The address of the register owned by this writer set
+    pub fn address(&self) -> RegisterAddress {
+        RegisterAddress::new(self.public_key_set.public_key())
+    }
+
+    /// The number of writers in the set
+    pub fn writers(&self) -> usize {
+        self.writers
+    }
+
+    /// The number of signatures required for a write
+    pub fn required_signatures(&self) -> usize {
+        self.public_key_set.threshold() + 1
+    }
+
+    /// The public key set of the writers
+    pub fn public_key_set(&self) -> &PublicKeySet {
+        &self.public_key_set
+    }
+}
+
+/// A write to a multi-writer register waiting for the writers' signatures.
+///
+/// Proposals are created with [`RegisterWriteProposal::create`] or
+/// [`Client::register_propose_update`], handed to the writers who each return a
+/// [`RegisterWriteSignature`], and applied with [`Client::register_apply_write`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegisterWriteProposal {
+    address: RegisterAddress,
+    /// Derivation of the new entry key from the register key, `None` for the root entry
+    entry_derivation: Option<DerivationIndex>,
+    parents: Vec<PublicKey>,
+    value: RegisterValue,
+    descendants: Vec<(PublicKey, GraphContent)>,
+    pointer_counter: u64,
+}
+
+/// One writer's signature shares of a [`RegisterWriteProposal`]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegisterWriteSignature {
+    writer: usize,
+    entry: SignatureShare,
+    pointer: SignatureShare,
+}
+
+impl RegisterWriteProposal {
+    /// Propose the creation of a multi-writer register with an initial value
+    pub fn create(writers: &RegisterWriters, initial_value: RegisterValue) -> Self {
+        let register_pk = writers.public_key_set.public_key();
+        let next_derivation = DerivationIndex::random(&mut rand::thread_rng());
+        let next_pk = register_pk.derive_child(next_derivation.as_bytes());
+        Self {
+            address: writers.address(),
+            entry_derivation: None,
+            parents: vec![],
+            value: initial_value,
+            descendants: vec![(next_pk, next_derivation.into_bytes())],
+            pointer_counter: 0,
+        }
+    }
+
+    /// The register this proposal writes to
+    pub fn address(&self) -> RegisterAddress {
+        self.address
+    }
+
+    /// The value this proposal writes, writers should check it before signing
+    pub fn value(&self) -> RegisterValue {
+        self.value
+    }
+
+    /// Returns true if this proposal creates the register
+    pub fn is_creation(&self) -> bool {
+        self.entry_derivation.is_none()
+    }
+
+    /// Sign the proposal with a writer's key share
+    pub fn sign(&self, writer: usize, key_share: &SecretKeyShare) -> RegisterWriteSignature {
+        let entry_share = match &self.entry_derivation {
+            Some(derivation) => key_share.derive_child(derivation.as_bytes()),
+            None => key_share.clone(),
+        };
+        let pointer_share = key_share.derive_child(&REGISTER_HEAD_DERIVATION_INDEX);
+
+        RegisterWriteSignature {
+            writer,
+            entry: entry_share.sign(self.entry_bytes_to_sign()),
+            pointer: pointer_share.sign(self.pointer_bytes_to_sign()),
+        }
+    }
+
+    fn entry_owner(&self) -> PublicKey {
+        let register_pk = self.address.owner();
+        match &self.entry_derivation {
+            Some(derivation) => register_pk.derive_child(derivation.as_bytes()),
+            None => register_pk,
+        }
+    }
+
+    fn pointer_owner(&self) -> PublicKey {
+        register_head_pointer_pk(&self.address)
+    }
+
+    fn pointer_target(&self) -> PointerTarget {
+        PointerTarget::GraphEntryAddress(GraphEntryAddress::new(self.entry_owner()))
+    }
+
+    fn entry_bytes_to_sign(&self) -> Vec<u8> {
+        GraphEntry::bytes_to_sign(
+            &self.entry_owner(),
+            &self.parents,
+            &self.value,
+            &self.descendants,
+        )
+    }
+
+    fn pointer_bytes_to_sign(&self) -> Vec<u8> {
+        Pointer::bytes_to_sign(
+            &self.pointer_owner(),
+            self.pointer_counter,
+            &self.pointer_target(),
+        )
+    }
+
+    /// Combine the writers' signatures into the signed graph entry and head pointer
+    fn into_signed(
+        self,
+        writers: &RegisterWriters,
+        signatures: &[RegisterWriteSignature],
+    ) -> Result<(GraphEntry, Pointer), RegisterError> {
+        if self.address != writers.address() {
+            return Err(RegisterError::InvalidWriterSet(format!(
+                "proposal is for register {} but writer set owns {}",
+                self.address,
+                writers.address()
+            )));
+        }
+
+        // keep one signature per writer
+        let signatures: BTreeMap<usize, &RegisterWriteSignature> = signatures
+            .iter()
+            .filter(|s| s.writer < writers.writers)
+            .map(|s| (s.writer, s))
+            .collect();
+        if signatures.len() < writers.required_signatures() {
+            return Err(RegisterError::NotEnoughSignatures {
+                got: signatures.len(),
+                required: writers.required_signatures(),
+            });
+        }
+
+        let entry_key_set = match &self.entry_derivation {
+            Some(derivation) => writers.public_key_set.derive_child(derivation.as_bytes()),
+            None => writers.public_key_set.clone(),
+        };
+        let pointer_key_set = writers
+            .public_key_set
+            .derive_child(&REGISTER_HEAD_DERIVATION_INDEX);
+
+        let entry_signature = entry_key_set
+            .combine_signatures(signatures.iter().map(|(i, s)| (*i, &s.entry)))
+            .map_err(|e| RegisterError::InvalidSignatures(e.to_string()))?;
+        let pointer_signature = pointer_key_set
+            .combine_signatures(signatures.iter().map(|(i, s)| (*i, &s.pointer)))
+            .map_err(|e| RegisterError::InvalidSignatures(e.to_string()))?;
+
+        let pointer = Pointer::new_with_signature(
+            self.pointer_owner(),
+            self.pointer_counter,
+            self.pointer_target(),
+            pointer_signature,
+        );
+        let entry = GraphEntry::new_with_signature(
+            self.entry_owner(),
+            self.parents,
+            self.value,
+            self.descendants,
+            entry_signature,
+        );
+
+        // a single bad share yields an invalid combined signature
+        if !entry.verify_signature() || !pointer.verify_signature() {
+            return Err(RegisterError::InvalidSignatures(
+                "combined signature does not match the writer set".to_string(),
+            ));
+        }
+        Ok((entry, pointer))
+    }
+}
+
+impl Client {
+    /// Propose an update of a multi-writer register to a new value.
+    ///
+    /// The register needs to be created first with a [`RegisterWriteProposal::create`] proposal.
+    pub async fn register_propose_update(
+        &self,
+        writers: &RegisterWriters,
+        new_value: RegisterValue,
+    ) -> Result<RegisterWriteProposal, RegisterError> {
+        let addr = writers.address();
+        let pointer_addr = register_head_pointer_address(&addr);
+        debug!("Getting pointer of multi-writer register head at {pointer_addr:?}");
+        let pointer = match self.pointer_get(&pointer_addr).await {
+            Ok(pointer) => pointer,
+            Err(PointerError::GetError(GetError::RecordNotFound)) => {
+                return Err(RegisterError::CannotUpdateNewRegister);
+            }
+            Err(err) => return Err(err.into()),
+        };
+        let graph_entry_addr = match pointer.target() {
+            PointerTarget::GraphEntryAddress(addr) => addr,
+            other => return Err(RegisterError::InvalidHeadPointer(other.clone())),
+        };
+
+        let (parent_entry, new_derivation) = self
+            .register_get_graph_entry_and_next_derivation_index(graph_entry_addr)
+            .await?;
+
+        let next_derivation = DerivationIndex::random(&mut rand::thread_rng());
+        let next_pk = addr.owner().derive_child(next_derivation.as_bytes());
+        Ok(RegisterWriteProposal {
+            address: addr,
+            entry_derivation: Some(new_derivation),
+            parents: vec![parent_entry.owner],
+            value: new_value,
+            descendants: vec![(next_pk, next_derivation.into_bytes())],
+            pointer_counter: pointer.counter() + 1,
+        })
+    }
+
+    /// Apply a write to a multi-writer register once enough writers signed it.
+    pub async fn register_apply_write(
+        &self,
+        writers: &RegisterWriters,
+        proposal: RegisterWriteProposal,
+        signatures: &[RegisterWriteSignature],
+        payment_option: PaymentOption,
+    ) -> Result<AttoTokens, RegisterError> {
+        let is_creation = proposal.is_creation();
+        let (entry, pointer) = proposal.into_signed(writers, signatures)?;
+
+        let (graph_cost, _addr) = self.graph_entry_put(entry, payment_option.clone()).await?;
+
+        if is_creation {
+            let (pointer_cost, _pointer_addr) = self.pointer_put(pointer, payment_option).await?;
+            return graph_cost
+                .checked_add(pointer_cost)
+                .ok_or(RegisterError::InvalidCost);
+        }
+
+        self.pointer_put_update(pointer).await?;
+        Ok(graph_cost)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_multi_writer_signatures() {
+        let (writers, shares) = RegisterWriters::generate(3, 2).unwrap();
+        assert_eq!(writers.required_signatures(), 2);
+        let proposal = RegisterWriteProposal::create(&writers, [1; 32]);
+
+        let sig_0 = proposal.sign(0, &shares[0]);
+        let sig_2 = proposal.sign(2, &shares[2]);
+
+        let (entry, pointer) = proposal
+            .clone()
+            .into_signed(&writers, &[sig_0.clone(), sig_2])
+            .unwrap();
+        assert_eq!(entry.owner, writers.address().owner());
+        assert!(entry.verify_signature());
+        assert!(pointer.verify_signature());
+
+        // the same writer signing twice doesn't count twice
+        let res = proposal
+            .clone()
+            .into_signed(&writers, &[sig_0.clone(), sig_0.clone()]);
+        assert!(matches!(
+            res,
+            Err(RegisterError::NotEnoughSignatures {
+                got: 1,
+                required: 2
+            })
+        ));
+
+        // a share signed by the wrong writer index is rejected
+        let wrong = proposal.sign(1, &shares[2]);
+        let res = proposal.into_signed(&writers, &[sig_0, wrong]);
+        assert!(matches!(res, Err(RegisterError::InvalidSignatures(_))));
+    }
+
+    #[test]
+    fn test_invalid_writer_set() {
+        assert!(RegisterWriters::generate(2, 3).is_err());
+        assert!(RegisterWriters::generate(2, 0).is_err());
+    }
+}
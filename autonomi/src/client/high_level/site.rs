@@ -0,0 +1,330 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Static websites published on the Network.
+//!
+//! A site is a directory uploaded as a [`PublicArchive`], along with a [`SiteManifest`] mapping the
+//! routes of the site to the files of the archive. The address of the manifest is kept in a
+//! register derived from the owner's key and the site name: the register address is the stable
+//! [`SiteAddress`] of the site, and the register history retains all its published versions.
+
+use crate::client::high_level::data::DataAddress;
+use crate::client::high_level::files::archive_public::ArchiveAddress;
+use crate::client::high_level::files::{PublicArchive, UploadError};
+use crate::client::high_level::register::{RegisterAddress, RegisterError, RegisterValue};
+use crate::client::payment::{BulkPaymentOption, PaymentOption};
+use crate::client::quote::add_costs;
+use crate::client::{GetError, PutError};
+use crate::{AttoTokens, Client, SecretKey};
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use xor_name::XorName;
+
+/// The stable address of a site: the address of the register holding its manifest.
+pub type SiteAddress = RegisterAddress;
+
+/// Name of the file served at the root of a directory.
+pub const INDEX_FILE: &str = "index.html";
+
+/// Name of the file served for routes matching no file.
+pub const NOT_FOUND_FILE: &str = "404.html";
+
+#[derive(Debug, thiserror::Error)]
+pub enum SiteError {
+    #[error("Failed to upload the site content: {0}")]
+    Upload(#[from] UploadError),
+    #[error("Failed to upload the site manifest: {0}")]
+    Put(#[from] PutError),
+    #[error("Failed to fetch the site manifest: {0}")]
+    Get(#[from] GetError),
+    #[error("Site register error: {0}")]
+    Register(#[from] RegisterError),
+    #[error("Failed to serialize the site manifest: {0}")]
+    Serialization(#[from] rmp_serde::encode::Error),
+    #[error("Failed to deserialize the site manifest: {0}")]
+    Deserialization(#[from] rmp_serde::decode::Error),
+    #[error("A wallet is required to pay for the site archive, manifest and register")]
+    WalletRequired,
+}
+
+/// Maps the routes of a site to the files of its archive.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SiteManifest {
+    /// The archive holding the files of the site
+    pub archive: ArchiveAddress,
+    /// Routes, e.g. `/blog/`, to the path of the file served for them in the archive
+    pub routes: BTreeMap<String, PathBuf>,
+    /// Path of the file served for routes matching no file, if the site has a `404.html`
+    pub not_found: Option<PathBuf>,
+}
+
+/// This type wraps the manifest in a version marker, like archives.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SiteManifestVersioned {
+    V0(SiteManifest),
+}
+
+impl SiteManifest {
+    /// Build the manifest of a site from its archive.
+    ///
+    /// Each file is routed at its path within the uploaded directory, and each `index.html` is
+    /// also routed at its directory, with and without a trailing slash.
+    pub fn from_archive(archive_addr: ArchiveAddress, archive: &PublicArchive) -> Self {
+        let mut routes = BTreeMap::new();
+        let mut not_found = None;
+
+        for (path, _addr, _meta) in archive.iter() {
+            // archive paths start with the name of the uploaded directory
+            let components: Vec<String> = path
+                .components()
+                .skip(1)
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+                .collect();
+            let route = format!("/{}", components.join("/"));
+
+            if components.last().map(String::as_str) == Some(INDEX_FILE) {
+                let dir = &components[..components.len() - 1];
+                let dir_route = format!("/{}", dir.join("/"));
+                if !dir.is_empty() {
+                    routes.insert(format!("{dir_route}/"), path.clone());
+                }
+                routes.insert(dir_route, path.clone());
+            }
+            if components.len() == 1 && components[0] == NOT_FOUND_FILE {
+                not_found = Some(path.clone());
+            }
+            routes.insert(route, path.clone());
+        }
+
+        Self {
+            archive: archive_addr,
+            routes,
+            not_found,
+        }
+    }
+
+    /// The path of the file to serve for a route, along with whether it is the `404.html` page.
+    /// Query strings and fragments are ignored.
+    pub fn resolve(&self, route: &str) -> Option<(&Path, bool)> {
+        let route = route.split(['?', '#']).next().unwrap_or_default();
+        let route = if route.starts_with('/') {
+            route.to_string()
+        } else {
+            format!("/{route}")
+        };
+
+        if let Some(path) = self.routes.get(&route) {
+            return Some((path.as_path(), false));
+        }
+        self.not_found.as_deref().map(|path| (path, true))
+    }
+
+    /// Deserialize from bytes.
+    pub fn from_bytes(data: Bytes) -> Result<Self, rmp_serde::decode::Error> {
+        let SiteManifestVersioned::V0(manifest) = rmp_serde::from_slice(&data[..])?;
+        Ok(manifest)
+    }
+
+    /// Serialize to bytes.
+    pub fn to_bytes(&self) -> Result<Bytes, rmp_serde::encode::Error> {
+        let versioned = SiteManifestVersioned::V0(self.clone());
+        Ok(Bytes::from(rmp_serde::to_vec_named(&versioned)?))
+    }
+}
+
+impl Client {
+    /// Derive the key of the register of a site from the owner's key and the site name.
+    pub fn site_key_from_name(owner: &SecretKey, name: &str) -> SecretKey {
+        Self::register_key_from_name(owner, &format!("site:{name}"))
+    }
+
+    /// Publish a directory as a new site named `name`, owned by `owner`.
+    ///
+    /// Uploads the directory and its manifest, then creates the site register pointing to the
+    /// manifest. Returns the total cost and the stable [`SiteAddress`] of the site.
+    /// Use [`Client::site_update`] to publish the following versions.
+    ///
+    /// The directory content is paid for with `payment_option`, the archive, manifest and
+    /// register with its wallet, so receipt-only options are refused with
+    /// [`SiteError::WalletRequired`].
+    pub async fn site_publish(
+        &self,
+        owner: &SecretKey,
+        name: &str,
+        dir_path: PathBuf,
+        payment_option: BulkPaymentOption,
+    ) -> Result<(AttoTokens, SiteAddress), SiteError> {
+        let (upload_cost, manifest_addr, payment_option) =
+            self.site_upload_version(dir_path, payment_option).await?;
+
+        let site_key = Self::site_key_from_name(owner, name);
+        let (register_cost, site_addr) = self
+            .register_create(
+                &site_key,
+                manifest_register_value(&manifest_addr),
+                payment_option,
+            )
+            .await?;
+        info!("Published site {name} at {site_addr:?}, manifest at {manifest_addr:?}");
+
+        let total_cost = add_costs(upload_cost, register_cost).map_err(PutError::from)?;
+        Ok((total_cost, site_addr))
+    }
+
+    /// Publish a new version of the site named `name`, keeping its [`SiteAddress`].
+    ///
+    /// Previous versions remain available through [`Client::site_history`].
+    /// Payments are made as for [`Client::site_publish`].
+    pub async fn site_update(
+        &self,
+        owner: &SecretKey,
+        name: &str,
+        dir_path: PathBuf,
+        payment_option: BulkPaymentOption,
+    ) -> Result<AttoTokens, SiteError> {
+        let (upload_cost, manifest_addr, payment_option) =
+            self.site_upload_version(dir_path, payment_option).await?;
+
+        let site_key = Self::site_key_from_name(owner, name);
+        let register_cost = self
+            .register_update(
+                &site_key,
+                manifest_register_value(&manifest_addr),
+                payment_option,
+            )
+            .await?;
+        info!("Updated site {name}, new manifest at {manifest_addr:?}");
+
+        Ok(add_costs(upload_cost, register_cost).map_err(PutError::from)?)
+    }
+
+    /// Get the manifest of the current version of a site.
+    pub async fn site_get(&self, addr: &SiteAddress) -> Result<SiteManifest, SiteError> {
+        let value = self.register_get(addr).await?;
+        self.site_manifest_get(&manifest_address(&value)).await
+    }
+
+    /// Get the addresses of the manifests of all the versions of a site, from the first to the
+    /// latest. Use [`Client::site_manifest_get`] to fetch them.
+    pub async fn site_history(&self, addr: &SiteAddress) -> Result<Vec<DataAddress>, SiteError> {
+        let values = self.register_history(addr).collect().await?;
+        Ok(values.iter().map(manifest_address).collect())
+    }
+
+    /// Get a site manifest from its address.
+    pub async fn site_manifest_get(
+        &self,
+        manifest_addr: &DataAddress,
+    ) -> Result<SiteManifest, SiteError> {
+        let data = self.data_get_public(manifest_addr).await?;
+        Ok(SiteManifest::from_bytes(data)?)
+    }
+
+    /// Upload the directory of a site version along with its manifest.
+    /// Returns the total cost, the address of the manifest and the payment option for the single
+    /// records still to be paid for.
+    async fn site_upload_version(
+        &self,
+        dir_path: PathBuf,
+        payment_option: BulkPaymentOption,
+    ) -> Result<(AttoTokens, DataAddress, PaymentOption), SiteError> {
+        // Check for a wallet before paying for the content
+        let single_payment_option = payment_option
+            .to_payment_option()
+            .ok_or(SiteError::WalletRequired)?;
+
+        let (content_cost, archive) = self
+            .dir_content_upload_public(dir_path, payment_option)
+            .await?;
+        let (archive_cost, archive_addr) = self
+            .archive_put_public(&archive, single_payment_option.clone())
+            .await?;
+
+        let manifest = SiteManifest::from_archive(archive_addr, &archive);
+        debug!(
+            "Uploading site manifest with {} routes for archive {archive_addr:?}",
+            manifest.routes.len()
+        );
+        let (manifest_cost, manifest_addr) = self
+            .data_put_public(manifest.to_bytes()?, single_payment_option.clone())
+            .await?;
+
+        let total_cost = add_costs(content_cost, archive_cost)
+            .and_then(|cost| add_costs(cost, manifest_cost))
+            .map_err(PutError::from)?;
+        Ok((total_cost, manifest_addr, single_payment_option))
+    }
+}
+
+fn manifest_register_value(manifest_addr: &DataAddress) -> RegisterValue {
+    manifest_addr.xorname().0
+}
+
+fn manifest_address(value: &RegisterValue) -> DataAddress {
+    DataAddress::new(XorName(*value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::files::Metadata;
+
+    #[test]
+    fn test_manifest_routes() {
+        let mut archive = PublicArchive::new();
+        for path in [
+            "site/index.html",
+            "site/404.html",
+            "site/blog/index.html",
+            "site/blog/post.html",
+        ] {
+            archive.add_file(
+                PathBuf::from(path),
+                DataAddress::new(XorName::random(&mut rand::thread_rng())),
+                Metadata::new_with_size(1),
+            );
+        }
+        let archive_addr = DataAddress::new(XorName::random(&mut rand::thread_rng()));
+        let manifest = SiteManifest::from_archive(archive_addr, &archive);
+
+        let resolve = |route| manifest.resolve(route).map(|(p, nf)| (p.to_path_buf(), nf));
+        assert_eq!(
+            resolve("/"),
+            Some((PathBuf::from("site/index.html"), false))
+        );
+        assert_eq!(
+            resolve("index.html"),
+            Some((PathBuf::from("site/index.html"), false))
+        );
+        assert_eq!(
+            resolve("/blog"),
+            Some((PathBuf::from("site/blog/index.html"), false))
+        );
+        assert_eq!(
+            resolve("/blog/"),
+            Some((PathBuf::from("site/blog/index.html"), false))
+        );
+        assert_eq!(
+            resolve("/blog/post.html?page=2"),
+            Some((PathBuf::from("site/blog/post.html"), false))
+        );
+        assert_eq!(
+            resolve("/missing"),
+            Some((PathBuf::from("site/404.html"), true))
+        );
+
+        let bytes = manifest.to_bytes().expect("manifest shall serialize");
+        assert_eq!(
+            SiteManifest::from_bytes(bytes).expect("manifest shall deserialize"),
+            manifest
+        );
+    }
+}
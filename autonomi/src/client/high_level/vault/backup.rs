@@ -0,0 +1,198 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use super::{VaultContentType, VaultError, VaultSecretKey};
+use crate::client::Client;
+use crate::client::payment::PaymentOption;
+use aes_gcm_siv::{
+    Aes256GcmSiv, Key as AesKey, Nonce,
+    aead::{Aead, KeyInit},
+};
+use ant_evm::AttoTokens;
+use ant_protocol::Bytes;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::path::Path;
+
+/// Marks the start of a vault backup file
+const VAULT_BACKUP_MAGIC: &[u8; 8] = b"ANTVAULT";
+
+/// Version of the vault backup file format
+const VAULT_BACKUP_VERSION: u8 = 1;
+
+/// PBKDF2 rounds used to derive the backup encryption key from the passphrase
+const VAULT_BACKUP_KDF_ROUNDS: u32 = 210_000;
+
+const SALT_SIZE: usize = 16;
+const NONCE_SIZE: usize = 12;
+const HEADER_SIZE: usize = VAULT_BACKUP_MAGIC.len() + 1 + SALT_SIZE + NONCE_SIZE;
+
+#[derive(Debug, thiserror::Error)]
+pub enum VaultBackupError {
+    #[error("Vault error: {0}")]
+    Vault(#[from] VaultError),
+    #[error("Failed to read or write backup file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Serialization error: {0}")]
+    Serialization(String),
+    #[error("Not a vault backup file")]
+    NotABackupFile,
+    #[error("Unsupported vault backup version: {0}")]
+    UnsupportedVersion(u8),
+    #[error("Failed to decrypt vault backup, wrong passphrase or corrupted file")]
+    Decryption,
+    #[error("Failed to encrypt vault backup")]
+    Encryption,
+}
+
+/// Content of a vault backup file, once decrypted
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+struct VaultBackup {
+    content_type: VaultContentType,
+    content: Bytes,
+}
+
+impl Client {
+    /// Export a snapshot of the vault content to a single backup file encrypted with a passphrase.
+    ///
+    /// Only the vault content itself is backed up. For a [`super::UserData`] vault, that is the
+    /// references to the user's archives, registers and keys, not the records they point to.
+    pub async fn vault_export(
+        &self,
+        secret_key: &VaultSecretKey,
+        path: &Path,
+        passphrase: &str,
+    ) -> Result<(), VaultBackupError> {
+        let (content, content_type) = self.vault_get(secret_key).await?;
+        info!(
+            "Exporting {} bytes of vault content to {path:?}",
+            content.len()
+        );
+
+        let backup = VaultBackup {
+            content_type,
+            content,
+        };
+        let encrypted = encrypt_backup(&backup, passphrase)?;
+        tokio::fs::write(path, encrypted).await?;
+        Ok(())
+    }
+
+    /// Write the vault content snapshot of a backup file written by [`Client::vault_export`] to
+    /// the vault of `secret_key`, replacing its current content.
+    ///
+    /// Returns the total cost of the operation.
+    pub async fn vault_import(
+        &self,
+        secret_key: &VaultSecretKey,
+        path: &Path,
+        passphrase: &str,
+        payment_option: PaymentOption,
+    ) -> Result<AttoTokens, VaultBackupError> {
+        let encrypted = tokio::fs::read(path).await?;
+        let backup = decrypt_backup(&encrypted, passphrase)?;
+        info!(
+            "Importing {} bytes of vault content from {path:?}",
+            backup.content.len()
+        );
+
+        let cost = self
+            .vault_put(
+                backup.content,
+                payment_option,
+                secret_key,
+                backup.content_type,
+            )
+            .await?;
+        Ok(cost)
+    }
+}
+
+/// Derive the backup cipher from the passphrase and salt
+fn backup_cipher(passphrase: &str, salt: &[u8]) -> Aes256GcmSiv {
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(
+        passphrase.as_bytes(),
+        salt,
+        VAULT_BACKUP_KDF_ROUNDS,
+        &mut key,
+    );
+    Aes256GcmSiv::new(AesKey::<Aes256GcmSiv>::from_slice(&key))
+}
+
+/// Backup file layout: magic | version | salt | nonce | encrypted content
+fn encrypt_backup(backup: &VaultBackup, passphrase: &str) -> Result<Vec<u8>, VaultBackupError> {
+    let plaintext =
+        rmp_serde::to_vec(backup).map_err(|e| VaultBackupError::Serialization(e.to_string()))?;
+
+    let mut rng = rand::thread_rng();
+    let mut salt = [0u8; SALT_SIZE];
+    rng.fill_bytes(&mut salt);
+    let mut nonce = [0u8; NONCE_SIZE];
+    rng.fill_bytes(&mut nonce);
+
+    let ciphertext = backup_cipher(passphrase, &salt)
+        .encrypt(Nonce::from_slice(&nonce), plaintext.as_slice())
+        .map_err(|_| VaultBackupError::Encryption)?;
+
+    let mut file = Vec::with_capacity(HEADER_SIZE + ciphertext.len());
+    file.extend_from_slice(VAULT_BACKUP_MAGIC);
+    file.push(VAULT_BACKUP_VERSION);
+    file.extend_from_slice(&salt);
+    file.extend_from_slice(&nonce);
+    file.extend_from_slice(&ciphertext);
+    Ok(file)
+}
+
+fn decrypt_backup(file: &[u8], passphrase: &str) -> Result<VaultBackup, VaultBackupError> {
+    if file.len() < HEADER_SIZE || !file.starts_with(VAULT_BACKUP_MAGIC) {
+        return Err(VaultBackupError::NotABackupFile);
+    }
+
+    let (header, ciphertext) = file.split_at(HEADER_SIZE);
+    let version = header[VAULT_BACKUP_MAGIC.len()];
+    if version != VAULT_BACKUP_VERSION {
+        return Err(VaultBackupError::UnsupportedVersion(version));
+    }
+    let salt_start = VAULT_BACKUP_MAGIC.len() + 1;
+    let salt = &header[salt_start..salt_start + SALT_SIZE];
+    let nonce = &header[salt_start + SALT_SIZE..];
+
+    let plaintext = backup_cipher(passphrase, salt)
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| VaultBackupError::Decryption)?;
+
+    rmp_serde::from_slice(&plaintext).map_err(|e| VaultBackupError::Serialization(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backup_encryption_roundtrip() {
+        let backup = VaultBackup {
+            content_type: 42,
+            content: Bytes::from_static(b"vault content"),
+        };
+
+        let file = encrypt_backup(&backup, "correct horse").unwrap();
+        assert!(file.starts_with(VAULT_BACKUP_MAGIC));
+        assert_eq!(decrypt_backup(&file, "correct horse").unwrap(), backup);
+
+        assert!(matches!(
+            decrypt_backup(&file, "wrong passphrase"),
+            Err(VaultBackupError::Decryption)
+        ));
+        assert!(matches!(
+            decrypt_backup(b"not a backup", "correct horse"),
+            Err(VaultBackupError::NotABackupFile)
+        ));
+    }
+}
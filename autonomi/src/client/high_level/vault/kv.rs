@@ -0,0 +1,252 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! A key-value store kept in a vault, e.g. for app settings.
+//!
+//! The store has its own vault, derived from the vault key so that it does not overwrite the
+//! [`super::UserData`] or app data kept in the main vault. That vault holds the index of the store,
+//! encrypted like any vault content: small values are kept inline in the index, larger ones are
+//! uploaded as private data, self-encrypted into chunks, and only their data maps are kept in
+//! the index.
+//!
+//! Each write re-uploads the index, so concurrent writers to the same store overwrite each
+//! other's changes.
+
+use super::{VaultContentType, VaultError, VaultSecretKey, vault_content_type_from_app_name};
+use crate::chunk::DataMapChunk;
+use crate::client::key_derivation::{DerivationIndex, MainSecretKey};
+use crate::client::payment::PaymentOption;
+use crate::client::quote::add_costs;
+use crate::client::{Client, GetError, PutError};
+use crate::graph::GraphError;
+use ant_evm::AttoTokens;
+use ant_protocol::Bytes;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::LazyLock;
+use xor_name::XorName;
+
+/// Vault content type of the key-value store index
+pub static VAULT_KV_CONTENT_IDENTIFIER: LazyLock<VaultContentType> =
+    LazyLock::new(|| vault_content_type_from_app_name("VaultKv"));
+
+/// Values up to this size are kept inline in the index, larger ones are uploaded as private data.
+pub const MAX_INLINE_VALUE_SIZE: usize = 1024;
+
+/// Name the key of the key-value store vault is derived from.
+const VAULT_KV_DERIVATION_NAME: &str = "vault_kv";
+
+#[derive(Debug, thiserror::Error)]
+pub enum VaultKvError {
+    #[error("Vault error: {0}")]
+    Vault(#[from] VaultError),
+    #[error("Unsupported vault content type: {0}")]
+    UnsupportedVaultContentType(VaultContentType),
+    #[error("Serialization error: {0}")]
+    Serialization(String),
+    #[error("Failed to upload value: {0}")]
+    PutError(#[from] PutError),
+    #[error("Failed to fetch value: {0}")]
+    GetError(#[from] GetError),
+}
+
+/// A value of the store.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+enum VaultKvValue {
+    /// Small values, kept in the index
+    Inline(Bytes),
+    /// Larger values, uploaded as private data
+    Private(DataMapChunk),
+}
+
+/// The index of the store, kept in its vault.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+struct VaultKvIndex {
+    entries: BTreeMap<String, VaultKvValue>,
+}
+
+impl VaultKvIndex {
+    fn keys_with_prefix(&self, prefix: &str) -> Vec<String> {
+        self.entries
+            .range(prefix.to_string()..)
+            .take_while(|(key, _)| key.starts_with(prefix))
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+
+    fn to_bytes(&self) -> Result<Bytes, rmp_serde::encode::Error> {
+        Ok(Bytes::from(rmp_serde::to_vec_named(self)?))
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, rmp_serde::decode::Error> {
+        rmp_serde::from_slice(bytes)
+    }
+}
+
+/// Derive the key of the key-value store vault from the vault key.
+fn vault_kv_key(secret_key: &VaultSecretKey) -> VaultSecretKey {
+    let derivation_index =
+        DerivationIndex::from_bytes(XorName::from_content(VAULT_KV_DERIVATION_NAME.as_bytes()).0);
+    MainSecretKey::new(secret_key.clone())
+        .derive_key(&derivation_index)
+        .into()
+}
+
+impl Client {
+    /// Set the value of a key in the key-value store of the vault.
+    ///
+    /// Returns the total cost of uploading the value, if large, and the updated index.
+    pub async fn vault_kv_set(
+        &self,
+        secret_key: &VaultSecretKey,
+        key: &str,
+        value: Bytes,
+        payment_option: PaymentOption,
+    ) -> Result<AttoTokens, VaultKvError> {
+        let mut index = self.vault_kv_index_get(secret_key).await?;
+
+        let (value_cost, value) = if value.len() <= MAX_INLINE_VALUE_SIZE {
+            (AttoTokens::zero(), VaultKvValue::Inline(value))
+        } else {
+            debug!("Uploading {} bytes value of vault key {key}", value.len());
+            let (cost, data_map) = self.data_put(value, payment_option.clone()).await?;
+            (cost, VaultKvValue::Private(data_map))
+        };
+        index.entries.insert(key.to_string(), value);
+
+        let index_cost = self
+            .vault_kv_index_put(secret_key, &index, payment_option)
+            .await?;
+        Ok(add_costs(value_cost, index_cost).map_err(PutError::from)?)
+    }
+
+    /// Get the value of a key in the key-value store of the vault, if set.
+    pub async fn vault_kv_get(
+        &self,
+        secret_key: &VaultSecretKey,
+        key: &str,
+    ) -> Result<Option<Bytes>, VaultKvError> {
+        let index = self.vault_kv_index_get(secret_key).await?;
+        match index.entries.get(key) {
+            Some(VaultKvValue::Inline(value)) => Ok(Some(value.clone())),
+            Some(VaultKvValue::Private(data_map)) => Ok(Some(self.data_get(data_map).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// List the keys of the key-value store of the vault starting with `prefix`, in order.
+    pub async fn vault_kv_list(
+        &self,
+        secret_key: &VaultSecretKey,
+        prefix: &str,
+    ) -> Result<Vec<String>, VaultKvError> {
+        let index = self.vault_kv_index_get(secret_key).await?;
+        Ok(index.keys_with_prefix(prefix))
+    }
+
+    /// Remove a key from the key-value store of the vault.
+    ///
+    /// Returns the cost of updating the index, zero if the key was not set.
+    pub async fn vault_kv_remove(
+        &self,
+        secret_key: &VaultSecretKey,
+        key: &str,
+        payment_option: PaymentOption,
+    ) -> Result<AttoTokens, VaultKvError> {
+        let mut index = self.vault_kv_index_get(secret_key).await?;
+        if index.entries.remove(key).is_none() {
+            return Ok(AttoTokens::zero());
+        }
+        self.vault_kv_index_put(secret_key, &index, payment_option)
+            .await
+    }
+
+    /// Fetch the index of the store, empty if the store was never written to.
+    async fn vault_kv_index_get(
+        &self,
+        secret_key: &VaultSecretKey,
+    ) -> Result<VaultKvIndex, VaultKvError> {
+        let (bytes, content_type) = match self.vault_get(&vault_kv_key(secret_key)).await {
+            Ok(content) => content,
+            Err(VaultError::GraphEntry(GraphError::GetError(GetError::RecordNotFound))) => {
+                debug!("No vault key-value store found, starting from an empty one");
+                return Ok(VaultKvIndex::default());
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        if content_type != *VAULT_KV_CONTENT_IDENTIFIER {
+            return Err(VaultKvError::UnsupportedVaultContentType(content_type));
+        }
+        VaultKvIndex::from_bytes(&bytes).map_err(|e| {
+            VaultKvError::Serialization(format!("Failed to deserialize vault key-value index: {e}"))
+        })
+    }
+
+    async fn vault_kv_index_put(
+        &self,
+        secret_key: &VaultSecretKey,
+        index: &VaultKvIndex,
+        payment_option: PaymentOption,
+    ) -> Result<AttoTokens, VaultKvError> {
+        let bytes = index.to_bytes().map_err(|e| {
+            VaultKvError::Serialization(format!("Failed to serialize vault key-value index: {e}"))
+        })?;
+        let cost = self
+            .vault_put(
+                bytes,
+                payment_option,
+                &vault_kv_key(secret_key),
+                *VAULT_KV_CONTENT_IDENTIFIER,
+            )
+            .await?;
+        Ok(cost)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_prefix_listing_and_serialization() {
+        let mut index = VaultKvIndex::default();
+        for key in [
+            "settings/theme",
+            "settings/lang",
+            "profile/name",
+            "settings2",
+        ] {
+            index
+                .entries
+                .insert(key.to_string(), VaultKvValue::Inline(Bytes::from(key)));
+        }
+
+        assert_eq!(
+            index.keys_with_prefix("settings/"),
+            vec!["settings/lang".to_string(), "settings/theme".to_string()]
+        );
+        assert_eq!(index.keys_with_prefix("").len(), 4);
+        assert!(index.keys_with_prefix("unknown").is_empty());
+
+        let bytes = index.to_bytes().expect("index shall serialize");
+        assert_eq!(
+            VaultKvIndex::from_bytes(&bytes).expect("index shall deserialize"),
+            index
+        );
+    }
+
+    #[test]
+    fn test_kv_key_differs_from_vault_key() {
+        let secret_key = VaultSecretKey::random();
+        assert_ne!(
+            vault_kv_key(&secret_key).public_key(),
+            secret_key.public_key()
+        );
+    }
+}
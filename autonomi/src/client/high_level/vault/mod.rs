@@ -6,10 +6,14 @@
 // KIND, either express or implied. Please review the Licences for the specific language governing
 // permissions and limitations relating to use of the SAFE Network Software.
 
+pub mod backup;
 pub mod key;
+pub mod kv;
 pub mod user_data;
 
+pub use backup::VaultBackupError;
 pub use key::{VaultSecretKey, vault_derive_key};
+pub use kv::VaultKvError;
 pub use user_data::UserData;
 
 use crate::client::config::FILE_UPLOAD_BATCH_SIZE;
@@ -165,14 +165,17 @@ impl Client {
         crate::loud_info!(
             "🚀 Starting upload of {to_pay_len} chunks in {num_batches} Merkle Tree(s)..."
         );
-        let mut streams: Vec<EncryptionStream> = encrypt_directory_files(path, is_public)
-            .await
-            .map_err(|e| MerkleUploadErrorWithReceipt::encryption(receipt.clone(), e.to_string()))?
-            .into_iter()
-            .map(|stream| {
-                stream.map_err(|e| MerkleUploadErrorWithReceipt::encryption(receipt.clone(), e))
-            })
-            .collect::<Result<Vec<EncryptionStream>, MerkleUploadErrorWithReceipt>>()?;
+        let mut streams: Vec<EncryptionStream> =
+            encrypt_directory_files(path, is_public, self.config.compression)
+                .await
+                .map_err(|e| {
+                    MerkleUploadErrorWithReceipt::encryption(receipt.clone(), e.to_string())
+                })?
+                .into_iter()
+                .map(|stream| {
+                    stream.map_err(|e| MerkleUploadErrorWithReceipt::encryption(receipt.clone(), e))
+                })
+                .collect::<Result<Vec<EncryptionStream>, MerkleUploadErrorWithReceipt>>()?;
 
         let mut results: Vec<(PathBuf, DataMapChunk, Metadata)> = Vec::new();
 
@@ -376,11 +379,12 @@ impl Client {
         ),
         String,
     > {
-        let streams: Vec<EncryptionStream> = encrypt_directory_files(path, is_public)
-            .await
-            .map_err(|e| e.to_string())?
-            .into_iter()
-            .collect::<Result<Vec<EncryptionStream>, String>>()?;
+        let streams: Vec<EncryptionStream> =
+            encrypt_directory_files(path, is_public, self.config.compression)
+                .await
+                .map_err(|e| e.to_string())?
+                .into_iter()
+                .collect::<Result<Vec<EncryptionStream>, String>>()?;
 
         let mut all_xor_names = Vec::new();
         let mut file_chunk_counts = HashMap::new();
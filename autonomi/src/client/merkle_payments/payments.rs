@@ -6,6 +6,7 @@
 // KIND, either express or implied. Please review the Licences for the specific language governing
 // permissions and limitations relating to use of the SAFE Network Software.
 
+use crate::networking::audit_log::{AuditLogEntry, AuditOperation};
 use crate::{Client, networking::NetworkError};
 use ant_evm::{
     AttoTokens, EvmWallet,
@@ -21,7 +22,7 @@ use ant_protocol::{
 use evmlib::merkle_batch_payment::PoolCommitment;
 use futures::stream::FuturesUnordered;
 use std::collections::{HashMap, HashSet};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, UNIX_EPOCH};
 use tracing::{debug, info, warn};
 use xor_name::XorName;
 
@@ -107,6 +108,8 @@ pub enum MerklePaymentError {
     TimestampError(#[from] std::time::SystemTimeError),
     #[error("Candidate pool verification failed: {0}")]
     PoolVerification(#[from] MerklePaymentVerificationError),
+    #[error("The client is read-only and cannot pay for uploads")]
+    ReadOnlyClient,
 }
 
 impl Client {
@@ -404,6 +407,9 @@ impl Client {
         data_size: usize,
         wallet: &EvmWallet,
     ) -> Result<MerklePaymentReceipt, MerklePaymentError> {
+        if self.is_read_only() {
+            return Err(MerklePaymentError::ReadOnlyClient);
+        }
         if wallet.network() != self.evm_network() {
             return Err(MerklePaymentError::EvmWalletNetworkMismatch);
         }
@@ -462,7 +468,8 @@ impl Client {
         info!("Built Merkle tree: depth={depth}");
 
         // Get timestamp and reward candidates
-        let merkle_payment_timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        // Use the clock of the network, as nodes refuse timestamps too far from their own clock
+        let merkle_payment_timestamp = self.network_time().duration_since(UNIX_EPOCH)?.as_secs();
         let midpoint_proofs = tree.reward_candidates(merkle_payment_timestamp)?;
         info!("Generated {} midpoint proofs", midpoint_proofs.len());
 
@@ -507,9 +514,17 @@ impl Client {
         debug!("Waiting for wallet lock");
         let lock_guard = wallet.lock().await;
         debug!("Locked wallet");
+        #[cfg(feature = "open-metrics")]
+        self.network.metrics().payment_attempt();
+        let start = Instant::now();
         let (winner_pool_hash, amount, gas_info) = wallet
             .pay_for_merkle_tree(depth, pool_commitments, merkle_payment_timestamp)
-            .await?;
+            .await
+            .inspect_err(|err| {
+                #[cfg(feature = "open-metrics")]
+                self.network.metrics().payment_failure();
+                self.audit_merkle_payment(&addresses, Err(&err.to_string()), start.elapsed());
+            })?;
         let amount = AttoTokens::from_atto(amount);
         drop(lock_guard);
         debug!("Unlocked wallet");
@@ -529,6 +544,7 @@ impl Client {
                     hex::encode(winner_pool_hash)
                 ))
             })?;
+        self.audit_merkle_payment(&addresses, Ok(&winner_pool), start.elapsed());
 
         let mut proofs = HashMap::new();
         for (i, address) in addresses.into_iter().enumerate() {
@@ -555,4 +571,34 @@ impl Client {
         );
         Ok(receipt)
     }
+
+    /// Record the Merkle payment of each address to the audit log, if enabled.
+    fn audit_merkle_payment(
+        &self,
+        addresses: &[XorName],
+        result: Result<&MerklePaymentCandidatePool, &str>,
+        duration: Duration,
+    ) {
+        let Some(audit_log) = self.network.audit_log() else {
+            return;
+        };
+
+        for address in addresses {
+            let mut entry =
+                AuditLogEntry::new(AuditOperation::MerklePayment, address.to_string(), duration);
+            match result {
+                Ok(winner_pool) => {
+                    entry.peers = winner_pool
+                        .candidate_nodes
+                        .iter()
+                        .filter_map(|node| node.peer_id().ok())
+                        .map(|peer_id| peer_id.to_string())
+                        .collect();
+                    entry.payment = Some(vec![hex::encode(winner_pool.hash())]);
+                }
+                Err(err) => entry.error = Some(err.to_string()),
+            }
+            audit_log.write(&entry);
+        }
+    }
 }
@@ -0,0 +1,151 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Metrics of the operations carried out by a client.
+//!
+//! The metrics are kept in a dedicated [`Registry`], returned by [`crate::Client::metrics_registry`].
+//! Services embedding the client can serve it on their own Prometheus endpoint, e.g. by encoding it
+//! with [`prometheus_client::encoding::text::encode`].
+
+use prometheus_client::{metrics::counter::Counter, registry::Registry};
+use std::sync::Arc;
+
+const METRICS_PREFIX: &str = "autonomi_client";
+
+/// The shared recorders of the client operation metrics.
+#[derive(Clone, Debug)]
+pub(crate) struct ClientMetrics {
+    registry: Arc<Registry>,
+    records_put: Counter,
+    records_got: Counter,
+    bytes_uploaded: Counter,
+    bytes_downloaded: Counter,
+    put_retries: Counter,
+    get_retries: Counter,
+    payment_attempts: Counter,
+    payment_failures: Counter,
+}
+
+impl ClientMetrics {
+    pub(crate) fn new() -> Self {
+        let mut registry = Registry::default();
+        let sub_registry = registry.sub_registry_with_prefix(METRICS_PREFIX);
+
+        let records_put = Counter::default();
+        sub_registry.register(
+            "records_put",
+            "Number of records successfully put to the network",
+            records_put.clone(),
+        );
+        let records_got = Counter::default();
+        sub_registry.register(
+            "records_got",
+            "Number of records successfully fetched from the network",
+            records_got.clone(),
+        );
+        let bytes_uploaded = Counter::default();
+        sub_registry.register(
+            "bytes_uploaded",
+            "Number of record bytes put to the network",
+            bytes_uploaded.clone(),
+        );
+        let bytes_downloaded = Counter::default();
+        sub_registry.register(
+            "bytes_downloaded",
+            "Number of record bytes fetched from the network",
+            bytes_downloaded.clone(),
+        );
+        let put_retries = Counter::default();
+        sub_registry.register(
+            "put_retries",
+            "Number of record puts retried after a failure",
+            put_retries.clone(),
+        );
+        let get_retries = Counter::default();
+        sub_registry.register(
+            "get_retries",
+            "Number of record gets retried after a failure or a missing record",
+            get_retries.clone(),
+        );
+        let payment_attempts = Counter::default();
+        sub_registry.register(
+            "payment_attempts",
+            "Number of payment transactions attempted",
+            payment_attempts.clone(),
+        );
+        let payment_failures = Counter::default();
+        sub_registry.register(
+            "payment_failures",
+            "Number of payment transactions that failed",
+            payment_failures.clone(),
+        );
+
+        Self {
+            registry: Arc::new(registry),
+            records_put,
+            records_got,
+            bytes_uploaded,
+            bytes_downloaded,
+            put_retries,
+            get_retries,
+            payment_attempts,
+            payment_failures,
+        }
+    }
+
+    pub(crate) fn registry(&self) -> Arc<Registry> {
+        Arc::clone(&self.registry)
+    }
+
+    pub(crate) fn record_put(&self, bytes: usize) {
+        let _ = self.records_put.inc();
+        let _ = self.bytes_uploaded.inc_by(bytes as u64);
+    }
+
+    pub(crate) fn record_got(&self, bytes: usize) {
+        let _ = self.records_got.inc();
+        let _ = self.bytes_downloaded.inc_by(bytes as u64);
+    }
+
+    pub(crate) fn put_retry(&self) {
+        let _ = self.put_retries.inc();
+    }
+
+    pub(crate) fn get_retry(&self) {
+        let _ = self.get_retries.inc();
+    }
+
+    pub(crate) fn payment_attempt(&self) {
+        let _ = self.payment_attempts.inc();
+    }
+
+    pub(crate) fn payment_failure(&self) {
+        let _ = self.payment_failures.inc();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prometheus_client::encoding::text::encode;
+
+    #[test]
+    fn test_metrics_are_encoded() {
+        let metrics = ClientMetrics::new();
+        metrics.record_put(100);
+        metrics.record_put(50);
+        metrics.payment_attempt();
+
+        let mut encoded = String::new();
+        encode(&mut encoded, &metrics.registry()).expect("encoding shall succeed");
+        assert!(encoded.contains("autonomi_client_records_put_total 2"));
+        assert!(encoded.contains("autonomi_client_bytes_uploaded_total 150"));
+        assert!(encoded.contains("autonomi_client_payment_attempts_total 1"));
+        assert!(encoded.contains("autonomi_client_payment_failures_total 0"));
+    }
+}
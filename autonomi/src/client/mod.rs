@@ -26,39 +26,55 @@ pub use data_types::scratchpad;
 mod high_level;
 pub use high_level::data;
 pub use high_level::files;
+pub use high_level::index;
 pub use high_level::register;
+pub use high_level::site;
 pub use high_level::vault;
 
 pub mod analyze;
+pub mod audit;
+pub mod builder;
 pub mod config;
+pub mod datamap;
+pub mod health;
 pub mod key_derivation;
 pub mod merkle_payments;
+pub mod network_clock;
 pub mod payment;
 pub mod quote;
+pub mod repair;
+pub mod store;
+pub mod upload_plan;
 
 #[cfg(feature = "external-signer")]
 #[cfg_attr(docsrs, doc(cfg(feature = "external-signer")))]
 pub mod external_signer;
 
+#[cfg(feature = "open-metrics")]
+pub(crate) mod metrics;
+
 // private module with utility functions
 mod chunk_cache;
 mod data_map_restoration;
 mod network;
 mod put_error_state;
 
+use network_clock::NetworkClock;
 use payment::Receipt;
 pub use put_error_state::ChunkBatchUploadState;
 use quote::PaymentMode;
+use upload_plan::UploadBatchPlan;
 
-use ant_bootstrap::{bootstrap::Bootstrap, contacts_fetcher::ALPHANET_CONTACTS};
+use ant_bootstrap::bootstrap::Bootstrap;
 pub use ant_evm::Amount;
 use ant_evm::EvmNetwork;
 use config::ClientConfig;
 use payment::PayError;
 use quote::CostError;
 use self_encryption::DataMap;
-use std::collections::HashSet;
+use std::time::SystemTime;
 use tokio::sync::mpsc;
+use xor_name::XorName;
 
 /// Time before considering the connection timed out.
 pub const CONNECT_TIMEOUT_SECS: u64 = 10;
@@ -68,7 +84,10 @@ const CLIENT_EVENT_CHANNEL_SIZE: usize = 100;
 // Amount of peers to confirm into our routing table before we consider the client ready.
 use crate::client::config::ClientOperatingStrategy;
 use crate::client::merkle_payments::{MerklePaymentReceipt, MerkleUploadError};
-use crate::networking::{Multiaddr, Network, NetworkAddress, NetworkError, multiaddr_is_global};
+use crate::networking::{
+    Multiaddr, Network, NetworkAddress, NetworkError, NetworkProtocol, ProtocolMismatch,
+    multiaddr_is_global,
+};
 pub use ant_protocol::CLOSE_GROUP_SIZE;
 use ant_protocol::storage::RecordKind;
 
@@ -76,7 +95,8 @@ use ant_protocol::storage::RecordKind;
 ///
 /// # Example
 ///
-/// To start interacting with the network, use [`Client::init`].
+/// To start interacting with the network, use [`Client::init`], or [`Client::builder`] to set the
+/// options of the client.
 ///
 /// ```no_run
 /// # use autonomi::client::Client;
@@ -101,6 +121,10 @@ pub struct Client {
     retry_failed: u64,
     /// Payment mode to use for uploads
     payment_mode: PaymentMode,
+    /// Whether the client refuses to pay for uploads, see [`Client::init_read_only`].
+    read_only: bool,
+    /// The clock of the network, estimated from the quotes of the nodes.
+    network_clock: NetworkClock,
 }
 
 /// Error returned by [`Client::init`].
@@ -110,9 +134,10 @@ pub enum ConnectError {
     #[error("Failed to populate our routing table with enough peers in time")]
     TimedOut,
 
-    /// Same as [`ConnectError::TimedOut`] but with a list of incompatible protocols.
-    #[error("Failed to populate our routing table due to incompatible protocol: {0:?}")]
-    TimedOutWithIncompatibleProtocol(HashSet<String>, String),
+    /// The peers reached do not talk our protocol: they belong to another network, or run an
+    /// incompatible version.
+    #[error("{0}")]
+    ProtocolMismatch(#[from] ProtocolMismatch),
 
     /// An error occurred while bootstrapping the client.
     #[error("Failed to bootstrap the client: {0}")]
@@ -125,6 +150,10 @@ pub enum ConnectError {
     /// An error occurred while initializing the EVM network.
     #[error("Failed to initialize the EVM network: {0}")]
     EvmNetworkError(String),
+
+    /// The options given to the [`builder::ClientBuilder`] can't be combined.
+    #[error("Invalid client configuration: {0}")]
+    InvalidConfig(#[from] builder::ClientConfigError),
 }
 
 /// Errors that can occur during the put operation.
@@ -188,41 +217,51 @@ pub enum GetError {
 impl Client {
     /// Initialize the client with default configuration.
     ///
-    /// See [`Client::init_with_config`].
+    /// See [`Client::builder`] to set its options.
     pub async fn init() -> Result<Self, ConnectError> {
-        Self::init_with_config(ClientConfig {
-            bootstrap_config: BootstrapConfig::new(false),
-            ..Default::default()
-        })
-        .await
+        Self::builder().build().await
+    }
+
+    /// Initialize a read-only client of the main network, for fetching public data.
+    ///
+    /// A read-only client never needs a wallet: every payment, and so every upload, fails with
+    /// [`PayError::ReadOnlyClient`](crate::client::payment::PayError::ReadOnlyClient).
+    ///
+    /// This is a runtime mode only: the EVM and wallet crates are still compiled in, as the
+    /// network messages carry their payment types. Compiling them out is tracked separately.
+    ///
+    /// ```no_run
+    /// # use autonomi::Client;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::init_read_only().await?;
+    /// assert!(client.is_read_only());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn init_read_only() -> Result<Self, ConnectError> {
+        let client = Self::init().await?;
+        Ok(client.with_read_only(true))
     }
 
     /// Initialize a client that is configured to be local.
     ///
-    /// See [`Client::init_with_config`].
+    /// See [`Client::builder`] with [`builder::ClientNetwork::Local`].
     pub async fn init_local() -> Result<Self, ConnectError> {
-        Self::init_with_config(ClientConfig {
-            evm_network: EvmNetwork::new(true)
-                .map_err(|e| ConnectError::EvmNetworkError(e.to_string()))?,
-            strategy: Default::default(),
-            network_id: None,
-            bootstrap_config: BootstrapConfig::new(true),
-        })
-        .await
+        Self::builder()
+            .network(builder::ClientNetwork::Local)
+            .build()
+            .await
     }
 
     /// Initialize a client that is configured to be connected to the the alpha network (Impossible Futures).
+    ///
+    /// See [`Client::builder`] with [`builder::ClientNetwork::Alpha`].
     pub async fn init_alpha() -> Result<Self, ConnectError> {
-        let client_config = ClientConfig {
-            bootstrap_config: BootstrapConfig {
-                network_contacts_url: ALPHANET_CONTACTS.iter().map(|s| s.to_string()).collect(),
-                ..Default::default()
-            },
-            evm_network: EvmNetwork::ArbitrumSepoliaTest,
-            strategy: Default::default(),
-            network_id: Some(2),
-        };
-        Self::init_with_config(client_config).await
+        Self::builder()
+            .network(builder::ClientNetwork::Alpha)
+            .build()
+            .await
     }
 
     /// Initialize a client that bootstraps from a list of peers.
@@ -252,6 +291,8 @@ impl Client {
             evm_network: EvmNetwork::new(local).unwrap_or_default(),
             strategy: Default::default(),
             network_id: None,
+            tcp_fallback: false,
+            connection_budget: Default::default(),
         })
         .await
     }
@@ -271,19 +312,38 @@ impl Client {
     /// # }
     /// ```
     pub async fn init_with_config(config: ClientConfig) -> Result<Self, ConnectError> {
-        if let Some(network_id) = config.network_id {
-            ant_protocol::version::set_network_id(network_id);
+        // The network id is scoped to this client rather than set process-wide, so that clients of
+        // different networks can run in the same process.
+        let network_id = config
+            .network_id
+            .or(config.bootstrap_config.network_id)
+            .unwrap_or_else(ant_protocol::version::get_network_id);
+        let protocol = NetworkProtocol::new(network_id);
+
+        // Contract addresses published after this release take precedence over the compiled in ones
+        if let Err(err) = evmlib::contract_registry::discover_contracts(&config.evm_network).await {
+            warn!("Failed to discover the EVM contract addresses, using the default ones: {err}");
         }
 
-        let bootstrap = Bootstrap::new(config.bootstrap_config.clone()).await?;
-        let network = Network::new(bootstrap)?;
+        let bootstrap_config = BootstrapConfig {
+            network_id: Some(network_id),
+            ..config.bootstrap_config.clone()
+        };
+
+        let bootstrap = Bootstrap::new(bootstrap_config.clone()).await?;
+        let network = Network::new_with_budget(
+            bootstrap,
+            config.tcp_fallback,
+            protocol,
+            config.connection_budget,
+        )?;
 
         // Wait for the network to be ready with enough peers
         let connectivity_result = network.wait_for_connectivity().await;
 
         // If the connection failed and we were using the bootstrap cache,
         // retry once with the cache disabled to fall back to mainnet contacts
-        if connectivity_result.is_err() && !config.bootstrap_config.disable_cache_reading {
+        if connectivity_result.is_err() && !bootstrap_config.disable_cache_reading {
             warn!(
                 "Initial connection failed with bootstrap cache enabled. Retrying with cache disabled to use mainnet contacts..."
             );
@@ -291,12 +351,17 @@ impl Client {
             // Create a new config with cache reading disabled
             let retry_config = BootstrapConfig {
                 disable_cache_reading: true,
-                ..config.bootstrap_config.clone()
+                ..bootstrap_config
             };
 
             // Retry the bootstrap and connection with cache disabled
             let bootstrap_retry = Bootstrap::new(retry_config).await?;
-            let network_retry = Network::new(bootstrap_retry)?;
+            let network_retry = Network::new_with_budget(
+                bootstrap_retry,
+                config.tcp_fallback,
+                protocol,
+                config.connection_budget,
+            )?;
 
             // Wait for connectivity with the new bootstrap configuration
             network_retry.wait_for_connectivity().await?;
@@ -312,6 +377,8 @@ impl Client {
                 config: config.strategy,
                 retry_failed: 0,
                 payment_mode: PaymentMode::Standard,
+                read_only: false,
+                network_clock: NetworkClock::default(),
             });
         }
 
@@ -325,6 +392,8 @@ impl Client {
             config: config.strategy,
             retry_failed: 0,
             payment_mode: PaymentMode::default(),
+            read_only: false,
+            network_clock: NetworkClock::default(),
         })
     }
 
@@ -346,6 +415,28 @@ impl Client {
         self
     }
 
+    /// Set whether the client refuses to pay for uploads, see [`Client::init_read_only`].
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Returns true if the client refuses to pay for uploads.
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// The current time of the network, as estimated from the timestamps of the quotes received
+    /// by the client. This is the local time until the client has received quotes.
+    pub fn network_time(&self) -> SystemTime {
+        self.network_clock.now()
+    }
+
+    /// How far the local clock is ahead of the network's, negative if it is behind.
+    pub fn clock_skew_millis(&self) -> i64 {
+        -self.network_clock.offset_millis()
+    }
+
     /// Receive events from the client.
     pub fn enable_client_events(&mut self) -> mpsc::Receiver<ClientEvent> {
         let (client_event_sender, client_event_receiver) =
@@ -356,6 +447,17 @@ impl Client {
         client_event_receiver
     }
 
+    /// Record every network operation of this client to a structured audit log at `path`, one
+    /// JSON object per line: record key, operation, peers contacted, quorum achieved, duration
+    /// and payment reference. The log is appended to if it already exists.
+    ///
+    /// The audit log is shared by all the clones of the client, including the ones made before.
+    pub fn enable_audit_log(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        let audit_log = crate::networking::audit_log::AuditLog::open(path.as_ref())?;
+        self.network.set_audit_log(audit_log);
+        Ok(())
+    }
+
     /// Get the evm network.
     pub fn evm_network(&self) -> &EvmNetwork {
         &self.evm_network
@@ -365,11 +467,25 @@ impl Client {
     pub fn network(&self) -> &Network {
         &self.network
     }
+
+    /// Get the registry of the client operation metrics: record puts and gets, bytes transferred,
+    /// retries and payments.
+    ///
+    /// The registry is shared by all the clones of this client, and can be served on the
+    /// Prometheus endpoint of the embedding service.
+    #[cfg(feature = "open-metrics")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "open-metrics")))]
+    pub fn metrics_registry(&self) -> std::sync::Arc<prometheus_client::registry::Registry> {
+        self.network.metrics().registry()
+    }
 }
 
 /// Events that can be sent by the client.
+///
+/// New events may be added, so matches on the events shall ignore the unknown ones.
 #[derive(Debug, Clone)]
 #[allow(clippy::enum_variant_names)]
+#[non_exhaustive]
 pub enum ClientEvent {
     UploadComplete(UploadSummary),
     /// Emitted after each Merkle tree batch payment completes.
@@ -378,6 +494,12 @@ pub enum ClientEvent {
     /// Emitted after each regular (non-merkle) batch payment completes.
     /// This allows progressive saving of the receipt to disk for upload resume.
     RegularBatchPaymentComplete(Receipt),
+    /// Emitted before each batch of a bulk upload is quoted and uploaded,
+    /// with the composition of the batch.
+    UploadBatchPlanned(UploadBatchPlan),
+    /// Emitted when the quotes of some addresses expired, or were about to, before being paid
+    /// for or uploaded, and these addresses were re-quoted to be paid for again.
+    QuotesRefreshed(Vec<XorName>),
 }
 
 /// Summary of an upload operation.
@@ -0,0 +1,126 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Estimate of the clock of the network, from the timestamps of the quotes of the nodes.
+//!
+//! Quote and payment timestamps end up checked against the clocks of the nodes, so a client with
+//! a skewed clock gets its payments refused late in an upload. Checking timestamps against the
+//! clock of the network instead catches stale quotes before paying for them.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::time::{Duration, SystemTime};
+
+/// Difference between the local clock and the network's above which the user is warned.
+pub const CLOCK_SKEW_WARNING_THRESHOLD: Duration = Duration::from_secs(5 * 60);
+
+/// Quotes timestamped further than this from the clock of the network, in either direction,
+/// come from nodes with a skewed clock and are refused.
+pub const QUOTE_MAX_CLOCK_DRIFT: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// The clock of the network, as the local clock shifted by the offset estimated from the
+/// timestamps of the last quotes received.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct NetworkClock {
+    /// Network time minus local time, in milliseconds
+    offset_millis: Arc<AtomicI64>,
+}
+
+impl NetworkClock {
+    /// Update the estimate with the timestamps of quotes received at `received_at`.
+    pub(crate) fn observe(&self, peer_timestamps: &[SystemTime], received_at: SystemTime) {
+        let Some(offset) = estimate_offset_millis(peer_timestamps, received_at) else {
+            return;
+        };
+        let previous = self.offset_millis.swap(offset, Ordering::Relaxed);
+
+        let threshold = CLOCK_SKEW_WARNING_THRESHOLD.as_millis() as i64;
+        if offset.abs() > threshold && previous.abs() <= threshold {
+            warn!(
+                "The local clock is {}s {} the network's, payments use the clock of the network",
+                offset.abs() / 1000,
+                if offset < 0 { "ahead of" } else { "behind" }
+            );
+        }
+        trace!("Network clock offset estimated to {offset}ms");
+    }
+
+    /// Network time minus local time, in milliseconds.
+    pub(crate) fn offset_millis(&self) -> i64 {
+        self.offset_millis.load(Ordering::Relaxed)
+    }
+
+    /// The current time of the network.
+    pub(crate) fn now(&self) -> SystemTime {
+        shift(SystemTime::now(), self.offset_millis())
+    }
+}
+
+/// Returns true if a quote timestamp is close enough to the clock of the network to be paid for.
+pub(crate) fn is_quote_timestamp_valid(timestamp: SystemTime, network_now: SystemTime) -> bool {
+    signed_millis_between(network_now, timestamp).unsigned_abs()
+        <= QUOTE_MAX_CLOCK_DRIFT.as_millis() as u64
+}
+
+/// The median of the differences between the peer timestamps and the local time.
+fn estimate_offset_millis(peer_timestamps: &[SystemTime], received_at: SystemTime) -> Option<i64> {
+    let mut offsets: Vec<i64> = peer_timestamps
+        .iter()
+        .map(|timestamp| signed_millis_between(received_at, *timestamp))
+        .collect();
+    offsets.sort_unstable();
+    offsets.get(offsets.len() / 2).copied()
+}
+
+/// `to - from`, in milliseconds.
+fn signed_millis_between(from: SystemTime, to: SystemTime) -> i64 {
+    match to.duration_since(from) {
+        Ok(ahead) => ahead.as_millis() as i64,
+        Err(err) => -(err.duration().as_millis() as i64),
+    }
+}
+
+fn shift(time: SystemTime, millis: i64) -> SystemTime {
+    let delta = Duration::from_millis(millis.unsigned_abs());
+    if millis < 0 {
+        time.checked_sub(delta).unwrap_or(SystemTime::UNIX_EPOCH)
+    } else {
+        time.checked_add(delta).unwrap_or(time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_network_clock_offset_estimate() {
+        let now = SystemTime::now();
+        let hour = Duration::from_secs(3600);
+
+        // the local clock is an hour behind most nodes, one node is a day ahead of them
+        let peer_timestamps = [now + hour, now + hour, now + 24 * hour, now + hour];
+        let clock = NetworkClock::default();
+        clock.observe(&peer_timestamps, now);
+        assert_eq!(clock.offset_millis(), hour.as_millis() as i64);
+
+        let network_now = clock.now();
+        assert!(network_now > now + hour - Duration::from_secs(60));
+        assert!(is_quote_timestamp_valid(now + hour, network_now));
+        assert!(is_quote_timestamp_valid(now, network_now));
+        assert!(!is_quote_timestamp_valid(now + 30 * hour, network_now));
+        assert!(!is_quote_timestamp_valid(now - 24 * hour, network_now));
+
+        // no timestamps keep the previous estimate
+        clock.observe(&[], now);
+        assert_eq!(clock.offset_millis(), hour.as_millis() as i64);
+
+        clock.observe(&[now - hour], now);
+        assert_eq!(clock.offset_millis(), -(hour.as_millis() as i64));
+    }
+}
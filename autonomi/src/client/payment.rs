@@ -9,8 +9,11 @@
 use crate::Client;
 use crate::client::merkle_payments::MerklePaymentReceipt;
 use crate::client::quote::{DataTypes, StoreQuote};
-use ant_evm::{ClientProofOfPayment, EncodedPeerId, EvmWallet, EvmWalletError};
-use std::collections::HashMap;
+use crate::networking::audit_log::{AuditLogEntry, AuditOperation};
+use ant_evm::{ClientProofOfPayment, EncodedPeerId, EvmWallet, EvmWalletError, QuoteHash, TxHash};
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, Instant, SystemTime};
+use tokio::sync::MutexGuard;
 use xor_name::XorName;
 
 use super::quote::CostError;
@@ -22,6 +25,15 @@ pub type Receipt = HashMap<XorName, (ClientProofOfPayment, AttoTokens)>;
 
 pub type AlreadyPaidAddressesCount = usize;
 
+/// The number of times the expired quotes of a payment are refreshed before giving up.
+const MAX_QUOTE_REFRESHES: usize = 3;
+
+/// Payments older than this, as per the timestamp of their quotes, are not reused.
+pub const PAYMENT_EXPIRATION: Duration = Duration::from_secs(3600 * 24 * 30);
+
+/// Time left for the upload of a reused payment to complete before the payment expires.
+pub const PAYMENT_EXPIRATION_MARGIN: Duration = Duration::from_secs(3600 * 24);
+
 /// Errors that can occur during the pay operation.
 #[derive(Debug, thiserror::Error)]
 pub enum PayError {
@@ -35,6 +47,52 @@ pub enum PayError {
     SelfEncryption(#[from] crate::self_encryption::Error),
     #[error("Cost error: {0:?}")]
     Cost(#[from] CostError),
+    #[error("The client is read-only and cannot pay for uploads")]
+    ReadOnlyClient,
+    #[error("The quotes of {0} addresses kept expiring before they could be paid for")]
+    QuotesExpired(usize),
+}
+
+/// Returns true if a quote timestamped at `timestamp` is too old to be paid for or reused at
+/// `now`, or would expire before an upload could complete.
+pub fn is_quote_expired(timestamp: SystemTime, now: SystemTime) -> bool {
+    let expired_if_before = now
+        .checked_sub(PAYMENT_EXPIRATION - PAYMENT_EXPIRATION_MARGIN)
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+    timestamp < expired_if_before
+}
+
+/// Returns true if the payment is too old to be reused at `now`, or would expire before an upload
+/// could complete, or has no quote to tell its age.
+pub fn is_payment_expired(proof: &ClientProofOfPayment, now: SystemTime) -> bool {
+    match proof
+        .peer_quotes
+        .iter()
+        .map(|(_, _, quote)| quote.timestamp)
+        .min()
+    {
+        Some(oldest_quote) => is_quote_expired(oldest_quote, now),
+        None => true,
+    }
+}
+
+/// The payments of a receipt from a previous upload attempt that can still be reused at `now` for
+/// the given addresses.
+pub fn reusable_payments<'a>(
+    receipt: &Receipt,
+    addrs: impl Iterator<Item = &'a XorName>,
+    now: SystemTime,
+) -> Receipt {
+    addrs
+        .filter_map(|addr| {
+            let (proof, cost) = receipt.get(addr)?;
+            if is_payment_expired(proof, now) {
+                debug!("Previous payment of chunk {addr:?} has expired, paying again");
+                return None;
+            }
+            Some((*addr, (proof.clone(), *cost)))
+        })
+        .collect()
 }
 
 pub fn receipt_from_store_quotes(quotes: StoreQuote) -> Receipt {
@@ -91,6 +149,9 @@ pub enum PaymentOption {
     Wallet(EvmWallet),
     /// Resume upload with existing payment receipt
     Receipt(Receipt),
+    /// Resume upload - reuses the still valid payments of the receipt, pays for the other
+    /// chunks with the wallet
+    ContinueWithReceipt(EvmWallet, Receipt),
 }
 
 impl From<EvmWallet> for PaymentOption {
@@ -134,6 +195,8 @@ impl From<Receipt> for PaymentOption {
 /// - `Receipt`: Resume regular payment upload with existing receipt
 /// - `MerkleReceipt`: Resume merkle upload with existing proofs (fails if unpaid chunks remain)
 /// - `ContinueMerkle`: Resume merkle upload, paying for any remaining chunks with wallet
+/// - `ContinueRegular`: Resume regular payment upload, reusing the payments still valid and
+///   paying for any remaining chunks with wallet
 ///
 /// When a merkle upload fails, check `UploadError::MerkleUpload` for a receipt containing
 /// valid payment proofs that can be reused.
@@ -151,6 +214,9 @@ pub enum BulkPaymentOption {
     MerkleReceipt(MerklePaymentReceipt),
     /// Continue merkle upload - uses existing proofs from receipt, pays for any unpaid chunks with wallet
     ContinueMerkle(EvmWallet, MerklePaymentReceipt),
+    /// Continue regular upload - reuses the still valid payments of the receipt, pays for any
+    /// unpaid chunks with wallet
+    ContinueRegular(EvmWallet, Receipt),
 }
 
 impl From<EvmWallet> for BulkPaymentOption {
@@ -182,6 +248,7 @@ impl From<PaymentOption> for BulkPaymentOption {
         match value {
             PaymentOption::Wallet(w) => BulkPaymentOption::Wallet(w),
             PaymentOption::Receipt(r) => BulkPaymentOption::Receipt(r),
+            PaymentOption::ContinueWithReceipt(w, r) => BulkPaymentOption::ContinueRegular(w, r),
         }
     }
 }
@@ -200,6 +267,7 @@ impl BulkPaymentOption {
             BulkPaymentOption::Receipt(_) => None,
             BulkPaymentOption::MerkleReceipt(_) => None,
             BulkPaymentOption::ContinueMerkle(w, _) => Some(w),
+            BulkPaymentOption::ContinueRegular(w, _) => Some(w),
         }
     }
 
@@ -215,6 +283,7 @@ impl BulkPaymentOption {
             BulkPaymentOption::Receipt(_) => None,
             BulkPaymentOption::MerkleReceipt(_) => None,
             BulkPaymentOption::ContinueMerkle(w, _) => Some(PaymentOption::Wallet(w.clone())),
+            BulkPaymentOption::ContinueRegular(w, _) => Some(PaymentOption::Wallet(w.clone())),
         }
     }
 
@@ -240,6 +309,10 @@ impl Client {
         match payment_option {
             PaymentOption::Wallet(wallet) => self.pay(data_type, content_addrs, &wallet).await,
             PaymentOption::Receipt(receipt) => Ok((receipt, 0)),
+            // the reusable payments of the receipt are picked by the callers uploading chunks
+            PaymentOption::ContinueWithReceipt(wallet, _) => {
+                self.pay(data_type, content_addrs, &wallet).await
+            }
         }
     }
 
@@ -250,28 +323,39 @@ impl Client {
         content_addrs: impl Iterator<Item = (XorName, usize)> + Clone,
         wallet: &EvmWallet,
     ) -> Result<(Receipt, AlreadyPaidAddressesCount), PayError> {
+        if self.is_read_only() {
+            return Err(PayError::ReadOnlyClient);
+        }
+
         // Check if the wallet uses the same network as the client
         if wallet.network() != self.evm_network() {
             return Err(PayError::EvmWalletNetworkMismatch);
         }
 
         let number_of_content_addrs = content_addrs.clone().count();
-        let quotes = self.get_store_quotes(data_type, content_addrs).await?;
+        let data_sizes: HashMap<XorName, usize> = content_addrs.clone().collect();
+        let mut quotes = self.get_store_quotes(data_type, content_addrs).await?;
 
         crate::loud_info!("Paying for {} addresses..", quotes.len());
 
         if !quotes.is_empty() {
             // Make sure nobody else can use the wallet while we are paying
             debug!("Waiting for wallet lock");
-            let lock_guard = wallet.lock().await;
+            let lock_guard = self
+                .lock_wallet_with_fresh_quotes(wallet, data_type, &data_sizes, &mut quotes)
+                .await?;
             debug!("Locked wallet");
 
             // Execute payments
+            #[cfg(feature = "open-metrics")]
+            self.network.metrics().payment_attempt();
+            let start = Instant::now();
             match wallet.pay_for_quotes(quotes.payments()).await {
-                Ok((_tx_hashes, gas_info)) => {
+                Ok((tx_hashes, gas_info)) => {
                     // payment is done, unlock the wallet for other threads
                     drop(lock_guard);
                     debug!("Unlocked wallet");
+                    self.audit_payment(&quotes, Ok(&tx_hashes), start.elapsed());
 
                     // Display gas cost to user
                     crate::loud_info!("Gas cost: {gas_info}");
@@ -280,6 +364,9 @@ impl Client {
                     // payment failed, unlock the wallet for other threads
                     drop(lock_guard);
                     debug!("Unlocked wallet after payment error");
+                    #[cfg(feature = "open-metrics")]
+                    self.network.metrics().payment_failure();
+                    self.audit_payment(&quotes, Err(&pay_err.0), start.elapsed());
                     return Err(PayError::from(pay_err.0));
                 }
             }
@@ -296,4 +383,181 @@ impl Client {
 
         Ok((receipt, skipped_chunks))
     }
+
+    /// Lock the wallet to pay for the quotes. The addresses whose quotes expired, or are about to,
+    /// by the time the wallet is available, e.g. after quoting a large batch or waiting for other
+    /// payments, are re-quoted first, so that only the affected addresses are quoted again.
+    async fn lock_wallet_with_fresh_quotes<'a>(
+        &self,
+        wallet: &'a EvmWallet,
+        data_type: DataTypes,
+        data_sizes: &HashMap<XorName, usize>,
+        quotes: &mut StoreQuote,
+    ) -> Result<MutexGuard<'a, ()>, PayError> {
+        let mut refreshes = 0;
+        loop {
+            let lock_guard = wallet.lock().await;
+            let expiring = quotes.expiring_addresses(self.network_time());
+            if expiring.is_empty() {
+                return Ok(lock_guard);
+            }
+            // Don't hold the wallet while quoting
+            drop(lock_guard);
+
+            if refreshes == MAX_QUOTE_REFRESHES {
+                error!(
+                    "Quotes of {} addresses kept expiring after {refreshes} refreshes",
+                    expiring.len()
+                );
+                return Err(PayError::QuotesExpired(expiring.len()));
+            }
+            refreshes += 1;
+
+            crate::loud_info!(
+                "Quotes of {} addresses expired before being paid for, re-quoting them..",
+                expiring.len()
+            );
+            let refreshed = self
+                .get_store_quotes(
+                    data_type,
+                    expiring
+                        .iter()
+                        .filter_map(|addr| data_sizes.get(addr).map(|size| (*addr, *size))),
+                )
+                .await?;
+            // Addresses stored in the meantime are no longer quoted, and are free
+            for addr in &expiring {
+                let _ = quotes.0.remove(addr);
+            }
+            quotes.0.extend(refreshed.0);
+            self.send_quotes_refreshed(&expiring).await;
+        }
+    }
+
+    /// Record the payment of each content address to the audit log, if enabled.
+    fn audit_payment(
+        &self,
+        quotes: &StoreQuote,
+        result: Result<&BTreeMap<QuoteHash, TxHash>, &EvmWalletError>,
+        duration: Duration,
+    ) {
+        let Some(audit_log) = self.network.audit_log() else {
+            return;
+        };
+
+        for (content_addr, quote_for_address) in &quotes.0 {
+            let mut entry =
+                AuditLogEntry::new(AuditOperation::Payment, content_addr.to_string(), duration);
+            entry.peers = quote_for_address
+                .0
+                .iter()
+                .map(|(peer_id, ..)| peer_id.to_string())
+                .collect();
+            match result {
+                Ok(tx_hashes) => {
+                    let paid: Vec<_> = quote_for_address
+                        .0
+                        .iter()
+                        .filter_map(|(peer_id, _, quote, _)| {
+                            tx_hashes
+                                .get(&quote.hash())
+                                .map(|tx_hash| (peer_id.to_string(), tx_hash.to_string()))
+                        })
+                        .collect();
+                    entry.holders = paid.iter().map(|(peer_id, _)| peer_id.clone()).collect();
+                    entry.payment = Some(paid.into_iter().map(|(_, tx_hash)| tx_hash).collect());
+                }
+                Err(err) => entry.error = Some(err.to_string()),
+            }
+            audit_log.write(&entry);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ant_evm::{PaymentQuote, QuotingMetrics, RewardsAddress};
+    use libp2p::PeerId;
+
+    fn proof_quoted_at(timestamp: SystemTime) -> ClientProofOfPayment {
+        let quote = PaymentQuote {
+            content: XorName::random(&mut rand::thread_rng()),
+            timestamp,
+            quoting_metrics: QuotingMetrics {
+                data_type: 0,
+                data_size: 0,
+                close_records_stored: 0,
+                records_per_type: vec![],
+                max_records: 0,
+                received_payment_count: 0,
+                live_time: 0,
+                network_density: None,
+                network_size: None,
+            },
+            rewards_address: RewardsAddress::ZERO,
+            pub_key: vec![],
+            signature: vec![],
+        };
+        ClientProofOfPayment {
+            peer_quotes: vec![(EncodedPeerId::from(PeerId::random()), vec![], quote)],
+        }
+    }
+
+    #[test]
+    fn test_only_valid_payments_are_reused() {
+        let now = SystemTime::now();
+        let day = Duration::from_secs(3600 * 24);
+        let fresh = XorName::random(&mut rand::thread_rng());
+        let quoted_days_ago = XorName::random(&mut rand::thread_rng());
+        let expired = XorName::random(&mut rand::thread_rng());
+        let expiring = XorName::random(&mut rand::thread_rng());
+        let not_in_batch = XorName::random(&mut rand::thread_rng());
+        let unpaid = XorName::random(&mut rand::thread_rng());
+
+        let receipt = Receipt::from([
+            (fresh, (proof_quoted_at(now), AttoTokens::from_u64(1))),
+            (
+                quoted_days_ago,
+                (proof_quoted_at(now - 3 * day), AttoTokens::from_u64(1)),
+            ),
+            (
+                expired,
+                (
+                    proof_quoted_at(now - PAYMENT_EXPIRATION - Duration::from_secs(1)),
+                    AttoTokens::from_u64(1),
+                ),
+            ),
+            (
+                expiring,
+                (
+                    proof_quoted_at(now - PAYMENT_EXPIRATION + PAYMENT_EXPIRATION_MARGIN / 2),
+                    AttoTokens::from_u64(1),
+                ),
+            ),
+            (
+                not_in_batch,
+                (proof_quoted_at(now), AttoTokens::from_u64(1)),
+            ),
+        ]);
+
+        let reused = reusable_payments(
+            &receipt,
+            [fresh, quoted_days_ago, expired, expiring, unpaid].iter(),
+            now,
+        );
+        assert_eq!(reused.len(), 2);
+        assert_eq!(reused.get(&fresh), receipt.get(&fresh));
+        assert_eq!(reused.get(&quoted_days_ago), receipt.get(&quoted_days_ago));
+    }
+
+    #[test]
+    fn test_payment_without_quotes_is_expired() {
+        assert!(is_payment_expired(
+            &ClientProofOfPayment {
+                peer_quotes: vec![]
+            },
+            SystemTime::now()
+        ));
+    }
 }
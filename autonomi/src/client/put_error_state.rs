@@ -7,7 +7,6 @@
 // permissions and limitations relating to use of the SAFE Network Software.
 
 use super::PutError;
-use crate::client::NetworkAddress;
 use crate::client::data_types::chunk::ChunkAddress;
 use crate::client::payment::Receipt;
 
@@ -48,12 +47,9 @@ impl ChunkBatchUploadState {
                 network_error,
                 payment,
             } => {
-                let chunk_addr = match *address {
-                    NetworkAddress::ChunkAddress(chunk_addr) => chunk_addr,
-                    _ => {
-                        error!("Skip unexpected non-chunk address: {address:?}");
-                        return;
-                    }
+                let Some(chunk_addr) = address.as_chunk_address() else {
+                    error!("Skip unexpected non-chunk address: {address:?}");
+                    return;
                 };
                 self.failed.push((chunk_addr, network_error.to_string()));
                 match self.payment {
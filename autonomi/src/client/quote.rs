@@ -8,9 +8,12 @@
 
 use super::Client;
 use crate::client::config::CHUNK_UPLOAD_BATCH_SIZE;
+use crate::client::network_clock::{NetworkClock, is_quote_timestamp_valid};
+use crate::client::payment::is_quote_expired;
 use crate::networking::Network;
 use crate::networking::PeerInfo;
 use crate::networking::common::Addresses;
+use crate::self_encryption::MAX_CHUNK_SIZE;
 use crate::utils::process_tasks_with_max_concurrency;
 use ant_evm::payment_vault::get_market_price;
 use ant_evm::{Amount, AttoTokens, PaymentQuote, QuotePayment, QuotingMetrics};
@@ -18,6 +21,7 @@ pub use ant_protocol::storage::DataTypes;
 use ant_protocol::{CLOSE_GROUP_SIZE, NetworkAddress, storage::ChunkAddress};
 use libp2p::PeerId;
 use std::collections::HashMap;
+use std::time::SystemTime;
 use xor_name::XorName;
 
 /// Payment strategy for uploads
@@ -30,6 +34,69 @@ pub enum PaymentMode {
     SingleNode,
 }
 
+/// How the nodes to pay for a record are selected among the quotes of its close group.
+///
+/// Quotes are sorted by price. When a single node is paid, it is paid 3x the median price, the
+/// amount the payment vault verifies a single payment against.
+/// [`PaymentMode::Standard`] always pays [`DEFAULT_REDUNDANT_PAYEES`] nodes, as
+/// [`QuoteSelectionStrategy::RedundancyWeighted`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QuoteSelectionStrategy {
+    /// Pay only the cheapest node, 3x the median price. This costs as much as
+    /// [`QuoteSelectionStrategy::MedianOfMajority`], but rewards nodes for underquoting, and the
+    /// cheapest node is more often unreliable.
+    Cheapest,
+    /// Pay only the node with the median price, which a majority of the nodes agree is fair.
+    #[default]
+    MedianOfMajority,
+    /// Pay the `payees` most expensive nodes their own price, spreading the payment over several
+    /// payees, so that more nodes hold the record with a payment backing their responsibility.
+    /// `payees` must be within [`MIN_REDUNDANT_PAYEES`] and [`MAX_REDUNDANT_PAYEES`].
+    RedundancyWeighted { payees: usize },
+}
+
+/// The number of nodes paid by [`PaymentMode::Standard`].
+pub const DEFAULT_REDUNDANT_PAYEES: usize = 3;
+
+/// The fewest nodes a payment can be spread over: the payment vault only accepts a payment to
+/// several nodes if the 3 most expensive ones are paid their price.
+pub const MIN_REDUNDANT_PAYEES: usize = 3;
+
+/// The most nodes a payment can be spread over, all the quotes of a record.
+pub const MAX_REDUNDANT_PAYEES: usize = MINIMUM_QUOTES_TO_PAY;
+
+impl QuoteSelectionStrategy {
+    /// Check that the payments of this strategy can be verified by the nodes.
+    pub fn validate(&self) -> Result<(), CostError> {
+        match self {
+            Self::RedundancyWeighted { payees }
+                if !(MIN_REDUNDANT_PAYEES..=MAX_REDUNDANT_PAYEES).contains(payees) =>
+            {
+                Err(CostError::InvalidPayeeCount(*payees))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// The amounts to pay for the given prices, sorted in increasing order.
+    /// Requires at least [`MINIMUM_QUOTES_TO_PAY`] prices.
+    pub(crate) fn payment_amounts(&self, sorted_prices: &[Amount]) -> Vec<Amount> {
+        let mut amounts = vec![Amount::ZERO; sorted_prices.len()];
+        match self {
+            Self::Cheapest => amounts[0] = sorted_prices[2] * Amount::from(3u64),
+            Self::MedianOfMajority => amounts[2] = sorted_prices[2] * Amount::from(3u64),
+            Self::RedundancyWeighted { payees } => {
+                let first_payee = sorted_prices.len() - (*payees).min(sorted_prices.len());
+                amounts[first_payee..].copy_from_slice(&sorted_prices[first_payee..]);
+            }
+        }
+        amounts
+    }
+}
+
+/// The number of quotes needed to pay for a record.
+const MINIMUM_QUOTES_TO_PAY: usize = 5;
+
 // todo: limit depends per RPC endpoint. We should make this configurable
 // todo: test the limit for the Arbitrum One public RPC endpoint
 // Working limit of the Arbitrum Sepolia public RPC endpoint
@@ -72,6 +139,20 @@ impl StoreQuote {
         quote_payments
     }
 
+    /// The addresses with a quote too old to be paid for at `network_now`, or about to be.
+    pub(crate) fn expiring_addresses(&self, network_now: SystemTime) -> Vec<XorName> {
+        self.0
+            .iter()
+            .filter(|(_, quote)| {
+                quote
+                    .0
+                    .iter()
+                    .any(|(_, _, quote, _)| is_quote_expired(quote.timestamp, network_now))
+            })
+            .map(|(address, _)| *address)
+            .collect()
+    }
+
     pub fn payees_info(&self) -> Vec<(PeerId, Addresses)> {
         let mut payees_info = vec![];
         for (_address, quote) in self.0.iter() {
@@ -108,6 +189,64 @@ pub enum CostError {
     TotalCostOverflow(AttoTokens, AttoTokens),
     #[error("No content addresses generated for file")]
     NoAddressesForContent,
+    #[error("Could not obtain any node prices while sampling the network")]
+    NoPricesSampled,
+    #[error(
+        "Cannot pay {0} nodes per record, payments must be spread over {MIN_REDUNDANT_PAYEES} to {MAX_REDUNDANT_PAYEES} nodes"
+    )]
+    InvalidPayeeCount(usize),
+}
+
+/// Distribution statistics of store costs sampled across random network addresses.
+///
+/// Prices are the market price of each individual node quote, as returned by the payment vault.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriceSample {
+    /// Number of addresses that were sampled
+    pub addresses_sampled: usize,
+    /// Number of addresses for which no quotes could be obtained
+    pub addresses_failed: usize,
+    /// Number of node prices the statistics were computed from
+    pub prices_count: usize,
+    /// Lowest node price seen
+    pub min: Amount,
+    /// Median node price
+    pub median: Amount,
+    /// 90th percentile node price
+    pub p90: Amount,
+    /// Highest node price seen
+    pub max: Amount,
+}
+
+impl PriceSample {
+    /// Compute the statistics from a list of node prices.
+    /// Returns `None` if the list is empty.
+    pub fn from_prices(
+        mut prices: Vec<Amount>,
+        addresses_sampled: usize,
+        addresses_failed: usize,
+    ) -> Option<Self> {
+        if prices.is_empty() {
+            return None;
+        }
+        prices.sort();
+
+        let percentile = |pct: usize| {
+            // nearest-rank method, index is always within bounds as pct <= 100
+            let rank = (pct * prices.len()).div_ceil(100).max(1);
+            prices[rank - 1]
+        };
+
+        Some(Self {
+            addresses_sampled,
+            addresses_failed,
+            prices_count: prices.len(),
+            min: prices[0],
+            median: percentile(50),
+            p90: percentile(90),
+            max: prices[prices.len() - 1],
+        })
+    }
 }
 
 /// Add two costs together, returning an error on overflow.
@@ -130,6 +269,7 @@ impl Client {
                 crate::loud_info!("Quoting for {content_addr:?} ..");
                 fetch_store_quote(
                     &self.network,
+                    &self.network_clock,
                     content_addr,
                     data_type.get_index(),
                     data_size,
@@ -179,6 +319,8 @@ impl Client {
         content_addrs: impl Iterator<Item = (XorName, usize)>,
     ) -> Result<StoreQuote, CostError> {
         let raw_quotes_per_addr = self.get_raw_quotes(data_type, content_addrs).await;
+        // Check the quote timestamps against the clock of the network rather than the local one
+        let network_now = self.network_clock.now();
         let mut all_quotes = Vec::new();
 
         for result in raw_quotes_per_addr {
@@ -188,6 +330,23 @@ impl Client {
                 raw_quotes.len()
             );
 
+            let quotes_count = raw_quotes.len();
+            raw_quotes
+                .retain(|(_, _, quote)| is_quote_timestamp_valid(quote.timestamp, network_now));
+            if raw_quotes.len() < quotes_count {
+                warn!(
+                    "Refused {} quote(s) for {content_addr} with a timestamp too far from the network's clock",
+                    quotes_count - raw_quotes.len()
+                );
+                if raw_quotes.is_empty() {
+                    return Err(self.create_insufficient_quotes_error(
+                        content_addr,
+                        0,
+                        CLOSE_GROUP_SIZE,
+                    ));
+                }
+            }
+
             if raw_quotes.is_empty() {
                 debug!(
                     "content_addr: {content_addr} is already paid for. No need to fetch market price."
@@ -249,115 +408,108 @@ impl Client {
         Ok(StoreQuote(quotes_to_pay_per_addr))
     }
 
-    /// Process quotes according to the payment mode
-    fn process_quotes_by_payment_mode(
+    /// Sample the store costs of `n_random_addresses` random addresses on the Network and return
+    /// distribution statistics of the node prices.
+    ///
+    /// Nothing is uploaded or paid for, this only gathers quotes and asks the payment vault for their market price.
+    /// Addresses for which no quotes could be fetched are counted in [`PriceSample::addresses_failed`].
+    pub async fn network_price_sample(
         &self,
-        quotes_per_addr: HashMap<XorName, Vec<(PeerId, Addresses, PaymentQuote, Amount)>>,
-    ) -> Result<HashMap<XorName, QuoteForAddress>, CostError> {
-        match self.payment_mode {
-            PaymentMode::Standard => self.process_standard_payment_quotes(quotes_per_addr),
-            PaymentMode::SingleNode => self.process_single_node_payment_quotes(quotes_per_addr),
-        }
-    }
+        n_random_addresses: usize,
+    ) -> Result<PriceSample, CostError> {
+        let mut rng = rand::thread_rng();
+        let addrs: Vec<(XorName, usize)> = (0..n_random_addresses)
+            .map(|_| (XorName::random(&mut rng), MAX_CHUNK_SIZE))
+            .collect();
 
-    /// Process quotes for standard payment mode (pay 3 nodes)
-    fn process_standard_payment_quotes(
-        &self,
-        quotes_per_addr: HashMap<XorName, Vec<(PeerId, Addresses, PaymentQuote, Amount)>>,
-    ) -> Result<HashMap<XorName, QuoteForAddress>, CostError> {
-        const MINIMUM_QUOTES_TO_PAY: usize = 5;
-        let mut quotes_to_pay_per_addr = HashMap::new();
+        let raw_quotes_per_addr = self
+            .get_raw_quotes(DataTypes::Chunk, addrs.into_iter())
+            .await;
 
-        for (content_addr, quotes) in quotes_per_addr {
-            if quotes.len() >= MINIMUM_QUOTES_TO_PAY {
-                let quote_for_addr = self.create_standard_quote_payment(&quotes, content_addr);
-                quotes_to_pay_per_addr.insert(content_addr, quote_for_addr);
-            } else {
-                return Err(self.create_insufficient_quotes_error(
-                    content_addr,
-                    quotes.len(),
-                    MINIMUM_QUOTES_TO_PAY,
-                ));
+        let mut addresses_failed = 0;
+        let mut quoting_metrics = Vec::new();
+        for result in raw_quotes_per_addr {
+            match result {
+                Ok((_, quotes)) if !quotes.is_empty() => {
+                    quoting_metrics.extend(
+                        quotes
+                            .into_iter()
+                            .map(|(_, _, quote)| quote.quoting_metrics),
+                    );
+                }
+                Ok((content_addr, _)) => {
+                    warn!("No quotes returned for sampled address {content_addr:?}");
+                    addresses_failed += 1;
+                }
+                Err(err) => {
+                    warn!("Failed to fetch quotes for a sampled address: {err:?}");
+                    addresses_failed += 1;
+                }
             }
         }
 
-        Ok(quotes_to_pay_per_addr)
+        let mut prices = Vec::with_capacity(quoting_metrics.len());
+        for chunk in quoting_metrics.chunks(GET_MARKET_PRICE_BATCH_LIMIT) {
+            prices.extend(get_market_price(&self.evm_network, chunk.to_vec()).await?);
+        }
+
+        debug!(
+            "Sampled {} node prices across {n_random_addresses} addresses ({addresses_failed} failed)",
+            prices.len()
+        );
+
+        PriceSample::from_prices(prices, n_random_addresses, addresses_failed)
+            .ok_or(CostError::NoPricesSampled)
     }
 
-    /// Process quotes for single node payment mode (pay only median priced node with 3x amount)
-    fn process_single_node_payment_quotes(
+    /// Select the quotes to pay according to the payment mode and quote selection strategy
+    fn process_quotes_by_payment_mode(
         &self,
         quotes_per_addr: HashMap<XorName, Vec<(PeerId, Addresses, PaymentQuote, Amount)>>,
     ) -> Result<HashMap<XorName, QuoteForAddress>, CostError> {
-        const MINIMUM_QUOTES_TO_PAY: usize = 5;
+        let strategy = match self.payment_mode {
+            PaymentMode::Standard => QuoteSelectionStrategy::RedundancyWeighted {
+                payees: DEFAULT_REDUNDANT_PAYEES,
+            },
+            PaymentMode::SingleNode => self.config.quote_selection,
+        };
+        strategy.validate()?;
         let mut quotes_to_pay_per_addr = HashMap::new();
 
-        for (content_addr, mut quotes) in quotes_per_addr {
-            if quotes.len() >= MINIMUM_QUOTES_TO_PAY {
-                let quote_for_addr =
-                    self.create_single_node_quote_payment(&mut quotes, content_addr);
-                quotes_to_pay_per_addr.insert(content_addr, quote_for_addr);
-            } else {
+        for (content_addr, quotes) in quotes_per_addr {
+            if quotes.len() < MINIMUM_QUOTES_TO_PAY {
                 return Err(self.create_insufficient_quotes_error(
                     content_addr,
                     quotes.len(),
                     MINIMUM_QUOTES_TO_PAY,
                 ));
             }
+
+            // quotes are sorted by price
+            let prices: Vec<Amount> = quotes.iter().map(|(_, _, _, price)| *price).collect();
+            let amounts = strategy.payment_amounts(&prices);
+            let quote_for_addr = QuoteForAddress(
+                quotes
+                    .into_iter()
+                    .zip(amounts)
+                    .map(|((peer_id, addrs, quote, _), amount)| (peer_id, addrs, quote, amount))
+                    .collect(),
+            );
+            trace!(
+                "Peers to pay for {content_addr} with {strategy:?}: {:?}",
+                quote_for_addr
+                    .0
+                    .iter()
+                    .filter(|(_, _, _, amount)| !amount.is_zero())
+                    .map(|(peer_id, _, _, amount)| (peer_id, amount))
+                    .collect::<Vec<_>>()
+            );
+            quotes_to_pay_per_addr.insert(content_addr, quote_for_addr);
         }
 
         Ok(quotes_to_pay_per_addr)
     }
 
-    /// Create a payment structure for standard mode (pay nodes at indices 2, 3, 4)
-    fn create_standard_quote_payment(
-        &self,
-        quotes: &[(PeerId, Addresses, PaymentQuote, Amount)],
-        content_addr: XorName,
-    ) -> QuoteForAddress {
-        let (p1, a1, q1, _) = &quotes[0];
-        let (p2, a2, q2, _) = &quotes[1];
-
-        let peer_ids = vec![quotes[2].0, quotes[3].0, quotes[4].0];
-        trace!("Peers to pay for {content_addr}: {peer_ids:?}");
-
-        QuoteForAddress(vec![
-            (*p1, a1.clone(), q1.clone(), Amount::ZERO),
-            (*p2, a2.clone(), q2.clone(), Amount::ZERO),
-            quotes[2].clone(),
-            quotes[3].clone(),
-            quotes[4].clone(),
-        ])
-    }
-
-    /// Create a payment structure for single node mode (pay only the median priced node with 3x the amount)
-    fn create_single_node_quote_payment(
-        &self,
-        quotes: &mut [(PeerId, Addresses, PaymentQuote, Amount)],
-        content_addr: XorName,
-    ) -> QuoteForAddress {
-        // Get the median priced node (index 2 after already sorting by price)
-        let (p3, a3, q3, median_price) = &quotes[2];
-        let enhanced_price = *median_price * Amount::from(3u64);
-
-        trace!(
-            "Single peer to pay for {content_addr}: {p3:?} with price {enhanced_price} (3x of {median_price})"
-        );
-
-        let (p1, a1, q1, _) = &quotes[0];
-        let (p2, a2, q2, _) = &quotes[1];
-        let (p4, a4, q4, _) = &quotes[3];
-        let (p5, a5, q5, _) = &quotes[4];
-
-        QuoteForAddress(vec![
-            (*p1, a1.clone(), q1.clone(), Amount::ZERO),
-            (*p2, a2.clone(), q2.clone(), Amount::ZERO),
-            (*p3, a3.clone(), q3.clone(), enhanced_price),
-            (*p4, a4.clone(), q4.clone(), Amount::ZERO),
-            (*p5, a5.clone(), q5.clone(), Amount::ZERO),
-        ])
-    }
-
     /// Create error for insufficient quotes
     fn create_insufficient_quotes_error(
         &self,
@@ -380,6 +532,7 @@ impl Client {
 /// Returns an empty vector if the record already exists and there is no need to pay for it.
 async fn fetch_store_quote(
     network: &Network,
+    network_clock: &NetworkClock,
     content_addr: XorName,
     data_type: u32,
     data_size: usize,
@@ -397,6 +550,8 @@ async fn fetch_store_quote(
 
     // if no quotes are returned an empty vector is returned
     let quotes = maybe_quotes.unwrap_or_default();
+    let timestamps: Vec<SystemTime> = quotes.iter().map(|(_, quote)| quote.timestamp).collect();
+    network_clock.observe(&timestamps, SystemTime::now());
     let quotes_with_peer_id = quotes
         .into_iter()
         .filter_map(|(peer, quote)| match quote.peer_id() {
@@ -409,3 +564,75 @@ async fn fetch_store_quote(
         .collect();
     Ok((content_addr, quotes_with_peer_id))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn price_sample_statistics() {
+        let prices: Vec<Amount> = (1..=10u64).rev().map(Amount::from).collect();
+        let sample = PriceSample::from_prices(prices, 2, 0).expect("non empty prices");
+
+        assert_eq!(sample.prices_count, 10);
+        assert_eq!(sample.min, Amount::from(1u64));
+        assert_eq!(sample.median, Amount::from(5u64));
+        assert_eq!(sample.p90, Amount::from(9u64));
+        assert_eq!(sample.max, Amount::from(10u64));
+    }
+
+    #[test]
+    fn quote_selection_payment_amounts() {
+        let prices: Vec<Amount> = (1..=5u64).map(Amount::from).collect();
+        let amounts = |strategy: QuoteSelectionStrategy| -> Vec<u64> {
+            strategy
+                .payment_amounts(&prices)
+                .into_iter()
+                .map(|amount| amount.to::<u64>())
+                .collect()
+        };
+
+        assert_eq!(amounts(QuoteSelectionStrategy::Cheapest), [9, 0, 0, 0, 0]);
+        assert_eq!(
+            amounts(QuoteSelectionStrategy::MedianOfMajority),
+            [0, 0, 9, 0, 0]
+        );
+        assert_eq!(
+            amounts(QuoteSelectionStrategy::RedundancyWeighted { payees: 3 }),
+            [0, 0, 3, 4, 5]
+        );
+        assert_eq!(
+            amounts(QuoteSelectionStrategy::RedundancyWeighted { payees: 5 }),
+            [1, 2, 3, 4, 5]
+        );
+    }
+
+    #[test]
+    fn payments_to_fewer_than_three_nodes_are_rejected() {
+        for payees in [0, 1, 2, 6] {
+            assert!(matches!(
+                QuoteSelectionStrategy::RedundancyWeighted { payees }.validate(),
+                Err(CostError::InvalidPayeeCount(count)) if count == payees
+            ));
+        }
+        for payees in MIN_REDUNDANT_PAYEES..=MAX_REDUNDANT_PAYEES {
+            assert!(
+                QuoteSelectionStrategy::RedundancyWeighted { payees }
+                    .validate()
+                    .is_ok()
+            );
+        }
+        assert!(QuoteSelectionStrategy::Cheapest.validate().is_ok());
+        assert!(QuoteSelectionStrategy::MedianOfMajority.validate().is_ok());
+    }
+
+    #[test]
+    fn price_sample_single_and_empty() {
+        assert!(PriceSample::from_prices(vec![], 1, 1).is_none());
+
+        let sample =
+            PriceSample::from_prices(vec![Amount::from(7u64)], 1, 0).expect("non empty prices");
+        assert_eq!(sample.min, sample.p90);
+        assert_eq!(sample.median, Amount::from(7u64));
+    }
+}
@@ -0,0 +1,415 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Repair of the records weakly held by the network.
+//!
+//! The holders of every record are counted with a GET at [`Quorum::All`], and the records held by
+//! fewer peers than a threshold are fetched and uploaded again. The upload is paid for when the
+//! close group of the record asks for it, which is the case after churn moved the record to peers
+//! that never stored it.
+
+use crate::Client;
+use crate::chunk::ChunkAddress;
+use crate::client::GetError;
+use crate::client::config::CHUNK_DOWNLOAD_BATCH_SIZE;
+use crate::client::data::DataAddress;
+use crate::client::data_types::graph::GraphError;
+use crate::client::files::archive_public::ArchiveAddress;
+use crate::client::payment::PaymentOption;
+use crate::networking::{NetworkError, Quorum};
+use crate::self_encryption::unwrap_data_map_chunk;
+use crate::utils::process_tasks_with_max_concurrency;
+use ant_evm::AttoTokens;
+use ant_protocol::{CLOSE_GROUP_SIZE, NetworkAddress};
+use bytes::Bytes;
+use self_encryption::DataMap;
+
+/// The default minimum number of holders of a record, a majority of its close group, below which
+/// the record is repaired.
+pub const DEFAULT_MIN_HOLDERS: usize = CLOSE_GROUP_SIZE / 2 + 1;
+
+/// What was done for a record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RepairOutcome {
+    /// The record is held by enough peers, nothing was uploaded
+    Healthy,
+    /// The record was uploaded again, for the given cost, which is zero for the free updates of
+    /// pointers and scratchpads
+    Repaired(AttoTokens),
+    /// The close group reported the record as already stored, so it was not uploaded again, and
+    /// is left to the replication between the nodes
+    AlreadyStored,
+    /// No peer holds the record anymore, it cannot be repaired
+    Lost,
+    /// The record could not be checked or uploaded again
+    Failed(String),
+}
+
+/// Repair result of a single record.
+#[derive(Debug, Clone)]
+pub struct RecordRepair {
+    pub address: NetworkAddress,
+    /// The number of peers found holding the record, before any repair
+    pub holders: usize,
+    pub outcome: RepairOutcome,
+}
+
+/// Report of the repair of a set of records.
+#[derive(Debug, Clone, Default)]
+pub struct RepairReport {
+    pub records: Vec<RecordRepair>,
+}
+
+impl RepairReport {
+    /// Number of records that were uploaded again.
+    pub fn repaired_count(&self) -> usize {
+        self.count(|outcome| matches!(outcome, RepairOutcome::Repaired(_)))
+    }
+
+    /// Number of weakly held records the close group reported as already stored, so not uploaded
+    /// again.
+    pub fn already_stored_count(&self) -> usize {
+        self.count(|outcome| matches!(outcome, RepairOutcome::AlreadyStored))
+    }
+
+    /// Number of records no peer holds anymore.
+    pub fn lost_count(&self) -> usize {
+        self.count(|outcome| matches!(outcome, RepairOutcome::Lost))
+    }
+
+    /// Number of records that could not be checked or uploaded again.
+    pub fn failed_count(&self) -> usize {
+        self.count(|outcome| matches!(outcome, RepairOutcome::Failed(_)))
+    }
+
+    /// Total cost of the uploads made to repair the records.
+    pub fn total_cost(&self) -> AttoTokens {
+        self.records
+            .iter()
+            .filter_map(|record| match record.outcome {
+                RepairOutcome::Repaired(cost) => Some(cost),
+                _ => None,
+            })
+            .fold(AttoTokens::zero(), |total, cost| {
+                total.checked_add(cost).unwrap_or(total)
+            })
+    }
+
+    /// Returns true if every record is now held by enough peers.
+    pub fn is_healthy(&self) -> bool {
+        self.already_stored_count() == 0 && self.lost_count() == 0 && self.failed_count() == 0
+    }
+
+    fn count(&self, filter: impl Fn(&RepairOutcome) -> bool) -> usize {
+        self.records
+            .iter()
+            .filter(|record| filter(&record.outcome))
+            .count()
+    }
+}
+
+impl Client {
+    /// Repair the given records: the ones held by fewer than `min_holders` peers are fetched and
+    /// uploaded again, paying for them with `payment_option` if needed.
+    ///
+    /// Only the addresses of the data types (chunks, graph entries, pointers and scratchpads) can
+    /// be repaired.
+    pub async fn repair(
+        &self,
+        addresses: &[NetworkAddress],
+        min_holders: usize,
+        payment_option: PaymentOption,
+    ) -> RepairReport {
+        let mut addresses = addresses.to_vec();
+        addresses.sort();
+        addresses.dedup();
+        info!(
+            "Repairing {} records held by fewer than {min_holders} peers",
+            addresses.len()
+        );
+
+        let tasks = addresses.into_iter().map(|address| {
+            let payment_option = payment_option.clone();
+            async move {
+                self.repair_record(address, min_holders, payment_option)
+                    .await
+            }
+        });
+        let records = process_tasks_with_max_concurrency(tasks, *CHUNK_DOWNLOAD_BATCH_SIZE).await;
+
+        let report = RepairReport { records };
+        info!(
+            "Repair done: {} repaired, {} already stored, {} lost, {} failed, for {}",
+            report.repaired_count(),
+            report.already_stored_count(),
+            report.lost_count(),
+            report.failed_count(),
+            report.total_cost()
+        );
+        report
+    }
+
+    /// Repair the chunks of public data: its data map and the chunks it refers to.
+    pub async fn repair_public_data(
+        &self,
+        addr: &DataAddress,
+        min_holders: usize,
+        payment_option: PaymentOption,
+    ) -> Result<RepairReport, GetError> {
+        let addresses = self.public_data_chunk_addresses(addr).await?;
+        Ok(self.repair(&addresses, min_holders, payment_option).await)
+    }
+
+    /// Repair a public archive: the chunks of the archive itself and of all its files.
+    pub async fn repair_public_archive(
+        &self,
+        addr: &ArchiveAddress,
+        min_holders: usize,
+        payment_option: PaymentOption,
+    ) -> Result<RepairReport, GetError> {
+        let archive = self.archive_get_public(addr).await?;
+        let mut addresses = self.public_data_chunk_addresses(addr).await?;
+        for (_path, data_addr, _metadata) in archive.iter() {
+            addresses.extend(self.public_data_chunk_addresses(data_addr).await?);
+        }
+        Ok(self.repair(&addresses, min_holders, payment_option).await)
+    }
+
+    /// The addresses of the data map chunk of public data and of the chunks it refers to, at
+    /// every level of the data map.
+    async fn public_data_chunk_addresses(
+        &self,
+        addr: &DataAddress,
+    ) -> Result<Vec<NetworkAddress>, GetError> {
+        let data_map_address = ChunkAddress::new(*addr.xorname());
+        let data_map_chunk = self.chunk_get(&data_map_address).await?;
+        let mut addresses = vec![NetworkAddress::from(data_map_address)];
+
+        let mut data_map = top_data_map(data_map_chunk.value())?;
+        loop {
+            addresses.extend(
+                data_map
+                    .infos()
+                    .iter()
+                    .map(|info| NetworkAddress::from(ChunkAddress::new(info.dst_hash))),
+            );
+            if !data_map.is_child() {
+                return Ok(addresses);
+            }
+            data_map.child = None;
+            let data_map_bytes = self.fetch_from_data_map(&data_map).await?;
+            data_map = Self::deserialize_data_map(&data_map_bytes)?;
+        }
+    }
+
+    async fn repair_record(
+        &self,
+        address: NetworkAddress,
+        min_holders: usize,
+        payment_option: PaymentOption,
+    ) -> RecordRepair {
+        let holders = match self.count_holders(address.clone()).await {
+            Ok(holders) => holders,
+            Err(err) => {
+                warn!("Failed to count the holders of {address:?}: {err}");
+                return RecordRepair {
+                    address,
+                    holders: 0,
+                    outcome: RepairOutcome::Failed(err.to_string()),
+                };
+            }
+        };
+
+        let outcome = if holders >= min_holders {
+            RepairOutcome::Healthy
+        } else if holders == 0 {
+            warn!("No peer holds {address:?} anymore, it cannot be repaired");
+            RepairOutcome::Lost
+        } else {
+            debug!("Record {address:?} is held by {holders} peers only, uploading it again");
+            match self.reupload(&address, payment_option).await {
+                Ok(Some(cost)) => RepairOutcome::Repaired(cost),
+                Ok(None) => {
+                    warn!("Record {address:?} is reported as already stored, it was not uploaded");
+                    RepairOutcome::AlreadyStored
+                }
+                Err(err) => {
+                    warn!("Failed to upload {address:?} again: {err}");
+                    RepairOutcome::Failed(err)
+                }
+            }
+        };
+
+        RecordRepair {
+            address,
+            holders,
+            outcome,
+        }
+    }
+
+    /// The number of peers that returned the record to a GET at [`Quorum::All`].
+    async fn count_holders(&self, address: NetworkAddress) -> Result<usize, NetworkError> {
+        match self
+            .network
+            .get_record_and_holders(address, Quorum::All)
+            .await
+        {
+            Ok((_record, holders)) | Err(NetworkError::GetRecordTimeout(holders)) => {
+                Ok(holders.len())
+            }
+            Err(NetworkError::GetRecordQuorumFailed { holders, .. })
+            | Err(NetworkError::SplitRecord(holders)) => Ok(holders.len()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Fetch the record at the address and upload it again, returning the cost of the upload, or
+    /// `None` if the record was not uploaded as its close group reported it as already stored.
+    async fn reupload(
+        &self,
+        address: &NetworkAddress,
+        payment_option: PaymentOption,
+    ) -> Result<Option<AttoTokens>, String> {
+        let cost = match address {
+            NetworkAddress::ChunkAddress(addr) => {
+                let chunk = self.chunk_get(addr).await.map_err(|err| err.to_string())?;
+                let (cost, _) = self
+                    .chunk_put(&chunk, payment_option)
+                    .await
+                    .map_err(|err| err.to_string())?;
+                // a chunk is only uploaded along with its payment, none being made when the close
+                // group reports the chunk as already stored
+                if cost.is_zero() {
+                    return Ok(None);
+                }
+                cost
+            }
+            NetworkAddress::GraphEntryAddress(addr) => {
+                let entry = self
+                    .graph_entry_get(addr)
+                    .await
+                    .map_err(|err| err.to_string())?;
+                match self.graph_entry_put(entry, payment_option).await {
+                    Ok((cost, _)) => cost,
+                    Err(GraphError::AlreadyExists(_)) => return Ok(None),
+                    Err(err) => return Err(err.to_string()),
+                }
+            }
+            NetworkAddress::PointerAddress(addr) => {
+                let pointer = self
+                    .pointer_get(addr)
+                    .await
+                    .map_err(|err| err.to_string())?;
+                self.pointer_put(pointer, payment_option)
+                    .await
+                    .map_err(|err| err.to_string())?
+                    .0
+            }
+            NetworkAddress::ScratchpadAddress(addr) => {
+                let scratchpad = self
+                    .scratchpad_get(addr)
+                    .await
+                    .map_err(|err| err.to_string())?;
+                self.scratchpad_put(scratchpad, payment_option)
+                    .await
+                    .map_err(|err| err.to_string())?
+                    .0
+            }
+            NetworkAddress::PeerId(_) | NetworkAddress::RecordKey(_) => {
+                return Err(format!(
+                    "{address:?} is not the address of a data type, it cannot be uploaded again"
+                ));
+            }
+        };
+        Ok(Some(cost))
+    }
+}
+
+/// The top level data map of a data map chunk, once unwrapped from the compression of the data
+/// if any.
+fn top_data_map(data_map_chunk_bytes: &Bytes) -> Result<DataMap, GetError> {
+    let data_map_bytes = match unwrap_data_map_chunk(data_map_chunk_bytes) {
+        Some((_compression, data_map_bytes)) => data_map_bytes,
+        None => data_map_chunk_bytes.clone(),
+    };
+    Client::deserialize_data_map(&data_map_bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use xor_name::XorName;
+
+    fn record(outcome: RepairOutcome) -> RecordRepair {
+        RecordRepair {
+            address: NetworkAddress::from(XorName::random(&mut rand::thread_rng())),
+            holders: 1,
+            outcome,
+        }
+    }
+
+    #[test]
+    fn test_repair_report_counts() {
+        let report = RepairReport {
+            records: vec![
+                record(RepairOutcome::Healthy),
+                record(RepairOutcome::Repaired(AttoTokens::from_u64(10))),
+                record(RepairOutcome::Repaired(AttoTokens::from_u64(5))),
+                record(RepairOutcome::Lost),
+            ],
+        };
+        assert_eq!(report.repaired_count(), 2);
+        assert_eq!(report.already_stored_count(), 0);
+        assert_eq!(report.lost_count(), 1);
+        assert_eq!(report.failed_count(), 0);
+        assert_eq!(report.total_cost(), AttoTokens::from_u64(15));
+        assert!(!report.is_healthy());
+
+        let healthy = RepairReport {
+            records: vec![record(RepairOutcome::Healthy)],
+        };
+        assert!(healthy.is_healthy());
+        assert_eq!(healthy.total_cost(), AttoTokens::zero());
+
+        // A weakly held record that was not uploaded again is not repaired
+        let not_uploaded = RepairReport {
+            records: vec![
+                record(RepairOutcome::Healthy),
+                record(RepairOutcome::AlreadyStored),
+            ],
+        };
+        assert_eq!(not_uploaded.repaired_count(), 0);
+        assert_eq!(not_uploaded.already_stored_count(), 1);
+        assert_eq!(not_uploaded.total_cost(), AttoTokens::zero());
+        assert!(!not_uploaded.is_healthy());
+    }
+
+    #[test]
+    fn test_top_data_map_of_compressed_data() {
+        use crate::self_encryption::{Compression, compress, encrypt, wrap_data_map_chunk};
+
+        let data = Bytes::from("data to repair ".repeat(100_000));
+        let (compressed, compression) =
+            compress(data, Compression::Zstd(3)).expect("Failed to compress data");
+        assert_eq!(compression, Compression::Zstd(3));
+        let (data_map_chunk, chunks) = encrypt(compressed).expect("Failed to encrypt data");
+        let expected = top_data_map(&data_map_chunk.value).expect("Failed to read data map");
+
+        let wrapped = wrap_data_map_chunk(data_map_chunk, compression)
+            .expect("Failed to wrap the data map chunk");
+        assert!(Client::deserialize_data_map(&wrapped.value).is_err());
+        let data_map = top_data_map(&wrapped.value).expect("Failed to read wrapped data map");
+        assert_eq!(data_map, expected);
+        assert!(
+            data_map
+                .infos()
+                .iter()
+                .all(|info| chunks.iter().any(|chunk| *chunk.name() == info.dst_hash))
+        );
+    }
+}
@@ -0,0 +1,277 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use super::NetworkStore;
+use crate::client::data::DataAddress;
+use crate::client::payment::PaymentOption;
+use crate::client::pointer::{PointerError, PointerTarget};
+use crate::client::register::{RegisterAddress, RegisterError, RegisterValue};
+use crate::client::{GetError, PutError};
+use crate::{AttoTokens, Bytes, Chunk, ChunkAddress, Pointer, PointerAddress, SecretKey};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex, MutexGuard};
+use xor_name::XorName;
+
+/// An in-memory [`NetworkStore`], to unit test applications without a network.
+///
+/// Nothing is paid for: the payment options are ignored and every cost is zero. Clones share the
+/// same records, so a clone handed to the code under test can be inspected afterwards.
+#[derive(Clone, Debug, Default)]
+pub struct MockStore {
+    records: Arc<Mutex<Records>>,
+}
+
+#[derive(Debug, Default)]
+struct Records {
+    chunks: HashMap<ChunkAddress, Chunk>,
+    data: HashMap<DataAddress, Bytes>,
+    pointers: HashMap<PointerAddress, Pointer>,
+    registers: HashMap<RegisterAddress, RegisterValue>,
+}
+
+impl MockStore {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of chunks stored.
+    pub fn chunk_count(&self) -> usize {
+        self.records().chunks.len()
+    }
+
+    /// Remove every record from the store.
+    pub fn clear(&self) {
+        *self.records() = Records::default();
+    }
+
+    fn records(&self) -> MutexGuard<'_, Records> {
+        // A test panicking while holding the lock leaves the records consistent
+        self.records
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl NetworkStore for MockStore {
+    fn chunk_get(
+        &self,
+        addr: &ChunkAddress,
+    ) -> impl Future<Output = Result<Chunk, GetError>> + Send {
+        let chunk = self
+            .records()
+            .chunks
+            .get(addr)
+            .cloned()
+            .ok_or(GetError::RecordNotFound);
+        async move { chunk }
+    }
+
+    fn chunk_put(
+        &self,
+        chunk: &Chunk,
+        _payment_option: PaymentOption,
+    ) -> impl Future<Output = Result<(AttoTokens, ChunkAddress), PutError>> + Send {
+        let addr = *chunk.address();
+        let _ = self.records().chunks.insert(addr, chunk.clone());
+        async move { Ok((AttoTokens::zero(), addr)) }
+    }
+
+    fn data_get_public(
+        &self,
+        addr: &DataAddress,
+    ) -> impl Future<Output = Result<Bytes, GetError>> + Send {
+        let data = self
+            .records()
+            .data
+            .get(addr)
+            .cloned()
+            .ok_or(GetError::RecordNotFound);
+        async move { data }
+    }
+
+    fn data_put_public(
+        &self,
+        data: Bytes,
+        _payment_option: PaymentOption,
+    ) -> impl Future<Output = Result<(AttoTokens, DataAddress), PutError>> + Send {
+        let addr = DataAddress::new(XorName::from_content(&data));
+        let _ = self.records().data.insert(addr, data);
+        async move { Ok((AttoTokens::zero(), addr)) }
+    }
+
+    fn pointer_get(
+        &self,
+        address: &PointerAddress,
+    ) -> impl Future<Output = Result<Pointer, PointerError>> + Send {
+        let pointer = self
+            .records()
+            .pointers
+            .get(address)
+            .cloned()
+            .ok_or(PointerError::GetError(GetError::RecordNotFound));
+        async move { pointer }
+    }
+
+    fn pointer_create(
+        &self,
+        owner: &SecretKey,
+        target: PointerTarget,
+        _payment_option: PaymentOption,
+    ) -> impl Future<Output = Result<(AttoTokens, PointerAddress), PointerError>> + Send {
+        let address = PointerAddress::new(owner.public_key());
+        let mut records = self.records();
+        let result = if records.pointers.contains_key(&address) {
+            Err(PointerError::PointerAlreadyExists(address))
+        } else {
+            let _ = records
+                .pointers
+                .insert(address, Pointer::new(owner, 0, target));
+            Ok((AttoTokens::zero(), address))
+        };
+        async move { result }
+    }
+
+    fn pointer_update(
+        &self,
+        owner: &SecretKey,
+        target: PointerTarget,
+    ) -> impl Future<Output = Result<(), PointerError>> + Send {
+        let address = PointerAddress::new(owner.public_key());
+        let result = match self.records().pointers.get_mut(&address) {
+            Some(pointer) => {
+                *pointer = Pointer::new(owner, pointer.counter() + 1, target);
+                Ok(())
+            }
+            None => Err(PointerError::CannotUpdateNewPointer),
+        };
+        async move { result }
+    }
+
+    fn register_get(
+        &self,
+        addr: &RegisterAddress,
+    ) -> impl Future<Output = Result<RegisterValue, RegisterError>> + Send {
+        let value = self
+            .records()
+            .registers
+            .get(addr)
+            .copied()
+            .ok_or(RegisterError::PointerError(PointerError::GetError(
+                GetError::RecordNotFound,
+            )));
+        async move { value }
+    }
+
+    fn register_create(
+        &self,
+        owner: &SecretKey,
+        initial_value: RegisterValue,
+        _payment_option: PaymentOption,
+    ) -> impl Future<Output = Result<(AttoTokens, RegisterAddress), RegisterError>> + Send {
+        let addr = RegisterAddress::new(owner.public_key());
+        let mut records = self.records();
+        let result = if records.registers.contains_key(&addr) {
+            Err(RegisterError::PointerError(
+                PointerError::PointerAlreadyExists(addr.to_underlying_head_pointer()),
+            ))
+        } else {
+            let _ = records.registers.insert(addr, initial_value);
+            Ok((AttoTokens::zero(), addr))
+        };
+        async move { result }
+    }
+
+    fn register_update(
+        &self,
+        owner: &SecretKey,
+        new_value: RegisterValue,
+        _payment_option: PaymentOption,
+    ) -> impl Future<Output = Result<AttoTokens, RegisterError>> + Send {
+        let addr = RegisterAddress::new(owner.public_key());
+        let result = match self.records().registers.get_mut(&addr) {
+            Some(value) => {
+                *value = new_value;
+                Ok(AttoTokens::zero())
+            }
+            None => Err(RegisterError::CannotUpdateNewRegister),
+        };
+        async move { result }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::payment::Receipt;
+
+    fn payment() -> PaymentOption {
+        PaymentOption::Receipt(Receipt::default())
+    }
+
+    #[tokio::test]
+    async fn data_and_chunks_should_round_trip() {
+        let store = MockStore::new();
+        let data = Bytes::from("hello");
+        let (cost, addr) = store
+            .data_put_public(data.clone(), payment())
+            .await
+            .unwrap();
+        assert_eq!(cost, AttoTokens::zero());
+        assert_eq!(store.data_get_public(&addr).await.unwrap(), data);
+
+        let chunk = Chunk::new(Bytes::from("chunk"));
+        let (_, chunk_addr) = store.chunk_put(&chunk, payment()).await.unwrap();
+        // Clones share the records
+        assert_eq!(store.clone().chunk_get(&chunk_addr).await.unwrap(), chunk);
+        assert_eq!(store.chunk_count(), 1);
+
+        store.clear();
+        assert!(matches!(
+            store.chunk_get(&chunk_addr).await,
+            Err(GetError::RecordNotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn pointers_and_registers_should_behave_as_on_the_network() {
+        let store = MockStore::new();
+        let owner = SecretKey::random();
+        let target = PointerTarget::ChunkAddress(ChunkAddress::new(XorName::random(
+            &mut rand::thread_rng(),
+        )));
+
+        assert!(matches!(
+            store.pointer_update(&owner, target.clone()).await,
+            Err(PointerError::CannotUpdateNewPointer)
+        ));
+        let (_, address) = store
+            .pointer_create(&owner, target.clone(), payment())
+            .await
+            .unwrap();
+        assert!(matches!(
+            store
+                .pointer_create(&owner, target.clone(), payment())
+                .await,
+            Err(PointerError::PointerAlreadyExists(_))
+        ));
+        store.pointer_update(&owner, target).await.unwrap();
+        assert_eq!(store.pointer_get(&address).await.unwrap().counter(), 1);
+
+        let (_, addr) = store
+            .register_create(&owner, [1; 32], payment())
+            .await
+            .unwrap();
+        let _ = store
+            .register_update(&owner, [2; 32], payment())
+            .await
+            .unwrap();
+        assert_eq!(store.register_get(&addr).await.unwrap(), [2; 32]);
+    }
+}
@@ -0,0 +1,196 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! The storage operations of the [`Client`] as a trait, so that applications can be written
+//! against [`NetworkStore`] and unit tested without a live network.
+//!
+//! With the `test-utils` feature, [`MockStore`] implements the trait in memory:
+//!
+//! ```ignore
+//! use autonomi::client::store::{MockStore, NetworkStore};
+//!
+//! async fn publish(store: &impl NetworkStore, data: Bytes) -> Result<DataAddress, PutError> {
+//!     let (_cost, addr) = store.data_put_public(data, payment_option).await?;
+//!     Ok(addr)
+//! }
+//!
+//! let store = MockStore::new();
+//! let addr = publish(&store, Bytes::from("hello")).await?;
+//! assert_eq!(store.data_get_public(&addr).await?, Bytes::from("hello"));
+//! ```
+
+// Also built for the unit tests, so that the tests of the mock run without the feature
+#[cfg(any(test, feature = "test-utils"))]
+mod mock;
+
+#[cfg(any(test, feature = "test-utils"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "test-utils")))]
+pub use mock::MockStore;
+
+use crate::client::data::DataAddress;
+use crate::client::payment::PaymentOption;
+use crate::client::pointer::{PointerError, PointerTarget};
+use crate::client::register::{RegisterAddress, RegisterError, RegisterValue};
+use crate::client::{Client, GetError, PutError};
+use crate::{AttoTokens, Bytes, Chunk, ChunkAddress, Pointer, PointerAddress, SecretKey};
+use std::future::Future;
+
+/// Storage operations on the network, implemented by [`Client`].
+///
+/// The methods behave as the [`Client`] methods of the same name.
+pub trait NetworkStore {
+    /// Fetch a chunk, see [`Client::chunk_get`].
+    fn chunk_get(
+        &self,
+        addr: &ChunkAddress,
+    ) -> impl Future<Output = Result<Chunk, GetError>> + Send;
+
+    /// Store a chunk, see [`Client::chunk_put`].
+    fn chunk_put(
+        &self,
+        chunk: &Chunk,
+        payment_option: PaymentOption,
+    ) -> impl Future<Output = Result<(AttoTokens, ChunkAddress), PutError>> + Send;
+
+    /// Fetch public data, see [`Client::data_get_public`].
+    fn data_get_public(
+        &self,
+        addr: &DataAddress,
+    ) -> impl Future<Output = Result<Bytes, GetError>> + Send;
+
+    /// Store public data, see [`Client::data_put_public`].
+    fn data_put_public(
+        &self,
+        data: Bytes,
+        payment_option: PaymentOption,
+    ) -> impl Future<Output = Result<(AttoTokens, DataAddress), PutError>> + Send;
+
+    /// Fetch a pointer, see [`Client::pointer_get`].
+    fn pointer_get(
+        &self,
+        address: &PointerAddress,
+    ) -> impl Future<Output = Result<Pointer, PointerError>> + Send;
+
+    /// Create a pointer, see [`Client::pointer_create`].
+    fn pointer_create(
+        &self,
+        owner: &SecretKey,
+        target: PointerTarget,
+        payment_option: PaymentOption,
+    ) -> impl Future<Output = Result<(AttoTokens, PointerAddress), PointerError>> + Send;
+
+    /// Update the target of an existing pointer, see [`Client::pointer_update`].
+    fn pointer_update(
+        &self,
+        owner: &SecretKey,
+        target: PointerTarget,
+    ) -> impl Future<Output = Result<(), PointerError>> + Send;
+
+    /// Fetch the value of a register, see [`Client::register_get`].
+    fn register_get(
+        &self,
+        addr: &RegisterAddress,
+    ) -> impl Future<Output = Result<RegisterValue, RegisterError>> + Send;
+
+    /// Create a register, see [`Client::register_create`].
+    fn register_create(
+        &self,
+        owner: &SecretKey,
+        initial_value: RegisterValue,
+        payment_option: PaymentOption,
+    ) -> impl Future<Output = Result<(AttoTokens, RegisterAddress), RegisterError>> + Send;
+
+    /// Update the value of an existing register, see [`Client::register_update`].
+    fn register_update(
+        &self,
+        owner: &SecretKey,
+        new_value: RegisterValue,
+        payment_option: PaymentOption,
+    ) -> impl Future<Output = Result<AttoTokens, RegisterError>> + Send;
+}
+
+impl NetworkStore for Client {
+    fn chunk_get(
+        &self,
+        addr: &ChunkAddress,
+    ) -> impl Future<Output = Result<Chunk, GetError>> + Send {
+        Client::chunk_get(self, addr)
+    }
+
+    fn chunk_put(
+        &self,
+        chunk: &Chunk,
+        payment_option: PaymentOption,
+    ) -> impl Future<Output = Result<(AttoTokens, ChunkAddress), PutError>> + Send {
+        Client::chunk_put(self, chunk, payment_option)
+    }
+
+    fn data_get_public(
+        &self,
+        addr: &DataAddress,
+    ) -> impl Future<Output = Result<Bytes, GetError>> + Send {
+        Client::data_get_public(self, addr)
+    }
+
+    fn data_put_public(
+        &self,
+        data: Bytes,
+        payment_option: PaymentOption,
+    ) -> impl Future<Output = Result<(AttoTokens, DataAddress), PutError>> + Send {
+        Client::data_put_public(self, data, payment_option)
+    }
+
+    fn pointer_get(
+        &self,
+        address: &PointerAddress,
+    ) -> impl Future<Output = Result<Pointer, PointerError>> + Send {
+        Client::pointer_get(self, address)
+    }
+
+    fn pointer_create(
+        &self,
+        owner: &SecretKey,
+        target: PointerTarget,
+        payment_option: PaymentOption,
+    ) -> impl Future<Output = Result<(AttoTokens, PointerAddress), PointerError>> + Send {
+        Client::pointer_create(self, owner, target, payment_option)
+    }
+
+    fn pointer_update(
+        &self,
+        owner: &SecretKey,
+        target: PointerTarget,
+    ) -> impl Future<Output = Result<(), PointerError>> + Send {
+        Client::pointer_update(self, owner, target)
+    }
+
+    fn register_get(
+        &self,
+        addr: &RegisterAddress,
+    ) -> impl Future<Output = Result<RegisterValue, RegisterError>> + Send {
+        Client::register_get(self, addr)
+    }
+
+    fn register_create(
+        &self,
+        owner: &SecretKey,
+        initial_value: RegisterValue,
+        payment_option: PaymentOption,
+    ) -> impl Future<Output = Result<(AttoTokens, RegisterAddress), RegisterError>> + Send {
+        Client::register_create(self, owner, initial_value, payment_option)
+    }
+
+    fn register_update(
+        &self,
+        owner: &SecretKey,
+        new_value: RegisterValue,
+        payment_option: PaymentOption,
+    ) -> impl Future<Output = Result<AttoTokens, RegisterError>> + Send {
+        Client::register_update(self, owner, new_value, payment_option)
+    }
+}
@@ -0,0 +1,118 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Planning of the batches of bulk uploads.
+//!
+//! The chunks of a batch are uploaded in order, `CHUNK_UPLOAD_BATCH_SIZE` at a time. In the
+//! order of the self-encryption, their addresses are random, so the chunks in flight together are
+//! stored by close groups all over the network, and each upload dials peers of its own. Ordered
+//! by address, the chunks in flight together fall in a narrow range of the address space, where
+//! the close groups of neighbouring chunks share most of their peers: the connections dialed for
+//! a chunk are reused by the next ones instead of being closed idle and dialed again later in the
+//! batch.
+//!
+//! The plan reports this order grouped into neighbourhoods of chunks sharing the leading
+//! [`NEIGHBOURHOOD_PREFIX_BITS`] bits of their addresses, so that the composition of the batch
+//! can be seen in the progress events.
+
+use xor_name::XorName;
+
+/// Number of leading address bits shared by the chunks of a neighbourhood. Only the reporting
+/// of the plan depends on it, the upload order is the order of the addresses.
+pub const NEIGHBOURHOOD_PREFIX_BITS: u32 = 12;
+
+/// Chunks of a batch stored by overlapping close groups.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Neighbourhood {
+    /// The leading [`NEIGHBOURHOOD_PREFIX_BITS`] bits shared by the addresses of the chunks
+    pub prefix: u16,
+    /// Addresses of the chunks, in upload order
+    pub chunks: Vec<XorName>,
+}
+
+/// The composition of an upload batch, in upload order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UploadBatchPlan {
+    pub neighbourhoods: Vec<Neighbourhood>,
+}
+
+impl UploadBatchPlan {
+    /// Plan the upload of a batch of chunks.
+    pub fn new(addrs: impl IntoIterator<Item = XorName>) -> Self {
+        let mut addrs: Vec<_> = addrs.into_iter().collect();
+        addrs.sort();
+        addrs.dedup();
+
+        let mut neighbourhoods: Vec<Neighbourhood> = vec![];
+        for addr in addrs {
+            let prefix = neighbourhood_prefix(&addr);
+            match neighbourhoods.last_mut() {
+                Some(neighbourhood) if neighbourhood.prefix == prefix => {
+                    neighbourhood.chunks.push(addr)
+                }
+                _ => neighbourhoods.push(Neighbourhood {
+                    prefix,
+                    chunks: vec![addr],
+                }),
+            }
+        }
+        Self { neighbourhoods }
+    }
+
+    /// Number of chunks in the batch.
+    pub fn chunk_count(&self) -> usize {
+        self.neighbourhoods.iter().map(|n| n.chunks.len()).sum()
+    }
+
+    /// Addresses of the chunks of the batch, in upload order.
+    pub fn upload_order(&self) -> impl Iterator<Item = &XorName> {
+        self.neighbourhoods.iter().flat_map(|n| n.chunks.iter())
+    }
+}
+
+fn neighbourhood_prefix(addr: &XorName) -> u16 {
+    u16::from_be_bytes([addr.0[0], addr.0[1]]) >> (16 - NEIGHBOURHOOD_PREFIX_BITS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addr(first_bytes: [u8; 2], last_byte: u8) -> XorName {
+        let mut addr = [0u8; 32];
+        addr[0] = first_bytes[0];
+        addr[1] = first_bytes[1];
+        addr[31] = last_byte;
+        XorName(addr)
+    }
+
+    #[test]
+    fn test_plan_groups_and_orders_by_neighbourhood() {
+        let plan = UploadBatchPlan::new([
+            addr([0xff, 0x00], 1),
+            addr([0x12, 0x30], 1),
+            addr([0x12, 0x3f], 2),
+            addr([0xff, 0x0f], 2),
+            addr([0x12, 0x40], 3),
+            addr([0x12, 0x30], 1),
+        ]);
+
+        assert_eq!(plan.chunk_count(), 5);
+        let composition: Vec<_> = plan
+            .neighbourhoods
+            .iter()
+            .map(|n| (n.prefix, n.chunks.len()))
+            .collect();
+        assert_eq!(composition, vec![(0x123, 2), (0x124, 1), (0xff0, 2)]);
+
+        let order: Vec<_> = plan.upload_order().copied().collect();
+        let mut sorted = order.clone();
+        sorted.sort();
+        assert_eq!(order, sorted);
+    }
+}
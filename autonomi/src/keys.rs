@@ -0,0 +1,346 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Deterministic key derivation from a single mnemonic.
+//!
+//! A [`MasterKey`] is created from a BIP39 mnemonic phrase. Every key needed to own data on the
+//! network (registers, vaults, scratchpads, ...) is then derived from it along a well-defined,
+//! hardened SLIP-10 style path, so that backing up the mnemonic is enough to recover all of them.
+//!
+//! Paths have the form `m/<purpose>'/<key type>'/<index>'` where `purpose` is [`AUTONOMI_PURPOSE`]
+//! and `key type` is one of the [`KeyType`] values.
+//!
+//! ```
+//! use autonomi::keys::MasterKey;
+//!
+//! let master_key = MasterKey::generate();
+//! let phrase = master_key.export_mnemonic();
+//!
+//! // The same mnemonic always gives back the same keys
+//! let restored = MasterKey::import_mnemonic(&phrase, "").unwrap();
+//! assert_eq!(master_key.register_key(0).unwrap(), restored.register_key(0).unwrap());
+//! ```
+
+use crate::vault::key::{blst_to_blsttc, derive_secret_key_from_seed};
+use bip39::Mnemonic;
+use bls::SecretKey;
+use hmac::{Hmac, Mac};
+use libp2p::identity::ed25519;
+use rand::RngCore;
+use sha2::Sha512;
+use std::fmt;
+use std::str::FromStr;
+
+type HmacSha512 = Hmac<Sha512>;
+
+/// Purpose level of all Autonomi derivation paths.
+pub const AUTONOMI_PURPOSE: u32 = 7474;
+
+/// Offset of hardened indexes, only hardened derivation is supported.
+const HARDENED_OFFSET: u32 = 0x8000_0000;
+
+/// HMAC key of the SLIP-10 master node for ed25519 keys.
+const ED25519_CURVE_SEED: &[u8] = b"ed25519 seed";
+
+/// HMAC key of the master node for BLS keys, which SLIP-10 does not define.
+const BLS_CURVE_SEED: &[u8] = b"Autonomi BLS12-381 seed";
+
+/// Entropy of a generated mnemonic, giving a 24 words phrase.
+const MNEMONIC_ENTROPY_SIZE: usize = 32;
+
+/// Errors that can occur when deriving, importing or exporting keys
+#[derive(Debug, thiserror::Error)]
+pub enum KeyError {
+    #[error("Invalid mnemonic: {0}")]
+    InvalidMnemonic(#[from] bip39::Error),
+    #[error("Invalid derivation path: {0}")]
+    InvalidPath(String),
+    #[error("Index {0} is too large, indexes must be below 2^31")]
+    IndexTooLarge(u32),
+    #[error("Failed to derive key: {0}")]
+    DerivationFailed(String),
+    #[error("Failed to decode key: {0}")]
+    InvalidKey(String),
+}
+
+/// The kind of data a derived key is used for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyType {
+    /// Owner keys of registers
+    Register,
+    /// Vault keys, one per account
+    Vault,
+    /// Owner keys of scratchpads
+    Scratchpad,
+    /// Owner keys of pointers
+    Pointer,
+    /// Owner keys of graph entries
+    GraphEntry,
+}
+
+impl KeyType {
+    /// The index of this key type in derivation paths
+    pub fn index(&self) -> u32 {
+        match self {
+            KeyType::Register => 0,
+            KeyType::Vault => 1,
+            KeyType::Scratchpad => 2,
+            KeyType::Pointer => 3,
+            KeyType::GraphEntry => 4,
+        }
+    }
+}
+
+/// A hardened derivation path such as `m/7474'/0'/3'`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DerivationPath(Vec<u32>);
+
+impl DerivationPath {
+    /// The path of the key of the given type at the given index: `m/7474'/<key type>'/<index>'`
+    pub fn for_key(key_type: KeyType, index: u32) -> Result<Self, KeyError> {
+        Self::from_indexes(vec![AUTONOMI_PURPOSE, key_type.index(), index])
+    }
+
+    /// Create a path from its (non hardened) indexes, they will all be hardened.
+    pub fn from_indexes(indexes: Vec<u32>) -> Result<Self, KeyError> {
+        if let Some(index) = indexes.iter().find(|index| **index >= HARDENED_OFFSET) {
+            return Err(KeyError::IndexTooLarge(*index));
+        }
+        Ok(Self(indexes))
+    }
+
+    /// The (non hardened) indexes of this path
+    pub fn indexes(&self) -> &[u32] {
+        &self.0
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "m")?;
+        for index in &self.0 {
+            write!(f, "/{index}'")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = KeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.trim().split('/');
+        if parts.next() != Some("m") {
+            return Err(KeyError::InvalidPath(format!("{s:?} must start with 'm'")));
+        }
+
+        let indexes = parts
+            .map(|part| {
+                let index = part
+                    .strip_suffix('\'')
+                    .or_else(|| part.strip_suffix('h'))
+                    .ok_or_else(|| {
+                        KeyError::InvalidPath(format!(
+                            "{s:?}: only hardened indexes are supported, got {part:?}"
+                        ))
+                    })?;
+                index
+                    .parse::<u32>()
+                    .map_err(|e| KeyError::InvalidPath(format!("{s:?}: {part:?} {e}")))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Self::from_indexes(indexes)
+    }
+}
+
+/// Root of all the keys of a user, created from a BIP39 mnemonic.
+#[derive(Clone)]
+pub struct MasterKey {
+    mnemonic: Mnemonic,
+    seed: [u8; 64],
+}
+
+impl fmt::Debug for MasterKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Never print the mnemonic or the seed
+        write!(f, "MasterKey(..)")
+    }
+}
+
+impl MasterKey {
+    /// Generate a new master key from a random 24 words mnemonic.
+    pub fn generate() -> Self {
+        let mut entropy = [0u8; MNEMONIC_ENTROPY_SIZE];
+        rand::thread_rng().fill_bytes(&mut entropy);
+        let mnemonic =
+            Mnemonic::from_entropy(&entropy).expect("32 bytes is a valid mnemonic entropy size");
+        Self::from_mnemonic(mnemonic, "")
+    }
+
+    /// Create the master key from a mnemonic and an optional passphrase (use `""` for none).
+    pub fn from_mnemonic(mnemonic: Mnemonic, passphrase: &str) -> Self {
+        let seed = mnemonic.to_seed(passphrase);
+        Self { mnemonic, seed }
+    }
+
+    /// Import a master key from its mnemonic phrase and passphrase (use `""` for none).
+    pub fn import_mnemonic(phrase: &str, passphrase: &str) -> Result<Self, KeyError> {
+        let mnemonic = Mnemonic::parse(phrase)?;
+        Ok(Self::from_mnemonic(mnemonic, passphrase))
+    }
+
+    /// Export the mnemonic phrase. Together with the passphrase, this is all that is needed to
+    /// recover every derived key, so it must be kept secret.
+    pub fn export_mnemonic(&self) -> String {
+        self.mnemonic.to_string()
+    }
+
+    /// Derive the BLS secret key at the given path.
+    pub fn derive_bls(&self, path: &DerivationPath) -> Result<SecretKey, KeyError> {
+        let (key, _chain_code) = derive_hardened(BLS_CURVE_SEED, &self.seed, path);
+        let blst_key = derive_secret_key_from_seed(&key)
+            .map_err(|e| KeyError::DerivationFailed(e.to_string()))?;
+        blst_to_blsttc(&blst_key).map_err(|e| KeyError::DerivationFailed(e.to_string()))
+    }
+
+    /// Derive the ed25519 keypair at the given path, following SLIP-10.
+    pub fn derive_ed25519(&self, path: &DerivationPath) -> Result<ed25519::Keypair, KeyError> {
+        let (mut key, _chain_code) = derive_hardened(ED25519_CURVE_SEED, &self.seed, path);
+        let secret = ed25519::SecretKey::try_from_bytes(&mut key)
+            .map_err(|e| KeyError::DerivationFailed(e.to_string()))?;
+        Ok(ed25519::Keypair::from(secret))
+    }
+
+    /// Derive the BLS secret key of the given type at the given index.
+    pub fn derive_key(&self, key_type: KeyType, index: u32) -> Result<SecretKey, KeyError> {
+        self.derive_bls(&DerivationPath::for_key(key_type, index)?)
+    }
+
+    /// The owner key of the register at the given index.
+    pub fn register_key(&self, index: u32) -> Result<SecretKey, KeyError> {
+        self.derive_key(KeyType::Register, index)
+    }
+
+    /// The vault key of the given account.
+    pub fn vault_key(&self, account: u32) -> Result<SecretKey, KeyError> {
+        self.derive_key(KeyType::Vault, account)
+    }
+
+    /// The owner key of the scratchpad at the given index.
+    pub fn scratchpad_key(&self, index: u32) -> Result<SecretKey, KeyError> {
+        self.derive_key(KeyType::Scratchpad, index)
+    }
+}
+
+/// Export a derived BLS secret key as hex.
+pub fn export_secret_key(key: &SecretKey) -> String {
+    key.to_hex()
+}
+
+/// Import a BLS secret key exported with [`export_secret_key`].
+pub fn import_secret_key(hex: &str) -> Result<SecretKey, KeyError> {
+    SecretKey::from_hex(hex.trim()).map_err(|e| KeyError::InvalidKey(e.to_string()))
+}
+
+/// SLIP-10 hardened derivation, returning the key material and chain code at the end of the path.
+fn derive_hardened(curve_seed: &[u8], seed: &[u8], path: &DerivationPath) -> ([u8; 32], [u8; 32]) {
+    let (mut key, mut chain_code) = hmac_split(curve_seed, &[seed]);
+
+    for index in path.indexes() {
+        let hardened_index = (index | HARDENED_OFFSET).to_be_bytes();
+        (key, chain_code) = hmac_split(&chain_code, &[&[0u8], &key, &hardened_index]);
+    }
+
+    (key, chain_code)
+}
+
+/// HMAC-SHA512 of the concatenated data, split into its left and right halves.
+fn hmac_split(key: &[u8], data: &[&[u8]]) -> ([u8; 32], [u8; 32]) {
+    let mut mac = HmacSha512::new_from_slice(key).expect("HMAC accepts keys of any size");
+    for part in data {
+        mac.update(part);
+    }
+    let output = mac.finalize().into_bytes();
+
+    let mut left = [0u8; 32];
+    let mut right = [0u8; 32];
+    left.copy_from_slice(&output[..32]);
+    right.copy_from_slice(&output[32..]);
+    (left, right)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn test_derivation_path_roundtrip() {
+        let path = DerivationPath::for_key(KeyType::Scratchpad, 5).unwrap();
+        assert_eq!(path.to_string(), "m/7474'/2'/5'");
+        assert_eq!(path.to_string().parse::<DerivationPath>().unwrap(), path);
+        assert_eq!("m/7474h/2h/5h".parse::<DerivationPath>().unwrap(), path);
+
+        assert!("m/7474'/2".parse::<DerivationPath>().is_err());
+        assert!("7474'/2'".parse::<DerivationPath>().is_err());
+        assert!(DerivationPath::for_key(KeyType::Register, HARDENED_OFFSET).is_err());
+    }
+
+    #[test]
+    fn test_keys_are_deterministic_and_distinct() {
+        let a = MasterKey::import_mnemonic(TEST_MNEMONIC, "").unwrap();
+        let b = MasterKey::import_mnemonic(TEST_MNEMONIC, "").unwrap();
+        let with_passphrase = MasterKey::import_mnemonic(TEST_MNEMONIC, "passphrase").unwrap();
+
+        assert_eq!(a.register_key(0).unwrap(), b.register_key(0).unwrap());
+        assert_ne!(a.register_key(0).unwrap(), a.register_key(1).unwrap());
+        assert_ne!(a.register_key(0).unwrap(), a.scratchpad_key(0).unwrap());
+        assert_ne!(
+            a.vault_key(0).unwrap(),
+            with_passphrase.vault_key(0).unwrap()
+        );
+
+        let path = DerivationPath::for_key(KeyType::Pointer, 0).unwrap();
+        assert_eq!(
+            a.derive_ed25519(&path).unwrap().public(),
+            b.derive_ed25519(&path).unwrap().public()
+        );
+    }
+
+    #[test]
+    fn test_slip10_ed25519_test_vector() {
+        // SLIP-10 test vector 1 for ed25519, chain m/0'
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        let path = DerivationPath::from_indexes(vec![0]).unwrap();
+        let (key, chain_code) = derive_hardened(ED25519_CURVE_SEED, &seed, &path);
+        assert_eq!(
+            hex::encode(key),
+            "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3"
+        );
+        assert_eq!(
+            hex::encode(chain_code),
+            "8b59aa11380b624e81507a27fedda59fea6d0b779a778918a2fd3590e16e9c69"
+        );
+    }
+
+    #[test]
+    fn test_export_import() {
+        let master_key = MasterKey::generate();
+        let restored = MasterKey::import_mnemonic(&master_key.export_mnemonic(), "").unwrap();
+        assert_eq!(
+            master_key.vault_key(3).unwrap(),
+            restored.vault_key(3).unwrap()
+        );
+
+        let key = master_key.register_key(7).unwrap();
+        assert_eq!(import_secret_key(&export_secret_key(&key)).unwrap(), key);
+        assert!(MasterKey::import_mnemonic("not a valid mnemonic", "").is_err());
+    }
+}
@@ -45,6 +45,8 @@
 //! # Features
 //!
 //! - `loud`: Print debug information to stdout
+//! - `blocking`: Synchronous facade of the client API, in the `blocking` module
+//! - `test-utils`: In-memory `MockStore` implementing `NetworkStore`, to test applications offline
 
 // docs.rs generation will enable unstable `doc_cfg` feature
 #![cfg_attr(docsrs, feature(doc_cfg))]
@@ -58,37 +60,41 @@
 #[macro_use]
 extern crate tracing;
 
-/// Log at info level and print to stdout when the `loud` feature is enabled.
+/// Log at info level and print to stderr when the `loud` feature is enabled.
 #[macro_export]
 macro_rules! loud_info {
     ($($arg:tt)*) => {{
         #[cfg(feature = "loud")]
-        println!($($arg)*);
+        eprintln!($($arg)*);
         tracing::info!($($arg)*);
     }};
 }
 
-/// Log at debug level and print to stdout when the `loud` feature is enabled.
+/// Log at debug level and print to stderr when the `loud` feature is enabled.
 #[macro_export]
 macro_rules! loud_debug {
     ($($arg:tt)*) => {{
         #[cfg(feature = "loud")]
-        println!($($arg)*);
+        eprintln!($($arg)*);
         tracing::debug!($($arg)*);
     }};
 }
 
-/// Log at error level and print to stdout when the `loud` feature is enabled.
+/// Log at error level and print to stderr when the `loud` feature is enabled.
 #[macro_export]
 macro_rules! loud_error {
     ($($arg:tt)*) => {{
         #[cfg(feature = "loud")]
-        println!($($arg)*);
+        eprintln!($($arg)*);
         tracing::error!($($arg)*);
     }};
 }
 
+#[cfg(feature = "blocking")]
+#[cfg_attr(docsrs, doc(cfg(feature = "blocking")))]
+pub mod blocking;
 pub mod client;
+pub mod keys;
 pub mod networking;
 pub mod self_encryption;
 
@@ -101,7 +107,9 @@ pub use client::data_types::scratchpad;
 // The high-level data types
 pub use client::data;
 pub use client::files;
+pub use client::index;
 pub use client::register;
+pub use client::site;
 pub use client::vault;
 
 // Re-exports of the evm types
@@ -109,6 +117,8 @@ pub use ant_evm::EvmNetwork as Network;
 pub use ant_evm::EvmWallet as Wallet;
 pub use ant_evm::QuoteHash;
 pub use ant_evm::RewardsAddress;
+/// Local EVM testnet, with [`evm_testnet::fund`] to fund wallets for development and tests
+pub use ant_evm::testnet as evm_testnet;
 pub use ant_evm::utils::{Error as EvmUtilError, get_evm_network};
 pub use ant_evm::{Amount, AttoTokens};
 pub use ant_evm::{MaxFeePerGas, TransactionConfig};
@@ -136,11 +146,15 @@ pub use client::{
     // Client
     Client,
     // Client Configs
+    builder::ClientBuilder,
+    builder::ClientNetwork,
+    builder::NetworkProfile,
     config::Bootstrap,
     config::BootstrapConfig,
     config::BootstrapError,
     config::ClientConfig,
     config::ClientOperatingStrategy,
+    config::ConnectionBudget,
     config::InitialPeersConfig,
     // Native data types
     data_types::chunk::Chunk,
@@ -153,6 +167,9 @@ pub use client::{
     data_types::scratchpad::ScratchpadAddress,
     // Payment
     quote::PaymentMode,
+    quote::QuoteSelectionStrategy,
+    // Storage interface
+    store::NetworkStore,
 };
 
 #[cfg(feature = "extension-module")]
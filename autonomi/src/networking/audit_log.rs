@@ -0,0 +1,179 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Structured log of the network operations carried out by the client, one JSON object per line.
+
+use serde::Serialize;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::sync::mpsc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Kind of network operation recorded in the audit log.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum AuditOperation {
+    PutRecord,
+    GetRecord,
+    Payment,
+    MerklePayment,
+}
+
+/// A line of the audit log.
+#[derive(Debug, Serialize)]
+pub(crate) struct AuditLogEntry {
+    /// Milliseconds since UNIX epoch when the operation completed
+    pub(crate) timestamp_ms: u64,
+    pub(crate) operation: AuditOperation,
+    /// Record key, or content address for payments
+    pub(crate) key: String,
+    /// Peers the record was sent to, or the payees for payments.
+    /// Empty for gets, as those go through a Kademlia query.
+    pub(crate) peers: Vec<String>,
+    /// Peers that stored, returned or were paid for the record
+    pub(crate) holders: Vec<String>,
+    pub(crate) quorum: Option<String>,
+    pub(crate) quorum_achieved: bool,
+    pub(crate) duration_ms: u64,
+    /// Transaction hashes of the payment, or the winner pool hash for Merkle payments
+    pub(crate) payment: Option<Vec<String>>,
+    pub(crate) error: Option<String>,
+}
+
+impl AuditLogEntry {
+    pub(crate) fn new(operation: AuditOperation, key: String, duration: Duration) -> Self {
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|since_epoch| since_epoch.as_millis() as u64)
+            .unwrap_or_default();
+        Self {
+            timestamp_ms,
+            operation,
+            key,
+            peers: vec![],
+            holders: vec![],
+            quorum: None,
+            quorum_achieved: false,
+            duration_ms: duration.as_millis() as u64,
+            payment: None,
+            error: None,
+        }
+    }
+}
+
+/// Message to the writer thread of the audit log.
+enum AuditLogMessage {
+    Line(Vec<u8>),
+    /// Acknowledged once all the lines sent before are written
+    #[cfg(test)]
+    Flush(mpsc::Sender<()>),
+}
+
+/// Appends entries to the audit log file, shared by all the clones of a client.
+///
+/// The file is written by a dedicated thread, so the network operations never wait on the disk.
+/// The thread exits once all the clones are dropped.
+#[derive(Debug, Clone)]
+pub(crate) struct AuditLog {
+    sender: mpsc::Sender<AuditLogMessage>,
+}
+
+impl AuditLog {
+    /// Open the audit log at `path`, appending to it if it already exists.
+    pub(crate) fn open(path: &Path) -> std::io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        info!("Writing the client audit log to {path:?}");
+
+        let (sender, receiver) = mpsc::channel();
+        let _handle = std::thread::Builder::new()
+            .name("audit-log".to_string())
+            .spawn(move || {
+                for message in receiver {
+                    match message {
+                        AuditLogMessage::Line(line) => {
+                            if let Err(err) = file.write_all(&line) {
+                                warn!("Failed to write audit log entry: {err}");
+                            }
+                        }
+                        #[cfg(test)]
+                        AuditLogMessage::Flush(ack) => {
+                            let _ = ack.send(());
+                        }
+                    }
+                }
+            })?;
+
+        Ok(Self { sender })
+    }
+
+    /// Append an entry. Failures are logged, as they shall not fail the operation itself.
+    pub(crate) fn write(&self, entry: &AuditLogEntry) {
+        let mut line = match serde_json::to_vec(entry) {
+            Ok(line) => line,
+            Err(err) => {
+                warn!("Failed to serialize audit log entry {entry:?}: {err}");
+                return;
+            }
+        };
+        line.push(b'\n');
+
+        if self.sender.send(AuditLogMessage::Line(line)).is_err() {
+            warn!("Audit log writer has stopped, dropping entry {entry:?}");
+        }
+    }
+
+    /// Wait for the entries written so far to reach the file.
+    #[cfg(test)]
+    pub(crate) fn flush(&self) {
+        let (ack, done) = mpsc::channel();
+        if self.sender.send(AuditLogMessage::Flush(ack)).is_ok() {
+            let _ = done.recv();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entries_are_appended_as_json_lines() {
+        let dir = tempfile::tempdir().expect("tempdir shall be created");
+        let path = dir.path().join("audit").join("client_audit.jsonl");
+        let log = AuditLog::open(&path).expect("audit log shall be opened");
+
+        let mut entry = AuditLogEntry::new(
+            AuditOperation::PutRecord,
+            "key".to_string(),
+            Duration::from_millis(42),
+        );
+        entry.quorum = Some("Majority".to_string());
+        log.write(&entry);
+        log.clone().write(&AuditLogEntry::new(
+            AuditOperation::GetRecord,
+            "key".to_string(),
+            Duration::ZERO,
+        ));
+        log.flush();
+
+        let content = std::fs::read_to_string(&path).expect("audit log shall be readable");
+        let lines: Vec<serde_json::Value> = content
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("each line shall be valid JSON"))
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["operation"], "put_record");
+        assert_eq!(lines[0]["duration_ms"], 42);
+        assert_eq!(lines[0]["quorum"], "Majority");
+        assert_eq!(lines[1]["operation"], "get_record");
+    }
+}
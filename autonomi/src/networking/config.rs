@@ -57,7 +57,7 @@ impl RetryStrategy {
 
 /// The strategy to adopt when puting and getting data from the network
 ///
-/// Puts are followed by a verification using get, to ensure the data is stored correctly. This verification can be configured separately from the regular gets.
+/// Puts can be followed by a verification reading the record back, to ensure the data is stored correctly. This verification can be configured separately from the regular gets.
 #[derive(Debug, Clone)]
 pub struct Strategy {
     /// The number of responses to wait for before considering the put operation successful
@@ -70,4 +70,22 @@ pub struct Strategy {
     pub get_quorum: Quorum,
     /// The retry strategy to use if the get operation fails
     pub get_retry: RetryStrategy,
+    /// Read the record back from the close group after a successful put, and fail the put with
+    /// [`super::NetworkError::WriteNotObserved`] unless `verification_quorum` of them return it
+    pub verify_after_write: bool,
+}
+
+/// Limits on the concurrency of the connections of the client to the network.
+///
+/// The defaults suit desktop machines. In constrained environments, like mobile devices or CI
+/// runners, lower limits avoid exhausting file descriptors, memory or bandwidth, at the cost of
+/// slower uploads and downloads. `None` keeps the libp2p default of each limit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConnectionBudget {
+    /// Maximum number of outgoing connections being dialed at once
+    pub max_concurrent_dials: Option<u32>,
+    /// Maximum number of concurrent request streams over all peers
+    pub max_concurrent_streams: Option<usize>,
+    /// Maximum number of concurrent streams over the connection to a single peer
+    pub max_streams_per_peer: Option<u32>,
 }
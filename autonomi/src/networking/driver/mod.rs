@@ -10,18 +10,18 @@
 mod swarm_events;
 mod task_handler;
 
-use std::collections::BTreeMap;
-use std::{num::NonZeroUsize, time::Duration};
+use std::collections::{BTreeMap, HashSet};
+use std::{
+    num::NonZeroUsize,
+    time::{Duration, Instant},
+};
 
-use crate::networking::NetworkError;
-use crate::networking::interface::NetworkTask;
+use crate::networking::interface::{ConnectivityInfo, NetworkTask};
+use crate::networking::{ConnectionBudget, NetworkError};
 use ant_bootstrap::bootstrap::Bootstrap;
 use ant_protocol::NetworkAddress;
-use ant_protocol::version::IDENTIFY_PROTOCOL_STR;
-use ant_protocol::{
-    messages::{Query, Request, Response},
-    version::REQ_RESPONSE_VERSION_STR,
-};
+use ant_protocol::messages::{Query, Request, Response};
+use ant_protocol::version::{NetworkProtocol, ProtocolMismatch};
 use futures::future::Either;
 use libp2p::kad::store::MemoryStoreConfig;
 use libp2p::multiaddr::Protocol;
@@ -38,6 +38,7 @@ use libp2p::{
 };
 use task_handler::TaskHandler;
 use tokio::sync::mpsc;
+use tracing::Span;
 
 use ant_protocol::constants::{
     KAD_STREAM_PROTOCOL_ID, MAX_PACKET_SIZE, MAX_RECORD_SIZE, REPLICATION_FACTOR,
@@ -80,12 +81,29 @@ pub(crate) struct NetworkDriver {
     /// libp2p interaction through the swarm and its events
     swarm: Swarm<AutonomiClientBehaviour>,
     /// can receive tasks from the [`crate::Network`]
-    task_receiver: mpsc::Receiver<NetworkTask>,
+    task_receiver: mpsc::Receiver<(NetworkTask, Span)>,
     /// pending tasks currently awaiting swarm events to progress
     /// this is an opaque struct that can only be mutated by the module were [`crate::driver::task_handler::TaskHandler`] is defined
     pending_tasks: TaskHandler,
     /// Count of connections established to peers. Can be used to determine if we are a 'connected' client.
     connections_made: usize,
+    /// When the driver was created, to time the first connection
+    started_at: Instant,
+    /// Time from the start of the driver to the first established connection
+    time_to_first_connection: Option<Duration>,
+    /// Outcome of the protocol negotiation with the identified peers
+    negotiation: ProtocolNegotiation,
+}
+
+/// The protocol of the network the client talks to, and how the identified peers matched it.
+pub(crate) struct ProtocolNegotiation {
+    pub protocol: NetworkProtocol,
+    /// Identified peers talking our protocol
+    pub compatible_peers: HashSet<PeerId>,
+    /// Identified peers not talking our protocol
+    pub incompatible_peers: HashSet<PeerId>,
+    /// The last protocol mismatch with a peer, if any
+    pub last_mismatch: Option<ProtocolMismatch>,
 }
 
 #[derive(NetworkBehaviour)]
@@ -95,16 +113,28 @@ pub(crate) struct AutonomiClientBehaviour {
     pub relay_client: libp2p::relay::client::Behaviour,
     pub request_response: request_response::cbor::Behaviour<Request, Response>,
     pub blocklist: libp2p::allow_block_list::Behaviour<libp2p::allow_block_list::BlockedPeers>,
+    pub connection_limits: libp2p::connection_limits::Behaviour,
 }
 
 impl NetworkDriver {
-    /// Create a new network runner
-    pub fn new(bootstrap: Bootstrap, task_receiver: mpsc::Receiver<NetworkTask>) -> Self {
+    /// Create a new network runner, talking the protocol of the given network.
+    /// Peers are dialed over QUIC, and over TCP as well when `tcp_fallback` is set.
+    /// The concurrency of the connections is limited by `budget`.
+    pub fn new(
+        bootstrap: Bootstrap,
+        tcp_fallback: bool,
+        protocol: NetworkProtocol,
+        budget: ConnectionBudget,
+        task_receiver: mpsc::Receiver<(NetworkTask, Span)>,
+    ) -> Self {
         // random new client id
         let keypair = Keypair::generate_ed25519();
         let peer_id = PeerId::from(keypair.public());
 
         info!("Client Peer ID: {peer_id}");
+        if budget != ConnectionBudget::default() {
+            info!("Client connection budget: {budget:?}");
+        }
 
         // set transport
         let mut quic_config = libp2p::quic::Config::new(&keypair);
@@ -124,6 +154,9 @@ impl NetworkDriver {
             .unwrap_or(1024 * 1024); // 1 MB - proven to work with all nodes
 
         quic_config.max_stream_data = max_stream_data;
+        if let Some(max_streams) = budget.max_streams_per_peer {
+            quic_config.max_concurrent_stream_limit = max_streams;
+        }
 
         info!(
             "Client QUIC max_stream_data: {} bytes ({:.2} MB)",
@@ -133,7 +166,26 @@ impl NetworkDriver {
 
         let transport_gen = QuicTransport::new(quic_config);
         let trans = transport_gen.map(|(peer_id, muxer), _| (peer_id, StreamMuxerBox::new(muxer)));
-        let transport = trans.boxed();
+        let transport = if tcp_fallback {
+            info!("Client TCP fallback transport enabled");
+            let tcp_transport =
+                libp2p::tcp::tokio::Transport::new(libp2p::tcp::Config::default().nodelay(true))
+                    .upgrade(libp2p::core::upgrade::Version::V1Lazy)
+                    .authenticate(
+                        libp2p::noise::Config::new(&keypair)
+                            .expect("Signing libp2p-noise static DH keypair failed."),
+                    )
+                    .multiplex(yamux_config(&budget));
+            trans
+                .or_transport(tcp_transport)
+                .map(|either_output, _| match either_output {
+                    Either::Left(output) => output,
+                    Either::Right((peer_id, muxer)) => (peer_id, StreamMuxerBox::new(muxer)),
+                })
+                .boxed()
+        } else {
+            trans.boxed()
+        };
 
         let (relay_transport, relay_client_behaviour) = libp2p::relay::client::new(peer_id);
         let relay_transport = relay_transport
@@ -142,7 +194,7 @@ impl NetworkDriver {
                 libp2p::noise::Config::new(&keypair)
                     .expect("Signing libp2p-noise static DH keypair failed."),
             )
-            .multiplex(libp2p::yamux::Config::default())
+            .multiplex(yamux_config(&budget))
             .or_transport(transport);
 
         let transport = relay_transport
@@ -154,13 +206,8 @@ impl NetworkDriver {
 
         // identify behaviour
         let identify = {
-            let identify_protocol_str = IDENTIFY_PROTOCOL_STR
-                .read()
-                .expect("Could not get IDENTIFY_PROTOCOL_STR")
-                .clone();
-            let agent_version = ant_protocol::version::construct_client_user_agent(
-                env!("CARGO_PKG_VERSION").to_string(),
-            );
+            let identify_protocol_str = protocol.identify_protocol_str();
+            let agent_version = protocol.client_user_agent(env!("CARGO_PKG_VERSION").to_string());
             info!("Client user agent: {agent_version}");
             let cfg = libp2p::identify::Config::new(identify_protocol_str, keypair.public())
                 .with_agent_version(agent_version)
@@ -172,13 +219,12 @@ impl NetworkDriver {
 
         // autonomi requests
         let request_response = {
-            let cfg = request_response::Config::default().with_request_timeout(REQ_TIMEOUT);
+            let mut cfg = request_response::Config::default().with_request_timeout(REQ_TIMEOUT);
+            if let Some(max_streams) = budget.max_concurrent_streams {
+                cfg = cfg.with_max_concurrent_streams(max_streams);
+            }
 
-            let req_res_version_str = REQ_RESPONSE_VERSION_STR
-                .read()
-                .expect("no protocol version")
-                .clone();
-            let stream = StreamProtocol::try_from_owned(req_res_version_str)
+            let stream = StreamProtocol::try_from_owned(protocol.req_response_version_str())
                 .expect("StreamProtocol should start with a /");
             let proto = [(stream, ProtocolSupport::Outbound)];
 
@@ -213,6 +259,10 @@ impl NetworkDriver {
             identify,
             request_response,
             blocklist: libp2p::allow_block_list::Behaviour::default(),
+            connection_limits: libp2p::connection_limits::Behaviour::new(
+                libp2p::connection_limits::ConnectionLimits::default()
+                    .with_max_pending_outgoing(budget.max_concurrent_dials),
+            ),
         };
 
         // create swarm
@@ -228,6 +278,14 @@ impl NetworkDriver {
             task_receiver,
             pending_tasks: task_handler,
             connections_made: 0,
+            started_at: Instant::now(),
+            time_to_first_connection: None,
+            negotiation: ProtocolNegotiation {
+                protocol,
+                compatible_peers: HashSet::new(),
+                incompatible_peers: HashSet::new(),
+                last_mismatch: None,
+            },
         };
 
         driver.bootstrap_network();
@@ -243,7 +301,13 @@ impl NetworkDriver {
                 // tasks sent by client
                 task = self.task_receiver.recv() => {
                     match task {
-                        Some(task) => self.process_task(task),
+                        Some((task, parent_span)) => {
+                            // Process the task within the span of its sender, so the requests
+                            // it sends are part of the sender's trace
+                            let span = debug_span!(parent: &parent_span, "network_task");
+                            let _entered = span.enter();
+                            self.process_task(task)
+                        }
                         None => {
                             info!("Task receiver closed, exiting");
                             break;
@@ -316,6 +380,24 @@ impl NetworkDriver {
         &mut self.swarm.behaviour_mut().request_response
     }
 
+    /// Send a request to a peer, along with the trace context of the current span when enabled
+    fn send_request(
+        &mut self,
+        peer_id: &PeerId,
+        req: Request,
+        addrs: Vec<Multiaddr>,
+    ) -> request_response::OutboundRequestId {
+        #[cfg(feature = "otlp")]
+        let req = match (req, ant_logging::trace_context::current_traceparent()) {
+            (Request::Query(query), Some(trace_context)) => Request::TracedQuery {
+                query,
+                trace_context,
+            },
+            (req, _) => req,
+        };
+        self.req().send_request_with_addresses(peer_id, req, addrs)
+    }
+
     /// Process a task sent by the client, start the query on kad and add it to the pending tasks
     /// Events from the swarm will help update the task, they are handled in [`crate::driver::NetworkDriver::process_swarm_event`]
     fn process_task(&mut self, task: NetworkTask) {
@@ -369,15 +451,13 @@ impl NetworkDriver {
             NetworkTask::PutRecordReq { record, to, resp } => {
                 let record_address = NetworkAddress::from(&record.key);
                 let peer_address = NetworkAddress::from(to.peer_id);
-                let req = Request::Query(Query::PutRecord {
+                let req = Request::from(Query::PutRecord {
                     holder: peer_address,
                     serialized_record: record.value.clone(),
                     address: record_address,
                 });
 
-                let req_id =
-                    self.req()
-                        .send_request_with_addresses(&to.peer_id, req, to.addrs.clone());
+                let req_id = self.send_request(&to.peer_id, req, to.addrs.clone());
 
                 self.pending_tasks
                     .insert_query(req_id, NetworkTask::PutRecordReq { record, to, resp });
@@ -389,7 +469,7 @@ impl NetworkDriver {
                 data_size,
                 resp,
             } => {
-                let req = Request::Query(Query::GetStoreQuote {
+                let req = Request::from(Query::GetStoreQuote {
                     key: addr.clone(),
                     data_type,
                     data_size,
@@ -397,9 +477,7 @@ impl NetworkDriver {
                     difficulty: 0,
                 });
 
-                let req_id =
-                    self.req()
-                        .send_request_with_addresses(&peer.peer_id, req, peer.addrs.clone());
+                let req_id = self.send_request(&peer.peer_id, req, peer.addrs.clone());
 
                 self.pending_tasks.insert_query(
                     req_id,
@@ -413,29 +491,84 @@ impl NetworkDriver {
                 );
             }
             NetworkTask::GetVersion { peer, resp } => {
-                let req = Request::Query(Query::GetVersion(NetworkAddress::from(peer.peer_id)));
+                let req = Request::from(Query::GetVersion(NetworkAddress::from(peer.peer_id)));
 
-                let req_id =
-                    self.req()
-                        .send_request_with_addresses(&peer.peer_id, req, peer.addrs.clone());
+                let req_id = self.send_request(&peer.peer_id, req, peer.addrs.clone());
 
                 self.pending_tasks
                     .insert_query(req_id, NetworkTask::GetVersion { peer, resp });
             }
             NetworkTask::GetRecordFromPeer { addr, peer, resp } => {
-                let req = Request::Query(Query::GetReplicatedRecord {
+                let req = Request::from(Query::GetReplicatedRecord {
                     // using the recipient's address as the requester as a placeholder
                     requester: NetworkAddress::from(peer.peer_id),
                     key: addr.clone(),
                 });
 
-                let req_id =
-                    self.req()
-                        .send_request_with_addresses(&peer.peer_id, req, peer.addrs.clone());
+                let req_id = self.send_request(&peer.peer_id, req, peer.addrs.clone());
 
                 self.pending_tasks
                     .insert_query(req_id, NetworkTask::GetRecordFromPeer { addr, peer, resp });
             }
+            NetworkTask::GetRecordHeaderFromPeer { addr, peer, resp } => {
+                let req = Request::from(Query::GetRecordHeader(addr.clone()));
+
+                let req_id = self.send_request(&peer.peer_id, req, peer.addrs.clone());
+
+                self.pending_tasks.insert_query(
+                    req_id,
+                    NetworkTask::GetRecordHeaderFromPeer { addr, peer, resp },
+                );
+            }
+            NetworkTask::GetChunkRangeFromPeer {
+                addr,
+                peer,
+                offset,
+                len,
+                resp,
+            } => {
+                let req = Request::from(Query::GetChunkRange {
+                    addr: addr.clone(),
+                    offset,
+                    len,
+                });
+
+                let req_id = self.send_request(&peer.peer_id, req, peer.addrs.clone());
+
+                self.pending_tasks.insert_query(
+                    req_id,
+                    NetworkTask::GetChunkRangeFromPeer {
+                        addr,
+                        peer,
+                        offset,
+                        len,
+                        resp,
+                    },
+                );
+            }
+            NetworkTask::GetStorageStatementFromPeer {
+                addr,
+                peer,
+                nonce,
+                resp,
+            } => {
+                let req = Request::from(Query::GetStorageStatement {
+                    key: addr.clone(),
+                    nonce,
+                });
+
+                let req_id = self.send_request(&peer.peer_id, req, peer.addrs.clone());
+
+                self.pending_tasks.insert_query(
+                    req_id,
+                    NetworkTask::GetStorageStatementFromPeer {
+                        addr,
+                        peer,
+                        nonce,
+                        resp,
+                    },
+                );
+            }
             NetworkTask::GetStorageProofsFromPeer {
                 addr,
                 peer,
@@ -445,7 +578,7 @@ impl NetworkDriver {
                 data_size,
                 resp,
             } => {
-                let req = Request::Query(Query::GetStoreQuote {
+                let req = Request::from(Query::GetStoreQuote {
                     key: addr.clone(),
                     data_type: data_type as u32,
                     data_size,
@@ -453,9 +586,7 @@ impl NetworkDriver {
                     difficulty,
                 });
 
-                let req_id =
-                    self.req()
-                        .send_request_with_addresses(&peer.peer_id, req, peer.addrs.clone());
+                let req_id = self.send_request(&peer.peer_id, req, peer.addrs.clone());
 
                 self.pending_tasks.insert_query(
                     req_id,
@@ -476,16 +607,14 @@ impl NetworkDriver {
                 num_of_peers,
                 resp,
             } => {
-                let req = Request::Query(Query::GetClosestPeers {
+                let req = Request::from(Query::GetClosestPeers {
                     key: addr.clone(),
                     num_of_peers,
                     range: None,
                     sign_result: true,
                 });
 
-                let req_id =
-                    self.req()
-                        .send_request_with_addresses(&peer.peer_id, req, peer.addrs.clone());
+                let req_id = self.send_request(&peer.peer_id, req, peer.addrs.clone());
 
                 self.pending_tasks.insert_query(
                     req_id,
@@ -503,6 +632,27 @@ impl NetworkDriver {
                     error!("Error sending connections made response: {e:?}");
                 }
             }
+            NetworkTask::ConnectivityInfo { resp } => {
+                let kademlia = &mut self.swarm.behaviour_mut().kademlia;
+                let mut info = ConnectivityInfo {
+                    time_to_first_connection: self.time_to_first_connection,
+                    connections_made: self.connections_made,
+                    compatible_peers: self.negotiation.compatible_peers.len(),
+                    incompatible_peers: self.negotiation.incompatible_peers.len(),
+                    protocol_mismatch: self.negotiation.last_mismatch.clone(),
+                    ..Default::default()
+                };
+                for kbucket in kademlia.kbuckets() {
+                    let num_entries = kbucket.num_entries();
+                    info.peers_in_routing_table += num_entries;
+                    if num_entries > 0 {
+                        info.non_empty_kbuckets += 1;
+                    }
+                }
+                if let Err(e) = resp.send(Ok(info)) {
+                    error!("Error sending connectivity info response: {e:?}");
+                }
+            }
             NetworkTask::GetMerkleCandidateQuote {
                 addr,
                 peer,
@@ -511,16 +661,14 @@ impl NetworkDriver {
                 merkle_payment_timestamp,
                 resp,
             } => {
-                let req = Request::Query(Query::GetMerkleCandidateQuote {
+                let req = Request::from(Query::GetMerkleCandidateQuote {
                     key: addr.clone(),
                     data_type,
                     data_size,
                     merkle_payment_timestamp,
                 });
 
-                let req_id =
-                    self.req()
-                        .send_request_with_addresses(&peer.peer_id, req, peer.addrs.clone());
+                let req_id = self.send_request(&peer.peer_id, req, peer.addrs.clone());
 
                 self.pending_tasks.insert_query(
                     req_id,
@@ -541,14 +689,12 @@ impl NetworkDriver {
                 num_of_peers,
                 resp,
             } => {
-                let req = Request::Query(Query::DevGetClosestPeersFromNetwork {
+                let req = Request::from(Query::DevGetClosestPeersFromNetwork {
                     key: addr.clone(),
                     num_of_peers,
                 });
 
-                let req_id =
-                    self.req()
-                        .send_request_with_addresses(&peer.peer_id, req, peer.addrs.clone());
+                let req_id = self.send_request(&peer.peer_id, req, peer.addrs.clone());
 
                 self.pending_tasks.insert_query(
                     req_id,
@@ -563,3 +709,13 @@ impl NetworkDriver {
         }
     }
 }
+
+/// The yamux multiplexer config of the TCP and relayed connections, within the stream limit of
+/// the [`ConnectionBudget`].
+fn yamux_config(budget: &ConnectionBudget) -> libp2p::yamux::Config {
+    let mut config = libp2p::yamux::Config::default();
+    if let Some(max_streams) = budget.max_streams_per_peer {
+        let _ = config.set_max_num_streams(max_streams as usize);
+    }
+    config
+}
@@ -77,6 +77,11 @@ impl NetworkDriver {
                     (peer_id, endpoint.get_remote_address().clone()),
                 );
                 self.connections_made += 1;
+                if self.time_to_first_connection.is_none() {
+                    let elapsed = self.started_at.elapsed();
+                    debug!("First connection established after {elapsed:?}");
+                    self.time_to_first_connection = Some(elapsed);
+                }
                 self.bootstrap
                     .on_connection_established(&peer_id, &endpoint);
                 Ok(())
@@ -206,6 +211,18 @@ impl NetworkDriver {
                 self.pending_tasks
                     .update_get_record_from_peer(request_id, result)?;
             }
+            Response::Query(QueryResponse::GetRecordHeader(result)) => {
+                self.pending_tasks
+                    .update_get_record_header_from_peer(request_id, result)?;
+            }
+            Response::Query(QueryResponse::GetChunkRange(result)) => {
+                self.pending_tasks
+                    .update_get_chunk_range_from_peer(request_id, result)?;
+            }
+            Response::Query(QueryResponse::GetStorageStatement(result)) => {
+                self.pending_tasks
+                    .update_get_storage_statement_from_peer(request_id, result)?;
+            }
             Response::Query(QueryResponse::GetClosestPeers {
                 target: _,
                 peers,
@@ -263,6 +280,16 @@ impl NetworkDriver {
                     "identify: received info from {peer_id:?} on {connection_id:?}. Info: {info:?}"
                 );
 
+                if let Err(mismatch) = self.negotiation.protocol.negotiate(&info.protocol_version) {
+                    warn!("identify: {peer_id:?} does not have the same protocol. {mismatch}");
+                    let _ = self.swarm.behaviour_mut().blocklist.block_peer(*peer_id);
+                    let _ = self.swarm.behaviour_mut().kademlia.remove_peer(peer_id);
+                    let _ = self.negotiation.incompatible_peers.insert(*peer_id);
+                    self.negotiation.last_mismatch = Some(mismatch);
+                    return Ok(());
+                }
+                let _ = self.negotiation.compatible_peers.insert(*peer_id);
+
                 let banned = self.handle_blocklist(*peer_id, info);
 
                 let Some((peer_id, addr_fom_connection)) =
@@ -366,7 +393,8 @@ fn endpoint_str(endpoint: &libp2p::core::ConnectedPoint) -> String {
     }
 }
 
-/// Craft valid multiaddr like /ip4/68.183.39.80/udp/31055/quic-v1
+/// Craft valid multiaddr like /ip4/68.183.39.80/udp/31055/quic-v1, or /ip4/68.183.39.80/tcp/31055
+/// if the peer is connected over the TCP fallback.
 /// RelayManager::craft_relay_address for relayed addr. This is for non-relayed addr.
 fn craft_valid_multiaddr_without_p2p(addr: &Multiaddr) -> Option<Multiaddr> {
     let mut new_multiaddr = Multiaddr::empty();
@@ -377,15 +405,19 @@ fn craft_valid_multiaddr_without_p2p(addr: &Multiaddr) -> Option<Multiaddr> {
     let port = multiaddr_get_port(addr)?;
 
     new_multiaddr.push(Protocol::Ip4(ip));
-    new_multiaddr.push(Protocol::Udp(port));
-    new_multiaddr.push(Protocol::QuicV1);
+    if addr.iter().any(|p| matches!(p, Protocol::Tcp(_))) {
+        new_multiaddr.push(Protocol::Tcp(port));
+    } else {
+        new_multiaddr.push(Protocol::Udp(port));
+        new_multiaddr.push(Protocol::QuicV1);
+    }
 
     Some(new_multiaddr)
 }
 
 fn multiaddr_get_port(addr: &Multiaddr) -> Option<u16> {
     addr.iter().find_map(|p| match p {
-        Protocol::Udp(port) => Some(port),
+        Protocol::Udp(port) | Protocol::Tcp(port) => Some(port),
         _ => None,
     })
 }
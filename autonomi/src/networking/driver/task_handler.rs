@@ -14,7 +14,9 @@ use crate::networking::interface::DevGetClosestPeersFromNetworkResponse;
 use crate::networking::interface::NetworkTask;
 use crate::networking::utils::get_quorum_amount;
 use ant_evm::{PaymentQuote, merkle_payments::MerklePaymentCandidateNode};
+use ant_protocol::messages::{RecordMetadata, StorageStatement};
 use ant_protocol::{NetworkAddress, PrettyPrintRecordKey};
+use bytes::Bytes;
 use libp2p::PeerId;
 use libp2p::kad::{self, PeerInfo, QueryId, Quorum, Record};
 use libp2p::request_response::OutboundRequestId;
@@ -53,6 +55,12 @@ pub(crate) struct TaskHandler {
     get_record_accumulator: HashMap<QueryId, HashMap<PeerId, Record>>,
     get_version: HashMap<OutboundRequestId, OneShotTaskResult<String>>,
     get_record_from_peer: HashMap<OutboundRequestId, OneShotTaskResult<Option<Record>>>,
+    get_record_header_from_peer:
+        HashMap<OutboundRequestId, OneShotTaskResult<Option<RecordMetadata>>>,
+    get_chunk_range_from_peer:
+        HashMap<OutboundRequestId, OneShotTaskResult<Option<(Bytes, usize)>>>,
+    get_storage_statement_from_peer:
+        HashMap<OutboundRequestId, OneShotTaskResult<Option<StorageStatement>>>,
     get_storage_proofs_from_peer:
         HashMap<OutboundRequestId, OneShotTaskResult<PeerQuoteWithStorageProof>>,
     get_closest_peers_from_peer: HashMap<
@@ -77,6 +85,9 @@ impl TaskHandler {
             get_record_accumulator: Default::default(),
             get_version: Default::default(),
             get_record_from_peer: Default::default(),
+            get_record_header_from_peer: Default::default(),
+            get_chunk_range_from_peer: Default::default(),
+            get_storage_statement_from_peer: Default::default(),
             get_storage_proofs_from_peer: Default::default(),
             get_closest_peers_from_peer: Default::default(),
             get_merkle_candidate_quote: Default::default(),
@@ -96,6 +107,9 @@ impl TaskHandler {
             || self.put_record_req.contains_key(id)
             || self.get_version.contains_key(id)
             || self.get_record_from_peer.contains_key(id)
+            || self.get_record_header_from_peer.contains_key(id)
+            || self.get_chunk_range_from_peer.contains_key(id)
+            || self.get_storage_statement_from_peer.contains_key(id)
             || self.get_storage_proofs_from_peer.contains_key(id)
             || self.get_closest_peers_from_peer.contains_key(id)
             || self.get_merkle_candidate_quote.contains_key(id);
@@ -140,6 +154,15 @@ impl TaskHandler {
             NetworkTask::GetRecordFromPeer { resp, .. } => {
                 self.get_record_from_peer.insert(id, resp);
             }
+            NetworkTask::GetRecordHeaderFromPeer { resp, .. } => {
+                self.get_record_header_from_peer.insert(id, resp);
+            }
+            NetworkTask::GetChunkRangeFromPeer { resp, .. } => {
+                self.get_chunk_range_from_peer.insert(id, resp);
+            }
+            NetworkTask::GetStorageStatementFromPeer { resp, .. } => {
+                self.get_storage_statement_from_peer.insert(id, resp);
+            }
             NetworkTask::GetStorageProofsFromPeer { resp, .. } => {
                 self.get_storage_proofs_from_peer.insert(id, resp);
             }
@@ -380,6 +403,20 @@ impl TaskHandler {
                     }))
                     .map_err(|_| TaskHandlerError::NetworkClientDropped(format!("{id:?}")))?;
             }
+            Err(ant_protocol::error::Error::FreeUpdateRateLimited {
+                key,
+                work_difficulty,
+            }) => {
+                trace!(
+                    "OutboundRequestId({id}): put record got rate limited free update error for {key:?}, work difficulty: {work_difficulty}"
+                );
+                responder
+                    .send(Err(NetworkError::FreeUpdateRateLimited {
+                        holders: vec![],
+                        work_difficulty,
+                    }))
+                    .map_err(|_| TaskHandlerError::NetworkClientDropped(format!("{id:?}")))?;
+            }
             Err(ant_protocol::error::Error::TopologyVerificationFailed {
                 target_address,
                 valid_count,
@@ -505,6 +542,90 @@ impl TaskHandler {
         Ok(())
     }
 
+    pub fn update_get_record_header_from_peer(
+        &mut self,
+        id: OutboundRequestId,
+        result: Result<(NetworkAddress, RecordMetadata), ant_protocol::error::Error>,
+    ) -> Result<(), TaskHandlerError> {
+        let responder =
+            self.get_record_header_from_peer
+                .remove(&id)
+                .ok_or(TaskHandlerError::UnknownQuery(format!(
+                    "OutboundRequestId {id:?}"
+                )))?;
+
+        let metadata = match result {
+            Ok((holder, metadata)) => {
+                trace!("OutboundRequestId({id}): got record header {metadata:?} from {holder:?}");
+                Some(metadata)
+            }
+            Err(e) => {
+                trace!("OutboundRequestId({id}): failed to get record header from peer: {e:?}");
+                None
+            }
+        };
+        responder
+            .send(Ok(metadata))
+            .map_err(|_| TaskHandlerError::NetworkClientDropped(format!("{id:?}")))?;
+        Ok(())
+    }
+
+    pub fn update_get_chunk_range_from_peer(
+        &mut self,
+        id: OutboundRequestId,
+        result: Result<(Bytes, usize), ant_protocol::error::Error>,
+    ) -> Result<(), TaskHandlerError> {
+        let responder =
+            self.get_chunk_range_from_peer
+                .remove(&id)
+                .ok_or(TaskHandlerError::UnknownQuery(format!(
+                    "OutboundRequestId {id:?}"
+                )))?;
+
+        let range = match result {
+            Ok((data, chunk_size)) => {
+                trace!(
+                    "OutboundRequestId({id}): got {} bytes of a chunk of {chunk_size} bytes",
+                    data.len()
+                );
+                Some((data, chunk_size))
+            }
+            Err(e) => {
+                trace!("OutboundRequestId({id}): failed to get chunk range from peer: {e:?}");
+                None
+            }
+        };
+        responder
+            .send(Ok(range))
+            .map_err(|_| TaskHandlerError::NetworkClientDropped(format!("{id:?}")))?;
+        Ok(())
+    }
+
+    pub fn update_get_storage_statement_from_peer(
+        &mut self,
+        id: OutboundRequestId,
+        result: Result<StorageStatement, ant_protocol::error::Error>,
+    ) -> Result<(), TaskHandlerError> {
+        let responder = self.get_storage_statement_from_peer.remove(&id).ok_or(
+            TaskHandlerError::UnknownQuery(format!("OutboundRequestId {id:?}")),
+        )?;
+
+        let statement = match result {
+            Ok(statement) => {
+                trace!("OutboundRequestId({id}): got storage statement {statement:?}");
+                Some(statement)
+            }
+            Err(e) => {
+                trace!("OutboundRequestId({id}): failed to get storage statement from peer: {e:?}");
+                None
+            }
+        };
+        responder
+            .send(Ok(statement))
+            .map_err(|_| TaskHandlerError::NetworkClientDropped(format!("{id:?}")))?;
+        Ok(())
+    }
+
     pub fn update_get_storage_proofs_from_peer(
         &mut self,
         id: OutboundRequestId,
@@ -643,6 +764,30 @@ impl TaskHandler {
             responder
                 .send(Ok(None))
                 .map_err(|_| TaskHandlerError::NetworkClientDropped(format!("{id:?}")))?;
+        // Get record header from peer case
+        } else if let Some(responder) = self.get_record_header_from_peer.remove(&id) {
+            trace!(
+                "OutboundRequestId({id}): get record header from peer got fatal error from peer {peer:?}: {error:?}"
+            );
+            responder
+                .send(Ok(None))
+                .map_err(|_| TaskHandlerError::NetworkClientDropped(format!("{id:?}")))?;
+        // Get chunk range from peer case
+        } else if let Some(responder) = self.get_chunk_range_from_peer.remove(&id) {
+            trace!(
+                "OutboundRequestId({id}): get chunk range from peer got fatal error from peer {peer:?}: {error:?}"
+            );
+            responder
+                .send(Ok(None))
+                .map_err(|_| TaskHandlerError::NetworkClientDropped(format!("{id:?}")))?;
+        // Get storage statement from peer case
+        } else if let Some(responder) = self.get_storage_statement_from_peer.remove(&id) {
+            trace!(
+                "OutboundRequestId({id}): get storage statement from peer got fatal error from peer {peer:?}: {error:?}"
+            );
+            responder
+                .send(Ok(None))
+                .map_err(|_| TaskHandlerError::NetworkClientDropped(format!("{id:?}")))?;
         // Get storage proofs from peer case
         } else if let Some(responder) = self.get_storage_proofs_from_peer.remove(&id) {
             trace!(
@@ -9,12 +9,16 @@
 use crate::networking::{OneShotTaskResult, PeerQuoteWithStorageProof};
 use ant_evm::{PaymentQuote, merkle_payments::MerklePaymentCandidateNode};
 use ant_protocol::NetworkAddress;
+use ant_protocol::messages::{RecordMetadata, StorageStatement};
 use ant_protocol::storage::DataTypes;
+use ant_protocol::version::ProtocolMismatch;
+use bytes::Bytes;
 use libp2p::{
     PeerId,
     kad::{PeerInfo, Quorum, Record},
 };
 use std::num::NonZeroUsize;
+use std::time::Duration;
 
 /// Task for the underlying network driver
 /// Sent by the [`crate::Network`], handled by the [`crate::driver::NetworkDriver`]
@@ -82,6 +86,30 @@ pub(super) enum NetworkTask {
         #[debug(skip)]
         resp: OneShotTaskResult<Option<Record>>,
     },
+    /// Get the metadata of a record from a specific peer, without downloading its content
+    GetRecordHeaderFromPeer {
+        addr: NetworkAddress,
+        peer: PeerInfo,
+        #[debug(skip)]
+        resp: OneShotTaskResult<Option<RecordMetadata>>,
+    },
+    /// Get a byte range of a chunk from a specific peer, along with the full size of the chunk
+    GetChunkRangeFromPeer {
+        addr: NetworkAddress,
+        peer: PeerInfo,
+        offset: usize,
+        len: usize,
+        #[debug(skip)]
+        resp: OneShotTaskResult<Option<(Bytes, usize)>>,
+    },
+    /// Get a signed statement from a specific peer that it holds a record
+    GetStorageStatementFromPeer {
+        addr: NetworkAddress,
+        peer: PeerInfo,
+        nonce: u64,
+        #[debug(skip)]
+        resp: OneShotTaskResult<Option<StorageStatement>>,
+    },
     /// Get storage proofs directly from a specific peer using request/response
     GetStorageProofsFromPeer {
         addr: NetworkAddress,
@@ -106,6 +134,11 @@ pub(super) enum NetworkTask {
         #[debug(skip)]
         resp: OneShotTaskResult<usize>,
     },
+    /// Get information about the connectivity of the client and its routing table
+    ConnectivityInfo {
+        #[debug(skip)]
+        resp: OneShotTaskResult<ConnectivityInfo>,
+    },
     /// Get a Merkle candidate quote from a specific peer
     /// Used for Merkle batch payment system
     GetMerkleCandidateQuote {
@@ -134,6 +167,25 @@ pub(super) enum NetworkTask {
     },
 }
 
+/// Connectivity of the client and the state of its routing table
+#[derive(Debug, Clone, Default)]
+pub struct ConnectivityInfo {
+    /// Time from the start of the client to its first established connection, if any
+    pub time_to_first_connection: Option<Duration>,
+    /// Count of connections established to peers
+    pub connections_made: usize,
+    /// The number of peers in the routing table
+    pub peers_in_routing_table: usize,
+    /// The number of kbuckets holding at least one peer
+    pub non_empty_kbuckets: usize,
+    /// The number of identified peers talking our protocol
+    pub compatible_peers: usize,
+    /// The number of identified peers not talking our protocol
+    pub incompatible_peers: usize,
+    /// The last protocol mismatch with a peer, if any
+    pub protocol_mismatch: Option<ProtocolMismatch>,
+}
+
 /// Response from DevGetClosestPeersFromNetwork
 /// Contains the query results and metadata about which node performed the query
 #[cfg(feature = "developer")]
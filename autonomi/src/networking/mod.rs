@@ -7,6 +7,7 @@
 // permissions and limitations relating to use of the SAFE Network Software.
 
 // all modules are private to this networking module
+pub(crate) mod audit_log;
 pub(crate) mod common;
 mod config;
 mod driver;
@@ -17,13 +18,17 @@ pub mod version;
 
 use crate::client::CONNECT_TIMEOUT_SECS;
 use ant_bootstrap::bootstrap::Bootstrap;
+use audit_log::{AuditLog, AuditLogEntry, AuditOperation};
 // export the utils
 pub(crate) use utils::multiaddr_is_global;
 
 // re-export the types our API exposes to avoid dependency version conflicts
 pub use ant_evm::PaymentQuote;
 pub use ant_protocol::NetworkAddress;
-pub use config::{RetryStrategy, Strategy};
+pub use ant_protocol::messages::{RecordMetadata, StorageStatement};
+pub use ant_protocol::version::{MismatchReason, NetworkProtocol, ProtocolMismatch};
+pub use config::{ConnectionBudget, RetryStrategy, Strategy};
+pub use interface::ConnectivityInfo;
 #[cfg(feature = "developer")]
 pub use interface::DevGetClosestPeersFromNetworkResponse;
 pub use libp2p::kad::PeerInfo;
@@ -34,7 +39,8 @@ pub use libp2p::{
 
 // internal needs
 use crate::networking::version::PackageVersion;
-use ant_protocol::{CLOSE_GROUP_SIZE, PrettyPrintRecordKey};
+use ant_protocol::{CLOSE_GROUP_SIZE, PrettyPrintRecordKey, close_group::close_group_majority};
+use bytes::Bytes;
 use driver::NetworkDriver;
 use futures::stream::{FuturesUnordered, StreamExt};
 use interface::NetworkTask;
@@ -43,16 +49,18 @@ use libp2p::kad::NoKnownPeers;
 use std::collections::{HashMap, HashSet};
 use std::num::NonZeroUsize;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 use tokio::sync::{mpsc, oneshot};
 use tokio::time::{sleep, timeout};
+use tracing::Span;
+use xor_name::XorName;
 
 /// Result type for tasks responses sent by the [`crate::driver::NetworkDriver`] to the [`crate::Network`]
 pub(in crate::networking) type OneShotTaskResult<T> = oneshot::Sender<Result<T, NetworkError>>;
 
 /// The majority size within the close group.
-pub const CLOSE_GROUP_SIZE_MAJORITY: usize = CLOSE_GROUP_SIZE / 2 + 1;
+pub const CLOSE_GROUP_SIZE_MAJORITY: usize = close_group_majority();
 
 /// The number of quoting candidates to be queried.
 pub(crate) const QUOTING_CANDIDATES: usize = 10;
@@ -190,6 +198,14 @@ pub enum NetworkError {
     PutRecordRejected(String),
     #[error("Outdated record rejected: with counter {counter}, expected any above {expected}")]
     OutdatedRecordRejected { counter: u64, expected: u64 },
+    #[error(
+        "Too many free updates of the record, the following peers stored it: {holders:?}, the update must be paid for or carry a proof of work of difficulty {work_difficulty}"
+    )]
+    FreeUpdateRateLimited {
+        holders: Vec<PeerId>,
+        /// The hardest proof of work asked for instead of a payment, 0 if none is accepted
+        work_difficulty: u8,
+    },
     #[error("Network topology verification failed: {}", format_topology_error(.rejecting_node, .target_address, .valid_count, .total_paid, .closest_count, .node_peers, .paid_peers))]
     TopologyVerificationFailed {
         /// The node that rejected the record due to topology mismatch
@@ -245,11 +261,35 @@ pub enum NetworkError {
     #[error("Failed to get version: {0}")]
     GetVersionError(String),
 
+    /// Error reading back a written record
+    #[error(
+        "Write to {address:?} not observed by enough peers, expected {expected_holders}, got: {observed:?}"
+    )]
+    WriteNotObserved {
+        address: NetworkAddress,
+        expected_holders: usize,
+        /// What each queried peer returned
+        observed: Vec<(PeerId, ReadBack)>,
+    },
+
     /// Invalid retry strategy
     #[error("Invalid retry strategy, check your config or use the default")]
     InvalidRetryStrategy,
 }
 
+/// What a peer returned when reading a record back after writing it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReadBack {
+    /// The written record
+    Written,
+    /// Another version of the record, identified by the hash of its content
+    Other(XorName),
+    /// No record
+    Missing,
+    /// The peer could not be queried
+    Failed(String),
+}
+
 impl NetworkError {
     /// When encountering these, create a new [`Network`] instance
     pub fn is_fatal(&self) -> bool {
@@ -263,14 +303,22 @@ impl NetworkError {
 
     /// When encountering these, the request should not be retried
     pub fn cannot_retry(&self) -> bool {
-        matches!(self, NetworkError::OutdatedRecordRejected { .. }) || self.is_fatal()
+        matches!(
+            self,
+            NetworkError::OutdatedRecordRejected { .. }
+                | NetworkError::FreeUpdateRateLimited { .. }
+        ) || self.is_fatal()
     }
 }
 
 /// The Client interface to the Autonomi Network
 #[derive(Debug, Clone)]
 pub struct Network {
-    task_sender: Arc<tokio::sync::mpsc::Sender<NetworkTask>>,
+    task_sender: Arc<tokio::sync::mpsc::Sender<(NetworkTask, Span)>>,
+    #[cfg(feature = "open-metrics")]
+    metrics: crate::client::metrics::ClientMetrics,
+    /// Shared by all the clones, so that enabling the audit log on one of them enables it on all
+    audit_log: Arc<std::sync::RwLock<Option<AuditLog>>>,
 }
 
 impl Network {
@@ -278,8 +326,44 @@ impl Network {
     /// This will start the network driver in a background thread, which is a long-running task that runs until the [`Network`] is dropped
     /// The [`Network`] is cheaply cloneable, prefer cloning over creating new instances to avoid creating multiple network drivers
     pub fn new(bootstrap: Bootstrap) -> Result<Self, NoKnownPeers> {
+        Self::new_with_transports(bootstrap, false)
+    }
+
+    /// Create a new network client, also dialing peers over TCP when `tcp_fallback` is set.
+    /// See [`Network::new`].
+    pub fn new_with_transports(
+        bootstrap: Bootstrap,
+        tcp_fallback: bool,
+    ) -> Result<Self, NoKnownPeers> {
+        Self::new_with_protocol(bootstrap, tcp_fallback, NetworkProtocol::current())
+    }
+
+    /// Create a new network client talking the protocol of the given network, regardless of the
+    /// process-wide network id, so that clients of different networks can share a process.
+    /// See [`Network::new_with_transports`].
+    pub fn new_with_protocol(
+        bootstrap: Bootstrap,
+        tcp_fallback: bool,
+        protocol: NetworkProtocol,
+    ) -> Result<Self, NoKnownPeers> {
+        Self::new_with_budget(
+            bootstrap,
+            tcp_fallback,
+            protocol,
+            ConnectionBudget::default(),
+        )
+    }
+
+    /// Create a new network client whose connections are limited by the given
+    /// [`ConnectionBudget`]. See [`Network::new_with_protocol`].
+    pub fn new_with_budget(
+        bootstrap: Bootstrap,
+        tcp_fallback: bool,
+        protocol: NetworkProtocol,
+        budget: ConnectionBudget,
+    ) -> Result<Self, NoKnownPeers> {
         let (task_sender, task_receiver) = mpsc::channel(100);
-        let driver = NetworkDriver::new(bootstrap, task_receiver);
+        let driver = NetworkDriver::new(bootstrap, tcp_fallback, protocol, budget, task_receiver);
 
         // run the network driver in a background task
         tokio::spawn(async move {
@@ -288,11 +372,37 @@ impl Network {
 
         let network = Self {
             task_sender: Arc::new(task_sender),
+            #[cfg(feature = "open-metrics")]
+            metrics: crate::client::metrics::ClientMetrics::new(),
+            audit_log: Arc::new(std::sync::RwLock::new(None)),
         };
 
         Ok(network)
     }
 
+    /// Record every network operation of this network and of all its clones to the given audit
+    /// log from now on.
+    pub(crate) fn set_audit_log(&self, audit_log: AuditLog) {
+        match self.audit_log.write() {
+            Ok(mut current) => *current = Some(audit_log),
+            Err(err) => warn!("Audit log lock poisoned, the audit log is not enabled: {err}"),
+        }
+    }
+
+    /// The audit log of the network operations, if enabled.
+    pub(crate) fn audit_log(&self) -> Option<AuditLog> {
+        self.audit_log
+            .read()
+            .ok()
+            .and_then(|audit_log| audit_log.clone())
+    }
+
+    /// The recorders of the client operation metrics.
+    #[cfg(feature = "open-metrics")]
+    pub(crate) fn metrics(&self) -> &crate::client::metrics::ClientMetrics {
+        &self.metrics
+    }
+
     /// Wait until we made [`CLOSE_GROUP_SIZE`] connections to the network.
     pub async fn wait_for_connectivity(&self) -> Result<(), crate::client::ConnectError> {
         let timeout_duration = Duration::from_secs(CONNECT_TIMEOUT_SECS); // Total timeout
@@ -303,25 +413,45 @@ impl Network {
             CONNECT_TIMEOUT_SECS, CLOSE_GROUP_SIZE
         );
 
-        match timeout(timeout_duration, async {
+        // We are connected once enough connections are made and at least one peer negotiated our
+        // protocol. Peers of another network or of an incompatible version still accept
+        // connections, so without the latter we would wrongly report being connected to them.
+        let mut last_mismatch = None;
+        let result = timeout(timeout_duration, async {
             loop {
-                match self.get_connections_made().await {
-                    Ok(count) => {
-                        if count >= CLOSE_GROUP_SIZE {
+                match self.get_connectivity_info().await {
+                    Ok(info) => {
+                        if info.connections_made >= CLOSE_GROUP_SIZE && info.compatible_peers > 0 {
                             return Ok(());
                         }
+                        if info.compatible_peers == 0
+                            && info.incompatible_peers >= CLOSE_GROUP_SIZE
+                            && let Some(mismatch) = &info.protocol_mismatch
+                        {
+                            error!("No peer talks our protocol: {mismatch}");
+                            return Err(crate::client::ConnectError::ProtocolMismatch(
+                                mismatch.clone(),
+                            ));
+                        }
+                        last_mismatch = info
+                            .protocol_mismatch
+                            .filter(|_| info.compatible_peers == 0);
                     }
                     Err(err) => {
-                        tracing::warn!("Failed to get connections made: {err}, retrying...");
+                        tracing::warn!("Failed to get connectivity info: {err}, retrying...");
                     }
                 }
                 sleep(check_interval).await;
             }
         })
-        .await
-        {
+        .await;
+
+        match result {
             Ok(result) => result,
-            Err(_) => Err(crate::client::ConnectError::TimedOut),
+            Err(_) => match last_mismatch {
+                Some(mismatch) => Err(crate::client::ConnectError::ProtocolMismatch(mismatch)),
+                None => Err(crate::client::ConnectError::TimedOut),
+            },
         }
     }
 
@@ -348,6 +478,37 @@ impl Network {
         &self,
         addr: NetworkAddress,
         quorum: Quorum,
+    ) -> Result<(Option<Record>, Vec<PeerId>), NetworkError> {
+        let Some(audit_log) = self.audit_log() else {
+            return self.get_record_and_holders_inner(addr, quorum).await;
+        };
+
+        let start = Instant::now();
+        let key = addr.to_record_key();
+        let result = self.get_record_and_holders_inner(addr, quorum).await;
+
+        let mut entry = AuditLogEntry::new(
+            AuditOperation::GetRecord,
+            PrettyPrintRecordKey::from(&key).to_string(),
+            start.elapsed(),
+        );
+        entry.quorum = Some(format!("{quorum:?}"));
+        match &result {
+            Ok((record, holders)) => {
+                entry.holders = holders.iter().map(PeerId::to_string).collect();
+                entry.quorum_achieved = record.is_some();
+            }
+            Err(err) => entry.error = Some(err.to_string()),
+        }
+        audit_log.write(&entry);
+
+        result
+    }
+
+    async fn get_record_and_holders_inner(
+        &self,
+        addr: NetworkAddress,
+        quorum: Quorum,
     ) -> Result<(Option<Record>, Vec<PeerId>), NetworkError> {
         let (tx, rx) = oneshot::channel();
         let task = NetworkTask::GetRecord {
@@ -356,7 +517,7 @@ impl Network {
             resp: tx,
         };
         self.task_sender
-            .send(task)
+            .send((task, Span::current()))
             .await
             .map_err(|_| NetworkError::NetworkDriverOffline)?;
         rx.await?
@@ -370,6 +531,48 @@ impl Network {
         to: Vec<PeerInfo>,
         quorum: Quorum,
     ) -> Result<(), NetworkError> {
+        let Some(audit_log) = self.audit_log() else {
+            return self.put_record_inner(record, to, quorum).await.map(|_| ());
+        };
+
+        let start = Instant::now();
+        let mut entry = AuditLogEntry::new(
+            AuditOperation::PutRecord,
+            PrettyPrintRecordKey::from(&record.key).to_string(),
+            Duration::ZERO,
+        );
+        entry.peers = to.iter().map(|peer| peer.peer_id.to_string()).collect();
+        entry.quorum = Some(format!("{quorum:?}"));
+
+        let result = self.put_record_inner(record, to, quorum).await;
+
+        entry.duration_ms = start.elapsed().as_millis() as u64;
+        match &result {
+            Ok(holders) => {
+                entry.holders = holders.iter().map(PeerId::to_string).collect();
+                entry.quorum_achieved = true;
+            }
+            Err(err) => {
+                if let NetworkError::PutRecordTooManyPeerFailed(holders, _)
+                | NetworkError::FreeUpdateRateLimited { holders, .. } = err
+                {
+                    entry.holders = holders.iter().map(PeerId::to_string).collect();
+                }
+                entry.error = Some(err.to_string());
+            }
+        }
+        audit_log.write(&entry);
+
+        result.map(|_| ())
+    }
+
+    /// Put a record to the peers, returning the ones that stored it once the quorum is met.
+    async fn put_record_inner(
+        &self,
+        record: Record,
+        to: Vec<PeerInfo>,
+        quorum: Quorum,
+    ) -> Result<Vec<PeerId>, NetworkError> {
         let key = PrettyPrintRecordKey::from(&record.key);
         // For data_type like ScratchPad, it is observed the holders will be 7
         // which result in the expected_holders to be 4, and could result in false alert.
@@ -395,16 +598,25 @@ impl Network {
         // collect results
         let mut ok_res = vec![];
         let mut err_res = vec![];
+        let mut rate_limited = None;
         while let Some((res, peer)) = tasks.next().await {
             match res {
                 // accumulate oks until Quorum is met
                 Ok(()) => {
                     ok_res.push(peer);
                     if ok_res.len() >= expected_holders.get() {
-                        return Ok(());
+                        return Ok(ok_res.iter().map(|p| p.peer_id).collect());
+                    }
+                }
+                Err(e) => {
+                    if let NetworkError::FreeUpdateRateLimited {
+                        work_difficulty, ..
+                    } = e
+                    {
+                        rate_limited = rate_limited.max(Some(work_difficulty));
                     }
+                    err_res.push((peer.peer_id, e.to_string()));
                 }
-                Err(e) => err_res.push((peer.peer_id, e.to_string())),
             }
         }
 
@@ -413,10 +625,69 @@ impl Network {
         warn!(
             "Put record {key} failed, only the following peers stored the record: {ok_peers:?}, needed {expected_holders} peers. Errors: {err_res:?}"
         );
+        if let Some(work_difficulty) = rate_limited {
+            return Err(NetworkError::FreeUpdateRateLimited {
+                holders: ok_peers,
+                work_difficulty,
+            });
+        }
 
         Err(NetworkError::PutRecordTooManyPeerFailed(ok_peers, err_res))
     }
 
+    /// Read a written record back from the closest of the peers it was put to.
+    /// Returns [`NetworkError::WriteNotObserved`] unless the quorum of them return the exact record.
+    pub async fn verify_record_written(
+        &self,
+        record: &Record,
+        to: &[PeerInfo],
+        quorum: Quorum,
+    ) -> Result<(), NetworkError> {
+        let address = NetworkAddress::from(&record.key);
+        let peers: Vec<PeerInfo> = to.iter().take(CLOSE_GROUP_SIZE).cloned().collect();
+        let total = NonZeroUsize::new(peers.len())
+            .ok_or(NetworkError::InvalidNonZeroUsize(peers.len().to_string()))?;
+        let expected_holders = expected_holders(quorum, total).get();
+
+        let mut tasks = FuturesUnordered::new();
+        for peer in peers {
+            let address = address.clone();
+            tasks.push(async move {
+                let peer_id = peer.peer_id;
+                (peer_id, self.get_record_from_peer(address, peer).await)
+            });
+        }
+
+        let mut observed = vec![];
+        while let Some((peer_id, res)) = tasks.next().await {
+            let read_back = match res {
+                Ok(Some(got)) if got.value == record.value => ReadBack::Written,
+                Ok(Some(got)) => ReadBack::Other(XorName::from_content(&got.value)),
+                Ok(None) => ReadBack::Missing,
+                Err(err) => ReadBack::Failed(err.to_string()),
+            };
+            observed.push((peer_id, read_back));
+        }
+
+        let written = observed
+            .iter()
+            .filter(|(_, read_back)| *read_back == ReadBack::Written)
+            .count();
+        if written >= expected_holders {
+            debug!("Write to {address:?} observed by {written} peers");
+            return Ok(());
+        }
+
+        warn!(
+            "Write to {address:?} observed by {written} peers, expected {expected_holders}: {observed:?}"
+        );
+        Err(NetworkError::WriteNotObserved {
+            address,
+            expected_holders,
+            observed,
+        })
+    }
+
     async fn put_record_req(&self, record: Record, to: PeerInfo) -> Result<(), NetworkError> {
         let (tx, rx) = oneshot::channel();
         let task = NetworkTask::PutRecordReq {
@@ -425,7 +696,7 @@ impl Network {
             resp: tx,
         };
         self.task_sender
-            .send(task)
+            .send((task, Span::current()))
             .await
             .map_err(|_| NetworkError::NetworkDriverOffline)?;
 
@@ -468,7 +739,7 @@ impl Network {
             n,
         };
         self.task_sender
-            .send(task)
+            .send((task, Span::current()))
             .await
             .map_err(|_| NetworkError::NetworkDriverOffline)?;
 
@@ -511,7 +782,7 @@ impl Network {
             n,
         };
         self.task_sender
-            .send(task)
+            .send((task, Span::current()))
             .await
             .map_err(|_| NetworkError::NetworkDriverOffline)?;
 
@@ -857,7 +1128,79 @@ impl Network {
             resp: tx,
         };
         self.task_sender
-            .send(task)
+            .send((task, Span::current()))
+            .await
+            .map_err(|_| NetworkError::NetworkDriverOffline)?;
+        rx.await?
+    }
+
+    /// Get the metadata of a record from a specific peer on the Network, without its content
+    /// Returns:
+    /// - Some(RecordMetadata) if the peer holds the record
+    /// - None if the peer doesn't hold the record or the request fails
+    pub async fn get_record_header_from_peer(
+        &self,
+        addr: NetworkAddress,
+        peer: PeerInfo,
+    ) -> Result<Option<RecordMetadata>, NetworkError> {
+        let (tx, rx) = oneshot::channel();
+        let task = NetworkTask::GetRecordHeaderFromPeer {
+            addr,
+            peer,
+            resp: tx,
+        };
+        self.task_sender
+            .send((task, Span::current()))
+            .await
+            .map_err(|_| NetworkError::NetworkDriverOffline)?;
+        rx.await?
+    }
+
+    /// Get a byte range of a chunk from a specific peer on the Network
+    /// Returns:
+    /// - Some((bytes, chunk_size)) if the peer holds the chunk, the range being truncated to its size
+    /// - None if the peer doesn't hold the chunk or the request fails
+    pub async fn get_chunk_range_from_peer(
+        &self,
+        addr: NetworkAddress,
+        peer: PeerInfo,
+        offset: usize,
+        len: usize,
+    ) -> Result<Option<(Bytes, usize)>, NetworkError> {
+        let (tx, rx) = oneshot::channel();
+        let task = NetworkTask::GetChunkRangeFromPeer {
+            addr,
+            peer,
+            offset,
+            len,
+            resp: tx,
+        };
+        self.task_sender
+            .send((task, Span::current()))
+            .await
+            .map_err(|_| NetworkError::NetworkDriverOffline)?;
+        rx.await?
+    }
+
+    /// Get a signed statement from a specific peer that it holds a record, for the given nonce
+    /// Returns:
+    /// - Some(StorageStatement) if the peer holds the record, the signature is not verified here
+    /// - None if the peer doesn't hold the record or the request fails
+    pub async fn get_storage_statement_from_peer(
+        &self,
+        addr: NetworkAddress,
+        peer: PeerInfo,
+        nonce: u64,
+    ) -> Result<Option<StorageStatement>, NetworkError> {
+        let (tx, rx) = oneshot::channel();
+        let task = NetworkTask::GetStorageStatementFromPeer {
+            addr,
+            peer,
+            nonce,
+            resp: tx,
+        };
+        self.task_sender
+            .send((task, Span::current()))
             .await
             .map_err(|_| NetworkError::NetworkDriverOffline)?;
         rx.await?
@@ -879,7 +1222,7 @@ impl Network {
             resp: tx,
         };
         self.task_sender
-            .send(task)
+            .send((task, Span::current()))
             .await
             .map_err(|_| NetworkError::NetworkDriverOffline)?;
         rx.await?
@@ -907,7 +1250,7 @@ impl Network {
             resp: tx,
         };
         self.task_sender
-            .send(task)
+            .send((task, Span::current()))
             .await
             .map_err(|_| NetworkError::NetworkDriverOffline)?;
         rx.await?
@@ -933,7 +1276,7 @@ impl Network {
             resp: tx,
         };
         self.task_sender
-            .send(task)
+            .send((task, Span::current()))
             .await
             .map_err(|_| NetworkError::NetworkDriverOffline)?;
         rx.await?
@@ -974,7 +1317,7 @@ impl Network {
             resp: tx,
         };
         self.task_sender
-            .send(task)
+            .send((task, Span::current()))
             .await
             .map_err(|_| NetworkError::NetworkDriverOffline)?;
         let candidate = rx.await??;
@@ -1048,7 +1391,7 @@ impl Network {
             resp: tx,
         };
         self.task_sender
-            .send(task)
+            .send((task, Span::current()))
             .await
             .map_err(|_| NetworkError::NetworkDriverOffline)?;
         rx.await?
@@ -1128,7 +1471,7 @@ impl Network {
         let (tx, rx) = oneshot::channel();
         let task = NetworkTask::GetVersion { peer, resp: tx };
         self.task_sender
-            .send(task)
+            .send((task, Span::current()))
             .await
             .map_err(|_| "Network driver offline".to_string())?;
 
@@ -1147,12 +1490,23 @@ impl Network {
         let (tx, rx) = oneshot::channel();
         let task = NetworkTask::ConnectionsMade { resp: tx };
         self.task_sender
-            .send(task)
+            .send((task, Span::current()))
             .await
             .map_err(|_| NetworkError::NetworkDriverOffline)?;
         tracing::trace!("Waiting for connections made response");
         rx.await?
     }
+
+    /// Get information about the connectivity of the client and its routing table
+    pub async fn get_connectivity_info(&self) -> Result<ConnectivityInfo, NetworkError> {
+        let (tx, rx) = oneshot::channel();
+        let task = NetworkTask::ConnectivityInfo { resp: tx };
+        self.task_sender
+            .send((task, Span::current()))
+            .await
+            .map_err(|_| NetworkError::NetworkDriverOffline)?;
+        rx.await?
+    }
 }
 
 fn expected_holders(quorum: Quorum, total: NonZeroUsize) -> NonZeroUsize {
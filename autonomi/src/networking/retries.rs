@@ -20,7 +20,8 @@ pub const FALLBACK_PEERS_COUNT: usize = 20;
 impl Network {
     /// Put a record to the network with retries
     ///
-    /// Will carry out network get after put success, to verify the existence of the record.
+    /// When `verify_after_write` is set, the record is read back after put success, and the put
+    /// retried if not enough peers return it.
     pub async fn put_record_with_retries(
         &self,
         record: Record,
@@ -30,13 +31,24 @@ impl Network {
         let addr = PrettyPrintRecordKey::from(&record.key).into_owned();
         let mut errors = vec![];
         for duration in strategy.put_retry.backoff() {
-            match self
+            let result = match self
                 .put_record(record.clone(), to.clone(), strategy.put_quorum)
                 .await
             {
+                Ok(()) if strategy.verify_after_write => {
+                    self.verify_record_written(&record, &to, strategy.verification_quorum)
+                        .await
+                }
+                result => result,
+            };
+            match result {
                 // Exitence verification is no longer mandatory as req/rsp upload allows client
-                // collect storage result from nodes directly.
-                Ok(()) => return Ok(()),
+                // collect storage result from nodes directly, it is only done if configured.
+                Ok(()) => {
+                    #[cfg(feature = "open-metrics")]
+                    self.metrics().record_put(record.value.len());
+                    return Ok(());
+                }
                 // return fatal errors
                 Err(err) if err.cannot_retry() => {
                     return Err(err);
@@ -46,7 +58,11 @@ impl Network {
                     warn!("Put record failed at {addr}: {err:?}, retrying in {duration:?}");
                     errors.push(err.clone());
                     match duration {
-                        Some(retry_delay) => sleep(retry_delay).await,
+                        Some(retry_delay) => {
+                            #[cfg(feature = "open-metrics")]
+                            self.metrics().put_retry();
+                            sleep(retry_delay).await
+                        }
                         None => return Err(err),
                     }
                 }
@@ -79,7 +95,11 @@ impl Network {
         for duration in strategy.get_retry.backoff() {
             match self.get_record(addr.clone(), quorum).await {
                 // return success as single-element vec
-                Ok(Some(record)) => return Ok(Some(vec![record])),
+                Ok(Some(record)) => {
+                    #[cfg(feature = "open-metrics")]
+                    self.metrics().record_got(record.value.len());
+                    return Ok(Some(vec![record]));
+                }
                 // don't retry on split - return for caller to resolve
                 Err(err) if matches!(err, NetworkError::SplitRecord(_)) => {
                     return Err(err);
@@ -92,7 +112,11 @@ impl Network {
                 Ok(None) => {
                     warn!("Record not found at {addr}, retrying in {duration:?}");
                     match duration {
-                        Some(retry_delay) => sleep(retry_delay).await,
+                        Some(retry_delay) => {
+                            #[cfg(feature = "open-metrics")]
+                            self.metrics().get_retry();
+                            sleep(retry_delay).await
+                        }
                         None => break,
                     }
                 }
@@ -101,7 +125,11 @@ impl Network {
                     warn!("Get record failed at {addr}: {err:?}, retrying in {duration:?}");
                     errors.push(err.clone());
                     match duration {
-                        Some(retry_delay) => sleep(retry_delay).await,
+                        Some(retry_delay) => {
+                            #[cfg(feature = "open-metrics")]
+                            self.metrics().get_retry();
+                            sleep(retry_delay).await
+                        }
                         None => break,
                     }
                 }
@@ -181,6 +209,9 @@ impl Network {
                 "✅ Retrieved {} records from closest peers for {addr:?}",
                 records.len()
             );
+            #[cfg(feature = "open-metrics")]
+            self.metrics()
+                .record_got(records.iter().map(|record| record.value.len()).sum());
             Ok(Some(records))
         }
     }
@@ -1,5 +1,5 @@
 use crate::Multiaddr;
-use ant_protocol::CLOSE_GROUP_SIZE;
+use ant_protocol::{CLOSE_GROUP_SIZE, close_group::close_group_majority};
 use libp2p::kad::Quorum;
 use libp2p::multiaddr::Protocol;
 // @anselme: this is a duplicate function from ant_networking, wasn't sure where to place it
@@ -22,13 +22,6 @@ pub(crate) fn multiaddr_is_global(multiaddr: &Multiaddr) -> bool {
     })
 }
 
-/// Majority of a given group (i.e. > 1/2).
-pub const fn close_group_majority() -> usize {
-    // Calculate the majority of the close group size by dividing it by 2 and adding 1.
-    // This ensures that the majority is always greater than half.
-    CLOSE_GROUP_SIZE / 2 + 1
-}
-
 /// Get the value of the provided `Quorum` as usize.
 pub fn get_quorum_amount(quorum: &Quorum) -> usize {
     match quorum {
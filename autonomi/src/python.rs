@@ -217,6 +217,17 @@ impl PyClient {
         })
     }
 
+    /// Initialize a read-only client of the main network, which cannot pay for uploads.
+    #[staticmethod]
+    fn init_read_only(py: Python) -> PyResult<Bound<PyAny>> {
+        future_into_py(py, async {
+            let inner = Client::init_read_only()
+                .await
+                .map_err(|e| PyConnectionError::new_err(format!("Failed to connect: {e}")))?;
+            Ok(PyClient { inner })
+        })
+    }
+
     /// Initialize a client that is configured to be local.
     #[staticmethod]
     fn init_local(py: Python) -> PyResult<Bound<PyAny>> {
@@ -1576,6 +1587,8 @@ impl PyClientEvent {
             ClientEvent::UploadComplete(_) => "UploadComplete",
             ClientEvent::MerkleBatchPaymentComplete(_) => "MerkleBatchPaymentComplete",
             ClientEvent::RegularBatchPaymentComplete(_) => "RegularBatchPaymentComplete",
+            ClientEvent::UploadBatchPlanned(_) => "UploadBatchPlanned",
+            ClientEvent::QuotesRefreshed(_) => "QuotesRefreshed",
         }
     }
 
@@ -1587,6 +1600,8 @@ impl PyClientEvent {
             }),
             ClientEvent::MerkleBatchPaymentComplete(_) => None,
             ClientEvent::RegularBatchPaymentComplete(_) => None,
+            ClientEvent::UploadBatchPlanned(_) => None,
+            ClientEvent::QuotesRefreshed(_) => None,
         }
     }
 
@@ -2712,7 +2727,10 @@ impl Into<TransactionConfig> for PyTransactionConfig {
             PyMaxFeePerGas::Custom(limit) => MaxFeePerGas::Custom(limit),
         };
 
-        TransactionConfig { max_fee_per_gas }
+        TransactionConfig {
+            max_fee_per_gas,
+            ..Default::default()
+        }
     }
 }
 
@@ -4078,6 +4096,18 @@ impl PyMetadata {
     fn set_size(&mut self, value: u64) {
         self.inner.size = value;
     }
+
+    /// Get the MIME type of the file, if detected on upload
+    #[getter]
+    fn get_content_type(&self) -> Option<String> {
+        self.inner.content_type.clone()
+    }
+
+    /// Set the MIME type of the file
+    #[setter]
+    fn set_content_type(&mut self, value: Option<String>) {
+        self.inner.content_type = value;
+    }
 }
 
 /// A public archive containing files that can be accessed by anyone on the network.
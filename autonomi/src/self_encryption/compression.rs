@@ -0,0 +1,134 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Transparent compression of data before self-encryption.
+//!
+//! Compressed data is recorded by wrapping its data map chunk with the compression used, so that
+//! downloads know to decompress the decrypted data.
+
+use super::Error;
+use ant_protocol::storage::Chunk;
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+
+/// Compression applied to data before it is self-encrypted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Compression {
+    /// Data is encrypted as is
+    #[default]
+    None,
+    /// Zstandard compression at the given level, from 1 (fastest) to 22 (smallest)
+    Zstd(i32),
+}
+
+/// The content of the data map chunk of compressed data: the data map chunk of the compressed
+/// data, along with the compression to undo after decryption.
+#[derive(Serialize, Deserialize)]
+struct CompressedDataMap {
+    compression: Compression,
+    data_map: Bytes,
+}
+
+/// Compress data before encryption. Returns the data as is with [`Compression::None`] when
+/// compressing doesn't make it smaller, so incompressible data isn't flagged as compressed.
+pub(crate) fn compress(
+    data: Bytes,
+    compression: Compression,
+) -> Result<(Bytes, Compression), Error> {
+    let compressed = match compression {
+        Compression::None => return Ok((data, Compression::None)),
+        Compression::Zstd(level) => {
+            zstd::encode_all(&data[..], level).map_err(Error::Compression)?
+        }
+    };
+    if compressed.len() >= data.len() {
+        debug!(
+            "Compression of {} bytes doesn't reduce their size, storing them uncompressed",
+            data.len()
+        );
+        return Ok((data, Compression::None));
+    }
+    debug!(
+        "Compressed {} bytes to {} bytes with {compression:?}",
+        data.len(),
+        compressed.len()
+    );
+    Ok((Bytes::from(compressed), compression))
+}
+
+/// Decompress decrypted data.
+pub(crate) fn decompress(data: Bytes, compression: Compression) -> Result<Bytes, Error> {
+    match compression {
+        Compression::None => Ok(data),
+        Compression::Zstd(_) => zstd::decode_all(&data[..])
+            .map(Bytes::from)
+            .map_err(Error::Compression),
+    }
+}
+
+/// Record the compression of the data in its data map chunk.
+pub(crate) fn wrap_data_map_chunk(
+    data_map_chunk: Chunk,
+    compression: Compression,
+) -> Result<Chunk, Error> {
+    if compression == Compression::None {
+        return Ok(data_map_chunk);
+    }
+    let wrapped = rmp_serde::to_vec(&CompressedDataMap {
+        compression,
+        data_map: data_map_chunk.value,
+    })?;
+    Ok(Chunk::new(Bytes::from(wrapped)))
+}
+
+/// Read the compression recorded in a data map chunk, along with the content of the data map chunk
+/// of the compressed data. Returns `None` for data stored uncompressed.
+pub(crate) fn unwrap_data_map_chunk(data_map_bytes: &Bytes) -> Option<(Compression, Bytes)> {
+    let wrapped: CompressedDataMap = rmp_serde::from_slice(data_map_bytes).ok()?;
+    Some((wrapped.compression, wrapped.data_map))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compression_roundtrip() {
+        let data = Bytes::from("all work and no play makes jack a dull boy. ".repeat(1000));
+        let (compressed, compression) = compress(data.clone(), Compression::Zstd(3)).unwrap();
+        assert_eq!(compression, Compression::Zstd(3));
+        assert!(compressed.len() < data.len());
+        assert_eq!(decompress(compressed, compression).unwrap(), data);
+
+        // incompressible data is left as is
+        let data = Bytes::from_static(b"abc");
+        let (uncompressed, compression) = compress(data.clone(), Compression::Zstd(3)).unwrap();
+        assert_eq!(compression, Compression::None);
+        assert_eq!(uncompressed, data);
+    }
+
+    #[test]
+    fn test_wrapped_data_map_chunk() {
+        let data = Bytes::from(vec![7u8; 1024 * 1024]);
+        let (data_map, _chunks) = self_encryption::encrypt(data).unwrap();
+        let data_map_bytes = Bytes::from(rmp_serde::to_vec(&data_map).unwrap());
+        let data_map_chunk = Chunk::new(data_map_bytes.clone());
+
+        // uncompressed data map chunks are left untouched
+        let chunk = wrap_data_map_chunk(data_map_chunk.clone(), Compression::None).unwrap();
+        assert_eq!(chunk, data_map_chunk);
+        assert!(unwrap_data_map_chunk(&chunk.value).is_none());
+
+        let chunk = wrap_data_map_chunk(data_map_chunk, Compression::Zstd(3)).unwrap();
+        assert!(rmp_serde::from_slice::<self_encryption::DataMap>(&chunk.value).is_err());
+        assert_eq!(
+            unwrap_data_map_chunk(&chunk.value),
+            Some((Compression::Zstd(3), data_map_bytes))
+        );
+    }
+}
@@ -19,6 +19,8 @@ pub enum Error {
     Encoding(#[from] rmp_serde::encode::Error),
     #[error(transparent)]
     SelfEncryption(#[from] self_encryption::Error),
+    #[error("Compression error: {0}")]
+    Compression(std::io::Error),
 }
 
 #[derive(Serialize, Deserialize)]
@@ -6,9 +6,13 @@
 // KIND, either express or implied. Please review the Licences for the specific language governing
 // permissions and limitations relating to use of the SAFE Network Software.
 
+mod compression;
 mod memory_encryption;
 mod stream_encryption;
 
+pub use compression::Compression;
+pub(crate) use compression::{compress, decompress, unwrap_data_map_chunk, wrap_data_map_chunk};
 pub use memory_encryption::{DataMapLevel, Error, encrypt};
 pub use self_encryption::MAX_CHUNK_SIZE;
+pub(crate) use stream_encryption::encrypt_file;
 pub use stream_encryption::{EncryptionStream, encrypt_directory_files};
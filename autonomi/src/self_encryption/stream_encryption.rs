@@ -8,7 +8,7 @@
 
 use crate::client::data_types::chunk::DataMapChunk;
 use crate::files::{Metadata, get_relative_file_path_from_abs_file_and_folder_path};
-use crate::self_encryption::encrypt;
+use crate::self_encryption::{Compression, compress, encrypt, wrap_data_map_chunk};
 use ant_protocol::storage::Chunk;
 use bytes::Bytes;
 use self_encryption::MAX_CHUNK_SIZE;
@@ -174,9 +174,31 @@ impl EncryptionStream {
         metadata: Metadata,
         is_public: bool,
         bytes: Bytes,
+    ) -> Result<(Self, DataMapChunk), crate::self_encryption::Error> {
+        Self::new_in_memory_compressed(
+            file_path,
+            relative_path,
+            metadata,
+            is_public,
+            bytes,
+            Compression::None,
+        )
+    }
+
+    /// Same as [`EncryptionStream::new_in_memory_with`], compressing the data before encrypting
+    /// it. The compression is recorded in the data map chunk, for downloads to decompress the data.
+    pub(crate) fn new_in_memory_compressed(
+        file_path: String,
+        relative_path: PathBuf,
+        metadata: Metadata,
+        is_public: bool,
+        bytes: Bytes,
+        compression: Compression,
     ) -> Result<(Self, DataMapChunk), crate::self_encryption::Error> {
         let start = Instant::now();
+        let (bytes, compression) = compress(bytes, compression)?;
         let (data_map_chunk, mut chunks) = encrypt(bytes)?;
+        let data_map_chunk = wrap_data_map_chunk(data_map_chunk, compression)?;
 
         if is_public {
             chunks.push(data_map_chunk.clone());
@@ -214,6 +236,32 @@ impl EncryptionStream {
         is_public: bool,
         file_size: usize,
     ) -> Result<Self, String> {
+        let path = file_path.clone();
+        Self::new_stream_with(
+            file_path,
+            relative_path,
+            metadata,
+            is_public,
+            file_size,
+            move || create_file_data_iterator(&path),
+        )
+    }
+
+    /// Start a streaming encryption of `data_size` bytes read from the iterator returned by
+    /// `open_data`, which is called on the encryption thread. `file_path` only names the source
+    /// in logs and errors, it doesn't need to exist on disk.
+    pub(crate) fn new_stream_with<I, F>(
+        file_path: String,
+        relative_path: PathBuf,
+        metadata: Metadata,
+        is_public: bool,
+        file_size: usize,
+        open_data: F,
+    ) -> Result<Self, String>
+    where
+        I: Iterator<Item = Bytes>,
+        F: FnOnce() -> std::io::Result<I> + Send + 'static,
+    {
         let start = Instant::now();
         let (chunk_sender, chunk_receiver) =
             std::sync::mpsc::sync_channel(STREAM_CHUNK_CHANNEL_CAPACITY);
@@ -222,11 +270,11 @@ impl EncryptionStream {
 
         // Spawn a thread to handle streaming encryption
         std::thread::spawn(move || {
-            // Create iterator that reads file in chunks
-            let data_iter = match create_file_data_iterator(&file_path_clone) {
+            // Create iterator that reads the data in chunks
+            let data_iter = match open_data() {
                 Ok(iter) => iter,
                 Err(e) => {
-                    error!("Failed to open file {file_path_clone}: {e}");
+                    error!("Failed to open {file_path_clone}: {e}");
                     return;
                 }
             };
@@ -307,9 +355,11 @@ impl EncryptionStream {
 }
 
 /// Encrypts all files in a directory and returns the encryption results (common logic)
+/// Files small enough to be encrypted in memory are compressed first with `compression`.
 pub async fn encrypt_directory_files(
     dir_path: PathBuf,
     is_public: bool,
+    compression: Compression,
 ) -> Result<Vec<Result<EncryptionStream, String>>, walkdir::Error> {
     let mut encryption_tasks = vec![];
 
@@ -332,7 +382,15 @@ pub async fn encrypt_directory_files(
                 .metadata()
                 .map_err(|err| format!("Error getting file size {file_path:?}: {err:?}"))?
                 .len() as usize;
-            encrypt_file(relative_path, file_path, file_size, metadata, is_public).await
+            encrypt_file(
+                relative_path,
+                file_path,
+                file_size,
+                metadata,
+                is_public,
+                compression,
+            )
+            .await
         });
     }
 
@@ -348,6 +406,7 @@ pub(crate) async fn encrypt_file(
     file_size: usize,
     metadata: Metadata,
     is_public: bool,
+    compression: Compression,
 ) -> Result<EncryptionStream, String> {
     crate::loud_info!("Encrypting file: {file_path:?}..");
 
@@ -355,7 +414,7 @@ pub(crate) async fn encrypt_file(
     if file_size > *IN_MEMORY_ENCRYPTION_MAX_SIZE {
         encrypt_file_in_stream(file_path, is_public, metadata, relative_path, file_size)
     } else {
-        encrypt_file_in_memory(file_path, is_public, metadata, relative_path).await
+        encrypt_file_in_memory(file_path, is_public, metadata, relative_path, compression).await
     }
 }
 
@@ -381,6 +440,7 @@ async fn encrypt_file_in_memory(
     is_public: bool,
     metadata: Metadata,
     relative_path: PathBuf,
+    compression: Compression,
 ) -> Result<EncryptionStream, String> {
     info!("Encrypting file in memory: {file_path:?}..");
     let data = tokio::fs::read(&file_path)
@@ -394,12 +454,13 @@ async fn encrypt_file_in_memory(
     }
 
     let start = Instant::now();
-    let (file_chunk_iterator, _data_map) = EncryptionStream::new_in_memory_with(
+    let (file_chunk_iterator, _data_map) = EncryptionStream::new_in_memory_compressed(
         file_path.to_string_lossy().to_string(),
         relative_path,
         metadata,
         is_public,
         data,
+        compression,
     )
     .map_err(|err| format!("Error encrypting file {file_path:?}: {err:?}"))?;
 
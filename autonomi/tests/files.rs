@@ -148,6 +148,8 @@ async fn file_advanced_use() -> Result<()> {
         modified: 84,
         size: 126,
         extra: Some("custom metadata".to_string()),
+        content_type: None,
+        packed: None,
     };
     archive.add_file("example_file_b".into(), file_datamap, custom_metadata);
 
@@ -8,7 +8,12 @@
 
 use ant_evm::AttoTokens;
 use ant_logging::LogBuilder;
-use autonomi::{Client, vault::vault_content_type_from_app_name};
+use autonomi::{
+    Client, XorName,
+    files::archive_public::ArchiveAddress,
+    register::RegisterAddress,
+    vault::{UserData, vault_content_type_from_app_name},
+};
 use eyre::Result;
 use serial_test::serial;
 use test_utils::{evm::get_funded_wallet, gen_random_data};
@@ -98,3 +103,43 @@ async fn vault_expand() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+#[serial]
+async fn vault_export_import_user_data() -> Result<()> {
+    let _log_appender_guard = LogBuilder::init_single_threaded_tokio_test();
+    let client = Client::init_local().await?;
+    let wallet = get_funded_wallet();
+    let main_key = bls::SecretKey::random();
+
+    let mut user_data = UserData::new();
+    let _ = user_data.add_file_archive_with_name(
+        ArchiveAddress::new(XorName::random(&mut rand::thread_rng())),
+        "archive".to_string(),
+    );
+    let _ = user_data.add_register(
+        RegisterAddress::new(bls::SecretKey::random().public_key()),
+        "register".to_string(),
+    );
+    let _ = client
+        .vault_put_user_data(&main_key, wallet.clone().into(), user_data.clone())
+        .await?;
+
+    let backup_dir = tempfile::tempdir()?;
+    let backup_path = backup_dir.path().join("vault.backup");
+    client
+        .vault_export(&main_key, &backup_path, "correct horse")
+        .await?;
+
+    // Import the backup into a fresh vault, whose scratchpads don't exist yet
+    let new_key = bls::SecretKey::random();
+    let cost = client
+        .vault_import(&new_key, &backup_path, "correct horse", wallet.into())
+        .await?;
+    assert!(!cost.is_zero());
+
+    let imported = client.vault_get_user_data(&new_key).await?;
+    assert_eq!(imported, user_data);
+
+    Ok(())
+}
@@ -9,10 +9,12 @@
 // Allow expect usage in this testnet binary
 #![allow(clippy::expect_used)]
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use evmlib::common::{Address, Amount};
-use evmlib::testnet::Testnet;
+use evmlib::testnet::{Testnet, TestnetSnapshot, snapshot_node};
+use evmlib::utils::get_evm_network;
 use evmlib::wallet::{Wallet, balance_of_gas_tokens, balance_of_tokens};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 /// A tool to start a local Ethereum node.
@@ -22,12 +24,47 @@ struct Args {
     /// Wallet that will hold ~all gas funds and payment tokens.
     #[clap(long, short)]
     genesis_wallet: Option<Address>,
+
+    #[clap(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Save the chain state of a running local testnet to a file.
+    ///
+    /// The testnet is located using the same env vars as the CLI and Node, defaulting to the
+    /// local testnet started by this tool.
+    Snapshot {
+        /// File to write the snapshot to.
+        #[clap(long, short)]
+        output: PathBuf,
+    },
+    /// Start a local testnet from a snapshot file instead of deploying the contracts.
+    Restore {
+        /// Snapshot file, as written by the `snapshot` command.
+        #[clap(long, short)]
+        input: PathBuf,
+    },
 }
 
 #[tokio::main]
 async fn main() {
     let args = Args::parse();
-    let testnet = Testnet::new().await;
+
+    let testnet = match args.command {
+        Some(Command::Snapshot { output }) => {
+            snapshot(&output).await;
+            return;
+        }
+        Some(Command::Restore { input }) => {
+            let snapshot = read_snapshot(&input);
+            Testnet::from_snapshot(&snapshot)
+                .await
+                .expect("Could not restore testnet from snapshot")
+        }
+        None => Testnet::new().await,
+    };
 
     println!("*************************");
     println!("* Ethereum node started *");
@@ -46,6 +83,38 @@ async fn main() {
     println!("Ethereum node stopped.");
 }
 
+async fn snapshot(output: &Path) {
+    let network = get_evm_network(true, None).expect("Could not get local EVM network");
+    let merkle_payments_address = *network
+        .merkle_payments_address()
+        .expect("Local EVM network has no merkle payments address");
+
+    let state = snapshot_node(network.rpc_url())
+        .await
+        .expect("Could not snapshot the testnet, is it running?");
+
+    let snapshot = TestnetSnapshot {
+        state,
+        payment_token_address: *network.payment_token_address(),
+        data_payments_address: *network.data_payments_address(),
+        merkle_payments_address,
+    };
+
+    let json = serde_json::to_string(&snapshot).expect("Could not serialize snapshot");
+    std::fs::write(output, json).expect("Could not write snapshot file");
+
+    println!(
+        "Snapshot of {} written to {}",
+        network.rpc_url(),
+        output.display()
+    );
+}
+
+fn read_snapshot(input: &Path) -> TestnetSnapshot {
+    let json = std::fs::read_to_string(input).expect("Could not read snapshot file");
+    serde_json::from_str(&json).expect("Could not parse snapshot file")
+}
+
 async fn transfer_funds(testnet: &Testnet, genesis_wallet: Address) {
     let wallet =
         Wallet::new_from_private_key(testnet.to_network(), &testnet.default_wallet_private_key())
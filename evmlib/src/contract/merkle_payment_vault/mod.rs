@@ -9,6 +9,7 @@
 use crate::contract::merkle_payment_vault::error::Error;
 use crate::contract::merkle_payment_vault::handler::MerklePaymentVaultHandler;
 use crate::merkle_batch_payment::{PoolCommitmentPacked, PoolHash};
+use crate::rpc_endpoints::with_rpc_failover;
 use crate::utils::http_provider;
 
 pub mod error;
@@ -32,10 +33,12 @@ pub async fn get_merkle_payment_info(
         hex::encode(winner_pool_hash)
     );
 
-    let provider = http_provider(network.rpc_url().clone());
-    let merkle_vault = MerklePaymentVaultHandler::new(*merkle_vault_address, provider);
-
-    merkle_vault.get_payment_info(winner_pool_hash).await
+    with_rpc_failover(network, "get_merkle_payment_info", |rpc_url| async move {
+        let provider = http_provider(rpc_url);
+        let merkle_vault = MerklePaymentVaultHandler::new(*merkle_vault_address, provider);
+        merkle_vault.get_payment_info(winner_pool_hash).await
+    })
+    .await
 }
 
 /// Helper function to get the packed pool commitments from a payment transaction's calldata.
@@ -52,12 +55,18 @@ pub async fn get_merkle_payment_packed_commitments(
         .merkle_payments_address()
         .ok_or(Error::MerklePaymentsAddressNotConfigured)?;
 
-    let provider = http_provider(network.rpc_url().clone());
-    let merkle_vault = MerklePaymentVaultHandler::new(*merkle_vault_address, provider);
-
-    merkle_vault
-        .get_payment_packed_commitments(winner_pool_hash, merkle_payment_timestamp)
-        .await
+    with_rpc_failover(
+        network,
+        "get_merkle_payment_packed_commitments",
+        |rpc_url| async move {
+            let provider = http_provider(rpc_url);
+            let merkle_vault = MerklePaymentVaultHandler::new(*merkle_vault_address, provider);
+            merkle_vault
+                .get_payment_packed_commitments(winner_pool_hash, merkle_payment_timestamp)
+                .await
+        },
+    )
+    .await
 }
 
 #[cfg(test)]
@@ -18,3 +18,25 @@ pub enum Error {
     #[error(transparent)]
     Transaction(#[from] retry::TransactionError),
 }
+
+impl Error {
+    /// Returns true if the error was caused by the RPC endpoint rate limiting our requests,
+    /// or by the endpoint being temporarily unavailable.
+    pub fn is_rate_limited(&self) -> bool {
+        match self {
+            Error::ContractError(alloy::contract::Error::TransportError(err))
+            | Error::RpcError(err) => is_rate_limit_rpc_error(err),
+            _ => false,
+        }
+    }
+}
+
+fn is_rate_limit_rpc_error(err: &RpcError<TransportErrorKind>) -> bool {
+    if let Some(transport_err) = err.as_transport_err() {
+        return transport_err.is_retry_err();
+    }
+    if let Some(error_resp) = err.as_error_resp() {
+        return error_resp.is_retry_err();
+    }
+    false
+}
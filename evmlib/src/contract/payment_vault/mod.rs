@@ -2,6 +2,7 @@ use crate::Network;
 use crate::common::{Address, Amount, QuoteHash};
 use crate::contract::payment_vault::handler::PaymentVaultHandler;
 use crate::quoting_metrics::QuotingMetrics;
+use crate::rpc_endpoints::with_rpc_failover;
 use crate::utils::http_provider;
 
 pub mod error;
@@ -16,9 +17,16 @@ pub async fn get_market_price(
     network: &Network,
     quoting_metrics: Vec<QuotingMetrics>,
 ) -> Result<Vec<Amount>, error::Error> {
-    let provider = http_provider(network.rpc_url().clone());
-    let payment_vault = PaymentVaultHandler::new(*network.data_payments_address(), provider);
-    payment_vault.get_quote(quoting_metrics).await
+    with_rpc_failover(network, "getQuote", |rpc_url| {
+        let quoting_metrics = quoting_metrics.clone();
+        async move {
+            let provider = http_provider(rpc_url);
+            let payment_vault =
+                PaymentVaultHandler::new(*network.data_payments_address(), provider);
+            payment_vault.get_quote(quoting_metrics).await
+        }
+    })
+    .await
 }
 
 /// Helper function to verify whether a data payment is valid.
@@ -28,9 +36,6 @@ pub async fn verify_data_payment(
     owned_quote_hashes: Vec<QuoteHash>,
     payment: Vec<(QuoteHash, QuotingMetrics, Address)>,
 ) -> Result<Amount, error::Error> {
-    let provider = http_provider(network.rpc_url().clone());
-    let payment_vault = PaymentVaultHandler::new(*network.data_payments_address(), provider);
-
     let mut amount = Amount::ZERO;
 
     let payment_verifications: Vec<_> = payment
@@ -38,7 +43,16 @@ pub async fn verify_data_payment(
         .map(interface::IPaymentVault::PaymentVerification::from)
         .collect();
 
-    let payment_verification_results = payment_vault.verify_payment(payment_verifications).await?;
+    let payment_verification_results = with_rpc_failover(network, "verifyPayment", |rpc_url| {
+        let payment_verifications = payment_verifications.clone();
+        async move {
+            let provider = http_provider(rpc_url);
+            let payment_vault =
+                PaymentVaultHandler::new(*network.data_payments_address(), provider);
+            payment_vault.verify_payment(payment_verifications).await
+        }
+    })
+    .await?;
 
     for payment_verification_result in payment_verification_results {
         // TODO we currently fail on a single invalid payment, maybe we should deal with this in a different way
@@ -0,0 +1,476 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Discovery of the contract addresses of the EVM networks from a signed manifest.
+//!
+//! The addresses of the contracts of [`crate::Network::ArbitrumOne`] and
+//! [`crate::Network::ArbitrumSepoliaTest`] are compiled in, which means that upgrading a contract
+//! would require a new release of every client. A [`ContractRegistry`] fetches a JSON manifest over
+//! HTTP listing the current addresses per network, verifies that it was signed by a trusted EVM
+//! address, and installs it so that [`crate::Network`] returns the discovered addresses instead of
+//! the compiled in ones. Networks missing from the manifest keep their compiled in addresses.
+//!
+//! The URL of the manifest and the address it must be signed by are pinned per network in the
+//! binary, see [`ContractRegistry::for_network`]. The signer addresses are set when building a
+//! release, discovery being disabled for the networks without one.
+//!
+//! The manifest is installed once per process: a contract upgrade is picked up on the next start.
+//! The last verified manifest is cached on disk, so that it is only fetched again once stale, and
+//! still used when the registry is unreachable. A fetched manifest whose version is older than the
+//! cached one is rejected, so that an old manifest can't be replayed to roll the addresses back.
+
+use crate::Network;
+use crate::common::Address;
+use alloy::primitives::{Signature, SignatureError};
+use alloy::signers::SignerSync;
+use alloy::signers::local::PrivateKeySigner;
+use alloy::transports::http::reqwest;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime};
+
+/// Env var overriding the URL of the contract manifest, only honoured by test builds.
+pub const CONTRACT_REGISTRY_URL: &str = "CONTRACT_REGISTRY_URL";
+/// Env var overriding the EVM address the contract manifest must be signed by, only honoured by
+/// test builds.
+pub const CONTRACT_REGISTRY_SIGNER: &str = "CONTRACT_REGISTRY_SIGNER";
+
+/// Where the contract manifest of [`Network::ArbitrumOne`] is published.
+const ARBITRUM_ONE_REGISTRY_URL: &str =
+    "https://sn-testnet.s3.eu-west-2.amazonaws.com/contract-registry/arbitrum-one.json";
+
+/// Where the contract manifest of [`Network::ArbitrumSepoliaTest`] is published.
+const ARBITRUM_SEPOLIA_TEST_REGISTRY_URL: &str =
+    "https://sn-testnet.s3.eu-west-2.amazonaws.com/contract-registry/arbitrum-sepolia-test.json";
+
+/// The address the contract manifest of [`Network::ArbitrumOne`] must be signed by, compiled in
+/// from the build environment of a release.
+const ARBITRUM_ONE_REGISTRY_SIGNER: Option<&str> =
+    option_env!("ARBITRUM_ONE_CONTRACT_REGISTRY_SIGNER");
+
+/// The address the contract manifest of [`Network::ArbitrumSepoliaTest`] must be signed by,
+/// compiled in from the build environment of a release.
+const ARBITRUM_SEPOLIA_TEST_REGISTRY_SIGNER: Option<&str> =
+    option_env!("ARBITRUM_SEPOLIA_TEST_CONTRACT_REGISTRY_SIGNER");
+
+/// Duration after which a cached manifest is fetched again.
+const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// Timeout of the manifest download.
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The manifest installed for this process.
+static INSTALLED_MANIFEST: OnceLock<ContractManifest> = OnceLock::new();
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Failed to fetch the contract manifest: {0}")]
+    Fetch(#[from] reqwest::Error),
+    #[error("Failed to read or write the cached contract manifest: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid contract manifest: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Invalid contract manifest signature: {0}")]
+    InvalidSignature(String),
+    #[error("Contract manifest signed by {signer}, expected {expected}")]
+    UntrustedSigner { signer: Address, expected: Address },
+    #[error("Contract manifest version {version} is not newer than the cached version {cached}")]
+    StaleManifest { version: u64, cached: u64 },
+    #[error("Invalid contract registry configuration: {0}")]
+    InvalidConfig(String),
+}
+
+impl From<SignatureError> for Error {
+    fn from(err: SignatureError) -> Self {
+        Self::InvalidSignature(err.to_string())
+    }
+}
+
+/// The addresses of the contracts of a network.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractAddresses {
+    pub payment_token_address: Address,
+    pub data_payments_address: Address,
+    pub merkle_payments_address: Option<Address>,
+}
+
+/// The contract addresses of every network, keyed by [`crate::Network::identifier`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractManifest {
+    /// Increased by every new manifest
+    pub version: u64,
+    pub networks: HashMap<String, ContractAddresses>,
+}
+
+/// A [`ContractManifest`] along with the signature of its JSON serialization.
+///
+/// The manifest is kept serialized so that the signed bytes are the ones verified.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedContractManifest {
+    /// The JSON serialization of the manifest
+    pub manifest: String,
+    /// The hex encoded EIP-191 signature of `manifest`
+    pub signature: String,
+}
+
+impl SignedContractManifest {
+    /// Serialize and sign a manifest.
+    pub fn sign(manifest: &ContractManifest, signer: &PrivateKeySigner) -> Result<Self, Error> {
+        let manifest = serde_json::to_string(manifest)?;
+        let signature = signer
+            .sign_message_sync(manifest.as_bytes())
+            .map_err(|err| Error::InvalidSignature(err.to_string()))?;
+        Ok(Self {
+            manifest,
+            signature: hex::encode(signature.as_bytes()),
+        })
+    }
+
+    /// Check that the manifest was signed by `trusted_signer`, and deserialize it.
+    pub fn verify(&self, trusted_signer: &Address) -> Result<ContractManifest, Error> {
+        let signature_bytes = hex::decode(self.signature.trim_start_matches("0x"))
+            .map_err(|err| Error::InvalidSignature(err.to_string()))?;
+        let signature = Signature::try_from(signature_bytes.as_slice())?;
+        let signer = signature.recover_address_from_msg(self.manifest.as_bytes())?;
+        if &signer != trusted_signer {
+            return Err(Error::UntrustedSigner {
+                signer,
+                expected: *trusted_signer,
+            });
+        }
+        Ok(serde_json::from_str(&self.manifest)?)
+    }
+}
+
+/// Where to fetch the contract manifest from, and who must have signed it.
+#[derive(Clone, Debug)]
+pub struct ContractRegistry {
+    url: reqwest::Url,
+    trusted_signer: Address,
+    cache_path: Option<PathBuf>,
+    cache_ttl: Duration,
+}
+
+impl ContractRegistry {
+    pub fn new(url: reqwest::Url, trusted_signer: Address) -> Self {
+        Self {
+            url,
+            trusted_signer,
+            cache_path: None,
+            cache_ttl: DEFAULT_CACHE_TTL,
+        }
+    }
+
+    /// The registry pinned in this binary for `network`, caching its manifest in the data dir.
+    /// Returns `None` for custom networks, and for the networks without a pinned signer.
+    ///
+    /// Test builds can override the registry through the [`CONTRACT_REGISTRY_URL`] and
+    /// [`CONTRACT_REGISTRY_SIGNER`] env vars, in which case the manifest is not cached.
+    pub fn for_network(network: &Network) -> Result<Option<Self>, Error> {
+        #[cfg(feature = "test-utils")]
+        if let Some(registry) = Self::from_env()? {
+            return Ok(Some(registry));
+        }
+
+        let (url, signer) = match network {
+            Network::ArbitrumOne => (ARBITRUM_ONE_REGISTRY_URL, ARBITRUM_ONE_REGISTRY_SIGNER),
+            Network::ArbitrumSepoliaTest => (
+                ARBITRUM_SEPOLIA_TEST_REGISTRY_URL,
+                ARBITRUM_SEPOLIA_TEST_REGISTRY_SIGNER,
+            ),
+            Network::Custom(_) => return Ok(None),
+        };
+        let Some(signer) = signer else {
+            debug!(
+                "No contract registry signer pinned for {}",
+                network.identifier()
+            );
+            return Ok(None);
+        };
+        let url = reqwest::Url::parse(url)
+            .map_err(|err| Error::InvalidConfig(format!("registry URL {url}: {err}")))?;
+        let trusted_signer = Address::from_str(signer)
+            .map_err(|err| Error::InvalidConfig(format!("registry signer {signer}: {err}")))?;
+
+        let registry = Self::new(url, trusted_signer);
+        Ok(Some(match default_cache_path(network) {
+            Some(cache_path) => registry.with_cache_path(cache_path),
+            None => registry,
+        }))
+    }
+
+    /// The registry configured through the [`CONTRACT_REGISTRY_URL`] and
+    /// [`CONTRACT_REGISTRY_SIGNER`] env vars, if the URL is set.
+    #[cfg(feature = "test-utils")]
+    pub fn from_env() -> Result<Option<Self>, Error> {
+        let Ok(url) = std::env::var(CONTRACT_REGISTRY_URL) else {
+            return Ok(None);
+        };
+        let url = reqwest::Url::parse(&url)
+            .map_err(|err| Error::InvalidConfig(format!("{CONTRACT_REGISTRY_URL}: {err}")))?;
+        let signer = std::env::var(CONTRACT_REGISTRY_SIGNER)
+            .map_err(|_| Error::InvalidConfig(format!("{CONTRACT_REGISTRY_SIGNER} is not set")))?;
+        let trusted_signer = Address::from_str(&signer)
+            .map_err(|err| Error::InvalidConfig(format!("{CONTRACT_REGISTRY_SIGNER}: {err}")))?;
+        Ok(Some(Self::new(url, trusted_signer)))
+    }
+
+    /// Cache the last verified manifest in a file.
+    pub fn with_cache_path(mut self, cache_path: PathBuf) -> Self {
+        self.cache_path = Some(cache_path);
+        self
+    }
+
+    /// Duration after which the cached manifest is fetched again.
+    pub fn with_cache_ttl(mut self, cache_ttl: Duration) -> Self {
+        self.cache_ttl = cache_ttl;
+        self
+    }
+
+    /// Get the verified manifest, from the cache if fresh enough, otherwise from the registry.
+    /// A stale cached manifest is used when the registry can't be reached, or when it serves an
+    /// older manifest than the cached one.
+    pub async fn manifest(&self) -> Result<ContractManifest, Error> {
+        let cached = self
+            .cache_path
+            .as_deref()
+            .and_then(|path| self.read_cache(path));
+        if let Some((manifest, age)) = &cached
+            && *age < self.cache_ttl
+        {
+            return Ok(manifest.clone());
+        }
+
+        let fetched = self.fetch().await.and_then(|(signed, manifest)| {
+            if let Some((cached, _)) = &cached {
+                check_newer(&manifest, cached)?;
+            }
+            Ok((signed, manifest))
+        });
+        match fetched {
+            Ok((signed, manifest)) => {
+                if let Some(path) = &self.cache_path
+                    && let Err(err) = write_cache(path, &signed)
+                {
+                    warn!("Failed to cache the contract manifest at {path:?}: {err}");
+                }
+                Ok(manifest)
+            }
+            Err(err) => match cached {
+                Some((manifest, _)) => {
+                    warn!("Failed to fetch the contract manifest, using the cached one: {err}");
+                    Ok(manifest)
+                }
+                None => Err(err),
+            },
+        }
+    }
+
+    /// Get the verified manifest and install it for this process.
+    /// Returns false if a manifest was already installed.
+    pub async fn discover(&self) -> Result<bool, Error> {
+        let manifest = self.manifest().await?;
+        Ok(install_manifest(manifest))
+    }
+
+    async fn fetch(&self) -> Result<(SignedContractManifest, ContractManifest), Error> {
+        debug!("Fetching the contract manifest from {}", self.url);
+        let response = reqwest::Client::new()
+            .get(self.url.clone())
+            .timeout(FETCH_TIMEOUT)
+            .send()
+            .await?
+            .error_for_status()?;
+        let signed: SignedContractManifest = serde_json::from_slice(&response.bytes().await?)?;
+        let manifest = signed.verify(&self.trusted_signer)?;
+        Ok((signed, manifest))
+    }
+
+    /// The cached manifest, if it can still be verified, along with its age.
+    fn read_cache(&self, path: &Path) -> Option<(ContractManifest, Duration)> {
+        let content = std::fs::read(path).ok()?;
+        let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+        let signed: SignedContractManifest = serde_json::from_slice(&content).ok()?;
+        match signed.verify(&self.trusted_signer) {
+            Ok(manifest) => {
+                let age = SystemTime::now()
+                    .duration_since(modified)
+                    .unwrap_or_default();
+                Some((manifest, age))
+            }
+            Err(err) => {
+                warn!("Ignoring the cached contract manifest at {path:?}: {err}");
+                None
+            }
+        }
+    }
+}
+
+/// Check that a fetched manifest doesn't roll back the cached one: its version must be newer,
+/// unless it is the cached manifest itself.
+fn check_newer(fetched: &ContractManifest, cached: &ContractManifest) -> Result<(), Error> {
+    if fetched.version > cached.version || fetched == cached {
+        return Ok(());
+    }
+    Err(Error::StaleManifest {
+        version: fetched.version,
+        cached: cached.version,
+    })
+}
+
+/// The file caching the manifest of `network`, in the platform data dir, e.g.
+/// `$HOME/.local/share/autonomi/contract_registry/arbitrum-one.json` on Linux.
+fn default_cache_path(network: &Network) -> Option<PathBuf> {
+    let Some(data_dir) = dirs_next::data_dir() else {
+        warn!("Failed to obtain the data directory, not caching the contract manifest");
+        return None;
+    };
+    Some(
+        data_dir
+            .join("autonomi")
+            .join("contract_registry")
+            .join(format!("{}.json", network.identifier())),
+    )
+}
+
+fn write_cache(path: &Path, signed: &SignedContractManifest) -> Result<(), Error> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp_path = path.with_extension("tmp");
+    std::fs::write(&tmp_path, serde_json::to_vec_pretty(signed)?)?;
+    std::fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+/// Install a verified manifest for this process. Returns false if one was already installed.
+pub fn install_manifest(manifest: ContractManifest) -> bool {
+    let version = manifest.version;
+    let installed = INSTALLED_MANIFEST.set(manifest).is_ok();
+    if installed {
+        info!("Installed version {version} of the contract manifest");
+    }
+    installed
+}
+
+/// Discover the contract addresses of `network` from the registry pinned for it, if any.
+/// Returns true if a manifest was installed.
+///
+/// Clients and nodes shall both call this before paying or verifying payments, so that they agree
+/// on the contracts.
+pub async fn discover_contracts(network: &Network) -> Result<bool, Error> {
+    match ContractRegistry::for_network(network)? {
+        Some(registry) => registry.discover().await,
+        None => Ok(false),
+    }
+}
+
+/// The discovered contract addresses of a network, if any.
+pub fn discovered_contracts(network_identifier: &str) -> Option<&'static ContractAddresses> {
+    INSTALLED_MANIFEST.get()?.networks.get(network_identifier)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::address;
+
+    fn manifest() -> ContractManifest {
+        ContractManifest {
+            version: 2,
+            networks: HashMap::from([(
+                "arbitrum-one".to_string(),
+                ContractAddresses {
+                    payment_token_address: address!("a78d8321B20c4Ef90eCd72f2588AA985A4BDb684"),
+                    data_payments_address: address!("1111111111111111111111111111111111111111"),
+                    merkle_payments_address: None,
+                },
+            )]),
+        }
+    }
+
+    #[test]
+    fn signed_manifest_is_verified() {
+        let signer = PrivateKeySigner::random();
+        let signed = SignedContractManifest::sign(&manifest(), &signer).unwrap();
+        assert_eq!(signed.verify(&signer.address()).unwrap(), manifest());
+
+        let other_signer = PrivateKeySigner::random();
+        assert!(matches!(
+            signed.verify(&other_signer.address()),
+            Err(Error::UntrustedSigner { .. })
+        ));
+
+        let tampered = SignedContractManifest {
+            manifest: signed.manifest.replace("1111", "2222"),
+            ..signed
+        };
+        assert!(tampered.verify(&signer.address()).is_err());
+    }
+
+    #[tokio::test]
+    async fn fresh_cached_manifest_is_used_without_fetching() {
+        let signer = PrivateKeySigner::random();
+        let signed = SignedContractManifest::sign(&manifest(), &signer).unwrap();
+        let cache_dir = tempfile::tempdir().unwrap();
+        let cache_path = cache_dir.path().join("contracts.json");
+        write_cache(&cache_path, &signed).unwrap();
+
+        // Nothing listens there, so the manifest can only come from the cache
+        let url = reqwest::Url::parse("http://127.0.0.1:9/contracts.json").unwrap();
+        let registry = ContractRegistry::new(url.clone(), signer.address())
+            .with_cache_path(cache_path.clone());
+        assert_eq!(registry.manifest().await.unwrap(), manifest());
+
+        // A cached manifest signed by someone else is ignored
+        let registry = ContractRegistry::new(url, PrivateKeySigner::random().address())
+            .with_cache_path(cache_path);
+        assert!(registry.manifest().await.is_err());
+    }
+
+    #[test]
+    fn older_manifest_is_rejected() {
+        let cached = manifest();
+
+        let mut older = manifest();
+        older.version = cached.version - 1;
+        assert!(matches!(
+            check_newer(&older, &cached),
+            Err(Error::StaleManifest {
+                version: 1,
+                cached: 2
+            })
+        ));
+
+        // Same version, other addresses: a replay of another manifest
+        let mut replayed = manifest();
+        replayed.networks.clear();
+        assert!(check_newer(&replayed, &cached).is_err());
+
+        // The cached manifest itself is served until a new one is published
+        assert!(check_newer(&cached, &cached).is_ok());
+
+        let mut newer = older;
+        newer.version = cached.version + 1;
+        assert!(check_newer(&newer, &cached).is_ok());
+    }
+
+    #[test]
+    fn custom_networks_have_no_registry() {
+        let network = Network::new_custom(
+            "http://localhost:8545",
+            "0x5FbDB2315678afecb367f032d93F642f64180aa3",
+            "0x8464135c8F25Da09e49BC8782676a84730C318bC",
+            None,
+        );
+        assert!(ContractRegistry::for_network(&network).unwrap().is_none());
+    }
+}
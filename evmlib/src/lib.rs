@@ -14,7 +14,9 @@
 use crate::common::{Address, Amount};
 use crate::contract::merkle_payment_vault::error::Error as MerklePaymentError;
 use crate::contract::merkle_payment_vault::handler::MerklePaymentVaultHandler;
+use crate::contract_registry::ContractAddresses;
 use crate::merkle_batch_payment::PoolCommitment;
+use crate::rpc_endpoints::FallbackRpcUrls;
 use crate::utils::{get_evm_network, http_provider};
 use alloy::primitives::address;
 use alloy::transports::http::reqwest;
@@ -28,16 +30,20 @@ extern crate tracing;
 
 pub mod common;
 pub mod contract;
+pub mod contract_registry;
 pub mod cryptography;
 #[cfg(feature = "external-signer")]
 pub mod external_signer;
 pub mod merkle_batch_payment;
+pub mod payment_events;
 pub mod quoting_metrics;
 mod retry;
+pub mod rpc_endpoints;
 pub mod testnet;
 pub mod transaction_config;
 pub mod utils;
 pub mod wallet;
+pub mod ws_confirmation;
 
 // Re-export GasInfo for use by other crates
 pub use retry::GasInfo;
@@ -83,25 +89,53 @@ pub struct CustomNetwork {
     pub payment_token_address: Address,
     pub data_payments_address: Address,
     pub merkle_payments_address: Option<Address>,
+    /// Endpoints to fail over to, in priority order, when `rpc_url_http` is unavailable
+    #[serde(default, skip_serializing_if = "FallbackRpcUrls::is_empty")]
+    pub fallback_rpc_urls: FallbackRpcUrls,
 }
 
 impl CustomNetwork {
+    /// Create a custom network.
+    ///
+    /// `rpc_url` can be a comma separated list of endpoints in priority order,
+    /// in which case the first one is the primary endpoint and the others are the fallbacks.
     pub fn new(
         rpc_url: &str,
         payment_token_addr: &str,
         data_payments_addr: &str,
         merkle_payments_addr: Option<&str>,
     ) -> Self {
+        let mut rpc_urls = rpc_url
+            .split(',')
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .map(|url| reqwest::Url::parse(url).expect("Invalid RPC URL"));
+        let rpc_url_http = rpc_urls.next().expect("Invalid RPC URL");
+        let fallback_rpc_urls =
+            FallbackRpcUrls::new(rpc_urls.filter(|url| *url != rpc_url_http).collect());
+
         Self {
-            rpc_url_http: reqwest::Url::parse(rpc_url).expect("Invalid RPC URL"),
+            rpc_url_http,
             payment_token_address: Address::from_str(payment_token_addr)
                 .expect("Invalid payment token address"),
             data_payments_address: Address::from_str(data_payments_addr)
                 .expect("Invalid chunk payments address"),
             merkle_payments_address: merkle_payments_addr
                 .map(|addr| Address::from_str(addr).expect("Invalid merkle payments address")),
+            fallback_rpc_urls,
         }
     }
+
+    /// Set the endpoints, in priority order, to fail over to when the primary endpoint is
+    /// unavailable or rate limiting us.
+    pub fn with_fallback_rpc_urls(mut self, fallback_rpc_urls: Vec<reqwest::Url>) -> Self {
+        let fallback_rpc_urls = fallback_rpc_urls
+            .into_iter()
+            .filter(|url| *url != self.rpc_url_http)
+            .collect();
+        self.fallback_rpc_urls = FallbackRpcUrls::new(fallback_rpc_urls);
+        self
+    }
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
@@ -163,6 +197,8 @@ impl Network {
         }
     }
 
+    /// The primary RPC endpoint of the network. The calls made through evmlib fail over to the
+    /// [`Network::rpc_urls`] fallbacks when it is unavailable.
     pub fn rpc_url(&self) -> &reqwest::Url {
         match self {
             Network::ArbitrumOne => &PUBLIC_ARBITRUM_ONE_HTTP_RPC_URL,
@@ -171,7 +207,38 @@ impl Network {
         }
     }
 
+    /// All the RPC endpoints of the network in priority order, starting with the primary endpoint.
+    pub fn rpc_urls(&self) -> Vec<reqwest::Url> {
+        let mut rpc_urls = vec![self.rpc_url().clone()];
+        if let Some(fallbacks) = self.fallback_rpc_urls() {
+            rpc_urls.extend(fallbacks.urls().iter().cloned());
+        }
+        rpc_urls
+    }
+
+    /// The endpoints to fail over to and their health, for the networks with fallback endpoints.
+    pub(crate) fn fallback_rpc_urls(&self) -> Option<&FallbackRpcUrls> {
+        match self {
+            Network::Custom(custom) if !custom.fallback_rpc_urls.is_empty() => {
+                Some(&custom.fallback_rpc_urls)
+            }
+            _ => None,
+        }
+    }
+
+    /// The contract addresses discovered through the [`contract_registry`], if any.
+    /// Custom networks always use their own addresses.
+    fn discovered_contracts(&self) -> Option<&'static ContractAddresses> {
+        match self {
+            Network::Custom(_) => None,
+            _ => contract_registry::discovered_contracts(self.identifier()),
+        }
+    }
+
     pub fn payment_token_address(&self) -> &Address {
+        if let Some(contracts) = self.discovered_contracts() {
+            return &contracts.payment_token_address;
+        }
         match self {
             Network::ArbitrumOne => &ARBITRUM_ONE_PAYMENT_TOKEN_ADDRESS,
             Network::ArbitrumSepoliaTest => &ARBITRUM_SEPOLIA_TEST_PAYMENT_TOKEN_ADDRESS,
@@ -180,6 +247,9 @@ impl Network {
     }
 
     pub fn data_payments_address(&self) -> &Address {
+        if let Some(contracts) = self.discovered_contracts() {
+            return &contracts.data_payments_address;
+        }
         match self {
             Network::ArbitrumOne => &ARBITRUM_ONE_DATA_PAYMENTS_ADDRESS,
             Network::ArbitrumSepoliaTest => &ARBITRUM_SEPOLIA_TEST_DATA_PAYMENTS_ADDRESS,
@@ -187,7 +257,27 @@ impl Network {
         }
     }
 
+    /// The addresses of the payment vaults of the network, the current one first. When contracts
+    /// were discovered at other addresses than the built-in ones, the built-in payment vault is
+    /// included as well, since it holds the payments made before the switch.
+    pub fn data_payments_addresses(&self) -> Vec<Address> {
+        let current = *self.data_payments_address();
+        let built_in = match self {
+            Network::ArbitrumOne => ARBITRUM_ONE_DATA_PAYMENTS_ADDRESS,
+            Network::ArbitrumSepoliaTest => ARBITRUM_SEPOLIA_TEST_DATA_PAYMENTS_ADDRESS,
+            Network::Custom(custom) => custom.data_payments_address,
+        };
+        if built_in == current {
+            vec![current]
+        } else {
+            vec![current, built_in]
+        }
+    }
+
     pub fn merkle_payments_address(&self) -> Option<&Address> {
+        if let Some(contracts) = self.discovered_contracts() {
+            return contracts.merkle_payments_address.as_ref();
+        }
         match self {
             Network::ArbitrumOne => Some(&ARBITRUM_ONE_MERKLE_PAYMENTS_ADDRESS),
             Network::ArbitrumSepoliaTest => Some(&ARBITRUM_SEPOLIA_TEST_MERKLE_PAYMENTS_ADDRESS),
@@ -218,19 +308,28 @@ impl Network {
             return Ok(Amount::ZERO);
         }
 
-        // Create provider (no wallet needed for view calls)
-        let provider = http_provider(self.rpc_url().clone());
-
         // Get Merkle payment vault address
         let merkle_vault_address = *self
             .merkle_payments_address()
             .ok_or(MerklePaymentError::MerklePaymentsAddressNotConfigured)?;
 
         // Create handler and call the contract's view function
-        let handler = MerklePaymentVaultHandler::new(merkle_vault_address, provider);
-        let total_amount = handler
-            .estimate_merkle_tree_cost(depth, pool_commitments.to_vec(), merkle_payment_timestamp)
-            .await?;
+        let total_amount = rpc_endpoints::with_rpc_failover(
+            self,
+            "estimate_merkle_tree_cost",
+            |rpc_url| async move {
+                let provider = http_provider(rpc_url);
+                let handler = MerklePaymentVaultHandler::new(merkle_vault_address, provider);
+                handler
+                    .estimate_merkle_tree_cost(
+                        depth,
+                        pool_commitments.to_vec(),
+                        merkle_payment_timestamp,
+                    )
+                    .await
+            },
+        )
+        .await?;
 
         Ok(total_amount)
     }
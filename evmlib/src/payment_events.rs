@@ -0,0 +1,441 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Indexing of the `DataPaymentMade` events of the payment vault, to reconcile the payments a node
+//! expects with the payments it actually received, without relying on a third-party indexer.
+//!
+//! A [`PaymentEventIndexer`] scans a block range in windows small enough for public RPC endpoints,
+//! and advances a [`ScanCursor`] as it goes. The cursor can be saved to disk so that the next scan
+//! resumes where the previous one stopped. Scans to the latest block stop short of the blocks that
+//! a chain reorganisation could still drop, so that the cursor never moves past them.
+//!
+//! All the payment vaults of the network are scanned, not only the current one, as the payments
+//! made before a switch of payment vault are held by the former.
+//!
+//! The payments made within the last blocks can also be looked up, to tell whether a payment is
+//! buried deep enough not to be dropped by a chain reorganisation, and whether the block holding
+//! a payment is still part of the chain.
+
+use crate::Network;
+use crate::common::{Address, Amount, Hash, QuoteHash, TxHash};
+use crate::contract::payment_vault::interface::IPaymentVault::DataPaymentMade;
+use crate::rpc_endpoints::with_rpc_failover;
+use crate::utils::http_provider;
+use alloy::providers::Provider;
+use alloy::rpc::types::{BlockNumberOrTag, Filter};
+use alloy::sol_types::SolEvent;
+use alloy::transports::{RpcError, TransportErrorKind};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Max number of blocks queried at once, as most RPC endpoints limit the range of log queries.
+pub const DEFAULT_MAX_BLOCK_RANGE: u64 = 10_000;
+
+/// Number of blocks a payment must be buried under before [`PaymentEventIndexer::scan_to_latest`]
+/// scans it, as the last blocks could still be dropped by a chain reorganisation.
+pub const DEFAULT_CONFIRMATIONS: u64 = 20;
+
+/// Max number of quote hashes in the topic filter of a single log query, as RPC endpoints limit the
+/// size of the filters.
+pub const MAX_QUOTE_HASHES_PER_QUERY: usize = 100;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Rpc(#[from] RpcError<TransportErrorKind>),
+    #[error("Failed to decode a DataPaymentMade event: {0}")]
+    Decode(#[from] alloy::sol_types::Error),
+    #[error("Failed to read or write the scan cursor: {0}")]
+    Cursor(#[from] std::io::Error),
+}
+
+/// A payment made to the payment vault.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DataPaymentEvent {
+    pub rewards_address: Address,
+    pub amount: Amount,
+    pub quote_hash: QuoteHash,
+    pub block_number: u64,
+    pub block_hash: Option<Hash>,
+    pub transaction_hash: Option<TxHash>,
+}
+
+/// The payments to look for.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PaymentEventFilter {
+    /// Payments for any of these quotes
+    QuoteHashes(Vec<QuoteHash>),
+    /// Payments to this rewards address
+    RewardsAddress(Address),
+}
+
+/// The next block to scan.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScanCursor {
+    pub next_block: u64,
+}
+
+impl ScanCursor {
+    /// A cursor starting the scan at `from_block`.
+    pub fn new(from_block: u64) -> Self {
+        Self {
+            next_block: from_block,
+        }
+    }
+
+    /// Read a cursor previously saved, if any.
+    pub fn load(path: &Path) -> Result<Option<Self>, Error> {
+        match std::fs::read(path) {
+            Ok(content) => Ok(Some(serde_json::from_slice(&content).map_err(|err| {
+                Error::Cursor(std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+            })?)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Save the cursor, to resume the scan later on.
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let content = serde_json::to_vec(self).map_err(std::io::Error::other)?;
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, content)?;
+        std::fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+}
+
+/// Scans the `DataPaymentMade` events of the payment vault of a network.
+#[derive(Clone, Debug)]
+pub struct PaymentEventIndexer {
+    network: Network,
+    filter: PaymentEventFilter,
+    payment_vaults: Vec<Address>,
+    max_block_range: u64,
+    confirmations: u64,
+}
+
+impl PaymentEventIndexer {
+    pub fn new(network: Network, filter: PaymentEventFilter) -> Self {
+        Self {
+            payment_vaults: network.data_payments_addresses(),
+            network,
+            filter,
+            max_block_range: DEFAULT_MAX_BLOCK_RANGE,
+            confirmations: DEFAULT_CONFIRMATIONS,
+        }
+    }
+
+    /// Set the max number of blocks queried at once.
+    pub fn with_max_block_range(mut self, max_block_range: u64) -> Self {
+        self.max_block_range = max_block_range.max(1);
+        self
+    }
+
+    /// Set the number of blocks a payment must be buried under to be scanned by
+    /// [`Self::scan_to_latest`].
+    pub fn with_confirmations(mut self, confirmations: u64) -> Self {
+        self.confirmations = confirmations;
+        self
+    }
+
+    /// Also scan the given payment vaults, e.g. the former ones of a custom network.
+    pub fn with_payment_vaults(
+        mut self,
+        payment_vaults: impl IntoIterator<Item = Address>,
+    ) -> Self {
+        for payment_vault in payment_vaults {
+            if !self.payment_vaults.contains(&payment_vault) {
+                self.payment_vaults.push(payment_vault);
+            }
+        }
+        self
+    }
+
+    /// The number of the latest block of the network.
+    pub async fn latest_block(&self) -> Result<u64, Error> {
+        with_rpc_failover(&self.network, "get_block_number", |rpc_url| async move {
+            Ok(http_provider(rpc_url).get_block_number().await?)
+        })
+        .await
+    }
+
+    /// Scan the blocks from the cursor up to `to_block` included, advancing the cursor past the
+    /// scanned blocks.
+    ///
+    /// If a window fails after some blocks were scanned, the events found so far are returned and
+    /// the cursor is left at the first block not scanned, so that no event is skipped on resume.
+    pub async fn scan(
+        &self,
+        cursor: &mut ScanCursor,
+        to_block: u64,
+    ) -> Result<Vec<DataPaymentEvent>, Error> {
+        let mut events = vec![];
+        while cursor.next_block <= to_block {
+            let window_end = cursor
+                .next_block
+                .saturating_add(self.max_block_range - 1)
+                .min(to_block);
+            match self.scan_window(cursor.next_block, window_end).await {
+                Ok(window_events) => {
+                    events.extend(window_events);
+                    cursor.next_block = window_end + 1;
+                }
+                Err(err) if !events.is_empty() => {
+                    warn!(
+                        "Payment event scan stopped at block {}: {err}",
+                        cursor.next_block
+                    );
+                    break;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(events)
+    }
+
+    /// Scan the blocks from the cursor up to the latest confirmed block, i.e. buried under the
+    /// number of confirmations of the indexer. Nothing is scanned while the cursor is past it.
+    pub async fn scan_to_latest(
+        &self,
+        cursor: &mut ScanCursor,
+    ) -> Result<Vec<DataPaymentEvent>, Error> {
+        let latest_block = self.latest_block().await?;
+        let Some(confirmed_block) = confirmed_block(latest_block, self.confirmations) else {
+            return Ok(vec![]);
+        };
+        self.scan(cursor, confirmed_block).await
+    }
+
+    async fn scan_window(
+        &self,
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<Vec<DataPaymentEvent>, Error> {
+        let filter = Filter::new()
+            .address(self.payment_vaults.clone())
+            .event_signature(DataPaymentMade::SIGNATURE_HASH)
+            .from_block(from_block)
+            .to_block(to_block);
+        debug!("Scanning blocks {from_block} to {to_block} for payment events");
+
+        match &self.filter {
+            PaymentEventFilter::QuoteHashes(quote_hashes) => {
+                // No query without quote hashes, as an empty topic would match any payment
+                let mut events = vec![];
+                for quote_hashes in quote_hashes.chunks(MAX_QUOTE_HASHES_PER_QUERY) {
+                    let filter = filter.clone().topic3(quote_hashes.to_vec());
+                    events.extend(self.get_events(filter, from_block).await?);
+                }
+                Ok(events)
+            }
+            PaymentEventFilter::RewardsAddress(rewards_address) => {
+                self.get_events(filter.topic1(rewards_address.into_word()), from_block)
+                    .await
+            }
+        }
+    }
+
+    async fn get_events(
+        &self,
+        filter: Filter,
+        from_block: u64,
+    ) -> Result<Vec<DataPaymentEvent>, Error> {
+        let logs = with_rpc_failover(&self.network, "get_logs", |rpc_url| {
+            let filter = filter.clone();
+            async move { Ok::<_, Error>(http_provider(rpc_url).get_logs(&filter).await?) }
+        })
+        .await?;
+
+        logs.into_iter()
+            .map(|log| {
+                let decoded = log.log_decode::<DataPaymentMade>()?;
+                let event = &decoded.inner.data;
+                Ok(DataPaymentEvent {
+                    rewards_address: event.rewardsAddress,
+                    amount: event.amount,
+                    quote_hash: event.quoteHash,
+                    block_number: log.block_number.unwrap_or(from_block),
+                    block_hash: log.block_hash,
+                    transaction_hash: log.transaction_hash,
+                })
+            })
+            .collect()
+    }
+}
+
+/// The latest block buried under `confirmations` blocks, `None` if the chain is not long enough.
+fn confirmed_block(latest_block: u64, confirmations: u64) -> Option<u64> {
+    latest_block.checked_sub(confirmations)
+}
+
+/// The number of the latest block of the network.
+pub async fn latest_block_number(network: &Network) -> Result<u64, Error> {
+    with_rpc_failover(network, "get_block_number", |rpc_url| async move {
+        Ok::<_, Error>(http_provider(rpc_url).get_block_number().await?)
+    })
+    .await
+}
+
+/// The payments for the quotes made within the `confirmation_depth` blocks up to `latest_block`,
+/// i.e. which are not yet confirmed by that many blocks. The quotes of several payments can be
+/// looked up at once.
+pub async fn unconfirmed_payments(
+    network: &Network,
+    quote_hashes: Vec<QuoteHash>,
+    confirmation_depth: u64,
+    latest_block: u64,
+) -> Result<Vec<DataPaymentEvent>, Error> {
+    if confirmation_depth == 0 || quote_hashes.is_empty() {
+        return Ok(vec![]);
+    }
+    let indexer = PaymentEventIndexer::new(
+        network.clone(),
+        PaymentEventFilter::QuoteHashes(quote_hashes),
+    )
+    .with_max_block_range(confirmation_depth);
+    let from_block = latest_block.saturating_sub(confirmation_depth - 1);
+    indexer.scan_window(from_block, latest_block).await
+}
+
+/// The hash of the block of the chain with the given number, `None` if the chain has no such block,
+/// e.g. after a chain reorganisation.
+pub async fn block_hash(network: &Network, block_number: u64) -> Result<Option<Hash>, Error> {
+    let block = with_rpc_failover(network, "get_block_by_number", |rpc_url| async move {
+        Ok::<_, Error>(
+            http_provider(rpc_url)
+                .get_block_by_number(BlockNumberOrTag::Number(block_number))
+                .await?,
+        )
+    })
+    .await?;
+    Ok(block.map(|block| block.header.hash))
+}
+
+/// The outcome of the reconciliation of expected payments against payment events.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Reconciliation {
+    /// Quotes paid at least the expected amount
+    pub paid: Vec<QuoteHash>,
+    /// Quotes paid less than expected, with the expected and received amounts
+    pub underpaid: Vec<(QuoteHash, Amount, Amount)>,
+    /// Quotes for which no payment was found
+    pub missing: Vec<QuoteHash>,
+    /// Payments for quotes that were not expected
+    pub unexpected: Vec<DataPaymentEvent>,
+}
+
+/// Reconcile the expected amount per quote with the payments found.
+pub fn reconcile(
+    expected: &HashMap<QuoteHash, Amount>,
+    events: &[DataPaymentEvent],
+) -> Reconciliation {
+    let mut received: HashMap<QuoteHash, Amount> = HashMap::new();
+    let mut reconciliation = Reconciliation::default();
+    for event in events {
+        if expected.contains_key(&event.quote_hash) {
+            *received.entry(event.quote_hash).or_default() += event.amount;
+        } else {
+            reconciliation.unexpected.push(event.clone());
+        }
+    }
+
+    for (quote_hash, expected_amount) in expected {
+        match received.get(quote_hash) {
+            Some(amount) if amount >= expected_amount => reconciliation.paid.push(*quote_hash),
+            Some(amount) => reconciliation
+                .underpaid
+                .push((*quote_hash, *expected_amount, *amount)),
+            None => reconciliation.missing.push(*quote_hash),
+        }
+    }
+    reconciliation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(quote_hash: QuoteHash, amount: u64) -> DataPaymentEvent {
+        DataPaymentEvent {
+            rewards_address: Address::repeat_byte(1),
+            amount: Amount::from(amount),
+            quote_hash,
+            block_number: 1,
+            block_hash: None,
+            transaction_hash: None,
+        }
+    }
+
+    #[test]
+    fn payments_are_reconciled_with_expected_amounts() {
+        let paid = QuoteHash::repeat_byte(1);
+        let underpaid = QuoteHash::repeat_byte(2);
+        let missing = QuoteHash::repeat_byte(3);
+        let unexpected = QuoteHash::repeat_byte(4);
+        let expected = HashMap::from([
+            (paid, Amount::from(10)),
+            (underpaid, Amount::from(10)),
+            (missing, Amount::from(10)),
+        ]);
+        let events = vec![
+            event(paid, 4),
+            event(paid, 6),
+            event(underpaid, 9),
+            event(unexpected, 10),
+        ];
+
+        let reconciliation = reconcile(&expected, &events);
+        assert_eq!(reconciliation.paid, vec![paid]);
+        assert_eq!(
+            reconciliation.underpaid,
+            vec![(underpaid, Amount::from(10), Amount::from(9))]
+        );
+        assert_eq!(reconciliation.missing, vec![missing]);
+        assert_eq!(reconciliation.unexpected, vec![event(unexpected, 10)]);
+    }
+
+    #[test]
+    fn cursor_is_saved_and_loaded() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("payment_events_cursor.json");
+        assert_eq!(ScanCursor::load(&path).unwrap(), None);
+        ScanCursor::new(42).save(&path).unwrap();
+        assert_eq!(ScanCursor::load(&path).unwrap(), Some(ScanCursor::new(42)));
+        ScanCursor::new(43).save(&path).unwrap();
+        assert_eq!(ScanCursor::load(&path).unwrap(), Some(ScanCursor::new(43)));
+    }
+
+    #[test]
+    fn scans_stop_short_of_the_unconfirmed_blocks() {
+        assert_eq!(confirmed_block(100, 20), Some(80));
+        assert_eq!(confirmed_block(100, 0), Some(100));
+        assert_eq!(confirmed_block(20, 20), Some(0));
+        assert_eq!(confirmed_block(19, 20), None);
+    }
+
+    #[test]
+    fn former_payment_vaults_are_scanned() {
+        let network = Network::Custom(crate::CustomNetwork::new(
+            "http://localhost:8545",
+            "0x5FbDB2315678afecb367f032d93F642f64180aa3",
+            "0x8464135c8F25Da09e49BC8782676a84730C318bC",
+            None,
+        ));
+        let former_vault = Address::repeat_byte(7);
+        let indexer = PaymentEventIndexer::new(
+            network.clone(),
+            PaymentEventFilter::RewardsAddress(Address::repeat_byte(1)),
+        )
+        .with_payment_vaults([former_vault, *network.data_payments_address()]);
+        assert_eq!(
+            indexer.payment_vaults,
+            vec![*network.data_payments_address(), former_vault]
+        );
+    }
+}
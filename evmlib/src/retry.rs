@@ -1,9 +1,10 @@
 use crate::TX_TIMEOUT;
 use crate::common::{Address, Calldata, TxHash};
 use crate::transaction_config::{MaxFeePerGas, TransactionConfig};
+use crate::ws_confirmation;
 use alloy::network::{Network, ReceiptResponse, TransactionBuilder};
 use alloy::providers::{PendingTransactionBuilder, Provider};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 pub(crate) const MAX_RETRIES: u8 = 3;
 const DEFAULT_RETRY_INTERVAL_MS: u64 = 4000;
@@ -220,7 +221,7 @@ where
     transaction_request.set_gas_limit(gas_with_buffer);
 
     // Prepare gas info (actual values will be set from receipt)
-    let mut gas_info = GasInfo {
+    let gas_info = GasInfo {
         estimated_gas,
         gas_with_buffer,
         max_fee_per_gas: eip1559_fees.as_ref().map(|f| f.max_fee_per_gas),
@@ -258,20 +259,39 @@ where
         pending_tx_builder.tx_hash()
     );
 
-    let watch_result = retry(
-        || async {
-            PendingTransactionBuilder::from_config(
-                provider.root().clone(),
-                pending_tx_builder.inner().clone(),
-            )
-            .with_timeout(Some(TX_TIMEOUT))
-            .watch()
-            .await
-        },
-        "watching pending transaction",
-        Some(WATCH_TIMEOUT_MS),
+    // Prefer a WebSocket subscription to confirm the transaction, fall back to polling
+    let tx_hash = *pending_tx_builder.tx_hash();
+    let ws_started = Instant::now();
+    if let Some(receipt) = ws_confirmation::wait_for_transaction(
+        transaction_config.ws_rpc_url.as_ref(),
+        tx_hash,
+        TX_TIMEOUT,
     )
-    .await;
+    .await
+    {
+        return check_receipt(&receipt, tx_hash, tx_identifier, gas_info, nonce);
+    }
+
+    // Only poll for the time left after waiting on the WebSocket endpoint, if at all
+    let time_left = TX_TIMEOUT.saturating_sub(ws_started.elapsed());
+    let watch_result = if time_left.is_zero() {
+        Ok(tx_hash)
+    } else {
+        retry(
+            || async {
+                PendingTransactionBuilder::from_config(
+                    provider.root().clone(),
+                    pending_tx_builder.inner().clone(),
+                )
+                .with_timeout(Some(time_left))
+                .watch()
+                .await
+            },
+            "watching pending transaction",
+            Some(WATCH_TIMEOUT_MS),
+        )
+        .await
+    };
 
     match watch_result {
         Ok(tx_hash) => {
@@ -279,39 +299,7 @@ where
             // We must fetch the receipt and check the status field.
             match provider.get_transaction_receipt(tx_hash).await {
                 Ok(Some(receipt)) => {
-                    let gas_used = receipt.gas_used();
-                    let effective_gas_price = receipt.effective_gas_price();
-                    let gas_cost_wei = (gas_used as u128).saturating_mul(effective_gas_price);
-
-                    gas_info.actual_gas_used = gas_used;
-                    gas_info.effective_gas_price = effective_gas_price;
-                    gas_info.gas_cost_wei = gas_cost_wei;
-
-                    if receipt.status() {
-                        debug!("{tx_identifier} transaction with hash {tx_hash:?} succeeded");
-                        info!(
-                            "Gas details: estimated={}, buffer={}, actual={}, effective_price={} wei, total_cost={} wei",
-                            gas_info.estimated_gas,
-                            gas_info.gas_with_buffer,
-                            gas_used,
-                            effective_gas_price,
-                            gas_cost_wei
-                        );
-                        Ok((tx_hash, gas_info))
-                    } else {
-                        // Transaction was mined but reverted (e.g., out of gas)
-                        error!(
-                            "{tx_identifier} transaction {tx_hash:?} was mined but reverted. \
-                            Gas used: {gas_used}"
-                        );
-                        Err(TransactionError::TransactionReverted {
-                            message: format!(
-                                "Transaction was mined but execution failed (gas used: {gas_used})"
-                            ),
-                            revert_data: None,
-                            nonce,
-                        })
-                    }
+                    check_receipt(&receipt, tx_hash, tx_identifier, gas_info, nonce)
                 }
                 Ok(None) => {
                     // This shouldn't happen after .watch() succeeds, but handle it
@@ -350,6 +338,47 @@ where
     }
 }
 
+/// Fill in the gas details from the receipt of a mined transaction, and check that it succeeded.
+fn check_receipt(
+    receipt: &impl ReceiptResponse,
+    tx_hash: TxHash,
+    tx_identifier: &str,
+    mut gas_info: GasInfo,
+    nonce: Option<u64>,
+) -> Result<(TxHash, GasInfo), TransactionError> {
+    let gas_used = receipt.gas_used();
+    let effective_gas_price = receipt.effective_gas_price();
+    let gas_cost_wei = (gas_used as u128).saturating_mul(effective_gas_price);
+
+    gas_info.actual_gas_used = gas_used;
+    gas_info.effective_gas_price = effective_gas_price;
+    gas_info.gas_cost_wei = gas_cost_wei;
+
+    if receipt.status() {
+        debug!("{tx_identifier} transaction with hash {tx_hash:?} succeeded");
+        info!(
+            "Gas details: estimated={}, buffer={}, actual={}, effective_price={} wei, total_cost={} wei",
+            gas_info.estimated_gas,
+            gas_info.gas_with_buffer,
+            gas_used,
+            effective_gas_price,
+            gas_cost_wei
+        );
+        Ok((tx_hash, gas_info))
+    } else {
+        // Transaction was mined but reverted (e.g., out of gas)
+        error!(
+            "{tx_identifier} transaction {tx_hash:?} was mined but reverted. \
+            Gas used: {gas_used}"
+        );
+        Err(TransactionError::TransactionReverted {
+            message: format!("Transaction was mined but execution failed (gas used: {gas_used})"),
+            revert_data: None,
+            nonce,
+        })
+    }
+}
+
 /// Extract revert data from a PendingTransactionError
 ///
 /// When a transaction reverts, we try to extract the revert reason data.
@@ -0,0 +1,404 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Prioritized EVM RPC endpoints with health tracking and failover.
+//!
+//! A [`crate::Network`] always has a primary RPC endpoint. A custom network can carry fallback
+//! endpoints, after which all RPC calls made through evmlib pick the first healthy endpoint in
+//! priority order. An endpoint that fails (transport errors, rate limits, ...) is put in a
+//! cool-down period and the next endpoint in the list is used instead.
+//!
+//! Transactions only fail over when they could not be sent, so that a payment broadcast through an
+//! endpoint is never sent again through another one.
+
+use crate::Network;
+use crate::contract::{merkle_payment_vault, network_token, payment_vault};
+use crate::payment_events;
+use crate::retry::TransactionError;
+use crate::utils::http_provider;
+use alloy::providers::Provider;
+use alloy::transports::http::reqwest;
+use alloy::transports::{RpcError, TransportErrorKind};
+use serde::{Deserialize, Serialize};
+use serde_with::{DisplayFromStr, serde_as};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Base cool-down applied to an endpoint after a failure. Grows linearly with consecutive failures.
+const ENDPOINT_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Max multiplier applied to [`ENDPOINT_COOLDOWN`].
+const MAX_COOLDOWN_MULTIPLIER: u32 = 10;
+
+/// Timeout of a single endpoint health check.
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Default)]
+struct EndpointHealth {
+    consecutive_failures: u32,
+    unhealthy_until: Option<Instant>,
+}
+
+impl EndpointHealth {
+    fn is_healthy(&self) -> bool {
+        self.unhealthy_until
+            .is_none_or(|unhealthy_until| Instant::now() >= unhealthy_until)
+    }
+}
+
+/// The fallback RPC endpoints of a network, in priority order, along with the health of all the
+/// endpoints of the network.
+///
+/// Only the endpoints are serialized. The health is shared by the clones of the network, so that
+/// an endpoint failing for a wallet is also avoided by the payment verification using that network.
+#[serde_as]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct FallbackRpcUrls {
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    urls: Vec<reqwest::Url>,
+    #[serde(skip)]
+    health: Arc<Mutex<HashMap<reqwest::Url, EndpointHealth>>>,
+}
+
+impl PartialEq for FallbackRpcUrls {
+    fn eq(&self, other: &Self) -> bool {
+        self.urls == other.urls
+    }
+}
+
+impl FallbackRpcUrls {
+    pub fn new(urls: Vec<reqwest::Url>) -> Self {
+        Self {
+            urls,
+            health: Default::default(),
+        }
+    }
+
+    /// The fallback endpoints, in priority order.
+    pub fn urls(&self) -> &[reqwest::Url] {
+        &self.urls
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.urls.is_empty()
+    }
+
+    /// Select the endpoint to use: the first healthy one in priority order.
+    /// If none is healthy, the one whose cool-down ends first is used.
+    fn select_endpoint(&self, endpoints: &[reqwest::Url]) -> Option<reqwest::Url> {
+        let Ok(health) = self.health.lock() else {
+            return endpoints.first().cloned();
+        };
+
+        endpoints
+            .iter()
+            .find(|url| health.get(*url).is_none_or(EndpointHealth::is_healthy))
+            .or_else(|| {
+                endpoints
+                    .iter()
+                    .min_by_key(|url| health.get(*url).and_then(|h| h.unhealthy_until))
+            })
+            .cloned()
+    }
+
+    /// Mark an endpoint as having served a call successfully.
+    fn mark_endpoint_success(&self, url: &reqwest::Url) {
+        if let Ok(mut health) = self.health.lock()
+            && let Some(endpoint) = health.get_mut(url)
+            && endpoint.consecutive_failures > 0
+        {
+            info!("RPC endpoint {url} recovered");
+            *endpoint = EndpointHealth::default();
+        }
+    }
+
+    /// Mark an endpoint as failed, putting it in a cool-down period.
+    fn mark_endpoint_failure(&self, url: &reqwest::Url) {
+        if let Ok(mut health) = self.health.lock() {
+            let endpoint = health.entry(url.clone()).or_default();
+            endpoint.consecutive_failures = endpoint.consecutive_failures.saturating_add(1);
+            let cooldown =
+                ENDPOINT_COOLDOWN * endpoint.consecutive_failures.min(MAX_COOLDOWN_MULTIPLIER);
+            endpoint.unhealthy_until = Some(Instant::now() + cooldown);
+            warn!(
+                "RPC endpoint {url} failed {} time(s) in a row, cooling down for {cooldown:?}",
+                endpoint.consecutive_failures
+            );
+        }
+    }
+}
+
+/// Errors that can tell whether they were caused by the RPC endpoint itself,
+/// in which case the call can be retried on another endpoint.
+pub trait RpcEndpointError {
+    /// Returns true if the error was caused by the endpoint (unreachable, rate limited, ...)
+    /// rather than by the call itself (e.g. a contract revert).
+    fn is_endpoint_failure(&self) -> bool;
+
+    /// Returns true if a transaction could not be sent because of the endpoint, hence can be sent
+    /// through another endpoint without being executed twice.
+    fn is_unsent_transaction(&self) -> bool {
+        false
+    }
+}
+
+/// Probe every endpoint of the network and update their health.
+/// Returns the endpoints with whether they responded.
+pub async fn check_rpc_endpoints_health(network: &Network) -> Vec<(reqwest::Url, bool)> {
+    let mut results = vec![];
+    for url in network.rpc_urls() {
+        let provider = http_provider(url.clone());
+        let healthy = matches!(
+            tokio::time::timeout(HEALTH_CHECK_TIMEOUT, provider.get_chain_id()).await,
+            Ok(Ok(_))
+        );
+        if let Some(fallbacks) = network.fallback_rpc_urls() {
+            if healthy {
+                fallbacks.mark_endpoint_success(&url);
+            } else {
+                fallbacks.mark_endpoint_failure(&url);
+            }
+        }
+        results.push((url, healthy));
+    }
+    results
+}
+
+/// Run an RPC call against the network endpoints in priority order,
+/// failing over to the next endpoint when the call fails because of the endpoint.
+pub(crate) async fn with_rpc_failover<T, E, F, Fut>(
+    network: &Network,
+    operation_id: &str,
+    action: F,
+) -> Result<T, E>
+where
+    F: FnMut(reqwest::Url) -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    E: RpcEndpointError + std::fmt::Debug,
+{
+    failover(network, operation_id, E::is_endpoint_failure, action).await
+}
+
+/// Send a transaction through the network endpoints in priority order, failing over to the next
+/// endpoint only when the transaction could not be sent because of the endpoint.
+pub(crate) async fn with_send_failover<T, E, F, Fut>(
+    network: &Network,
+    operation_id: &str,
+    action: F,
+) -> Result<T, E>
+where
+    F: FnMut(reqwest::Url) -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    E: RpcEndpointError + std::fmt::Debug,
+{
+    failover(network, operation_id, E::is_unsent_transaction, action).await
+}
+
+async fn failover<T, E, F, Fut>(
+    network: &Network,
+    operation_id: &str,
+    can_fail_over: fn(&E) -> bool,
+    mut action: F,
+) -> Result<T, E>
+where
+    F: FnMut(reqwest::Url) -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    E: std::fmt::Debug,
+{
+    let Some(fallbacks) = network.fallback_rpc_urls() else {
+        return action(network.rpc_url().clone()).await;
+    };
+    let mut endpoints = network.rpc_urls();
+
+    // Start with the selected endpoint, then try the others in priority order
+    if let Some(selected) = fallbacks.select_endpoint(&endpoints)
+        && let Some(pos) = endpoints.iter().position(|url| *url == selected)
+    {
+        let selected = endpoints.remove(pos);
+        endpoints.insert(0, selected);
+    }
+
+    let last = endpoints.pop().unwrap_or_else(|| network.rpc_url().clone());
+
+    for url in endpoints {
+        match action(url.clone()).await {
+            Ok(result) => {
+                fallbacks.mark_endpoint_success(&url);
+                return Ok(result);
+            }
+            Err(err) if can_fail_over(&err) => {
+                fallbacks.mark_endpoint_failure(&url);
+                warn!("{operation_id} failed on RPC endpoint {url}, failing over: {err:?}");
+            }
+            Err(err) => return Err(err),
+        }
+    }
+
+    let result = action(last.clone()).await;
+    match &result {
+        Ok(_) => fallbacks.mark_endpoint_success(&last),
+        Err(err) if can_fail_over(err) => fallbacks.mark_endpoint_failure(&last),
+        Err(_) => {}
+    }
+    result
+}
+
+/// Returns true if a transaction failed before being broadcast.
+fn is_unsent_transaction_error(err: &TransactionError) -> bool {
+    matches!(
+        err,
+        TransactionError::CouldNotGetGasPrice(_) | TransactionError::TransactionFailedToSend(_)
+    )
+}
+
+/// Returns true if an RPC error was caused by the endpoint rather than by the request.
+pub(crate) fn is_endpoint_rpc_error(err: &RpcError<TransportErrorKind>) -> bool {
+    match err {
+        RpcError::ErrorResp(payload) => payload.is_retry_err(),
+        RpcError::Transport(_) | RpcError::NullResp | RpcError::DeserError { .. } => true,
+        _ => false,
+    }
+}
+
+fn is_endpoint_contract_error(err: &alloy::contract::Error) -> bool {
+    match err {
+        alloy::contract::Error::TransportError(err) => is_endpoint_rpc_error(err),
+        _ => false,
+    }
+}
+
+impl RpcEndpointError for payment_vault::error::Error {
+    fn is_endpoint_failure(&self) -> bool {
+        match self {
+            Self::ContractError(err) => is_endpoint_contract_error(err),
+            Self::RpcError(err) => is_endpoint_rpc_error(err),
+            Self::Timeout(_) => true,
+            _ => false,
+        }
+    }
+
+    fn is_unsent_transaction(&self) -> bool {
+        match self {
+            Self::Transaction(err) => is_unsent_transaction_error(err),
+            _ => false,
+        }
+    }
+}
+
+impl RpcEndpointError for merkle_payment_vault::error::Error {
+    fn is_endpoint_failure(&self) -> bool {
+        match self {
+            Self::Contract(err) => is_endpoint_contract_error(err),
+            Self::Rpc(_) => true,
+            _ => false,
+        }
+    }
+}
+
+impl RpcEndpointError for network_token::Error {
+    fn is_endpoint_failure(&self) -> bool {
+        match self {
+            Self::ContractError(err) => is_endpoint_contract_error(err),
+            Self::RpcError(err) => is_endpoint_rpc_error(err),
+            Self::Timeout(_) => true,
+            _ => false,
+        }
+    }
+
+    fn is_unsent_transaction(&self) -> bool {
+        match self {
+            Self::Transaction(err) => is_unsent_transaction_error(err),
+            // Gas tokens are sent straight through the provider
+            Self::RpcError(err) => is_endpoint_rpc_error(err),
+            _ => false,
+        }
+    }
+}
+
+impl RpcEndpointError for payment_events::Error {
+    fn is_endpoint_failure(&self) -> bool {
+        match self {
+            Self::Rpc(err) => is_endpoint_rpc_error(err),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CustomNetwork;
+
+    fn url(s: &str) -> reqwest::Url {
+        s.parse().expect("valid url")
+    }
+
+    #[test]
+    fn test_select_endpoint_skips_unhealthy() {
+        let primary = url("http://primary.failover-test:8545");
+        let fallback = url("http://fallback.failover-test:8545");
+        let fallbacks = FallbackRpcUrls::new(vec![fallback.clone()]);
+
+        let endpoints = vec![primary.clone(), fallback.clone()];
+        assert_eq!(fallbacks.select_endpoint(&endpoints), Some(primary.clone()));
+
+        fallbacks.mark_endpoint_failure(&primary);
+        assert_eq!(
+            fallbacks.select_endpoint(&endpoints),
+            Some(fallback.clone())
+        );
+
+        // All endpoints unhealthy, the one cooling down first is used
+        fallbacks.mark_endpoint_failure(&fallback);
+        assert_eq!(fallbacks.select_endpoint(&endpoints), Some(primary.clone()));
+
+        fallbacks.mark_endpoint_success(&fallback);
+        assert_eq!(fallbacks.select_endpoint(&endpoints), Some(fallback));
+
+        fallbacks.mark_endpoint_success(&primary);
+        assert_eq!(fallbacks.select_endpoint(&endpoints), Some(primary));
+    }
+
+    #[test]
+    fn test_fallbacks_are_kept_by_the_network() -> Result<(), serde_json::Error> {
+        let network = Network::Custom(CustomNetwork::new(
+            "http://primary.failover-test:8545, http://fallback.failover-test:8545",
+            "0x5FbDB2315678afecb367f032d93F642f64180aa3",
+            "0x8464135c8F25Da09e49BC8782676a84730C318bC",
+            None,
+        ));
+        let primary = url("http://primary.failover-test:8545");
+        let fallback = url("http://fallback.failover-test:8545");
+        assert_eq!(network.rpc_url(), &primary);
+        assert_eq!(network.rpc_urls(), vec![primary.clone(), fallback.clone()]);
+
+        // The health is shared by the clones of the network
+        let clone = network.clone();
+        let fallbacks = network.fallback_rpc_urls().expect("custom network");
+        fallbacks.mark_endpoint_failure(&primary);
+        let clone_fallbacks = clone.fallback_rpc_urls().expect("custom network");
+        assert_eq!(
+            clone_fallbacks.select_endpoint(&clone.rpc_urls()),
+            Some(fallback.clone())
+        );
+
+        // The endpoints survive serialization, the health is not serialized
+        let deserialized: Network = serde_json::from_str(&serde_json::to_string(&network)?)?;
+        assert_eq!(deserialized, network);
+        assert_eq!(deserialized.rpc_urls(), vec![primary.clone(), fallback]);
+        let deserialized_fallbacks = deserialized.fallback_rpc_urls().expect("custom network");
+        assert_eq!(
+            deserialized_fallbacks.select_endpoint(&deserialized.rpc_urls()),
+            Some(primary)
+        );
+        Ok(())
+    }
+}
@@ -6,24 +6,61 @@
 // KIND, either express or implied. Please review the Licences for the specific language governing
 // permissions and limitations relating to use of the SAFE Network Software.
 
-use crate::common::Address;
+use crate::common::{Address, Amount};
 use crate::contract::merkle_payment_vault::handler::MerklePaymentVaultHandler;
 use crate::contract::payment_vault::handler::PaymentVaultHandler;
-use crate::contract::{merkle_payment_vault, network_token::NetworkToken, payment_vault};
+use crate::contract::{
+    merkle_payment_vault, network_token, network_token::NetworkToken, payment_vault,
+};
 use crate::reqwest::Url;
+use crate::wallet::{self, Wallet};
 use crate::{CustomNetwork, Network};
 use alloy::hex::ToHexExt;
 use alloy::network::{Ethereum, EthereumWallet};
 use alloy::node_bindings::{Anvil, AnvilInstance};
+use alloy::primitives::Bytes;
+use alloy::providers::ext::AnvilApi;
 use alloy::providers::fillers::{
     BlobGasFiller, ChainIdFiller, FillProvider, GasFiller, JoinFill, NonceFiller,
     SimpleNonceManager, WalletFiller,
 };
 use alloy::providers::{Identity, ProviderBuilder, RootProvider};
 use alloy::signers::local::PrivateKeySigner;
+use alloy::transports::TransportError;
+use serde::{Deserialize, Serialize};
 
 const ANVIL_DEFAULT_PORT: u16 = 61611;
 
+/// Private key of the first default Anvil account (Alice), which deploys the network token and
+/// so holds all the payment tokens of a fresh testnet.
+pub const ANVIL_DEFAULT_PRIVATE_KEY: &str =
+    "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+/// Gas tokens sent along with the payment tokens by [`fund`], enough for many payments.
+pub const FUND_GAS_AMOUNT: u64 = 1_000_000_000_000_000_000;
+
+#[derive(thiserror::Error, Debug)]
+pub enum TestnetError {
+    #[error("RPC error: {0}")]
+    Rpc(#[from] TransportError),
+    #[error("Anvil node refused to load the state snapshot")]
+    LoadStateRejected,
+    #[error("Faucet wallet error: {0}")]
+    Wallet(#[from] wallet::Error),
+    #[error("Faucet transfer failed: {0}")]
+    Transfer(#[from] network_token::Error),
+}
+
+/// Snapshot of a local testnet: the full chain state (accounts, balances, contract code and
+/// storage) along with the addresses of the deployed Autonomi contracts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestnetSnapshot {
+    pub state: Bytes,
+    pub payment_token_address: Address,
+    pub data_payments_address: Address,
+    pub merkle_payments_address: Address,
+}
+
 pub struct Testnet {
     anvil: AnvilInstance,
     rpc_url: Url,
@@ -53,12 +90,52 @@ impl Testnet {
         }
     }
 
+    /// Starts an Anvil node and loads the chain state of a snapshot into it,
+    /// skipping the contract deployments.
+    pub async fn from_snapshot(snapshot: &TestnetSnapshot) -> Result<Self, TestnetError> {
+        let (node, rpc_url) = start_node();
+
+        restore_node(&rpc_url, snapshot.state.clone()).await?;
+
+        Ok(Testnet {
+            anvil: node,
+            rpc_url,
+            network_token_address: snapshot.payment_token_address,
+            data_payments_address: snapshot.data_payments_address,
+            merkle_payments_address: snapshot.merkle_payments_address,
+        })
+    }
+
+    /// Take a snapshot of the current chain state.
+    pub async fn snapshot(&self) -> Result<TestnetSnapshot, TestnetError> {
+        Ok(TestnetSnapshot {
+            state: snapshot_node(&self.rpc_url).await?,
+            payment_token_address: self.network_token_address,
+            data_payments_address: self.data_payments_address,
+            merkle_payments_address: self.merkle_payments_address,
+        })
+    }
+
+    /// Load the chain state of a snapshot into this testnet.
+    ///
+    /// Accounts and storage present in the snapshot are overwritten, anything created after the
+    /// snapshot was taken is left untouched. Use [`Testnet::from_snapshot`] to get the exact state.
+    pub async fn restore(&mut self, snapshot: &TestnetSnapshot) -> Result<(), TestnetError> {
+        restore_node(&self.rpc_url, snapshot.state.clone()).await?;
+
+        self.network_token_address = snapshot.payment_token_address;
+        self.data_payments_address = snapshot.data_payments_address;
+        self.merkle_payments_address = snapshot.merkle_payments_address;
+        Ok(())
+    }
+
     pub fn to_network(&self) -> Network {
         Network::Custom(CustomNetwork {
             rpc_url_http: self.rpc_url.clone(),
             payment_token_address: self.network_token_address,
             data_payments_address: self.data_payments_address,
             merkle_payments_address: Some(self.merkle_payments_address),
+            fallback_rpc_urls: Default::default(),
         })
     }
 
@@ -71,6 +148,27 @@ impl Testnet {
     pub fn merkle_payments_address(&self) -> Address {
         self.merkle_payments_address
     }
+
+    /// Send payment tokens and some gas to a wallet, see [`fund`].
+    pub async fn fund(&self, to: Address, amount: Amount) -> Result<(), TestnetError> {
+        fund(&self.to_network(), to, amount).await
+    }
+}
+
+/// Send `amount` payment tokens and [`FUND_GAS_AMOUNT`] gas tokens to a wallet of a local testnet,
+/// from the default Anvil account holding the tokens.
+///
+/// This fails if the tokens of the default account were moved elsewhere, e.g. by starting the
+/// testnet with a genesis wallet.
+pub async fn fund(network: &Network, to: Address, amount: Amount) -> Result<(), TestnetError> {
+    let faucet = Wallet::new_from_private_key(network.clone(), ANVIL_DEFAULT_PRIVATE_KEY)?;
+    debug!("Funding {to} with {amount} tokens from the testnet faucet");
+
+    let _ = faucet.transfer_tokens(to, amount).await?;
+    let _ = faucet
+        .transfer_gas_tokens(to, Amount::from(FUND_GAS_AMOUNT))
+        .await?;
+    Ok(())
 }
 
 /// Runs a local Anvil node bound to a specified IP address.
@@ -97,6 +195,25 @@ pub fn start_node() -> (AnvilInstance, Url) {
     (anvil, url)
 }
 
+/// Dump the chain state of a running Anvil node.
+pub async fn snapshot_node(rpc_url: &Url) -> Result<Bytes, TestnetError> {
+    let provider = ProviderBuilder::new().connect_http(rpc_url.clone());
+    let state = provider.anvil_dump_state().await?;
+    debug!("Dumped {} bytes of chain state from {rpc_url}", state.len());
+    Ok(state)
+}
+
+/// Load a chain state, as returned by [`snapshot_node`], into a running Anvil node.
+pub async fn restore_node(rpc_url: &Url, state: Bytes) -> Result<(), TestnetError> {
+    let provider = ProviderBuilder::new().connect_http(rpc_url.clone());
+    if provider.anvil_load_state(state).await? {
+        debug!("Loaded chain state into {rpc_url}");
+        Ok(())
+    } else {
+        Err(TestnetError::LoadStateRejected)
+    }
+}
+
 pub async fn deploy_network_token_contract(
     rpc_url: &Url,
     anvil: &AnvilInstance,
@@ -217,7 +334,9 @@ pub async fn deploy_merkle_payments_contract(
 
 #[cfg(test)]
 mod tests {
-    use crate::testnet::Testnet;
+    use crate::common::Amount;
+    use crate::testnet::{FUND_GAS_AMOUNT, Testnet};
+    use crate::wallet::{Wallet, balance_of_gas_tokens, balance_of_tokens};
 
     #[tokio::test]
     async fn test_run_multiple_testnets_in_parallel() {
@@ -226,4 +345,48 @@ mod tests {
         let _testnet_3 = Testnet::new().await;
         let _testnet_4 = Testnet::new().await;
     }
+
+    #[tokio::test]
+    async fn test_snapshot_and_restore() {
+        let testnet = Testnet::new().await;
+        let snapshot = testnet
+            .snapshot()
+            .await
+            .expect("Could not snapshot testnet");
+        assert!(!snapshot.state.is_empty());
+
+        let restored = Testnet::from_snapshot(&snapshot)
+            .await
+            .expect("Could not restore testnet");
+        assert_eq!(
+            restored.merkle_payments_address(),
+            testnet.merkle_payments_address()
+        );
+        assert_eq!(
+            restored.to_network().payment_token_address(),
+            testnet.to_network().payment_token_address()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fund() {
+        let testnet = Testnet::new().await;
+        let network = testnet.to_network();
+        let to = Wallet::new_with_random_wallet(network.clone()).address();
+        let amount = Amount::from(1_000_000u64);
+
+        testnet
+            .fund(to, amount)
+            .await
+            .expect("Could not fund wallet");
+
+        let tokens = balance_of_tokens(to, &network)
+            .await
+            .expect("Could not get balance of tokens");
+        let gas = balance_of_gas_tokens(to, &network)
+            .await
+            .expect("Could not get balance of gas tokens");
+        assert_eq!(tokens, amount);
+        assert_eq!(gas, Amount::from(FUND_GAS_AMOUNT));
+    }
 }
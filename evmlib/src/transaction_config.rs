@@ -6,9 +6,24 @@
 // KIND, either express or implied. Please review the Licences for the specific language governing
 // permissions and limitations relating to use of the SAFE Network Software.
 
-#[derive(Clone, Debug, Default)]
+use crate::utils::get_ws_rpc_url_from_env;
+use alloy::transports::http::reqwest;
+
+#[derive(Clone, Debug)]
 pub struct TransactionConfig {
     pub max_fee_per_gas: MaxFeePerGas,
+    /// WebSocket endpoint to confirm transactions over instead of polling the HTTP one.
+    /// Requires the `websocket` feature, defaults to the `RPC_WS_URL` env var.
+    pub ws_rpc_url: Option<reqwest::Url>,
+}
+
+impl Default for TransactionConfig {
+    fn default() -> Self {
+        Self {
+            max_fee_per_gas: MaxFeePerGas::default(),
+            ws_rpc_url: get_ws_rpc_url_from_env(),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Default)]
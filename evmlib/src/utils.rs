@@ -22,8 +22,12 @@ use std::env;
 const MAINNET_ID: u8 = 1;
 const ALPHANET_ID: u8 = 2;
 
-/// environment variable to connect to a custom EVM network
+/// environment variable to connect to a custom EVM network.
+/// Can be a comma separated list of endpoints in priority order, to fail over when one is unavailable.
 pub const RPC_URL: &str = "RPC_URL";
+/// environment variable to confirm transactions over a WebSocket endpoint (e.g. `wss://...`)
+/// instead of polling the HTTP one. Requires the `websocket` feature.
+pub const RPC_WS_URL: &str = "RPC_WS_URL";
 const RPC_URL_BUILD_TIME_VAL: Option<&str> = option_env!("RPC_URL");
 pub const PAYMENT_TOKEN_ADDRESS: &str = "PAYMENT_TOKEN_ADDRESS";
 const PAYMENT_TOKEN_ADDRESS_BUILD_TIME_VAL: Option<&str> = option_env!("PAYMENT_TOKEN_ADDRESS");
@@ -180,6 +184,19 @@ fn get_evm_network_from_env() -> Result<Network, Error> {
     }
 }
 
+/// Get the WebSocket endpoint to confirm transactions over from the [`RPC_WS_URL`] env var, if set
+/// to a valid URL.
+pub fn get_ws_rpc_url_from_env() -> Option<reqwest::Url> {
+    let url = env::var(RPC_WS_URL).ok()?;
+    match url.parse() {
+        Ok(url) => Some(url),
+        Err(err) => {
+            warn!("Ignoring invalid {RPC_WS_URL} value {url:?}: {err}");
+            None
+        }
+    }
+}
+
 /// Get the `Network::Custom` from the hardcoded values.
 fn local_evm_network_hardcoded() -> Network {
     // Merkle payments address is deterministic when deployed by Anvil's third default account (Charlie)
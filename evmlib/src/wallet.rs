@@ -16,6 +16,7 @@ use crate::contract::payment_vault::handler::PaymentVaultHandler;
 use crate::contract::{network_token, payment_vault};
 use crate::merkle_batch_payment::{CostUnitOverflow, PoolCommitment};
 use crate::retry::GasInfo;
+use crate::rpc_endpoints::{with_rpc_failover, with_send_failover};
 use crate::transaction_config::TransactionConfig;
 use crate::utils::http_provider;
 use crate::{Network, TX_TIMEOUT};
@@ -295,9 +296,12 @@ pub async fn balance_of_tokens(
     network: &Network,
 ) -> Result<U256, network_token::Error> {
     info!("Getting balance of tokens for account: {account}");
-    let provider = http_provider(network.rpc_url().clone());
-    let network_token = NetworkToken::new(*network.payment_token_address(), provider);
-    network_token.balance_of(account).await
+    with_rpc_failover(network, "balance_of_tokens", |rpc_url| async move {
+        let provider = http_provider(rpc_url);
+        let network_token = NetworkToken::new(*network.payment_token_address(), provider);
+        network_token.balance_of(account).await
+    })
+    .await
 }
 
 /// Returns the raw balance of gas tokens for this wallet.
@@ -306,9 +310,12 @@ pub async fn balance_of_gas_tokens(
     network: &Network,
 ) -> Result<U256, network_token::Error> {
     debug!("Getting balance of gas tokens for account: {account}");
-    let provider = http_provider(network.rpc_url().clone());
-    let balance = provider.get_balance(account).await?;
-    Ok(balance)
+    with_rpc_failover(network, "balance_of_gas_tokens", |rpc_url| async move {
+        let provider = http_provider(rpc_url);
+        let balance = provider.get_balance(account).await?;
+        Ok::<_, network_token::Error>(balance)
+    })
+    .await
 }
 
 /// See how many tokens of the owner may be spent by the spender.
@@ -318,9 +325,12 @@ pub async fn token_allowance(
     spender: Address,
 ) -> Result<U256, network_token::Error> {
     debug!("Getting allowance for owner: {owner} and spender: {spender}",);
-    let provider = http_provider(network.rpc_url().clone());
-    let network_token = NetworkToken::new(*network.payment_token_address(), provider);
-    network_token.allowance(owner, spender).await
+    with_rpc_failover(network, "token_allowance", |rpc_url| async move {
+        let provider = http_provider(rpc_url);
+        let network_token = NetworkToken::new(*network.payment_token_address(), provider);
+        network_token.allowance(owner, spender).await
+    })
+    .await
 }
 
 /// Approve an address / smart contract to spend this wallet's payment tokens.
@@ -332,11 +342,16 @@ pub async fn approve_to_spend_tokens(
     transaction_config: &TransactionConfig,
 ) -> Result<TxHash, network_token::Error> {
     debug!("Approving address/smart contract with {amount} tokens at address: {spender}",);
-    let provider = http_provider_with_wallet(network.rpc_url().clone(), wallet);
-    let network_token = NetworkToken::new(*network.payment_token_address(), provider);
-    network_token
-        .approve(spender, amount, transaction_config)
-        .await
+    with_send_failover(network, "approve", |rpc_url| {
+        let provider = http_provider_with_wallet(rpc_url, wallet.clone());
+        async move {
+            let network_token = NetworkToken::new(*network.payment_token_address(), provider);
+            network_token
+                .approve(spender, amount, transaction_config)
+                .await
+        }
+    })
+    .await
 }
 
 /// Transfer payment tokens from the supplied wallet to an address.
@@ -348,11 +363,16 @@ pub async fn transfer_tokens(
     transaction_config: &TransactionConfig,
 ) -> Result<TxHash, network_token::Error> {
     debug!("Transferring {amount} tokens to {receiver}");
-    let provider = http_provider_with_wallet(network.rpc_url().clone(), wallet);
-    let network_token = NetworkToken::new(*network.payment_token_address(), provider);
-    network_token
-        .transfer(receiver, amount, transaction_config)
-        .await
+    with_send_failover(network, "transfer", |rpc_url| {
+        let provider = http_provider_with_wallet(rpc_url, wallet.clone());
+        async move {
+            let network_token = NetworkToken::new(*network.payment_token_address(), provider);
+            network_token
+                .transfer(receiver, amount, transaction_config)
+                .await
+        }
+    })
+    .await
 }
 
 /// Transfer native/gas tokens from the supplied wallet to an address.
@@ -363,18 +383,25 @@ pub async fn transfer_gas_tokens(
     amount: U256,
 ) -> Result<TxHash, network_token::Error> {
     debug!("Transferring {amount} gas tokens to {receiver}");
-    let provider = http_provider_with_wallet(network.rpc_url().clone(), wallet);
     let tx = TransactionRequest::default()
         .with_to(receiver)
         .with_value(amount);
 
-    let pending_tx_builder = provider
-        .send_transaction(tx)
-        .await
-        .inspect_err(|err| {
-            error!("Error to send_transaction during transfer_gas_tokens: {err}");
-        })?
-        .with_timeout(Some(TX_TIMEOUT));
+    let pending_tx_builder = with_send_failover(network, "transfer_gas_tokens", |rpc_url| {
+        let provider = http_provider_with_wallet(rpc_url, wallet.clone());
+        let tx = tx.clone();
+        async move {
+            provider
+                .send_transaction(tx)
+                .await
+                .map_err(network_token::Error::from)
+        }
+    })
+    .await
+    .inspect_err(|err| {
+        error!("Error to send_transaction during transfer_gas_tokens: {err}");
+    })?
+    .with_timeout(Some(TX_TIMEOUT));
     let pending_tx_hash = *pending_tx_builder.tx_hash();
     debug!("The transfer of gas tokens is pending with tx_hash: {pending_tx_hash}");
 
@@ -440,9 +467,6 @@ pub async fn pay_for_quotes<T: IntoIterator<Item = QuotePayment>>(
         .map_err(|err| PayForQuotesError(Error::from(err), Default::default()))?;
     }
 
-    let provider = http_provider_with_wallet(network.rpc_url().clone(), wallet);
-    let data_payments = PaymentVaultHandler::new(*network.data_payments_address(), provider);
-
     // remove payments with 0 amount as they don't need to be paid for
     let payment_for_batch: Vec<QuotePayment> = payments
         .into_iter()
@@ -463,10 +487,19 @@ pub async fn pay_for_quotes<T: IntoIterator<Item = QuotePayment>>(
             batch.len()
         );
 
-        let (tx_hash, gas_info) = data_payments
-            .pay_for_quotes(batch.clone(), transaction_config)
-            .await
-            .map_err(|err| PayForQuotesError(Error::from(err), tx_hashes_by_quote.clone()))?;
+        let (tx_hash, gas_info) = with_send_failover(network, "pay_for_quotes", |rpc_url| {
+            let provider = http_provider_with_wallet(rpc_url, wallet.clone());
+            let batch = batch.clone();
+            async move {
+                let data_payments =
+                    PaymentVaultHandler::new(*network.data_payments_address(), provider);
+                data_payments
+                    .pay_for_quotes(batch, transaction_config)
+                    .await
+            }
+        })
+        .await
+        .map_err(|err| PayForQuotesError(Error::from(err), tx_hashes_by_quote.clone()))?;
 
         info!("Paid for batch of quotes with final tx hash: {tx_hash}");
 
@@ -0,0 +1,87 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Transaction confirmation over a WebSocket RPC endpoint.
+//!
+//! When a WebSocket endpoint is set in the transaction config (and the `websocket` feature is
+//! enabled), evmlib subscribes to new block headers with `eth_subscribe` and only asks for the
+//! transaction receipt when a new block comes in, instead of polling the HTTP endpoint. Any failure
+//! on the WebSocket side falls back to the regular HTTP polling, for the time left.
+
+use crate::common::TxHash;
+use alloy::rpc::types::TransactionReceipt;
+use alloy::transports::http::reqwest;
+use std::time::Duration;
+
+/// Wait for a transaction to be mined using a WebSocket subscription to `ws_url`, and return its
+/// receipt, which may be the one of a reverted transaction.
+///
+/// Returns `None` if no WebSocket endpoint is given, or the subscription failed or timed out,
+/// in which case the caller should fall back to polling.
+#[cfg_attr(not(feature = "websocket"), allow(clippy::unused_async))]
+pub(crate) async fn wait_for_transaction(
+    ws_url: Option<&reqwest::Url>,
+    tx_hash: TxHash,
+    timeout: Duration,
+) -> Option<TransactionReceipt> {
+    #[cfg(feature = "websocket")]
+    if let Some(ws_url) = ws_url {
+        match tokio::time::timeout(timeout, subscribe_for_receipt(ws_url, tx_hash)).await {
+            Ok(Ok(receipt)) => {
+                debug!("Transaction {tx_hash:?} mined, seen over WebSocket endpoint {ws_url}");
+                return Some(receipt);
+            }
+            Ok(Err(err)) => {
+                warn!(
+                    "Failed to confirm transaction {tx_hash:?} over WebSocket endpoint {ws_url}, falling back to polling: {err}"
+                );
+            }
+            Err(_) => {
+                warn!(
+                    "Transaction {tx_hash:?} not confirmed over WebSocket endpoint {ws_url} within {timeout:?}, falling back to polling"
+                );
+            }
+        }
+    }
+
+    #[cfg(not(feature = "websocket"))]
+    let _ = (ws_url, tx_hash, timeout);
+
+    None
+}
+
+/// Subscribe to new blocks and return the transaction receipt once it is available.
+#[cfg(feature = "websocket")]
+async fn subscribe_for_receipt(
+    ws_url: &reqwest::Url,
+    tx_hash: TxHash,
+) -> Result<TransactionReceipt, alloy::transports::TransportError> {
+    use alloy::providers::{Provider, ProviderBuilder, WsConnect};
+    use alloy::transports::TransportErrorKind;
+
+    let provider = ProviderBuilder::new()
+        .connect_ws(WsConnect::new(ws_url.as_str()))
+        .await?;
+    let mut blocks = provider.subscribe_blocks().await?;
+
+    // The transaction may have been mined before the subscription was set up
+    if let Some(receipt) = provider.get_transaction_receipt(tx_hash).await? {
+        return Ok(receipt);
+    }
+
+    loop {
+        let _header = blocks
+            .recv()
+            .await
+            .map_err(|err| TransportErrorKind::custom_str(&err.to_string()))?;
+
+        if let Some(receipt) = provider.get_transaction_receipt(tx_hash).await? {
+            return Ok(receipt);
+        }
+    }
+}
@@ -14,14 +14,17 @@ use alloy::providers::fillers::{
 use alloy::providers::{Identity, ProviderBuilder, RootProvider, WalletProvider};
 use alloy::signers::local::{LocalSigner, PrivateKeySigner};
 use evmlib::Network;
-use evmlib::common::U256;
+use evmlib::common::{Address, QuoteHash, U256};
 use evmlib::contract::network_token::NetworkToken;
+use evmlib::contract::payment_vault::error::Error as PaymentVaultError;
 use evmlib::contract::payment_vault::handler::PaymentVaultHandler;
-use evmlib::contract::payment_vault::{MAX_TRANSFERS_PER_TRANSACTION, interface};
+use evmlib::contract::payment_vault::{
+    MAX_TRANSFERS_PER_TRANSACTION, interface, verify_data_payment,
+};
 use evmlib::quoting_metrics::QuotingMetrics;
 use evmlib::testnet::{deploy_data_payments_contract, deploy_network_token_contract, start_node};
 use evmlib::transaction_config::TransactionConfig;
-use evmlib::utils::http_provider;
+use evmlib::utils::{dummy_address, dummy_hash, http_provider};
 use evmlib::wallet::wallet_address;
 
 async fn setup() -> (
@@ -242,3 +245,125 @@ async fn test_verify_payment_on_local() {
         assert!(result.isValid);
     }
 }
+
+/// Pay for the 5 quotes of a record the amounts given for their prices, sorted in increasing order,
+/// and verify the payment as a node does.
+async fn pay_and_verify_as_node(
+    amounts: impl Fn(&[U256]) -> Vec<U256>,
+) -> Result<U256, PaymentVaultError> {
+    let (anvil, network_token, mut payment_vault) = setup().await;
+    let transaction_config = TransactionConfig::default();
+
+    // Nodes storing more records quote higher prices
+    let quotes: Vec<(QuoteHash, QuotingMetrics, Address)> = (0..5)
+        .map(|i| {
+            let metrics = QuotingMetrics {
+                data_type: 0,
+                data_size: 1024,
+                close_records_stored: i * 3_000,
+                records_per_type: vec![],
+                max_records: 16 * 1024,
+                received_payment_count: 0,
+                live_time: 0,
+                network_density: None,
+                network_size: None,
+            };
+            (dummy_hash(), metrics, dummy_address())
+        })
+        .collect();
+    let prices = payment_vault
+        .get_quote(quotes.iter().map(|(_, metrics, _)| metrics.clone()))
+        .await
+        .expect("Get quote failed");
+    let mut priced_quotes: Vec<_> = quotes.iter().zip(prices).collect();
+    priced_quotes.sort_by_key(|(_, price)| *price);
+    let sorted_prices: Vec<U256> = priced_quotes.iter().map(|(_, price)| *price).collect();
+
+    let quote_payments: Vec<_> = priced_quotes
+        .iter()
+        .zip(amounts(&sorted_prices))
+        .filter(|(_, amount)| !amount.is_zero())
+        .map(|(((quote_hash, _, rewards_address), _), amount)| {
+            (*quote_hash, *rewards_address, amount)
+        })
+        .collect();
+
+    let _ = network_token
+        .approve(
+            *payment_vault.contract.address(),
+            U256::MAX,
+            &transaction_config,
+        )
+        .await
+        .expect("Approve failed");
+    payment_vault.set_provider(network_token.contract.provider().clone());
+    let _ = payment_vault
+        .pay_for_quotes(quote_payments, &transaction_config)
+        .await
+        .expect("Pay for quotes failed");
+
+    let network = Network::new_custom(
+        &anvil.endpoint(),
+        &network_token.contract.address().to_string(),
+        &payment_vault.contract.address().to_string(),
+        None,
+    );
+    let owned_quote_hashes = quotes
+        .iter()
+        .map(|(quote_hash, _, _)| *quote_hash)
+        .collect();
+    verify_data_payment(&network, owned_quote_hashes, quotes).await
+}
+
+/// The amounts paid to the `payees` most expensive nodes, at their own price.
+fn redundant_amounts(sorted_prices: &[U256], payees: usize) -> Vec<U256> {
+    let first_payee = sorted_prices.len() - payees;
+    sorted_prices
+        .iter()
+        .enumerate()
+        .map(|(i, price)| if i >= first_payee { *price } else { U256::ZERO })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_node_accepts_payments_to_the_three_most_expensive_nodes_on_local() {
+    for payees in 3..=5 {
+        let result = pay_and_verify_as_node(|prices| redundant_amounts(prices, payees)).await;
+        assert!(
+            result.is_ok(),
+            "Payment to {payees} nodes failed with error: {:?}",
+            result.err()
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_node_rejects_payments_to_fewer_than_three_nodes_on_local() {
+    for payees in 1..=2 {
+        let result = pay_and_verify_as_node(|prices| redundant_amounts(prices, payees)).await;
+        assert!(
+            matches!(result, Err(PaymentVaultError::PaymentInvalid)),
+            "Payment to {payees} nodes was not rejected: {result:?}"
+        );
+    }
+}
+
+/// The amount paid to a single node, 3x the median price.
+fn single_node_amounts(sorted_prices: &[U256], payee: usize) -> Vec<U256> {
+    let mut amounts = vec![U256::ZERO; sorted_prices.len()];
+    amounts[payee] = sorted_prices[2] * U256::from(3);
+    amounts
+}
+
+#[tokio::test]
+async fn test_node_accepts_single_node_payments_on_local() {
+    // The median node, and the cheapest node
+    for payee in [2, 0] {
+        let result = pay_and_verify_as_node(|prices| single_node_amounts(prices, payee)).await;
+        assert!(
+            result.is_ok(),
+            "Payment to node {payee} failed with error: {:?}",
+            result.err()
+        );
+    }
+}
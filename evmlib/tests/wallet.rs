@@ -36,6 +36,7 @@ async fn local_testnet() -> (AnvilInstance, Network, EthereumWallet) {
             payment_token_address,
             data_payments_address: *data_payments.contract.address(),
             merkle_payments_address: Some(*merkle_payments.contract.address()),
+            fallback_rpc_urls: Default::default(),
         }),
         network_token.contract.provider().wallet().clone(),
     )
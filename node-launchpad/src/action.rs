@@ -21,6 +21,7 @@ use strum::Display;
 pub enum Action {
     StatusActions(StatusActions),
     OptionsActions(OptionsActions),
+    WalletActions(WalletActions),
 
     SwitchScene(Scene),
     SwitchInputMode(InputMode),
@@ -126,6 +127,7 @@ pub enum StatusActions {
     TriggerRewardsAddress,
     TriggerNodeLogs,
     TriggerRemoveNode,
+    ToggleNodeGraphs,
 
     PreviousTableItem,
     NextTableItem,
@@ -149,6 +151,13 @@ pub enum OptionsActions {
     UpdateStorageDrive(PathBuf, String),
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Display, Deserialize)]
+pub enum WalletActions {
+    RefreshBalance,
+    BalanceObtained { tokens: String, gas: String },
+    ErrorFetchingBalance { raw_error: String },
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Display, Deserialize)]
 pub enum UpgradeLaunchpadActions {
     UpdateAvailable {
@@ -21,6 +21,7 @@ use crate::{
             upgrade_nodes::UpgradeNodesPopUp,
         },
         status::{Status, StatusConfig},
+        wallet::Wallet,
     },
     config::{AppData, Config, get_launchpad_nodes_data_dir_path},
     connection_mode::ConnectionMode,
@@ -119,6 +120,7 @@ impl App {
             Some(port_to),
         )
         .await?;
+        let wallet = Wallet::new(app_data.discord_username.clone());
         let help = Help::new().await?;
 
         // Popups
@@ -137,6 +139,7 @@ impl App {
             // Sections
             Box::new(status),
             Box::new(options),
+            Box::new(wallet),
             Box::new(help),
             // Popups
             Box::new(change_drive),
@@ -15,7 +15,7 @@ mod terminal;
 extern crate tracing;
 
 use ant_bootstrap::InitialPeersConfig;
-use ant_logging::LogBuilder;
+use ant_logging::{LogBuilder, LogShippingTarget};
 #[cfg(target_os = "windows")]
 use ant_node_manager::config::is_running_as_root;
 use clap::Parser;
@@ -46,6 +46,13 @@ pub struct Cli {
     #[clap(long, verbatim_doc_comment)]
     network_id: Option<u8>,
 
+    /// Ship the logs as JSON to a remote collector, in addition to the log file.
+    ///
+    /// Valid values are an HTTP(S) bulk endpoint URL, "syslog://host:port" (UDP) or
+    /// "syslog+tcp://host:port".
+    #[clap(long, value_parser = LogShippingTarget::parse_from_str)]
+    log_shipping: Option<LogShippingTarget>,
+
     /// Frame rate, i.e. number of frames per second
     #[arg(short, long, value_name = "FLOAT", default_value_t = 60.0)]
     frame_rate: f64,
@@ -79,7 +86,12 @@ fn is_running_in_terminal() -> bool {
 
 fn main() -> Result<()> {
     let rt = tokio::runtime::Runtime::new().expect("Failed to create tokio runtime");
-    let _log_handle = get_log_builder()?.initialize()?;
+    let args = Cli::parse();
+    let mut log_builder = get_log_builder()?;
+    if let Some(target) = args.log_shipping.clone() {
+        log_builder.log_shipping(target);
+    }
+    let _log_handle = log_builder.initialize()?;
     let result: Result<()> = rt.block_on(async {
         ensure_admin_privileges()?;
         configure_winsw().await?;
@@ -96,8 +108,6 @@ fn main() -> Result<()> {
             debug!("Running inside a terminal!");
         }
 
-        let args = Cli::parse();
-
         if args.version {
             println!(
                 "{}",
@@ -24,6 +24,7 @@ pub mod options;
 pub mod popup;
 pub mod status;
 pub mod utils;
+pub mod wallet;
 
 /// `Component` is a trait that represents a visual and interactive element of the user interface.
 /// Implementors of this trait can be registered with the main application loop and will be able to receive events,
@@ -12,6 +12,7 @@ use ratatui::{prelude::*, widgets::*};
 pub enum SelectedMenuItem {
     Status,
     Options,
+    Wallet,
     Help,
 }
 
@@ -62,6 +63,12 @@ impl StatefulWidget for Header {
             GHOST_WHITE
         };
 
+        let wallet_color = if matches!(state, SelectedMenuItem::Wallet) {
+            VIVID_SKY_BLUE
+        } else {
+            GHOST_WHITE
+        };
+
         let help_color = if matches!(state, SelectedMenuItem::Help) {
             VIVID_SKY_BLUE
         } else {
@@ -71,6 +78,7 @@ impl StatefulWidget for Header {
         // Create styled spans for each part of the menu
         let status = Span::styled("[S]tatus", Style::default().fg(status_color));
         let options = Span::styled("[O]ptions", Style::default().fg(options_color));
+        let wallet = Span::styled("[W]allet", Style::default().fg(wallet_color));
         let help = Span::styled("[H]elp", Style::default().fg(help_color));
 
         // Combine the menu parts with separators
@@ -79,6 +87,8 @@ impl StatefulWidget for Header {
             Span::raw(" | ").fg(VIVID_SKY_BLUE),
             options,
             Span::raw(" | ").fg(VIVID_SKY_BLUE),
+            wallet,
+            Span::raw(" | ").fg(VIVID_SKY_BLUE),
             help,
         ];
 
@@ -31,7 +31,7 @@ use crate::{
     action::{Action, StatusActions},
     config::Config,
     mode::{InputMode, Scene},
-    node_stats::NodeStats,
+    node_stats::{NodeStats, NodeStatsHistory},
     style::{EUCALYPTUS, GHOST_WHITE, LIGHT_PERIWINKLE, VERY_LIGHT_AZURE, VIVID_SKY_BLUE},
 };
 use ant_bootstrap::InitialPeersConfig;
@@ -59,6 +59,8 @@ pub const NODE_REGISTRY_UPDATE_INTERVAL: Duration = Duration::from_secs(180); //
 /// refresh_node_registry(full_refresh=false) skips the peer connection check, so this is
 /// lightweight enough for frequent polling.
 const NODE_REGISTRY_TRANSITION_UPDATE_INTERVAL: Duration = Duration::from_secs(5);
+/// Height of the graphs of the trends of the selected node.
+const NODE_GRAPHS_HEIGHT: u16 = 7;
 /// If nat detection fails for more than 3 times, we don't want to waste time running during every node start.
 const MAX_ERRORS_WHILE_RUNNING_NAT_DETECTION: usize = 3;
 
@@ -90,6 +92,8 @@ pub struct Status<'a> {
     // Device Stats Section
     node_stats: NodeStats,
     node_stats_last_update: Instant,
+    node_stats_history: NodeStatsHistory,
+    show_node_graphs: bool,
     // Nodes
     node_services: Vec<NodeServiceData>,
     items: Option<StatefulTable<NodeItem<'a>>>,
@@ -150,6 +154,8 @@ impl Status<'_> {
             network_id: config.network_id,
             node_stats: NodeStats::default(),
             node_stats_last_update: Instant::now(),
+            node_stats_history: NodeStatsHistory::default(),
+            show_node_graphs: false,
             node_services: Default::default(),
             node_management: NodeManagement::new(node_registry.clone())?,
             items: None,
@@ -514,6 +520,91 @@ impl Status<'_> {
 
         (service_names, peers_ids)
     }
+
+    /// Draws the trends of the bandwidth, record count and disk usage of a node, from the
+    /// samples of its metrics taken since the launchpad started.
+    fn draw_node_graphs(&self, f: &mut Frame<'_>, area: Rect, service_name: &str) {
+        let block = Block::default()
+            .title(Line::from(vec![
+                Span::styled(" Trends", Style::default().fg(GHOST_WHITE).bold()),
+                Span::styled(
+                    format!(" ({service_name}) "),
+                    Style::default().fg(LIGHT_PERIWINKLE),
+                ),
+            ]))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(EUCALYPTUS))
+            .padding(Padding::horizontal(1));
+        let inner_area = block.inner(area);
+        f.render_widget(block, area);
+
+        let Some(samples) = self.node_stats_history.get(service_name) else {
+            f.render_widget(
+                Paragraph::new("No metrics collected from this node yet").fg(COOL_GREY),
+                inner_area,
+            );
+            return;
+        };
+
+        let columns = Layout::new(Direction::Horizontal, [Constraint::Ratio(1, 4); 4])
+            .spacing(1)
+            .split(inner_area);
+        let graphs = [
+            (
+                "In",
+                &samples.bandwidth_inbound_rate,
+                samples
+                    .bandwidth_inbound_rate
+                    .back()
+                    .map(|rate| format_bandwidth_rate(*rate)),
+            ),
+            (
+                "Out",
+                &samples.bandwidth_outbound_rate,
+                samples
+                    .bandwidth_outbound_rate
+                    .back()
+                    .map(|rate| format_bandwidth_rate(*rate)),
+            ),
+            (
+                "Recs",
+                &samples.records,
+                samples.records.back().map(|records| records.to_string()),
+            ),
+            (
+                "Disk",
+                &samples.disk_usage_mb,
+                samples.disk_usage_mb.back().map(|mb| format!("{mb} MB")),
+            ),
+        ];
+        for ((title, data, latest), column) in graphs.into_iter().zip(columns.iter()) {
+            let layout = Layout::new(
+                Direction::Vertical,
+                [Constraint::Length(1), Constraint::Fill(1)],
+            )
+            .split(*column);
+            f.render_widget(
+                Line::from(vec![
+                    Span::styled(format!("{title} "), Style::default().fg(COOL_GREY)),
+                    Span::styled(latest.unwrap_or_default(), Style::default().fg(GHOST_WHITE)),
+                ]),
+                layout[0],
+            );
+            // only the latest samples fit the width of the graph
+            let visible = data.len().saturating_sub(layout[1].width as usize);
+            f.render_widget(
+                Sparkline::default()
+                    .data(data.iter().skip(visible))
+                    .style(Style::default().fg(VIVID_SKY_BLUE)),
+                layout[1],
+            );
+        }
+    }
+}
+
+/// Formats a bandwidth rate in bytes per second as megabits per second.
+fn format_bandwidth_rate(bytes_per_second: u64) -> String {
+    format!("{:.2} Mbps", bytes_per_second as f64 * 8.0 / 1_000_000.0)
 }
 
 impl Component for Status<'_> {
@@ -629,6 +720,13 @@ impl Component for Status<'_> {
             }
             Action::StatusActions(status_action) => match status_action {
                 StatusActions::NodesStatsObtained(stats) => {
+                    let service_names: Vec<String> = self
+                        .node_services
+                        .iter()
+                        .map(|node| node.service_name.clone())
+                        .collect();
+                    self.node_stats_history.retain(&service_names);
+                    self.node_stats_history.record(&stats, Instant::now());
                     self.node_stats = stats;
                 }
                 StatusActions::RegistryRefreshed {
@@ -1190,6 +1288,9 @@ impl Component for Status<'_> {
                         return Ok(None);
                     }
                 }
+                StatusActions::ToggleNodeGraphs => {
+                    self.show_node_graphs = !self.show_node_graphs;
+                }
                 StatusActions::TriggerNodeLogs => {
                     if let Some(node) = self.items.as_ref().and_then(|items| items.selected_item())
                     {
@@ -1249,6 +1350,17 @@ impl Component for Status<'_> {
             return Ok(());
         }
 
+        let selected_node = self
+            .items
+            .as_ref()
+            .and_then(|items| items.selected_item())
+            .map(|node| node.name.clone());
+        let graphs_height = if self.show_node_graphs && selected_node.is_some() {
+            NODE_GRAPHS_HEIGHT
+        } else {
+            0
+        };
+
         let layout = Layout::new(
             Direction::Vertical,
             [
@@ -1258,6 +1370,8 @@ impl Component for Status<'_> {
                 Constraint::Max(6),
                 // Node status
                 Constraint::Min(3),
+                // Node graphs
+                Constraint::Length(graphs_height),
                 // Footer
                 Constraint::Length(3),
             ],
@@ -1558,13 +1672,17 @@ impl Component for Status<'_> {
             }
         }
 
+        // ==== Node Graphs =====
+
+        if let Some(service_name) = selected_node.as_deref()
+            && graphs_height > 0
+        {
+            self.draw_node_graphs(f, layout[3], service_name);
+        }
+
         // ==== Footer =====
 
-        let selected = self
-            .items
-            .as_ref()
-            .and_then(|items| items.selected_item())
-            .is_some();
+        let selected = selected_node.is_some();
 
         let footer = Footer::default();
         let footer_state = if let Some(ref items) = self.items {
@@ -1586,7 +1704,7 @@ impl Component for Status<'_> {
         } else {
             &mut NodesToStart::NotRunning
         };
-        f.render_stateful_widget(footer, layout[3], footer_state);
+        f.render_stateful_widget(footer, layout[4], footer_state);
 
         // ===== Popups =====
 
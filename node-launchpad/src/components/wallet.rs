@@ -0,0 +1,321 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use super::header::SelectedMenuItem;
+use ant_evm::{AttoTokens, EvmNetwork, RewardsAddress, balance_of_gas_tokens, balance_of_tokens};
+use color_eyre::eyre::{Result, eyre};
+use qrcode::{QrCode, render::unicode::Dense1x2};
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Padding, Paragraph, Row, Table, Wrap},
+};
+use std::str::FromStr;
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::Component;
+use crate::{
+    action::{Action, OptionsActions, StatusActions, WalletActions},
+    components::header::Header,
+    mode::{InputMode, Scene},
+    node_stats::NodeStats,
+    style::{
+        COOL_GREY, EUCALYPTUS, GHOST_WHITE, LIGHT_PERIWINKLE, RED, VERY_LIGHT_AZURE, VIVID_SKY_BLUE,
+    },
+};
+
+/// Steps to move the earnings of the nodes to a cold wallet. The launchpad only knows the
+/// rewards address, never its private key, so the transfer is made from the operator's wallet.
+const WITHDRAWAL_STEPS: [&str; 4] = [
+    "1. Open the wallet holding the private key of the rewards address, e.g. MetaMask.",
+    "2. Make sure it is connected to the Arbitrum One network.",
+    "3. Keep a little ETH on the rewards address to pay for the transfer fees.",
+    "4. Send the ANT tokens to the address of your cold wallet.",
+];
+
+#[derive(Clone, Debug, Default, PartialEq)]
+enum BalanceState {
+    #[default]
+    NotFetched,
+    Fetching,
+    Fetched {
+        tokens: String,
+        gas: String,
+    },
+    Error(String),
+}
+
+pub struct Wallet {
+    active: bool,
+    action_tx: Option<UnboundedSender<Action>>,
+    rewards_address: String,
+    balance: BalanceState,
+    node_stats: NodeStats,
+}
+
+impl Wallet {
+    pub fn new(rewards_address: String) -> Self {
+        Self {
+            active: false,
+            action_tx: None,
+            rewards_address,
+            balance: BalanceState::default(),
+            node_stats: NodeStats::default(),
+        }
+    }
+
+    /// Fetches the on-chain balances of the rewards address in the background.
+    /// The result is sent via the WalletActions::BalanceObtained action.
+    fn fetch_balance(&mut self) -> Result<()> {
+        let action_tx = self
+            .action_tx
+            .clone()
+            .ok_or_else(|| eyre!("Action sender not registered"))?;
+        let Ok(address) = RewardsAddress::from_str(&self.rewards_address) else {
+            self.balance = BalanceState::Error("No valid rewards address set".to_string());
+            return Ok(());
+        };
+
+        self.balance = BalanceState::Fetching;
+        tokio::spawn(async move {
+            let network = EvmNetwork::default();
+            let action = match tokio::try_join!(
+                balance_of_tokens(address, &network),
+                balance_of_gas_tokens(address, &network)
+            ) {
+                Ok((tokens, gas)) => WalletActions::BalanceObtained {
+                    tokens: AttoTokens::from_atto(tokens).to_string(),
+                    gas: AttoTokens::from_atto(gas).to_string(),
+                },
+                Err(err) => {
+                    error!("Error while fetching the balance of {address}: {err:?}");
+                    WalletActions::ErrorFetchingBalance {
+                        raw_error: err.to_string(),
+                    }
+                }
+            };
+            let _ = action_tx.send(Action::WalletActions(action));
+        });
+        Ok(())
+    }
+
+    fn balance_lines(&self) -> Vec<Line<'_>> {
+        let label = |text: &'static str| Span::styled(text, Style::default().fg(LIGHT_PERIWINKLE));
+        match &self.balance {
+            BalanceState::NotFetched | BalanceState::Fetching => vec![Line::from(Span::styled(
+                "Fetching the balance...",
+                Style::default().fg(COOL_GREY),
+            ))],
+            BalanceState::Fetched { tokens, gas } => vec![
+                Line::from(vec![
+                    label("ANT: "),
+                    Span::styled(tokens.as_str(), Style::default().fg(EUCALYPTUS)),
+                ]),
+                Line::from(vec![
+                    label("ETH (gas): "),
+                    Span::styled(gas.as_str(), Style::default().fg(GHOST_WHITE)),
+                ]),
+            ],
+            BalanceState::Error(err) => vec![Line::from(Span::styled(
+                format!("Could not fetch the balance: {err}"),
+                Style::default().fg(RED),
+            ))],
+        }
+    }
+}
+
+/// Renders the address as a QR code made of unicode half blocks, light modules on dark.
+fn address_qr_code(address: &str) -> Option<String> {
+    let code = QrCode::new(address.as_bytes()).ok()?;
+    Some(
+        code.render::<Dense1x2>()
+            .dark_color(Dense1x2::Light)
+            .light_color(Dense1x2::Dark)
+            .quiet_zone(false)
+            .build(),
+    )
+}
+
+impl Component for Wallet {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
+        if !self.active {
+            return Ok(());
+        }
+
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![
+                Constraint::Length(1),
+                Constraint::Min(12),
+                Constraint::Length(8),
+            ])
+            .split(area);
+
+        // ==== Header =====
+        let header = Header::new();
+        f.render_stateful_widget(header, layout[0], &mut SelectedMenuItem::Wallet);
+
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(layout[1]);
+
+        // ---- Rewards Address ----
+        let address_block = Block::default()
+            .title(" Rewards Address ")
+            .title_style(Style::default().bold().fg(GHOST_WHITE))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(VERY_LIGHT_AZURE))
+            .padding(Padding::horizontal(1));
+        let mut address_lines = vec![];
+        if self.rewards_address.is_empty() {
+            address_lines.push(Line::from(Span::styled(
+                "No rewards address set, add one from the Options screen [Ctrl+B]",
+                Style::default().fg(LIGHT_PERIWINKLE),
+            )));
+        } else {
+            address_lines.push(Line::from(Span::styled(
+                self.rewards_address.as_str(),
+                Style::default().fg(VIVID_SKY_BLUE),
+            )));
+            address_lines.push(Line::raw(""));
+            if let Some(qr_code) = address_qr_code(&self.rewards_address) {
+                address_lines.extend(
+                    qr_code.lines().map(|line| {
+                        Line::styled(line.to_string(), Style::default().fg(GHOST_WHITE))
+                    }),
+                );
+            }
+        }
+        f.render_widget(
+            Paragraph::new(address_lines)
+                .alignment(Alignment::Center)
+                .block(address_block),
+            columns[0],
+        );
+
+        // ---- Balances ----
+        let balances_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![Constraint::Length(4), Constraint::Min(3)])
+            .split(columns[1]);
+        let refresh_key = Span::styled(" [Ctrl+R] Refresh ", Style::default().fg(GHOST_WHITE));
+        let balance_block = Block::default()
+            .title(" On-chain Balance ")
+            .title_style(Style::default().bold().fg(GHOST_WHITE))
+            .title_bottom(Line::from(refresh_key).alignment(Alignment::Right))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(VERY_LIGHT_AZURE))
+            .padding(Padding::horizontal(1));
+        f.render_widget(
+            Paragraph::new(self.balance_lines()).block(balance_block),
+            balances_layout[0],
+        );
+
+        // Rewards reported by the metrics of each node
+        let rows: Vec<Row> = self
+            .node_stats
+            .individual_stats
+            .iter()
+            .map(|stats| {
+                Row::new(vec![
+                    Cell::from(stats.service_name.clone()),
+                    Cell::from(
+                        Line::from(stats.rewards_wallet_balance.to_string())
+                            .alignment(Alignment::Right),
+                    ),
+                ])
+                .style(Style::default().fg(GHOST_WHITE))
+            })
+            .collect();
+        let total = Line::from(vec![
+            Span::styled(" Total: ", Style::default().fg(LIGHT_PERIWINKLE)),
+            Span::styled(
+                format!("{} ", self.node_stats.total_rewards_wallet_balance),
+                Style::default().fg(EUCALYPTUS),
+            ),
+        ]);
+        let nodes_block = Block::default()
+            .title(" Earned per Node (atto) ")
+            .title_style(Style::default().bold().fg(GHOST_WHITE))
+            .title_bottom(total.alignment(Alignment::Right))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(VERY_LIGHT_AZURE))
+            .padding(Padding::horizontal(1));
+        f.render_widget(
+            Table::new(rows, [Constraint::Fill(1), Constraint::Fill(1)]).block(nodes_block),
+            balances_layout[1],
+        );
+
+        // ---- Withdrawal ----
+        let withdrawal_block = Block::default()
+            .title(" Move Earnings to a Cold Wallet ")
+            .title_style(Style::default().bold().fg(GHOST_WHITE))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(EUCALYPTUS))
+            .padding(Padding::horizontal(1));
+        let withdrawal_lines: Vec<Line> = WITHDRAWAL_STEPS
+            .iter()
+            .map(|step| Line::styled(*step, Style::default().fg(LIGHT_PERIWINKLE)))
+            .collect();
+        f.render_widget(
+            Paragraph::new(withdrawal_lines)
+                .wrap(Wrap { trim: true })
+                .block(withdrawal_block),
+            layout[2],
+        );
+
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::SwitchScene(scene) => {
+                if let Scene::Wallet = scene {
+                    self.active = true;
+                    if self.balance == BalanceState::NotFetched {
+                        self.fetch_balance()?;
+                    }
+                    // make sure we're in navigation mode
+                    return Ok(Some(Action::SwitchInputMode(InputMode::Navigation)));
+                } else {
+                    self.active = false;
+                }
+            }
+            Action::OptionsActions(OptionsActions::UpdateRewardsAddress(rewards_address)) => {
+                self.rewards_address = rewards_address;
+                self.balance = BalanceState::NotFetched;
+            }
+            Action::StatusActions(StatusActions::NodesStatsObtained(stats)) => {
+                self.node_stats = stats;
+            }
+            Action::WalletActions(wallet_action) => match wallet_action {
+                WalletActions::RefreshBalance => {
+                    if self.balance != BalanceState::Fetching {
+                        self.fetch_balance()?;
+                    }
+                }
+                WalletActions::BalanceObtained { tokens, gas } => {
+                    self.balance = BalanceState::Fetched { tokens, gas };
+                }
+                WalletActions::ErrorFetchingBalance { raw_error } => {
+                    self.balance = BalanceState::Error(raw_error);
+                }
+            },
+            _ => {}
+        }
+        Ok(None)
+    }
+}
@@ -15,6 +15,7 @@ pub enum Scene {
     #[default]
     Status,
     Options,
+    Wallet,
     Help,
     ChangeDrivePopUp,
     ChangeConnectionModePopUp,
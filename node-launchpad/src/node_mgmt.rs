@@ -438,6 +438,7 @@ async fn add_node(args: MaintainNodesArgs, node_registry: NodeRegistryManager) {
         true,       // enable_metrics_server,
         None,       // env_variables,
         None,       // evm_network
+        None,       // image
         None,       // log_dir_path,
         None,       // log_format,
         None,       // max_archived_log_files,
@@ -453,6 +454,7 @@ async fn add_node(args: MaintainNodesArgs, node_registry: NodeRegistryManager) {
         RewardsAddress::from_str(config.rewards_address.as_str()).unwrap(),
         None,                        // rpc_address,
         None,                        // rpc_port,
+        None,                        // runtime
         config.antnode_path.clone(), // src_path,
         !config.upnp,
         None, // url,
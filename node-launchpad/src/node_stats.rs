@@ -10,7 +10,12 @@ use ant_service_management::{NodeServiceData, ServiceStatus};
 use color_eyre::Result;
 use futures::StreamExt;
 use serde::{Deserialize, Serialize};
-use std::{path::PathBuf, time::Instant};
+use std::{
+    collections::{HashMap, VecDeque},
+    path::{Path, PathBuf},
+    sync::{LazyLock, Mutex},
+    time::{Duration, Instant},
+};
 use tokio::sync::mpsc::UnboundedSender;
 
 use super::components::status::NODE_STAT_UPDATE_INTERVAL;
@@ -28,6 +33,7 @@ pub struct IndividualNodeStats {
     pub bandwidth_inbound_rate: usize,
     pub bandwidth_outbound_rate: usize,
     pub max_records: usize,
+    pub disk_usage_mb: usize,
     pub peers: usize,
     pub connections: usize,
 }
@@ -143,6 +149,7 @@ impl NodeStats {
                         bandwidth_inbound: stats.bandwidth_inbound,
                         bandwidth_outbound: stats.bandwidth_outbound,
                         max_records: stats.max_records,
+                        disk_usage_mb: stats.disk_usage_mb,
                         peers: stats.peers,
                         connections: stats.connections,
                         bandwidth_inbound_rate: stats.bandwidth_inbound_rate,
@@ -165,7 +172,7 @@ impl NodeStats {
 
     async fn fetch_stat_per_node(
         metrics_port: u16,
-        _data_dir: PathBuf,
+        data_dir: PathBuf,
     ) -> Result<IndividualNodeStats> {
         let now = Instant::now();
 
@@ -267,6 +274,8 @@ impl NodeStats {
                 }
             }
         }
+        stats.disk_usage_mb = disk_usage(data_dir).await as usize / (1024 * 1024);
+
         trace!(
             "Fetched stats from metrics_port {metrics_port:?} in {:?}",
             now.elapsed()
@@ -274,3 +283,210 @@ impl NodeStats {
         Ok(stats)
     }
 }
+
+/// How long the measured disk usage of a node is reused before its data directory is walked again.
+const DISK_USAGE_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The last measured disk usage of each data directory, shared by the stat fetches.
+static DISK_USAGE_CACHE: LazyLock<Mutex<DiskUsageCache>> = LazyLock::new(Default::default);
+
+/// Measured sizes of data directories, with the time they were measured at.
+#[derive(Debug, Default)]
+struct DiskUsageCache {
+    sizes: HashMap<PathBuf, (Instant, u64)>,
+}
+
+impl DiskUsageCache {
+    /// The size of `dir`, if it was measured less than `DISK_USAGE_REFRESH_INTERVAL` before `now`.
+    fn get(&self, dir: &Path, now: Instant) -> Option<u64> {
+        self.sizes
+            .get(dir)
+            .filter(|(measured_at, _)| {
+                now.duration_since(*measured_at) < DISK_USAGE_REFRESH_INTERVAL
+            })
+            .map(|(_, size)| *size)
+    }
+
+    fn insert(&mut self, dir: PathBuf, measured_at: Instant, size: u64) {
+        let _ = self.sizes.insert(dir, (measured_at, size));
+    }
+}
+
+/// Size in bytes of a node's data directory, walking it at most every `DISK_USAGE_REFRESH_INTERVAL`.
+async fn disk_usage(data_dir: PathBuf) -> u64 {
+    let now = Instant::now();
+    if let Some(size) = DISK_USAGE_CACHE
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(&data_dir, now))
+    {
+        return size;
+    }
+
+    let dir = data_dir.clone();
+    let size = tokio::task::spawn_blocking(move || dir_size(&dir))
+        .await
+        .unwrap_or_default();
+    if let Ok(mut cache) = DISK_USAGE_CACHE.lock() {
+        cache.insert(data_dir, now, size);
+    }
+    size
+}
+
+/// Total size in bytes of the files under a directory, skipping what cannot be read.
+fn dir_size(dir: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.metadata() {
+            Ok(metadata) if metadata.is_dir() => dir_size(&entry.path()),
+            Ok(metadata) => metadata.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+/// Number of samples kept per node, i.e. 5 minutes of samples taken every `NODE_STAT_UPDATE_INTERVAL`.
+pub const NODE_STATS_HISTORY_LEN: usize = 60;
+
+/// The latest samples of the metrics of a node, oldest first.
+#[derive(Debug, Default, Clone)]
+pub struct NodeStatsSamples {
+    /// Inbound bandwidth, in bytes per second
+    pub bandwidth_inbound_rate: VecDeque<u64>,
+    /// Outbound bandwidth, in bytes per second
+    pub bandwidth_outbound_rate: VecDeque<u64>,
+    pub records: VecDeque<u64>,
+    pub disk_usage_mb: VecDeque<u64>,
+    /// Time and cumulative inbound/outbound bytes of the previous sample, to compute the rates
+    last_bandwidth: Option<(Instant, usize, usize)>,
+}
+
+impl NodeStatsSamples {
+    fn push(&mut self, stats: &IndividualNodeStats, sampled_at: Instant) {
+        if let Some((last_at, last_inbound, last_outbound)) = self.last_bandwidth {
+            let elapsed = sampled_at.duration_since(last_at).as_secs_f64();
+            if elapsed > 0.0 {
+                let rate = |current: usize, last: usize| {
+                    (current.saturating_sub(last) as f64 / elapsed) as u64
+                };
+                push_sample(
+                    &mut self.bandwidth_inbound_rate,
+                    rate(stats.bandwidth_inbound, last_inbound),
+                );
+                push_sample(
+                    &mut self.bandwidth_outbound_rate,
+                    rate(stats.bandwidth_outbound, last_outbound),
+                );
+            }
+        }
+        self.last_bandwidth = Some((
+            sampled_at,
+            stats.bandwidth_inbound,
+            stats.bandwidth_outbound,
+        ));
+        push_sample(&mut self.records, stats.max_records as u64);
+        push_sample(&mut self.disk_usage_mb, stats.disk_usage_mb as u64);
+    }
+}
+
+fn push_sample(samples: &mut VecDeque<u64>, value: u64) {
+    if samples.len() == NODE_STATS_HISTORY_LEN {
+        let _ = samples.pop_front();
+    }
+    samples.push_back(value);
+}
+
+/// Ring buffers of the latest stats of each node, to graph their trends.
+#[derive(Debug, Default, Clone)]
+pub struct NodeStatsHistory {
+    nodes: HashMap<String, NodeStatsSamples>,
+}
+
+impl NodeStatsHistory {
+    /// Add the stats fetched from the nodes at `sampled_at` to their history.
+    pub fn record(&mut self, stats: &NodeStats, sampled_at: Instant) {
+        for node_stats in &stats.individual_stats {
+            self.nodes
+                .entry(node_stats.service_name.clone())
+                .or_default()
+                .push(node_stats, sampled_at);
+        }
+    }
+
+    /// The samples of a node, if any stats were fetched from it.
+    pub fn get(&self, service_name: &str) -> Option<&NodeStatsSamples> {
+        self.nodes.get(service_name)
+    }
+
+    /// Forget the history of the nodes that are not in `service_names` anymore.
+    pub fn retain(&mut self, service_names: &[String]) {
+        self.nodes.retain(|name, _| service_names.contains(name));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disk_usage_cache() {
+        let mut cache = DiskUsageCache::default();
+        let dir = PathBuf::from("/var/antctl/services/antnode1");
+        let start = Instant::now();
+        assert_eq!(cache.get(&dir, start), None);
+
+        cache.insert(dir.clone(), start, 1_024);
+        assert_eq!(cache.get(&dir, start), Some(1_024));
+        assert_eq!(
+            cache.get(&dir, start + DISK_USAGE_REFRESH_INTERVAL / 2),
+            Some(1_024)
+        );
+        assert_eq!(cache.get(&dir, start + DISK_USAGE_REFRESH_INTERVAL), None);
+        assert_eq!(
+            cache.get(&PathBuf::from("/var/antctl/services/antnode2"), start),
+            None
+        );
+    }
+
+    #[test]
+    fn test_node_stats_history() {
+        let mut history = NodeStatsHistory::default();
+        let start = Instant::now();
+        let sample = |inbound: usize, records: usize| NodeStats {
+            individual_stats: vec![IndividualNodeStats {
+                service_name: "antnode1".to_string(),
+                bandwidth_inbound: inbound,
+                max_records: records,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        history.record(&sample(1_000, 10), start);
+        let samples = history.get("antnode1").expect("history of antnode1");
+        assert!(samples.bandwidth_inbound_rate.is_empty());
+        assert_eq!(samples.records, [10]);
+
+        history.record(&sample(11_000, 12), start + Duration::from_secs(5));
+        let samples = history.get("antnode1").expect("history of antnode1");
+        assert_eq!(samples.bandwidth_inbound_rate, [2_000]);
+        assert_eq!(samples.bandwidth_outbound_rate, [0]);
+        assert_eq!(samples.records, [10, 12]);
+
+        for i in 0..NODE_STATS_HISTORY_LEN {
+            history.record(
+                &sample(11_000, i),
+                start + Duration::from_secs(10 + i as u64),
+            );
+        }
+        let samples = history.get("antnode1").expect("history of antnode1");
+        assert_eq!(samples.records.len(), NODE_STATS_HISTORY_LEN);
+        assert_eq!(samples.records.front(), Some(&0));
+
+        history.retain(&[]);
+        assert!(history.get("antnode1").is_none());
+    }
+}